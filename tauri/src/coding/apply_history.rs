@@ -0,0 +1,301 @@
+//! Undo for provider applies.
+//!
+//! Before Claude, Codex or OpenCode overwrites its runtime files on apply, the current content
+//! of those files and the id of the provider applied at that moment are captured; once the
+//! apply succeeds the capture is pushed onto the tool's history, which keeps the newest
+//! `HISTORY_DEPTH` entries. Histories are stored in `apply_history` (one record per tool) and
+//! cached in memory. `undo_last_apply` pops the newest entry, writes the captured files back
+//! (removing files that did not exist yet) and restores the `is_applied` flags.
+//!
+//! The captured files hold API keys and `apply_history` is part of backups, so file contents
+//! are sealed with the `secret_store` keyring key. When a capture fails (unreadable file, no
+//! keyring) the apply goes ahead without an undo step.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State};
+
+use crate::coding::{atomic_write, db_extract_id, secret_store};
+use crate::db::helpers::{db_get, db_list, db_put, db_update_applied_status};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+const HISTORY_DEPTH: usize = 10;
+
+/// Tools whose applies are recorded, as passed to `undo_last_apply`
+pub const APPLY_HISTORY_TOOLS: &[&str] = &["claude", "codex", "opencode"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileSnapshot {
+    path: String,
+    /// Sealed file content; None when the file did not exist before the apply
+    content: Option<String>,
+}
+
+/// Seals or opens a captured file content; `secret_store` outside tests.
+type SnapshotCipher = fn(&str) -> Result<String, String>;
+
+/// State of a tool right before one apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplyHistoryEntry {
+    previous_provider_id: Option<String>,
+    files: Vec<FileSnapshot>,
+    recorded_at: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ApplyHistory {
+    #[serde(default)]
+    entries: Vec<ApplyHistoryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyUndoResult {
+    pub tool: String,
+    /// Provider marked as applied again; None when nothing was applied before
+    pub restored_provider_id: Option<String>,
+    /// Entries left to undo
+    pub remaining: usize,
+}
+
+static HISTORY_CACHE: LazyLock<Mutex<HashMap<String, Vec<ApplyHistoryEntry>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Provider table holding the `is_applied` flag of `tool`; OpenCode has none.
+fn provider_table(tool: &str) -> Option<DbTable> {
+    match tool {
+        "claude" => Some(DbTable::ClaudeProvider),
+        "codex" => Some(DbTable::CodexProvider),
+        _ => None,
+    }
+}
+
+/// Id of the provider currently marked as applied in `table`.
+pub(crate) fn applied_provider_id(db: &SqliteDbState, table: DbTable) -> Option<String> {
+    db.with_conn(|conn| db_list(conn, table, None))
+        .ok()?
+        .into_iter()
+        .find(|record| record.get("is_applied").and_then(|v| v.as_bool()) == Some(true))
+        .map(|record| db_extract_id(&record))
+}
+
+/// Capture `paths` and the provider applied now, before an apply overwrites them. Returns
+/// `None` (logged) when a file cannot be read or sealed; the apply then has no undo step.
+pub(crate) fn capture(
+    previous_provider_id: Option<String>,
+    paths: &[PathBuf],
+) -> Option<ApplyHistoryEntry> {
+    match capture_with(previous_provider_id, paths, secret_store::seal_text) {
+        Ok(entry) => Some(entry),
+        Err(error) => {
+            log::warn!("Skipped apply undo snapshot: {}", error);
+            None
+        }
+    }
+}
+
+fn capture_with(
+    previous_provider_id: Option<String>,
+    paths: &[PathBuf],
+    seal: SnapshotCipher,
+) -> Result<ApplyHistoryEntry, String> {
+    let files = paths
+        .iter()
+        .map(|path| {
+            let content = if path.is_file() {
+                let content = fs::read_to_string(path).map_err(|error| {
+                    format!("Failed to read {} for undo: {}", path.display(), error)
+                })?;
+                Some(seal(&content)?)
+            } else {
+                None
+            };
+            Ok(FileSnapshot {
+                path: path.to_string_lossy().to_string(),
+                content,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(ApplyHistoryEntry {
+        previous_provider_id,
+        files,
+        recorded_at: chrono::Local::now().to_rfc3339(),
+    })
+}
+
+fn with_history<T>(
+    db: &SqliteDbState,
+    tool: &str,
+    update: impl FnOnce(&mut Vec<ApplyHistoryEntry>) -> T,
+) -> Result<T, String> {
+    let mut cache = HISTORY_CACHE
+        .lock()
+        .map_err(|_| "Apply history cache is poisoned".to_string())?;
+    let entries = match cache.entry(tool.to_string()) {
+        std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+        std::collections::hash_map::Entry::Vacant(entry) => {
+            let stored: ApplyHistory = db
+                .with_conn(|conn| db_get(conn, DbTable::ApplyHistory, tool))?
+                .and_then(|value| serde_json::from_value(value).ok())
+                .unwrap_or_default();
+            entry.insert(stored.entries)
+        }
+    };
+
+    let result = update(entries);
+    let value = serde_json::to_value(ApplyHistory {
+        entries: entries.clone(),
+    })
+    .map_err(|e| format!("Failed to serialize apply history: {}", e))?;
+    db.with_conn(|conn| db_put(conn, DbTable::ApplyHistory, tool, &value))?;
+    Ok(result)
+}
+
+/// Record a successful apply; the oldest entry is dropped beyond `HISTORY_DEPTH`.
+/// Failing to record only loses the undo step, so it is logged instead of failing the apply.
+pub(crate) fn push(db: &SqliteDbState, tool: &str, entry: ApplyHistoryEntry) {
+    let pushed = with_history(db, tool, |entries| {
+        entries.push(entry);
+        if entries.len() > HISTORY_DEPTH {
+            entries.drain(..entries.len() - HISTORY_DEPTH);
+        }
+    });
+    if let Err(error) = pushed {
+        log::warn!("Failed to record {} apply history: {}", tool, error);
+    }
+}
+
+fn restore_files(entry: &ApplyHistoryEntry, open: SnapshotCipher) -> Result<(), String> {
+    for snapshot in &entry.files {
+        let path = Path::new(&snapshot.path);
+        match &snapshot.content {
+            Some(content) => atomic_write::write_atomic(path, &open(content)?)?,
+            None if path.exists() => fs::remove_file(path)
+                .map_err(|error| format!("Failed to remove {}: {}", path.display(), error))?,
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Pop the newest entry of `tool` and put its files back. Returns the entry and how many
+/// are left; the entry is kept when restoring fails.
+fn undo_files(
+    db: &SqliteDbState,
+    tool: &str,
+    open: SnapshotCipher,
+) -> Result<(ApplyHistoryEntry, usize), String> {
+    let entry = with_history(db, tool, |entries| entries.pop())?
+        .ok_or_else(|| format!("Nothing to undo for {}", tool))?;
+    if let Err(error) = restore_files(&entry, open) {
+        push(db, tool, entry);
+        return Err(error);
+    }
+    let remaining = with_history(db, tool, |entries| entries.len())?;
+    Ok((entry, remaining))
+}
+
+/// Undo the last apply of `tool` ("claude", "codex" or "opencode"): restore the files it
+/// overwrote and mark the provider that was applied before as applied again. A provider
+/// deleted in the meantime leaves no provider marked as applied.
+#[tauri::command]
+pub async fn undo_last_apply(
+    state: State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    tool: String,
+) -> Result<ApplyUndoResult, String> {
+    if !APPLY_HISTORY_TOOLS.contains(&tool.as_str()) {
+        return Err(format!(
+            "Unknown tool '{}'; expected one of {}",
+            tool,
+            APPLY_HISTORY_TOOLS.join(", ")
+        ));
+    }
+    let (entry, remaining) = undo_files(&state, &tool, secret_store::open_text)?;
+
+    let mut restored_provider_id = None;
+    if let Some(table) = provider_table(&tool) {
+        restored_provider_id = entry
+            .previous_provider_id
+            .filter(|id| matches!(state.with_conn(|conn| db_get(conn, table, id)), Ok(Some(_))));
+        let now = chrono::Local::now().to_rfc3339();
+        state.with_conn_mut(|conn| {
+            db_update_applied_status(conn, table, restored_provider_id.as_deref(), &now)
+        })?;
+    }
+
+    let _ = app.emit("config-changed", "window");
+    crate::coding::wsl::emit_apply_sync_request(state.db(), &app, &tool);
+    Ok(ApplyUndoResult {
+        tool,
+        restored_provider_id,
+        remaining,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sealed(text: &str) -> Result<String, String> {
+        Ok(format!("sealed:{}", text))
+    }
+
+    fn opened(text: &str) -> Result<String, String> {
+        text.strip_prefix("sealed:")
+            .map(str::to_string)
+            .ok_or_else(|| "not sealed".to_string())
+    }
+
+    #[test]
+    fn undo_restores_files_and_keeps_bounded_history() {
+        let db = SqliteDbState::in_memory_for_test().expect("sqlite");
+        let temp = tempfile::tempdir().expect("temp dir");
+        let settings = temp.path().join("settings.json");
+        let created = temp.path().join("auth.json");
+        fs::write(&settings, "{\"model\":\"old\"}").unwrap();
+
+        let entry = capture_with(
+            Some("p1".to_string()),
+            &[settings.clone(), created.clone()],
+            sealed,
+        )
+        .expect("capture");
+        assert_eq!(
+            entry.files[0].content.as_deref(),
+            Some("sealed:{\"model\":\"old\"}")
+        );
+        fs::write(&settings, "{\"model\":\"new\"}").unwrap();
+        fs::write(&created, "{}").unwrap();
+        push(&db, "undo-test", entry);
+
+        let (undone, remaining) = undo_files(&db, "undo-test", opened).expect("undo");
+        assert_eq!(undone.previous_provider_id.as_deref(), Some("p1"));
+        assert_eq!(remaining, 0);
+        assert_eq!(
+            fs::read_to_string(&settings).unwrap(),
+            "{\"model\":\"old\"}"
+        );
+        assert!(!created.exists());
+        assert!(undo_files(&db, "undo-test", opened).is_err());
+
+        for index in 0..HISTORY_DEPTH + 2 {
+            push(
+                &db,
+                "undo-test",
+                capture_with(Some(format!("p{}", index)), &[], sealed).expect("capture"),
+            );
+        }
+        let (newest, remaining) = undo_files(&db, "undo-test", opened).expect("undo");
+        assert_eq!(
+            newest.previous_provider_id,
+            Some(format!("p{}", HISTORY_DEPTH + 1))
+        );
+        assert_eq!(remaining, HISTORY_DEPTH - 1);
+    }
+}
@@ -0,0 +1,145 @@
+//! Crash-safe writes for runtime config files.
+//!
+//! Content goes to a temp file in the target's directory and is renamed into place, so a
+//! killed process leaves either the old file or the new one, never a truncated one. The temp
+//! file lives next to the target so the rename never crosses filesystems, and it is removed
+//! on drop when anything fails before the rename.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use tempfile::NamedTempFile;
+
+/// Follow a symlinked target so the rename replaces the real file, not the link.
+fn resolve_target(path: &Path) -> PathBuf {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => {
+            fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+fn write_atomic_with(
+    path: &Path,
+    fill: impl FnOnce(&mut fs::File) -> Result<(), String>,
+) -> Result<(), String> {
+    let target = resolve_target(path);
+    let parent_dir = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    if !parent_dir.exists() {
+        fs::create_dir_all(&parent_dir)
+            .map_err(|error| format!("Failed to create {}: {}", parent_dir.display(), error))?;
+    }
+
+    let mut temp_file = NamedTempFile::new_in(&parent_dir).map_err(|error| {
+        format!(
+            "Failed to create temp file for {}: {}",
+            target.display(),
+            error
+        )
+    })?;
+    fill(temp_file.as_file_mut())?;
+    temp_file
+        .as_file_mut()
+        .sync_all()
+        .map_err(|error| format!("Failed to flush {}: {}", target.display(), error))?;
+
+    // Temp files are created owner-only; keep the mode of the file being replaced.
+    if let Ok(meta) = fs::metadata(&target) {
+        let _ = fs::set_permissions(temp_file.path(), meta.permissions());
+    }
+
+    temp_file
+        .persist(&target)
+        .map_err(|error| format!("Failed to replace {}: {}", target.display(), error.error))?;
+    super::config_watcher::record_app_write(path);
+    Ok(())
+}
+
+/// Atomically replace `path` with `contents`, creating the parent directory if needed.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    write_atomic_with(path, |file| {
+        file.write_all(contents.as_ref())
+            .map_err(|error| format!("Failed to write {}: {}", path.display(), error))
+    })
+}
+
+/// Atomically replace `path` with pretty-printed JSON plus a trailing newline.
+/// Serialization happens into the temp file, which is discarded if it fails.
+pub fn write_json_atomic(path: &Path, value: &Value) -> Result<(), String> {
+    write_atomic_with(path, |file| {
+        serde_json::to_writer_pretty(&mut *file, value)
+            .map_err(|error| format!("Failed to serialize {}: {}", path.display(), error))?;
+        file.write_all(b"\n")
+            .map_err(|error| format!("Failed to write {}: {}", path.display(), error))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dir_entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn replaces_content_and_leaves_no_temp_file() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("nested").join("settings.json");
+
+        write_atomic(&path, "old").unwrap();
+        write_json_atomic(&path, &serde_json::json!({ "model": "opus" })).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "{\n  \"model\": \"opus\"\n}\n"
+        );
+        assert_eq!(dir_entries(path.parent().unwrap()), vec!["settings.json"]);
+    }
+
+    #[test]
+    fn failed_fill_keeps_original_and_removes_temp_file() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("config.toml");
+        fs::write(&path, "model = \"o3\"\n").unwrap();
+
+        let result = write_atomic_with(&path, |file| {
+            file.write_all(b"partial").unwrap();
+            Err("serialization failed".to_string())
+        });
+
+        assert_eq!(result.unwrap_err(), "serialization failed");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "model = \"o3\"\n");
+        assert_eq!(dir_entries(temp.path()), vec!["config.toml"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_target_is_written_through() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let real = temp.path().join("real.json");
+        let link = temp.path().join("link.json");
+        fs::write(&real, "{}").unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        write_atomic(&link, "{\"a\":1}").unwrap();
+
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&real).unwrap(), "{\"a\":1}");
+    }
+}
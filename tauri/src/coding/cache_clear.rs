@@ -0,0 +1,133 @@
+//! One-shot clearing of every rebuildable cache the app keeps.
+//!
+//! Only data that is re-fetched or re-detected on demand is touched: the skills git clone
+//! cache, the models.dev provider models cache and fetched model lists, the resolved Claude
+//! plugins directory and the runtime locations behind tool detection. Settings, providers and
+//! backups are never removed.
+
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::{Manager, State};
+use walkdir::WalkDir;
+
+use crate::coding::open_code::{free_models, models_cache};
+use crate::coding::runtime_location;
+use crate::coding::skills;
+use crate::db::SqliteDbState;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheClearEntry {
+    pub name: String,
+    pub entries: usize,
+    pub bytes: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheClearReport {
+    pub caches: Vec<CacheClearEntry>,
+    pub total_bytes: u64,
+}
+
+fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn cache_entry(name: &str, result: Result<(usize, u64), String>) -> CacheClearEntry {
+    match result {
+        Ok((entries, bytes)) => CacheClearEntry {
+            name: name.to_string(),
+            entries,
+            bytes,
+            error: None,
+        },
+        Err(error) => CacheClearEntry {
+            name: name.to_string(),
+            entries: 0,
+            bytes: 0,
+            error: Some(error),
+        },
+    }
+}
+
+async fn clear_git_cache(app: &tauri::AppHandle) -> Result<(usize, u64), String> {
+    let cache_root = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| e.to_string())?
+        .join(skills::cache_cleanup::CACHE_DIR_NAME);
+    let before = dir_size(&cache_root);
+    let removed = skills::skills_clear_git_cache(app.clone()).await?;
+    Ok((removed, before.saturating_sub(dir_size(&cache_root))))
+}
+
+/// Clear the git, provider models, plugin registry and tool detection caches.
+/// Each cache is cleared independently; a failure is reported on its entry.
+#[tauri::command]
+pub async fn clear_all_caches(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<CacheClearReport, String> {
+    let mut caches = vec![
+        cache_entry("gitCache", clear_git_cache(&app).await),
+        cache_entry(
+            "providerModels",
+            free_models::clear_models_cache().and_then(|(providers, bytes)| {
+                let cached_lists = models_cache::clear_provider_models_cache(state.db())?;
+                Ok((providers + cached_lists, bytes))
+            }),
+        ),
+        cache_entry(
+            "pluginRegistry",
+            Ok((runtime_location::clear_claude_plugins_dir_cache(), 0)),
+        ),
+    ];
+
+    // Re-detect right away so later lookups don't fall back to default locations.
+    let detected = runtime_location::clear_runtime_location_cache();
+    let mut tool_detection = cache_entry("toolDetection", Ok((detected, 0)));
+    if let Err(error) = runtime_location::refresh_runtime_location_cache_async(state.db()).await {
+        log::warn!("Failed to refresh runtime location cache: {}", error);
+        tool_detection.error = Some(error);
+    }
+    caches.push(tool_detection);
+
+    let total_bytes = caches.iter().map(|cache| cache.bytes).sum();
+    Ok(CacheClearReport {
+        caches,
+        total_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dir_size_sums_nested_files() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        std::fs::create_dir(temp.path().join("repo")).expect("create dir");
+        std::fs::write(temp.path().join("repo").join("a"), "1234").expect("write");
+        std::fs::write(temp.path().join("b"), "12").expect("write");
+
+        assert_eq!(dir_size(temp.path()), 6);
+        assert_eq!(dir_size(&temp.path().join("missing")), 0);
+    }
+
+    #[test]
+    fn failed_cache_reports_error_without_counts() {
+        let entry = cache_entry("gitCache", Err("boom".to_string()));
+        assert_eq!((entry.entries, entry.bytes), (0, 0));
+        assert_eq!(entry.error.as_deref(), Some("boom"));
+    }
+}
@@ -3,6 +3,7 @@ use super::types::{
     ClaudePromptConfigContent,
 };
 use crate::coding::db_id::db_extract_id;
+use crate::coding::secret_store;
 use chrono::Local;
 use serde_json::{json, Value};
 
@@ -10,71 +11,58 @@ use serde_json::{json, Value};
 // Provider Adapter Functions
 // ============================================================================
 
-/// Helper function to get string value with backward compatibility (camelCase and snake_case)
-fn get_str_compat(value: &Value, snake_key: &str, camel_key: &str, default: &str) -> String {
+/// Helper function to get string value
+fn get_str(value: &Value, key: &str, default: &str) -> String {
     value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
+        .get(key)
         .and_then(|v| v.as_str())
         .unwrap_or(default)
         .to_string()
 }
 
-/// Helper function to get optional string with backward compatibility
-fn get_opt_str_compat(value: &Value, snake_key: &str, camel_key: &str) -> Option<String> {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_str())
-        .map(String::from)
+/// Helper function to get optional string
+fn get_opt_str(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
 }
 
-/// Helper function to get i64 with backward compatibility
-fn get_i64_compat(value: &Value, snake_key: &str, camel_key: &str) -> Option<i32> {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_i64())
-        .map(|v| v as i32)
+/// Helper function to get i64
+fn get_i64(value: &Value, key: &str) -> Option<i32> {
+    value.get(key).and_then(|v| v.as_i64()).map(|v| v as i32)
 }
 
-/// Helper function to get bool with backward compatibility
-fn get_bool_compat(value: &Value, snake_key: &str, camel_key: &str, default: bool) -> bool {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_bool())
-        .unwrap_or(default)
+/// Helper function to get bool
+fn get_bool(value: &Value, key: &str, default: bool) -> bool {
+    value.get(key).and_then(|v| v.as_bool()).unwrap_or(default)
 }
 
 /// Convert database Value to ClaudeCodeProvider with fault tolerance
-/// Supports both snake_case (new) and camelCase (legacy) field names
 pub fn from_db_value_provider(value: Value) -> ClaudeCodeProvider {
     // Use common utility to extract and clean the record ID
     let id = db_extract_id(&value);
 
     ClaudeCodeProvider {
         id,
-        name: get_str_compat(&value, "name", "name", "Unnamed Provider"),
-        category: get_str_compat(&value, "category", "category", "other"),
-        settings_config: get_str_compat(&value, "settings_config", "settingsConfig", "{}"),
-        extra_settings_config: get_str_compat(
+        name: get_str(&value, "name", "Unnamed Provider"),
+        category: get_str(&value, "category", "other"),
+        // Secrets may be encrypted at rest; callers always get plaintext.
+        settings_config: secret_store::reveal_settings_config(&get_str(
             &value,
-            "extra_settings_config",
-            "extraSettingsConfig",
+            "settings_config",
             "{}",
-        ),
-        source_provider_id: get_opt_str_compat(&value, "source_provider_id", "sourceProviderId"),
-        website_url: get_opt_str_compat(&value, "website_url", "websiteUrl"),
-        notes: get_opt_str_compat(&value, "notes", "notes"),
-        icon: get_opt_str_compat(&value, "icon", "icon"),
-        icon_color: get_opt_str_compat(&value, "icon_color", "iconColor"),
-        sort_index: get_i64_compat(&value, "sort_index", "sortIndex"),
+        )),
+        extra_settings_config: get_str(&value, "extra_settings_config", "{}"),
+        source_provider_id: get_opt_str(&value, "source_provider_id"),
+        website_url: get_opt_str(&value, "website_url"),
+        notes: get_opt_str(&value, "notes"),
+        icon: get_opt_str(&value, "icon"),
+        icon_color: get_opt_str(&value, "icon_color"),
+        sort_index: get_i64(&value, "sort_index"),
         meta: value.get("meta").cloned(),
-        is_applied: get_bool_compat(&value, "is_applied", "isApplied", false),
-        is_disabled: get_bool_compat(&value, "is_disabled", "isDisabled", false),
-        created_at: get_str_compat(&value, "created_at", "createdAt", ""),
-        updated_at: get_str_compat(&value, "updated_at", "updatedAt", ""),
+        is_applied: get_bool(&value, "is_applied", false),
+        is_disabled: get_bool(&value, "is_disabled", false),
+        pinned: get_bool(&value, "pinned", false),
+        created_at: get_str(&value, "created_at", ""),
+        updated_at: get_str(&value, "updated_at", ""),
     }
 }
 
@@ -91,7 +79,6 @@ pub fn to_db_value_provider(content: &ClaudeCodeProviderContent) -> Value {
 // ============================================================================
 
 /// Convert database Value to ClaudeCommonConfig with fault tolerance
-/// Supports both snake_case (new) and camelCase (legacy) field names
 pub fn from_db_value_common(value: Value) -> ClaudeCommonConfig {
     ClaudeCommonConfig {
         config: value
@@ -101,12 +88,10 @@ pub fn from_db_value_common(value: Value) -> ClaudeCommonConfig {
             .to_string(),
         root_dir: value
             .get("root_dir")
-            .or_else(|| value.get("rootDir"))
             .and_then(|v| v.as_str())
             .map(|v| v.to_string()),
         updated_at: value
             .get("updated_at")
-            .or_else(|| value.get("updatedAt"))
             .and_then(|v| v.as_str())
             .unwrap_or_else(|| {
                 let now = Local::now().to_rfc3339();
@@ -138,12 +123,12 @@ pub fn to_db_value_common(config: &str, root_dir: Option<&str>) -> Value {
 pub fn from_db_value_prompt(value: Value) -> ClaudePromptConfig {
     ClaudePromptConfig {
         id: db_extract_id(&value),
-        name: get_str_compat(&value, "name", "name", "Unnamed Prompt"),
-        content: get_str_compat(&value, "content", "content", ""),
-        is_applied: get_bool_compat(&value, "is_applied", "isApplied", false),
-        sort_index: get_i64_compat(&value, "sort_index", "sortIndex"),
-        created_at: get_opt_str_compat(&value, "created_at", "createdAt"),
-        updated_at: get_opt_str_compat(&value, "updated_at", "updatedAt"),
+        name: get_str(&value, "name", "Unnamed Prompt"),
+        content: get_str(&value, "content", ""),
+        is_applied: get_bool(&value, "is_applied", false),
+        sort_index: get_i64(&value, "sort_index"),
+        created_at: get_opt_str(&value, "created_at"),
+        updated_at: get_opt_str(&value, "updated_at"),
     }
 }
 
@@ -4,6 +4,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use super::adapter;
+use super::config_validation::ensure_valid_claude_settings_config;
 use super::plugin_cli;
 use super::plugin_state;
 use super::plugin_types::{
@@ -15,7 +16,11 @@ use super::settings_merge;
 use super::settings_merge::KNOWN_ENV_FIELDS;
 use super::types::*;
 use crate::coding::all_api_hub;
+use crate::coding::apply_history;
+use crate::coding::atomic_write;
+use crate::coding::config_backup;
 use crate::coding::db_id::db_new_id;
+use crate::coding::global_headers;
 use crate::coding::open_code::shell_env;
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
 use crate::coding::proxy_gateway::{
@@ -23,9 +28,13 @@ use crate::coding::proxy_gateway::{
 };
 use crate::coding::runtime_location;
 use crate::coding::skills::commands::resync_all_skills_if_tool_path_changed;
-use crate::db::helpers::{db_delete, db_get, db_list, db_max_i64, db_put};
+use crate::db::helpers::{
+    db_delete, db_get, db_list, db_max_i64, db_next_sort_index, db_patch_fields, db_put,
+    db_update_applied_status,
+};
 use crate::db::schema::{DbTable, JsonFieldPath, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
+use crate::settings::store::{should_import_config_files, should_write_config_files_on_save};
 use tauri::{Emitter, Manager};
 
 fn claude_gateway_takeover_active<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> bool {
@@ -229,10 +238,16 @@ async fn write_claude_settings_value_async(
         }
     }
 
+    config_backup::backup_before_write(&settings_path)?;
+    atomic_write::write_atomic(&settings_path, serialize_claude_settings(settings_value)?)
+        .map_err(|error| format!("Failed to write settings file: {}", error))
+}
+
+/// settings.json text exactly as apply writes it
+fn serialize_claude_settings(settings_value: &Value) -> Result<String, String> {
     let serialized = serde_json::to_string_pretty(settings_value)
         .map_err(|error| format!("Failed to serialize settings: {}", error))?;
-    fs::write(&settings_path, format!("{serialized}\n"))
-        .map_err(|error| format!("Failed to write settings file: {}", error))
+    Ok(format!("{serialized}\n"))
 }
 
 async fn load_temp_provider_from_file_with_db(
@@ -280,6 +295,7 @@ async fn load_temp_provider_from_file_with_db(
         meta: None,
         is_applied: true,
         is_disabled: false,
+        pinned: false,
         created_at: now.clone(),
         updated_at: now,
     })
@@ -467,7 +483,7 @@ fn emit_prompt_sync_requests<R: tauri::Runtime>(_app: &tauri::AppHandle<R>) {
     let _ = _app.emit("wsl-sync-request-claude", ());
 }
 
-fn claude_provider_order() -> Result<OrderSpec, String> {
+pub(crate) fn claude_provider_order() -> Result<OrderSpec, String> {
     Ok(OrderSpec::single(OrderField::json_integer(
         "sort_index",
         OrderDirection::Asc,
@@ -481,7 +497,7 @@ fn claude_prompt_order() -> Result<OrderSpec, String> {
     ]))
 }
 
-fn list_claude_providers_from_sqlite(
+pub(crate) fn list_claude_providers_from_sqlite(
     sqlite_state: &SqliteDbState,
 ) -> Result<Vec<ClaudeCodeProvider>, String> {
     let order = claude_provider_order()?;
@@ -510,14 +526,9 @@ fn put_claude_provider_to_sqlite(
     provider_id: &str,
     content: &ClaudeCodeProviderContent,
 ) -> Result<(), String> {
-    sqlite_state.with_conn(|conn| {
-        db_put(
-            conn,
-            DbTable::ClaudeProvider,
-            provider_id,
-            &adapter::to_db_value_provider(content),
-        )
-    })
+    let mut record = adapter::to_db_value_provider(content);
+    crate::coding::secret_store::seal_claude_provider_record_if_enabled(sqlite_state, &mut record)?;
+    sqlite_state.with_conn(|conn| db_put(conn, DbTable::ClaudeProvider, provider_id, &record))
 }
 
 fn delete_claude_provider_from_sqlite(
@@ -608,7 +619,7 @@ pub async fn list_claude_providers(
 ) -> Result<Vec<ClaudeCodeProvider>, String> {
     let db = state.db();
     let records = list_claude_providers_from_sqlite(db)?;
-    if records.is_empty() {
+    if records.is_empty() && should_import_config_files(db, "claude") {
         if let Ok(temp_provider) = load_temp_provider_from_file_with_db(db).await {
             return Ok(vec![temp_provider]);
         }
@@ -625,7 +636,16 @@ pub async fn create_claude_provider(
     app: tauri::AppHandle,
     provider: ClaudeCodeProviderInput,
 ) -> Result<ClaudeCodeProvider, String> {
-    let db = state.db();
+    create_claude_provider_internal(state.db(), &app, provider).await
+}
+
+/// Validate, normalize and store a new provider; shared by create and duplicate.
+async fn create_claude_provider_internal(
+    db: &SqliteDbState,
+    app: &tauri::AppHandle,
+    provider: ClaudeCodeProviderInput,
+) -> Result<ClaudeCodeProvider, String> {
+    ensure_valid_claude_settings_config(&provider.settings_config)?;
     let normalized_settings_config =
         normalize_provider_settings_for_storage(&db, &provider.settings_config, None).await?;
     let extra_settings_config = normalize_extra_settings_config_for_storage(
@@ -648,6 +668,7 @@ pub async fn create_claude_provider(
         meta: provider.meta,
         is_applied: false,
         is_disabled: false,
+        pinned: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -669,6 +690,110 @@ pub async fn create_claude_provider(
     }))
 }
 
+/// Create a provider from `base_id` with `overlay_id`'s settings layered on top (overlay
+/// wins). The new provider keeps the base's category and extra settings and is not applied.
+#[tauri::command]
+pub async fn merge_claude_providers(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    base_id: String,
+    overlay_id: String,
+    new_name: String,
+) -> Result<ClaudeProviderMergeResult, String> {
+    let db = state.db();
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err("Merged provider name is required".to_string());
+    }
+    let load = |provider_id: &str| {
+        get_claude_provider_from_sqlite(db, provider_id)?
+            .ok_or_else(|| format!("Claude provider '{}' not found", provider_id))
+    };
+    let base = load(&base_id)?;
+    let overlay = load(&overlay_id)?;
+    let parse = |provider: &ClaudeCodeProvider| {
+        serde_json::from_str::<Value>(&provider.settings_config)
+            .map_err(|e| format!("Failed to parse config of '{}': {}", provider.name, e))
+    };
+    let (merged_settings, overridden_keys) = settings_merge::overlay_provider_settings(
+        &parse(&base)?,
+        &parse(&overlay)?,
+        &KNOWN_ENV_FIELDS,
+    )?;
+    let merged_settings_config = serde_json::to_string(&merged_settings)
+        .map_err(|e| format!("Failed to serialize merged provider config: {}", e))?;
+    ensure_valid_claude_settings_config(&merged_settings_config)?;
+    let settings_config =
+        normalize_provider_settings_for_storage(&db, &merged_settings_config, None).await?;
+
+    let now = Local::now().to_rfc3339();
+    let content = ClaudeCodeProviderContent {
+        name: new_name,
+        category: base.category,
+        settings_config,
+        extra_settings_config: base.extra_settings_config,
+        source_provider_id: None,
+        website_url: overlay.website_url.or(base.website_url),
+        notes: Some(format!("Merged from {} + {}", base.name, overlay.name)),
+        icon: base.icon,
+        icon_color: base.icon_color,
+        sort_index: None,
+        meta: base.meta,
+        is_applied: false,
+        is_disabled: false,
+        pinned: false,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    let provider_id = db_new_id();
+    put_claude_provider_to_sqlite(db, &provider_id, &content)?;
+    let provider = get_claude_provider_from_sqlite(db, &provider_id)?
+        .ok_or_else(|| "Merged provider was not saved".to_string())?;
+
+    let _ = app.emit("config-changed", "window");
+
+    Ok(ClaudeProviderMergeResult {
+        provider,
+        overridden_keys,
+    })
+}
+
+/// Copy a provider under `new_name` through the create path, so the copy is validated and its
+/// secrets stored like a new provider. It is enabled, not applied nor pinned, and is placed
+/// after the last provider.
+#[tauri::command]
+pub async fn duplicate_claude_provider(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    new_name: String,
+) -> Result<ClaudeCodeProvider, String> {
+    let db = state.db();
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err("Provider name is required".to_string());
+    }
+    let source = get_claude_provider_from_sqlite(db, &provider_id)?
+        .ok_or_else(|| format!("Claude provider '{}' not found", provider_id))?;
+    let sort_index = db.with_conn(|conn| db_next_sort_index(conn, DbTable::ClaudeProvider))?;
+
+    let input = ClaudeCodeProviderInput {
+        id: None,
+        name: new_name,
+        category: source.category,
+        settings_config: source.settings_config,
+        extra_settings_config: Some(source.extra_settings_config),
+        source_provider_id: source.source_provider_id,
+        website_url: source.website_url,
+        notes: source.notes,
+        icon: source.icon,
+        icon_color: source.icon_color,
+        sort_index: Some(sort_index),
+        meta: source.meta,
+    };
+    create_claude_provider_internal(db, &app, input).await
+}
+
 /// Update an existing Claude Code provider
 #[tauri::command]
 pub async fn update_claude_provider(
@@ -677,6 +802,7 @@ pub async fn update_claude_provider(
     provider: ClaudeCodeProvider,
 ) -> Result<ClaudeCodeProvider, String> {
     let db = state.db();
+    ensure_valid_claude_settings_config(&provider.settings_config)?;
     let normalized_settings_config =
         normalize_provider_settings_for_storage(&db, &provider.settings_config, None).await?;
     let extra_settings_config = normalize_extra_settings_config_for_storage(
@@ -724,14 +850,15 @@ pub async fn update_claude_provider(
         meta: provider.meta,
         is_applied: provider.is_applied,
         is_disabled: existing_is_disabled,
+        pinned: existing_provider.pinned,
         created_at,
         updated_at: now,
     };
 
     put_claude_provider_to_sqlite(db, &id, &content)?;
 
-    // 如果该配置当前是应用状态，立即重新写入到配置文件
-    if content.is_applied {
+    // 如果该配置当前是应用状态，立即重新写入到配置文件（file_primary 模式下跳过）
+    if content.is_applied && should_write_config_files_on_save(db, "claude") {
         if let Err(e) =
             apply_config_to_file_with_context(&db, &id, None, previous_extra_settings_config_value)
                 .await
@@ -759,6 +886,7 @@ pub async fn update_claude_provider(
         meta: content.meta,
         is_applied: content.is_applied,
         is_disabled: content.is_disabled,
+        pinned: content.pinned,
         created_at: content.created_at,
         updated_at: content.updated_at,
     })
@@ -773,6 +901,11 @@ pub async fn delete_claude_provider(
 ) -> Result<(), String> {
     let db = state.db();
     delete_claude_provider_from_sqlite(db, &id)?;
+    crate::coding::proxy_gateway::clear_fallback_provider_after_delete(
+        &app,
+        GatewayCliKey::Claude,
+        &id,
+    );
 
     // Notify to refresh tray menu
     let _ = app.emit("config-changed", "window");
@@ -807,6 +940,7 @@ pub async fn reorder_claude_providers(
                 meta: provider.meta,
                 is_applied: provider.is_applied,
                 is_disabled: provider.is_disabled,
+                pinned: provider.pinned,
                 created_at: provider.created_at,
                 updated_at: provider.updated_at,
             };
@@ -852,6 +986,7 @@ pub async fn select_claude_provider(
             meta: provider.meta,
             is_applied: provider.is_applied,
             is_disabled: provider.is_disabled,
+            pinned: provider.pinned,
             created_at: provider.created_at,
             updated_at: provider.updated_at,
         };
@@ -1006,6 +1141,71 @@ async fn apply_config_to_file_with_context(
     previous_common_config: Option<Value>,
     previous_extra_settings_config: Option<Value>,
 ) -> Result<(), String> {
+    let current_settings = read_current_claude_settings_value_async(db).await?;
+    let (merged_settings, global) = build_claude_settings_with_global_headers(
+        db,
+        provider_id,
+        current_settings.as_ref(),
+        previous_common_config,
+        previous_extra_settings_config,
+    )
+    .await?;
+    crate::coding::tool_limits::warn_claude_settings_limits(&merged_settings);
+    write_claude_settings_value_async(db, &merged_settings).await?;
+    if let Some(global) = &global {
+        global_headers::mark_global_headers_applied(db, "claude", global)?;
+    }
+    let settings_path = get_claude_settings_path_from_db_async(db).await?;
+    crate::coding::config_provenance::record_applied_config(&settings_path, Some(provider_id));
+    Ok(())
+}
+
+/// 内部函数：计算应用指定 provider 后的 settings.json 内容（不写入文件）
+pub(crate) async fn build_claude_settings_for_provider(
+    db: &crate::db::SqliteDbState,
+    provider_id: &str,
+    previous_common_config: Option<Value>,
+    previous_extra_settings_config: Option<Value>,
+) -> Result<Value, String> {
+    let current_settings = read_current_claude_settings_value_async(db).await?;
+    build_claude_settings_for_provider_on(
+        db,
+        provider_id,
+        current_settings.as_ref(),
+        previous_common_config,
+        previous_extra_settings_config,
+    )
+    .await
+}
+
+/// Merge a provider into `current_settings` the way apply does, without touching the host
+/// settings.json. Used to apply into another target such as a single WSL distro.
+pub(crate) async fn build_claude_settings_for_provider_on(
+    db: &crate::db::SqliteDbState,
+    provider_id: &str,
+    current_settings: Option<&Value>,
+    previous_common_config: Option<Value>,
+    previous_extra_settings_config: Option<Value>,
+) -> Result<Value, String> {
+    build_claude_settings_with_global_headers(
+        db,
+        provider_id,
+        current_settings,
+        previous_common_config,
+        previous_extra_settings_config,
+    )
+    .await
+    .map(|(settings, _)| settings)
+}
+
+/// Merged settings plus the global headers baked into them, for marking after the write.
+async fn build_claude_settings_with_global_headers(
+    db: &crate::db::SqliteDbState,
+    provider_id: &str,
+    current_settings: Option<&Value>,
+    previous_common_config: Option<Value>,
+    previous_extra_settings_config: Option<Value>,
+) -> Result<(Value, Option<global_headers::GlobalHeaders>), String> {
     // Get the provider
     let provider = get_claude_provider_from_sqlite(db, provider_id)?
         .ok_or_else(|| "Provider not found".to_string())?;
@@ -1018,6 +1218,8 @@ async fn apply_config_to_file_with_context(
         ));
     }
 
+    crate::coding::secret_store::ensure_secrets_revealed(&provider.settings_config)?;
+
     // Parse provider settings_config
     let provider_config: serde_json::Value = serde_json::from_str(&provider.settings_config)
         .map_err(|e| format!("Failed to parse provider config: {}", e))?;
@@ -1036,9 +1238,8 @@ async fn apply_config_to_file_with_context(
         serde_json::json!({})
     };
 
-    let current_settings = read_current_claude_settings_value_async(db).await?;
-    let merged_settings = settings_merge::merge_claude_settings_for_provider(
-        current_settings.as_ref(),
+    let mut merged_settings = settings_merge::merge_claude_settings_for_provider(
+        current_settings,
         previous_common_config.as_ref(),
         &common_config,
         previous_extra_settings_config.as_ref(),
@@ -1046,7 +1247,11 @@ async fn apply_config_to_file_with_context(
         &provider_config,
         &KNOWN_ENV_FIELDS,
     )?;
-    write_claude_settings_value_async(db, &merged_settings).await
+    let global = global_headers::load_global_headers(db, "claude")?;
+    if let Some(global) = &global {
+        global_headers::apply_claude_global_headers(&mut merged_settings, global);
+    }
+    Ok((merged_settings, global))
 }
 
 /// Public version of apply_config_to_file for tray module
@@ -1087,6 +1292,7 @@ pub async fn toggle_claude_code_provider_disabled(
             meta: provider.meta,
             is_applied: provider.is_applied,
             is_disabled: provider.is_disabled,
+            pinned: provider.pinned,
             created_at: provider.created_at,
             updated_at: provider.updated_at,
         };
@@ -1104,6 +1310,97 @@ pub async fn toggle_claude_code_provider_disabled(
     Ok(())
 }
 
+/// Pin or unpin a provider for quick apply from the tray and the pinned list
+#[tauri::command]
+pub async fn set_claude_provider_pinned(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    pinned: bool,
+) -> Result<(), String> {
+    let now = Local::now().to_rfc3339();
+    let updated = state.db().with_conn(|conn| {
+        db_patch_fields(
+            conn,
+            DbTable::ClaudeProvider,
+            &provider_id,
+            &[
+                ("pinned", Value::Bool(pinned)),
+                ("updated_at", Value::String(now.clone())),
+            ],
+        )
+    })?;
+    if updated.is_none() {
+        return Err(format!(
+            "Claude Code provider with ID '{}' not found",
+            provider_id
+        ));
+    }
+
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+/// Set only the model slots of a provider, e.g. from a fetched model list.
+/// Empty or missing values clear the slot; an applied provider is re-applied.
+#[tauri::command]
+pub async fn set_claude_provider_models(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    model: Option<String>,
+    haiku: Option<String>,
+    sonnet: Option<String>,
+    opus: Option<String>,
+) -> Result<(), String> {
+    let db = state.db();
+    let provider = get_claude_provider_from_sqlite(db, &provider_id)?
+        .ok_or_else(|| format!("Claude Code provider with ID '{}' not found", provider_id))?;
+
+    let mut settings: Value = serde_json::from_str(&provider.settings_config)
+        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    settings_merge::set_provider_model_slots(
+        &mut settings,
+        &[
+            ("model", model.as_deref()),
+            ("haikuModel", haiku.as_deref()),
+            ("sonnetModel", sonnet.as_deref()),
+            ("opusModel", opus.as_deref()),
+        ],
+    )?;
+
+    let is_applied = provider.is_applied;
+    let content = ClaudeCodeProviderContent {
+        name: provider.name,
+        category: provider.category,
+        settings_config: serde_json::to_string(&settings)
+            .map_err(|e| format!("Failed to serialize provider config: {}", e))?,
+        extra_settings_config: provider.extra_settings_config,
+        source_provider_id: provider.source_provider_id,
+        website_url: provider.website_url,
+        notes: provider.notes,
+        icon: provider.icon,
+        icon_color: provider.icon_color,
+        sort_index: provider.sort_index,
+        meta: provider.meta,
+        is_applied: provider.is_applied,
+        is_disabled: provider.is_disabled,
+        pinned: provider.pinned,
+        created_at: provider.created_at,
+        updated_at: Local::now().to_rfc3339(),
+    };
+    put_claude_provider_to_sqlite(db, &provider_id, &content)?;
+
+    if is_applied && should_write_config_files_on_save(db, "claude") {
+        // Emits config-changed and the WSL sync request
+        apply_config_internal(&db, &app, &provider_id, false).await?;
+    } else {
+        let _ = app.emit("config-changed", "window");
+    }
+
+    Ok(())
+}
+
 /// Apply Claude Code provider configuration to settings.json
 #[tauri::command]
 pub async fn apply_claude_config(
@@ -1155,6 +1452,15 @@ async fn apply_config_internal_with_events<R: tauri::Runtime>(
     emit_config_changed: bool,
     emit_sync_request: bool,
 ) -> Result<(), String> {
+    // Restore-driven re-applies run without events and are not user applies to undo
+    let undo_entry = if emit_config_changed {
+        let settings_path = get_claude_settings_path_from_db_async(db).await?;
+        let previous_id = apply_history::applied_provider_id(db, DbTable::ClaudeProvider);
+        apply_history::capture(previous_id, &[settings_path])
+    } else {
+        None
+    };
+
     // 应用配置到文件
     apply_config_to_file(db, provider_id).await?;
 
@@ -1183,12 +1489,17 @@ async fn apply_config_internal_with_events<R: tauri::Runtime>(
             meta: provider.meta,
             is_applied: provider.is_applied,
             is_disabled: provider.is_disabled,
+            pinned: provider.pinned,
             created_at: provider.created_at,
             updated_at: provider.updated_at,
         };
         put_claude_provider_to_sqlite(db, &current_id, &content)?;
     }
 
+    if let Some(entry) = undo_entry {
+        apply_history::push(db, "claude", entry);
+    }
+
     if emit_config_changed {
         let payload = if from_tray { "tray" } else { "window" };
         let _ = app.emit("config-changed", payload);
@@ -1196,8 +1507,7 @@ async fn apply_config_internal_with_events<R: tauri::Runtime>(
 
     // Trigger WSL sync via event (Windows only)
     if emit_sync_request {
-        #[cfg(target_os = "windows")]
-        let _ = app.emit("wsl-sync-request-claude", ());
+        crate::coding::wsl::emit_apply_sync_request(db, app, "claude");
     }
 
     Ok(())
@@ -1543,7 +1853,8 @@ pub async fn save_claude_common_config(
     // 查找当前应用的 provider，如果存在则重新应用到文件
     let applied_provider = list_claude_providers_from_sqlite(db)?
         .into_iter()
-        .find(|provider| provider.is_applied);
+        .find(|provider| provider.is_applied)
+        .filter(|_| should_write_config_files_on_save(db, "claude"));
 
     if let Some(applied_provider) = applied_provider {
         // 重新应用配置到文件（不改变数据库中的 is_applied 状态）
@@ -1560,8 +1871,7 @@ pub async fn save_claude_common_config(
             );
             // 不中断保存流程，只记录错误
         } else {
-            #[cfg(target_os = "windows")]
-            let _ = app.emit("wsl-sync-request-claude", ());
+            crate::coding::wsl::emit_apply_sync_request(db, &app, "claude");
         }
     }
 
@@ -1665,6 +1975,7 @@ pub async fn save_claude_local_config(
         meta: resolve_local_provider_meta(provider_input.as_ref(), base_provider.meta),
         is_applied: true,
         is_disabled: false,
+        pinned: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -1702,8 +2013,7 @@ pub async fn save_claude_local_config(
     {
         eprintln!("Failed to apply config after local save: {}", e);
     } else {
-        #[cfg(target_os = "windows")]
-        let _ = app.emit("wsl-sync-request-claude", ());
+        crate::coding::wsl::emit_apply_sync_request(db, &app, "claude");
     }
 
     resync_all_skills_if_tool_path_changed(
@@ -1749,7 +2059,13 @@ pub async fn get_claude_plugin_status(
     state: tauri::State<'_, SqliteDbState>,
 ) -> Result<ClaudePluginStatus, String> {
     let db = state.db();
-    let config_path = get_claude_plugin_config_path_from_db_async(&db).await?;
+    read_claude_plugin_status(&db).await
+}
+
+pub(super) async fn read_claude_plugin_status(
+    db: &crate::db::SqliteDbState,
+) -> Result<ClaudePluginStatus, String> {
+    let config_path = get_claude_plugin_config_path_from_db_async(db).await?;
     let has_config_file = config_path.exists();
 
     if !has_config_file {
@@ -1819,10 +2135,7 @@ pub async fn apply_claude_plugin_config(
     }
 
     // Write back to file
-    let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-
-    fs::write(&config_path, format!("{serialized}\n"))
+    atomic_write::write_json_atomic(&config_path, &serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
@@ -2081,8 +2394,10 @@ pub async fn uninstall_claude_plugin_user_scope(
 pub async fn init_claude_provider_from_settings(
     db: &crate::db::SqliteDbState,
 ) -> Result<(), String> {
-    if !list_claude_providers_from_sqlite(db)?.is_empty() {
-        // Already have providers, skip initialization
+    if !list_claude_providers_from_sqlite(db)?.is_empty()
+        || !should_import_config_files(db, "claude")
+    {
+        // Already have providers (or the DB is primary), skip initialization
         return Ok(());
     }
 
@@ -2151,6 +2466,7 @@ pub async fn init_claude_provider_from_settings(
         meta: None,
         is_applied: true,
         is_disabled: false,
+        pinned: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -2180,7 +2496,13 @@ pub async fn get_claude_onboarding_status(
     state: tauri::State<'_, SqliteDbState>,
 ) -> Result<bool, String> {
     let db = state.db();
-    let config_path = get_claude_mcp_config_path(&db).await?;
+    read_claude_onboarding_status(&db).await
+}
+
+pub(super) async fn read_claude_onboarding_status(
+    db: &crate::db::SqliteDbState,
+) -> Result<bool, String> {
+    let config_path = get_claude_mcp_config_path(db).await?;
 
     if !config_path.exists() {
         return Ok(false);
@@ -2246,10 +2568,7 @@ pub async fn apply_claude_onboarding_skip(
     );
 
     // Write back to file
-    let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-
-    fs::write(&config_path, format!("{serialized}\n"))
+    atomic_write::write_json_atomic(&config_path, &serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
@@ -2285,10 +2604,7 @@ pub async fn clear_claude_onboarding_skip(
     }
 
     // Write back to file
-    let serialized = serde_json::to_string_pretty(&serde_json::Value::Object(obj))
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
-
-    fs::write(&config_path, format!("{serialized}\n"))
+    atomic_write::write_json_atomic(&config_path, &serde_json::Value::Object(obj))
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(true)
@@ -2391,9 +2707,121 @@ pub async fn resolve_claude_all_api_hub_providers(
         .collect())
 }
 
+// ============================================================================
+// Claude Full Preview
+// ============================================================================
+
+/// Mask string values under secret-like keys (tokens, API keys, passwords) in place.
+fn redact_claude_settings_secrets(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, child) in object.iter_mut() {
+                match child {
+                    Value::String(text) if crate::coding::setup_script::is_secret_key(key) => {
+                        *text = all_api_hub::mask_api_key_preview(text);
+                    }
+                    _ => redact_claude_settings_secrets(child),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_claude_settings_secrets),
+        _ => {}
+    }
+}
+
+/// Preview merged settings.json together with plugin config.json and onboarding state.
+/// With a provider id the settings are merged as apply would write them; nothing is written.
+#[tauri::command]
+pub async fn claude_full_preview(
+    state: tauri::State<'_, SqliteDbState>,
+    provider_id: Option<String>,
+) -> Result<ClaudeFullPreview, String> {
+    let db = state.db();
+    let mut settings = match provider_id.as_deref() {
+        Some(provider_id) => {
+            build_claude_settings_for_provider(&db, provider_id, None, None).await?
+        }
+        None => read_current_claude_settings_value_async(&db)
+            .await?
+            .unwrap_or_else(|| serde_json::json!({})),
+    };
+    redact_claude_settings_secrets(&mut settings);
+
+    Ok(ClaudeFullPreview {
+        provider_id,
+        settings,
+        plugin: read_claude_plugin_status(&db).await?,
+        has_completed_onboarding: read_claude_onboarding_status(&db).await?,
+    })
+}
+
+/// Dry run of apply: settings.json as it is now and as applying the provider would write it.
+/// Nothing is written and `is_applied` flags are left unchanged.
+#[tauri::command]
+pub async fn preview_claude_config(
+    state: tauri::State<'_, SqliteDbState>,
+    provider_id: String,
+) -> Result<ClaudeConfigPreview, String> {
+    let db = state.db();
+    let settings_path = get_claude_settings_path_from_db_async(&db).await?;
+    let current = if settings_path.exists() {
+        Some(
+            fs::read_to_string(&settings_path)
+                .map_err(|error| format!("Failed to read settings file: {}", error))?,
+        )
+    } else {
+        None
+    };
+    let proposed = build_claude_settings_for_provider(&db, &provider_id, None, None).await?;
+
+    Ok(ClaudeConfigPreview {
+        provider_id,
+        settings_path: settings_path.to_string_lossy().to_string(),
+        current,
+        proposed: serialize_claude_settings(&proposed)?,
+    })
+}
+
+/// Roll settings.json back to the backup taken before an earlier write (1 = newest, up to 3).
+#[tauri::command]
+pub async fn restore_claude_settings_backup(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    index: u8,
+) -> Result<(), String> {
+    let db = state.db();
+    let settings_path = get_claude_settings_path_from_db_async(db).await?;
+    config_backup::restore_backup(&settings_path, index)?;
+
+    let _ = app.emit("config-changed", "window");
+    crate::coding::wsl::emit_apply_sync_request(db, &app, "claude");
+    Ok(())
+}
+
+/// Replace settings.json with an empty `{}` after backing it up, and clear the applied
+/// provider so nothing shows as applied.
+#[tauri::command]
+pub async fn reset_claude_settings(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    let db = state.db();
+    write_claude_settings_value_async(db, &serde_json::json!({})).await?;
+
+    let now = Local::now().to_rfc3339();
+    db.with_conn_mut(|conn| db_update_applied_status(conn, DbTable::ClaudeProvider, None, &now))?;
+
+    let _ = app.emit("config-changed", "window");
+    crate::coding::wsl::emit_apply_sync_request(db, &app, "claude");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{is_third_party_claude_provider_settings, resolve_local_provider_meta};
+    use super::{
+        is_third_party_claude_provider_settings, redact_claude_settings_secrets,
+        resolve_local_provider_meta,
+    };
     use crate::coding::claude_code::types::ClaudeCodeProviderInput;
     use serde_json::json;
 
@@ -2485,4 +2913,25 @@ mod tests {
             base_meta
         );
     }
+
+    #[test]
+    fn full_preview_redaction_masks_only_secret_values() {
+        let mut settings = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-ant-1234567890abcdef",
+                "ANTHROPIC_BASE_URL": "https://api.example.com",
+                "ANTHROPIC_MODEL": "claude-sonnet-4-5"
+            }
+        });
+
+        redact_claude_settings_secrets(&mut settings);
+
+        let token = settings["env"]["ANTHROPIC_AUTH_TOKEN"].as_str().unwrap();
+        assert!(!token.contains("1234567890"));
+        assert_eq!(
+            settings["env"]["ANTHROPIC_BASE_URL"],
+            "https://api.example.com"
+        );
+        assert_eq!(settings["env"]["ANTHROPIC_MODEL"], "claude-sonnet-4-5");
+    }
 }
@@ -0,0 +1,206 @@
+//! Structural checks for a Claude provider's `settings_config` before it is stored.
+//!
+//! Errors describe configs that would break apply (invalid JSON, `env` not an object, a base
+//! URL that is not http(s), non-string model ids) and block create/update. Warnings point at
+//! likely mistakes that still apply cleanly.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::settings_merge::{is_provider_model_env_field, is_provider_model_field};
+
+/// One finding about a provider `settings_config`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeConfigIssue {
+    /// Dot path of the offending field, e.g. "env.ANTHROPIC_BASE_URL"; empty for the document
+    pub field: String,
+    /// "error" | "warning"
+    pub severity: String,
+    pub message: String,
+}
+
+fn issue(field: &str, severity: &str, message: impl Into<String>) -> ClaudeConfigIssue {
+    ClaudeConfigIssue {
+        field: field.to_string(),
+        severity: severity.to_string(),
+        message: message.into(),
+    }
+}
+
+fn is_model_env_field(key: &str) -> bool {
+    is_provider_model_env_field(key)
+        || matches!(
+            key,
+            "ANTHROPIC_REASONING_MODEL" | "ANTHROPIC_SMALL_FAST_MODEL"
+        )
+}
+
+fn validate_base_url(field: &str, value: &Value, issues: &mut Vec<ClaudeConfigIssue>) {
+    let Some(raw) = value.as_str() else {
+        issues.push(issue(field, "error", "ANTHROPIC_BASE_URL must be a string"));
+        return;
+    };
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+    match reqwest::Url::parse(trimmed) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.host_str().is_some() => {
+            if url.path().trim_end_matches('/').ends_with("/v1") {
+                issues.push(issue(
+                    field,
+                    "warning",
+                    "ANTHROPIC_BASE_URL usually omits /v1; Claude Code appends it",
+                ));
+            }
+        }
+        Ok(url) => issues.push(issue(
+            field,
+            "error",
+            format!(
+                "ANTHROPIC_BASE_URL must be an http(s) URL, got '{}'",
+                url.scheme()
+            ),
+        )),
+        Err(error) => issues.push(issue(
+            field,
+            "error",
+            format!("ANTHROPIC_BASE_URL is not a valid URL: {}", error),
+        )),
+    }
+}
+
+/// Validate a provider `settings_config` JSON string. An empty list means it is clean.
+pub fn validate_claude_settings_config(settings_config: &str) -> Vec<ClaudeConfigIssue> {
+    let mut issues = Vec::new();
+    let parsed = match serde_json::from_str::<Value>(settings_config) {
+        Ok(value) => value,
+        Err(error) => {
+            issues.push(issue("", "error", format!("Invalid JSON: {}", error)));
+            return issues;
+        }
+    };
+    let Some(settings) = parsed.as_object() else {
+        issues.push(issue("", "error", "Settings must be a JSON object"));
+        return issues;
+    };
+
+    for (key, value) in settings {
+        if is_provider_model_field(key) && !value.is_string() && !value.is_null() {
+            issues.push(issue(key, "error", format!("{} must be a string", key)));
+        }
+    }
+
+    match settings.get("env") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(env)) => {
+            for (key, value) in env {
+                let field = format!("env.{}", key);
+                if key == "ANTHROPIC_BASE_URL" {
+                    validate_base_url(&field, value, &mut issues);
+                } else if is_model_env_field(key) && !value.is_string() && !value.is_null() {
+                    issues.push(issue(&field, "error", format!("{} must be a string", key)));
+                } else if !value.is_string() {
+                    issues.push(issue(
+                        &field,
+                        "warning",
+                        format!(
+                            "{} is not a string; Claude Code reads env values as text",
+                            key
+                        ),
+                    ));
+                }
+            }
+        }
+        Some(_) => issues.push(issue("env", "error", "env must be an object")),
+    }
+
+    issues
+}
+
+/// Reject a `settings_config` that has validation errors; warnings pass.
+pub(super) fn ensure_valid_claude_settings_config(settings_config: &str) -> Result<(), String> {
+    let errors: Vec<String> = validate_claude_settings_config(settings_config)
+        .into_iter()
+        .filter(|issue| issue.severity == "error")
+        .map(|issue| {
+            if issue.field.is_empty() {
+                issue.message
+            } else {
+                format!("{}: {}", issue.field, issue.message)
+            }
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Invalid provider config: {}", errors.join("; ")))
+    }
+}
+
+/// Validate a provider `settings_config` without saving it, e.g. when the editor loses focus.
+#[tauri::command]
+pub fn validate_claude_provider_config(
+    settings_config: String,
+) -> Result<Vec<ClaudeConfigIssue>, String> {
+    Ok(validate_claude_settings_config(&settings_config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(settings_config: &str, severity: &str) -> Vec<String> {
+        validate_claude_settings_config(settings_config)
+            .into_iter()
+            .filter(|issue| issue.severity == severity)
+            .map(|issue| issue.field)
+            .collect()
+    }
+
+    #[test]
+    fn clean_config_has_no_issues() {
+        let config = r#"{
+            "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-test",
+                "ANTHROPIC_MODEL": "claude-sonnet"
+            },
+            "model": "claude-sonnet"
+        }"#;
+        assert!(validate_claude_settings_config(config).is_empty());
+        assert!(ensure_valid_claude_settings_config(config).is_ok());
+    }
+
+    #[test]
+    fn structural_problems_are_errors() {
+        assert_eq!(fields("{ nope", "error"), vec![""]);
+        assert_eq!(fields("[]", "error"), vec![""]);
+        assert_eq!(fields(r#"{"env": "x"}"#, "error"), vec!["env"]);
+        assert_eq!(
+            fields(
+                r#"{"haikuModel": 3, "env": {"ANTHROPIC_BASE_URL": "relay.example.com", "ANTHROPIC_DEFAULT_OPUS_MODEL": true}}"#,
+                "error"
+            ),
+            vec![
+                "haikuModel",
+                "env.ANTHROPIC_BASE_URL",
+                "env.ANTHROPIC_DEFAULT_OPUS_MODEL"
+            ]
+        );
+        assert!(ensure_valid_claude_settings_config(r#"{"env": []}"#)
+            .unwrap_err()
+            .contains("env: env must be an object"));
+    }
+
+    #[test]
+    fn likely_mistakes_are_warnings() {
+        let config = r#"{"env": {"ANTHROPIC_BASE_URL": "https://relay.example.com/v1/", "API_TIMEOUT_MS": 600000}}"#;
+        assert_eq!(
+            fields(config, "warning"),
+            vec!["env.ANTHROPIC_BASE_URL", "env.API_TIMEOUT_MS"]
+        );
+        assert!(ensure_valid_claude_settings_config(config).is_ok());
+    }
+}
@@ -1,13 +1,17 @@
 pub mod adapter;
 pub mod cli_launch;
 pub mod commands;
+pub mod config_validation;
 pub mod plugin_cli;
 pub mod plugin_metadata_sync;
 pub mod plugin_state;
 pub mod plugin_types;
+pub mod selftest;
 pub mod settings_merge;
 pub mod tray_support;
 pub mod types;
 
 pub use commands::*;
+pub use config_validation::*;
+pub use selftest::*;
 pub use types::*;
@@ -5,13 +5,13 @@ use std::sync::OnceLock;
 
 use serde::Deserialize;
 use serde_json::{Map, Value};
-use tempfile::NamedTempFile;
 use tokio::sync::Mutex;
 
 use super::plugin_types::{
     ClaudeInstalledPlugin, ClaudeKnownMarketplace, ClaudeMarketplaceOwner, ClaudeMarketplacePlugin,
     ClaudePluginRuntimeStatus,
 };
+use crate::coding::atomic_write;
 use crate::coding::runtime_location::{self, RuntimeLocationInfo, RuntimeLocationMode};
 
 #[derive(Debug, Deserialize, Default)]
@@ -141,39 +141,6 @@ fn known_marketplaces_path(root_dir: &Path) -> PathBuf {
     claude_plugins_root(root_dir).join("known_marketplaces.json")
 }
 
-fn write_json_value_atomic(path: &Path, value: &Value) -> Result<(), String> {
-    if let Some(parent_dir) = path.parent() {
-        if !parent_dir.exists() {
-            fs::create_dir_all(parent_dir)
-                .map_err(|error| format!("Failed to create {}: {}", parent_dir.display(), error))?;
-        }
-
-        let mut temp_file = NamedTempFile::new_in(parent_dir).map_err(|error| {
-            format!(
-                "Failed to create temp file for {}: {}",
-                path.display(),
-                error
-            )
-        })?;
-        serde_json::to_writer_pretty(temp_file.as_file_mut(), value)
-            .map_err(|error| format!("Failed to serialize {}: {}", path.display(), error))?;
-        use std::io::Write;
-        temp_file
-            .as_file_mut()
-            .write_all(b"\n")
-            .map_err(|error| format!("Failed to finalize {}: {}", path.display(), error))?;
-        temp_file
-            .persist(path)
-            .map_err(|error| format!("Failed to replace {}: {}", path.display(), error.error))?;
-        return Ok(());
-    }
-
-    let serialized = serde_json::to_string_pretty(value)
-        .map_err(|error| format!("Failed to serialize {}: {}", path.display(), error))?;
-    fs::write(path, format!("{serialized}\n"))
-        .map_err(|error| format!("Failed to write {}: {}", path.display(), error))
-}
-
 fn plugin_manifest_path(install_path: &Path) -> PathBuf {
     install_path.join(".claude-plugin").join("plugin.json")
 }
@@ -427,7 +394,7 @@ where
         return Ok(());
     }
 
-    write_json_value_atomic(
+    atomic_write::write_json_atomic(
         &known_marketplaces_file_path,
         &Value::Object(after_marketplaces),
     )
@@ -450,7 +417,7 @@ pub async fn set_claude_marketplace_auto_update_enabled(
         return Ok(());
     }
 
-    write_json_value_atomic(
+    atomic_write::write_json_atomic(
         &known_marketplaces_file_path,
         &Value::Object(marketplaces_file),
     )
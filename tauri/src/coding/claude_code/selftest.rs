@@ -0,0 +1,268 @@
+//! One-call diagnosis of a Claude Code setup for support reports.
+//!
+//! Runs the existing provider, plugin and onboarding status reads plus the provider endpoint
+//! check, and probes whether the files apply writes to are writable. Each check reports
+//! pass/warn/fail with a hint; a failing check never stops the remaining ones.
+
+use std::fs;
+use std::path::Path;
+
+use super::commands::{
+    list_claude_providers_from_sqlite, read_claude_onboarding_status, read_claude_plugin_status,
+};
+use super::types::{ClaudeCodeProvider, ClaudeSelfTest, ClaudeSelfTestCheck};
+use crate::coding::{model_validation, runtime_location};
+use crate::db::SqliteDbState;
+
+fn check(
+    id: &str,
+    status: &str,
+    message: impl Into<String>,
+    hint: Option<&str>,
+) -> ClaudeSelfTestCheck {
+    ClaudeSelfTestCheck {
+        id: id.to_string(),
+        status: status.to_string(),
+        message: message.into(),
+        hint: hint.map(str::to_string),
+    }
+}
+
+/// Whether apply could replace `path`: an existing file must open for writing without
+/// truncation, a missing one needs a writable parent directory.
+fn probe_writable(path: &Path) -> Result<(), String> {
+    if path.is_dir() {
+        return tempfile::NamedTempFile::new_in(path)
+            .map(|_| ())
+            .map_err(|error| format!("{} is not writable: {}", path.display(), error));
+    }
+    if path.exists() {
+        return fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map(|_| ())
+            .map_err(|error| format!("{} is not writable: {}", path.display(), error));
+    }
+    match path.parent() {
+        Some(parent) if parent.exists() => probe_writable(parent),
+        Some(parent) => Err(format!("{} does not exist", parent.display())),
+        None => Err(format!("{} has no parent directory", path.display())),
+    }
+}
+
+fn writable_check(id: &str, label: &str, path: &Path) -> ClaudeSelfTestCheck {
+    match probe_writable(path) {
+        Ok(()) => check(id, "pass", format!("{} is writable", path.display()), None),
+        Err(error) => check(
+            id,
+            "fail",
+            format!("{}: {}", label, error),
+            Some("Fix the file owner or permissions (e.g. chmod u+w), or close programs locking it, then apply again"),
+        ),
+    }
+}
+
+fn settings_file_check(settings_path: &Path) -> ClaudeSelfTestCheck {
+    if !settings_path.exists() {
+        return check(
+            "settingsFile",
+            "warn",
+            format!("{} does not exist yet", settings_path.display()),
+            Some("Apply a provider to create it"),
+        );
+    }
+    match fs::read_to_string(settings_path)
+        .map_err(|error| error.to_string())
+        .and_then(|content| {
+            serde_json::from_str::<serde_json::Value>(&content).map_err(|error| error.to_string())
+        }) {
+        Ok(_) => check(
+            "settingsFile",
+            "pass",
+            format!("{} is valid JSON", settings_path.display()),
+            None,
+        ),
+        Err(error) => check(
+            "settingsFile",
+            "fail",
+            format!("{} cannot be read: {}", settings_path.display(), error),
+            Some(
+                "Fix the JSON by hand or restore a backup (settings.json.bak.1), then apply again",
+            ),
+        ),
+    }
+}
+
+async fn provider_checks(
+    db: &SqliteDbState,
+    applied: Option<&ClaudeCodeProvider>,
+) -> Vec<ClaudeSelfTestCheck> {
+    let Some(provider) = applied else {
+        return vec![check(
+            "providerApplied",
+            "fail",
+            "No Claude Code provider is applied",
+            Some("Select a provider on the Claude Code page and apply it"),
+        )];
+    };
+
+    let applied_check = if provider.is_disabled {
+        check(
+            "providerApplied",
+            "warn",
+            format!("Applied provider '{}' is disabled", provider.name),
+            Some("Enable the provider or apply another one"),
+        )
+    } else {
+        check(
+            "providerApplied",
+            "pass",
+            format!("Applied provider: {}", provider.name),
+            None,
+        )
+    };
+    let reachable_check =
+        match model_validation::check_provider_endpoint(db, "claude", &provider.id).await {
+            Ok(None) => check(
+                "providerReachable",
+                "pass",
+                "Provider uses the official Anthropic endpoint",
+                None,
+            ),
+            Ok(Some(count)) => check(
+                "providerReachable",
+                "pass",
+                format!("Provider endpoint answered with {} model(s)", count),
+                None,
+            ),
+            Err(error) => check(
+                "providerReachable",
+                "warn",
+                format!("Provider model list could not be fetched: {}", error),
+                Some("Check the base URL, API key and proxy settings; relays without /models can be verified with the model connectivity test"),
+            ),
+        };
+    vec![applied_check, reachable_check]
+}
+
+/// Run every Claude setup check and report pass/warn/fail with remediation hints.
+#[tauri::command]
+pub async fn claude_setup_selftest(
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<ClaudeSelfTest, String> {
+    let db = state.db();
+    let mut checks = Vec::new();
+
+    let applied = list_claude_providers_from_sqlite(db)?
+        .into_iter()
+        .find(|provider| provider.is_applied);
+    checks.extend(provider_checks(db, applied.as_ref()).await);
+    let third_party = applied
+        .as_ref()
+        .is_some_and(|provider| provider.category != "official");
+
+    checks.push(match read_claude_plugin_status(db).await {
+        Ok(status) if status.enabled || !third_party => check(
+            "plugin",
+            "pass",
+            if status.enabled {
+                "Plugin integration is enabled"
+            } else {
+                "Plugin integration is off (not needed for the official endpoint)"
+            },
+            None,
+        ),
+        Ok(_) => check(
+            "plugin",
+            "warn",
+            "Plugin integration is off, so the IDE extension ignores the third-party provider",
+            Some("Turn on plugin integration on the Claude Code page"),
+        ),
+        Err(error) => check(
+            "plugin",
+            "fail",
+            format!("Plugin config cannot be read: {}", error),
+            Some("Fix or delete ~/.claude/config.json, then toggle plugin integration again"),
+        ),
+    });
+
+    checks.push(match read_claude_onboarding_status(db).await {
+        Ok(true) => check("onboarding", "pass", "Onboarding is marked complete", None),
+        Ok(false) if !third_party => check(
+            "onboarding",
+            "pass",
+            "Onboarding not completed yet (Claude Code will run its login flow)",
+            None,
+        ),
+        Ok(false) => check(
+            "onboarding",
+            "warn",
+            "Onboarding is not complete, so Claude Code asks for an Anthropic login first",
+            Some("Use 'Skip onboarding' on the Claude Code page"),
+        ),
+        Err(error) => check(
+            "onboarding",
+            "fail",
+            format!("~/.claude.json cannot be read: {}", error),
+            Some("Fix the JSON in ~/.claude.json or remove it and let Claude Code recreate it"),
+        ),
+    });
+
+    let root_dir = runtime_location::get_claude_runtime_location_async(db)
+        .await?
+        .host_path;
+    let settings_path = runtime_location::get_claude_settings_path_async(db).await?;
+    let claude_json_path = runtime_location::get_claude_mcp_config_path_async(db).await?;
+    checks.push(settings_file_check(&settings_path));
+    checks.push(writable_check(
+        "rootWritable",
+        "Claude config directory",
+        &root_dir,
+    ));
+    checks.push(writable_check(
+        "settingsWritable",
+        "settings.json",
+        &settings_path,
+    ));
+    checks.push(writable_check(
+        "claudeJsonWritable",
+        "~/.claude.json",
+        &claude_json_path,
+    ));
+
+    Ok(ClaudeSelfTest {
+        passed: checks.iter().all(|check| check.status != "fail"),
+        checks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writable_probe_covers_files_dirs_and_missing_parents() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let settings = temp.path().join("settings.json");
+        fs::write(&settings, "{}").unwrap();
+
+        assert!(probe_writable(temp.path()).is_ok());
+        assert!(probe_writable(&settings).is_ok());
+        assert!(probe_writable(&temp.path().join("new.json")).is_ok());
+        assert!(probe_writable(&temp.path().join("missing").join("settings.json")).is_err());
+        assert_eq!(fs::read_to_string(&settings).unwrap(), "{}");
+    }
+
+    #[test]
+    fn settings_file_check_flags_invalid_json() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let settings = temp.path().join("settings.json");
+        assert_eq!(settings_file_check(&settings).status, "warn");
+
+        fs::write(&settings, "{ broken").unwrap();
+        assert_eq!(settings_file_check(&settings).status, "fail");
+
+        fs::write(&settings, "{}").unwrap();
+        assert_eq!(settings_file_check(&settings).status, "pass");
+    }
+}
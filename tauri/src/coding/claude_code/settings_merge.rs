@@ -18,14 +18,14 @@ const PROVIDER_MODEL_NAME_ENV_FIELDS: [&str; 4] = [
     "ANTHROPIC_DEFAULT_FABLE_MODEL_NAME",
 ];
 
-fn is_provider_model_field(field_key: &str) -> bool {
+pub(super) fn is_provider_model_field(field_key: &str) -> bool {
     PROVIDER_MODEL_FIELD_MAPPINGS
         .iter()
         .any(|(provider_field, _)| provider_field == &field_key)
         || field_key == "reasoningModel"
 }
 
-fn is_provider_model_env_field(field_key: &str) -> bool {
+pub(super) fn is_provider_model_env_field(field_key: &str) -> bool {
     PROVIDER_MODEL_FIELD_MAPPINGS
         .iter()
         .any(|(_, env_field)| env_field == &field_key)
@@ -341,6 +341,118 @@ pub fn build_provider_managed_env(
     managed_env
 }
 
+/// Set provider model slots (legacy field name -> model id) in stored provider settings.
+/// Slots are written to their `env` field and the legacy top-level field is dropped; `None`
+/// or an empty id clears the slot.
+pub fn set_provider_model_slots(
+    provider_settings: &mut Value,
+    slots: &[(&str, Option<&str>)],
+) -> Result<(), String> {
+    let settings = provider_settings
+        .as_object_mut()
+        .ok_or_else(|| "Provider config must be a JSON object".to_string())?;
+    let env = settings
+        .entry("env")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or_else(|| "Provider config env must be a JSON object".to_string())?;
+
+    for (provider_field, model) in slots {
+        let (_, env_field) = PROVIDER_MODEL_FIELD_MAPPINGS
+            .iter()
+            .find(|(field, _)| field == provider_field)
+            .ok_or_else(|| format!("Unknown model slot: {}", provider_field))?;
+        match model.map(str::trim).filter(|model| !model.is_empty()) {
+            Some(model) => {
+                env.insert(env_field.to_string(), Value::String(model.to_string()));
+            }
+            None => {
+                env.remove(*env_field);
+            }
+        }
+    }
+    for (provider_field, _) in slots {
+        settings.remove(*provider_field);
+    }
+    Ok(())
+}
+
+/// Provider settings with the env apply would write: `ANTHROPIC_API_KEY` becomes
+/// `ANTHROPIC_AUTH_TOKEN`, legacy model fields move into env and empty values are dropped.
+fn normalize_provider_env(
+    provider_settings: &Value,
+    known_env_fields: &[&str],
+) -> Result<Map<String, Value>, String> {
+    let mut settings = provider_settings
+        .as_object()
+        .cloned()
+        .ok_or_else(|| "Provider config must be a JSON object".to_string())?;
+    let mut env = settings
+        .get("env")
+        .and_then(value_as_object)
+        .cloned()
+        .unwrap_or_default();
+    env.retain(|field_key, _| !known_env_fields.contains(&field_key.as_str()));
+    env.extend(build_provider_managed_env(
+        provider_settings,
+        known_env_fields,
+    ));
+    for (provider_field, _) in PROVIDER_MODEL_FIELD_MAPPINGS {
+        settings.remove(provider_field);
+    }
+    if env.is_empty() {
+        settings.remove("env");
+    } else {
+        settings.insert("env".to_string(), Value::Object(env));
+    }
+    Ok(settings)
+}
+
+fn overlay_json_object(
+    target: &mut Map<String, Value>,
+    overlay: &Map<String, Value>,
+    path: &str,
+    overridden: &mut Vec<String>,
+) {
+    for (field_key, overlay_value) in overlay {
+        let field_path = if path.is_empty() {
+            field_key.clone()
+        } else {
+            format!("{}.{}", path, field_key)
+        };
+        match (target.get_mut(field_key), overlay_value) {
+            (Some(Value::Object(target_object)), Value::Object(overlay_object)) => {
+                overlay_json_object(target_object, overlay_object, &field_path, overridden);
+            }
+            (Some(existing_value), _) => {
+                if existing_value != overlay_value {
+                    overridden.push(field_path);
+                }
+                *existing_value = overlay_value.clone();
+            }
+            (None, _) => {
+                target.insert(field_key.clone(), overlay_value.clone());
+            }
+        }
+    }
+}
+
+/// Deep-merge `overlay` provider settings onto `base` (overlay wins) and return the merged
+/// settings with the dotted paths of overridden values, e.g. "env.ANTHROPIC_BASE_URL".
+/// Both sides are normalized to the env apply writes first, so the merged provider applies
+/// like the base with the overlay's values on top.
+pub fn overlay_provider_settings(
+    base: &Value,
+    overlay: &Value,
+    known_env_fields: &[&str],
+) -> Result<(Value, Vec<String>), String> {
+    let mut merged = normalize_provider_env(base, known_env_fields)?;
+    let overlay = normalize_provider_env(overlay, known_env_fields)?;
+    let mut overridden = Vec::new();
+    overlay_json_object(&mut merged, &overlay, "", &mut overridden);
+    Ok((Value::Object(merged), overridden))
+}
+
 pub fn merge_claude_settings_for_provider(
     current_disk_settings: Option<&Value>,
     previous_common_config: Option<&Value>,
@@ -735,4 +847,65 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn model_slots_are_written_to_env_and_replace_legacy_fields() {
+        let mut settings = json!({
+            "env": { "ANTHROPIC_BASE_URL": "https://provider.example.com" },
+            "model": "legacy-model",
+            "haikuModel": "legacy-haiku"
+        });
+        set_provider_model_slots(
+            &mut settings,
+            &[("model", Some(" glm-4.6 ")), ("haikuModel", None)],
+        )
+        .expect("model slots should be set");
+
+        assert_eq!(settings["env"]["ANTHROPIC_MODEL"], "glm-4.6");
+        assert!(settings["env"]
+            .get("ANTHROPIC_DEFAULT_HAIKU_MODEL")
+            .is_none());
+        assert!(settings.get("model").is_none() && settings.get("haikuModel").is_none());
+        assert!(set_provider_model_slots(&mut settings, &[("unknown", None)]).is_err());
+    }
+
+    #[test]
+    fn overlay_provider_settings_uses_apply_env_semantics() {
+        let base = json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": "https://base.example.com",
+                "ANTHROPIC_API_KEY": "base-key"
+            },
+            "model": "base-model",
+            "permissions": { "allow": ["Read"], "deny": ["Bash"] }
+        });
+        let overlay = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "overlay-key",
+                "ANTHROPIC_DEFAULT_HAIKU_MODEL": "overlay-haiku"
+            },
+            "permissions": { "allow": ["Read"], "deny": [] }
+        });
+
+        let (merged, overridden) =
+            overlay_provider_settings(&base, &overlay, &KNOWN_ENV_FIELDS).expect("merge");
+
+        assert_eq!(
+            merged["env"]["ANTHROPIC_BASE_URL"],
+            "https://base.example.com"
+        );
+        assert_eq!(merged["env"]["ANTHROPIC_AUTH_TOKEN"], "overlay-key");
+        assert!(merged["env"].get("ANTHROPIC_API_KEY").is_none());
+        assert_eq!(merged["env"]["ANTHROPIC_MODEL"], "base-model");
+        assert_eq!(
+            merged["env"]["ANTHROPIC_DEFAULT_HAIKU_MODEL"],
+            "overlay-haiku"
+        );
+        assert!(merged.get("model").is_none());
+        assert_eq!(
+            overridden,
+            vec!["env.ANTHROPIC_AUTH_TOKEN", "permissions.deny"]
+        );
+        assert!(overlay_provider_settings(&json!([]), &overlay, &KNOWN_ENV_FIELDS).is_err());
+    }
 }
@@ -19,6 +19,8 @@ pub struct TrayProviderItem {
     pub is_selected: bool,
     /// Whether this provider is disabled
     pub is_disabled: bool,
+    /// Pinned providers are listed first and marked with a star
+    pub is_pinned: bool,
     /// Sort index for ordering
     pub sort_index: i64,
 }
@@ -72,6 +74,8 @@ pub async fn get_claude_code_tray_data<R: Runtime>(
     let mut items: Vec<TrayProviderItem> = providers
         .into_iter()
         .filter(|provider| provider.id != "__local__")
+        // Disabled providers cannot be switched to; keep the applied one so its check shows
+        .filter(|provider| !provider.is_disabled || provider.is_applied)
         .map(|provider| {
             let provider_needs_proxy = provider_protocol::provider_needs_gateway_proxy(
                 GatewayCliKey::Claude,
@@ -81,7 +85,11 @@ pub async fn get_claude_code_tray_data<R: Runtime>(
             );
             TrayProviderItem {
                 id: provider.id,
-                display_name: provider.name,
+                display_name: if provider.pinned {
+                    format!("★ {}", provider.name)
+                } else {
+                    provider.name
+                },
                 is_selected: provider.is_applied,
                 is_disabled: provider_disabled_for_tray(
                     provider.is_disabled,
@@ -91,13 +99,14 @@ pub async fn get_claude_code_tray_data<R: Runtime>(
                     gateway_running,
                     provider_needs_proxy,
                 ),
+                is_pinned: provider.pinned,
                 sort_index: provider.sort_index.unwrap_or(0) as i64,
             }
         })
         .collect();
 
-    // Sort by sort_index
-    items.sort_by_key(|c| c.sort_index);
+    // Pinned first, then by sort_index
+    items.sort_by_key(|c| (!c.is_pinned, c.sort_index));
 
     let data = TrayProviderData {
         title: "──── Claude Code ────".to_string(),
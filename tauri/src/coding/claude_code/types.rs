@@ -34,6 +34,8 @@ pub struct ClaudeCodeProviderRecord {
     pub meta: Option<Value>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    #[serde(default)]
+    pub pinned: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -64,6 +66,8 @@ pub struct ClaudeCodeProvider {
     pub meta: Option<Value>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    #[serde(default)]
+    pub pinned: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -85,6 +89,7 @@ impl From<ClaudeCodeProviderRecord> for ClaudeCodeProvider {
             meta: record.meta,
             is_applied: record.is_applied,
             is_disabled: record.is_disabled,
+            pinned: record.pinned,
             created_at: record.created_at,
             updated_at: record.updated_at,
         }
@@ -115,6 +120,8 @@ pub struct ClaudeCodeProviderContent {
     pub meta: Option<Value>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    #[serde(default)]
+    pub pinned: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -224,6 +231,61 @@ pub struct ClaudePluginStatus {
     pub has_config_file: bool,
 }
 
+/// ClaudeFullPreview - merged settings.json plus plugin and onboarding state
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeFullPreview {
+    /// Provider the settings were merged for; None means the current settings.json on disk
+    pub provider_id: Option<String>,
+    /// settings.json content with secret values masked
+    pub settings: serde_json::Value,
+    pub plugin: ClaudePluginStatus,
+    pub has_completed_onboarding: bool,
+}
+
+/// ClaudeConfigPreview - settings.json before and after applying a provider
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeConfigPreview {
+    pub provider_id: String,
+    pub settings_path: String,
+    /// Current settings.json text; None when the file does not exist yet
+    pub current: Option<String>,
+    /// settings.json text apply would write
+    pub proposed: String,
+}
+
+/// ClaudeProviderMergeResult - provider created by merge_claude_providers
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeProviderMergeResult {
+    pub provider: ClaudeCodeProvider,
+    /// Dotted settings paths where the overlay replaced a base value, e.g. "env.ANTHROPIC_BASE_URL"
+    pub overridden_keys: Vec<String>,
+}
+
+/// ClaudeSelfTestCheck - one step of the Claude setup self-test
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSelfTestCheck {
+    /// Stable check id, e.g. "providerApplied" or "settingsWritable"
+    pub id: String,
+    /// "pass" | "warn" | "fail"
+    pub status: String,
+    pub message: String,
+    /// What to do about a warn/fail result
+    pub hint: Option<String>,
+}
+
+/// ClaudeSelfTest - result of claude_setup_selftest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSelfTest {
+    /// False when any check failed; warnings do not fail the self-test
+    pub passed: bool,
+    pub checks: Vec<ClaudeSelfTestCheck>,
+}
+
 // ============================================================================
 // Claude Prompt Config Types
 // ============================================================================
@@ -131,6 +131,27 @@ pub fn resolve_local_npx_program() -> LocalCliProgram {
     resolve_local_cli_program("npx", candidates)
 }
 
+/// Look a CLI up on the current PATH only.
+pub fn find_cli_on_path(command_name: &str) -> Option<PathBuf> {
+    resolve_cli_from_path(command_name)
+}
+
+/// Look a CLI up in `~/.local/bin`, Homebrew and the node-manager global bins, ignoring PATH.
+pub fn find_cli_in_common_dirs(command_name: &str) -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(home_dir) = dirs::home_dir() {
+        push_command_candidate(
+            &mut candidates,
+            home_dir.join(".local").join("bin"),
+            command_name,
+        );
+    }
+    push_command_candidate(&mut candidates, "/opt/homebrew/bin", command_name);
+    push_command_candidate(&mut candidates, "/usr/local/bin", command_name);
+    append_node_global_candidates(&mut candidates, command_name);
+    select_existing_command_path(&candidates)
+}
+
 pub fn build_local_std_command(program_path: &Path) -> Command {
     build_local_std_command_impl(program_path)
 }
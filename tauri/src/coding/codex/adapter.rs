@@ -139,7 +139,10 @@ pub fn from_db_value_provider(value: Value) -> CodexProvider {
             .unwrap_or(false),
         is_disabled: value
             .get("is_disabled")
-            .or_else(|| value.get("isDisabled"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        pinned: value
+            .get("pinned")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
         created_at: value
@@ -195,6 +198,7 @@ pub fn to_db_value_provider(content: &CodexProviderContent) -> Value {
 
     map.insert("is_applied".to_string(), Value::Bool(content.is_applied));
     map.insert("is_disabled".to_string(), Value::Bool(content.is_disabled));
+    map.insert("pinned".to_string(), Value::Bool(content.pinned));
     map.insert(
         "created_at".to_string(),
         Value::String(content.created_at.clone()),
@@ -427,7 +431,6 @@ pub fn from_db_value_common(value: Value) -> CodexCommonConfig {
             .to_string(),
         root_dir: value
             .get("root_dir")
-            .or_else(|| value.get("rootDir"))
             .and_then(|v| v.as_str())
             .map(|v| v.to_string()),
         updated_at: updated_at_value.unwrap_or_else(|| Local::now().to_rfc3339()),
@@ -469,22 +472,18 @@ pub fn from_db_value_prompt(value: Value) -> CodexPromptConfig {
             .to_string(),
         is_applied: value
             .get("is_applied")
-            .or_else(|| value.get("isApplied"))
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
         sort_index: value
             .get("sort_index")
-            .or_else(|| value.get("sortIndex"))
             .and_then(|v| v.as_i64())
             .map(|n| n as i32),
         created_at: value
             .get("created_at")
-            .or_else(|| value.get("createdAt"))
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
         updated_at: value
             .get("updated_at")
-            .or_else(|| value.get("updatedAt"))
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()),
     }
@@ -521,6 +520,7 @@ mod tests {
             })),
             is_applied: false,
             is_disabled: false,
+            pinned: false,
             created_at: "2026-01-01T00:00:00+00:00".to_string(),
             updated_at: "2026-01-01T00:00:00+00:00".to_string(),
         };
@@ -23,6 +23,9 @@ use super::plugin_workspace;
 use super::types::*;
 use super::unified_history;
 use crate::coding::all_api_hub;
+use crate::coding::apply_history;
+use crate::coding::atomic_write;
+use crate::coding::config_backup;
 use crate::coding::db_id::db_new_id;
 use crate::coding::open_code::shell_env;
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
@@ -32,12 +35,13 @@ use crate::coding::proxy_gateway::{
 use crate::coding::runtime_location;
 use crate::coding::skills::commands::resync_all_skills_if_tool_path_changed;
 use crate::db::helpers::{
-    db_count, db_delete, db_delete_all, db_get, db_list, db_max_i64, db_patch_fields, db_put,
-    db_query_by_bool, db_update_applied_status,
+    db_count, db_delete, db_delete_all, db_get, db_list, db_max_i64, db_next_sort_index,
+    db_patch_fields, db_put, db_query_by_bool, db_update_applied_status,
 };
 use crate::db::schema::{DbTable, JsonFieldPath, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
 use crate::http_client;
+use crate::settings::store::{should_import_config_files, should_write_config_files_on_save};
 use chrono::Local;
 use tauri::{Emitter, Manager, Runtime};
 
@@ -735,7 +739,7 @@ fn ensure_codex_provider_native_for_direct(
     Ok(())
 }
 
-fn codex_provider_order() -> Result<OrderSpec, String> {
+pub(crate) fn codex_provider_order() -> Result<OrderSpec, String> {
     Ok(OrderSpec::single(OrderField::json_integer(
         "sort_index",
         OrderDirection::Asc,
@@ -749,7 +753,7 @@ fn codex_prompt_order() -> Result<OrderSpec, String> {
     ]))
 }
 
-fn list_codex_providers_from_sqlite(
+pub(crate) fn list_codex_providers_from_sqlite(
     sqlite_state: &SqliteDbState,
 ) -> Result<Vec<CodexProvider>, String> {
     let order = codex_provider_order()?;
@@ -1335,11 +1339,12 @@ pub async fn list_codex_providers_for_db(
     db: &crate::db::SqliteDbState,
 ) -> Result<Vec<CodexProvider>, String> {
     let mut providers = list_codex_providers_from_sqlite(db)?;
-    if providers.is_empty() {
+    let import_config_files = should_import_config_files(db, "codex");
+    if providers.is_empty() && import_config_files {
         import_codex_default_provider_from_local_files(db, true).await?;
         providers = list_codex_providers_from_sqlite(db)?;
     }
-    if providers.is_empty() {
+    if providers.is_empty() && import_config_files {
         if let Ok(temp_provider) = load_temp_provider_from_files_with_db(Some(db)).await {
             return Ok(vec![temp_provider]);
         }
@@ -1422,6 +1427,7 @@ async fn load_temp_provider_from_files_with_db(
         meta: None,
         is_applied: true,
         is_disabled: false,
+        pinned: false,
         created_at: now.clone(),
         updated_at: now,
     })
@@ -1462,6 +1468,7 @@ pub async fn import_codex_default_provider_from_local_files(
         meta: None,
         is_applied: true,
         is_disabled: false,
+        pinned: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -1498,6 +1505,7 @@ pub async fn import_codex_default_provider_from_local_files(
         meta: content.meta,
         is_applied: content.is_applied,
         is_disabled: content.is_disabled,
+        pinned: content.pinned,
         created_at: content.created_at,
         updated_at: content.updated_at,
     }))
@@ -2247,20 +2255,61 @@ fn merge_toml_tables(base: &mut toml_edit::Table, overlay: &toml_edit::Table) {
     }
 }
 
+/// Values are updated in place: an unchanged value keeps its formatting, and a changed one
+/// keeps the trailing comment of the value it replaces.
 fn merge_toml_items(base: &mut toml_edit::Item, overlay: &toml_edit::Item) {
     match (base, overlay) {
         (toml_edit::Item::Table(base_table), toml_edit::Item::Table(overlay_table)) => {
             merge_toml_tables(base_table, overlay_table);
         }
+        (toml_edit::Item::Value(base_value), toml_edit::Item::Value(overlay_value)) => {
+            if !toml_values_equal(base_value, overlay_value) {
+                let decor = base_value.decor().clone();
+                *base_value = overlay_value.clone();
+                *base_value.decor_mut() = decor;
+            }
+        }
         (base_item, overlay_item) => {
             *base_item = overlay_item.clone();
         }
     }
 }
 
+/// Compare TOML values by content, ignoring quoting style, whitespace and comments.
+fn toml_values_equal(left: &toml_edit::Value, right: &toml_edit::Value) -> bool {
+    use toml_edit::Value as TomlValue;
+    match (left, right) {
+        (TomlValue::String(left), TomlValue::String(right)) => left.value() == right.value(),
+        (TomlValue::Integer(left), TomlValue::Integer(right)) => left.value() == right.value(),
+        (TomlValue::Float(left), TomlValue::Float(right)) => left.value() == right.value(),
+        (TomlValue::Boolean(left), TomlValue::Boolean(right)) => left.value() == right.value(),
+        (TomlValue::Datetime(left), TomlValue::Datetime(right)) => left.value() == right.value(),
+        (TomlValue::Array(left), TomlValue::Array(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|(left, right)| toml_values_equal(left, right))
+        }
+        (TomlValue::InlineTable(left), TomlValue::InlineTable(right)) => {
+            left.len() == right.len()
+                && left.iter().all(|(key, left)| {
+                    right
+                        .get(key)
+                        .is_some_and(|right| toml_values_equal(left, right))
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Remove fields written by the previous apply. Fields the next managed config still sets
+/// are left for `merge_toml_tables` to update in place, so their position and comments
+/// survive the rewrite.
 fn remove_managed_toml_fields(
     current_table: &mut toml_edit::Table,
     previous_table: &toml_edit::Table,
+    next_table: Option<&toml_edit::Table>,
     preserve_protected_top_level_keys: bool,
 ) {
     let mut keys_to_remove = Vec::new();
@@ -2273,6 +2322,7 @@ fn remove_managed_toml_fields(
             continue;
         }
 
+        let next_item = next_table.and_then(|next_table| next_table.get(key));
         let should_remove_key = if let Some(current_item) = current_table.get_mut(key) {
             match previous_item {
                 toml_edit::Item::Table(previous_child_table) => {
@@ -2280,14 +2330,15 @@ fn remove_managed_toml_fields(
                         remove_managed_toml_fields(
                             current_child_table,
                             previous_child_table,
+                            next_item.and_then(toml_edit::Item::as_table),
                             false,
                         );
-                        current_child_table.is_empty()
+                        current_child_table.is_empty() && next_item.is_none()
                     } else {
-                        true
+                        next_item.is_none()
                     }
                 }
-                _ => true,
+                _ => next_item.is_none(),
             }
         } else {
             false
@@ -2303,6 +2354,34 @@ fn remove_managed_toml_fields(
     }
 }
 
+/// Highest document position of any table nested in `table`.
+fn max_toml_table_position(table: &toml_edit::Table) -> usize {
+    table
+        .iter()
+        .filter_map(|(_, item)| item.as_table())
+        .map(|child| {
+            child
+                .position()
+                .unwrap_or(0)
+                .max(max_toml_table_position(child))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Shift every nested table position by `offset`, so tables merged in from this document
+/// are rendered after the tables already in the target document.
+fn offset_toml_table_positions(table: &mut toml_edit::Table, offset: usize) {
+    for (_, item) in table.iter_mut() {
+        if let Some(child) = item.as_table_mut() {
+            if let Some(position) = child.position() {
+                child.set_position(position + offset);
+            }
+            offset_toml_table_positions(child, offset);
+        }
+    }
+}
+
 fn render_codex_config_document(document: &toml_edit::DocumentMut) -> String {
     let document_content = document.to_string();
     if document_content.trim_start().starts_with("#:schema") {
@@ -2329,10 +2408,14 @@ fn build_written_codex_config_toml(
         remove_managed_toml_fields(
             current_document.as_table_mut(),
             previous_managed_document.as_table(),
+            Some(next_managed_document.as_table()),
             true,
         );
     }
 
+    // Untouched keys keep their place; new tables go after the existing ones
+    let position_offset = max_toml_table_position(current_document.as_table()) + 1;
+    offset_toml_table_positions(next_managed_document.as_table_mut(), position_offset);
     merge_toml_tables(
         current_document.as_table_mut(),
         next_managed_document.as_table(),
@@ -2542,7 +2625,7 @@ fn prepare_codex_config_with_model_catalog(
     let catalog_path = config_dir.join(AI_TOOLBOX_CODEX_MODEL_CATALOG_FILENAME);
     let catalog_content = serde_json::to_string_pretty(&catalog)
         .map_err(|e| format!("Failed to serialize Codex model catalog: {}", e))?;
-    fs::write(&catalog_path, catalog_content)
+    atomic_write::write_atomic(&catalog_path, catalog_content)
         .map_err(|e| format!("Failed to write Codex model catalog: {}", e))?;
 
     set_codex_model_catalog_json_field(config_toml, true)
@@ -2626,7 +2709,15 @@ pub async fn create_codex_provider(
     app: tauri::AppHandle,
     provider: CodexProviderInput,
 ) -> Result<CodexProvider, String> {
-    let db = state.db();
+    create_codex_provider_internal(state.db(), &app, provider).await
+}
+
+/// Normalize and store a new provider; shared by create and duplicate.
+async fn create_codex_provider_internal(
+    db: &SqliteDbState,
+    app: &tauri::AppHandle,
+    provider: CodexProviderInput,
+) -> Result<CodexProvider, String> {
     let normalized_settings_config =
         normalize_provider_settings_for_storage(&db, &provider.settings_config, None).await?;
 
@@ -2644,6 +2735,7 @@ pub async fn create_codex_provider(
         meta: provider.meta,
         is_applied: false,
         is_disabled: provider.is_disabled.unwrap_or(false),
+        pinned: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -2668,11 +2760,233 @@ pub async fn create_codex_provider(
         meta: content.meta,
         is_applied: content.is_applied,
         is_disabled: content.is_disabled,
+        pinned: content.pinned,
+        created_at: content.created_at,
+        updated_at: content.updated_at,
+    })
+}
+
+/// Whether a config string is an absolute path on any platform (`/x`, `~/x`, `C:\\x`, `\\\\host`).
+fn looks_like_absolute_path(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    value.starts_with('/')
+        || value.starts_with("~/")
+        || value.starts_with("\\\\")
+        || (bytes.len() > 2
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && (bytes[2] == b'\\' || bytes[2] == b'/'))
+}
+
+fn expand_home_path(value: &str) -> PathBuf {
+    match (value.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(value),
+    }
+}
+
+fn collect_missing_toml_paths(item: &toml_edit::Item, key_path: &str, warnings: &mut Vec<String>) {
+    match item {
+        toml_edit::Item::Table(table) => {
+            for (key, child) in table.iter() {
+                collect_missing_toml_paths(child, &format!("{}.{}", key_path, key), warnings);
+            }
+        }
+        toml_edit::Item::Value(value) => {
+            collect_missing_toml_value_paths(value, key_path, warnings);
+        }
+        toml_edit::Item::ArrayOfTables(tables) => {
+            for (index, table) in tables.iter().enumerate() {
+                for (key, child) in table.iter() {
+                    let child_path = format!("{}[{}].{}", key_path, index, key);
+                    collect_missing_toml_paths(child, &child_path, warnings);
+                }
+            }
+        }
+        toml_edit::Item::None => {}
+    }
+}
+
+fn collect_missing_toml_value_paths(
+    value: &toml_edit::Value,
+    key_path: &str,
+    warnings: &mut Vec<String>,
+) {
+    match value {
+        toml_edit::Value::String(text) => {
+            let text = text.value().trim();
+            if looks_like_absolute_path(text) && !expand_home_path(text).exists() {
+                warnings.push(format!(
+                    "{} points to '{}', which does not exist on this machine",
+                    key_path, text
+                ));
+            }
+        }
+        toml_edit::Value::Array(array) => {
+            for (index, entry) in array.iter().enumerate() {
+                let entry_path = format!("{}[{}]", key_path, index);
+                collect_missing_toml_value_paths(entry, &entry_path, warnings);
+            }
+        }
+        toml_edit::Value::InlineTable(table) => {
+            for (key, entry) in table.iter() {
+                let entry_path = format!("{}.{}", key_path, key);
+                collect_missing_toml_value_paths(entry, &entry_path, warnings);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Problems worth telling the user about a config.toml copied from another machine:
+/// absolute paths that do not exist here, and `mcp_servers` entries, which providers never
+/// carry and have to be imported on the MCP page instead.
+fn codex_config_import_warnings(document: &toml_edit::DocumentMut) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (key, item) in document.as_table().iter() {
+        if PROTECTED_TOP_LEVEL_TOML_KEYS.contains(&key) {
+            continue;
+        }
+        collect_missing_toml_paths(item, key, &mut warnings);
+    }
+
+    if let Some(servers) = document
+        .as_table()
+        .get("mcp_servers")
+        .and_then(|item| item.as_table_like())
+    {
+        let names: Vec<&str> = servers.iter().map(|(name, _)| name).collect();
+        if !names.is_empty() {
+            warnings.push(format!(
+                "mcp_servers ({}) are not imported into the provider; import them from the MCP page",
+                names.join(", ")
+            ));
+        }
+    }
+    warnings
+}
+
+/// Create an unapplied provider from a config.toml copied from another machine. Keys that
+/// match the local common config are stripped like a regular save, and the merged result
+/// must still parse. Missing absolute paths and skipped `mcp_servers` are listed in the
+/// provider notes.
+#[tauri::command]
+pub async fn import_codex_config_toml(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    content: String,
+    name: String,
+) -> Result<CodexProvider, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Provider name is required".to_string());
+    }
+    if content.trim().is_empty() {
+        return Err("config.toml is empty".to_string());
+    }
+    let document = parse_toml_document(&content, "config.toml")?;
+    let warnings = codex_config_import_warnings(&document);
+
+    let db = state.db();
+    let raw_settings_config = serde_json::json!({ "auth": {}, "config": content }).to_string();
+    let settings_config =
+        normalize_provider_settings_for_storage(&db, &raw_settings_config, None).await?;
+    let provider_settings = parse_codex_settings_config(&settings_config)?;
+    let provider_config = provider_settings
+        .get("config")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+    let common_config = get_codex_common_toml(&db).await?.unwrap_or_default();
+    append_toml_configs(provider_config, &common_config)?;
+
+    for warning in &warnings {
+        log::warn!("Codex config.toml import '{}': {}", name, warning);
+    }
+    let mut notes = "Imported from config.toml".to_string();
+    for warning in &warnings {
+        notes.push_str("\n- ");
+        notes.push_str(warning);
+    }
+
+    let now = Local::now().to_rfc3339();
+    let content = CodexProviderContent {
+        name,
+        category: infer_codex_provider_category_from_settings(&provider_settings),
+        settings_config,
+        source_provider_id: None,
+        website_url: None,
+        notes: Some(notes),
+        icon: None,
+        icon_color: None,
+        sort_index: None,
+        meta: None,
+        is_applied: false,
+        is_disabled: false,
+        pinned: false,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    let provider_id = db_new_id();
+    put_codex_provider_to_sqlite(db, &provider_id, &content)?;
+    let _ = app.emit("config-changed", "window");
+
+    Ok(CodexProvider {
+        id: provider_id,
+        name: content.name,
+        category: content.category,
+        settings_config: content.settings_config,
+        source_provider_id: content.source_provider_id,
+        website_url: content.website_url,
+        notes: content.notes,
+        icon: content.icon,
+        icon_color: content.icon_color,
+        sort_index: content.sort_index,
+        meta: content.meta,
+        is_applied: content.is_applied,
+        is_disabled: content.is_disabled,
+        pinned: content.pinned,
         created_at: content.created_at,
         updated_at: content.updated_at,
     })
 }
 
+/// Copy a provider under `new_name` through the create path, so the copy's config and secrets
+/// are stored like a new provider. It keeps the disabled state, is not applied nor pinned,
+/// and is placed after the last provider.
+#[tauri::command]
+pub async fn duplicate_codex_provider(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    new_name: String,
+) -> Result<CodexProvider, String> {
+    let db = state.db();
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err("Provider name is required".to_string());
+    }
+    let source = get_codex_provider_from_sqlite(db, &provider_id)?
+        .ok_or_else(|| format!("Codex provider '{}' not found", provider_id))?;
+    let sort_index = db.with_conn(|conn| db_next_sort_index(conn, DbTable::CodexProvider))?;
+
+    let input = CodexProviderInput {
+        id: None,
+        name: new_name,
+        category: source.category,
+        settings_config: source.settings_config,
+        source_provider_id: source.source_provider_id,
+        website_url: source.website_url,
+        notes: source.notes,
+        icon: source.icon,
+        icon_color: source.icon_color,
+        sort_index: Some(sort_index),
+        meta: source.meta,
+        is_disabled: Some(source.is_disabled),
+    };
+    create_codex_provider_internal(db, &app, input).await
+}
+
 /// Update an existing Codex provider
 #[tauri::command]
 pub async fn update_codex_provider(
@@ -2727,14 +3041,15 @@ pub async fn update_codex_provider(
         meta: provider.meta,
         is_applied: provider.is_applied,
         is_disabled: existing_is_disabled,
+        pinned: existing_provider.pinned,
         created_at,
         updated_at: now,
     };
 
     put_codex_provider_to_sqlite(db, &id, &content)?;
 
-    // If this provider is applied, re-apply to config file
-    if content.is_applied {
+    // If this provider is applied, re-apply to config file (skipped in file_primary mode)
+    if content.is_applied && should_write_config_files_on_save(db, "codex") {
         if let Err(e) = apply_config_to_file_with_previous_managed_config(
             &db,
             &id,
@@ -2744,8 +3059,7 @@ pub async fn update_codex_provider(
         {
             eprintln!("Failed to auto-apply updated config: {}", e);
         } else {
-            #[cfg(target_os = "windows")]
-            let _ = app.emit("wsl-sync-request-codex", ());
+            crate::coding::wsl::emit_apply_sync_request(db, &app, "codex");
         }
     }
 
@@ -2766,6 +3080,7 @@ pub async fn update_codex_provider(
         meta: content.meta,
         is_applied: content.is_applied,
         is_disabled: content.is_disabled,
+        pinned: content.pinned,
         created_at: content.created_at,
         updated_at: content.updated_at,
     })
@@ -2785,6 +3100,11 @@ pub async fn delete_codex_provider(
     ensure_codex_provider_has_no_official_accounts(&db, &id).await?;
 
     delete_codex_provider_from_sqlite(db, &id)?;
+    crate::coding::proxy_gateway::clear_fallback_provider_after_delete(
+        &app,
+        GatewayCliKey::Codex,
+        &id,
+    );
 
     let _ = app.emit("config-changed", "window");
     Ok(())
@@ -2934,6 +3254,8 @@ async fn apply_config_to_file_with_previous_managed_config(
         preserve_official_auth,
     )
     .await?;
+    let config_path = runtime_location::get_codex_config_path_async(db).await?;
+    crate::coding::config_provenance::record_applied_config(&config_path, Some(provider_id));
     Ok(())
 }
 
@@ -2996,7 +3318,9 @@ async fn write_codex_config_files(
     let merged_auth = merge_codex_auth_json(&existing_auth, auth_to_write);
     let auth_content = serde_json::to_string_pretty(&merged_auth)
         .map_err(|e| format!("Failed to serialize auth: {}", e))?;
-    fs::write(&auth_path, auth_content).map_err(|e| format!("Failed to write auth.json: {}", e))?;
+    config_backup::backup_before_write(&auth_path)?;
+    atomic_write::write_atomic(&auth_path, auth_content)
+        .map_err(|e| format!("Failed to write auth.json: {}", e))?;
 
     // Replace previous AI Toolbox managed config while preserving runtime-owned sections.
     let config_path = config_dir.join("config.toml");
@@ -3024,7 +3348,8 @@ async fn write_codex_config_files(
     if !has_model_catalog {
         final_content = set_codex_model_catalog_json_field(&final_content, false)?;
     }
-    fs::write(config_path, final_content)
+    config_backup::backup_before_write(&config_path)?;
+    atomic_write::write_atomic(&config_path, final_content)
         .map_err(|e| format!("Failed to write config.toml: {}", e))?;
 
     Ok(())
@@ -3091,6 +3416,40 @@ pub async fn toggle_codex_provider_disabled(
     Ok(())
 }
 
+/// Pin or unpin a provider for quick apply from the tray and the pinned list
+#[tauri::command]
+pub async fn set_codex_provider_pinned(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    pinned: bool,
+) -> Result<(), String> {
+    if provider_id == CODEX_LOCAL_PROVIDER_ID {
+        return Err("Local Codex provider must be saved before it can be changed".to_string());
+    }
+    let now = Local::now().to_rfc3339();
+    let updated = state.db().with_conn(|conn| {
+        db_patch_fields(
+            conn,
+            DbTable::CodexProvider,
+            &provider_id,
+            &[
+                ("pinned", serde_json::Value::Bool(pinned)),
+                ("updated_at", serde_json::Value::String(now.clone())),
+            ],
+        )
+    })?;
+    if updated.is_none() {
+        return Err(format!(
+            "Codex provider with ID '{}' not found",
+            provider_id
+        ));
+    }
+
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
 /// Internal function to apply config
 pub async fn apply_config_internal<R: tauri::Runtime>(
     db: &crate::db::SqliteDbState,
@@ -3131,12 +3490,28 @@ async fn apply_config_internal_with_events<R: tauri::Runtime>(
     if provider_id == CODEX_LOCAL_PROVIDER_ID {
         return Err("Local Codex provider must be saved before it can be applied".to_string());
     }
+    // Restore-driven re-applies run without events and are not user applies to undo
+    let undo_entry = if emit_config_changed {
+        let files = [
+            get_codex_config_path_from_db_async(db).await?,
+            get_codex_auth_path_from_db_async(db).await?,
+        ];
+        let previous_id = apply_history::applied_provider_id(db, DbTable::CodexProvider);
+        apply_history::capture(previous_id, &files)
+    } else {
+        None
+    };
+
     // Apply config to files
     apply_config_to_file(db, provider_id).await?;
 
     // Update is_applied status in SQLite.
     update_is_applied_status(db, provider_id).await?;
 
+    if let Some(entry) = undo_entry {
+        apply_history::push(db, "codex", entry);
+    }
+
     if emit_config_changed {
         let payload = if from_tray { "tray" } else { "window" };
         let _ = app.emit("config-changed", payload);
@@ -3144,8 +3519,7 @@ async fn apply_config_internal_with_events<R: tauri::Runtime>(
 
     // Trigger WSL sync via event (Windows only)
     if emit_sync_request {
-        #[cfg(target_os = "windows")]
-        let _ = app.emit("wsl-sync-request-codex", ());
+        crate::coding::wsl::emit_apply_sync_request(db, app, "codex");
     }
 
     Ok(())
@@ -3522,15 +3896,77 @@ pub async fn read_codex_settings(
     read_codex_settings_from_disk(Some(&db)).await
 }
 
+/// Roll `config.toml` or `auth.json` back to the backup taken before an earlier write
+/// (1 = newest, up to 3). The two files rotate independently, so each is restored on its own.
+#[tauri::command]
+pub async fn restore_codex_config_backup(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    file: String,
+    index: u8,
+) -> Result<(), String> {
+    let db = state.db();
+    let path = match file.as_str() {
+        "config.toml" => get_codex_config_path_from_db_async(db).await?,
+        "auth.json" => get_codex_auth_path_from_db_async(db).await?,
+        other => return Err(format!("Unsupported Codex config file: {}", other)),
+    };
+    config_backup::restore_backup(&path, index)?;
+    emit_codex_runtime_config_changed(&app);
+    Ok(())
+}
+
+/// Truncate config.toml to the schema header and, when given, auth.json to `{}`. Both are
+/// backed up first; a missing file is created in its reset state.
+fn reset_codex_files(config_path: &Path, auth_path: Option<&Path>) -> Result<(), String> {
+    if let Some(parent_dir) = config_path.parent() {
+        fs::create_dir_all(parent_dir)
+            .map_err(|e| format!("Failed to create Codex config directory: {}", e))?;
+    }
+    config_backup::backup_before_write(config_path)?;
+    atomic_write::write_atomic(config_path, "#:schema none\n")
+        .map_err(|e| format!("Failed to write config.toml: {}", e))?;
+    if let Some(auth_path) = auth_path {
+        config_backup::backup_before_write(auth_path)?;
+        atomic_write::write_atomic(auth_path, "{}")
+            .map_err(|e| format!("Failed to write auth.json: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Reset config.toml to a clean slate after backing it up; auth.json is emptied too unless
+/// `keep_auth`. Clears the applied provider so nothing shows as applied.
+#[tauri::command]
+pub async fn reset_codex_config(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    keep_auth: bool,
+) -> Result<(), String> {
+    let db = state.db();
+    let config_path = get_codex_config_path_from_db_async(db).await?;
+    let auth_path = if keep_auth {
+        None
+    } else {
+        Some(get_codex_auth_path_from_db_async(db).await?)
+    };
+    reset_codex_files(&config_path, auth_path.as_deref())?;
+
+    let now = Local::now().to_rfc3339();
+    db.with_conn_mut(|conn| db_update_applied_status(conn, DbTable::CodexProvider, None, &now))?;
+    let _ = app.emit("config-changed", "window");
+    crate::coding::wsl::emit_apply_sync_request(db, &app, "codex");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
         append_toml_configs, build_written_codex_config_toml, codex_catalog_model_specs,
-        extract_codex_common_config_from_settings_toml, extract_provider_settings_for_storage,
-        infer_codex_provider_category_from_settings, merge_codex_auth_json,
-        merge_remote_codex_official_models, normalize_codex_model_tier,
+        codex_config_import_warnings, extract_codex_common_config_from_settings_toml,
+        extract_provider_settings_for_storage, infer_codex_provider_category_from_settings,
+        merge_codex_auth_json, merge_remote_codex_official_models, normalize_codex_model_tier,
         prepare_codex_config_with_model_catalog, project_codex_auth_to_runtime_config,
-        resolve_local_provider_meta, static_codex_official_models,
+        reset_codex_files, resolve_local_provider_meta, static_codex_official_models,
         strip_codex_common_config_from_toml, CodexHistoryRuntimeSource,
         CodexHistorySourceCandidate, CodexHistorySourceMode, RemoteCodexModel,
         AI_TOOLBOX_CODEX_MODEL_CATALOG_FILENAME, CODEX_BUILTIN_IMAGE_MODEL_ID,
@@ -3655,6 +4091,31 @@ mod tests {
         assert_eq!(image_models[0].owned_by.as_deref(), Some("openai"));
     }
 
+    #[test]
+    fn codex_config_import_warns_about_missing_paths_and_mcp_servers() {
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let existing = temp_dir.path().to_string_lossy().replace('\\', "/");
+        let config = format!(
+            r#"model = "gpt-5"
+model_instructions_file = "/nonexistent-ai-toolbox/instructions.md"
+
+[sandbox_workspace_write]
+writable_roots = ["{existing}", "/nonexistent-ai-toolbox/work"]
+
+[mcp_servers.docs]
+command = "/nonexistent-ai-toolbox/bin/docs"
+"#
+        );
+        let document: DocumentMut = config.parse().expect("parse config");
+
+        let warnings = codex_config_import_warnings(&document);
+
+        assert_eq!(warnings.len(), 3, "{warnings:?}");
+        assert!(warnings[0].starts_with("model_instructions_file points to"));
+        assert!(warnings[1].starts_with("sandbox_workspace_write.writable_roots[1] points to"));
+        assert!(warnings[2].starts_with("mcp_servers (docs)"));
+    }
+
     #[test]
     fn append_toml_configs_keeps_common_root_keys_at_root() {
         let provider = r#"
@@ -4009,6 +4470,71 @@ model_provider = "custom"
         );
     }
 
+    #[test]
+    fn build_written_codex_config_toml_keeps_comments_and_order_of_untouched_keys() {
+        let existing = r#"#:schema none
+# Main model
+model = "gpt-5" # pinned
+# Sandbox
+sandbox_mode = "workspace-write"
+model_provider = "old"
+
+# Hand-written notes
+[model_providers.old]
+name = "Old"
+
+# Servers managed by Codex itself
+[mcp_servers.test]
+command = "uvx" # local
+
+[features]
+# keep this one
+runtime_only = true
+"#;
+        let previous_managed = r#"
+model = "gpt-5"
+model_provider = "old"
+
+[model_providers.old]
+name = "Old"
+"#;
+        let next_managed = r#"
+model = "gpt-5.4"
+model_provider = "custom"
+approval_policy = "never"
+
+[model_providers.custom]
+name = "Custom"
+"#;
+
+        let rendered =
+            build_written_codex_config_toml(existing, Some(previous_managed), next_managed)
+                .unwrap();
+
+        assert_eq!(
+            rendered,
+            r#"#:schema none
+# Main model
+model = "gpt-5.4" # pinned
+# Sandbox
+sandbox_mode = "workspace-write"
+model_provider = "custom"
+approval_policy = "never"
+
+# Servers managed by Codex itself
+[mcp_servers.test]
+command = "uvx" # local
+
+[features]
+# keep this one
+runtime_only = true
+
+[model_providers.custom]
+name = "Custom"
+"#
+        );
+    }
+
     #[test]
     fn project_codex_auth_to_runtime_config_writes_provider_scoped_bearer_token() {
         let managed_config = r#"
@@ -4689,6 +5215,36 @@ approval_policy = "never"
         assert!(doc.get("base_url").is_none());
         assert_eq!(doc["approval_policy"].as_str(), Some("never"));
     }
+
+    #[test]
+    fn reset_codex_files_backs_up_and_optionally_keeps_auth() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let config_path = temp_dir.path().join("config.toml");
+        let auth_path = temp_dir.path().join("auth.json");
+        std::fs::write(&config_path, "model = \"gpt-5.4\"\n").unwrap();
+        std::fs::write(&auth_path, "{\"OPENAI_API_KEY\":\"sk-test\"}").unwrap();
+
+        reset_codex_files(&config_path, None).expect("reset keeping auth");
+        assert_eq!(
+            std::fs::read_to_string(&config_path).unwrap(),
+            "#:schema none\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(config_path.with_file_name("config.toml.bak.1")).unwrap(),
+            "model = \"gpt-5.4\"\n"
+        );
+        assert!(std::fs::read_to_string(&auth_path)
+            .unwrap()
+            .contains("sk-test"));
+
+        reset_codex_files(&config_path, Some(&auth_path)).expect("reset auth");
+        assert_eq!(std::fs::read_to_string(&auth_path).unwrap(), "{}");
+        assert!(
+            std::fs::read_to_string(auth_path.with_file_name("auth.json.bak.1"))
+                .unwrap()
+                .contains("sk-test")
+        );
+    }
 }
 
 // ============================================================================
@@ -4747,7 +5303,12 @@ pub async fn save_codex_common_config(
     runtime_location::refresh_runtime_location_cache_for_module_async(&db, "codex").await?;
 
     // Re-apply current provider config to write merged config to file
-    if let Some(provider) = get_applied_codex_provider(&db).await? {
+    let applied_provider = if should_write_config_files_on_save(db, "codex") {
+        get_applied_codex_provider(&db).await?
+    } else {
+        None
+    };
+    if let Some(provider) = applied_provider {
         if let Err(e) = apply_config_to_file_with_previous_managed_config(
             &db,
             &provider.id,
@@ -4854,6 +5415,7 @@ pub async fn save_codex_local_config(
         meta: resolve_local_provider_meta(provider_input.as_ref(), base_provider.meta),
         is_applied: true,
         is_disabled: provider_is_disabled,
+        pinned: false,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -4916,6 +5478,9 @@ pub async fn save_codex_local_config(
 pub async fn init_codex_provider_from_settings(
     db: &crate::db::SqliteDbState,
 ) -> Result<(), String> {
+    if !should_import_config_files(db, "codex") {
+        return Ok(());
+    }
     if import_codex_default_provider_from_local_files(db, true)
         .await?
         .is_some()
@@ -30,7 +30,7 @@ fn write_document(config_path: &Path, document: &DocumentMut) -> Result<(), Stri
     }
 
     let rendered = document.to_string();
-    fs::write(config_path, rendered)
+    crate::coding::atomic_write::write_atomic(config_path, rendered)
         .map_err(|error| format!("Failed to write {}: {}", config_path.display(), error))
 }
 
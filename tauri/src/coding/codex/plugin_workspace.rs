@@ -79,7 +79,6 @@ fn resolve_workspace_marketplace_path(
 fn from_db_value_workspace_root_paths(value: Value) -> Vec<String> {
     value
         .get("workspace_roots")
-        .or_else(|| value.get("workspaceRoots"))
         .and_then(Value::as_array)
         .map(|items| {
             items
@@ -17,6 +17,7 @@ pub struct TrayProviderItem {
     pub display_name: String,
     pub is_selected: bool,
     pub is_disabled: bool,
+    pub is_pinned: bool,
     pub sort_index: i64,
 }
 
@@ -67,6 +68,8 @@ pub async fn get_codex_tray_data<R: Runtime>(
     let mut items: Vec<TrayProviderItem> = providers
         .into_iter()
         .filter(|provider| provider.id != CODEX_LOCAL_PROVIDER_ID)
+        // Disabled providers cannot be switched to; keep the applied one so its check shows
+        .filter(|provider| !provider.is_disabled || provider.is_applied)
         .map(|provider| {
             let provider_needs_proxy = provider_protocol::provider_needs_gateway_proxy(
                 GatewayCliKey::Codex,
@@ -76,7 +79,11 @@ pub async fn get_codex_tray_data<R: Runtime>(
             );
             TrayProviderItem {
                 id: provider.id,
-                display_name: provider.name,
+                display_name: if provider.pinned {
+                    format!("★ {}", provider.name)
+                } else {
+                    provider.name
+                },
                 is_selected: provider.is_applied,
                 is_disabled: provider_disabled_for_tray(
                     provider.is_disabled,
@@ -86,12 +93,14 @@ pub async fn get_codex_tray_data<R: Runtime>(
                     gateway_running,
                     provider_needs_proxy,
                 ),
+                is_pinned: provider.pinned,
                 sort_index: provider.sort_index.unwrap_or(0) as i64,
             }
         })
         .collect();
 
-    items.sort_by_key(|c| c.sort_index);
+    // Pinned first, then by sort_index
+    items.sort_by_key(|c| (!c.is_pinned, c.sort_index));
 
     Ok(TrayProviderData {
         title: "──── Codex ────".to_string(),
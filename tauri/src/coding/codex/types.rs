@@ -28,6 +28,8 @@ pub struct CodexProviderRecord {
     pub meta: Option<Value>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    #[serde(default)]
+    pub pinned: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -56,6 +58,8 @@ pub struct CodexProvider {
     pub meta: Option<Value>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    #[serde(default)]
+    pub pinned: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -76,6 +80,7 @@ impl From<CodexProviderRecord> for CodexProvider {
             meta: record.meta,
             is_applied: record.is_applied,
             is_disabled: record.is_disabled,
+            pinned: record.pinned,
             created_at: record.created_at,
             updated_at: record.updated_at,
         }
@@ -104,6 +109,8 @@ pub struct CodexProviderContent {
     pub meta: Option<Value>,
     pub is_applied: bool,
     pub is_disabled: bool,
+    #[serde(default)]
+    pub pinned: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -0,0 +1,115 @@
+//! Rotating backups of runtime config files taken before apply overwrites them.
+//!
+//! Backups sit next to the file as `<name>.bak.1` (newest) to `<name>.bak.3` (oldest), so
+//! edits made outside the app survive a bad apply. Re-applying over an unchanged file does
+//! not rotate, otherwise a few repeated applies would push every useful backup out.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of backups kept per config file.
+pub const CONFIG_BACKUP_COUNT: u8 = 3;
+
+/// Path of backup `index` (1 = newest) for `path`.
+pub fn backup_path(path: &Path, index: u8) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".bak.{}", index));
+    path.with_file_name(file_name)
+}
+
+fn validate_index(index: u8) -> Result<(), String> {
+    if (1..=CONFIG_BACKUP_COUNT).contains(&index) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Backup index must be between 1 and {}",
+            CONFIG_BACKUP_COUNT
+        ))
+    }
+}
+
+/// Copy the current file to `.bak.1`, shifting older backups down and dropping the oldest.
+/// Does nothing when the file does not exist yet or already matches the newest backup.
+pub fn backup_before_write(path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Ok(());
+    }
+    let current = fs::read(path)
+        .map_err(|error| format!("Failed to read {} for backup: {}", path.display(), error))?;
+    let newest = backup_path(path, 1);
+    if fs::read(&newest).is_ok_and(|content| content == current) {
+        return Ok(());
+    }
+
+    for index in (1..CONFIG_BACKUP_COUNT).rev() {
+        let from = backup_path(path, index);
+        if from.exists() {
+            let to = backup_path(path, index + 1);
+            fs::rename(&from, &to).map_err(|error| {
+                format!("Failed to rotate backup {}: {}", from.display(), error)
+            })?;
+        }
+    }
+    // fs::copy keeps the permissions of the source, which matters for auth files.
+    fs::copy(path, &newest)
+        .map_err(|error| format!("Failed to back up {}: {}", path.display(), error))?;
+    Ok(())
+}
+
+/// Replace `path` with backup `index`. The backups themselves are left as they are.
+pub fn restore_backup(path: &Path, index: u8) -> Result<(), String> {
+    validate_index(index)?;
+    let backup = backup_path(path, index);
+    if !backup.is_file() {
+        return Err(format!("Backup {} does not exist", backup.display()));
+    }
+    let content = fs::read(&backup)
+        .map_err(|error| format!("Failed to read backup {}: {}", backup.display(), error))?;
+    crate::coding::atomic_write::write_atomic(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(path: &Path) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn backups_rotate_and_keep_the_last_three() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("settings.json");
+        backup_before_write(&path).unwrap();
+        assert!(!backup_path(&path, 1).exists());
+
+        for version in 1..=4 {
+            fs::write(&path, format!("v{version}")).unwrap();
+            backup_before_write(&path).unwrap();
+        }
+        // Unchanged file: no rotation
+        backup_before_write(&path).unwrap();
+
+        assert_eq!(read(&backup_path(&path, 1)), "v4");
+        assert_eq!(read(&backup_path(&path, 2)), "v3");
+        assert_eq!(read(&backup_path(&path, 3)), "v2");
+        assert!(!backup_path(&path, 4).exists());
+    }
+
+    #[test]
+    fn restore_replaces_file_with_selected_backup() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("config.toml");
+        fs::write(&path, "old").unwrap();
+        backup_before_write(&path).unwrap();
+        fs::write(&path, "broken").unwrap();
+
+        restore_backup(&path, 1).unwrap();
+
+        assert_eq!(read(&path), "old");
+        assert_eq!(read(&backup_path(&path, 1)), "old");
+        assert!(restore_backup(&path, 2).is_err());
+        assert!(restore_backup(&path, 0).is_err());
+        assert!(restore_backup(&path, 4).is_err());
+    }
+}
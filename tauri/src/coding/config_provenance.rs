@@ -0,0 +1,163 @@
+//! Provenance of applied runtime config files.
+//!
+//! After writing a tool's main config on apply, a sidecar `<file>.ai-toolbox-managed` records
+//! the provider id and a SHA-256 of the written content. Comparing the sidecar with the file
+//! on disk tells whether the current config is exactly what the app wrote or was edited by
+//! hand since. A sidecar is used instead of inline markers because JSON cannot hold comments.
+//! OpenCode has no applied provider; its marker records the provider of the default `model`.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::State;
+
+use crate::coding::{open_code, runtime_location};
+use crate::db::SqliteDbState;
+
+const SIDECAR_SUFFIX: &str = ".ai-toolbox-managed";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManagedMarker {
+    #[serde(default)]
+    provider_id: Option<String>,
+    content_hash: String,
+    written_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigProvenance {
+    pub tool: String,
+    pub path: String,
+    pub exists: bool,
+    /// A marker from a previous apply is present.
+    pub managed: bool,
+    /// The file on disk still matches the content the app wrote.
+    pub matches: bool,
+    pub provider_id: Option<String>,
+    pub written_at: Option<String>,
+}
+
+fn sidecar_path(config_path: &Path) -> PathBuf {
+    let mut file_name = config_path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    file_name.push(SIDECAR_SUFFIX);
+    config_path.with_file_name(file_name)
+}
+
+fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Record that `config_path` was just written by applying `provider_id`.
+/// Best-effort: a failed marker write never fails the apply itself.
+pub(crate) fn record_applied_config(config_path: &Path, provider_id: Option<&str>) {
+    let content = match std::fs::read(config_path) {
+        Ok(content) => content,
+        Err(error) => {
+            log::warn!(
+                "Skip provenance marker for {}: {}",
+                config_path.display(),
+                error
+            );
+            return;
+        }
+    };
+    let marker = ManagedMarker {
+        provider_id: provider_id.map(str::to_string),
+        content_hash: content_hash(&content),
+        written_at: chrono::Local::now().to_rfc3339(),
+    };
+    let result = serde_json::to_string_pretty(&marker)
+        .map_err(|error| error.to_string())
+        .and_then(|serialized| {
+            std::fs::write(sidecar_path(config_path), serialized).map_err(|e| e.to_string())
+        });
+    if let Err(error) = result {
+        log::warn!(
+            "Failed to write provenance marker for {}: {}",
+            config_path.display(),
+            error
+        );
+    }
+}
+
+fn read_provenance(tool: &str, config_path: &Path) -> ConfigProvenance {
+    let content = std::fs::read(config_path).ok();
+    let marker = std::fs::read_to_string(sidecar_path(config_path))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<ManagedMarker>(&raw).ok());
+    let matches = match (&content, &marker) {
+        (Some(content), Some(marker)) => content_hash(content) == marker.content_hash,
+        _ => false,
+    };
+
+    ConfigProvenance {
+        tool: tool.to_string(),
+        path: config_path.to_string_lossy().to_string(),
+        exists: content.is_some(),
+        managed: marker.is_some(),
+        matches,
+        provider_id: marker
+            .as_ref()
+            .and_then(|marker| marker.provider_id.clone()),
+        written_at: marker.map(|marker| marker.written_at),
+    }
+}
+
+/// Report whether a tool's applied config on disk came from this app or was hand-edited.
+/// Supported tools: "claude" (settings.json), "codex" (config.toml) and "opencode".
+#[tauri::command]
+pub async fn get_config_provenance(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+) -> Result<ConfigProvenance, String> {
+    let db = state.db();
+    let config_path = match tool.as_str() {
+        "claude" => runtime_location::get_claude_settings_path_async(db).await?,
+        "codex" => runtime_location::get_codex_config_path_async(db).await?,
+        "opencode" => PathBuf::from(open_code::get_opencode_config_path(state.clone()).await?),
+        other => {
+            return Err(format!(
+                "Config provenance is not supported for '{}'",
+                other
+            ))
+        }
+    };
+    Ok(read_provenance(&tool, &config_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provenance_detects_hand_edits_after_apply() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let config_path = temp.path().join("settings.json");
+
+        let missing = read_provenance("claude", &config_path);
+        assert!(!missing.exists && !missing.managed && !missing.matches);
+
+        std::fs::write(&config_path, "{\"env\":{}}\n").expect("write config");
+        let unmanaged = read_provenance("claude", &config_path);
+        assert!(unmanaged.exists && !unmanaged.managed);
+
+        record_applied_config(&config_path, Some("provider-1"));
+        assert!(temp
+            .path()
+            .join("settings.json.ai-toolbox-managed")
+            .exists());
+        let applied = read_provenance("claude", &config_path);
+        assert!(applied.managed && applied.matches);
+        assert_eq!(applied.provider_id.as_deref(), Some("provider-1"));
+
+        std::fs::write(&config_path, "{\"env\":{\"A\":\"1\"}}\n").expect("edit config");
+        let edited = read_provenance("claude", &config_path);
+        assert!(edited.managed && !edited.matches);
+    }
+}
@@ -0,0 +1,236 @@
+//! Report edits to managed runtime config files made outside AI Toolbox.
+//!
+//! Claude `settings.json`, Codex `config.toml` / `auth.json` and the OpenCode config are
+//! watched through their parent directories: editors and `atomic_write` replace a file by
+//! renaming over it, which would drop a watch placed on the file itself. Events are debounced
+//! per file for `DEBOUNCE`, then `config-file-changed` is emitted with the tool key, unless
+//! AI Toolbox wrote that file itself within `SELF_WRITE_GUARD`. Paths are re-resolved every
+//! `RESOLVE_INTERVAL` so runtime location changes are followed.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::coding::runtime_location;
+use crate::coding::tools::platform_path_compare_key;
+use crate::db::SqliteDbState;
+
+const DEBOUNCE: Duration = Duration::from_millis(500);
+const SELF_WRITE_GUARD: Duration = Duration::from_secs(2);
+const RESOLVE_INTERVAL: Duration = Duration::from_secs(60);
+const TICK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Files written by AI Toolbox recently, keyed by `platform_path_compare_key`
+static RECENT_APP_WRITES: LazyLock<Mutex<HashMap<String, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigFileChangedPayload {
+    /// "claude_code" | "codex" | "opencode"
+    tool: &'static str,
+    path: String,
+}
+
+/// Remember that AI Toolbox just wrote `path` so the watcher does not report it.
+pub(crate) fn record_app_write(path: &Path) {
+    if let Ok(mut writes) = RECENT_APP_WRITES.lock() {
+        let now = Instant::now();
+        writes.retain(|_, written_at| now.duration_since(*written_at) < SELF_WRITE_GUARD);
+        writes.insert(platform_path_compare_key(path), now);
+    }
+}
+
+fn written_by_app(key: &str, now: Instant) -> bool {
+    RECENT_APP_WRITES
+        .lock()
+        .ok()
+        .and_then(|writes| writes.get(key).copied())
+        .is_some_and(|written_at| now.saturating_duration_since(written_at) < SELF_WRITE_GUARD)
+}
+
+/// Per-file debounce: a file settles once no event arrived for `DEBOUNCE`.
+#[derive(Default)]
+struct Debouncer {
+    pending: HashMap<String, Instant>,
+}
+
+impl Debouncer {
+    fn note(&mut self, key: String, now: Instant) {
+        self.pending.insert(key, now);
+    }
+
+    fn take_settled(&mut self, now: Instant) -> Vec<String> {
+        let settled: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, last_event)| now.saturating_duration_since(**last_event) >= DEBOUNCE)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &settled {
+            self.pending.remove(key);
+        }
+        settled
+    }
+}
+
+async fn resolve_watched_files(db: &SqliteDbState) -> Vec<(&'static str, PathBuf)> {
+    let mut files = Vec::new();
+    if let Ok(path) = runtime_location::get_claude_settings_path_async(db).await {
+        files.push(("claude_code", path));
+    }
+    if let Ok(path) = runtime_location::get_codex_config_path_async(db).await {
+        files.push(("codex", path));
+    }
+    if let Ok(path) = runtime_location::get_codex_auth_path_async(db).await {
+        files.push(("codex", path));
+    }
+    if let Ok(location) = runtime_location::get_opencode_runtime_location_async(db).await {
+        files.push(("opencode", location.host_path));
+    }
+    files
+}
+
+/// Watch the parent directory of every file, dropping directories no longer needed.
+/// Directories that cannot be watched (missing, unreachable WSL share) are retried on the
+/// next resolve.
+fn update_watches(
+    watcher: &mut impl Watcher,
+    watched_dirs: &mut HashSet<PathBuf>,
+    files: &[(&'static str, PathBuf)],
+) {
+    let wanted: HashSet<PathBuf> = files
+        .iter()
+        .filter_map(|(_, path)| path.parent().map(Path::to_path_buf))
+        .collect();
+
+    for dir in watched_dirs
+        .difference(&wanted)
+        .cloned()
+        .collect::<Vec<_>>()
+    {
+        let _ = watcher.unwatch(&dir);
+        watched_dirs.remove(&dir);
+    }
+    for dir in wanted {
+        if watched_dirs.contains(&dir) || !dir.is_dir() {
+            continue;
+        }
+        match watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                watched_dirs.insert(dir);
+            }
+            Err(error) => log::debug!("Cannot watch {}: {}", dir.display(), error),
+        }
+    }
+}
+
+/// Background task: emit `config-file-changed` when a managed config file changes on disk
+/// outside AI Toolbox.
+pub async fn run_config_file_watcher<R: tauri::Runtime>(app: tauri::AppHandle<R>) {
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+    let watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        if matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) {
+            for path in event.paths {
+                let _ = sender.send(path);
+            }
+        }
+    });
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            log::warn!("Config file watcher unavailable: {}", error);
+            return;
+        }
+    };
+
+    let mut files: HashMap<String, (&'static str, PathBuf)> = HashMap::new();
+    let mut watched_dirs = HashSet::new();
+    let mut debouncer = Debouncer::default();
+    let mut next_resolve = Instant::now();
+    let mut tick = tokio::time::interval(TICK_INTERVAL);
+    loop {
+        tokio::select! {
+            Some(path) = receiver.recv() => {
+                let key = platform_path_compare_key(&path);
+                if files.contains_key(&key) {
+                    debouncer.note(key, Instant::now());
+                }
+            }
+            _ = tick.tick() => {
+                let now = Instant::now();
+                if now >= next_resolve {
+                    let resolved = resolve_watched_files(&app.state::<SqliteDbState>()).await;
+                    update_watches(&mut watcher, &mut watched_dirs, &resolved);
+                    files = resolved
+                        .into_iter()
+                        .map(|(tool, path)| (platform_path_compare_key(&path), (tool, path)))
+                        .collect();
+                    next_resolve = now + RESOLVE_INTERVAL;
+                }
+                for key in debouncer.take_settled(now) {
+                    if written_by_app(&key, now) {
+                        continue;
+                    }
+                    if let Some(&(tool, ref path)) = files.get(&key) {
+                        log::info!("{} config changed on disk: {}", tool, path.display());
+                        let _ = app.emit(
+                            "config-file-changed",
+                            ConfigFileChangedPayload {
+                                tool,
+                                path: path.to_string_lossy().to_string(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debouncer_settles_quiet_files_and_skips_app_writes() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::default();
+        debouncer.note("a".to_string(), start);
+        debouncer.note("b".to_string(), start);
+        // A burst of writes keeps "a" pending
+        debouncer.note("a".to_string(), start + Duration::from_millis(400));
+
+        assert_eq!(
+            debouncer.take_settled(start + Duration::from_millis(600)),
+            vec!["b".to_string()]
+        );
+        assert!(debouncer
+            .take_settled(start + Duration::from_millis(800))
+            .is_empty());
+        assert_eq!(
+            debouncer.take_settled(start + Duration::from_millis(900)),
+            vec!["a".to_string()]
+        );
+
+        let path = std::env::temp_dir()
+            .join("config-watcher-test")
+            .join("settings.json");
+        let key = platform_path_compare_key(&path);
+        assert!(!written_by_app(&key, Instant::now()));
+        record_app_write(&path);
+        assert!(written_by_app(&key, Instant::now()));
+        assert!(!written_by_app(&key, Instant::now() + SELF_WRITE_GUARD));
+    }
+}
@@ -0,0 +1,131 @@
+//! Check that the app can write into every tool's config directory.
+//!
+//! Each resolved runtime directory gets a temp file created and deleted in it, the same
+//! create/rename/remove rights an apply needs. Directories that do not exist are reported as
+//! missing rather than created, so the check never leaves anything behind for tools that are
+//! not installed.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::coding::runtime_location::{self, RuntimeLocationInfo};
+use crate::db::SqliteDbState;
+
+const WRITE_TEST_PREFIX: &str = ".ai-toolbox-write-test-";
+
+/// Writability of one tool's config directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WritabilityResult {
+    pub tool: String,
+    /// Empty when the directory could not be resolved
+    pub directory: String,
+    /// "writable" | "not_writable" | "missing" | "error"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Config directory of a runtime location; OpenCode and OpenClaw locations point at a file.
+fn config_dir(module: &str, location: &RuntimeLocationInfo) -> PathBuf {
+    match module {
+        "opencode" | "openclaw" => location
+            .host_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| location.host_path.clone()),
+        _ => location.host_path.clone(),
+    }
+}
+
+fn check_dir_writable(tool: &str, dir: &Path) -> WritabilityResult {
+    let mut result = WritabilityResult {
+        tool: tool.to_string(),
+        directory: dir.to_string_lossy().to_string(),
+        status: "writable".to_string(),
+        error: None,
+    };
+    if !dir.is_dir() {
+        result.status = "missing".to_string();
+        return result;
+    }
+    let written = tempfile::Builder::new()
+        .prefix(WRITE_TEST_PREFIX)
+        .tempfile_in(dir)
+        .map_err(|e| format!("Failed to create a file: {}", e))
+        .and_then(|file| {
+            file.close()
+                .map_err(|e| format!("Failed to delete a file: {}", e))
+        });
+    if let Err(error) = written {
+        result.status = "not_writable".to_string();
+        result.error = Some(error);
+    }
+    result
+}
+
+/// Create and delete a temp file in every tool's config directory and report, per tool, the
+/// exact directory and any permission error. Tools whose directory does not exist are "missing".
+#[tauri::command]
+pub async fn test_config_writability(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<WritabilityResult>, String> {
+    let db = state.db();
+    let mut results = Vec::with_capacity(runtime_location::MODULE_KEYS.len());
+    for module in runtime_location::MODULE_KEYS {
+        let result =
+            match runtime_location::refresh_runtime_location_cache_for_module_async(db, module)
+                .await
+            {
+                Ok(location) => check_dir_writable(module, &config_dir(module, &location)),
+                Err(error) => WritabilityResult {
+                    tool: module.to_string(),
+                    directory: String::new(),
+                    status: "error".to_string(),
+                    error: Some(error),
+                },
+            };
+        results.push(result);
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writability_check_cleans_up_and_reports_missing_dirs() {
+        let temp = tempfile::tempdir().expect("temp dir");
+
+        let writable = check_dir_writable("claude", temp.path());
+        assert_eq!(writable.status, "writable");
+        assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 0);
+
+        let missing = check_dir_writable("codex", &temp.path().join("absent"));
+        assert_eq!(missing.status, "missing");
+        assert!(!temp.path().join("absent").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writability_check_reports_read_only_dirs() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("temp dir");
+        std::fs::set_permissions(temp.path(), std::fs::Permissions::from_mode(0o555)).unwrap();
+        // Root ignores directory permissions, so there is nothing to observe.
+        let probe = temp.path().join("probe");
+        if std::fs::write(&probe, b"").is_ok() {
+            let _ = std::fs::remove_file(&probe);
+            return;
+        }
+
+        let result = check_dir_writable("claude", temp.path());
+        std::fs::set_permissions(temp.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(result.status, "not_writable");
+        assert!(result.error.unwrap().contains("Failed to create"));
+    }
+}
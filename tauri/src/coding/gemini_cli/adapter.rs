@@ -7,37 +7,24 @@ use crate::coding::db_id::db_extract_id;
 use chrono::Local;
 use serde_json::{json, Value};
 
-fn get_str_compat(value: &Value, snake_key: &str, camel_key: &str, default: &str) -> String {
+fn get_str(value: &Value, key: &str, default: &str) -> String {
     value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
+        .get(key)
         .and_then(|v| v.as_str())
         .unwrap_or(default)
         .to_string()
 }
 
-fn get_opt_str_compat(value: &Value, snake_key: &str, camel_key: &str) -> Option<String> {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_str())
-        .map(String::from)
+fn get_opt_str(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
 }
 
-fn get_i64_compat(value: &Value, snake_key: &str, camel_key: &str) -> Option<i32> {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_i64())
-        .map(|v| v as i32)
+fn get_i64(value: &Value, key: &str) -> Option<i32> {
+    value.get(key).and_then(|v| v.as_i64()).map(|v| v as i32)
 }
 
-fn get_bool_compat(value: &Value, snake_key: &str, camel_key: &str, default: bool) -> bool {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_bool())
-        .unwrap_or(default)
+fn get_bool(value: &Value, key: &str, default: bool) -> bool {
+    value.get(key).and_then(|v| v.as_bool()).unwrap_or(default)
 }
 
 fn mask_token_preview(value: &str) -> Option<String> {
@@ -87,20 +74,20 @@ fn token_expires_at_from_snapshot(snapshot: &str) -> Option<i64> {
 pub fn from_db_value_provider(value: Value) -> GeminiCliProvider {
     GeminiCliProvider {
         id: db_extract_id(&value),
-        name: get_str_compat(&value, "name", "name", "Unnamed Provider"),
-        category: get_str_compat(&value, "category", "category", "custom"),
-        settings_config: get_str_compat(&value, "settings_config", "settingsConfig", "{}"),
-        source_provider_id: get_opt_str_compat(&value, "source_provider_id", "sourceProviderId"),
-        website_url: get_opt_str_compat(&value, "website_url", "websiteUrl"),
-        notes: get_opt_str_compat(&value, "notes", "notes"),
-        icon: get_opt_str_compat(&value, "icon", "icon"),
-        icon_color: get_opt_str_compat(&value, "icon_color", "iconColor"),
-        sort_index: get_i64_compat(&value, "sort_index", "sortIndex"),
+        name: get_str(&value, "name", "Unnamed Provider"),
+        category: get_str(&value, "category", "custom"),
+        settings_config: get_str(&value, "settings_config", "{}"),
+        source_provider_id: get_opt_str(&value, "source_provider_id"),
+        website_url: get_opt_str(&value, "website_url"),
+        notes: get_opt_str(&value, "notes"),
+        icon: get_opt_str(&value, "icon"),
+        icon_color: get_opt_str(&value, "icon_color"),
+        sort_index: get_i64(&value, "sort_index"),
         meta: value.get("meta").cloned(),
-        is_applied: get_bool_compat(&value, "is_applied", "isApplied", false),
-        is_disabled: get_bool_compat(&value, "is_disabled", "isDisabled", false),
-        created_at: get_str_compat(&value, "created_at", "createdAt", ""),
-        updated_at: get_str_compat(&value, "updated_at", "updatedAt", ""),
+        is_applied: get_bool(&value, "is_applied", false),
+        is_disabled: get_bool(&value, "is_disabled", false),
+        created_at: get_str(&value, "created_at", ""),
+        updated_at: get_str(&value, "updated_at", ""),
     }
 }
 
@@ -112,47 +99,40 @@ pub fn to_db_value_provider(content: &GeminiCliProviderContent) -> Value {
 }
 
 pub fn from_db_value_official_account(value: Value) -> GeminiCliOfficialAccount {
-    let auth_snapshot = get_str_compat(&value, "auth_snapshot", "authSnapshot", "");
+    let auth_snapshot = get_str(&value, "auth_snapshot", "");
 
     GeminiCliOfficialAccount {
         id: db_extract_id(&value),
-        provider_id: get_str_compat(&value, "provider_id", "providerId", ""),
-        name: get_str_compat(&value, "name", "name", ""),
-        kind: get_str_compat(&value, "kind", "kind", "oauth"),
-        email: get_opt_str_compat(&value, "email", "email"),
+        provider_id: get_str(&value, "provider_id", ""),
+        name: get_str(&value, "name", ""),
+        kind: get_str(&value, "kind", "oauth"),
+        email: get_opt_str(&value, "email"),
         auth_snapshot: Some(auth_snapshot.clone()),
-        auth_mode: get_opt_str_compat(&value, "auth_mode", "authMode"),
-        account_id: get_opt_str_compat(&value, "account_id", "accountId"),
-        project_id: get_opt_str_compat(&value, "project_id", "projectId"),
-        plan_type: get_opt_str_compat(&value, "plan_type", "planType"),
-        last_refresh: get_opt_str_compat(&value, "last_refresh", "lastRefresh"),
+        auth_mode: get_opt_str(&value, "auth_mode"),
+        account_id: get_opt_str(&value, "account_id"),
+        project_id: get_opt_str(&value, "project_id"),
+        plan_type: get_opt_str(&value, "plan_type"),
+        last_refresh: get_opt_str(&value, "last_refresh"),
         token_expires_at: token_expires_at_from_snapshot(&auth_snapshot),
         access_token_preview: token_preview_from_snapshot(&auth_snapshot, "/token/access_token")
             .or_else(|| token_preview_from_snapshot(&auth_snapshot, "/access_token")),
         refresh_token_preview: token_preview_from_snapshot(&auth_snapshot, "/token/refresh_token")
             .or_else(|| token_preview_from_snapshot(&auth_snapshot, "/refresh_token")),
-        limit_short_label: get_opt_str_compat(&value, "limit_short_label", "limitShortLabel"),
-        limit_5h_text: get_opt_str_compat(&value, "limit_5h_text", "limit5hText"),
-        limit_weekly_text: get_opt_str_compat(&value, "limit_weekly_text", "limitWeeklyText"),
+        limit_short_label: get_opt_str(&value, "limit_short_label"),
+        limit_5h_text: get_opt_str(&value, "limit_5h_text"),
+        limit_weekly_text: get_opt_str(&value, "limit_weekly_text"),
         limit_5h_reset_at: value
             .get("limit_5h_reset_at")
             .or_else(|| value.get("limit5hResetAt"))
             .and_then(Value::as_i64),
-        limit_weekly_reset_at: value
-            .get("limit_weekly_reset_at")
-            .or_else(|| value.get("limitWeeklyResetAt"))
-            .and_then(Value::as_i64),
-        last_limits_fetched_at: get_opt_str_compat(
-            &value,
-            "last_limits_fetched_at",
-            "lastLimitsFetchedAt",
-        ),
-        last_error: get_opt_str_compat(&value, "last_error", "lastError"),
-        sort_index: get_i64_compat(&value, "sort_index", "sortIndex"),
-        is_applied: get_bool_compat(&value, "is_applied", "isApplied", false),
+        limit_weekly_reset_at: value.get("limit_weekly_reset_at").and_then(Value::as_i64),
+        last_limits_fetched_at: get_opt_str(&value, "last_limits_fetched_at"),
+        last_error: get_opt_str(&value, "last_error"),
+        sort_index: get_i64(&value, "sort_index"),
+        is_applied: get_bool(&value, "is_applied", false),
         is_virtual: false,
-        created_at: get_str_compat(&value, "created_at", "createdAt", ""),
-        updated_at: get_str_compat(&value, "updated_at", "updatedAt", ""),
+        created_at: get_str(&value, "created_at", ""),
+        updated_at: get_str(&value, "updated_at", ""),
     }
 }
 
@@ -175,12 +155,10 @@ pub fn from_db_value_common(value: Value) -> GeminiCliCommonConfig {
             .to_string(),
         root_dir: value
             .get("root_dir")
-            .or_else(|| value.get("rootDir"))
             .and_then(|v| v.as_str())
             .map(|v| v.to_string()),
         updated_at: value
             .get("updated_at")
-            .or_else(|| value.get("updatedAt"))
             .and_then(|v| v.as_str())
             .map(str::to_string)
             .unwrap_or_else(|| Local::now().to_rfc3339()),
@@ -203,12 +181,12 @@ pub fn to_db_value_common(config: &str, root_dir: Option<&str>) -> Value {
 pub fn from_db_value_prompt(value: Value) -> GeminiCliPromptConfig {
     GeminiCliPromptConfig {
         id: db_extract_id(&value),
-        name: get_str_compat(&value, "name", "name", "Unnamed Prompt"),
-        content: get_str_compat(&value, "content", "content", ""),
-        is_applied: get_bool_compat(&value, "is_applied", "isApplied", false),
-        sort_index: get_i64_compat(&value, "sort_index", "sortIndex"),
-        created_at: get_opt_str_compat(&value, "created_at", "createdAt"),
-        updated_at: get_opt_str_compat(&value, "updated_at", "updatedAt"),
+        name: get_str(&value, "name", "Unnamed Prompt"),
+        content: get_str(&value, "content", ""),
+        is_applied: get_bool(&value, "is_applied", false),
+        sort_index: get_i64(&value, "sort_index"),
+        created_at: get_opt_str(&value, "created_at"),
+        updated_at: get_opt_str(&value, "updated_at"),
     }
 }
 
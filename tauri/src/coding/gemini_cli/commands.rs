@@ -7,6 +7,7 @@ use std::path::{Component, Path, PathBuf};
 
 use super::adapter;
 use super::types::*;
+use crate::coding::atomic_write;
 use crate::coding::db_id::db_new_id;
 use crate::coding::open_code::shell_env;
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
@@ -19,6 +20,7 @@ use crate::db::helpers::{
 use crate::db::schema::{DbTable, JsonFieldPath, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
 use crate::http_client;
+use crate::settings::store::{should_import_config_files, should_write_config_files_on_save};
 use tauri::{Emitter, Manager};
 
 fn gemini_cli_gateway_takeover_active<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> bool {
@@ -587,9 +589,7 @@ async fn write_settings_value_to_db_async(
         fs::create_dir_all(parent)
             .map_err(|error| format!("Failed to create Gemini CLI directory: {}", error))?;
     }
-    let serialized = serde_json::to_string_pretty(value)
-        .map_err(|error| format!("Failed to serialize Gemini CLI settings: {}", error))?;
-    fs::write(&settings_path, format!("{serialized}\n"))
+    atomic_write::write_json_atomic(&settings_path, value)
         .map_err(|error| format!("Failed to write Gemini CLI settings.json: {}", error))
 }
 
@@ -609,7 +609,7 @@ async fn write_env_to_db_async(
         fs::create_dir_all(parent)
             .map_err(|error| format!("Failed to create Gemini CLI directory: {}", error))?;
     }
-    fs::write(&env_path, merged)
+    atomic_write::write_atomic(&env_path, merged)
         .map_err(|error| format!("Failed to write Gemini CLI .env: {}", error))
 }
 
@@ -769,7 +769,7 @@ fn emit_sync_requests<R: tauri::Runtime>(_app: &tauri::AppHandle<R>) {
     let _ = _app.emit("wsl-sync-request-geminicli", ());
 }
 
-fn gemini_provider_order() -> Result<OrderSpec, String> {
+pub(crate) fn gemini_provider_order() -> Result<OrderSpec, String> {
     Ok(OrderSpec::single(OrderField::json_integer(
         "sort_index",
         OrderDirection::Asc,
@@ -1236,6 +1236,9 @@ pub async fn import_gemini_cli_default_provider_from_local_files(
 pub async fn init_gemini_cli_provider_from_settings(
     db: &crate::db::SqliteDbState,
 ) -> Result<(), String> {
+    if !should_import_config_files(db, "geminicli") {
+        return Ok(());
+    }
     let _ = import_gemini_cli_default_provider_from_local_files(db, true).await?;
     Ok(())
 }
@@ -1251,11 +1254,12 @@ pub async fn list_gemini_cli_providers_for_db(
     db: &crate::db::SqliteDbState,
 ) -> Result<Vec<GeminiCliProvider>, String> {
     let mut providers = list_gemini_providers_from_sqlite(db)?;
-    if providers.is_empty() {
+    let import_config_files = should_import_config_files(db, "geminicli");
+    if providers.is_empty() && import_config_files {
         import_gemini_cli_default_provider_from_local_files(db, true).await?;
         providers = list_gemini_providers_from_sqlite(db)?;
     }
-    if providers.is_empty() {
+    if providers.is_empty() && import_config_files {
         if let Ok(temp_provider) = load_temp_provider_from_files_with_db(db).await {
             return Ok(vec![temp_provider]);
         }
@@ -1360,7 +1364,7 @@ pub async fn update_gemini_cli_provider(
 
     put_gemini_provider_to_sqlite(db, &id, &content)?;
 
-    if content.is_applied {
+    if content.is_applied && should_write_config_files_on_save(db, "geminicli") {
         if let Err(error) = apply_config_to_file(&db, &id).await {
             eprintln!("Failed to auto-apply Gemini CLI provider: {}", error);
         } else {
@@ -1398,6 +1402,11 @@ pub async fn delete_gemini_cli_provider(
     let db = state.db();
     super::official_accounts::ensure_gemini_cli_provider_has_no_official_accounts(&db, &id).await?;
     db.with_conn(|conn| db_delete(conn, DbTable::GeminiCliProvider, &id).map(|_| ()))?;
+    crate::coding::proxy_gateway::clear_fallback_provider_after_delete(
+        &app,
+        GatewayCliKey::Gemini,
+        &id,
+    );
     let _ = app.emit("config-changed", "window");
     Ok(())
 }
@@ -1519,7 +1528,7 @@ async fn apply_config_internal_with_events<R: tauri::Runtime>(
         let _ = app.emit("config-changed", payload);
     }
     if emit_sync_request {
-        emit_sync_requests(app);
+        crate::coding::wsl::emit_apply_sync_request(db, app, "geminicli");
     }
     Ok(())
 }
@@ -1630,7 +1639,8 @@ pub async fn save_gemini_cli_common_config(
         })?
         .into_iter()
         .next()
-        .map(adapter::from_db_value_provider);
+        .map(adapter::from_db_value_provider)
+        .filter(|_| should_write_config_files_on_save(db, "geminicli"));
     if let Some(provider) = applied_provider {
         if apply_config_to_file(&db, &provider.id).await.is_ok() {
             if let Err(error) = rewrite_applied_prompt_to_current_file(&db).await {
@@ -554,7 +554,7 @@ async fn write_oauth_creds_to_disk(
     let runtime_creds = runtime_oauth_creds_from_auth(auth);
     let content = serde_json::to_string_pretty(&runtime_creds)
         .map_err(|error| format!("Failed to serialize oauth_creds.json: {error}"))?;
-    fs::write(auth_path, format!("{content}\n"))
+    crate::coding::atomic_write::write_atomic(auth_path, format!("{content}\n"))
         .map_err(|error| format!("Failed to write Gemini CLI oauth_creds.json: {error}"))
 }
 
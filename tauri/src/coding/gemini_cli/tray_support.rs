@@ -54,17 +54,14 @@ pub async fn get_gemini_cli_tray_data<R: Runtime>(
             let name = record.get("name").and_then(Value::as_str)?;
             let is_applied = record
                 .get("is_applied")
-                .or_else(|| record.get("isApplied"))
                 .and_then(Value::as_bool)
                 .unwrap_or(false);
             let sort_index = record
                 .get("sort_index")
-                .or_else(|| record.get("sortIndex"))
                 .and_then(Value::as_i64)
                 .unwrap_or(0);
             let is_disabled = record
                 .get("is_disabled")
-                .or_else(|| record.get("isDisabled"))
                 .and_then(Value::as_bool)
                 .unwrap_or(false);
             let category = record
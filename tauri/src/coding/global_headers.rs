@@ -0,0 +1,309 @@
+//! Tool-level request headers shared by every provider of Claude Code or OpenCode.
+//!
+//! Headers live in a `global_headers` record of the tool's common config table, next to the
+//! `common` record so saving the common config never clobbers them. On apply they are merged
+//! into the written runtime config with provider-level headers taking precedence. The record
+//! also remembers what was last applied, so headers that were removed or changed globally are
+//! stripped from the runtime config instead of lingering as if they were provider-level.
+
+use serde_json::{json, Map, Value};
+use tauri::{Emitter, State};
+
+use crate::db::helpers::{db_get, db_put};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+const GLOBAL_HEADERS_RECORD_ID: &str = "global_headers";
+/// Claude Code reads extra request headers from this env var, one `Name: Value` per line.
+pub(crate) const CLAUDE_CUSTOM_HEADERS_ENV: &str = "ANTHROPIC_CUSTOM_HEADERS";
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GlobalHeaders {
+    pub headers: Map<String, Value>,
+    pub applied_headers: Map<String, Value>,
+}
+
+fn common_config_table(tool: &str) -> Result<DbTable, String> {
+    match tool {
+        "claude" => Ok(DbTable::ClaudeCommonConfig),
+        "opencode" => Ok(DbTable::OpenCodeCommonConfig),
+        other => Err(format!("Global headers are not supported for '{}'", other)),
+    }
+}
+
+fn string_headers(value: Option<&Value>) -> Map<String, Value> {
+    value
+        .and_then(Value::as_object)
+        .map(|object| {
+            object
+                .iter()
+                .filter(|(_, value)| value.is_string())
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Load the tool's global headers; None when the feature has never been configured.
+pub(crate) fn load_global_headers(
+    db: &SqliteDbState,
+    tool: &str,
+) -> Result<Option<GlobalHeaders>, String> {
+    let table = common_config_table(tool)?;
+    let record = db.with_conn(|conn| db_get(conn, table, GLOBAL_HEADERS_RECORD_ID))?;
+    Ok(record.map(|record| GlobalHeaders {
+        headers: string_headers(record.get("headers")),
+        applied_headers: string_headers(record.get("applied_headers")),
+    }))
+}
+
+fn save_global_headers(
+    db: &SqliteDbState,
+    tool: &str,
+    value: &GlobalHeaders,
+) -> Result<(), String> {
+    let table = common_config_table(tool)?;
+    let record = json!({
+        "headers": value.headers,
+        "applied_headers": value.applied_headers,
+        "updated_at": chrono::Local::now().to_rfc3339(),
+    });
+    db.with_conn(|conn| db_put(conn, table, GLOBAL_HEADERS_RECORD_ID, &record))
+}
+
+/// Remember `written` as the global headers of the latest apply. Call only after the config
+/// carrying them was written, so a failed write keeps the previous applied set.
+pub(crate) fn mark_global_headers_applied(
+    db: &SqliteDbState,
+    tool: &str,
+    written: &GlobalHeaders,
+) -> Result<(), String> {
+    let Some(mut global) = load_global_headers(db, tool)? else {
+        return Ok(());
+    };
+    if global.applied_headers == written.headers {
+        return Ok(());
+    }
+    global.applied_headers = written.headers.clone();
+    save_global_headers(db, tool, &global)
+}
+
+/// Global headers overlaid by the existing headers, minus entries left by a previous apply.
+pub(crate) fn merge_global_headers(
+    existing: &Map<String, Value>,
+    global: &GlobalHeaders,
+) -> Map<String, Value> {
+    let mut merged = global.headers.clone();
+    for (key, value) in existing {
+        if global.applied_headers.get(key) == Some(value) {
+            continue;
+        }
+        merged.insert(key.clone(), value.clone());
+    }
+    merged
+}
+
+/// Global headers overlaid by request headers, for model fetches and connectivity tests.
+pub(crate) fn request_headers_with_global(
+    db: &SqliteDbState,
+    tool: Option<&str>,
+    request_headers: Option<&Value>,
+) -> Result<Option<Value>, String> {
+    let Some(tool) = tool else {
+        return Ok(request_headers.cloned());
+    };
+    let Some(global) = load_global_headers(db, tool)? else {
+        return Ok(request_headers.cloned());
+    };
+    let mut merged = global.headers;
+    if let Some(object) = request_headers.and_then(Value::as_object) {
+        merged.extend(object.clone());
+    }
+    Ok((!merged.is_empty()).then_some(Value::Object(merged)))
+}
+
+/// Parse Claude's `ANTHROPIC_CUSTOM_HEADERS` (`Name: Value` per line).
+pub(crate) fn parse_claude_custom_headers(raw: &str) -> Map<String, Value> {
+    raw.lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, value)| (name.to_string(), Value::String(value.to_string())))
+        .collect()
+}
+
+pub(crate) fn format_claude_custom_headers(headers: &Map<String, Value>) -> String {
+    headers
+        .iter()
+        .filter_map(|(name, value)| value.as_str().map(|value| format!("{}: {}", name, value)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Apply global headers to merged Claude settings via `env.ANTHROPIC_CUSTOM_HEADERS`.
+pub(crate) fn apply_claude_global_headers(settings: &mut Value, global: &GlobalHeaders) {
+    let Some(settings_object) = settings.as_object_mut() else {
+        return;
+    };
+    let env = settings_object
+        .entry("env")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let Some(env_object) = env.as_object_mut() else {
+        return;
+    };
+    let existing = env_object
+        .get(CLAUDE_CUSTOM_HEADERS_ENV)
+        .and_then(Value::as_str)
+        .map(parse_claude_custom_headers)
+        .unwrap_or_default();
+    let merged = merge_global_headers(&existing, global);
+    if merged.is_empty() {
+        env_object.remove(CLAUDE_CUSTOM_HEADERS_ENV);
+    } else {
+        env_object.insert(
+            CLAUDE_CUSTOM_HEADERS_ENV.to_string(),
+            Value::String(format_claude_custom_headers(&merged)),
+        );
+    }
+}
+
+/// Apply global headers to every provider's `options.headers` in an OpenCode config value.
+pub(crate) fn apply_opencode_global_headers(config: &mut Value, global: &GlobalHeaders) {
+    let Some(providers) = config.get_mut("provider").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for provider in providers.values_mut() {
+        let Some(provider_object) = provider.as_object_mut() else {
+            continue;
+        };
+        let options = provider_object
+            .entry("options")
+            .or_insert_with(|| Value::Object(Map::new()));
+        let Some(options_object) = options.as_object_mut() else {
+            continue;
+        };
+        let existing = options_object
+            .get("headers")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let merged = merge_global_headers(&existing, global);
+        if merged.is_empty() {
+            options_object.remove("headers");
+        } else {
+            options_object.insert("headers".to_string(), Value::Object(merged));
+        }
+        if options_object.is_empty() {
+            provider_object.remove("options");
+        }
+    }
+}
+
+/// Get the request headers applied to every provider of `tool` ("claude" or "opencode").
+#[tauri::command]
+pub async fn get_global_headers(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+) -> Result<Map<String, Value>, String> {
+    Ok(load_global_headers(&state, &tool)?
+        .map(|global| global.headers)
+        .unwrap_or_default())
+}
+
+/// Set the request headers applied to every provider of `tool`. Takes effect on the next apply.
+#[tauri::command]
+pub async fn set_global_headers(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    tool: String,
+    headers: Map<String, Value>,
+) -> Result<(), String> {
+    let mut normalized = Map::new();
+    for (name, value) in headers {
+        let name = name.trim();
+        let Some(value) = value.as_str() else {
+            return Err(format!("Header '{}' must have a string value", name));
+        };
+        if name.is_empty() || name.contains([':', '\n', '\r']) || value.contains(['\n', '\r']) {
+            return Err(format!("Invalid header '{}'", name));
+        }
+        normalized.insert(name.to_string(), Value::String(value.trim().to_string()));
+    }
+
+    let mut global = load_global_headers(&state, &tool)?.unwrap_or_default();
+    global.headers = normalized;
+    save_global_headers(&state, &tool, &global)?;
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Map<String, Value> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), Value::String(value.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn provider_headers_override_global_and_stale_global_is_dropped() {
+        let global = GlobalHeaders {
+            headers: headers(&[("X-Org", "new-org"), ("X-Team", "core")]),
+            applied_headers: headers(&[("X-Org", "old-org"), ("X-Removed", "1")]),
+        };
+        let existing = headers(&[
+            ("X-Org", "old-org"),
+            ("X-Removed", "1"),
+            ("X-Team", "provider-team"),
+        ]);
+
+        let merged = merge_global_headers(&existing, &global);
+        assert_eq!(
+            merged,
+            headers(&[("X-Org", "new-org"), ("X-Team", "provider-team")])
+        );
+    }
+
+    #[test]
+    fn claude_headers_round_trip_through_env() {
+        let global = GlobalHeaders {
+            headers: headers(&[("X-Org", "acme")]),
+            applied_headers: Map::new(),
+        };
+        let mut settings = json!({ "env": { "ANTHROPIC_CUSTOM_HEADERS": "X-Trace: on" } });
+        apply_claude_global_headers(&mut settings, &global);
+
+        let raw = settings["env"][CLAUDE_CUSTOM_HEADERS_ENV].as_str().unwrap();
+        assert_eq!(
+            parse_claude_custom_headers(raw),
+            headers(&[("X-Org", "acme"), ("X-Trace", "on")])
+        );
+    }
+
+    #[test]
+    fn opencode_headers_are_merged_per_provider() {
+        let global = GlobalHeaders {
+            headers: headers(&[("X-Org", "acme")]),
+            applied_headers: Map::new(),
+        };
+        let mut config = json!({
+            "provider": {
+                "a": { "options": { "headers": { "X-Org": "own" } } },
+                "b": { "npm": "@ai-sdk/openai-compatible" }
+            }
+        });
+        apply_opencode_global_headers(&mut config, &global);
+
+        assert_eq!(
+            config["provider"]["a"]["options"]["headers"]["X-Org"],
+            "own"
+        );
+        assert_eq!(
+            config["provider"]["b"]["options"]["headers"]["X-Org"],
+            "acme"
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Read-only search across providers, MCP servers and skills for the command palette.
+//!
+//! `search_all` matches the query case-insensitively against provider names, notes and base
+//! URLs (Claude Code, Codex, OpenCode favorites), MCP server names, tags and stdio commands,
+//! and managed skill names. Results are capped per kind so a broad query stays cheap to render.
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use crate::coding::mcp::config_sync::stdio_command;
+use crate::coding::model_validation::provider_config_base_url;
+use crate::coding::{claude_code, codex, db_extract_id, mcp, open_code, skills};
+use crate::db::helpers::db_list;
+use crate::db::schema::{DbTable, OrderSpec};
+use crate::db::SqliteDbState;
+
+const MAX_RESULTS_PER_KIND: usize = 20;
+
+/// One entity matching the search query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    /// "claude_provider" | "codex_provider" | "opencode_provider" | "mcp_server" | "skill"
+    pub kind: String,
+    /// Record id; the config key for OpenCode providers
+    pub id: String,
+    pub name: String,
+    /// "name" | "id" | "notes" | "baseUrl" | "tag" | "command"
+    pub matched_field: String,
+    /// The matched value, for fields other than the name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_text: Option<String>,
+}
+
+/// First field whose value contains `needle` (already lowercased), in the given order.
+fn first_match(needle: &str, fields: &[(&str, Option<&str>)]) -> Option<(String, String)> {
+    fields.iter().find_map(|(field, value)| {
+        let value = (*value)?;
+        value
+            .to_lowercase()
+            .contains(needle)
+            .then(|| (field.to_string(), value.to_string()))
+    })
+}
+
+/// Collects matches of one kind, up to `MAX_RESULTS_PER_KIND`.
+struct KindResults<'a> {
+    kind: &'static str,
+    needle: &'a str,
+    results: Vec<SearchResult>,
+}
+
+impl<'a> KindResults<'a> {
+    fn new(kind: &'static str, needle: &'a str) -> Self {
+        Self {
+            kind,
+            needle,
+            results: Vec::new(),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.results.len() >= MAX_RESULTS_PER_KIND
+    }
+
+    fn check(&mut self, id: &str, name: &str, fields: &[(&str, Option<&str>)]) {
+        if self.is_full() {
+            return;
+        }
+        let name_field = [("name", Some(name))];
+        let Some((matched_field, matched_text)) =
+            first_match(self.needle, &name_field).or_else(|| first_match(self.needle, fields))
+        else {
+            return;
+        };
+        self.results.push(SearchResult {
+            kind: self.kind.to_string(),
+            id: id.to_string(),
+            name: name.to_string(),
+            matched_text: (matched_field != "name").then_some(matched_text),
+            matched_field,
+        });
+    }
+}
+
+/// Match Claude Code or Codex provider rows as stored, without going through the adapters:
+/// those decrypt sealed secrets, while the base URL and notes are never sealed.
+fn search_provider_rows(
+    db: &SqliteDbState,
+    table: DbTable,
+    order: OrderSpec,
+    tool: &str,
+    mut matches: KindResults<'_>,
+) -> Result<Vec<SearchResult>, String> {
+    let rows = db.with_conn(|conn| db_list(conn, table, Some(&order)))?;
+    for row in rows {
+        let name = row.get("name").and_then(Value::as_str).unwrap_or_default();
+        let notes = row.get("notes").and_then(Value::as_str);
+        let base_url = row
+            .get("settings_config")
+            .and_then(Value::as_str)
+            .and_then(|settings_config| provider_config_base_url(tool, settings_config));
+        matches.check(
+            &db_extract_id(&row),
+            name,
+            &[("notes", notes), ("baseUrl", base_url.as_deref())],
+        );
+    }
+    Ok(matches.results)
+}
+
+/// Search providers, MCP servers and skills; an empty query returns nothing.
+#[tauri::command]
+pub async fn search_all(
+    state: State<'_, SqliteDbState>,
+    query: String,
+) -> Result<Vec<SearchResult>, String> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Ok(Vec::new());
+    }
+    let db = state.db();
+    let mut results = Vec::new();
+
+    results.extend(search_provider_rows(
+        db,
+        DbTable::ClaudeProvider,
+        claude_code::commands::claude_provider_order()?,
+        "claude",
+        KindResults::new("claude_provider", &needle),
+    )?);
+    results.extend(search_provider_rows(
+        db,
+        DbTable::CodexProvider,
+        codex::commands::codex_provider_order()?,
+        "codex",
+        KindResults::new("codex_provider", &needle),
+    )?);
+
+    let mut opencode = KindResults::new("opencode_provider", &needle);
+    for favorite in open_code::list_opencode_favorite_providers(state.clone()).await? {
+        let name = favorite
+            .provider_config
+            .name
+            .clone()
+            .unwrap_or_else(|| favorite.provider_id.clone());
+        opencode.check(
+            &favorite.provider_id,
+            &name,
+            &[
+                ("id", Some(favorite.provider_id.as_str())),
+                ("baseUrl", Some(favorite.base_url.as_str())),
+            ],
+        );
+    }
+    results.extend(opencode.results);
+
+    let mut mcp_servers = KindResults::new("mcp_server", &needle);
+    for server in mcp::mcp_store::get_mcp_servers(db).await? {
+        let mut fields: Vec<(&str, Option<&str>)> = server
+            .tags
+            .iter()
+            .map(|tag| ("tag", Some(tag.as_str())))
+            .collect();
+        fields.push(("command", stdio_command(&server)));
+        mcp_servers.check(&server.id, &server.name, &fields);
+    }
+    results.extend(mcp_servers.results);
+
+    let mut skill_results = KindResults::new("skill", &needle);
+    for skill in skills::skill_store::sqlite_get_managed_skills(db)? {
+        skill_results.check(&skill.id, &skill.name, &[]);
+    }
+    results.extend(skill_results.results);
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_name_first_and_caps_each_kind() {
+        let mut results = KindResults::new("mcp_server", "git");
+        results.check(
+            "1",
+            "GitHub",
+            &[("tag", Some("git")), ("command", Some("npx"))],
+        );
+        results.check(
+            "2",
+            "Files",
+            &[
+                ("tag", Some("local")),
+                ("command", Some("/usr/bin/GIT-mcp")),
+            ],
+        );
+        results.check("3", "Memory", &[("tag", Some("notes")), ("command", None)]);
+        assert_eq!(
+            results.results,
+            vec![
+                SearchResult {
+                    kind: "mcp_server".to_string(),
+                    id: "1".to_string(),
+                    name: "GitHub".to_string(),
+                    matched_field: "name".to_string(),
+                    matched_text: None,
+                },
+                SearchResult {
+                    kind: "mcp_server".to_string(),
+                    id: "2".to_string(),
+                    name: "Files".to_string(),
+                    matched_field: "command".to_string(),
+                    matched_text: Some("/usr/bin/GIT-mcp".to_string()),
+                },
+            ]
+        );
+
+        let mut capped = KindResults::new("skill", "skill");
+        for index in 0..MAX_RESULTS_PER_KIND + 5 {
+            capped.check(&index.to_string(), &format!("skill-{}", index), &[]);
+        }
+        assert_eq!(capped.results.len(), MAX_RESULTS_PER_KIND);
+    }
+}
@@ -399,6 +399,11 @@ pub async fn delete_grok_provider(
     state
         .db()
         .with_conn(|conn| db_delete(conn, DbTable::GrokProvider, &id).map(|_| ()))?;
+    crate::coding::proxy_gateway::clear_fallback_provider_after_delete(
+        &app,
+        crate::coding::proxy_gateway::types::GatewayCliKey::Grok,
+        &id,
+    );
     let _ = app.emit("config-changed", "window");
     Ok(())
 }
@@ -494,7 +499,7 @@ pub async fn select_grok_provider_internal_with_sync<R: tauri::Runtime>(
     }
     if emit_events {
         let _ = app.emit("config-changed", if from_tray { "tray" } else { "window" });
-        emit_grok_sync(app);
+        crate::coding::wsl::emit_apply_sync_request(state, app, "grok");
     }
     Ok(())
 }
@@ -1294,7 +1299,7 @@ fn get_prompt(db: &SqliteDbState, id: &str) -> Result<Option<GrokPromptConfig>,
         .map(|value| value.map(adapter::prompt_from_db_value))
 }
 
-fn provider_order() -> Result<OrderSpec, String> {
+pub(crate) fn provider_order() -> Result<OrderSpec, String> {
     Ok(OrderSpec::new(vec![
         OrderField::json_integer("sort_index", OrderDirection::Asc)?,
         OrderField::created_at(OrderDirection::Asc),
@@ -0,0 +1,632 @@
+//! Comment-preserving rewrites of JSONC config files.
+//!
+//! `serde_json` drops comments, so writing a `.jsonc` file from a `Value` loses everything
+//! the user annotated. `update_jsonc_text` parses the existing text into object member spans
+//! and only edits what changed: unchanged members keep their text and comments, changed
+//! scalars and arrays are replaced in place, nested objects are diffed recursively, removed
+//! members are cut out together with their leading comments, and new members are appended
+//! at the end of their object. Objects written on one line stay on one line, and inserted
+//! lines use the file's line endings. Comments inside a replaced value are lost.
+
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+const DEFAULT_INDENT_UNIT: &str = "  ";
+
+struct Member {
+    key: String,
+    /// Start of the member's leading trivia (the lines of comments above its key)
+    start: usize,
+    key_start: usize,
+    value: Node,
+    /// Offset of the comma after the value
+    comma: Option<usize>,
+    /// End of the member: past the comma, a same-line comment and the line break
+    end: usize,
+}
+
+enum Node {
+    Object {
+        start: usize,
+        end: usize,
+        members: Vec<Member>,
+    },
+    Other {
+        start: usize,
+        end: usize,
+    },
+}
+
+impl Node {
+    fn span(&self) -> (usize, usize) {
+        match self {
+            Node::Object { start, end, .. } | Node::Other { start, end } => (*start, *end),
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    has_comments: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: text
+                .strip_prefix('\u{feff}')
+                .map_or(0, |_| '\u{feff}'.len_utf8()),
+            has_comments: false,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("Invalid JSONC at byte {}: {}", self.pos, message)
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), String> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.has_comments = true;
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(body) = trimmed.strip_prefix("/*") {
+                self.has_comments = true;
+                let close = body
+                    .find("*/")
+                    .ok_or_else(|| self.error("unterminated block comment"))?;
+                self.pos += close + 4;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Node, String> {
+        self.skip_trivia()?;
+        let start = self.pos;
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(quote @ (b'"' | b'\'')) => {
+                self.parse_string(quote)?;
+                Ok(Node::Other {
+                    start,
+                    end: self.pos,
+                })
+            }
+            Some(_) => {
+                let len = self
+                    .rest()
+                    .find(|c: char| c.is_whitespace() || ",:]}/".contains(c))
+                    .unwrap_or(self.rest().len());
+                if len == 0 {
+                    return Err(self.error("expected a value"));
+                }
+                self.pos += len;
+                Ok(Node::Other {
+                    start,
+                    end: self.pos,
+                })
+            }
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn parse_string(&mut self, quote: u8) -> Result<(), String> {
+        let bytes = self.text.as_bytes();
+        let mut pos = self.pos + 1;
+        while pos < bytes.len() {
+            match bytes[pos] {
+                b'\\' => pos += 2,
+                byte if byte == quote => {
+                    self.pos = pos + 1;
+                    return Ok(());
+                }
+                _ => pos += 1,
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn parse_array(&mut self) -> Result<Node, String> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Node::Other {
+                        start,
+                        end: self.pos,
+                    });
+                }
+                Some(b',') => self.pos += 1,
+                Some(_) => {
+                    self.parse_value()?;
+                }
+                None => return Err(self.error("unterminated array")),
+            }
+        }
+    }
+
+    fn parse_key(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        match self.peek() {
+            Some(quote @ (b'"' | b'\'')) => {
+                self.parse_string(quote)?;
+                json5::from_str::<String>(&self.text[start..self.pos])
+                    .map_err(|e| format!("Invalid JSONC key at byte {}: {}", start, e))
+            }
+            _ => {
+                let len = self
+                    .rest()
+                    .find(|c: char| c.is_whitespace() || c == ':' || c == '/')
+                    .unwrap_or(self.rest().len());
+                if len == 0 {
+                    return Err(self.error("expected an object key"));
+                }
+                self.pos += len;
+                Ok(self.text[start..self.pos].to_string())
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Node, String> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut members = Vec::new();
+        let mut boundary = line_end(self.text, self.pos);
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Node::Object {
+                        start,
+                        end: self.pos,
+                        members,
+                    });
+                }
+                None => return Err(self.error("unterminated object")),
+                Some(_) => {}
+            }
+
+            let key_start = self.pos;
+            let key = self.parse_key()?;
+            self.skip_trivia()?;
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected ':' after object key"));
+            }
+            self.pos += 1;
+            let value = self.parse_value()?;
+            let value_end = self.pos;
+            self.skip_trivia()?;
+            let comma = (self.peek() == Some(b',')).then_some(self.pos);
+            let end = match comma {
+                Some(comma) => {
+                    self.pos += 1;
+                    line_end(self.text, comma + 1)
+                }
+                None => {
+                    self.pos = value_end;
+                    line_end(self.text, value_end)
+                }
+            };
+            members.push(Member {
+                key,
+                start: boundary.min(key_start),
+                key_start,
+                value,
+                comma,
+                end,
+            });
+            boundary = end;
+        }
+    }
+}
+
+/// Past the line break when only whitespace and comments follow `pos` on its line;
+/// `pos` itself when more content shares the line.
+fn line_end(text: &str, pos: usize) -> usize {
+    let mut cursor = pos;
+    loop {
+        let rest = &text[cursor..];
+        let trimmed = rest.trim_start_matches([' ', '\t']);
+        cursor += rest.len() - trimmed.len();
+        if trimmed.starts_with("//") {
+            cursor += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else if let Some(body) = trimmed.strip_prefix("/*") {
+            match body.find("*/") {
+                Some(close) if !body[..close].contains('\n') => cursor += close + 4,
+                _ => return pos,
+            }
+        } else if trimmed.starts_with("\r\n") {
+            return cursor + 2;
+        } else if trimmed.starts_with('\n') {
+            return cursor + 1;
+        } else if trimmed.is_empty() {
+            return cursor;
+        } else {
+            return pos;
+        }
+    }
+}
+
+/// Leading whitespace of the line containing `pos`.
+fn line_indent(text: &str, pos: usize) -> &str {
+    let line_start = text[..pos].rfind('\n').map_or(0, |index| index + 1);
+    let line = &text[line_start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Position of the line break that ends a member, so appended members go after its comment.
+fn insertion_point(text: &str, member: &Member) -> usize {
+    let before = &text[..member.end];
+    if before.ends_with("\r\n") {
+        member.end - 2
+    } else if before.ends_with('\n') {
+        member.end - 1
+    } else {
+        member.end
+    }
+}
+
+fn render_value(value: &Value, indent: &str, unit: &str, newline: &str) -> Result<String, String> {
+    let mut buffer = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+    value
+        .serialize(&mut serializer)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let rendered =
+        String::from_utf8(buffer).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    Ok(rendered.replace('\n', &format!("{}{}", newline, indent)))
+}
+
+/// How values are written into one object: pretty-printed with its indentation, or compact
+/// for an object that fits on one line.
+struct Layout<'a> {
+    inline: bool,
+    unit: &'a str,
+    newline: &'a str,
+}
+
+impl Layout<'_> {
+    fn value(&self, value: &Value, indent: &str) -> Result<String, String> {
+        if self.inline {
+            serde_json::to_string(value).map_err(|e| format!("Failed to serialize config: {}", e))
+        } else {
+            render_value(value, indent, self.unit, self.newline)
+        }
+    }
+
+    /// A new member including what separates it from the previous one
+    fn member(&self, key: &str, value: &Value, indent: &str) -> Result<String, String> {
+        let member = format!(
+            "{}: {}",
+            Value::String(key.to_string()),
+            self.value(value, indent)?
+        );
+        Ok(if self.inline {
+            format!(" {}", member)
+        } else {
+            format!("{}{}{}", self.newline, indent, member)
+        })
+    }
+}
+
+type Edit = (usize, usize, String);
+
+fn edit_object(
+    text: &str,
+    node: &Node,
+    next: &serde_json::Map<String, Value>,
+    newline: &str,
+    edits: &mut Vec<Edit>,
+) -> Result<(), String> {
+    let Node::Object {
+        start,
+        end,
+        members,
+    } = node
+    else {
+        return Err("Expected a JSONC object".to_string());
+    };
+    let object_indent = line_indent(text, *start);
+    let member_indent = members
+        .first()
+        .map(|member| line_indent(text, member.key_start))
+        .filter(|indent| indent.len() > object_indent.len())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}{}", object_indent, DEFAULT_INDENT_UNIT));
+    let unit = member_indent
+        .strip_prefix(object_indent)
+        .filter(|unit| !unit.is_empty())
+        .unwrap_or(DEFAULT_INDENT_UNIT)
+        .to_string();
+    let layout = Layout {
+        inline: !text[*start..*end].contains('\n'),
+        unit: &unit,
+        newline,
+    };
+
+    let kept: Vec<&Member> = members
+        .iter()
+        .filter(|member| next.contains_key(&member.key))
+        .collect();
+    let appended: Vec<(&String, &Value)> = next
+        .iter()
+        .filter(|(key, _)| !members.iter().any(|member| &member.key == *key))
+        .collect();
+
+    let Some(last_kept) = kept.last() else {
+        // Nothing survives: rebuild the object body
+        let body = if appended.is_empty() {
+            String::new()
+        } else {
+            let lines = appended
+                .iter()
+                .map(|(key, value)| layout.member(key, value, &member_indent))
+                .collect::<Result<Vec<_>, _>>()?;
+            if layout.inline {
+                format!("{} ", lines.join(","))
+            } else {
+                format!("{}{}{}", lines.join(","), newline, object_indent)
+            }
+        };
+        edits.push((start + 1, end - 1, body));
+        return Ok(());
+    };
+
+    for member in members {
+        let Some(next_value) = next.get(&member.key) else {
+            edits.push((member.start, member.end, String::new()));
+            continue;
+        };
+        let (value_start, value_end) = member.value.span();
+        if let (Node::Object { .. }, Value::Object(next_object)) = (&member.value, next_value) {
+            edit_object(text, &member.value, next_object, newline, edits)?;
+            continue;
+        }
+        let current = json5::from_str::<Value>(&text[value_start..value_end]).ok();
+        if current.as_ref() != Some(next_value) {
+            let indent = line_indent(text, member.key_start);
+            edits.push((value_start, value_end, layout.value(next_value, indent)?));
+        }
+    }
+
+    let trailing_comma = members.last().is_some_and(|member| member.comma.is_some());
+    if appended.is_empty() {
+        if let Some(comma) = last_kept.comma.filter(|_| !trailing_comma) {
+            edits.push((comma, comma + 1, String::new()));
+        }
+        return Ok(());
+    }
+
+    let lines = appended
+        .iter()
+        .map(|(key, value)| layout.member(key, value, &member_indent))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut inserted = lines.join(",");
+    if trailing_comma {
+        inserted.push(',');
+    }
+    let position = insertion_point(text, last_kept);
+    if last_kept.comma.is_none() {
+        let value_end = last_kept.value.span().1;
+        // Same offset as the insertion (nothing but the object's end follows the value)
+        if position == value_end {
+            inserted.insert(0, ',');
+        } else {
+            edits.push((value_end, value_end, ",".to_string()));
+        }
+    }
+    edits.push((position, position, inserted));
+    Ok(())
+}
+
+fn parse_root(text: &str) -> Result<(Node, bool), String> {
+    let mut parser = Parser::new(text);
+    let root = parser.parse_value()?;
+    parser.skip_trivia()?;
+    if parser.pos != text.len() {
+        return Err(parser.error("unexpected content after the root value"));
+    }
+    Ok((root, parser.has_comments))
+}
+
+/// Rewrite `existing` JSONC text so it holds `next`, keeping comments and the formatting of
+/// everything that did not change. Both must be objects at the top level.
+pub(crate) fn update_jsonc_text(existing: &str, next: &Value) -> Result<String, String> {
+    let next_object = next
+        .as_object()
+        .ok_or_else(|| "JSONC config must be a JSON object".to_string())?;
+    let (root, _) = parse_root(existing)?;
+    let newline = if existing.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut edits = Vec::new();
+    edit_object(existing, &root, next_object, newline, &mut edits)?;
+
+    // Apply back to front; at equal offsets the removal goes first so an insertion at the
+    // start of a removed member is not cut out with it
+    edits.sort_by(|left, right| right.0.cmp(&left.0).then(right.1.cmp(&left.1)));
+    let mut updated = existing.to_string();
+    for (start, end, replacement) in edits {
+        updated.replace_range(start..end, &replacement);
+    }
+    Ok(updated)
+}
+
+/// Content for writing `next` to `path`. When the current file contains comments they are
+/// kept via `update_jsonc_text`; missing, unparsable or comment-free files get plain pretty
+/// JSON as before.
+pub(crate) fn render_config_for_path(path: &Path, next: &Value) -> Result<String, String> {
+    let pretty = || {
+        serde_json::to_string_pretty(next).map_err(|e| format!("Failed to serialize config: {}", e))
+    };
+    let Ok(existing) = std::fs::read_to_string(path) else {
+        return pretty();
+    };
+    let has_comments = matches!(parse_root(&existing), Ok((Node::Object { .. }, true)));
+    if !has_comments || !next.is_object() {
+        return pretty();
+    }
+    update_jsonc_text(&existing, next).or_else(|error| {
+        log::warn!(
+            "Rewriting {} without its comments: {}",
+            path.display(),
+            error
+        );
+        pretty()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn update_keeps_comments_and_only_touches_changed_members() {
+        let existing = r#"{
+  // Schema for editor completion
+  "$schema": "https://example.com/schema.json",
+  "agents": {
+    /* main agent */
+    "sisyphus": { "model": "old-model" }, // keep me
+    "oracle": { "model": "o3" },
+  },
+  // dropped by the profile
+  "disabled_hooks": ["a"],
+}
+"#;
+        let next = json!({
+            "$schema": "https://example.com/schema.json",
+            "agents": {
+                "sisyphus": { "model": "new-model" },
+                "oracle": { "model": "o3" },
+                "librarian": { "model": "glm" }
+            }
+        });
+
+        let updated = update_jsonc_text(existing, &next).expect("update");
+
+        assert_eq!(
+            updated,
+            r#"{
+  // Schema for editor completion
+  "$schema": "https://example.com/schema.json",
+  "agents": {
+    /* main agent */
+    "sisyphus": { "model": "new-model" }, // keep me
+    "oracle": { "model": "o3" },
+    "librarian": {
+      "model": "glm"
+    },
+  },
+}
+"#
+        );
+        assert_eq!(json5::from_str::<Value>(&updated).expect("valid"), next);
+
+        let no_trailing = "{\n  \"a\": 1, // one\n  \"b\": 2\n}";
+        let updated = update_jsonc_text(no_trailing, &json!({ "a": 3 })).expect("update");
+        assert_eq!(updated, "{\n  \"a\": 3 // one\n}");
+        assert!(update_jsonc_text("{ \"a\": ", &json!({})).is_err());
+    }
+
+    fn assert_update(existing: &str, next: Value, expected: &str) {
+        let updated = update_jsonc_text(existing, &next).expect("update");
+        assert_eq!(updated, expected);
+        assert_eq!(json5::from_str::<Value>(&updated).expect("valid"), next);
+    }
+
+    #[test]
+    fn update_keeps_single_line_objects_on_one_line() {
+        assert_update(
+            "{ \"a\": 1 } // tail",
+            json!({ "a": 1, "b": [2] }),
+            "{ \"a\": 1, \"b\": [2] } // tail",
+        );
+        assert_update(
+            "{ /* head */ \"a\": 1, \"b\": 2 }",
+            json!({ "a": 3 }),
+            "{ /* head */ \"a\": 3 }",
+        );
+        assert_update("{ \"a\": 1, \"b\": 2 }", json!({ "b": 2 }), "{ \"b\": 2 }");
+        assert_update("{ \"a\": 1 }", json!({ "c": 1 }), "{ \"c\": 1 }");
+    }
+
+    #[test]
+    fn update_removes_last_member_and_keeps_comma_style() {
+        // Trailing comma stays on the new last member, the removed member's comment goes
+        assert_update(
+            "{\n  \"a\": 1,\n  // about b\n  \"b\": 2,\n}\n",
+            json!({ "a": 1 }),
+            "{\n  \"a\": 1,\n}\n",
+        );
+        // Without a trailing comma the separator is dropped, same-line comments stay
+        assert_update(
+            "{\n  \"a\": 1, // one\n  // about b\n  \"b\": 2 // two\n}\n",
+            json!({ "a": 1 }),
+            "{\n  \"a\": 1 // one\n}\n",
+        );
+        assert_update("{\n  // only\n  \"a\": 1\n}\n", json!({}), "{}\n");
+    }
+
+    #[test]
+    fn update_keeps_crlf_line_endings() {
+        assert_update(
+            "{\r\n  // keep\r\n  \"a\": 1,\r\n  \"b\": 2\r\n}\r\n",
+            json!({ "a": 5, "c": { "d": 1 } }),
+            "{\r\n  // keep\r\n  \"a\": 5,\r\n  \"c\": {\r\n    \"d\": 1\r\n  }\r\n}\r\n",
+        );
+        assert_update(
+            "{\r\n  \"a\": 1,\r\n  // b note\r\n  \"b\": 2,\r\n  \"c\": 3\r\n}\r\n",
+            json!({ "a": 1, "c": 3 }),
+            "{\r\n  \"a\": 1,\r\n  \"c\": 3\r\n}\r\n",
+        );
+    }
+
+    #[test]
+    fn update_removes_nested_members() {
+        assert_update(
+            "{\n  \"agents\": {\n    // first\n    \"x\": { \"model\": \"m\" },\n    // second\n    \"y\": { \"model\": \"n\" }\n  },\n  \"z\": 1\n}\n",
+            json!({ "agents": { "x": { "model": "m" } }, "z": 1 }),
+            "{\n  \"agents\": {\n    // first\n    \"x\": { \"model\": \"m\" }\n  },\n  \"z\": 1\n}\n",
+        );
+        assert_update(
+            "{\n  \"a\": {\n    \"b\": {\n      \"c\": 1, // c\n      \"d\": 2\n    }\n  }\n}\n",
+            json!({ "a": { "b": { "d": 2 } } }),
+            "{\n  \"a\": {\n    \"b\": {\n      \"d\": 2\n    }\n  }\n}\n",
+        );
+    }
+
+    #[test]
+    fn update_keeps_comments_next_to_edited_keys() {
+        assert_update(
+            "{\n  // above a\n  \"a\": /* inline */ 1, // after a\n  /* above b */ \"b\": 2 // after b\n}\n",
+            json!({ "a": 10, "b": 20 }),
+            "{\n  // above a\n  \"a\": /* inline */ 10, // after a\n  /* above b */ \"b\": 20 // after b\n}\n",
+        );
+    }
+}
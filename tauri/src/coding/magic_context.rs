@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tokio::process::Command;
 
+use crate::coding::atomic_write;
 use crate::coding::cli_resolver::{
     build_local_tokio_command, local_cli_missing_hint, resolve_local_npx_program,
 };
@@ -248,7 +249,7 @@ fn write_config_file(path: &Path, content: &str) -> Result<(), String> {
             directory.display()
         )
     })?;
-    fs::write(path, content).map_err(|error| {
+    atomic_write::write_atomic(path, content).map_err(|error| {
         format!(
             "Failed to write Magic Context config {}: {error}",
             path.display()
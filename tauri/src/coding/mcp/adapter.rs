@@ -195,6 +195,15 @@ pub fn from_db_mcp_preferences(value: Value) -> McpPreferences {
             .get("limit_add_more_to_preferred_tools")
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
+        env_vars: value
+            .get("env_vars")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default(),
         updated_at: value
             .get("updated_at")
             .and_then(|v| v.as_i64())
@@ -210,6 +219,7 @@ pub fn to_mcp_preferences_payload(prefs: &McpPreferences) -> Value {
         "favorites_initialized": prefs.favorites_initialized,
         "sync_disabled_to_opencode": prefs.sync_disabled_to_opencode,
         "limit_add_more_to_preferred_tools": prefs.limit_add_more_to_preferred_tools,
+        "env_vars": prefs.env_vars,
         "updated_at": prefs.updated_at,
     })
 }
@@ -2,29 +2,41 @@
 //!
 //! Provides the public API for the MCP feature.
 
-use tauri::{AppHandle, Emitter, Runtime, State};
+use std::collections::BTreeMap;
+
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 
 use super::adapter::parse_sync_details_dto;
 use super::config_sync::{
-    import_servers_from_path, import_servers_from_plugin_mcp_json, import_servers_from_tool_async,
-    remove_server_from_tool_async, sync_server_to_tool_async,
-    sync_server_to_tool_with_enabled_async,
+    expand_env_placeholders, import_servers_from_path, import_servers_from_plugin_mcp_json,
+    import_servers_from_text, import_servers_from_tool_async, is_env_var_name,
+    missing_command_message, remove_server_from_tool_async, resolve_stdio_command,
+    server_drift_in_tool_async, stdio_command, sync_server_to_tool_async,
+    sync_server_to_tool_with_enabled_async, user_env_vars, validate_config_file,
 };
 use super::mcp_store;
+use super::snapshot::{self, McpSnapshot};
 use super::package_version;
 use super::types::{
     now_ms, CreateMcpServerInput, FavoriteMcp, FavoriteMcpDto, FavoriteMcpInput,
-    McpDiscoveredServerDto, McpImportResultDto, McpPackageVersionResolveRequest,
-    McpPackageVersionResolveResult, McpScanResultDto, McpServer, McpServerDto, McpSyncDetail,
-    McpSyncResultDto, UpdateMcpServerInput,
+    McpCommandCheckDto, McpDiscoveredServerDto, McpImportResultDto, McpOrphanDto,
+    McpOrphanRemovalDto, McpOrphanScanDto, McpOrphanSelectionDto, McpPackageVersionResolveRequest,
+    McpPackageVersionResolveResult, McpReconcileReport, McpScanResultDto, McpScanSkippedSourceDto,
+    McpServer, McpServerDto, McpServerTestDto, McpServerToolResultDto, McpSyncDetail,
+    McpSyncDriftDto, McpSyncResultDto, McpTagCountDto, SharedPathGroup, SharedPathTool,
+    UpdateMcpServerInput,
 };
 use crate::coding::tools::{
     custom_store, get_mcp_runtime_tools, is_tool_installed_with_db_async,
-    resolve_mcp_config_path_with_db_async, runtime_tool_by_key, to_runtime_tool_dto_with_db_async,
-    CustomTool, RuntimeToolDto,
+    platform_path_compare_key, resolve_mcp_config_path_with_db_async, runtime_tool_by_key,
+    to_runtime_tool_dto_with_db_async, CustomTool, RuntimeTool, RuntimeToolDto,
 };
 use crate::SqliteDbState;
 
+/// Source key/name reported for servers parsed by `mcp_import_from_text`
+const PASTED_SOURCE_KEY: &str = "pasted";
+const PASTED_SOURCE_NAME: &str = "Pasted";
+
 fn normalize_optional_text(value: Option<String>) -> Option<String> {
     value.and_then(|text| {
         let trimmed = text.trim();
@@ -38,32 +50,50 @@ fn normalize_optional_text(value: Option<String>) -> Option<String> {
 
 // ==================== MCP Server CRUD ====================
 
+fn server_to_dto(s: McpServer) -> McpServerDto {
+    McpServerDto {
+        sync_details: parse_sync_details_dto(&s),
+        id: s.id,
+        name: s.name,
+        server_type: s.server_type,
+        server_config: s.server_config,
+        enabled_tools: s.enabled_tools,
+        description: s.description,
+        user_group: s.user_group,
+        user_note: s.user_note,
+        tags: s.tags,
+        timeout: s.timeout,
+        sort_index: s.sort_index,
+        created_at: s.created_at,
+        updated_at: s.updated_at,
+    }
+}
+
 /// List all MCP servers
 #[tauri::command]
 pub async fn mcp_list_servers(
     state: State<'_, SqliteDbState>,
 ) -> Result<Vec<McpServerDto>, String> {
     let servers = mcp_store::get_mcp_servers(&state).await?;
+    Ok(servers.into_iter().map(server_to_dto).collect())
+}
 
-    Ok(servers
-        .into_iter()
-        .map(|s| McpServerDto {
-            id: s.id.clone(),
-            name: s.name.clone(),
-            server_type: s.server_type.clone(),
-            server_config: s.server_config.clone(),
-            enabled_tools: s.enabled_tools.clone(),
-            sync_details: parse_sync_details_dto(&s),
-            description: s.description.clone(),
-            user_group: s.user_group.clone(),
-            user_note: s.user_note.clone(),
-            tags: s.tags.clone(),
-            timeout: s.timeout,
-            sort_index: s.sort_index,
-            created_at: s.created_at,
-            updated_at: s.updated_at,
-        })
-        .collect())
+/// List MCP servers carrying `tag`; the match is exact but ignores case
+#[tauri::command]
+pub async fn mcp_list_servers_by_tag(
+    state: State<'_, SqliteDbState>,
+    tag: String,
+) -> Result<Vec<McpServerDto>, String> {
+    let servers = mcp_store::get_mcp_servers_by_tag(&state, tag.trim()).await?;
+    Ok(servers.into_iter().map(server_to_dto).collect())
+}
+
+/// List distinct MCP server tags with server counts, aggregated in the database
+#[tauri::command]
+pub async fn mcp_list_all_tags(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<McpTagCountDto>, String> {
+    mcp_store::get_mcp_tag_counts(&state).await
 }
 
 /// Resolve latest package versions for MCP stdio runner packages.
@@ -308,51 +338,163 @@ pub async fn mcp_toggle_tool<R: Runtime>(
     let custom_tools = custom_store::get_custom_tools(&state)
         .await
         .unwrap_or_default();
-    let db = state.db();
     let tool = runtime_tool_by_key(&toolKey, &custom_tools)
         .ok_or_else(|| format!("Tool not found: {}", toolKey))?;
 
     // Sync or remove based on new state
     if is_enabled {
-        // Sync to tool config
-        match sync_server_to_tool_async(&db, &server, &tool).await {
+        crate::coding::tool_limits::warn_mcp_limits(&state, &toolKey).await;
+    }
+    apply_server_tool_state(&state, &server, &tool, is_enabled).await?;
+
+    // Emit config-changed and mcp-changed events
+    let _ = app.emit("config-changed", "window");
+    let _ = app.emit("mcp-changed", "window");
+
+    Ok(is_enabled)
+}
+
+/// Write or remove `server` in `tool`'s config after the tool was enabled or disabled for
+/// it, and update the sync detail. A failed write is recorded and returned; removal errors
+/// are ignored.
+async fn apply_server_tool_state(
+    state: &SqliteDbState,
+    server: &McpServer,
+    tool: &RuntimeTool,
+    enabled: bool,
+) -> Result<(), String> {
+    let db = state.db();
+    if enabled {
+        match sync_server_to_tool_async(&db, server, tool).await {
             Ok(detail) => {
-                mcp_store::update_sync_detail(&state, &serverId, &detail).await?;
+                mcp_store::update_sync_detail(state, &server.id, &detail).await?;
             }
             Err(e) => {
                 let detail = McpSyncDetail {
-                    tool: toolKey.clone(),
+                    tool: tool.key.clone(),
                     status: "error".to_string(),
                     synced_at: Some(now_ms()),
                     error_message: Some(e.clone()),
                 };
-                mcp_store::update_sync_detail(&state, &serverId, &detail).await?;
+                mcp_store::update_sync_detail(state, &server.id, &detail).await?;
                 return Err(e);
             }
         }
     } else {
         // Remove from tool config (or write as disabled for opencode)
-        if toolKey == "opencode" {
-            let prefs = mcp_store::get_mcp_preferences(&state)
+        if tool.key == "opencode" {
+            let prefs = mcp_store::get_mcp_preferences(state)
                 .await
                 .unwrap_or_default();
             if prefs.sync_disabled_to_opencode {
                 // Write with enabled=false instead of removing
-                let _ = sync_server_to_tool_with_enabled_async(&db, &server, &tool, false).await;
+                let _ = sync_server_to_tool_with_enabled_async(&db, server, tool, false).await;
             } else {
-                let _ = remove_server_from_tool_async(&db, &server.name, &tool).await;
+                let _ = remove_server_from_tool_async(&db, &server.name, tool).await;
             }
         } else {
-            let _ = remove_server_from_tool_async(&db, &server.name, &tool).await;
+            let _ = remove_server_from_tool_async(&db, &server.name, tool).await;
         }
-        mcp_store::delete_sync_detail(&state, &serverId, &toolKey).await?;
+        mcp_store::delete_sync_detail(state, &server.id, &tool.key).await?;
     }
+    Ok(())
+}
 
-    // Emit config-changed and mcp-changed events
-    let _ = app.emit("config-changed", "window");
-    let _ = app.emit("mcp-changed", "window");
+/// Enable or disable a tool for every MCP server, syncing or removing each affected server.
+/// Servers already in the requested state are left alone. The first failure rolls back the
+/// servers changed so far (stored state and tool config) and is returned as the error.
+/// Emits a single `mcp-changed` at the end.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_set_tool_for_all<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    toolKey: String,
+    enabled: bool,
+) -> Result<Vec<McpServerToolResultDto>, String> {
+    let custom_tools = custom_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let tool = runtime_tool_by_key(&toolKey, &custom_tools)
+        .ok_or_else(|| format!("Tool not found: {}", toolKey))?;
 
-    Ok(is_enabled)
+    let mut changed: Vec<McpServer> = Vec::new();
+    let mut failure = None;
+    for mut server in mcp_store::get_mcp_servers(&state).await? {
+        if server.enabled_tools.contains(&toolKey) == enabled {
+            continue;
+        }
+        let original = server.clone();
+        if let Err(error) = mcp_store::toggle_tool_enabled(&state, &server.id, &toolKey).await {
+            failure = Some(format!("{}: {}", server.name, error));
+            break;
+        }
+        changed.push(original);
+        if enabled {
+            server.enabled_tools.push(toolKey.clone());
+        } else {
+            server.enabled_tools.retain(|key| key != &toolKey);
+        }
+        if let Err(error) = apply_server_tool_state(&state, &server, &tool, enabled).await {
+            failure = Some(format!("{}: {}", server.name, error));
+            break;
+        }
+    }
+    if let Some(error) = &failure {
+        log::warn!(
+            "Rolling back MCP tool {} for {} servers: {}",
+            toolKey,
+            changed.len(),
+            error
+        );
+        rollback_tool_for_servers(&state, &changed, &tool, enabled).await;
+    }
+
+    if !changed.is_empty() {
+        let _ = app.emit("config-changed", "window");
+        let _ = app.emit("mcp-changed", "window");
+    }
+    if let Some(error) = failure {
+        return Err(error);
+    }
+    Ok(changed
+        .into_iter()
+        .map(|server| McpServerToolResultDto {
+            server_id: server.id,
+            server_name: server.name,
+            success: true,
+            error_message: None,
+        })
+        .collect())
+}
+
+/// Undo `mcp_set_tool_for_all` for `servers` (their state before the change): restore the
+/// stored tool flag and re-apply the previous tool config. Failures are logged, not returned.
+async fn rollback_tool_for_servers(
+    state: &SqliteDbState,
+    servers: &[McpServer],
+    tool: &RuntimeTool,
+    enabled: bool,
+) {
+    for server in servers.iter().rev() {
+        if let Err(error) = mcp_store::toggle_tool_enabled(state, &server.id, &tool.key).await {
+            log::warn!(
+                "Failed to roll back MCP tool {} for {}: {}",
+                tool.key,
+                server.name,
+                error
+            );
+            continue;
+        }
+        if let Err(error) = apply_server_tool_state(state, server, tool, !enabled).await {
+            log::warn!(
+                "Failed to restore MCP tool {} config for {}: {}",
+                tool.key,
+                server.name,
+                error
+            );
+        }
+    }
 }
 
 /// Reorder MCP servers
@@ -403,6 +545,7 @@ pub async fn mcp_sync_to_tool<R: Runtime>(
         return Err(format!("Tool {} is not installed", toolKey));
     }
 
+    crate::coding::tool_limits::warn_mcp_limits(&state, &toolKey).await;
     let servers = mcp_store::get_mcp_servers(&state).await?;
     let mut results = Vec::new();
 
@@ -528,6 +671,344 @@ async fn mcp_sync_all_internal<R: Runtime>(
     Ok(results)
 }
 
+// ==================== MCP Snapshots ====================
+
+fn mcp_snapshot_dir<R: Runtime>(app: &AppHandle<R>) -> Result<std::path::PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    Ok(snapshot::snapshot_dir(&app_data_dir))
+}
+
+/// Capture all MCP servers, favorites and preferences into a standalone
+/// snapshot file. Returns the snapshot id used by `mcp_restore_snapshot`.
+#[tauri::command]
+pub async fn mcp_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<String, String> {
+    let created_at = now_ms();
+    let id = format!(
+        "{}-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        &crate::coding::db_new_id()[..8]
+    );
+    let mcp_snapshot = McpSnapshot {
+        id: id.clone(),
+        created_at,
+        servers: mcp_store::get_mcp_servers(&state).await?,
+        favorites: mcp_store::get_favorite_mcps(&state).await?,
+        preferences: mcp_store::get_mcp_preferences(&state).await?,
+    };
+    snapshot::write_snapshot(&mcp_snapshot_dir(&app)?, &mcp_snapshot)?;
+    Ok(id)
+}
+
+/// Restore an MCP snapshot: remove servers the snapshot no longer enables from
+/// tool configs, replace the stored MCP data, then re-sync every tool.
+#[tauri::command]
+pub async fn mcp_restore_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    id: String,
+) -> Result<Vec<McpSyncResultDto>, String> {
+    mcp_restore_snapshot_internal(app, state.inner(), &id).await
+}
+
+/// Whether an MCP snapshot with this id exists and parses.
+pub fn mcp_snapshot_exists<R: Runtime>(app: &AppHandle<R>, id: &str) -> Result<bool, String> {
+    Ok(snapshot::read_snapshot(&mcp_snapshot_dir(app)?, id).is_ok())
+}
+
+pub async fn mcp_restore_snapshot_internal<R: Runtime>(
+    app: AppHandle<R>,
+    state: &SqliteDbState,
+    id: &str,
+) -> Result<Vec<McpSyncResultDto>, String> {
+    let mcp_snapshot = snapshot::read_snapshot(&mcp_snapshot_dir(&app)?, id)?;
+
+    let custom_tools = custom_store::get_custom_tools(state)
+        .await
+        .unwrap_or_default();
+    let db = state.db();
+    for server in mcp_store::get_mcp_servers(state).await? {
+        let restored = mcp_snapshot
+            .servers
+            .iter()
+            .find(|candidate| candidate.name == server.name);
+        for tool_key in &server.enabled_tools {
+            if restored.is_some_and(|candidate| candidate.enabled_tools.contains(tool_key)) {
+                continue;
+            }
+            if let Some(tool) = runtime_tool_by_key(tool_key, &custom_tools) {
+                let _ = remove_server_from_tool_async(&db, &server.name, &tool).await;
+            }
+        }
+        if restored.is_none() {
+            maybe_remove_disabled_from_opencode(state, &server, &custom_tools).await;
+        }
+    }
+
+    mcp_store::replace_all_mcp_data(
+        state,
+        &mcp_snapshot.servers,
+        &mcp_snapshot.favorites,
+        &mcp_snapshot.preferences,
+    )
+    .await?;
+
+    mcp_sync_all_internal(app, state, true).await
+}
+
+/// How an imported server's name relates to the servers already stored
+enum ImportName {
+    /// No server with this name yet
+    Unchanged,
+    /// Same name with the same config; nothing to import
+    Identical,
+    /// Same name with a different config; import under a suffixed name
+    Renamed(String),
+}
+
+async fn resolve_import_name(
+    state: &SqliteDbState,
+    server: &McpServer,
+    source_display_name: &str,
+) -> Result<ImportName, String> {
+    // Check if server with same name already exists
+    let Some(existing) = mcp_store::get_mcp_server_by_name(state, &server.name).await? else {
+        return Ok(ImportName::Unchanged);
+    };
+    // Compare configurations
+    if existing.server_type == server.server_type && existing.server_config == server.server_config
+    {
+        Ok(ImportName::Identical)
+    } else {
+        Ok(ImportName::Renamed(format!(
+            "{} ({})",
+            server.name, source_display_name
+        )))
+    }
+}
+
+/// Parse MCP servers from pasted text for the user to confirm. `format` is "json"
+/// (Claude-style `mcpServers`, VSCode-style `servers`, or a bare server map) or "toml"
+/// (Codex `[mcp_servers.*]`). Servers identical to a stored one are left out; name clashes
+/// with a different config get the same suffix as `mcp_import_from_tool`.
+#[tauri::command]
+pub async fn mcp_import_from_text(
+    state: State<'_, SqliteDbState>,
+    text: String,
+    format: String,
+) -> Result<Vec<McpDiscoveredServerDto>, String> {
+    let mut discovered = Vec::new();
+    for server in import_servers_from_text(&text, &format)? {
+        let name = match resolve_import_name(&state, &server, PASTED_SOURCE_NAME).await? {
+            ImportName::Unchanged => server.name,
+            ImportName::Identical => continue,
+            ImportName::Renamed(new_name) => new_name,
+        };
+        discovered.push(McpDiscoveredServerDto {
+            name,
+            tool_key: PASTED_SOURCE_KEY.to_string(),
+            tool_name: PASTED_SOURCE_NAME.to_string(),
+            server_type: server.server_type,
+            server_config: server.server_config,
+        });
+    }
+    Ok(discovered)
+}
+
+const CODEX_TOOL_KEY: &str = "codex";
+
+/// How a server from Codex config.toml relates to the managed servers
+#[derive(Debug, PartialEq, Eq)]
+enum CodexReconcileAction {
+    /// No managed server has this name
+    Add,
+    /// A managed server with this name has the same config; `enable` when Codex is not yet
+    /// one of its tools
+    Match {
+        server_id: String,
+        enable: bool,
+    },
+    Conflict,
+}
+
+fn classify_codex_server(codex_server: &McpServer, managed: &[McpServer]) -> CodexReconcileAction {
+    let Some(existing) = managed.iter().find(|s| s.name == codex_server.name) else {
+        return CodexReconcileAction::Add;
+    };
+    if existing.server_type == codex_server.server_type
+        && existing.server_config == codex_server.server_config
+    {
+        CodexReconcileAction::Match {
+            server_id: existing.id.clone(),
+            enable: !existing.enabled_tools.iter().any(|t| t == CODEX_TOOL_KEY),
+        }
+    } else {
+        CodexReconcileAction::Conflict
+    }
+}
+
+/// Compare the `mcp_servers` in Codex config.toml with managed servers. With `adopt`,
+/// unmanaged entries become managed servers enabled for Codex only, and identical managed
+/// servers get Codex added to their tools. The TOML entries are recorded as synced but not
+/// rewritten; conflicting names are only reported.
+#[tauri::command]
+pub async fn mcp_reconcile_codex(
+    app: AppHandle,
+    state: State<'_, SqliteDbState>,
+    adopt: Option<bool>,
+) -> Result<McpReconcileReport, String> {
+    let adopt = adopt.unwrap_or(false);
+    let custom_tools = custom_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let tool = runtime_tool_by_key(CODEX_TOOL_KEY, &custom_tools)
+        .ok_or_else(|| "Codex tool is not available".to_string())?;
+    let codex_servers = import_servers_from_tool_async(&state.db(), &tool).await?;
+    let managed = mcp_store::get_mcp_servers(&state).await?;
+
+    let mut report = McpReconcileReport {
+        adopted: adopt,
+        ..Default::default()
+    };
+    let synced_detail = || McpSyncDetail {
+        tool: CODEX_TOOL_KEY.to_string(),
+        status: "ok".to_string(),
+        synced_at: Some(now_ms()),
+        error_message: None,
+    };
+    for mut server in codex_servers {
+        match classify_codex_server(&server, &managed) {
+            CodexReconcileAction::Add => {
+                if adopt {
+                    server.enabled_tools = vec![CODEX_TOOL_KEY.to_string()];
+                    let server_id = mcp_store::upsert_mcp_server(&state, &server).await?;
+                    mcp_store::update_sync_detail(&state, &server_id, &synced_detail()).await?;
+                }
+                report.added.push(server.name);
+            }
+            CodexReconcileAction::Match { server_id, enable } => {
+                if adopt && enable {
+                    mcp_store::toggle_tool_enabled(&state, &server_id, CODEX_TOOL_KEY).await?;
+                    mcp_store::update_sync_detail(&state, &server_id, &synced_detail()).await?;
+                }
+                report.matched.push(server.name);
+            }
+            CodexReconcileAction::Conflict => report.conflicting.push(server.name),
+        }
+    }
+
+    if adopt {
+        let _ = app.emit("config-changed", "window");
+        let _ = app.emit("mcp-changed", "window");
+    }
+    Ok(report)
+}
+
+/// Compare a server with its entry in each enabled tool's config file. Unlike the stored
+/// `sync_details`, this reads the files, so edits made outside AI Toolbox show up as
+/// "missing" or "drifted".
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_get_sync_drift(
+    state: State<'_, SqliteDbState>,
+    serverId: String,
+) -> Result<Vec<McpSyncDriftDto>, String> {
+    let server = mcp_store::get_mcp_server_by_id(&state, &serverId)
+        .await?
+        .ok_or_else(|| format!("MCP server not found: {}", serverId))?;
+    let custom_tools = custom_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let db = state.db();
+
+    let mut drift = Vec::new();
+    for tool_key in &server.enabled_tools {
+        let Some(tool) = runtime_tool_by_key(tool_key, &custom_tools) else {
+            continue;
+        };
+        let (status, differing_keys, error_message) =
+            match server_drift_in_tool_async(&db, &server, &tool).await {
+                Ok(None) => ("missing", Vec::new(), None),
+                Ok(Some(keys)) if keys.is_empty() => ("in-sync", keys, None),
+                Ok(Some(keys)) => ("drifted", keys, None),
+                Err(e) => ("error", Vec::new(), Some(e)),
+            };
+        drift.push(McpSyncDriftDto {
+            tool: tool_key.clone(),
+            status: status.to_string(),
+            differing_keys,
+            error_message,
+        });
+    }
+    Ok(drift)
+}
+
+/// Check whether a stdio server's command resolves locally (PATH plus the common install
+/// dirs). Remote servers always report available.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_check_server_command(
+    state: State<'_, SqliteDbState>,
+    serverId: String,
+) -> Result<McpCommandCheckDto, String> {
+    let server = mcp_store::get_mcp_server_by_id(&state, &serverId)
+        .await?
+        .ok_or_else(|| format!("MCP server not found: {}", serverId))?;
+    let Some(command) = stdio_command(&server).map(str::to_string) else {
+        return Ok(McpCommandCheckDto {
+            server_id: serverId,
+            command: None,
+            available: true,
+            resolved_path: None,
+            message: None,
+        });
+    };
+
+    let resolved = tokio::task::spawn_blocking({
+        let command = command.clone();
+        move || resolve_stdio_command(&command)
+    })
+    .await
+    .map_err(|e| format!("Failed to resolve command: {}", e))?;
+    Ok(McpCommandCheckDto {
+        server_id: serverId,
+        available: resolved.is_some(),
+        message: resolved
+            .is_none()
+            .then(|| missing_command_message(&command)),
+        resolved_path: resolved.map(|path| path.to_string_lossy().to_string()),
+        command: Some(command),
+    })
+}
+
+/// Launch a server and run the MCP `initialize` handshake: stdio servers are spawned with
+/// their command/args/env and killed afterwards, http/sse servers are contacted over the wire.
+/// `${VAR}` placeholders are expanded the same way sync writes them.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn mcp_test_server(
+    state: State<'_, SqliteDbState>,
+    serverId: String,
+) -> Result<McpServerTestDto, String> {
+    let server = mcp_store::get_mcp_server_by_id(&state, &serverId)
+        .await?
+        .ok_or_else(|| format!("MCP server not found: {}", serverId))?;
+    let (server, _) = expand_env_placeholders(&server, &user_env_vars(&state));
+
+    let client = if server.server_type == "stdio" {
+        None
+    } else {
+        let timeout_secs = super::server_test::handshake_timeout(&server).as_secs();
+        Some(crate::http_client::client_with_timeout(&state, timeout_secs).await?)
+    };
+    Ok(super::server_test::test_server(client.as_ref(), &server).await)
+}
+
 /// Import MCP servers from a tool's config file
 /// After import, automatically sync to specified tools (or preferred tools if not specified)
 /// If a server with the same name exists but has different config, create with suffix
@@ -644,18 +1125,13 @@ pub async fn mcp_import_from_tool(
     let mut errors = Vec::new();
 
     for mut server in imported_servers {
-        // Check if server with same name already exists
-        if let Some(existing) = mcp_store::get_mcp_server_by_name(&state, &server.name).await? {
-            // Compare configurations
-            if existing.server_type == server.server_type
-                && existing.server_config == server.server_config
-            {
-                // Same config, skip
+        match resolve_import_name(&state, &server, &source_display_name).await? {
+            ImportName::Unchanged => {}
+            ImportName::Identical => {
                 servers_skipped += 1;
                 continue;
-            } else {
-                // Different config, create with suffix
-                let new_name = format!("{} ({})", server.name, source_display_name);
+            }
+            ImportName::Renamed(new_name) => {
                 servers_duplicated.push(new_name.clone());
                 server.name = new_name;
             }
@@ -727,6 +1203,46 @@ pub async fn mcp_get_tools(state: State<'_, SqliteDbState>) -> Result<Vec<Runtim
     Ok(tool_dtos)
 }
 
+/// Group built-in and custom MCP tools by resolved config path and report every
+/// path used by more than one tool, since those tools overwrite each other on sync.
+#[tauri::command]
+pub async fn detect_shared_config_paths(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<SharedPathGroup>, String> {
+    let custom_tools = custom_store::get_custom_tools(&state).await?;
+    let mcp_tools = get_mcp_runtime_tools(&custom_tools);
+    let db = state.db();
+
+    let mut groups: Vec<(String, SharedPathGroup)> = Vec::new();
+    for tool in &mcp_tools {
+        let Some(config_path) = resolve_mcp_config_path_with_db_async(&db, tool).await else {
+            continue;
+        };
+        let compare_key = platform_path_compare_key(&config_path);
+        let entry = SharedPathTool {
+            key: tool.key.clone(),
+            display_name: super::mcp_tool_display_name(&tool.key, &tool.display_name),
+            is_custom: tool.is_custom,
+        };
+        match groups.iter_mut().find(|(key, _)| *key == compare_key) {
+            Some((_, group)) => group.tools.push(entry),
+            None => groups.push((
+                compare_key,
+                SharedPathGroup {
+                    config_path: config_path.to_string_lossy().to_string(),
+                    tools: vec![entry],
+                },
+            )),
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(_, group)| group)
+        .filter(|group| group.tools.len() > 1)
+        .collect())
+}
+
 /// Scan all installed MCP tools and return discovered servers (excluding already imported ones)
 #[tauri::command]
 pub async fn mcp_scan_servers(state: State<'_, SqliteDbState>) -> Result<McpScanResultDto, String> {
@@ -744,27 +1260,48 @@ pub async fn mcp_scan_servers(state: State<'_, SqliteDbState>) -> Result<McpScan
     }
 }
 
-async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDto, String> {
+/// Budget for reading a single scan source, so one slow config cannot starve the others
+const SCAN_SOURCE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Run one blocking scan source on its own thread and give up after `timeout`. A timed-out
+/// reader cannot be interrupted; it is left to finish in the background and its result is
+/// dropped.
+fn scan_source_with_timeout<T, F>(timeout: std::time::Duration, scan: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(scan());
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+            Err(format!("Timed out after {} seconds", timeout.as_secs_f32()))
+        }
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+            Err("Scan thread exited without a result".to_string())
+        }
+    }
+}
+
+/// Installed MCP tools whose config file exists, with that file's path
+async fn installed_mcp_config_targets(
+    state: &SqliteDbState,
+) -> Vec<(RuntimeTool, std::path::PathBuf)> {
     let custom_tools = custom_store::get_custom_tools(state)
         .await
         .unwrap_or_default();
-    let mcp_tools = get_mcp_runtime_tools(&custom_tools);
-    let scan_db = state.db();
-
-    // Get existing server names for filtering
-    let existing_servers = mcp_store::get_mcp_servers(state).await?;
-    let existing_names: std::collections::HashSet<String> =
-        existing_servers.iter().map(|s| s.name.clone()).collect();
-    let claude_plugins =
-        crate::coding::tools::claude_plugins::get_installed_plugins(&scan_db).await;
+    let db = state.db();
 
-    let mut scan_targets = Vec::new();
-    for tool in &mcp_tools {
-        if !is_tool_installed_with_db_async(&scan_db, tool).await {
+    let mut targets = Vec::new();
+    for tool in get_mcp_runtime_tools(&custom_tools) {
+        if !is_tool_installed_with_db_async(db, &tool).await {
             continue;
         }
 
-        let Some(config_path) = resolve_mcp_config_path_with_db_async(&scan_db, tool).await else {
+        let Some(config_path) = resolve_mcp_config_path_with_db_async(db, &tool).await else {
             continue;
         };
 
@@ -772,21 +1309,41 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
             continue;
         }
 
-        scan_targets.push((tool.clone(), config_path));
+        targets.push((tool, config_path));
     }
+    targets
+}
+
+async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDto, String> {
+    let scan_db = state.db();
+
+    // Get existing server names for filtering
+    let existing_servers = mcp_store::get_mcp_servers(state).await?;
+    let existing_names: std::collections::HashSet<String> =
+        existing_servers.iter().map(|s| s.name.clone()).collect();
+    let claude_plugins =
+        crate::coding::tools::claude_plugins::get_installed_plugins(&scan_db).await;
+
+    let scan_targets = installed_mcp_config_targets(state).await;
 
     // Run the blocking file system operations in a dedicated thread pool
     // to avoid blocking the tokio async runtime
     let scan_result = tokio::task::spawn_blocking(move || {
         let mut total_tools_scanned = 0;
         let mut servers: Vec<McpDiscoveredServerDto> = Vec::new();
+        let mut skipped: Vec<McpScanSkippedSourceDto> = Vec::new();
 
         for (tool, config_path) in &scan_targets {
             eprintln!("[DEBUG][mcp_scan_servers] scanning tool: {}", tool.key);
             total_tools_scanned += 1;
 
             // Try to import servers from this tool
-            match import_servers_from_path(tool, config_path) {
+            let scan = {
+                let tool = tool.clone();
+                let config_path = config_path.clone();
+                move || import_servers_from_path(&tool, &config_path)
+            };
+            match scan_source_with_timeout(SCAN_SOURCE_TIMEOUT, scan) {
                 Ok(imported) => {
                     eprintln!(
                         "[DEBUG][mcp_scan_servers] {} imported {} servers",
@@ -808,8 +1365,12 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
                     }
                 }
                 Err(e) => {
-                    // Log error but continue scanning
+                    // Record the error but continue scanning
                     eprintln!("Failed to scan {}: {}", tool.key, e);
+                    skipped.push(McpScanSkippedSourceDto {
+                        tool_key: tool.key.clone(),
+                        reason: e,
+                    });
                 }
             }
         }
@@ -825,7 +1386,8 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
             let tool_name = format!("Plugin: {}", plugin.display_name);
             total_tools_scanned += 1;
 
-            match import_servers_from_plugin_mcp_json(&mcp_json_path) {
+            let scan = move || import_servers_from_plugin_mcp_json(&mcp_json_path);
+            match scan_source_with_timeout(SCAN_SOURCE_TIMEOUT, scan) {
                 Ok(imported) => {
                     for server in imported {
                         if existing_names.contains(&server.name) {
@@ -842,13 +1404,18 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
                 }
                 Err(e) => {
                     eprintln!("Failed to scan plugin {}: {}", plugin.plugin_id, e);
+                    skipped.push(McpScanSkippedSourceDto {
+                        tool_key,
+                        reason: e,
+                    });
                 }
             }
         }
 
         // Scan CC Switch central mcp_servers table (no separate import button).
         // Only count as a scanned source when at least one non-existing server is listed.
-        match crate::coding::cc_switch::list_cc_switch_mcp_servers(None) {
+        let scan = || crate::coding::cc_switch::list_cc_switch_mcp_servers(None);
+        match scan_source_with_timeout(SCAN_SOURCE_TIMEOUT, scan) {
             Ok(candidates) if !candidates.is_empty() => {
                 let tool_key = crate::coding::cc_switch::CC_SWITCH_MCP_TOOL_KEY.to_string();
                 let tool_name = crate::coding::cc_switch::CC_SWITCH_MCP_TOOL_NAME.to_string();
@@ -872,6 +1439,10 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
             Ok(_) => {}
             Err(e) => {
                 eprintln!("Failed to scan CC Switch MCP: {}", e);
+                skipped.push(McpScanSkippedSourceDto {
+                    tool_key: crate::coding::cc_switch::CC_SWITCH_MCP_TOOL_KEY.to_string(),
+                    reason: e,
+                });
             }
         }
 
@@ -879,6 +1450,7 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
             total_tools_scanned,
             total_servers_found: servers.len() as i32,
             servers,
+            skipped,
         }
     })
     .await
@@ -887,6 +1459,119 @@ async fn mcp_scan_servers_inner(state: &SqliteDbState) -> Result<McpScanResultDt
     Ok(scan_result)
 }
 
+/// Orphans among the server names read from one tool config: names that no database server
+/// has, in file order and without duplicates.
+fn collect_orphans(
+    tool: &RuntimeTool,
+    config_path: &std::path::Path,
+    names: impl IntoIterator<Item = String>,
+    existing_names: &std::collections::HashSet<String>,
+) -> Vec<McpOrphanDto> {
+    let mut seen = std::collections::HashSet::new();
+    names
+        .into_iter()
+        .filter(|name| !existing_names.contains(name) && seen.insert(name.clone()))
+        .map(|server_name| McpOrphanDto {
+            tool_key: tool.key.clone(),
+            tool_name: super::mcp_tool_display_name(&tool.key, &tool.display_name),
+            server_name,
+            config_path: config_path.to_string_lossy().to_string(),
+        })
+        .collect()
+}
+
+/// List MCP entries in installed tools' config files whose names match no server in the
+/// database, e.g. leftovers of servers deleted while a tool was not managed. Tools sharing a
+/// config file are read once; unreadable files are reported as skipped.
+#[tauri::command]
+pub async fn mcp_find_orphans(state: State<'_, SqliteDbState>) -> Result<McpOrphanScanDto, String> {
+    let existing_names: std::collections::HashSet<String> = mcp_store::get_mcp_servers(&state)
+        .await?
+        .into_iter()
+        .map(|server| server.name)
+        .collect();
+    let mut seen_paths = std::collections::HashSet::new();
+    let targets: Vec<_> = installed_mcp_config_targets(&state)
+        .await
+        .into_iter()
+        .filter(|(_, path)| seen_paths.insert(platform_path_compare_key(path)))
+        .collect();
+
+    tokio::task::spawn_blocking(move || {
+        let mut orphans = Vec::new();
+        let mut skipped = Vec::new();
+        for (tool, config_path) in targets {
+            let scan = {
+                let tool = tool.clone();
+                let config_path = config_path.clone();
+                move || import_servers_from_path(&tool, &config_path)
+            };
+            match scan_source_with_timeout(SCAN_SOURCE_TIMEOUT, scan) {
+                Ok(imported) => orphans.extend(collect_orphans(
+                    &tool,
+                    &config_path,
+                    imported.into_iter().map(|server| server.name),
+                    &existing_names,
+                )),
+                Err(reason) => skipped.push(McpScanSkippedSourceDto {
+                    tool_key: tool.key.clone(),
+                    reason,
+                }),
+            }
+        }
+        McpOrphanScanDto { orphans, skipped }
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking failed: {}", e))
+}
+
+/// Remove the selected orphaned entries from their tools' config files. An entry whose name
+/// now belongs to a database server is refused; a failure on one entry does not stop the
+/// others.
+#[tauri::command]
+pub async fn mcp_remove_orphans<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+    selections: Vec<McpOrphanSelectionDto>,
+) -> Result<Vec<McpOrphanRemovalDto>, String> {
+    let custom_tools = custom_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let existing_names: std::collections::HashSet<String> = mcp_store::get_mcp_servers(&state)
+        .await?
+        .into_iter()
+        .map(|server| server.name)
+        .collect();
+
+    let mut results = Vec::new();
+    for selection in selections {
+        let outcome = async {
+            if existing_names.contains(&selection.server_name) {
+                return Err(format!(
+                    "'{}' is a managed MCP server, not an orphan",
+                    selection.server_name
+                ));
+            }
+            let tool = runtime_tool_by_key(&selection.tool_key, &custom_tools)
+                .ok_or_else(|| format!("Tool not found: {}", selection.tool_key))?;
+            remove_server_from_tool_async(&state, &selection.server_name, &tool).await
+        }
+        .await;
+        results.push(McpOrphanRemovalDto {
+            tool_key: selection.tool_key,
+            server_name: selection.server_name,
+            success: outcome.is_ok(),
+            error_message: outcome.err(),
+        });
+    }
+
+    if results.iter().any(|result| result.success) {
+        let _ = app.emit("config-changed", "window");
+        let _ = app.emit("mcp-changed", "window");
+    }
+    Ok(results)
+}
+
 // ==================== Preferences ====================
 
 /// Get MCP show in tray setting
@@ -950,6 +1635,30 @@ pub async fn mcp_set_limit_add_more_to_preferred_tools(
     mcp_store::save_mcp_preferences(&state, &prefs).await
 }
 
+/// Get the user-defined values for `${VAR}` placeholders in server configs
+#[tauri::command]
+pub async fn mcp_get_env_vars(
+    state: State<'_, SqliteDbState>,
+) -> Result<BTreeMap<String, String>, String> {
+    let prefs = mcp_store::get_mcp_preferences(&state).await?;
+    Ok(prefs.env_vars)
+}
+
+/// Set the user-defined placeholder values. They apply from the next sync of each server.
+#[tauri::command]
+pub async fn mcp_set_env_vars(
+    state: State<'_, SqliteDbState>,
+    vars: BTreeMap<String, String>,
+) -> Result<(), String> {
+    if let Some(name) = vars.keys().find(|name| !is_env_var_name(name)) {
+        return Err(format!("Invalid environment variable name: '{}'", name));
+    }
+    let mut prefs = mcp_store::get_mcp_preferences(&state).await?;
+    prefs.env_vars = vars;
+    prefs.updated_at = now_ms();
+    mcp_store::save_mcp_preferences(&state, &prefs).await
+}
+
 /// Get sync disabled to opencode setting
 #[tauri::command]
 pub async fn mcp_get_sync_disabled_to_opencode(
@@ -1126,6 +1835,30 @@ pub async fn mcp_add_custom_tool(
     .await
 }
 
+/// Check that a custom tool's MCP config file parses in its declared format.
+/// A missing file is valid: there is nothing to validate until the first sync creates it.
+#[tauri::command]
+pub async fn validate_custom_tool_config_file(
+    state: State<'_, SqliteDbState>,
+    key: String,
+) -> Result<(), Vec<String>> {
+    let custom_tools = custom_store::get_custom_tools(&state)
+        .await
+        .map_err(|e| vec![e])?;
+    let tool = runtime_tool_by_key(key.trim(), &custom_tools)
+        .filter(|tool| tool.is_custom)
+        .ok_or_else(|| vec![format!("Custom tool '{}' not found", key)])?;
+    let format = tool
+        .mcp_config_format
+        .clone()
+        .ok_or_else(|| vec![format!("Custom tool '{}' has no MCP config format", key)])?;
+    let db = state.db();
+    let config_path = resolve_mcp_config_path_with_db_async(&db, &tool)
+        .await
+        .ok_or_else(|| vec![format!("Custom tool '{}' has no MCP config path", key)])?;
+    validate_config_file(&config_path, &format)
+}
+
 /// Remove a custom tool (only if it has no Skills fields, otherwise just clear MCP fields)
 #[tauri::command]
 pub async fn mcp_remove_custom_tool(
@@ -1325,3 +2058,93 @@ pub async fn mcp_init_default_favorites(state: State<'_, SqliteDbState>) -> Resu
 
     Ok(inserted_count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn server(id: &str, name: &str, command: &str, enabled_tools: &[&str]) -> McpServer {
+        McpServer {
+            id: id.to_string(),
+            name: name.to_string(),
+            server_type: "stdio".to_string(),
+            server_config: serde_json::json!({ "command": command }),
+            enabled_tools: enabled_tools.iter().map(|t| t.to_string()).collect(),
+            sync_details: None,
+            description: None,
+            user_group: None,
+            user_note: None,
+            tags: vec![],
+            timeout: None,
+            sort_index: 0,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn classify_codex_server_matches_by_name_and_config() {
+        let managed = vec![
+            server("1", "docs", "npx", &["claude_code"]),
+            server("2", "fs", "uvx", &["codex"]),
+        ];
+
+        assert_eq!(
+            classify_codex_server(&server("", "new", "npx", &[]), &managed),
+            CodexReconcileAction::Add
+        );
+        assert_eq!(
+            classify_codex_server(&server("", "docs", "npx", &[]), &managed),
+            CodexReconcileAction::Match {
+                server_id: "1".to_string(),
+                enable: true
+            }
+        );
+        assert_eq!(
+            classify_codex_server(&server("", "fs", "uvx", &[]), &managed),
+            CodexReconcileAction::Match {
+                server_id: "2".to_string(),
+                enable: false
+            }
+        );
+        assert_eq!(
+            classify_codex_server(&server("", "fs", "npx", &[]), &managed),
+            CodexReconcileAction::Conflict
+        );
+    }
+
+    #[test]
+    fn scan_source_with_timeout_skips_slow_sources() {
+        let fast = scan_source_with_timeout(Duration::from_secs(1), || Ok(vec![1, 2]));
+        assert_eq!(fast, Ok(vec![1, 2]));
+
+        let slow = scan_source_with_timeout(Duration::from_millis(20), || {
+            std::thread::sleep(Duration::from_millis(500));
+            Ok(Vec::<i32>::new())
+        });
+        assert!(slow.unwrap_err().starts_with("Timed out"));
+
+        let failing: Result<(), String> =
+            scan_source_with_timeout(Duration::from_secs(1), || Err("bad config".to_string()));
+        assert_eq!(failing, Err("bad config".to_string()));
+    }
+
+    #[test]
+    fn collect_orphans_skips_known_and_duplicate_names() {
+        let tool = runtime_tool_by_key("claude_code", &[]).expect("builtin tool");
+        let existing: std::collections::HashSet<String> = ["kept".to_string()].into();
+        let names = ["stale", "kept", "other", "stale"].map(String::from);
+
+        let orphans = collect_orphans(
+            &tool,
+            std::path::Path::new("/home/u/.claude.json"),
+            names,
+            &existing,
+        );
+        let names: Vec<&str> = orphans.iter().map(|o| o.server_name.as_str()).collect();
+        assert_eq!(names, vec!["stale", "other"]);
+        assert_eq!(orphans[0].tool_key, "claude_code");
+        assert_eq!(orphans[0].config_path, "/home/u/.claude.json");
+    }
+}
@@ -4,20 +4,25 @@
 //! Supports JSON/JSONC (unified with json5) and TOML formats.
 //! Also handles format conversion for tools like OpenCode that use different schemas.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
 use serde_json::Value;
 
+use super::adapter::from_db_mcp_preferences;
 use super::command_normalize;
 use super::format_configs::get_format_config;
 use super::types::{now_ms, McpServer, McpSyncDetail};
+use crate::coding::wsl::{self, ConfigFormat};
 use crate::coding::{
-    runtime_location,
+    atomic_write, cli_resolver, runtime_location,
     tools::{
         resolve_mcp_config_path_with_db, resolve_mcp_config_path_with_db_async, McpFormatConfig,
         RuntimeTool,
     },
 };
+use crate::db::helpers::db_get;
+use crate::db::schema::DbTable;
 
 /// Sync an MCP server to a specific tool's config file
 pub fn sync_server_to_tool(
@@ -45,7 +50,7 @@ pub fn sync_server_to_tool_with_enabled(
 ) -> Result<McpSyncDetail, String> {
     let config_path = resolve_mcp_config_path_with_db(db, tool)
         .ok_or_else(|| format!("Tool {} does not support MCP", tool.key))?;
-    sync_server_to_path(tool, &config_path, server, enabled)
+    sync_server_to_path(tool, &config_path, server, enabled, &user_env_vars(db))
 }
 
 pub async fn sync_server_to_tool_with_enabled_async(
@@ -57,7 +62,103 @@ pub async fn sync_server_to_tool_with_enabled_async(
     let config_path = resolve_mcp_config_path_with_db_async(db, tool)
         .await
         .ok_or_else(|| format!("Tool {} does not support MCP", tool.key))?;
-    sync_server_to_path(tool, &config_path, server, enabled)
+    sync_server_to_path(tool, &config_path, server, enabled, &user_env_vars(db))
+}
+
+/// User-defined placeholder values from the MCP preferences; empty when unreadable.
+pub(crate) fn user_env_vars(db: &crate::db::SqliteDbState) -> BTreeMap<String, String> {
+    db.with_conn(|conn| db_get(conn, DbTable::McpPreferences, "default"))
+        .ok()
+        .flatten()
+        .map(|value| from_db_mcp_preferences(value).env_vars)
+        .unwrap_or_default()
+}
+
+/// Expand `${VAR}` placeholders in the string values of the server config, resolving
+/// against `user_vars` first and the process environment second. Unresolved placeholders
+/// are kept as written and returned by name, so the tool (or a later sync) can still
+/// resolve them; the stored server config is never touched.
+pub(crate) fn expand_env_placeholders(
+    server: &McpServer,
+    user_vars: &BTreeMap<String, String>,
+) -> (McpServer, Vec<String>) {
+    let lookup = |name: &str| {
+        user_vars
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+    };
+    let mut unresolved = BTreeSet::new();
+    let mut expanded = server.clone();
+    expand_value(&mut expanded.server_config, &lookup, &mut unresolved);
+    (expanded, unresolved.into_iter().collect())
+}
+
+fn expand_value(
+    value: &mut Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    unresolved: &mut BTreeSet<String>,
+) {
+    match value {
+        Value::String(text) if text.contains("${") => {
+            *text = expand_placeholders_in_str(text, lookup, unresolved);
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| expand_value(item, lookup, unresolved)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|item| expand_value(item, lookup, unresolved)),
+        _ => {}
+    }
+}
+
+fn expand_placeholders_in_str(
+    text: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    unresolved: &mut BTreeSet<String>,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &after[..end];
+        let placeholder = &rest[start..start + 2 + end + 1];
+        if is_env_var_name(name) {
+            match lookup(name) {
+                Some(resolved) => result.push_str(&resolved),
+                None => {
+                    unresolved.insert(name.to_string());
+                    result.push_str(placeholder);
+                }
+            }
+        } else {
+            result.push_str(placeholder);
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+pub(crate) fn is_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn unresolved_env_vars_message(names: &[String]) -> String {
+    format!(
+        "Environment variables {} are not defined in AI Toolbox or the app environment; their placeholders were written unchanged",
+        names.join(", ")
+    )
 }
 
 /// Remove an MCP server from a specific tool's config file
@@ -87,12 +188,20 @@ fn sync_server_to_path(
     config_path: &PathBuf,
     server: &McpServer,
     enabled: bool,
+    user_vars: &BTreeMap<String, String>,
 ) -> Result<McpSyncDetail, String> {
+    let (server, unresolved) = expand_env_placeholders(server, user_vars);
+    let server = &server;
     let format = tool.mcp_config_format.as_deref().unwrap_or("json");
     let field = tool.mcp_field.as_deref().unwrap_or("mcpServers");
     let format_config = get_format_config(&tool.key);
     let should_wrap_cmd = should_wrap_cmd_for_config_path(config_path);
 
+    // Custom tools point at user-chosen files; report parse errors with positions up front
+    if tool.is_custom {
+        validate_config_file(config_path, format).map_err(|errors| errors.join("; "))?;
+    }
+
     match format {
         // json5 handles both standard JSON and JSONC (with comments, trailing commas)
         "json" | "jsonc" => sync_server_to_json(
@@ -114,13 +223,189 @@ fn sync_server_to_path(
         ),
         _ => Err(format!("Unsupported config format: {}", format)),
     }
-    .map(|_| McpSyncDetail {
+    .map_err(|e| e.to_string())?;
+
+    // The config is written either way; a missing binary only downgrades the result.
+    // WSL targets run the command inside the distro, so the local PATH says nothing there.
+    let missing_command = if enabled && should_wrap_cmd_for_windows_config_path(config_path) {
+        stdio_command(server).filter(|command| resolve_stdio_command(command).is_none())
+    } else {
+        None
+    };
+    let mut warnings = Vec::new();
+    if let Some(command) = missing_command {
+        warnings.push(missing_command_message(command));
+    }
+    if !unresolved.is_empty() {
+        warnings.push(unresolved_env_vars_message(&unresolved));
+    }
+    Ok(McpSyncDetail {
         tool: tool.key.clone(),
-        status: "ok".to_string(),
+        status: if warnings.is_empty() { "ok" } else { "warning" }.to_string(),
         synced_at: Some(now_ms()),
-        error_message: None,
+        error_message: (!warnings.is_empty()).then(|| warnings.join("; ")),
     })
-    .map_err(|e| e.to_string())
+}
+
+/// The `command` of a stdio server; None for remote servers or an empty command.
+pub(crate) fn stdio_command(server: &McpServer) -> Option<&str> {
+    if server.server_type != "stdio" {
+        return None;
+    }
+    server
+        .server_config
+        .get("command")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+}
+
+/// Resolve a stdio command the way a launched tool would find it. Paths are checked as-is;
+/// bare names are looked up on PATH, then in the install dirs a GUI-launched app does not
+/// inherit from the login shell (`~/.local/bin`, Homebrew, node version managers).
+pub(crate) fn resolve_stdio_command(command: &str) -> Option<PathBuf> {
+    if command.contains('/') || command.contains('\\') {
+        let path = PathBuf::from(command);
+        return path.exists().then_some(path);
+    }
+    cli_resolver::find_cli_on_path(command)
+        .or_else(|| cli_resolver::find_cli_in_common_dirs(command))
+}
+
+pub(crate) fn missing_command_message(command: &str) -> String {
+    format!(
+        "Command '{}' was not found on PATH or in common install locations; the config was written, but the tool will fail to start this server until it is installed or configured with an absolute path",
+        command
+    )
+}
+
+/// Check that an existing MCP config file parses in `format` the way sync reads it.
+/// A missing or empty file is valid: sync creates it.
+pub fn validate_config_file(config_path: &Path, format: &str) -> Result<(), Vec<String>> {
+    if !config_path.exists() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(config_path)
+        .map_err(|e| vec![format!("Failed to read config file: {}", e)])?;
+    if content.trim().is_empty() {
+        return Ok(());
+    }
+
+    match format {
+        "json" | "jsonc" => json5::from_str::<Value>(&content).map(|_| ()).map_err(|e| {
+            vec![format!(
+                "Invalid {} in {}: {}",
+                format.to_uppercase(),
+                config_path.display(),
+                e
+            )]
+        }),
+        "toml" => content
+            .parse::<toml_edit::DocumentMut>()
+            .map(|_| ())
+            .map_err(|e| {
+                let position = e
+                    .span()
+                    .map(|span| line_column_at(&content, span.start))
+                    .map(|(line, column)| format!(" at line {} column {}", line, column))
+                    .unwrap_or_default();
+                vec![format!(
+                    "Invalid TOML in {}{}: {}",
+                    config_path.display(),
+                    position,
+                    e.message()
+                )]
+            }),
+        _ => Err(vec![format!("Unsupported config format: {}", format)]),
+    }
+}
+
+/// Compare the entry AI Toolbox would write for `server` with the one currently in the
+/// tool's config file. Returns None when the file or entry is missing, otherwise the
+/// paths of the fields whose values differ (empty when in sync).
+pub async fn server_drift_in_tool_async(
+    db: &crate::db::SqliteDbState,
+    server: &McpServer,
+    tool: &RuntimeTool,
+) -> Result<Option<Vec<String>>, String> {
+    let config_path = resolve_mcp_config_path_with_db_async(db, tool)
+        .await
+        .ok_or_else(|| format!("Tool {} does not support MCP", tool.key))?;
+    server_drift_in_path(tool, &config_path, server, &user_env_vars(db))
+}
+
+fn server_drift_in_path(
+    tool: &RuntimeTool,
+    config_path: &Path,
+    server: &McpServer,
+    user_vars: &BTreeMap<String, String>,
+) -> Result<Option<Vec<String>>, String> {
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    // Compare against what sync writes, i.e. with placeholders expanded
+    let (server, _) = expand_env_placeholders(server, user_vars);
+    let server = &server;
+    let format = tool.mcp_config_format.as_deref().unwrap_or("json");
+    let field = tool.mcp_field.as_deref().unwrap_or("mcpServers");
+    let should_wrap_cmd = should_wrap_cmd_for_config_path(config_path);
+    let content = std::fs::read_to_string(config_path)
+        .map_err(|e| format!("Failed to read config file: {}", e))?;
+
+    let (expected, actual) = match format {
+        "json" | "jsonc" => {
+            let expected = build_json_server_config(
+                server,
+                get_format_config(&tool.key),
+                true,
+                &tool.key,
+                should_wrap_cmd,
+            )?;
+            let config = wsl::parse_config(&content, ConfigFormat::Json, "config file")?;
+            let actual = get_json_value_by_path(&config, field)
+                .and_then(|servers| servers.get(&server.name))
+                .cloned();
+            (expected, actual)
+        }
+        "toml" => {
+            let table = if tool.key == "grok" {
+                build_grok_toml_server_config(server, true)?
+            } else {
+                build_toml_edit_server_config(server, should_wrap_cmd)?
+            };
+            let mut expected_doc = toml_edit::DocumentMut::new();
+            expected_doc[&server.name] = toml_edit::Item::Table(table);
+            let expected = wsl::parse_config(
+                &expected_doc.to_string(),
+                ConfigFormat::Toml,
+                "generated MCP entry",
+            )?
+            .get(&server.name)
+            .cloned()
+            .unwrap_or(Value::Null);
+            let actual = wsl::parse_config(&content, ConfigFormat::Toml, "config file")?
+                .get(field)
+                .and_then(|servers| servers.get(&server.name))
+                .cloned();
+            (expected, actual)
+        }
+        _ => return Err(format!("Unsupported config format: {}", format)),
+    };
+
+    Ok(actual.map(|actual| {
+        wsl::diff_json_fields(&expected, &actual)
+            .into_iter()
+            .map(|difference| difference.path)
+            .collect()
+    }))
+}
+
+/// 1-based line and column of a byte offset.
+fn line_column_at(content: &str, offset: usize) -> (usize, usize) {
+    let before = content.get(..offset).unwrap_or(content);
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
 }
 
 fn should_wrap_cmd_for_config_path(config_path: &Path) -> bool {
@@ -199,7 +484,7 @@ fn sync_server_to_json(
     // which is valid JSON5 (JSON is a subset of JSON5)
     let content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    std::fs::write(config_path, content)
+    atomic_write::write_atomic(config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -234,7 +519,7 @@ fn remove_server_from_json(
     // Write back to file
     let content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
-    std::fs::write(config_path, content)
+    atomic_write::write_atomic(config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -296,7 +581,7 @@ fn sync_server_to_toml(
 
     // Write back to file
     let content = doc.to_string();
-    std::fs::write(config_path, content)
+    atomic_write::write_atomic(config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -334,7 +619,7 @@ fn remove_server_from_toml(
 
     // Write back to file
     let content = doc.to_string();
-    std::fs::write(config_path, content)
+    atomic_write::write_atomic(config_path, content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -1156,6 +1441,53 @@ pub fn import_servers_from_plugin_mcp_json(
     Ok(servers)
 }
 
+/// Parse servers from a pasted config snippet. `format` is "json" or "toml".
+///
+/// JSON accepts the Claude-style `{"mcpServers": {...}}`, the VSCode-style `{"servers": {...}}`
+/// or `{"mcp": {"servers": {...}}}`, a bare name -> server map, and a single
+/// `"name": {...}` entry copied without its surrounding braces. TOML reads the Codex
+/// `[mcp_servers.*]` tables.
+pub(crate) fn import_servers_from_text(text: &str, format: &str) -> Result<Vec<McpServer>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Nothing to import: the pasted text is empty".to_string());
+    }
+
+    let servers = match format {
+        "json" => {
+            let root: Value = json5::from_str(text)
+                .or_else(|error| json5::from_str(&format!("{{{}}}", text)).map_err(|_| error))
+                .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+            let servers_obj = ["mcpServers", "servers", "mcp.servers"]
+                .iter()
+                .find_map(|field| get_json_value_by_path(&root, field))
+                .unwrap_or(&root)
+                .as_object()
+                .ok_or_else(|| "Expected a JSON object of MCP servers".to_string())?;
+            let now = now_ms();
+            servers_obj
+                .iter()
+                .filter(|(_, config)| {
+                    config.get("command").is_some() || config.get("url").is_some()
+                })
+                .filter_map(|(name, config)| parse_standard_server_config(name, config, now))
+                .collect()
+        }
+        "toml" => parse_toml_mcp_servers(text, "mcp_servers", "codex")?,
+        other => {
+            return Err(format!(
+                "Unsupported import format '{}'; expected json or toml",
+                other
+            ))
+        }
+    };
+
+    if servers.is_empty() {
+        return Err("No MCP servers found in the pasted text".to_string());
+    }
+    Ok(servers)
+}
+
 /// Import servers from TOML config file
 fn import_servers_from_toml(
     config_path: &PathBuf,
@@ -1171,6 +1503,15 @@ fn import_servers_from_toml(
 
     let content = std::fs::read_to_string(config_path)
         .map_err(|e| format!("Failed to read config file: {}", e))?;
+    parse_toml_mcp_servers(&content, field, tool_key)
+}
+
+/// Parse the `field` table of TOML config content into servers
+fn parse_toml_mcp_servers(
+    content: &str,
+    field: &str,
+    tool_key: &str,
+) -> Result<Vec<McpServer>, String> {
     let content_trimmed = content.trim();
     if content_trimmed.is_empty() {
         return Ok(vec![]);
@@ -1951,4 +2292,155 @@ X-Test = "yes"
         assert_eq!(servers[0].server_type, "sse");
         assert_eq!(servers[0].server_config["url"], "https://example.com/sse");
     }
+
+    #[test]
+    fn validate_config_file_reports_parse_errors_with_line_numbers() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let missing = temp_dir.path().join("missing.json");
+        assert!(validate_config_file(&missing, "json").is_ok());
+
+        let jsonc = temp_dir.path().join("ok.jsonc");
+        std::fs::write(&jsonc, "{\n  // comment\n  \"mcpServers\": {},\n}\n").expect("write jsonc");
+        assert!(validate_config_file(&jsonc, "jsonc").is_ok());
+
+        let broken_json = temp_dir.path().join("broken.json");
+        std::fs::write(&broken_json, "{\n  \"a\": 1,\n  \"b\": ?\n}\n").expect("write json");
+        let errors = validate_config_file(&broken_json, "json").unwrap_err();
+        assert!(errors[0].contains("line 3"), "{errors:?}");
+
+        let broken_toml = temp_dir.path().join("broken.toml");
+        std::fs::write(&broken_toml, "[mcp_servers]\nname = \n").expect("write toml");
+        let errors = validate_config_file(&broken_toml, "toml").unwrap_err();
+        assert!(errors[0].contains("line 2"), "{errors:?}");
+    }
+
+    #[test]
+    fn import_servers_from_text_accepts_common_pastes() {
+        let names = |servers: Vec<McpServer>| -> Vec<String> {
+            servers.into_iter().map(|server| server.name).collect()
+        };
+
+        let claude = r#"{ "mcpServers": { "fs": { "command": "npx", "args": ["-y", "fs"] } } }"#;
+        assert_eq!(
+            names(import_servers_from_text(claude, "json").unwrap()),
+            ["fs"]
+        );
+
+        let vscode =
+            r#"{ "inputs": [], "servers": { "gh": { "type": "http", "url": "https://x/mcp" } } }"#;
+        let servers = import_servers_from_text(vscode, "json").unwrap();
+        assert_eq!(servers[0].name, "gh");
+        assert_eq!(servers[0].server_type, "http");
+
+        let fragment = r#""fs": { "command": "npx", "args": ["fs"] },"#;
+        assert_eq!(
+            names(import_servers_from_text(fragment, "json").unwrap()),
+            ["fs"]
+        );
+
+        let codex = "[mcp_servers.docs]\ncommand = \"npx\"\nargs = [\"docs\"]\n";
+        assert_eq!(
+            names(import_servers_from_text(codex, "toml").unwrap()),
+            ["docs"]
+        );
+
+        assert!(import_servers_from_text(r#"{ "theme": "dark" }"#, "json").is_err());
+        assert!(import_servers_from_text(claude, "yaml").is_err());
+    }
+
+    #[test]
+    fn stdio_command_resolution_checks_paths_and_skips_remote_servers() {
+        assert_eq!(stdio_command(&build_npx_stdio_server()), Some("npx"));
+        assert_eq!(stdio_command(&build_http_server()), None);
+
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let binary = temp_dir.path().join("mcp-server");
+        std::fs::write(&binary, "").expect("write binary");
+        let binary = binary.to_string_lossy().to_string();
+        assert_eq!(resolve_stdio_command(&binary), Some(PathBuf::from(&binary)));
+
+        let missing = temp_dir.path().join("missing-server");
+        assert_eq!(resolve_stdio_command(&missing.to_string_lossy()), None);
+        assert!(missing_command_message("uvx").contains("'uvx'"));
+    }
+
+    #[test]
+    fn env_placeholders_expand_from_user_vars_then_process_env() {
+        let mut server = build_npx_stdio_server();
+        server.server_config = json!({
+            "command": "npx",
+            "args": ["--token=${MCP_TEST_TOKEN}", "${PATH}", "${not a var}"],
+            "env": {"KEY": "${AI_TOOLBOX_UNSET_TEST_VAR}", "PLAIN": 3}
+        });
+        let user_vars = BTreeMap::from([("MCP_TEST_TOKEN".to_string(), "secret".to_string())]);
+
+        let (expanded, unresolved) = expand_env_placeholders(&server, &user_vars);
+        assert_eq!(
+            expanded.server_config["args"],
+            json!([
+                "--token=secret",
+                std::env::var("PATH").unwrap_or_else(|_| "${PATH}".to_string()),
+                "${not a var}"
+            ])
+        );
+        assert_eq!(
+            expanded.server_config["env"],
+            json!({"KEY": "${AI_TOOLBOX_UNSET_TEST_VAR}", "PLAIN": 3})
+        );
+        assert_eq!(unresolved, vec!["AI_TOOLBOX_UNSET_TEST_VAR".to_string()]);
+        assert_eq!(
+            server.server_config["args"][0],
+            json!("--token=${MCP_TEST_TOKEN}")
+        );
+    }
+
+    #[test]
+    fn env_placeholders_resolve_process_env_vars_not_in_user_map() {
+        std::env::set_var("AI_TOOLBOX_MCP_PROCESS_ENV_TEST", "from-process");
+        let mut server = build_npx_stdio_server();
+        server.server_config = json!({
+            "command": "npx",
+            "env": {
+                "FROM_PROCESS": "${AI_TOOLBOX_MCP_PROCESS_ENV_TEST}",
+                "FROM_USER": "${MCP_TEST_TOKEN}"
+            }
+        });
+        let user_vars = BTreeMap::from([("MCP_TEST_TOKEN".to_string(), "secret".to_string())]);
+
+        let (expanded, unresolved) = expand_env_placeholders(&server, &user_vars);
+        assert_eq!(
+            expanded.server_config["env"],
+            json!({"FROM_PROCESS": "from-process", "FROM_USER": "secret"})
+        );
+        assert!(unresolved.is_empty());
+    }
+
+    #[test]
+    fn server_drift_reports_missing_in_sync_and_changed_keys() {
+        let tool = crate::coding::tools::runtime_tool_by_key("claude_code", &[]).expect("tool");
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let config_path = temp_dir.path().join("claude.json");
+        let server = build_npx_stdio_server();
+
+        assert_eq!(
+            server_drift_in_path(&tool, &config_path, &server, &BTreeMap::new()),
+            Ok(None)
+        );
+
+        sync_server_to_path(&tool, &config_path, &server, true, &BTreeMap::new()).expect("sync");
+        assert_eq!(
+            server_drift_in_path(&tool, &config_path, &server, &BTreeMap::new()),
+            Ok(Some(vec![]))
+        );
+
+        let mut config: Value =
+            serde_json::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        config["mcpServers"][&server.name]["args"] = json!(["-y", "other"]);
+        config["mcpServers"][&server.name]["cwd"] = json!("/tmp");
+        std::fs::write(&config_path, config.to_string()).unwrap();
+        assert_eq!(
+            server_drift_in_path(&tool, &config_path, &server, &BTreeMap::new()),
+            Ok(Some(vec!["args".to_string(), "cwd".to_string()]))
+        );
+    }
 }
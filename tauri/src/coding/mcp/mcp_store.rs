@@ -9,26 +9,64 @@ use super::adapter::{
     set_sync_detail, to_clean_mcp_server_payload, to_mcp_preferences_payload,
 };
 use super::command_normalize;
-use super::types::{now_ms, FavoriteMcp, McpPreferences, McpServer, McpSyncDetail};
+use super::types::{now_ms, FavoriteMcp, McpPreferences, McpServer, McpSyncDetail, McpTagCountDto};
 use crate::coding::db_id::db_new_id;
-use crate::db::helpers::{db_delete, db_get, db_list, db_max_i64, db_put, db_query_by_field};
+use crate::db::helpers::{
+    db_count_array_elements_nocase, db_delete, db_delete_all, db_get, db_list, db_max_i64, db_put,
+    db_query_by_array_element_nocase, db_query_by_field, db_transaction,
+};
 use crate::db::schema::{DbTable, JsonFieldPath, OrderDirection, OrderField, OrderSpec};
 use crate::SqliteDbState;
 
 // ==================== MCP Server CRUD ====================
 
+/// List order of MCP servers: sort_index, then id
+pub(crate) fn mcp_server_order() -> Result<OrderSpec, String> {
+    Ok(OrderSpec::new(vec![
+        OrderField::json_integer("sort_index", OrderDirection::Asc)?,
+        OrderField::id(OrderDirection::Asc),
+    ]))
+}
+
 /// Get all MCP servers ordered by sort_index
 pub async fn get_mcp_servers(state: &SqliteDbState) -> Result<Vec<McpServer>, String> {
     state.with_conn(|conn| {
-        let order = OrderSpec::new(vec![
-            OrderField::json_integer("sort_index", OrderDirection::Asc)?,
-            OrderField::id(OrderDirection::Asc),
-        ]);
+        let order = mcp_server_order()?;
         let records = db_list(conn, DbTable::McpServer, Some(&order))?;
         Ok(records.into_iter().map(from_db_mcp_server).collect())
     })
 }
 
+/// Get MCP servers carrying `tag` (exact match, ignoring case) ordered by sort_index
+pub async fn get_mcp_servers_by_tag(
+    state: &SqliteDbState,
+    tag: &str,
+) -> Result<Vec<McpServer>, String> {
+    state.with_conn(|conn| {
+        let order = mcp_server_order()?;
+        let records = db_query_by_array_element_nocase(
+            conn,
+            DbTable::McpServer,
+            &JsonFieldPath::new("tags")?,
+            tag,
+            Some(&order),
+        )?;
+        Ok(records.into_iter().map(from_db_mcp_server).collect())
+    })
+}
+
+/// Get distinct server tags with how many servers carry each, most used first
+pub async fn get_mcp_tag_counts(state: &SqliteDbState) -> Result<Vec<McpTagCountDto>, String> {
+    state.with_conn(|conn| {
+        let counts =
+            db_count_array_elements_nocase(conn, DbTable::McpServer, &JsonFieldPath::new("tags")?)?;
+        Ok(counts
+            .into_iter()
+            .map(|(tag, count)| McpTagCountDto { tag, count })
+            .collect())
+    })
+}
+
 /// Get a single MCP server by ID
 pub async fn get_mcp_server_by_id(
     state: &SqliteDbState,
@@ -267,6 +305,56 @@ pub async fn delete_favorite_mcp(state: &SqliteDbState, id: &str) -> Result<(),
     state.with_conn(|conn| db_delete(conn, DbTable::FavoriteMcp, id).map(|_| ()))
 }
 
+// ==================== Snapshot Restore ====================
+
+/// Replace all MCP servers, favorites and preferences in one transaction.
+/// Used by MCP snapshot restore; record ids are preserved as captured.
+pub async fn replace_all_mcp_data(
+    state: &SqliteDbState,
+    servers: &[McpServer],
+    favorites: &[FavoriteMcp],
+    prefs: &McpPreferences,
+) -> Result<(), String> {
+    let mut favorite_payloads = Vec::with_capacity(favorites.len());
+    for fav in favorites {
+        let mut payload = serde_json::to_value(fav).map_err(|e| e.to_string())?;
+        if let Some(obj) = payload.as_object_mut() {
+            obj.remove("id");
+        }
+        favorite_payloads.push((fav.id.clone(), payload));
+    }
+
+    state.with_conn_mut(|conn| {
+        db_transaction(conn, |tx| {
+            db_delete_all(tx, DbTable::McpServer)?;
+            for server in servers {
+                let id = if server.id.is_empty() {
+                    db_new_id()
+                } else {
+                    server.id.clone()
+                };
+                db_put(
+                    tx,
+                    DbTable::McpServer,
+                    &id,
+                    &to_clean_mcp_server_payload(server),
+                )?;
+            }
+            db_delete_all(tx, DbTable::FavoriteMcp)?;
+            for (id, payload) in &favorite_payloads {
+                let id = if id.is_empty() { db_new_id() } else { id.clone() };
+                db_put(tx, DbTable::FavoriteMcp, &id, payload)?;
+            }
+            db_put(
+                tx,
+                DbTable::McpPreferences,
+                "default",
+                &to_mcp_preferences_payload(prefs),
+            )
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,6 +397,7 @@ mod tests {
             favorites_initialized: true,
             sync_disabled_to_opencode: true,
             limit_add_more_to_preferred_tools: true,
+            env_vars: Default::default(),
             updated_at: 9,
         };
         save_mcp_preferences(&sqlite_state, &prefs)
@@ -341,4 +430,47 @@ mod tests {
         assert_eq!(favorites.len(), 1);
         assert_eq!(favorites[0].name, "Favorite A");
     }
+
+    #[tokio::test]
+    async fn replace_all_mcp_data_swaps_servers_and_keeps_ids() {
+        let sqlite_state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        let mut server = McpServer {
+            id: String::new(),
+            name: "Old".to_string(),
+            server_type: "http".to_string(),
+            server_config: json!({"url": "https://old.example.com/mcp"}),
+            enabled_tools: vec!["claude".to_string()],
+            sync_details: None,
+            description: None,
+            sort_index: 0,
+            created_at: 1,
+            updated_at: 1,
+            user_group: None,
+            user_note: None,
+            tags: Vec::new(),
+            timeout: None,
+        };
+        upsert_mcp_server(&sqlite_state, &server)
+            .await
+            .expect("upsert server");
+
+        server.id = "snapshot-server".to_string();
+        server.name = "Restored".to_string();
+        let prefs = McpPreferences {
+            show_in_tray: true,
+            ..McpPreferences::default()
+        };
+        replace_all_mcp_data(&sqlite_state, &[server], &[], &prefs)
+            .await
+            .expect("replace");
+
+        let servers = get_mcp_servers(&sqlite_state).await.expect("servers");
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].id, "snapshot-server");
+        assert_eq!(servers[0].name, "Restored");
+        assert!(get_mcp_preferences(&sqlite_state)
+            .await
+            .expect("prefs")
+            .show_in_tray);
+    }
 }
@@ -11,6 +11,8 @@ pub mod format_configs;
 pub mod mcp_store;
 pub mod opencode_path;
 pub mod package_version;
+pub mod server_test;
+pub mod snapshot;
 pub mod tray_support;
 pub mod types;
 
@@ -0,0 +1,571 @@
+//! Launch-and-handshake test for a single MCP server (`mcp_test_server`).
+//!
+//! stdio servers are spawned with their configured command, args, env and cwd, sent a
+//! JSON-RPC `initialize` request on stdin and killed once the reply (or the timeout) arrives.
+//! `http` servers get the same request as a Streamable HTTP POST; `sse` servers use the legacy
+//! SSE transport: open the event stream, wait for the `endpoint` event, POST `initialize`
+//! there and read the reply from the stream.
+
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+use super::config_sync::{resolve_stdio_command, stdio_command};
+use super::types::{McpServer, McpServerTestDto};
+use crate::coding::cli_resolver::{apply_create_no_window_tokio, build_local_tokio_command};
+
+/// Used when the server has no `startup_timeout_sec`; npx/uvx servers may download first
+const DEFAULT_HANDSHAKE_TIMEOUT_SECS: u64 = 30;
+const MCP_PROTOCOL_VERSION: &str = "2025-03-26";
+const INITIALIZE_REQUEST_ID: i64 = 1;
+/// Tail of stderr kept for the failure message
+const STDERR_TAIL_CHARS: usize = 500;
+
+/// Handshake details from a valid `initialize` result
+#[derive(Debug, PartialEq, Eq)]
+struct InitializeInfo {
+    protocol_version: String,
+    server_name: Option<String>,
+    server_version: Option<String>,
+}
+
+fn initialize_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": INITIALIZE_REQUEST_ID,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": MCP_PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": {
+                "name": "ai-toolbox",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        },
+    })
+}
+
+/// The server's `startup_timeout_sec` (shared with Codex/Grok), else the default.
+pub(crate) fn handshake_timeout(server: &McpServer) -> Duration {
+    let secs = server
+        .server_config
+        .get("startup_timeout_sec")
+        .and_then(Value::as_u64)
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_HANDSHAKE_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+}
+
+/// `None` for messages that are not the reply to our request (logs, notifications, other
+/// ids); otherwise the parsed result or the reason it is not a valid `initialize` reply.
+fn parse_initialize_response(message: &Value) -> Option<Result<InitializeInfo, String>> {
+    if message.get("id").and_then(Value::as_i64) != Some(INITIALIZE_REQUEST_ID) {
+        return None;
+    }
+    if let Some(error) = message.get("error") {
+        let detail = error
+            .get("message")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string());
+        return Some(Err(format!("Server rejected initialize: {}", detail)));
+    }
+    let result = message.get("result");
+    let Some(protocol_version) = result
+        .and_then(|result| result.get("protocolVersion"))
+        .and_then(Value::as_str)
+    else {
+        return Some(Err(
+            "Initialize response has no result.protocolVersion".to_string()
+        ));
+    };
+    let server_info = result.and_then(|result| result.get("serverInfo"));
+    let info_field = |key: &str| {
+        server_info
+            .and_then(|info| info.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    Some(Ok(InitializeInfo {
+        protocol_version: protocol_version.to_string(),
+        server_name: info_field("name"),
+        server_version: info_field("version"),
+    }))
+}
+
+/// Find the `initialize` reply in a JSON-RPC message or batch.
+fn find_initialize_response(message: &Value) -> Option<Result<InitializeInfo, String>> {
+    match message {
+        Value::Array(items) => items.iter().find_map(parse_initialize_response),
+        _ => parse_initialize_response(message),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SseEvent {
+    event: String,
+    data: String,
+}
+
+/// Drain complete events (terminated by a blank line) from `buffer`, leaving any partial
+/// event in place for the next chunk.
+fn take_sse_events(buffer: &mut String) -> Vec<SseEvent> {
+    if buffer.contains('\r') {
+        *buffer = buffer.replace("\r\n", "\n").replace('\r', "\n");
+    }
+    let mut events = Vec::new();
+    while let Some(end) = buffer.find("\n\n") {
+        let block: String = buffer.drain(..end + 2).collect();
+        let mut event = "message".to_string();
+        let mut data_lines = Vec::new();
+        for line in block.lines() {
+            if let Some(value) = line.strip_prefix("event:") {
+                event = value.trim().to_string();
+            } else if let Some(value) = line.strip_prefix("data:") {
+                data_lines.push(value.strip_prefix(' ').unwrap_or(value));
+            }
+        }
+        if !data_lines.is_empty() {
+            events.push(SseEvent {
+                event,
+                data: data_lines.join("\n"),
+            });
+        }
+    }
+    events
+}
+
+fn stderr_tail(stderr: &str) -> Option<String> {
+    let trimmed = stderr.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let skip = trimmed.chars().count().saturating_sub(STDERR_TAIL_CHARS);
+    Some(trimmed.chars().skip(skip).collect())
+}
+
+/// Kill the server and everything it spawned; npx/uvx run the real server as a child process.
+async fn kill_process_tree(child: &mut tokio::process::Child) {
+    if let Some(pid) = child.id() {
+        #[cfg(unix)]
+        let mut killer = {
+            // The server leads its own process group (see `stdio_handshake`)
+            let mut command = tokio::process::Command::new("kill");
+            command.args(["-KILL", "--", &format!("-{}", pid)]);
+            command
+        };
+        #[cfg(windows)]
+        let mut killer = {
+            let mut command = tokio::process::Command::new("taskkill");
+            command.args(["/T", "/F", "/PID", &pid.to_string()]);
+            command
+        };
+        apply_create_no_window_tokio(&mut killer);
+        let _ = killer.output().await;
+    }
+    let _ = child.kill().await;
+}
+
+async fn stdio_handshake(server: &McpServer, timeout: Duration) -> Result<InitializeInfo, String> {
+    let command = stdio_command(server).ok_or_else(|| "Server has no command".to_string())?;
+    let program = resolve_stdio_command(command).ok_or_else(|| {
+        format!(
+            "Command '{}' was not found on PATH or in common install locations",
+            command
+        )
+    })?;
+
+    let mut process = build_local_tokio_command(&program);
+    let config = &server.server_config;
+    if let Some(args) = config.get("args").and_then(Value::as_array) {
+        process.args(args.iter().filter_map(Value::as_str));
+    }
+    if let Some(env) = config.get("env").and_then(Value::as_object) {
+        for (key, value) in env {
+            if let Some(value) = value.as_str() {
+                process.env(key, value);
+            }
+        }
+    }
+    if let Some(cwd) = config
+        .get("cwd")
+        .and_then(Value::as_str)
+        .filter(|cwd| !cwd.trim().is_empty())
+    {
+        process.current_dir(cwd);
+    }
+    #[cfg(unix)]
+    process.process_group(0);
+    let mut child = process
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start '{}': {}", program.display(), e))?;
+
+    let mut stdin = child.stdin.take().ok_or("Failed to open server stdin")?;
+    let stdout = child.stdout.take().ok_or("Failed to open server stdout")?;
+    let mut stderr = child.stderr.take().ok_or("Failed to open server stderr")?;
+    // Drained concurrently so a chatty server cannot block on a full stderr pipe
+    let stderr_task = tokio::spawn(async move {
+        let mut output = Vec::new();
+        let _ = stderr.read_to_end(&mut output).await;
+        String::from_utf8_lossy(&output).into_owned()
+    });
+
+    // stdin stays open until the process is killed; some servers exit on EOF
+    let handshake = async {
+        let mut line = initialize_request().to_string();
+        line.push('\n');
+        stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write initialize request: {}", e))?;
+        stdin
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to write initialize request: {}", e))?;
+
+        let mut lines = BufReader::new(stdout).lines();
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| format!("Failed to read server output: {}", e))?
+        {
+            // Non-JSON lines are stray logs on stdout; keep waiting for the reply
+            let Ok(message) = serde_json::from_str::<Value>(line.trim()) else {
+                continue;
+            };
+            if let Some(result) = find_initialize_response(&message) {
+                return result;
+            }
+        }
+        Err("Server exited before answering initialize".to_string())
+    };
+    let result = match tokio::time::timeout(timeout, handshake).await {
+        Ok(result) => result,
+        Err(_) => Err(format!(
+            "No initialize response within {}s",
+            timeout.as_secs()
+        )),
+    };
+
+    kill_process_tree(&mut child).await;
+    let stderr = tokio::time::timeout(Duration::from_secs(1), stderr_task)
+        .await
+        .ok()
+        .and_then(|joined| joined.ok())
+        .unwrap_or_default();
+    result.map_err(|error| match stderr_tail(&stderr) {
+        Some(tail) => format!("{}\nstderr: {}", error, tail),
+        None => error,
+    })
+}
+
+fn apply_server_headers(
+    mut request: reqwest::RequestBuilder,
+    server: &McpServer,
+) -> reqwest::RequestBuilder {
+    if let Some(headers) = server
+        .server_config
+        .get("headers")
+        .and_then(Value::as_object)
+    {
+        for (key, value) in headers {
+            if let Some(value) = value.as_str() {
+                request = request.header(key.as_str(), value);
+            }
+        }
+    }
+    request
+}
+
+fn server_url(server: &McpServer) -> Result<reqwest::Url, String> {
+    let url = server
+        .server_config
+        .get("url")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| "Server has no url".to_string())?;
+    reqwest::Url::parse(url).map_err(|e| format!("Invalid url '{}': {}", url, e))
+}
+
+async fn error_status_message(response: reqwest::Response) -> String {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let body = body.trim();
+    if body.is_empty() {
+        format!("HTTP {}", status)
+    } else {
+        format!(
+            "HTTP {}: {}",
+            status,
+            body.chars().take(300).collect::<String>()
+        )
+    }
+}
+
+/// Read SSE events until one carries the `initialize` reply.
+async fn read_initialize_from_sse(
+    response: &mut reqwest::Response,
+    buffer: &mut String,
+) -> Result<InitializeInfo, String> {
+    loop {
+        for event in take_sse_events(buffer) {
+            if event.event != "message" {
+                continue;
+            }
+            if let Some(result) = serde_json::from_str::<Value>(&event.data)
+                .ok()
+                .as_ref()
+                .and_then(find_initialize_response)
+            {
+                return result;
+            }
+        }
+        let chunk = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read event stream: {}", e))?
+            .ok_or_else(|| "Event stream closed before the initialize response".to_string())?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+    }
+}
+
+/// Streamable HTTP: POST `initialize`; the reply is either JSON or an SSE stream.
+async fn http_handshake(
+    client: &reqwest::Client,
+    server: &McpServer,
+) -> Result<InitializeInfo, String> {
+    let url = server_url(server)?;
+    let request = client
+        .post(url.clone())
+        .header("Accept", "application/json, text/event-stream")
+        .json(&initialize_request());
+    let mut response = apply_server_headers(request, server)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(error_status_message(response).await);
+    }
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let is_event_stream = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+
+    let result = if is_event_stream {
+        read_initialize_from_sse(&mut response, &mut String::new()).await
+    } else {
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+        serde_json::from_str::<Value>(&body)
+            .ok()
+            .as_ref()
+            .and_then(find_initialize_response)
+            .unwrap_or_else(|| {
+                Err(format!(
+                    "Response is not an initialize result: {}",
+                    body.chars().take(300).collect::<String>()
+                ))
+            })
+    };
+
+    // Best effort: end the session we just opened
+    if let Some(session_id) = session_id {
+        let request = client.delete(url).header("Mcp-Session-Id", session_id);
+        let _ = apply_server_headers(request, server).send().await;
+    }
+    result
+}
+
+/// Legacy SSE transport: GET the stream, POST to the announced endpoint, read the reply.
+async fn sse_handshake(
+    client: &reqwest::Client,
+    server: &McpServer,
+) -> Result<InitializeInfo, String> {
+    let url = server_url(server)?;
+    let request = client
+        .get(url.clone())
+        .header("Accept", "text/event-stream");
+    let mut stream = apply_server_headers(request, server)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !stream.status().is_success() {
+        return Err(error_status_message(stream).await);
+    }
+
+    let mut buffer = String::new();
+    let endpoint = 'endpoint: loop {
+        for event in take_sse_events(&mut buffer) {
+            if event.event == "endpoint" {
+                break 'endpoint url
+                    .join(event.data.trim())
+                    .map_err(|e| format!("Invalid endpoint '{}': {}", event.data, e))?;
+            }
+        }
+        let chunk = stream
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read event stream: {}", e))?
+            .ok_or_else(|| "Event stream closed before announcing an endpoint".to_string())?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+    };
+
+    let request = client.post(endpoint).json(&initialize_request());
+    let response = apply_server_headers(request, server)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(error_status_message(response).await);
+    }
+    read_initialize_from_sse(&mut stream, &mut buffer).await
+}
+
+/// Run the `initialize` handshake against `server` (env placeholders already expanded).
+pub(crate) async fn test_server(
+    client: Option<&reqwest::Client>,
+    server: &McpServer,
+) -> McpServerTestDto {
+    let timeout = handshake_timeout(server);
+    let started = Instant::now();
+    let result = match (server.server_type.as_str(), client) {
+        ("stdio", _) => stdio_handshake(server, timeout).await,
+        ("http" | "sse", Some(client)) => {
+            let handshake = async {
+                if server.server_type == "sse" {
+                    sse_handshake(client, server).await
+                } else {
+                    http_handshake(client, server).await
+                }
+            };
+            tokio::time::timeout(timeout, handshake)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(format!(
+                        "No initialize response within {}s",
+                        timeout.as_secs()
+                    ))
+                })
+        }
+        ("http" | "sse", None) => Err("No HTTP client available".to_string()),
+        (other, _) => Err(format!("Unsupported server type: {}", other)),
+    };
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(info) => McpServerTestDto {
+            server_id: server.id.clone(),
+            success: true,
+            server_name: info.server_name,
+            server_version: info.server_version,
+            protocol_version: Some(info.protocol_version),
+            elapsed_ms,
+            message: None,
+        },
+        Err(message) => McpServerTestDto {
+            server_id: server.id.clone(),
+            success: false,
+            server_name: None,
+            server_version: None,
+            protocol_version: None,
+            elapsed_ms,
+            message: Some(message),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stdio_server(script: &str) -> McpServer {
+        McpServer {
+            id: "test".to_string(),
+            name: "test".to_string(),
+            server_type: "stdio".to_string(),
+            server_config: json!({
+                "command": "sh",
+                "args": ["-c", script],
+                "startup_timeout_sec": 5,
+            }),
+            enabled_tools: Vec::new(),
+            sync_details: None,
+            description: None,
+            user_group: None,
+            user_note: None,
+            tags: Vec::new(),
+            timeout: None,
+            sort_index: 0,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn sse_events_and_initialize_replies_are_parsed() {
+        let mut buffer =
+            "event: endpoint\r\ndata: /messages?session=1\r\n\r\n: ping\n\ndata: {\"id\""
+                .to_string();
+        assert_eq!(
+            take_sse_events(&mut buffer),
+            vec![SseEvent {
+                event: "endpoint".to_string(),
+                data: "/messages?session=1".to_string(),
+            }]
+        );
+        assert_eq!(buffer, "data: {\"id\"");
+
+        let reply = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "protocolVersion": "2025-03-26",
+                "serverInfo": { "name": "everything", "version": "1.2.0" },
+            },
+        });
+        assert_eq!(
+            find_initialize_response(&json!([{ "jsonrpc": "2.0", "method": "log" }, reply])),
+            Some(Ok(InitializeInfo {
+                protocol_version: "2025-03-26".to_string(),
+                server_name: Some("everything".to_string()),
+                server_version: Some("1.2.0".to_string()),
+            }))
+        );
+        assert!(parse_initialize_response(&json!({ "id": 2, "result": {} })).is_none());
+        assert!(matches!(
+            parse_initialize_response(&json!({ "id": 1, "error": { "message": "bad" } })),
+            Some(Err(message)) if message.contains("bad")
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stdio_server_is_launched_and_answers_initialize() {
+        let server = stdio_server(
+            r#"read line; echo starting >&2; echo 'not json'; echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","serverInfo":{"name":"demo","version":"0.1.0"}}}'; sleep 30"#,
+        );
+        let result = test_server(None, &server).await;
+        assert!(result.success, "{:?}", result.message);
+        assert_eq!(result.server_name.as_deref(), Some("demo"));
+        assert_eq!(result.server_version.as_deref(), Some("0.1.0"));
+
+        let result = test_server(None, &stdio_server("echo boom >&2; exit 1")).await;
+        assert!(!result.success);
+        assert!(result.message.unwrap().contains("boom"));
+    }
+}
@@ -0,0 +1,113 @@
+//! MCP-only configuration snapshots
+//!
+//! Snapshots capture MCP servers, favorites and preferences as JSON files under
+//! `<app data>/mcp-snapshots/`, independent of full backups, so users can try a
+//! different MCP setup and roll back without touching provider data.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{FavoriteMcp, McpPreferences, McpServer};
+
+const SNAPSHOT_DIR_NAME: &str = "mcp-snapshots";
+
+/// Serialized MCP snapshot file content
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct McpSnapshot {
+    pub id: String,
+    pub created_at: i64,
+    pub servers: Vec<McpServer>,
+    #[serde(default)]
+    pub favorites: Vec<FavoriteMcp>,
+    #[serde(default)]
+    pub preferences: McpPreferences,
+}
+
+pub fn snapshot_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(SNAPSHOT_DIR_NAME)
+}
+
+/// Snapshot ids are generated by us; reject anything that could escape the
+/// snapshot directory when used as a file name.
+fn validate_snapshot_id(id: &str) -> Result<(), String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid MCP snapshot id: {id}"));
+    }
+    Ok(())
+}
+
+fn snapshot_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    validate_snapshot_id(id)?;
+    Ok(dir.join(format!("{id}.json")))
+}
+
+pub fn write_snapshot(dir: &Path, snapshot: &McpSnapshot) -> Result<PathBuf, String> {
+    let path = snapshot_path(dir, &snapshot.id)?;
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create MCP snapshot directory: {}", e))?;
+    let content = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| format!("Failed to serialize MCP snapshot: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write MCP snapshot: {}", e))?;
+    Ok(path)
+}
+
+pub fn read_snapshot(dir: &Path, id: &str) -> Result<McpSnapshot, String> {
+    let path = snapshot_path(dir, id)?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read MCP snapshot '{}': {}", id, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse MCP snapshot '{}': {}", id, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn server(name: &str) -> McpServer {
+        McpServer {
+            id: format!("id-{name}"),
+            name: name.to_string(),
+            server_type: "stdio".to_string(),
+            server_config: json!({"command": "npx", "args": ["-y", name]}),
+            enabled_tools: vec!["claude_code".to_string()],
+            sync_details: None,
+            description: None,
+            sort_index: 0,
+            created_at: 1,
+            updated_at: 1,
+            user_group: None,
+            user_note: None,
+            tags: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_file() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = snapshot_dir(temp.path());
+        let snapshot = McpSnapshot {
+            id: "20260101-abc".to_string(),
+            created_at: 42,
+            servers: vec![server("fetch")],
+            favorites: Vec::new(),
+            preferences: McpPreferences::default(),
+        };
+
+        write_snapshot(&dir, &snapshot).expect("write");
+        let restored = read_snapshot(&dir, "20260101-abc").expect("read");
+
+        assert_eq!(restored.created_at, 42);
+        assert_eq!(restored.servers.len(), 1);
+        assert_eq!(restored.servers[0].name, "fetch");
+    }
+
+    #[test]
+    fn snapshot_id_cannot_escape_directory() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        assert!(read_snapshot(temp.path(), "../settings").is_err());
+        assert!(read_snapshot(temp.path(), "").is_err());
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Contains types for MCP server configuration and synchronization.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -86,7 +88,7 @@ pub struct McpServer {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct McpSyncDetail {
     pub tool: String,
-    pub status: String, // "ok" | "error" | "pending"
+    pub status: String, // "ok" | "warning" | "error" | "pending"
     pub synced_at: Option<i64>,
     pub error_message: Option<String>,
 }
@@ -158,6 +160,9 @@ pub struct McpPreferences {
     pub sync_disabled_to_opencode: bool,
     #[serde(default)]
     pub limit_add_more_to_preferred_tools: bool,
+    /// User-defined values for `${VAR}` placeholders, checked before the process environment
+    #[serde(default)]
+    pub env_vars: BTreeMap<String, String>,
     pub updated_at: i64,
 }
 
@@ -170,6 +175,7 @@ impl Default for McpPreferences {
             favorites_initialized: false,
             sync_disabled_to_opencode: false,
             limit_add_more_to_preferred_tools: false,
+            env_vars: BTreeMap::new(),
             updated_at: 0,
         }
     }
@@ -183,6 +189,15 @@ pub struct McpSyncResultDto {
     pub error_message: Option<String>,
 }
 
+/// Per-server outcome of enabling or disabling a tool for all servers
+#[derive(Debug, Serialize)]
+pub struct McpServerToolResultDto {
+    pub server_id: String,
+    pub server_name: String,
+    pub success: bool,
+    pub error_message: Option<String>,
+}
+
 /// Import result
 #[derive(Debug, Serialize)]
 pub struct McpImportResultDto {
@@ -202,12 +217,125 @@ pub struct McpDiscoveredServerDto {
     pub server_config: Value,
 }
 
+/// Result of reconciling Codex `config.toml` `mcp_servers` with managed servers
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct McpReconcileReport {
+    /// Unmanaged Codex servers taken into management (or that would be, when `adopted` is false)
+    pub added: Vec<String>,
+    /// Managed servers whose Codex entry has the same config
+    pub matched: Vec<String>,
+    /// Codex entries whose config differs from the managed server of the same name; untouched
+    pub conflicting: Vec<String>,
+    pub adopted: bool,
+}
+
+/// On-disk state of a server's entry in one enabled tool's config file
+#[derive(Debug, Serialize)]
+pub struct McpSyncDriftDto {
+    pub tool: String,
+    /// "in-sync" | "missing" | "drifted" | "error"
+    pub status: String,
+    /// Dotted paths of the entry fields that differ from what AI Toolbox would write
+    pub differing_keys: Vec<String>,
+    pub error_message: Option<String>,
+}
+
+/// Local availability of a stdio server's command (for the per-server status dot)
+#[derive(Debug, Serialize)]
+pub struct McpCommandCheckDto {
+    pub server_id: String,
+    /// None for http/sse servers, which have nothing to resolve
+    pub command: Option<String>,
+    pub available: bool,
+    pub resolved_path: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Result of launching an MCP server and running the `initialize` handshake
+#[derive(Debug, Serialize)]
+pub struct McpServerTestDto {
+    pub server_id: String,
+    /// A valid `initialize` result arrived within the timeout
+    pub success: bool,
+    /// `serverInfo` from the handshake
+    pub server_name: Option<String>,
+    pub server_version: Option<String>,
+    pub protocol_version: Option<String>,
+    pub elapsed_ms: u64,
+    /// Why the test failed, with the tail of stderr for stdio servers
+    pub message: Option<String>,
+}
+
 /// Scan result for discovered MCP servers
 #[derive(Debug, Serialize)]
 pub struct McpScanResultDto {
     pub total_tools_scanned: i32,
     pub total_servers_found: i32,
     pub servers: Vec<McpDiscoveredServerDto>,
+    /// Sources that failed or timed out; their servers are missing from `servers`
+    pub skipped: Vec<McpScanSkippedSourceDto>,
+}
+
+/// A scan source left out of a partial scan result
+#[derive(Debug, Serialize)]
+pub struct McpScanSkippedSourceDto {
+    pub tool_key: String,
+    pub reason: String,
+}
+
+/// A server entry in a tool's MCP config whose name matches no server in the database
+#[derive(Debug, Serialize)]
+pub struct McpOrphanDto {
+    pub tool_key: String,
+    pub tool_name: String,
+    pub server_name: String,
+    pub config_path: String,
+}
+
+/// Result of `mcp_find_orphans`
+#[derive(Debug, Serialize)]
+pub struct McpOrphanScanDto {
+    pub orphans: Vec<McpOrphanDto>,
+    /// Tool configs that could not be read
+    pub skipped: Vec<McpScanSkippedSourceDto>,
+}
+
+/// An orphaned entry chosen for removal
+#[derive(Clone, Debug, Deserialize)]
+pub struct McpOrphanSelectionDto {
+    pub tool_key: String,
+    pub server_name: String,
+}
+
+/// Per-entry outcome of `mcp_remove_orphans`
+#[derive(Debug, Serialize)]
+pub struct McpOrphanRemovalDto {
+    pub tool_key: String,
+    pub server_name: String,
+    pub success: bool,
+    pub error_message: Option<String>,
+}
+
+/// A server tag and how many servers carry it, from `mcp_list_all_tags`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct McpTagCountDto {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// Tool entry inside a shared MCP config path group
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedPathTool {
+    pub key: String,
+    pub display_name: String,
+    pub is_custom: bool,
+}
+
+/// MCP config file resolved to the same path by more than one tool
+#[derive(Debug, Serialize)]
+pub struct SharedPathGroup {
+    pub config_path: String,
+    pub tools: Vec<SharedPathTool>,
 }
 
 /// Package manager family used to resolve an MCP stdio runner package version.
@@ -1,32 +1,54 @@
 pub mod all_api_hub;
+pub mod apply_history;
+pub mod cache_clear;
 pub mod cc_switch;
 pub mod claude_code;
 pub mod cli_resolver;
 pub mod codex;
+pub mod config_backup;
 pub mod config_cleanup;
+pub mod config_provenance;
+pub mod config_watcher;
+pub mod config_writability;
 pub mod gemini_cli;
+pub mod global_headers;
+pub mod global_search;
 pub mod grok;
 pub mod image;
 pub mod magic_context;
 pub mod mcp;
+pub mod model_benchmark;
+pub mod model_validation;
 pub mod oh_my_openagent;
 pub mod oh_my_opencode_slim;
 pub mod open_claw;
 pub mod open_code;
 pub mod pi;
+pub mod pinned_providers;
 pub mod preset_models;
+pub mod provider_csv;
+pub mod provider_health;
 pub mod proxy_gateway;
 pub mod reapply_applied_runtime;
 pub mod runtime_location;
+pub mod secret_store;
 pub mod session_manager;
+pub mod setup_script;
 pub mod skills;
 pub mod ssh;
+pub mod sync_mappings;
+pub mod sync_performance;
+pub mod sync_trace;
+pub mod tls_inspect;
+pub mod tool_limits;
 pub mod tools;
 pub(crate) mod url_utils;
 pub mod wsl;
 
+pub(crate) mod atomic_write;
 mod db_id;
 pub(crate) mod file_io;
+pub(crate) mod jsonc_edit;
 #[cfg(test)]
 pub(crate) mod test_env {
     use std::sync::{LazyLock, Mutex, MutexGuard};
@@ -0,0 +1,273 @@
+//! Rank the providers that serve one model by how fast they answer it.
+//!
+//! Candidates come from the same sources the rest of the app uses: the OpenCode unified model
+//! list for "opencode", and the providers' configured or listed models (see
+//! `model_validation`) for "claude" and "codex". Each candidate gets one streaming
+//! connectivity test through the shared connectivity runner, so headers, proxies and
+//! auth.json credentials resolve exactly as in a manual test.
+
+use futures_util::StreamExt;
+use serde::Serialize;
+use tauri::State;
+
+use crate::coding::model_validation;
+use crate::coding::open_code::models_api::{
+    test_provider_model_connectivity_internal, ConnectivityTestRequest, ConnectivityTestResult,
+};
+use crate::coding::open_code::{self, ReadConfigResult};
+use crate::db::SqliteDbState;
+
+const BENCHMARK_PROMPT: &str = "say hi!";
+const BENCHMARK_TIMEOUT_SECS: u64 = 30;
+const BENCHMARK_CONCURRENCY: usize = 5;
+
+/// Connectivity result for one provider serving the benchmarked model
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderModelBenchmark {
+    pub provider_id: String,
+    /// Model id sent to the provider (the base model for OpenCode mode variants)
+    pub model_id: String,
+    /// "success" | "error" | "timeout"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_byte_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_per_second: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+struct BenchmarkCandidate {
+    provider_id: String,
+    request: ConnectivityTestRequest,
+}
+
+/// Single-model streaming test request; endpoint fields are filled in by the caller.
+fn connectivity_request(
+    npm: &str,
+    provider_id: &str,
+    model_id: &str,
+    tool: Option<&str>,
+) -> ConnectivityTestRequest {
+    ConnectivityTestRequest {
+        npm: npm.to_string(),
+        provider_id: Some(provider_id.to_string()),
+        base_url: String::new(),
+        api_key: None,
+        reasoning_effort: None,
+        headers: None,
+        prompt: BENCHMARK_PROMPT.to_string(),
+        temperature: None,
+        max_tokens: None,
+        max_output_tokens: None,
+        stream: Some(true),
+        body: None,
+        model_ids: vec![model_id.to_string()],
+        timeout_secs: Some(BENCHMARK_TIMEOUT_SECS),
+        concurrency: None,
+        tool: tool.map(str::to_string),
+        proxy_url: None,
+        max_retries: None,
+    }
+}
+
+async fn opencode_candidates(
+    state: State<'_, SqliteDbState>,
+    model_id: &str,
+) -> Result<Vec<BenchmarkCandidate>, String> {
+    let custom_providers = match open_code::read_opencode_config(state.clone()).await? {
+        ReadConfigResult::Success { config } => config.provider,
+        _ => None,
+    };
+    let auth_channels = open_code::free_models::read_auth_channels();
+    let models = open_code::free_models::get_unified_models(
+        &state,
+        custom_providers.as_ref(),
+        &auth_channels,
+    )
+    .await;
+
+    let mut candidates: Vec<BenchmarkCandidate> = Vec::new();
+    for model in models.iter().filter(|model| model.model_id == model_id) {
+        if candidates
+            .iter()
+            .any(|candidate| candidate.provider_id == model.provider_id)
+        {
+            continue;
+        }
+        let request_model = model.base_model_id.as_deref().unwrap_or(&model.model_id);
+        let custom = custom_providers
+            .as_ref()
+            .and_then(|providers| providers.get(&model.provider_id));
+        let options = custom.and_then(|provider| provider.options.as_ref());
+        // Empty base URL / key are resolved from models.dev and auth.json by provider id.
+        let npm = custom
+            .and_then(|provider| provider.npm.clone())
+            .or_else(|| open_code::free_models::resolve_provider_npm(&model.provider_id))
+            .unwrap_or_else(|| "@ai-sdk/openai-compatible".to_string());
+        let mut request =
+            connectivity_request(&npm, &model.provider_id, request_model, Some("opencode"));
+        if let Some(options) = options {
+            request.base_url = options.base_url.clone().unwrap_or_default();
+            request.api_key = options.api_key.clone();
+            request.headers = options.headers.clone();
+        }
+        candidates.push(BenchmarkCandidate {
+            provider_id: model.provider_id.clone(),
+            request,
+        });
+    }
+    Ok(candidates)
+}
+
+async fn claude_or_codex_candidates(
+    db: &SqliteDbState,
+    tool: &str,
+    model_id: &str,
+) -> Result<Vec<BenchmarkCandidate>, String> {
+    let (npm, headers_tool) = match tool {
+        "claude" => ("@ai-sdk/anthropic", Some("claude")),
+        // Codex has no tool-level global headers.
+        _ => ("@ai-sdk/openai", None),
+    };
+    Ok(
+        model_validation::find_providers_serving_model(db, tool, model_id)
+            .await?
+            .into_iter()
+            .map(|endpoint| {
+                let mut request =
+                    connectivity_request(npm, &endpoint.provider_id, model_id, headers_tool);
+                request.base_url = endpoint.base_url;
+                request.api_key = endpoint.api_key;
+                BenchmarkCandidate {
+                    provider_id: endpoint.provider_id,
+                    request,
+                }
+            })
+            .collect(),
+    )
+}
+
+fn benchmark_from_result(
+    provider_id: String,
+    result: ConnectivityTestResult,
+) -> ProviderModelBenchmark {
+    ProviderModelBenchmark {
+        provider_id,
+        model_id: result.model_id,
+        status: result.status,
+        first_byte_ms: result.first_byte_ms,
+        total_ms: result.total_ms,
+        tokens_per_second: result.tokens_per_second,
+        error_message: result.error_message,
+    }
+}
+
+/// Successful providers first, fastest first byte first (total time breaks ties);
+/// failed providers follow in provider id order.
+fn rank_benchmarks(benchmarks: &mut [ProviderModelBenchmark]) {
+    benchmarks.sort_by(|a, b| {
+        let a_failed = a.status != "success";
+        let b_failed = b.status != "success";
+        a_failed
+            .cmp(&b_failed)
+            .then_with(|| {
+                if a_failed {
+                    std::cmp::Ordering::Equal
+                } else {
+                    (a.first_byte_ms, a.total_ms).cmp(&(b.first_byte_ms, b.total_ms))
+                }
+            })
+            .then_with(|| a.provider_id.cmp(&b.provider_id))
+    });
+}
+
+/// Test `model_id` against every provider of `tool` ("opencode" | "claude" | "codex") that
+/// offers it and return the results ranked by latency. Providers without the model are skipped.
+#[tauri::command]
+pub async fn benchmark_model_across_providers(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+    model_id: String,
+) -> Result<Vec<ProviderModelBenchmark>, String> {
+    let model_id = model_id.trim().to_string();
+    if model_id.is_empty() {
+        return Err("Model id is required".to_string());
+    }
+    let candidates = match tool.as_str() {
+        "opencode" => opencode_candidates(state.clone(), &model_id).await?,
+        "claude" | "codex" => claude_or_codex_candidates(&state, &tool, &model_id).await?,
+        other => return Err(format!("Model benchmark is not supported for '{}'", other)),
+    };
+
+    let db: &SqliteDbState = &state;
+    let mut benchmarks: Vec<ProviderModelBenchmark> = futures_util::stream::iter(candidates)
+        .map(|candidate| async move {
+            let provider_id = candidate.provider_id;
+            let model_id = candidate.request.model_ids[0].clone();
+            match test_provider_model_connectivity_internal(db, candidate.request).await {
+                Ok(response) => response
+                    .results
+                    .into_iter()
+                    .next()
+                    .map(|result| benchmark_from_result(provider_id.clone(), result)),
+                Err(error) => Some(ProviderModelBenchmark {
+                    provider_id: provider_id.clone(),
+                    model_id,
+                    status: "error".to_string(),
+                    first_byte_ms: None,
+                    total_ms: None,
+                    tokens_per_second: None,
+                    error_message: Some(error),
+                }),
+            }
+        })
+        .buffer_unordered(BENCHMARK_CONCURRENCY)
+        .filter_map(|benchmark| async move { benchmark })
+        .collect()
+        .await;
+    rank_benchmarks(&mut benchmarks);
+    Ok(benchmarks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn benchmark(
+        provider_id: &str,
+        status: &str,
+        first_byte_ms: Option<u64>,
+    ) -> ProviderModelBenchmark {
+        ProviderModelBenchmark {
+            provider_id: provider_id.to_string(),
+            model_id: "gpt-5".to_string(),
+            status: status.to_string(),
+            first_byte_ms,
+            total_ms: first_byte_ms.map(|ms| ms + 100),
+            tokens_per_second: None,
+            error_message: None,
+        }
+    }
+
+    #[test]
+    fn successful_providers_rank_by_latency_before_failures() {
+        let mut benchmarks = vec![
+            benchmark("zeta", "error", None),
+            benchmark("slow", "success", Some(900)),
+            benchmark("alpha", "timeout", None),
+            benchmark("fast", "success", Some(120)),
+        ];
+
+        rank_benchmarks(&mut benchmarks);
+
+        let order: Vec<&str> = benchmarks
+            .iter()
+            .map(|benchmark| benchmark.provider_id.as_str())
+            .collect();
+        assert_eq!(order, vec!["fast", "slow", "alpha", "zeta"]);
+    }
+}
@@ -0,0 +1,710 @@
+//! Check that the models a provider references are actually served by its endpoint.
+//!
+//! The provider's model list is fetched through the shared provider models cache and reused for
+//! a few minutes, so validating several providers on the same gateway only lists once.
+//! Endpoints that cannot be listed (official accounts, relays without `/models`) yield an
+//! "unverified" issue per referenced model instead of an error.
+//!
+//! The same endpoint probe backs the optional startup check of the applied providers, which
+//! emits `applied-provider-unreachable` so the UI can prompt to switch. The tools in
+//! `REACHABILITY_TOOLS` (Claude Code, Codex, Gemini CLI and Grok) are checked; the probe always
+//! bypasses the cached model list so a dead endpoint is not hidden by a recent success.
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{Emitter, Manager, State};
+
+use crate::coding::open_code::models_api::{ApiType, FetchModelsRequest};
+use crate::coding::open_code::models_cache;
+use crate::coding::{claude_code, codex, db_extract_id, gemini_cli, grok};
+use crate::db::helpers::{db_get, db_list};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+/// Max age of a cached model list reused by validation and endpoint checks
+const MODEL_LIST_MAX_AGE_SECS: u64 = 300;
+/// Tools whose providers can be probed (applied-provider and periodic health checks): the ones
+/// whose providers store a base URL and key the model list request can use. OpenCode and
+/// OpenClaw providers are never probed, so an unreachable one is not reported.
+pub(crate) const REACHABILITY_TOOLS: [&str; 4] = ["claude", "codex", "geminicli", "grok"];
+const LOCAL_PROVIDER_ID: &str = "__local__";
+const CLAUDE_MODEL_FIELDS: [(&str, &str); 4] = [
+    ("model", "ANTHROPIC_MODEL"),
+    ("haikuModel", "ANTHROPIC_DEFAULT_HAIKU_MODEL"),
+    ("sonnetModel", "ANTHROPIC_DEFAULT_SONNET_MODEL"),
+    ("opusModel", "ANTHROPIC_DEFAULT_OPUS_MODEL"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelIssue {
+    /// Provider field that references the model, e.g. "sonnetModel".
+    pub field: String,
+    pub model: String,
+    pub status: String, // "missing" | "unverified"
+    pub message: String,
+}
+
+/// An applied provider whose endpoint did not answer.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnreachableProvider {
+    pub tool: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub base_url: String,
+    pub error: String,
+}
+
+#[derive(Debug, Default)]
+struct ProviderModelRefs {
+    base_url: Option<String>,
+    api_key: Option<String>,
+    sdk_type: Option<String>,
+    models: Vec<(String, String)>,
+}
+
+fn non_empty(value: Option<&Value>) -> Option<String> {
+    value
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+fn claude_model_refs(settings: &Value) -> ProviderModelRefs {
+    let env = settings.get("env");
+    let env_value = |key: &str| non_empty(env.and_then(|env| env.get(key)));
+    let models = CLAUDE_MODEL_FIELDS
+        .iter()
+        .filter_map(|(field, env_key)| {
+            env_value(env_key)
+                .or_else(|| non_empty(settings.get(*field)))
+                .map(|model| (field.to_string(), model))
+        })
+        .collect();
+    ProviderModelRefs {
+        // Claude base URLs omit the version segment the models endpoint needs.
+        base_url: env_value("ANTHROPIC_BASE_URL")
+            .map(|url| format!("{}/v1", url.trim_end_matches('/').trim_end_matches("/v1"))),
+        api_key: env_value("ANTHROPIC_AUTH_TOKEN").or_else(|| env_value("ANTHROPIC_API_KEY")),
+        sdk_type: Some("@ai-sdk/anthropic".to_string()),
+        models,
+    }
+}
+
+fn codex_model_refs(settings: &Value) -> Result<ProviderModelRefs, String> {
+    let config = settings.get("config").and_then(Value::as_str).unwrap_or("");
+    let document: toml_edit::DocumentMut = config
+        .parse()
+        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    let model = document.get("model").and_then(|item| item.as_str());
+    let base_url = document
+        .get("model_provider")
+        .and_then(|item| item.as_str())
+        .and_then(|provider_key| document.get("model_providers")?.get(provider_key))
+        .and_then(|provider| provider.get("base_url"))
+        .and_then(|item| item.as_str())
+        .map(str::to_string);
+    Ok(ProviderModelRefs {
+        base_url,
+        api_key: non_empty(
+            settings
+                .get("auth")
+                .and_then(|auth| auth.get("OPENAI_API_KEY")),
+        ),
+        sdk_type: None,
+        models: model
+            .map(|model| vec![("model".to_string(), model.to_string())])
+            .unwrap_or_default(),
+    })
+}
+
+/// Gemini CLI providers keep their gateway in `env`; without `GOOGLE_GEMINI_BASE_URL` they use
+/// the official endpoint.
+fn gemini_cli_model_refs(settings: &Value) -> ProviderModelRefs {
+    let env = settings.get("env");
+    let env_value = |key: &str| non_empty(env.and_then(|env| env.get(key)));
+    ProviderModelRefs {
+        base_url: env_value("GOOGLE_GEMINI_BASE_URL"),
+        api_key: env_value("GEMINI_API_KEY").or_else(|| env_value("GOOGLE_API_KEY")),
+        sdk_type: Some("@ai-sdk/google".to_string()),
+        models: env_value("GEMINI_MODEL")
+            .map(|model| vec![("model".to_string(), model)])
+            .unwrap_or_default(),
+    }
+}
+
+/// Grok custom providers list their models in `modelCatalog`; the default model's base URL
+/// (or the first one) is probed with its own key, falling back to `auth.API_KEY`.
+fn grok_model_refs(settings: &Value) -> ProviderModelRefs {
+    let catalog = settings
+        .pointer("/modelCatalog/models")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let default_key = non_empty(settings.get("defaultModelKey"));
+    let probed = catalog
+        .iter()
+        .find(|model| default_key.is_some() && non_empty(model.get("key")) == default_key)
+        .or_else(|| catalog.first());
+    ProviderModelRefs {
+        base_url: probed.and_then(|model| non_empty(model.get("baseUrl"))),
+        api_key: probed
+            .and_then(|model| non_empty(model.get("apiKey")))
+            .or_else(|| non_empty(settings.pointer("/auth/API_KEY"))),
+        sdk_type: None,
+        models: catalog
+            .iter()
+            .filter_map(|model| non_empty(model.get("model")))
+            .map(|model| ("model".to_string(), model))
+            .collect(),
+    }
+}
+
+fn load_model_refs(
+    db: &SqliteDbState,
+    tool: &str,
+    provider_id: &str,
+) -> Result<ProviderModelRefs, String> {
+    load_model_refs_if_exists(db, tool, provider_id)?
+        .ok_or_else(|| "Provider not found".to_string())
+}
+
+fn provider_table(tool: &str) -> Result<DbTable, String> {
+    match tool {
+        "claude" => Ok(DbTable::ClaudeProvider),
+        "codex" => Ok(DbTable::CodexProvider),
+        "geminicli" => Ok(DbTable::GeminiCliProvider),
+        "grok" => Ok(DbTable::GrokProvider),
+        other => Err(format!("Model validation is not supported for '{}'", other)),
+    }
+}
+
+fn load_model_refs_if_exists(
+    db: &SqliteDbState,
+    tool: &str,
+    provider_id: &str,
+) -> Result<Option<ProviderModelRefs>, String> {
+    let table = provider_table(tool)?;
+    let Some(record) = db.with_conn(|conn| db_get(conn, table, provider_id))? else {
+        return Ok(None);
+    };
+    let settings_config = match tool {
+        "claude" => claude_code::adapter::from_db_value_provider(record).settings_config,
+        "geminicli" => gemini_cli::adapter::from_db_value_provider(record).settings_config,
+        "grok" => grok::adapter::provider_from_db_value(record).settings_config,
+        _ => codex::adapter::from_db_value_provider(record).settings_config,
+    };
+    let settings: Value = serde_json::from_str(&settings_config)
+        .map_err(|e| format!("Failed to parse provider config: {}", e))?;
+    match tool {
+        "claude" => Ok(Some(claude_model_refs(&settings))),
+        "geminicli" => Ok(Some(gemini_cli_model_refs(&settings))),
+        "grok" => Ok(Some(grok_model_refs(&settings))),
+        _ => codex_model_refs(&settings).map(Some),
+    }
+}
+
+/// Base URL of a Claude or Codex provider, looked up by id across both tables.
+pub(crate) fn find_provider_base_url(
+    db: &SqliteDbState,
+    provider_id: &str,
+) -> Result<String, String> {
+    for tool in ["claude", "codex"] {
+        if let Some(refs) = load_model_refs_if_exists(db, tool, provider_id)? {
+            return refs
+                .base_url
+                .ok_or_else(|| "Provider has no base URL".to_string());
+        }
+    }
+    Err("Provider not found".to_string())
+}
+
+/// Base URL from a Claude or Codex provider's `settings_config`; `None` when unset or unparsable.
+pub(crate) fn provider_config_base_url(tool: &str, settings_config: &str) -> Option<String> {
+    let settings: Value = serde_json::from_str(settings_config).ok()?;
+    match tool {
+        "claude" => claude_model_refs(&settings).base_url,
+        _ => codex_model_refs(&settings).ok()?.base_url,
+    }
+}
+
+/// Model ids served at the provider's base URL; `force_refresh` bypasses a fresh cached list
+/// and replaces it.
+async fn list_provider_model_ids(
+    db: &SqliteDbState,
+    tool: &str,
+    provider_id: &str,
+    refs: &ProviderModelRefs,
+    force_refresh: bool,
+) -> Result<Vec<String>, String> {
+    let base_url = refs
+        .base_url
+        .clone()
+        .ok_or_else(|| "provider uses the official endpoint".to_string())?;
+    let api_type = if refs.sdk_type.is_some() {
+        ApiType::Native
+    } else {
+        ApiType::OpenaiCompat
+    };
+    let response = models_cache::fetch_models_with_cache(
+        db,
+        FetchModelsRequest {
+            provider_id: Some(provider_id.to_string()),
+            base_url,
+            api_key: refs.api_key.clone(),
+            headers: None,
+            api_type,
+            sdk_type: refs.sdk_type.clone(),
+            custom_url: None,
+            tool: Some(tool.to_string()),
+            proxy_url: None,
+            max_retries: None,
+        },
+        MODEL_LIST_MAX_AGE_SECS,
+        force_refresh,
+    )
+    .await?
+    .response;
+    let ids: Vec<String> = response.models.into_iter().map(|model| model.id).collect();
+    if ids.is_empty() {
+        return Err("provider returned an empty model list".to_string());
+    }
+    Ok(ids)
+}
+
+/// List a provider's models to check its endpoint answers.
+/// Returns `None` for providers on the official endpoint, which have no base URL to probe.
+pub(crate) async fn check_provider_endpoint(
+    db: &SqliteDbState,
+    tool: &str,
+    provider_id: &str,
+) -> Result<Option<usize>, String> {
+    let refs = load_model_refs(db, tool, provider_id)?;
+    if refs.base_url.is_none() {
+        return Ok(None);
+    }
+    list_provider_model_ids(db, tool, provider_id, &refs, false)
+        .await
+        .map(|ids| Some(ids.len()))
+}
+
+/// Endpoint of a Claude or Codex provider that serves a given model.
+#[derive(Debug, Clone)]
+pub(crate) struct ModelEndpoint {
+    pub provider_id: String,
+    pub base_url: String,
+    pub api_key: Option<String>,
+}
+
+/// Providers of `tool` that reference `model_id` in their config or list it on their endpoint.
+/// Providers on the official endpoint and providers whose config cannot be read are skipped.
+pub(crate) async fn find_providers_serving_model(
+    db: &SqliteDbState,
+    tool: &str,
+    model_id: &str,
+) -> Result<Vec<ModelEndpoint>, String> {
+    let table = provider_table(tool)?;
+    let provider_ids: Vec<String> = db.with_conn(|conn| {
+        Ok(db_list(conn, table, None)?
+            .iter()
+            .map(db_extract_id)
+            .collect())
+    })?;
+
+    let mut endpoints = Vec::new();
+    for provider_id in provider_ids {
+        let Ok(Some(refs)) = load_model_refs_if_exists(db, tool, &provider_id) else {
+            continue;
+        };
+        let Some(base_url) = refs.base_url.clone() else {
+            continue;
+        };
+        let serves_model = refs.models.iter().any(|(_, model)| model == model_id)
+            || list_provider_model_ids(db, tool, &provider_id, &refs, false)
+                .await
+                .is_ok_and(|ids| ids.iter().any(|id| id == model_id));
+        if serves_model {
+            endpoints.push(ModelEndpoint {
+                provider_id,
+                base_url,
+                api_key: refs.api_key,
+            });
+        }
+    }
+    Ok(endpoints)
+}
+
+fn find_model_issues(
+    models: &[(String, String)],
+    available: Result<&[String], &str>,
+) -> Vec<ModelIssue> {
+    models
+        .iter()
+        .filter_map(|(field, model)| {
+            let (status, message) = match available {
+                Err(reason) => ("unverified", format!("Unable to verify: {}", reason)),
+                Ok(ids) if ids.iter().any(|id| id == model) => return None,
+                Ok(_) => (
+                    "missing",
+                    format!("Model '{}' is not served by this provider", model),
+                ),
+            };
+            Some(ModelIssue {
+                field: field.clone(),
+                model: model.clone(),
+                status: status.to_string(),
+                message,
+            })
+        })
+        .collect()
+}
+
+/// Flag models referenced by a provider ("claude" or "codex") that its endpoint doesn't serve.
+#[tauri::command]
+pub async fn validate_provider_models(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+    provider_id: String,
+) -> Result<Vec<ModelIssue>, String> {
+    let refs = load_model_refs(&state, &tool, &provider_id)?;
+    if refs.models.is_empty() {
+        return Ok(Vec::new());
+    }
+    let available = list_provider_model_ids(&state, &tool, &provider_id, &refs, false).await;
+    Ok(find_model_issues(
+        &refs.models,
+        available.as_deref().map_err(String::as_str),
+    ))
+}
+
+/// An enabled provider of one of the `REACHABILITY_TOOLS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProviderSummary {
+    pub id: String,
+    pub name: String,
+    pub is_applied: bool,
+}
+
+/// Every enabled provider of `tool` (one of `REACHABILITY_TOOLS`), skipping the local placeholder.
+pub(crate) fn enabled_providers(
+    db: &SqliteDbState,
+    tool: &str,
+) -> Result<Vec<ProviderSummary>, String> {
+    let table = provider_table(tool)?;
+    let records = db.with_conn(|conn| db_list(conn, table, None))?;
+    Ok(records
+        .into_iter()
+        .filter_map(|record| {
+            let (summary, is_disabled) = match tool {
+                "claude" => {
+                    let provider = claude_code::adapter::from_db_value_provider(record);
+                    let summary = ProviderSummary {
+                        id: provider.id,
+                        name: provider.name,
+                        is_applied: provider.is_applied,
+                    };
+                    (summary, provider.is_disabled)
+                }
+                "geminicli" => {
+                    let provider = gemini_cli::adapter::from_db_value_provider(record);
+                    let summary = ProviderSummary {
+                        id: provider.id,
+                        name: provider.name,
+                        is_applied: provider.is_applied,
+                    };
+                    (summary, provider.is_disabled)
+                }
+                "grok" => {
+                    let provider = grok::adapter::provider_from_db_value(record);
+                    let summary = ProviderSummary {
+                        id: provider.id,
+                        name: provider.name,
+                        is_applied: provider.is_applied,
+                    };
+                    (summary, provider.is_disabled)
+                }
+                _ => {
+                    let provider = codex::adapter::from_db_value_provider(record);
+                    let summary = ProviderSummary {
+                        id: provider.id,
+                        name: provider.name,
+                        is_applied: provider.is_applied,
+                    };
+                    (summary, provider.is_disabled)
+                }
+            };
+            (!is_disabled && summary.id != LOCAL_PROVIDER_ID).then_some(summary)
+        })
+        .collect())
+}
+
+/// Id and name of the applied, enabled provider of `tool`.
+fn applied_provider(db: &SqliteDbState, tool: &str) -> Result<Option<(String, String)>, String> {
+    Ok(enabled_providers(db, tool)?
+        .into_iter()
+        .find(|provider| provider.is_applied)
+        .map(|provider| (provider.id, provider.name)))
+}
+
+/// Endpoint and first referenced model of a provider, for a single-model connectivity test.
+#[derive(Debug, Clone)]
+pub(crate) struct ProbeTarget {
+    pub base_url: String,
+    pub api_key: Option<String>,
+    /// Request format of the connectivity test ("@ai-sdk/anthropic", "@ai-sdk/openai", ...)
+    pub npm: String,
+    pub model_id: String,
+}
+
+/// What a connectivity test of the provider sends where. `None` for providers on the official
+/// endpoint or without a referenced model, which have nothing to test.
+pub(crate) fn provider_probe_target(
+    db: &SqliteDbState,
+    tool: &str,
+    provider_id: &str,
+) -> Result<Option<ProbeTarget>, String> {
+    let refs = load_model_refs(db, tool, provider_id)?;
+    let (Some(base_url), Some((_, model_id))) = (refs.base_url, refs.models.into_iter().next())
+    else {
+        return Ok(None);
+    };
+    let npm = refs.sdk_type.unwrap_or_else(|| {
+        match tool {
+            "codex" => "@ai-sdk/openai",
+            _ => "@ai-sdk/openai-compatible",
+        }
+        .to_string()
+    });
+    // Gemini CLI base URLs may omit the API version the generate endpoint needs.
+    let base_url = match tool {
+        "geminicli"
+            if !["/v1", "/v1alpha", "/v1beta"]
+                .iter()
+                .any(|version| base_url.trim_end_matches('/').ends_with(version)) =>
+        {
+            format!("{}/v1beta", base_url.trim_end_matches('/'))
+        }
+        _ => base_url,
+    };
+    Ok(Some(ProbeTarget {
+        base_url,
+        api_key: refs.api_key,
+        npm,
+        model_id,
+    }))
+}
+
+/// Probe the applied provider of every supported tool, bypassing the cached model list;
+/// providers on the official endpoint are skipped. A tool whose provider cannot be read is
+/// skipped with a warning.
+pub(crate) async fn find_unreachable_applied_providers(
+    db: &SqliteDbState,
+) -> Vec<UnreachableProvider> {
+    let mut unreachable = Vec::new();
+    for tool in REACHABILITY_TOOLS {
+        let (provider_id, provider_name) = match applied_provider(db, tool) {
+            Ok(Some(applied)) => applied,
+            Ok(None) => continue,
+            Err(error) => {
+                log::warn!("Skipped {} provider reachability check: {}", tool, error);
+                continue;
+            }
+        };
+        let refs = match load_model_refs(db, tool, &provider_id) {
+            Ok(refs) => refs,
+            Err(error) => {
+                log::warn!("Skipped {} provider reachability check: {}", tool, error);
+                continue;
+            }
+        };
+        let Some(base_url) = refs.base_url.clone() else {
+            continue;
+        };
+        if let Err(error) = list_provider_model_ids(db, tool, &provider_id, &refs, true).await {
+            unreachable.push(UnreachableProvider {
+                tool: tool.to_string(),
+                provider_id,
+                provider_name,
+                base_url,
+                error,
+            });
+        }
+    }
+    unreachable
+}
+
+/// Applied providers whose endpoint does not answer a model list request.
+#[tauri::command]
+pub async fn check_applied_providers_reachable(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<UnreachableProvider>, String> {
+    Ok(find_unreachable_applied_providers(&state).await)
+}
+
+/// Startup task: when enabled in settings, probe the applied providers and emit
+/// `applied-provider-unreachable` with the ones that did not answer.
+pub async fn check_applied_providers_on_startup<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    let db_state = app.state::<SqliteDbState>();
+    let enabled = crate::settings::store::load_settings_from_sqlite_state(&db_state)
+        .is_ok_and(|settings| settings.check_applied_providers_on_startup);
+    if !enabled {
+        return;
+    }
+
+    let unreachable = find_unreachable_applied_providers(&db_state).await;
+    if unreachable.is_empty() {
+        log::info!("Applied providers answered the startup reachability check");
+        return;
+    }
+    for provider in &unreachable {
+        log::warn!(
+            "Applied {} provider '{}' is unreachable at {}: {}",
+            provider.tool,
+            provider.provider_name,
+            provider.base_url,
+            provider.error
+        );
+    }
+    let _ = app.emit("applied-provider-unreachable", &unreachable);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn claude_refs_prefer_env_and_fall_back_to_legacy_fields() {
+        let refs = claude_model_refs(&json!({
+            "model": "ignored",
+            "sonnetModel": "glm-4.6",
+            "env": {
+                "ANTHROPIC_BASE_URL": "https://relay.example.com/",
+                "ANTHROPIC_AUTH_TOKEN": "sk-test",
+                "ANTHROPIC_MODEL": "kimi-k2"
+            }
+        }));
+        assert_eq!(
+            refs.base_url.as_deref(),
+            Some("https://relay.example.com/v1")
+        );
+        assert_eq!(
+            refs.models,
+            vec![
+                ("model".to_string(), "kimi-k2".to_string()),
+                ("sonnetModel".to_string(), "glm-4.6".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn codex_refs_read_selected_provider_base_url() {
+        let refs = codex_model_refs(&json!({
+            "auth": { "OPENAI_API_KEY": "sk-test" },
+            "config": "model_provider = \"custom\"\nmodel = \"gpt-5\"\n\n[model_providers.custom]\nbase_url = \"https://gw.example.com/v1\"\n"
+        }))
+        .unwrap();
+        assert_eq!(refs.base_url.as_deref(), Some("https://gw.example.com/v1"));
+        assert_eq!(
+            refs.models,
+            vec![("model".to_string(), "gpt-5".to_string())]
+        );
+    }
+
+    #[test]
+    fn gemini_cli_refs_read_gateway_env_and_skip_official() {
+        let refs = gemini_cli_model_refs(&json!({
+            "env": {
+                "GEMINI_MODEL": "gemini-2.5-pro",
+                "GEMINI_API_KEY": "key",
+                "GOOGLE_GEMINI_BASE_URL": "https://proxy.example/v1beta"
+            }
+        }));
+        assert_eq!(
+            refs.base_url.as_deref(),
+            Some("https://proxy.example/v1beta")
+        );
+        assert_eq!(refs.api_key.as_deref(), Some("key"));
+        assert_eq!(
+            refs.models,
+            vec![("model".to_string(), "gemini-2.5-pro".to_string())]
+        );
+        assert!(
+            gemini_cli_model_refs(&json!({"env": {"GEMINI_API_KEY": "key"}}))
+                .base_url
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn grok_refs_probe_the_default_catalog_model() {
+        let refs = grok_model_refs(&json!({
+            "defaultModelKey": "second",
+            "auth": { "API_KEY": "shared" },
+            "modelCatalog": { "models": [
+                { "key": "first", "model": "grok-a", "baseUrl": "https://a.example.com/v1" },
+                { "key": "second", "model": "grok-b", "baseUrl": "https://b.example.com/v1" }
+            ]}
+        }));
+        assert_eq!(refs.base_url.as_deref(), Some("https://b.example.com/v1"));
+        assert_eq!(refs.api_key.as_deref(), Some("shared"));
+        assert_eq!(refs.models.len(), 2);
+        assert!(grok_model_refs(&json!({ "defaultModelKey": "grok-4.5" }))
+            .base_url
+            .is_none());
+    }
+
+    #[test]
+    fn issues_flag_missing_or_unverifiable_models() {
+        let models = vec![
+            ("model".to_string(), "gpt-5".to_string()),
+            ("opusModel".to_string(), "gpt-5-typo".to_string()),
+        ];
+        let available = vec!["gpt-5".to_string()];
+        let issues = find_model_issues(&models, Ok(&available));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "opusModel");
+        assert_eq!(issues[0].status, "missing");
+
+        let unverified = find_model_issues(&models, Err("HTTP 404"));
+        assert_eq!(unverified.len(), 2);
+        assert!(unverified.iter().all(|issue| issue.status == "unverified"));
+    }
+
+    #[test]
+    fn applied_provider_skips_disabled_and_local_records() {
+        use crate::db::helpers::db_put;
+
+        let state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        state
+            .with_conn(|conn| {
+                db_put(
+                    conn,
+                    DbTable::ClaudeProvider,
+                    "__local__",
+                    &json!({ "name": "Local", "is_applied": true }),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::ClaudeProvider,
+                    "off",
+                    &json!({ "name": "Off", "is_applied": true, "is_disabled": true }),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::ClaudeProvider,
+                    "relay",
+                    &json!({ "name": "Relay", "is_applied": true }),
+                )
+            })
+            .expect("seed");
+
+        assert_eq!(
+            applied_provider(&state, "claude").expect("claude"),
+            Some(("relay".to_string(), "Relay".to_string()))
+        );
+        assert_eq!(applied_provider(&state, "codex").expect("codex"), None);
+    }
+}
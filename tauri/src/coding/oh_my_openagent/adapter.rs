@@ -9,32 +9,23 @@ use serde_json::{json, Value};
 // Helper Functions
 // ============================================================================
 
-/// Helper function to get string value with backward compatibility (camelCase and snake_case)
-fn get_str_compat(value: &Value, snake_key: &str, camel_key: &str, default: &str) -> String {
+/// Helper function to get string value
+fn get_str(value: &Value, key: &str, default: &str) -> String {
     value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
+        .get(key)
         .and_then(|v| v.as_str())
         .unwrap_or(default)
         .to_string()
 }
 
-/// Helper function to get optional string with backward compatibility
-fn get_opt_str_compat(value: &Value, snake_key: &str, camel_key: &str) -> Option<String> {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_str())
-        .map(String::from)
+/// Helper function to get optional string
+fn get_opt_str(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
 }
 
-/// Helper function to get bool with backward compatibility
-pub fn get_bool_compat(value: &Value, snake_key: &str, camel_key: &str, default: bool) -> bool {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_bool())
-        .unwrap_or(default)
+/// Helper function to get bool
+pub fn get_bool(value: &Value, key: &str, default: bool) -> bool {
+    value.get(key).and_then(|v| v.as_bool()).unwrap_or(default)
 }
 
 /// Deep merge two JSON Values recursively
@@ -76,27 +67,23 @@ pub fn clean_empty_values(value: &mut Value) {
 
 /// Convert database Value to OhMyOpenAgentAgentsProfile with fault tolerance
 pub fn from_db_value(value: Value) -> OhMyOpenAgentAgentsProfile {
-    let is_applied = get_bool_compat(&value, "is_applied", "isApplied", false);
-    let is_disabled = get_bool_compat(&value, "is_disabled", "isDisabled", false);
+    let is_applied = get_bool(&value, "is_applied", false);
+    let is_disabled = get_bool(&value, "is_disabled", false);
     let sort_index = value
         .get("sort_index")
-        .or_else(|| value.get("sortIndex"))
         .and_then(|v| v.as_i64())
         .map(|v| v as i32);
     OhMyOpenAgentAgentsProfile {
         id: db_extract_id(&value),
-        name: get_str_compat(&value, "name", "name", "Unnamed Config"),
+        name: get_str(&value, "name", "Unnamed Config"),
         is_applied,
         is_disabled,
         agents: value.get("agents").cloned(),
         categories: value.get("categories").cloned(),
-        other_fields: value
-            .get("other_fields")
-            .or_else(|| value.get("otherFields"))
-            .cloned(),
+        other_fields: value.get("other_fields").cloned(),
         sort_index,
-        created_at: get_opt_str_compat(&value, "created_at", "createdAt"),
-        updated_at: get_opt_str_compat(&value, "updated_at", "updatedAt"),
+        created_at: get_opt_str(&value, "created_at"),
+        updated_at: get_opt_str(&value, "updated_at"),
     }
 }
 
@@ -150,45 +137,26 @@ pub fn global_config_from_db_value(value: Value) -> OhMyOpenAgentGlobalConfig {
             .or_else(|| value.get("schema"))
             .and_then(|v| v.as_str())
             .map(String::from),
-        sisyphus_agent: value
-            .get("sisyphus_agent")
-            .or_else(|| value.get("sisyphusAgent"))
-            .cloned(),
+        sisyphus_agent: value.get("sisyphus_agent").cloned(),
         disabled_agents: value
             .get("disabled_agents")
-            .or_else(|| value.get("disabledAgents"))
             .and_then(|v| safe_to_string_array(v)),
         disabled_mcps: value
             .get("disabled_mcps")
-            .or_else(|| value.get("disabledMcps"))
             .and_then(|v| safe_to_string_array(v)),
         disabled_hooks: value
             .get("disabled_hooks")
-            .or_else(|| value.get("disabledHooks"))
             .and_then(|v| safe_to_string_array(v)),
         disabled_skills: value
             .get("disabled_skills")
-            .or_else(|| value.get("disabledSkills"))
             .and_then(|v| safe_to_string_array(v)),
         lsp: value.get("lsp").cloned(),
         experimental: value.get("experimental").cloned(),
-        background_task: value
-            .get("background_task")
-            .or_else(|| value.get("backgroundTask"))
-            .cloned(),
-        browser_automation_engine: value
-            .get("browser_automation_engine")
-            .or_else(|| value.get("browserAutomationEngine"))
-            .cloned(),
-        claude_code: value
-            .get("claude_code")
-            .or_else(|| value.get("claudeCode"))
-            .cloned(),
-        other_fields: value
-            .get("other_fields")
-            .or_else(|| value.get("otherFields"))
-            .cloned(),
-        updated_at: get_opt_str_compat(&value, "updated_at", "updatedAt"),
+        background_task: value.get("background_task").cloned(),
+        browser_automation_engine: value.get("browser_automation_engine").cloned(),
+        claude_code: value.get("claude_code").cloned(),
+        other_fields: value.get("other_fields").cloned(),
+        updated_at: get_opt_str(&value, "updated_at"),
     }
 }
 
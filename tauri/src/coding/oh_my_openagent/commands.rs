@@ -4,6 +4,8 @@ use std::fs;
 
 use super::adapter;
 use super::types::*;
+use crate::coding::atomic_write;
+use crate::coding::jsonc_edit;
 use crate::coding::runtime_location;
 use crate::db::helpers::{
     db_create, db_delete, db_get, db_list, db_patch_fields, db_put, db_query_by_bool,
@@ -628,45 +630,62 @@ pub async fn clear_oh_my_openagent_applied_config(
     Ok(())
 }
 
-/// 内部函数：将指定配置应用到配置文件（不改变数据库中的 is_applied 状态）
-async fn apply_config_to_file(
-    db: &crate::db::SqliteDbState,
-    config_id: &str,
-) -> Result<(), String> {
-    apply_config_to_file_public(db, config_id).await
+const LAYER_GLOBAL_EXPLICIT: &str = "globalExplicit";
+const LAYER_GLOBAL_OTHER_FIELDS: &str = "globalOtherFields";
+const LAYER_PROFILE_AGENTS: &str = "profileAgents";
+const LAYER_PROFILE_CATEGORIES: &str = "profileCategories";
+const LAYER_PROFILE_OTHER_FIELDS: &str = "profileOtherFields";
+
+/// Top-level keys of the merged config together with every layer that set them, in order.
+#[derive(Default)]
+struct MergeLayers {
+    json: serde_json::Map<String, Value>,
+    layers: Vec<(String, Vec<&'static str>)>,
 }
 
-/// Public version of apply_config_to_file for tray module
-pub async fn apply_config_to_file_public(
-    db: &crate::db::SqliteDbState,
-    config_id: &str,
-) -> Result<(), String> {
-    // Get the config from database using direct ID format (like ClaudeCode)
-    let agents_profile = get_config_from_sqlite(db, config_id)?
-        .ok_or_else(|| format!("Config '{}' not found", config_id))?;
-
-    // Check if config is disabled (P0-3 fix: Architect solution C)
-    if agents_profile.is_disabled {
-        return Err(format!(
-            "Config '{}' is disabled and cannot be applied",
-            config_id
-        ));
-    }
-
-    // Get config path using unified function
-    let config_path = get_oh_my_openagent_config_path(db).await?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = config_path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create opencode config directory: {}", e))?;
+impl MergeLayers {
+    fn set(&mut self, layer: &'static str, key: String, value: Value) {
+        match self
+            .layers
+            .iter_mut()
+            .find(|(existing, _)| *existing == key)
+        {
+            Some((_, layers)) => layers.push(layer),
+            None => self.layers.push((key.clone(), vec![layer])),
         }
+        self.json.insert(key, value);
     }
 
-    // 获取 Global Config
-    let global_config = get_global_config_from_sqlite(db)?.unwrap_or_else(default_global_config);
+    /// Clean empty values and drop provenance for keys the cleanup removed.
+    fn finish(self) -> (Value, Vec<MergeKeyProvenance>) {
+        let mut final_json = Value::Object(self.json);
+
+        // 清理空值：删除空对象和空数组
+        adapter::clean_empty_values(&mut final_json);
+
+        let provenance = self
+            .layers
+            .into_iter()
+            .filter(|(key, _)| final_json.get(key).is_some())
+            .map(|(key, mut layers)| {
+                let layer = layers.pop().unwrap_or_default().to_string();
+                MergeKeyProvenance {
+                    key,
+                    layer,
+                    overridden_layers: layers.into_iter().map(str::to_string).collect(),
+                }
+            })
+            .collect();
+        (final_json, provenance)
+    }
+}
 
+/// Merge the global config and an agents profile into the final config file content,
+/// recording which layer set each top-level key.
+fn merge_config_layers(
+    global_config: OhMyOpenAgentGlobalConfig,
+    agents_profile: OhMyOpenAgentAgentsProfile,
+) -> (Value, Vec<MergeKeyProvenance>) {
     // 合并配置的优先级顺序（从低到高）：
     // 1. 全局配置的明确字段（最低优先级）
     // 2. 全局配置的 other_fields
@@ -674,66 +693,91 @@ pub async fn apply_config_to_file_public(
     // 4. Agents Profile 的 categories
     // 5. Agents Profile 的 other_fields（最高优先级，可以覆盖所有）
 
-    let mut final_json = serde_json::Map::new();
+    let mut merged = MergeLayers::default();
 
     // 使用保存的 schema 或默认 schema
     let schema_url = global_config.schema.unwrap_or_else(|| {
         "https://raw.githubusercontent.com/code-yeongyu/oh-my-openagent/dev/assets/oh-my-opencode.schema.json".to_string()
     });
-    final_json.insert("$schema".to_string(), serde_json::json!(schema_url));
+    merged.set(
+        LAYER_GLOBAL_EXPLICIT,
+        "$schema".to_string(),
+        serde_json::json!(schema_url),
+    );
 
     // 1. 先设置全局配置的明确字段（优先级最低）
     if let Some(sisyphus) = global_config.sisyphus_agent {
-        final_json.insert("sisyphus_agent".to_string(), sisyphus);
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
+            "sisyphus_agent".to_string(),
+            sisyphus,
+        );
     }
     if let Some(disabled_agents) = global_config.disabled_agents {
-        final_json.insert(
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
             "disabled_agents".to_string(),
             serde_json::json!(disabled_agents),
         );
     }
     if let Some(disabled_mcps) = global_config.disabled_mcps {
-        final_json.insert(
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
             "disabled_mcps".to_string(),
             serde_json::json!(disabled_mcps),
         );
     }
     if let Some(disabled_hooks) = global_config.disabled_hooks {
-        final_json.insert(
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
             "disabled_hooks".to_string(),
             serde_json::json!(disabled_hooks),
         );
     }
     if let Some(disabled_skills) = global_config.disabled_skills {
-        final_json.insert(
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
             "disabled_skills".to_string(),
             serde_json::json!(disabled_skills),
         );
     }
     if let Some(lsp) = global_config.lsp {
-        final_json.insert("lsp".to_string(), lsp);
+        merged.set(LAYER_GLOBAL_EXPLICIT, "lsp".to_string(), lsp);
     }
     if let Some(experimental) = global_config.experimental {
-        final_json.insert("experimental".to_string(), experimental);
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
+            "experimental".to_string(),
+            experimental,
+        );
     }
     if let Some(background_task) = global_config.background_task {
-        final_json.insert("background_task".to_string(), background_task);
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
+            "background_task".to_string(),
+            background_task,
+        );
     }
     if let Some(browser_automation_engine) = global_config.browser_automation_engine {
-        final_json.insert(
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
             "browser_automation_engine".to_string(),
             browser_automation_engine,
         );
     }
     if let Some(claude_code) = global_config.claude_code {
-        final_json.insert("claude_code".to_string(), claude_code);
+        merged.set(
+            LAYER_GLOBAL_EXPLICIT,
+            "claude_code".to_string(),
+            claude_code,
+        );
     }
 
     // 2. 然后平铺全局配置的 other_fields（会覆盖上面的明确字段）
     if let Some(global_others) = global_config.other_fields {
         if let Some(others_obj) = global_others.as_object() {
             for (key, value) in others_obj {
-                final_json.insert(key.clone(), value.clone());
+                merged.set(LAYER_GLOBAL_OTHER_FIELDS, key.clone(), value.clone());
             }
         }
     }
@@ -741,37 +785,94 @@ pub async fn apply_config_to_file_public(
     // 3. 设置 Agents Profile 的 agents（会覆盖前面的 agents，并标准化键名为小写）
     if let Some(mut agents) = agents_profile.agents {
         normalize_agents_keys(&mut agents);
-        final_json.insert("agents".to_string(), agents);
+        merged.set(LAYER_PROFILE_AGENTS, "agents".to_string(), agents);
     }
 
     // 4. 设置 Agents Profile 的 categories（会覆盖前面的 categories）
     if let Some(categories) = agents_profile.categories {
-        final_json.insert("categories".to_string(), categories);
+        merged.set(
+            LAYER_PROFILE_CATEGORIES,
+            "categories".to_string(),
+            categories,
+        );
     }
 
     // 5. 最后平铺 Agents Profile 的 other_fields（最高优先级，可以覆盖所有字段）
     if let Some(profile_others) = agents_profile.other_fields {
         if let Some(others_obj) = profile_others.as_object() {
             for (key, value) in others_obj {
-                final_json.insert(key.clone(), value.clone());
+                merged.set(LAYER_PROFILE_OTHER_FIELDS, key.clone(), value.clone());
             }
         }
     }
 
-    let mut final_json = Value::Object(final_json);
+    merged.finish()
+}
+
+/// 内部函数：将指定配置应用到配置文件（不改变数据库中的 is_applied 状态）
+async fn apply_config_to_file(
+    db: &crate::db::SqliteDbState,
+    config_id: &str,
+) -> Result<(), String> {
+    apply_config_to_file_public(db, config_id).await
+}
+
+/// Public version of apply_config_to_file for tray module
+pub async fn apply_config_to_file_public(
+    db: &crate::db::SqliteDbState,
+    config_id: &str,
+) -> Result<(), String> {
+    // Get the config from database using direct ID format (like ClaudeCode)
+    let agents_profile = get_config_from_sqlite(db, config_id)?
+        .ok_or_else(|| format!("Config '{}' not found", config_id))?;
+
+    // Check if config is disabled (P0-3 fix: Architect solution C)
+    if agents_profile.is_disabled {
+        return Err(format!(
+            "Config '{}' is disabled and cannot be applied",
+            config_id
+        ));
+    }
+
+    // Get config path using unified function
+    let config_path = get_oh_my_openagent_config_path(db).await?;
+
+    // Ensure parent directory exists
+    if let Some(parent) = config_path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create opencode config directory: {}", e))?;
+        }
+    }
 
-    // 清理空值：删除空对象和空数组
-    adapter::clean_empty_values(&mut final_json);
+    // 获取 Global Config
+    let global_config = get_global_config_from_sqlite(db)?.unwrap_or_else(default_global_config);
+    let (final_json, _) = merge_config_layers(global_config, agents_profile);
 
-    // Write to file with pretty formatting
-    let json_content = serde_json::to_string_pretty(&final_json)
-        .map_err(|e| format!("Failed to serialize final config: {}", e))?;
+    // Keep the user's comments when the current file is annotated JSONC
+    let json_content = jsonc_edit::render_config_for_path(&config_path, &final_json)?;
 
-    fs::write(&config_path, json_content)
+    atomic_write::write_atomic(&config_path, json_content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
 }
+
+/// Preview the config file an apply of `config_id` would write, with the merge layer that
+/// set each top-level key. Nothing is written.
+#[tauri::command]
+pub async fn preview_oh_my_opencode_merge(
+    state: tauri::State<'_, SqliteDbState>,
+    config_id: String,
+) -> Result<MergePreview, String> {
+    let db = state.db();
+    let agents_profile = get_config_from_sqlite(db, &config_id)?
+        .ok_or_else(|| format!("Config '{}' not found", config_id))?;
+    let global_config = get_global_config_from_sqlite(db)?.unwrap_or_else(default_global_config);
+    let (config, provenance) = merge_config_layers(global_config, agents_profile);
+    Ok(MergePreview { config, provenance })
+}
+
 /// Apply an Oh My OpenAgent config to the JSON file
 #[tauri::command]
 pub async fn apply_oh_my_openagent_config(
@@ -879,7 +980,7 @@ pub async fn toggle_oh_my_openagent_config_disabled(
         })?
         .ok_or_else(|| format!("Config '{}' not found", config_id))?;
 
-    let is_applied = adapter::get_bool_compat(&config_value, "is_applied", "isApplied", false);
+    let is_applied = adapter::get_bool(&config_value, "is_applied", false);
     if is_applied {
         apply_config_internal(&db, &app, &config_id, false).await?;
     }
@@ -1130,6 +1231,38 @@ mod tests {
         assert_eq!(content.other_fields, None);
     }
 
+    #[test]
+    fn merge_provenance_reports_winning_and_overridden_layers() {
+        let mut global = local_global_with_other_fields();
+        global.other_fields =
+            Some(json!({ "lsp": { "go": { "enabled": true } }, "custom": "global" }));
+        global.experimental = Some(json!({}));
+        let mut profile = local_profile_with_other_fields();
+        profile.other_fields = Some(json!({ "custom": "profile" }));
+
+        let (config, provenance) = merge_config_layers(global, profile);
+        let source = |key: &str| {
+            provenance
+                .iter()
+                .find(|entry| entry.key == key)
+                .map(|entry| (entry.layer.as_str(), entry.overridden_layers.clone()))
+        };
+
+        assert_eq!(config["custom"], json!("profile"));
+        assert_eq!(
+            source("custom"),
+            Some(("profileOtherFields", vec!["globalOtherFields".to_string()]))
+        );
+        assert_eq!(
+            source("lsp"),
+            Some(("globalOtherFields", vec!["globalExplicit".to_string()]))
+        );
+        assert_eq!(source("categories"), Some(("profileCategories", vec![])));
+        // Keys removed by empty-value cleanup have no provenance
+        assert!(config.get("experimental").is_none());
+        assert_eq!(source("experimental"), None);
+    }
+
     #[test]
     fn local_global_without_input_reuses_local_file_fields() {
         let content = build_local_global_content(
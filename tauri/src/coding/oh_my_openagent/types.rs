@@ -9,6 +9,25 @@ pub struct ConfigPathInfo {
     pub source: String,
 }
 
+/// Which merge layer set a top-level key of the applied config file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeKeyProvenance {
+    pub key: String,
+    /// "globalExplicit" | "globalOtherFields" | "profileAgents" | "profileCategories" | "profileOtherFields"
+    pub layer: String,
+    /// Lower-priority layers that also set the key, lowest first
+    pub overridden_layers: Vec<String>,
+}
+
+/// The config file content an apply would write, with per-key provenance
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergePreview {
+    pub config: Value,
+    pub provenance: Vec<MergeKeyProvenance>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OhMyOpenAgentLegacyUpgradeStatus {
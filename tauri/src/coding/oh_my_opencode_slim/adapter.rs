@@ -9,32 +9,23 @@ use serde_json::{json, Map, Value};
 // Helper Functions
 // ============================================================================
 
-/// Helper function to get string value with backward compatibility (camelCase and snake_case)
-fn get_str_compat(value: &Value, snake_key: &str, camel_key: &str, default: &str) -> String {
+/// Helper function to get string value
+fn get_str(value: &Value, key: &str, default: &str) -> String {
     value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
+        .get(key)
         .and_then(|v| v.as_str())
         .unwrap_or(default)
         .to_string()
 }
 
-/// Helper function to get optional string with backward compatibility
-fn get_opt_str_compat(value: &Value, snake_key: &str, camel_key: &str) -> Option<String> {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_str())
-        .map(String::from)
+/// Helper function to get optional string
+fn get_opt_str(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from)
 }
 
-/// Helper function to get bool with backward compatibility
-pub fn get_bool_compat(value: &Value, snake_key: &str, camel_key: &str, default: bool) -> bool {
-    value
-        .get(snake_key)
-        .or_else(|| value.get(camel_key))
-        .and_then(|v| v.as_bool())
-        .unwrap_or(default)
+/// Helper function to get bool
+pub fn get_bool(value: &Value, key: &str, default: bool) -> bool {
+    value.get(key).and_then(|v| v.as_bool()).unwrap_or(default)
 }
 
 /// Deep merge two JSON Values recursively
@@ -410,12 +401,9 @@ pub fn resolve_slim_agents_from_config_value(value: &Value) -> Option<Value> {
 
 /// Convert database Value to OhMyOpenCodeSlimConfig (AgentsProfile) with fault tolerance
 pub fn from_db_value(value: Value) -> OhMyOpenCodeSlimConfig {
-    let is_applied = get_bool_compat(&value, "is_applied", "isApplied", false);
-    let is_disabled = get_bool_compat(&value, "is_disabled", "isDisabled", false);
-    let raw_other_fields = value
-        .get("other_fields")
-        .or_else(|| value.get("otherFields"))
-        .cloned();
+    let is_applied = get_bool(&value, "is_applied", false);
+    let is_disabled = get_bool(&value, "is_disabled", false);
+    let raw_other_fields = value.get("other_fields").cloned();
     let fallback_from_value = value.get("fallback").and_then(parse_fallback_config_value);
     let fallback_from_other_fields = raw_other_fields
         .as_ref()
@@ -437,12 +425,11 @@ pub fn from_db_value(value: Value) -> OhMyOpenCodeSlimConfig {
     });
     let sort_index = value
         .get("sort_index")
-        .or_else(|| value.get("sortIndex"))
         .and_then(|v| v.as_i64())
         .map(|v| v as i32);
     OhMyOpenCodeSlimConfig {
         id: db_extract_id(&value),
-        name: get_str_compat(&value, "name", "name", "Unnamed Config"),
+        name: get_str(&value, "name", "Unnamed Config"),
         is_applied,
         is_disabled,
         agents: value
@@ -453,8 +440,8 @@ pub fn from_db_value(value: Value) -> OhMyOpenCodeSlimConfig {
         fallback: merge_fallback_configs(fallback_from_value, fallback_from_other_fields),
         other_fields: cleaned_other_fields,
         sort_index,
-        created_at: get_opt_str_compat(&value, "created_at", "createdAt"),
-        updated_at: get_opt_str_compat(&value, "updated_at", "updatedAt"),
+        created_at: get_opt_str(&value, "created_at"),
+        updated_at: get_opt_str(&value, "updated_at"),
     }
 }
 
@@ -504,10 +491,7 @@ fn safe_to_string_array(value: &Value) -> Option<Vec<String>> {
 
 /// Convert database Value to OhMyOpenCodeSlimGlobalConfig with fault tolerance
 pub fn global_config_from_db_value(value: Value) -> OhMyOpenCodeSlimGlobalConfig {
-    let raw_other_fields = value
-        .get("other_fields")
-        .or_else(|| value.get("otherFields"))
-        .cloned();
+    let raw_other_fields = value.get("other_fields").cloned();
     let legacy_council = raw_other_fields
         .as_ref()
         .and_then(|other| other.get("council"))
@@ -524,27 +508,21 @@ pub fn global_config_from_db_value(value: Value) -> OhMyOpenCodeSlimGlobalConfig
 
     OhMyOpenCodeSlimGlobalConfig {
         id: db_extract_id(&value),
-        sisyphus_agent: value
-            .get("sisyphus_agent")
-            .or_else(|| value.get("sisyphusAgent"))
-            .cloned(),
+        sisyphus_agent: value.get("sisyphus_agent").cloned(),
         disabled_agents: value
             .get("disabled_agents")
-            .or_else(|| value.get("disabledAgents"))
             .and_then(|v| safe_to_string_array(v)),
         disabled_mcps: value
             .get("disabled_mcps")
-            .or_else(|| value.get("disabledMcps"))
             .and_then(|v| safe_to_string_array(v)),
         disabled_hooks: value
             .get("disabled_hooks")
-            .or_else(|| value.get("disabledHooks"))
             .and_then(|v| safe_to_string_array(v)),
         lsp: value.get("lsp").cloned(),
         experimental: value.get("experimental").cloned(),
         council: value.get("council").cloned().or(legacy_council),
         other_fields: cleaned_other_fields,
-        updated_at: get_opt_str_compat(&value, "updated_at", "updatedAt"),
+        updated_at: get_opt_str(&value, "updated_at"),
     }
 }
 
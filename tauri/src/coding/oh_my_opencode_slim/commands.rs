@@ -4,6 +4,8 @@ use std::fs;
 
 use super::adapter;
 use super::types::*;
+use crate::coding::atomic_write;
+use crate::coding::jsonc_edit;
 use crate::coding::runtime_location;
 use crate::db::helpers::{
     db_create, db_delete, db_get, db_list, db_patch_fields, db_put, db_query_by_bool,
@@ -577,10 +579,9 @@ pub async fn apply_config_to_file_public(
 
     adapter::clean_empty_values(&mut final_json);
 
-    let json_content = serde_json::to_string_pretty(&final_json)
-        .map_err(|e| format!("Failed to serialize final config: {}", e))?;
+    let json_content = jsonc_edit::render_config_for_path(&config_path, &final_json)?;
 
-    fs::write(&config_path, json_content)
+    atomic_write::write_atomic(&config_path, json_content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     Ok(())
@@ -784,7 +785,7 @@ pub async fn toggle_oh_my_opencode_slim_config_disabled(
         })?
         .ok_or_else(|| format!("Config '{}' not found", config_id))?;
 
-    let is_applied = adapter::get_bool_compat(&config_value, "is_applied", "isApplied", false);
+    let is_applied = adapter::get_bool(&config_value, "is_applied", false);
     if is_applied {
         apply_config_internal(&db, &app, &config_id, false).await?;
     }
@@ -47,17 +47,14 @@ pub async fn get_oh_my_opencode_slim_tray_data<R: Runtime>(
                     let name = record.get("name")?.as_str()?;
                     let is_applied = record
                         .get("is_applied")
-                        .or_else(|| record.get("isApplied"))
                         .and_then(|v| v.as_bool())
                         .unwrap_or(false);
                     let is_disabled = record
                         .get("is_disabled")
-                        .or_else(|| record.get("isDisabled"))
                         .and_then(|v| v.as_bool())
                         .unwrap_or(false);
                     let sort_index = record
                         .get("sort_index")
-                        .or_else(|| record.get("sortIndex"))
                         .and_then(|v| v.as_i64())
                         .unwrap_or(0);
 
@@ -7,12 +7,10 @@ pub fn from_db_value(value: Value) -> OpenClawCommonConfig {
     OpenClawCommonConfig {
         config_path: value
             .get("config_path")
-            .or_else(|| value.get("configPath"))
             .and_then(|v| v.as_str())
             .map(String::from),
         updated_at: value
             .get("updated_at")
-            .or_else(|| value.get("updatedAt"))
             .and_then(|v| v.as_str())
             .unwrap_or_else(|| {
                 let now = Local::now().to_rfc3339();
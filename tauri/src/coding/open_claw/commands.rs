@@ -5,6 +5,7 @@ use tauri::Emitter;
 use super::adapter;
 use super::types::*;
 use crate::coding::all_api_hub;
+use crate::coding::atomic_write;
 use crate::coding::runtime_location;
 use crate::coding::skills::commands::resync_all_skills_if_tool_path_changed;
 use crate::db::helpers::{db_get, db_put};
@@ -37,7 +38,7 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     config: OpenClawConfig,
     from_tray: bool,
 ) -> Result<(), String> {
-    let config_path_str = get_openclaw_config_path(state).await?;
+    let config_path_str = get_openclaw_config_path(state.clone()).await?;
     let config_path = Path::new(&config_path_str);
 
     // Ensure directory exists
@@ -52,15 +53,14 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     let json_content = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
 
-    fs::write(config_path, json_content)
+    atomic_write::write_atomic(config_path, json_content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
     let payload = if from_tray { "tray" } else { "window" };
     let _ = app.emit("openclaw-config-changed", payload);
 
     // Trigger WSL sync via event (Windows only)
-    #[cfg(target_os = "windows")]
-    let _ = app.emit("wsl-sync-request-openclaw", ());
+    crate::coding::wsl::emit_apply_sync_request(state.db(), app, "openclaw");
 
     Ok(())
 }
@@ -23,24 +23,19 @@ fn normalize_favorite_plugin_name(plugin_name: &str) -> String {
 // ============================================================================
 
 /// Convert database Value to OpenCodeCommonConfig with fault tolerance
-/// Supports both snake_case (new) and camelCase (legacy) field names
 pub fn from_db_value(value: Value) -> OpenCodeCommonConfig {
     OpenCodeCommonConfig {
         config_path: value
             .get("config_path")
-            .or_else(|| value.get("configPath"))
             .and_then(|v| v.as_str())
             .map(String::from),
         show_plugins_in_tray: value
             .get("show_plugins_in_tray")
-            .or_else(|| value.get("showPluginsInTray"))
             .or_else(|| value.get("show_plugins_in_menu"))
-            .or_else(|| value.get("showPluginsInMenu"))
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
         updated_at: value
             .get("updated_at")
-            .or_else(|| value.get("updatedAt"))
             .and_then(|v| v.as_str())
             .unwrap_or_else(|| {
                 let now = Local::now().to_rfc3339();
@@ -78,22 +73,18 @@ pub fn from_db_value_prompt_config(value: Value) -> OpenCodePromptConfig {
             .to_string(),
         is_applied: value
             .get("is_applied")
-            .or_else(|| value.get("isApplied"))
             .and_then(|v| v.as_bool())
             .unwrap_or(false),
         sort_index: value
             .get("sort_index")
-            .or_else(|| value.get("sortIndex"))
             .and_then(|v| v.as_i64())
             .map(|v| v as i32),
         created_at: value
             .get("created_at")
-            .or_else(|| value.get("createdAt"))
             .and_then(|v| v.as_str())
             .map(String::from),
         updated_at: value
             .get("updated_at")
-            .or_else(|| value.get("updatedAt"))
             .and_then(|v| v.as_str())
             .map(String::from),
     }
@@ -151,6 +142,10 @@ pub fn from_db_value_favorite_provider(value: Value) -> Option<OpenCodeFavoriteP
     let diagnostics: Option<OpenCodeDiagnosticsConfig> = value
         .get("diagnostics")
         .and_then(|v| serde_json::from_value(v.clone()).ok());
+    let pinned = value
+        .get("pinned")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
     let created_at = value
         .get("created_at")
         .and_then(|v| v.as_str())
@@ -169,6 +164,7 @@ pub fn from_db_value_favorite_provider(value: Value) -> Option<OpenCodeFavoriteP
         base_url,
         provider_config,
         diagnostics,
+        pinned,
         created_at,
         updated_at,
     })
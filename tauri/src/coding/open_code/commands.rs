@@ -1,13 +1,18 @@
 use indexmap::IndexMap;
 use serde_json::Value;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tauri::Emitter;
 
 use super::adapter;
 use super::types::*;
 use crate::coding::all_api_hub;
-use crate::coding::db_id::db_new_id;
+use crate::coding::apply_history;
+use crate::coding::atomic_write;
+use crate::coding::config_backup;
+use crate::coding::db_id::{db_extract_id, db_new_id};
+use crate::coding::global_headers;
+use crate::coding::jsonc_edit;
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
 use crate::coding::runtime_location;
 use crate::coding::skills::commands::resync_all_skills_if_tool_path_changed;
@@ -267,7 +272,7 @@ async fn write_opencode_config_file(
     state: tauri::State<'_, SqliteDbState>,
     config: &OpenCodeConfig,
 ) -> Result<(), String> {
-    let config_path_str = get_opencode_config_path(state).await?;
+    let config_path_str = get_opencode_config_path(state.clone()).await?;
     let config_path = Path::new(&config_path_str);
 
     if let Some(parent) = config_path.parent() {
@@ -284,12 +289,29 @@ async fn write_opencode_config_file(
         .map(|plugin_names| sanitize_opencode_plugin_list(plugin_names))
         .filter(|plugin_names| !plugin_names.is_empty());
 
-    let json_content = serde_json::to_string_pretty(&sanitized_config)
+    let global_headers = global_headers::load_global_headers(state.db(), "opencode")?;
+    let mut config_value = serde_json::to_value(&sanitized_config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    if let Some(global) = &global_headers {
+        global_headers::apply_opencode_global_headers(&mut config_value, global);
+    }
+    // Keep the user's comments when the current file is annotated JSONC
+    let json_content = jsonc_edit::render_config_for_path(config_path, &config_value)?;
 
-    fs::write(config_path, json_content)
+    config_backup::backup_before_write(config_path)?;
+    atomic_write::write_atomic(config_path, json_content)
         .map_err(|e| format!("Failed to write config file: {}", e))?;
 
+    if let Some(global) = &global_headers {
+        global_headers::mark_global_headers_applied(state.db(), "opencode", global)?;
+    }
+    let default_provider = config
+        .model
+        .as_deref()
+        .and_then(|model| model.split_once('/'))
+        .map(|(provider_id, _)| provider_id);
+    crate::coding::config_provenance::record_applied_config(config_path, default_provider);
+
     Ok(())
 }
 
@@ -297,6 +319,7 @@ async fn write_opencode_config_file(
 mod tests {
     use super::{
         is_opencode_plugin_equivalent, opencode_plugin_package_name, sanitize_opencode_plugin_list,
+        unique_provider_key,
     };
     use crate::coding::open_code::types::OpenCodePluginEntry;
     use serde_json::json;
@@ -416,6 +439,21 @@ mod tests {
             ))]
         );
     }
+
+    #[test]
+    fn unique_provider_key_slugs_name_and_avoids_taken_keys() {
+        let provider = || serde_json::from_value(json!({ "npm": "@ai-sdk/openai" })).unwrap();
+        let mut providers = indexmap::IndexMap::new();
+        assert_eq!(
+            unique_provider_key("My Relay (backup)", &providers),
+            "my-relay-backup"
+        );
+        assert_eq!(unique_provider_key("中转", &providers), "provider");
+
+        providers.insert("my-relay".to_string(), provider());
+        providers.insert("my-relay-2".to_string(), provider());
+        assert_eq!(unique_provider_key("My relay", &providers), "my-relay-3");
+    }
 }
 
 async fn get_opencode_prompt_file_path(
@@ -737,6 +775,109 @@ pub async fn save_opencode_config<R: tauri::Runtime>(
     apply_config_internal(state, &app, config, false).await
 }
 
+/// Config key for a provider copy named `name`: the name as a lowercase ASCII slug, with
+/// `-2`, `-3`, ... appended while the key is taken.
+fn unique_provider_key(name: &str, providers: &IndexMap<String, OpenCodeProvider>) -> String {
+    let slug = name
+        .to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let base = if slug.is_empty() {
+        "provider".to_string()
+    } else {
+        slug
+    };
+    let mut key = base.clone();
+    let mut suffix = 2;
+    while providers.contains_key(&key) {
+        key = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    key
+}
+
+/// Copy a provider of the OpenCode config under `new_name`. The copy keeps every field,
+/// gets a config key derived from the name and is added after the last provider.
+#[tauri::command]
+pub async fn duplicate_opencode_provider<R: tauri::Runtime>(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle<R>,
+    provider_id: String,
+    new_name: String,
+) -> Result<OpenCodeDuplicatedProvider, String> {
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err("Provider name is required".to_string());
+    }
+    let mut config = match read_opencode_config(state.clone()).await? {
+        ReadConfigResult::Success { config } => config,
+        ReadConfigResult::NotFound { path } => {
+            return Err(format!("OpenCode config file not found: {}", path))
+        }
+        ReadConfigResult::ParseError { error, .. } | ReadConfigResult::Error { error } => {
+            return Err(error)
+        }
+    };
+
+    let providers = config.provider.get_or_insert_with(IndexMap::new);
+    let mut provider_config = providers
+        .get(&provider_id)
+        .cloned()
+        .ok_or_else(|| format!("OpenCode provider '{}' not found", provider_id))?;
+    let new_id = unique_provider_key(&new_name, providers);
+    provider_config.name = Some(new_name);
+    providers.insert(new_id.clone(), provider_config.clone());
+
+    apply_config_internal(state, &app, config, false).await?;
+    Ok(OpenCodeDuplicatedProvider {
+        provider_id: new_id,
+        provider_config,
+    })
+}
+
+/// Roll the OpenCode config file back to the backup taken before an earlier write
+/// (1 = newest, up to 3).
+#[tauri::command]
+pub async fn restore_opencode_config_backup<R: tauri::Runtime>(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle<R>,
+    index: u8,
+) -> Result<(), String> {
+    let config_path_str = get_opencode_config_path(state.clone()).await?;
+    config_backup::restore_backup(Path::new(&config_path_str), index)?;
+
+    let _ = app.emit("config-changed", "window");
+    crate::coding::wsl::emit_apply_sync_request(state.db(), &app, "opencode");
+    Ok(())
+}
+
+/// Replace the OpenCode config file with a config holding only `$schema`, after backing it
+/// up. OpenCode has no applied-provider flag; the providers live in this file.
+#[tauri::command]
+pub async fn reset_opencode_config<R: tauri::Runtime>(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle<R>,
+) -> Result<(), String> {
+    let config_path = PathBuf::from(get_opencode_config_path(state.clone()).await?);
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create OpenCode config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&serde_json::json!({
+        "$schema": "https://opencode.ai/config.json",
+    }))
+    .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    config_backup::backup_before_write(&config_path)?;
+    atomic_write::write_atomic(&config_path, content)
+        .map_err(|e| format!("Failed to write config file: {}", e))?;
+
+    let _ = app.emit("config-changed", "window");
+    crate::coding::wsl::emit_apply_sync_request(state.db(), &app, "opencode");
+    Ok(())
+}
+
 /// Internal function to save config and emit events
 pub async fn apply_config_internal<R: tauri::Runtime>(
     state: tauri::State<'_, SqliteDbState>,
@@ -744,15 +885,19 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     config: OpenCodeConfig,
     from_tray: bool,
 ) -> Result<(), String> {
+    let config_path = PathBuf::from(get_opencode_config_path(state.clone()).await?);
+    let undo_entry = apply_history::capture(None, &[config_path]);
     write_opencode_config_file(state.clone(), &config).await?;
+    if let Some(entry) = undo_entry {
+        apply_history::push(state.db(), "opencode", entry);
+    }
 
     // Notify based on source
     let payload = if from_tray { "tray" } else { "window" };
     let _ = app.emit("config-changed", payload);
 
     // Trigger WSL sync via event (Windows only)
-    #[cfg(target_os = "windows")]
-    let _ = app.emit("wsl-sync-request-opencode", ());
+    crate::coding::wsl::emit_apply_sync_request(state.db(), app, "opencode");
 
     // Async sync providers to favorite DB in background (non-blocking)
     let db = state.db().clone();
@@ -1086,10 +1231,12 @@ pub async fn get_provider_models(
 // ============================================================================
 
 /// Get unified model list combining custom providers and official providers from auth.json
-/// Returns all available models sorted by display name
+/// Returns models in the user's saved order (default: sorted by display name), without hidden ones.
+/// With `dedupe`, models sharing a model id collapse into one entry listing their providers.
 #[tauri::command]
 pub async fn get_opencode_unified_models(
     state: tauri::State<'_, SqliteDbState>,
+    dedupe: Option<bool>,
 ) -> Result<Vec<UnifiedModelOption>, String> {
     // Read auth.json to get official provider ids
     let auth_channels = super::free_models::read_auth_channels();
@@ -1105,8 +1252,14 @@ pub async fn get_opencode_unified_models(
     let models =
         super::free_models::get_unified_models(&state, custom_providers.as_ref(), &auth_channels)
             .await;
+    let preferences = super::model_order::load_model_preferences(state.db())?;
+    let models = super::model_order::apply_model_preferences(models, &preferences);
 
-    Ok(models)
+    if dedupe.unwrap_or(false) {
+        Ok(super::model_order::dedupe_models(models, &preferences))
+    } else {
+        Ok(models)
+    }
 }
 
 // ============================================================================
@@ -1219,6 +1372,7 @@ fn favorite_provider_payload(
     provider_id: &str,
     provider_config: &OpenCodeProvider,
     diagnostics: Option<OpenCodeDiagnosticsConfig>,
+    pinned: bool,
     created_at: &str,
     updated_at: &str,
 ) -> Result<Value, String> {
@@ -1237,6 +1391,7 @@ fn favorite_provider_payload(
         "base_url": base_url,
         "provider_config": provider_config_json,
         "diagnostics": diagnostics,
+        "pinned": pinned,
         "created_at": created_at,
         "updated_at": updated_at,
     }))
@@ -1500,10 +1655,15 @@ pub async fn upsert_opencode_favorite_provider(
         .as_ref()
         .map(|record| record.id.clone())
         .unwrap_or_else(|| provider_id.clone());
+    let pinned = existing_record
+        .as_ref()
+        .map(|record| record.pinned)
+        .unwrap_or(false);
     let payload = favorite_provider_payload(
         &provider_id,
         &provider_config,
         diagnostics_to_save,
+        pinned,
         &created_at,
         &now,
     )?;
@@ -1525,6 +1685,56 @@ pub async fn upsert_opencode_favorite_provider(
         .ok_or_else(|| "Failed to parse favorite provider".to_string())
 }
 
+/// Pin or unpin a favorite provider for quick apply from the pinned list
+#[tauri::command]
+pub async fn set_opencode_favorite_provider_pinned(
+    state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    provider_id: String,
+    pinned: bool,
+) -> Result<(), String> {
+    let db = state.db();
+    let record = find_favorite_provider_record(db, &provider_id)?
+        .ok_or_else(|| format!("Favorite provider '{}' not found", provider_id))?;
+    let record_id = db_extract_id(&record);
+    let now = chrono::Local::now().to_rfc3339();
+    db.with_conn(|conn| {
+        db_patch_fields(
+            conn,
+            DbTable::OpenCodeFavoriteProvider,
+            &record_id,
+            &[
+                ("pinned", Value::Bool(pinned)),
+                ("updated_at", Value::String(now.clone())),
+            ],
+        )
+        .map(|_| ())
+    })?;
+
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+/// Save a provider that only exists in OpenCode's auth.json as a favorite provider.
+/// The API key is copied from auth.json; base URL and SDK package come from models.dev.
+#[tauri::command]
+pub async fn opencode_adopt_auth_provider(
+    state: tauri::State<'_, SqliteDbState>,
+    name: String,
+) -> Result<(), String> {
+    let provider_id = name.trim().to_string();
+    if provider_id.is_empty() {
+        return Err("Provider name is required".to_string());
+    }
+    let provider_config = super::free_models::build_adopted_auth_provider(&provider_id)?;
+    upsert_opencode_favorite_provider(state, provider_id.clone(), provider_config, None).await?;
+    log::info!(
+        "Adopted OpenCode auth provider '{}' as favorite",
+        provider_id
+    );
+    Ok(())
+}
+
 /// Delete a favorite provider from database
 #[tauri::command]
 pub async fn delete_opencode_favorite_provider(
@@ -1,6 +1,6 @@
 use super::types::{
     FreeModel, GetAuthProvidersResponse, OfficialModel, OfficialProvider, OpenCodeProvider,
-    ProviderModelsData, UnifiedModelOption,
+    OpenCodeProviderOptions, ProviderModelsData, UnifiedModelOption,
 };
 use crate::db::SqliteDbState;
 use crate::http_client;
@@ -94,6 +94,22 @@ fn write_cache_file(cache: &ModelsCache) -> Result<(), String> {
     Ok(())
 }
 
+/// Delete the cache file; returns the number of providers and bytes removed.
+/// Readers fall back to the embedded defaults and refresh in the background.
+pub fn clear_models_cache() -> Result<(usize, u64), String> {
+    let Some(path) = get_cache_file_path() else {
+        return Ok((0, 0));
+    };
+    let Ok(metadata) = fs::metadata(&path) else {
+        return Ok((0, 0));
+    };
+    let providers = read_cache_file()
+        .and_then(|cache| cache.providers.as_object().map(|map| map.len()))
+        .unwrap_or(0);
+    fs::remove_file(&path).map_err(|e| format!("Failed to remove models cache: {}", e))?;
+    Ok((providers, metadata.len()))
+}
+
 /// Read a specific provider's data from cache file
 fn read_provider_from_cache(provider_id: &str) -> Option<ProviderModelsData> {
     let cache = read_cache_file()?;
@@ -680,6 +696,90 @@ pub fn resolve_provider_api_base_url(provider_id: &str) -> Option<String> {
         .or_else(|| get_official_provider_default_base_url(provider_id).map(str::to_string))
 }
 
+/// SDK package models.dev lists for a provider, e.g. "@ai-sdk/anthropic"
+pub fn resolve_provider_npm(provider_id: &str) -> Option<String> {
+    read_provider_from_cache(provider_id)
+        .or_else(|| read_provider_from_defaults(provider_id))
+        .and_then(|provider_data| {
+            provider_data
+                .value
+                .get("npm")
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        })
+}
+
+const ADOPTED_PROVIDER_DEFAULT_NPM: &str = "@ai-sdk/openai-compatible";
+
+/// Build a provider config from an auth.json API key entry plus models.dev metadata
+fn adopted_provider_config(
+    provider_id: &str,
+    entry: &AuthEntry,
+    provider_data: Option<&serde_json::Value>,
+    base_url: Option<String>,
+) -> Result<OpenCodeProvider, String> {
+    if entry.auth_type != "api" {
+        return Err(format!(
+            "Provider '{}' uses {} auth; only API key entries can be adopted because OpenCode refreshes OAuth tokens itself",
+            provider_id, entry.auth_type
+        ));
+    }
+    let api_key = entry
+        .key
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("Provider '{}' has no API key in auth.json", provider_id))?;
+    let base_url = base_url
+        .ok_or_else(|| format!("No API base URL is known for provider '{}'", provider_id))?;
+    let metadata_str = |field: &str| {
+        provider_data
+            .and_then(|value| value.get(field))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+
+    Ok(OpenCodeProvider {
+        api: None,
+        env: None,
+        id: None,
+        npm: Some(metadata_str("npm").unwrap_or_else(|| ADOPTED_PROVIDER_DEFAULT_NPM.to_string())),
+        name: Some(metadata_str("name").unwrap_or_else(|| provider_id.to_string())),
+        options: Some(OpenCodeProviderOptions {
+            base_url: Some(base_url),
+            api_key: Some(api_key.to_string()),
+            headers: None,
+            timeout: None,
+            set_cache_key: None,
+            extra: serde_json::Map::new(),
+        }),
+        models: IndexMap::new(),
+        whitelist: None,
+        blacklist: None,
+        extra: serde_json::Map::new(),
+    })
+}
+
+/// Turn an existing auth.json provider into a provider config that can be saved as a favorite.
+/// Errors when the provider is missing from auth.json or is not an API key entry.
+pub fn build_adopted_auth_provider(provider_id: &str) -> Result<OpenCodeProvider, String> {
+    if provider_id == OPENCODE_PROVIDER_ID {
+        return Err("The built-in OpenCode provider cannot be adopted".to_string());
+    }
+    let auth_map = read_auth_map()?;
+    let entry = auth_map
+        .get(provider_id)
+        .ok_or_else(|| format!("Provider '{}' not found in auth.json", provider_id))?;
+    let provider_data =
+        read_provider_from_cache(provider_id).or_else(|| read_provider_from_defaults(provider_id));
+    adopted_provider_config(
+        provider_id,
+        entry,
+        provider_data.as_ref().map(|data| &data.value),
+        resolve_provider_api_base_url(provider_id),
+    )
+}
+
 pub fn get_resolved_auth_provider_ids() -> Vec<String> {
     let auth_map = match read_auth_map() {
         Ok(map) => map,
@@ -759,6 +859,7 @@ fn push_unified_model_option(
         is_free,
         base_model_id,
         experimental_mode,
+        providers: Vec::new(),
     });
 }
 
@@ -859,6 +960,7 @@ pub async fn get_unified_models(
                     is_free: false,
                     base_model_id: None,
                     experimental_mode: None,
+                    providers: Vec::new(),
                 });
             }
 
@@ -963,6 +1065,7 @@ pub async fn get_unified_models(
                         is_free: true,
                         base_model_id: free_model.base_model_id,
                         experimental_mode: free_model.experimental_mode,
+                        providers: Vec::new(),
                     });
                 }
                 free_vec.sort_by(|a, b| a.display_name.cmp(&b.display_name));
@@ -1308,6 +1411,7 @@ mod tests {
                 is_free: false,
                 base_model_id: None,
                 experimental_mode: None,
+                providers: Vec::new(),
             },
             UnifiedModelOption {
                 id: "openai/gpt-5.5-fast".to_string(),
@@ -1317,6 +1421,7 @@ mod tests {
                 is_free: false,
                 base_model_id: Some("gpt-5.5".to_string()),
                 experimental_mode: Some("fast".to_string()),
+                providers: Vec::new(),
             },
         ];
 
@@ -1358,4 +1463,69 @@ mod tests {
             .collect();
         assert_eq!(filtered_ids, vec!["gpt-5.5"]);
     }
+
+    #[test]
+    fn adopted_provider_config_uses_api_key_and_models_dev_metadata() {
+        let entry = AuthEntry {
+            auth_type: "api".to_string(),
+            key: Some(" sk-test ".to_string()),
+            access: None,
+            refresh: None,
+        };
+        let metadata = json!({ "npm": "@ai-sdk/deepseek", "name": "DeepSeek" });
+
+        let provider = adopted_provider_config(
+            "deepseek",
+            &entry,
+            Some(&metadata),
+            Some("https://api.deepseek.com".to_string()),
+        )
+        .expect("adoptable provider");
+        assert_eq!(provider.npm.as_deref(), Some("@ai-sdk/deepseek"));
+        assert_eq!(provider.name.as_deref(), Some("DeepSeek"));
+        let options = provider.options.expect("options");
+        assert_eq!(
+            options.base_url.as_deref(),
+            Some("https://api.deepseek.com")
+        );
+        assert_eq!(options.api_key.as_deref(), Some("sk-test"));
+
+        let fallback = adopted_provider_config(
+            "relay",
+            &entry,
+            None,
+            Some("https://relay.example.com/v1".to_string()),
+        )
+        .expect("adoptable provider");
+        assert_eq!(fallback.npm.as_deref(), Some(ADOPTED_PROVIDER_DEFAULT_NPM));
+        assert_eq!(fallback.name.as_deref(), Some("relay"));
+    }
+
+    #[test]
+    fn adopted_provider_config_rejects_oauth_and_missing_base_url() {
+        let oauth = AuthEntry {
+            auth_type: "oauth".to_string(),
+            key: None,
+            access: Some("access-token".to_string()),
+            refresh: Some("refresh-token".to_string()),
+        };
+        assert!(adopted_provider_config(
+            "anthropic",
+            &oauth,
+            None,
+            Some("https://api.anthropic.com/v1".to_string())
+        )
+        .unwrap_err()
+        .contains("oauth auth"));
+
+        let api = AuthEntry {
+            auth_type: "api".to_string(),
+            key: Some("sk-test".to_string()),
+            access: None,
+            refresh: None,
+        };
+        assert!(adopted_provider_config("unknown", &api, None, None)
+            .unwrap_err()
+            .contains("No API base URL"));
+    }
 }
@@ -2,7 +2,9 @@ pub mod adapter;
 pub mod commands;
 pub mod free_models;
 pub mod markdown_agents;
+pub mod model_order;
 pub mod models_api;
+pub mod models_cache;
 pub mod shell_env;
 pub mod tray_support;
 pub mod types;
@@ -10,5 +12,7 @@ pub mod types;
 pub use commands::*;
 pub use free_models::*;
 pub use markdown_agents::*;
+pub use model_order::*;
 pub use models_api::*;
+pub use models_cache::*;
 pub use types::*;
@@ -0,0 +1,328 @@
+//! Ordering and hiding of models in the OpenCode unified model list.
+//!
+//! Preferences live in a `model_preferences` record of the OpenCode common config table, next
+//! to the `common` record, so saving the common config from the settings page never drops
+//! them. Ids that no longer match a model are kept, so a provider that is removed and added
+//! back keeps its place.
+//!
+//! Deduplication is opt-in per call: the raw list stays the default because a model id
+//! offered by two providers is still two distinct `provider/model` choices in opencode.json.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde_json::json;
+
+use super::types::{OpenCodeModelPreferences, UnifiedModelOption};
+use crate::db::helpers::{db_get, db_put};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+const MODEL_PREFERENCES_RECORD_ID: &str = "model_preferences";
+
+fn string_map(value: Option<&serde_json::Value>) -> BTreeMap<String, String> {
+    value
+        .and_then(|value| value.as_object())
+        .map(|object| {
+            object
+                .iter()
+                .filter_map(|(key, value)| value.as_str().map(|v| (key.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn string_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|value| value.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Trim ids and drop empties and duplicates, keeping the first occurrence.
+fn normalize_ids(ids: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    ids.into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty() && seen.insert(id.clone()))
+        .collect()
+}
+
+pub(crate) fn load_model_preferences(
+    db: &SqliteDbState,
+) -> Result<OpenCodeModelPreferences, String> {
+    let record = db.with_conn(|conn| {
+        db_get(
+            conn,
+            DbTable::OpenCodeCommonConfig,
+            MODEL_PREFERENCES_RECORD_ID,
+        )
+    })?;
+    Ok(record
+        .map(|record| OpenCodeModelPreferences {
+            order: string_list(record.get("order")),
+            hidden: string_list(record.get("hidden")),
+            preferred_providers: string_map(record.get("preferred_providers")),
+        })
+        .unwrap_or_default())
+}
+
+fn save_model_preferences(
+    db: &SqliteDbState,
+    preferences: &OpenCodeModelPreferences,
+) -> Result<(), String> {
+    let record = json!({
+        "order": preferences.order,
+        "hidden": preferences.hidden,
+        "preferred_providers": preferences.preferred_providers,
+        "updated_at": chrono::Local::now().to_rfc3339(),
+    });
+    db.with_conn(|conn| {
+        db_put(
+            conn,
+            DbTable::OpenCodeCommonConfig,
+            MODEL_PREFERENCES_RECORD_ID,
+            &record,
+        )
+    })
+}
+
+/// Drop hidden models and move ordered ones to the front in preference order.
+/// Models without a position keep their default relative order after them.
+pub(crate) fn apply_model_preferences(
+    models: Vec<UnifiedModelOption>,
+    preferences: &OpenCodeModelPreferences,
+) -> Vec<UnifiedModelOption> {
+    let hidden: HashSet<&str> = preferences.hidden.iter().map(String::as_str).collect();
+    let rank: HashMap<&str, usize> = preferences
+        .order
+        .iter()
+        .enumerate()
+        .map(|(index, id)| (id.as_str(), index))
+        .collect();
+
+    let mut visible: Vec<UnifiedModelOption> = models
+        .into_iter()
+        .filter(|model| !hidden.contains(model.id.as_str()))
+        .collect();
+    // Stable sort: unranked models share usize::MAX and stay in their original order.
+    visible.sort_by_key(|model| rank.get(model.id.as_str()).copied().unwrap_or(usize::MAX));
+    visible
+}
+
+/// Collapse models that share a model id into one entry listing every provider in
+/// `providers`. The entry keeps the position of the group's first model and uses the
+/// preferred provider when it is still in the group, otherwise the first one.
+pub(crate) fn dedupe_models(
+    models: Vec<UnifiedModelOption>,
+    preferences: &OpenCodeModelPreferences,
+) -> Vec<UnifiedModelOption> {
+    let mut groups: Vec<(String, Vec<UnifiedModelOption>)> = Vec::new();
+    let mut group_index: HashMap<String, usize> = HashMap::new();
+    for model in models {
+        match group_index.get(&model.model_id) {
+            Some(index) => groups[*index].1.push(model),
+            None => {
+                group_index.insert(model.model_id.clone(), groups.len());
+                groups.push((model.model_id.clone(), vec![model]));
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(model_id, group)| {
+            let providers: Vec<String> = group
+                .iter()
+                .map(|model| model.provider_id.clone())
+                .collect();
+            let preferred = preferences
+                .preferred_providers
+                .get(&model_id)
+                .and_then(|provider_id| providers.iter().position(|id| id == provider_id))
+                .unwrap_or(0);
+            let mut chosen = group
+                .into_iter()
+                .nth(preferred)
+                .expect("group is never empty");
+            chosen.providers = providers;
+            chosen
+        })
+        .collect()
+}
+
+/// Get the saved order and hidden models of the unified model list
+#[tauri::command]
+pub async fn get_opencode_model_preferences(
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<OpenCodeModelPreferences, String> {
+    load_model_preferences(state.db())
+}
+
+/// Set which models appear first in the unified model list, and in what order.
+/// An empty list restores the default ordering.
+#[tauri::command]
+pub async fn set_opencode_model_order(
+    state: tauri::State<'_, SqliteDbState>,
+    order: Vec<String>,
+) -> Result<(), String> {
+    let db = state.db();
+    let mut preferences = load_model_preferences(db)?;
+    preferences.order = normalize_ids(order);
+    save_model_preferences(db, &preferences)
+}
+
+/// Hide a model ("provider_id/model_id") from the unified model list
+#[tauri::command]
+pub async fn opencode_hide_model(
+    state: tauri::State<'_, SqliteDbState>,
+    id: String,
+) -> Result<(), String> {
+    let id = id.trim().to_string();
+    if id.is_empty() {
+        return Err("Model id is required".to_string());
+    }
+    let db = state.db();
+    let mut preferences = load_model_preferences(db)?;
+    if preferences.hidden.contains(&id) {
+        return Ok(());
+    }
+    preferences.hidden.push(id);
+    save_model_preferences(db, &preferences)
+}
+
+/// Choose which provider a deduplicated model id resolves to; None clears the choice.
+#[tauri::command]
+pub async fn opencode_set_preferred_model_provider(
+    state: tauri::State<'_, SqliteDbState>,
+    model_id: String,
+    provider_id: Option<String>,
+) -> Result<(), String> {
+    let model_id = model_id.trim().to_string();
+    if model_id.is_empty() {
+        return Err("Model id is required".to_string());
+    }
+    let db = state.db();
+    let mut preferences = load_model_preferences(db)?;
+    match provider_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+    {
+        Some(provider_id) => {
+            preferences
+                .preferred_providers
+                .insert(model_id, provider_id.to_string());
+        }
+        None => {
+            preferences.preferred_providers.remove(&model_id);
+        }
+    }
+    save_model_preferences(db, &preferences)
+}
+
+/// Show a previously hidden model in the unified model list again
+#[tauri::command]
+pub async fn opencode_show_model(
+    state: tauri::State<'_, SqliteDbState>,
+    id: String,
+) -> Result<(), String> {
+    let id = id.trim();
+    let db = state.db();
+    let mut preferences = load_model_preferences(db)?;
+    let previous_len = preferences.hidden.len();
+    preferences.hidden.retain(|hidden_id| hidden_id != id);
+    if preferences.hidden.len() == previous_len {
+        return Ok(());
+    }
+    save_model_preferences(db, &preferences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str) -> UnifiedModelOption {
+        let (provider_id, model_id) = id.split_once('/').unwrap();
+        UnifiedModelOption {
+            id: id.to_string(),
+            display_name: id.to_string(),
+            provider_id: provider_id.to_string(),
+            model_id: model_id.to_string(),
+            is_free: false,
+            base_model_id: None,
+            experimental_mode: None,
+            providers: Vec::new(),
+        }
+    }
+
+    fn ids(models: &[UnifiedModelOption]) -> Vec<&str> {
+        models.iter().map(|model| model.id.as_str()).collect()
+    }
+
+    #[test]
+    fn preferences_reorder_and_hide_models() {
+        let models = vec![
+            model("a/one"),
+            model("b/two"),
+            model("c/three"),
+            model("d/four"),
+        ];
+        let preferences = OpenCodeModelPreferences {
+            order: vec![
+                "c/three".to_string(),
+                "gone/model".to_string(),
+                "a/one".to_string(),
+            ],
+            hidden: vec!["b/two".to_string()],
+            preferred_providers: BTreeMap::new(),
+        };
+
+        let ordered = apply_model_preferences(models.clone(), &preferences);
+        assert_eq!(ids(&ordered), vec!["c/three", "a/one", "d/four"]);
+
+        let untouched = apply_model_preferences(models, &OpenCodeModelPreferences::default());
+        assert_eq!(ids(&untouched), vec!["a/one", "b/two", "c/three", "d/four"]);
+    }
+
+    #[test]
+    fn dedupe_collapses_model_ids_and_honours_preferred_provider() {
+        let models = vec![
+            model("relay/gpt-5"),
+            model("anthropic/claude"),
+            model("openai/gpt-5"),
+            model("zenmux/claude"),
+        ];
+        let mut preferences = OpenCodeModelPreferences::default();
+        preferences
+            .preferred_providers
+            .insert("gpt-5".to_string(), "openai".to_string());
+        preferences
+            .preferred_providers
+            .insert("claude".to_string(), "removed".to_string());
+
+        let deduped = dedupe_models(models, &preferences);
+
+        assert_eq!(ids(&deduped), vec!["openai/gpt-5", "anthropic/claude"]);
+        assert_eq!(deduped[0].providers, vec!["relay", "openai"]);
+        assert_eq!(deduped[1].providers, vec!["anthropic", "zenmux"]);
+    }
+
+    #[test]
+    fn normalize_ids_trims_and_dedupes() {
+        assert_eq!(
+            normalize_ids(vec![
+                " a/one ".to_string(),
+                String::new(),
+                "b/two".to_string(),
+                "a/one".to_string(),
+            ]),
+            vec!["a/one", "b/two"]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::coding::tls_inspect;
 use crate::db::SqliteDbState;
 use crate::http_client;
 use futures_util::StreamExt;
@@ -31,6 +32,15 @@ pub struct FetchModelsRequest {
     pub sdk_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_url: Option<String>,
+    /// Tool whose global headers apply ("claude" | "opencode"); request headers override them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Proxy for this request only; the global proxy setting applies when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+    /// Retries on 429/502/503/504 and connection resets (at most 2; default 0, one attempt)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
 }
 
 /// OpenAI compatible models list response
@@ -144,6 +154,18 @@ pub struct ConnectivityTestRequest {
     pub model_ids: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout_secs: Option<u64>,
+    /// How many models are tested at once (default 5, at most 16)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// Tool whose global headers apply ("claude" | "opencode"); request headers override them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Proxy for this request only; the global proxy setting applies when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+    /// Retries on 429/502/503/504 and connection resets (at most 2; default 0, one attempt)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -164,6 +186,16 @@ pub struct ConnectivityTestResult {
     pub response_headers: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_body: Option<Value>,
+    /// Output tokens per second between the first and last byte (streaming only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_per_second: Option<f64>,
+    /// Number of stream events received: SSE `data:` events, or array elements for Google
+    /// native streams (streaming only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_count: Option<u64>,
+    /// Reply text extracted from the stream, for the preview (Google native streams only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_text: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -172,6 +204,39 @@ pub struct ConnectivityTestResponse {
     pub results: Vec<ConnectivityTestResult>,
 }
 
+/// Proxy-aware client; certificate checks are skipped only when the stored provider
+/// `provider_id` opted in. A non-empty `proxy_url` replaces the global proxy setting.
+async fn provider_client(
+    state: &SqliteDbState,
+    timeout_secs: u64,
+    provider_id: Option<&str>,
+    provider_label: &str,
+    proxy_url: Option<&str>,
+) -> Result<reqwest::Client, String> {
+    let allow_insecure_tls = match provider_id {
+        Some(provider_id) => tls_inspect::provider_allows_insecure_tls(state, provider_id)?,
+        None => false,
+    };
+    if let Some(proxy_url) = proxy_url.map(str::trim).filter(|url| !url.is_empty()) {
+        if allow_insecure_tls {
+            log::warn!(
+                "TLS certificate verification is DISABLED for provider '{}' (allow_insecure_tls opt-in)",
+                provider_label
+            );
+        }
+        return http_client::client_with_proxy_override(
+            proxy_url,
+            timeout_secs,
+            allow_insecure_tls,
+        );
+    }
+    if allow_insecure_tls {
+        http_client::client_with_timeout_insecure_tls(state, timeout_secs, provider_label).await
+    } else {
+        http_client::client_with_timeout(state, timeout_secs).await
+    }
+}
+
 #[derive(Debug, Clone)]
 struct ResolvedProviderRequest {
     base_url: String,
@@ -245,11 +310,84 @@ fn build_models_url(
     }
 }
 
+/// Upper bounds for following Anthropic `has_more` pagination, so a proxy that keeps
+/// answering `has_more: true` cannot loop forever.
+const ANTHROPIC_MODELS_MAX_PAGES: usize = 10;
+const ANTHROPIC_MODELS_MAX_COUNT: usize = 1000;
+
+async fn fetch_anthropic_models_page(
+    request: Option<&reqwest::RequestBuilder>,
+    after_id: String,
+    max_retries: u32,
+) -> Result<AnthropicModelsResponse, String> {
+    let request = request
+        .and_then(reqwest::RequestBuilder::try_clone)
+        .ok_or_else(|| "Failed to repeat models request for the next page".to_string())?;
+    let response =
+        http_client::send_with_retry(request.query(&[("after_id", after_id)]), max_retries)
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("API error: {} - {}", status, body));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Anthropic response: {}", e))
+}
+
+/// Follow `has_more`/`last_id` from the first page, stopping at the page and model caps or
+/// when a page does not advance the cursor.
+async fn collect_anthropic_model_pages<F, Fut>(
+    first_page: AnthropicModelsResponse,
+    mut fetch_after: F,
+) -> Result<Vec<AnthropicModel>, String>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = Result<AnthropicModelsResponse, String>>,
+{
+    let mut models = Vec::new();
+    let mut page = first_page;
+    let mut pages = 1;
+    loop {
+        models.extend(page.data);
+        let Some(last_id) = page.last_id.filter(|_| page.has_more == Some(true)) else {
+            break;
+        };
+        if pages >= ANTHROPIC_MODELS_MAX_PAGES || models.len() >= ANTHROPIC_MODELS_MAX_COUNT {
+            log::warn!(
+                "Stopped following Anthropic models pagination after {} pages / {} models",
+                pages,
+                models.len()
+            );
+            break;
+        }
+        let next_page = fetch_after(last_id.clone()).await?;
+        pages += 1;
+        if next_page.last_id.as_deref() == Some(last_id.as_str()) {
+            // The proxy ignored `after_id` and returned the same page again
+            break;
+        }
+        page = next_page;
+    }
+    models.truncate(ANTHROPIC_MODELS_MAX_COUNT);
+    Ok(models)
+}
+
 /// Fetch models list from provider API
 #[tauri::command]
 pub async fn fetch_provider_models(
     state: tauri::State<'_, SqliteDbState>,
     request: FetchModelsRequest,
+) -> Result<FetchModelsResponse, String> {
+    fetch_provider_models_internal(&state, request).await
+}
+
+pub(crate) async fn fetch_provider_models_internal(
+    state: &SqliteDbState,
+    request: FetchModelsRequest,
 ) -> Result<FetchModelsResponse, String> {
     let resolved_request = resolve_provider_request(
         request.provider_id.as_deref(),
@@ -258,7 +396,17 @@ pub async fn fetch_provider_models(
     );
 
     // Create HTTP client with timeout and proxy support
-    let client = http_client::client_with_timeout(&state, 30).await?;
+    let client = provider_client(
+        state,
+        30,
+        request.provider_id.as_deref(),
+        request
+            .provider_id
+            .as_deref()
+            .unwrap_or(&resolved_request.base_url),
+        request.proxy_url.as_deref(),
+    )
+    .await?;
 
     // Build request URL based on API type and SDK type
     // Use custom_url if provided, otherwise calculate it
@@ -321,8 +469,13 @@ pub async fn fetch_provider_models(
         }
     }
 
-    // Add custom headers
-    if let Some(headers) = &request.headers {
+    // Add custom headers (tool-level global headers first, request headers override)
+    let headers = crate::coding::global_headers::request_headers_with_global(
+        state,
+        request.tool.as_deref(),
+        request.headers.as_ref(),
+    )?;
+    if let Some(headers) = &headers {
         if let Some(obj) = headers.as_object() {
             for (key, value) in obj {
                 if let Some(v) = value.as_str() {
@@ -332,9 +485,12 @@ pub async fn fetch_provider_models(
         }
     }
 
-    // Send request
-    let response = req_builder
-        .send()
+    // Anthropic lists are paginated; later pages repeat this request with `after_id`
+    let next_page_request = req_builder.try_clone();
+
+    // Send request, retrying transient failures when the caller opted in
+    let max_retries = request.max_retries.unwrap_or(0);
+    let response = http_client::send_with_retry(req_builder, max_retries)
         .await
         .map_err(|e| format!("Request failed: {}", e))?;
 
@@ -380,9 +536,12 @@ pub async fn fetch_provider_models(
                 .json()
                 .await
                 .map_err(|e| format!("Failed to parse Anthropic response: {}", e))?;
+            let anthropic_models = collect_anthropic_model_pages(anthropic_response, |after_id| {
+                fetch_anthropic_models_page(next_page_request.as_ref(), after_id, max_retries)
+            })
+            .await?;
 
-            anthropic_response
-                .data
+            anthropic_models
                 .into_iter()
                 .map(|m| {
                     let name = m.display_name.clone().unwrap_or_else(|| m.id.clone());
@@ -489,7 +648,57 @@ fn parse_json_or_wrap(text: &str) -> Value {
     }
 }
 
-fn parse_stream_response(text: &str) -> Value {
+/// Google `streamGenerateContent` without `alt=sse` streams a single JSON array of
+/// `GenerateContentResponse` objects instead of SSE events.
+fn is_google_json_stream(npm: &str, text: &str) -> bool {
+    npm == "@ai-sdk/google" && !text.trim_start().starts_with("data:")
+}
+
+/// Elements of a streamed JSON array (`[{...},\r\n{...}]`). A body cut off mid-stream keeps
+/// its complete elements and the unparsed tail as `{"raw": ...}`.
+fn parse_json_array_stream(text: &str) -> Vec<Value> {
+    let mut items = Vec::new();
+    let trimmed = text.trim_start();
+    let mut rest = trimmed.strip_prefix('[').unwrap_or(trimmed);
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() || rest.starts_with(']') {
+            break;
+        }
+        let mut values = serde_json::Deserializer::from_str(rest).into_iter::<Value>();
+        match values.next() {
+            Some(Ok(value)) => {
+                items.push(wrap_json_object(value));
+                rest = &rest[values.byte_offset()..];
+            }
+            _ => {
+                items.push(json!({ "raw": rest }));
+                break;
+            }
+        }
+    }
+    items
+}
+
+/// Reply text of Google stream chunks: every `candidates[0].content.parts[].text`, in order.
+fn google_stream_text(items: &[Value]) -> String {
+    items
+        .iter()
+        .filter_map(|item| item.pointer("/candidates/0/content/parts")?.as_array())
+        .flatten()
+        .filter_map(|part| part.get("text")?.as_str())
+        .collect()
+}
+
+/// Parse a streamed response body for display, branching on the provider SDK: Google native
+/// streams are a JSON array, everything else is SSE `data:` events.
+fn parse_stream_response(npm: &str, text: &str) -> Value {
+    if is_google_json_stream(npm, text) {
+        let items = parse_json_array_stream(text);
+        if !items.is_empty() {
+            return Value::Array(items);
+        }
+    }
     let mut items: Vec<Value> = Vec::new();
     for line in text.lines() {
         let trimmed = line.trim();
@@ -518,6 +727,75 @@ fn parse_stream_response(text: &str) -> Value {
     Value::Array(items)
 }
 
+/// Output token count reported by a stream event, across the supported API shapes:
+/// Chat Completions `usage.completion_tokens`, Responses `response.usage.output_tokens`,
+/// Anthropic `usage.output_tokens` and Gemini `usageMetadata.candidatesTokenCount`.
+fn stream_event_output_tokens(event: &Value) -> Option<u64> {
+    event
+        .pointer("/usage/completion_tokens")
+        .or_else(|| event.pointer("/response/usage/output_tokens"))
+        .or_else(|| event.pointer("/usage/output_tokens"))
+        .or_else(|| event.pointer("/usageMetadata/candidatesTokenCount"))
+        .and_then(Value::as_u64)
+}
+
+/// Count SSE `data:` events and derive tokens per second over `streaming_ms`.
+/// Uses the last usage-reported output token count (usage values are cumulative); providers
+/// that never send usage fall back to one token per chunk.
+fn measure_stream_throughput(text: &str, streaming_ms: u64) -> (u64, Option<f64>) {
+    let mut chunk_count = 0u64;
+    let mut output_tokens = None;
+    for line in text.lines() {
+        let Some(payload) = line.trim().strip_prefix("data:") else {
+            continue;
+        };
+        let payload = payload.trim();
+        if payload.is_empty() || payload == "[DONE]" {
+            continue;
+        }
+        chunk_count += 1;
+        if let Some(tokens) = serde_json::from_str::<Value>(payload)
+            .ok()
+            .as_ref()
+            .and_then(stream_event_output_tokens)
+        {
+            output_tokens = Some(tokens);
+        }
+    }
+
+    (
+        chunk_count,
+        tokens_per_second(output_tokens.unwrap_or(chunk_count), streaming_ms),
+    )
+}
+
+/// Same as `measure_stream_throughput` for a Google JSON array stream: each parsed array
+/// element is one chunk.
+fn measure_google_stream_throughput(items: &[Value], streaming_ms: u64) -> (u64, Option<f64>) {
+    let chunks: Vec<&Value> = items
+        .iter()
+        .filter(|item| item.get("raw").is_none())
+        .collect();
+    let chunk_count = chunks.len() as u64;
+    // Usage is cumulative, so the last chunk that reports it wins
+    let output_tokens = chunks
+        .iter()
+        .rev()
+        .find_map(|chunk| stream_event_output_tokens(chunk));
+    (
+        chunk_count,
+        tokens_per_second(output_tokens.unwrap_or(chunk_count), streaming_ms),
+    )
+}
+
+fn tokens_per_second(tokens: u64, streaming_ms: u64) -> Option<f64> {
+    if streaming_ms == 0 || tokens == 0 {
+        None
+    } else {
+        Some(tokens as f64 * 1000.0 / streaming_ms as f64)
+    }
+}
+
 fn generate_anthropic_user_id() -> String {
     let user_hex = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
     let session_id = Uuid::new_v4();
@@ -863,7 +1141,8 @@ async fn run_connectivity_test_for_model(
     let request_headers_value = headers_to_value(&request_headers);
     let request_body_value = body.clone();
 
-    let response = match req_builder.send().await {
+    let max_retries = request.max_retries.unwrap_or(0);
+    let response = match http_client::send_with_retry(req_builder, max_retries).await {
         Ok(resp) => resp,
         Err(err) => {
             let status = if err.is_timeout() { "timeout" } else { "error" };
@@ -878,6 +1157,9 @@ async fn run_connectivity_test_for_model(
                 request_body: request_body_value,
                 response_headers: None,
                 response_body: None,
+                tokens_per_second: None,
+                chunk_count: None,
+                response_text: None,
             };
         }
     };
@@ -905,7 +1187,7 @@ async fn run_connectivity_test_for_model(
             Err(err) => {
                 let body_text = String::from_utf8_lossy(&body_bytes).to_string();
                 let response_body_value = if stream_enabled {
-                    parse_stream_response(&body_text)
+                    parse_stream_response(&request.npm, &body_text)
                 } else {
                     parse_json_or_wrap(&body_text)
                 };
@@ -920,6 +1202,9 @@ async fn run_connectivity_test_for_model(
                     request_body: request_body_value,
                     response_headers: Some(response_headers_value),
                     response_body: Some(response_body_value),
+                    tokens_per_second: None,
+                    chunk_count: None,
+                    response_text: None,
                 };
             }
         }
@@ -932,7 +1217,7 @@ async fn run_connectivity_test_for_model(
 
     let body_text = String::from_utf8_lossy(&body_bytes).to_string();
     let response_body_value = if stream_enabled {
-        parse_stream_response(&body_text)
+        parse_stream_response(&request.npm, &body_text)
     } else {
         parse_json_or_wrap(&body_text)
     };
@@ -949,9 +1234,28 @@ async fn run_connectivity_test_for_model(
             request_body: request_body_value,
             response_headers: Some(response_headers_value),
             response_body: Some(response_body_value),
+            tokens_per_second: None,
+            chunk_count: None,
+            response_text: None,
         };
     }
 
+    let google_json_stream = stream_enabled && is_google_json_stream(&request.npm, &body_text);
+    let (chunk_count, tokens_per_second) = if stream_enabled {
+        let streaming_ms = total_ms.saturating_sub(first_byte_ms.unwrap_or(total_ms));
+        let (chunk_count, tokens_per_second) = match (&response_body_value, google_json_stream) {
+            (Value::Array(items), true) => measure_google_stream_throughput(items, streaming_ms),
+            _ => measure_stream_throughput(&body_text, streaming_ms),
+        };
+        (Some(chunk_count), tokens_per_second)
+    } else {
+        (None, None)
+    };
+    let response_text = match (&response_body_value, google_json_stream) {
+        (Value::Array(items), true) => Some(google_stream_text(items)),
+        _ => None,
+    };
+
     ConnectivityTestResult {
         model_id: model_id.to_string(),
         status: "success".to_string(),
@@ -963,46 +1267,126 @@ async fn run_connectivity_test_for_model(
         request_body: request_body_value,
         response_headers: Some(response_headers_value),
         response_body: Some(response_body_value),
+        tokens_per_second,
+        chunk_count,
+        response_text,
+    }
+}
+
+const DEFAULT_CONNECTIVITY_CONCURRENCY: usize = 5;
+/// Upper bound for a requested `concurrency`, so one batch cannot open unbounded connections
+const MAX_CONNECTIVITY_CONCURRENCY: usize = 16;
+
+/// Test one model, giving up after `timeout_secs` so a stalled stream cannot hold its slot
+/// in the batch past the deadline.
+async fn run_connectivity_test_with_timeout(
+    client: reqwest::Client,
+    request: &ConnectivityTestRequest,
+    model_id: &str,
+    timeout_secs: u64,
+) -> ConnectivityTestResult {
+    if request.base_url.trim().is_empty() {
+        return ConnectivityTestResult {
+            model_id: model_id.to_string(),
+            status: "error".to_string(),
+            first_byte_ms: None,
+            total_ms: None,
+            error_message: Some("Missing Base URL".to_string()),
+            request_url: String::new(),
+            request_headers: json!({}),
+            request_body: json!({}),
+            response_headers: None,
+            response_body: None,
+            tokens_per_second: None,
+            chunk_count: None,
+            response_text: None,
+        };
+    }
+
+    let start_time = Instant::now();
+    match tokio::time::timeout(
+        std::time::Duration::from_secs(timeout_secs),
+        run_connectivity_test_for_model(&client, request, model_id),
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(_) => ConnectivityTestResult {
+            model_id: model_id.to_string(),
+            status: "timeout".to_string(),
+            first_byte_ms: None,
+            total_ms: Some(start_time.elapsed().as_millis() as u64),
+            error_message: Some(format!("Timed out after {}s", timeout_secs)),
+            request_url: build_connectivity_url(
+                request.npm.as_str(),
+                request.base_url.as_str(),
+                model_id,
+                request.api_key.as_deref(),
+                request.stream.unwrap_or(true),
+            ),
+            request_headers: json!({}),
+            request_body: json!({}),
+            response_headers: None,
+            response_body: None,
+            tokens_per_second: None,
+            chunk_count: None,
+            response_text: None,
+        },
     }
 }
 
+/// Test every requested model, up to `concurrency` (at most 16) at a time.
+/// Results come back in the order of `model_ids`.
 #[tauri::command]
 pub async fn test_provider_model_connectivity(
     state: tauri::State<'_, SqliteDbState>,
     request: ConnectivityTestRequest,
+) -> Result<ConnectivityTestResponse, String> {
+    test_provider_model_connectivity_internal(&state, request).await
+}
+
+pub(crate) async fn test_provider_model_connectivity_internal(
+    state: &SqliteDbState,
+    request: ConnectivityTestRequest,
 ) -> Result<ConnectivityTestResponse, String> {
     let timeout_secs = request.timeout_secs.unwrap_or(30);
-    let client = http_client::client_with_timeout(&state, timeout_secs).await?;
     let resolved_request = resolve_provider_request(
         request.provider_id.as_deref(),
         &request.base_url,
         request.api_key.as_deref(),
     );
+    let client = provider_client(
+        state,
+        timeout_secs,
+        request.provider_id.as_deref(),
+        request
+            .provider_id
+            .as_deref()
+            .unwrap_or(&resolved_request.base_url),
+        request.proxy_url.as_deref(),
+    )
+    .await?;
     let mut request = request;
     request.base_url = resolved_request.base_url;
     request.api_key = resolved_request.api_key;
-
-    let mut results = Vec::new();
-    for model_id in &request.model_ids {
-        if request.base_url.trim().is_empty() {
-            results.push(ConnectivityTestResult {
-                model_id: model_id.clone(),
-                status: "error".to_string(),
-                first_byte_ms: None,
-                total_ms: None,
-                error_message: Some("Missing Base URL".to_string()),
-                request_url: String::new(),
-                request_headers: json!({}),
-                request_body: json!({}),
-                response_headers: None,
-                response_body: None,
-            });
-            continue;
-        }
-
-        let result = run_connectivity_test_for_model(&client, &request, model_id).await;
-        results.push(result);
-    }
+    request.headers = crate::coding::global_headers::request_headers_with_global(
+        state,
+        request.tool.as_deref(),
+        request.headers.as_ref(),
+    )?;
+
+    let concurrency = request
+        .concurrency
+        .unwrap_or(DEFAULT_CONNECTIVITY_CONCURRENCY)
+        .clamp(1, MAX_CONNECTIVITY_CONCURRENCY);
+    let request = &request;
+    let results: Vec<ConnectivityTestResult> = futures_util::stream::iter(request.model_ids.iter())
+        .map(|model_id| {
+            run_connectivity_test_with_timeout(client.clone(), request, model_id, timeout_secs)
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
 
     Ok(ConnectivityTestResponse { results })
 }
@@ -1138,4 +1522,130 @@ mod tests {
             "https://api.example.com/v1/models"
         );
     }
+
+    #[test]
+    fn test_measure_stream_throughput_prefers_final_usage() {
+        let text = concat!(
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}],\"usage\":null}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}],\"usage\":null}\n\n",
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":5,\"completion_tokens\":40}}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let (chunk_count, tokens_per_second) = measure_stream_throughput(text, 2000);
+        assert_eq!(chunk_count, 3);
+        assert_eq!(tokens_per_second, Some(20.0));
+
+        let anthropic = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"output_tokens\":1}}}\n\n",
+            "event: message_delta\n",
+            "data: {\"type\":\"message_delta\",\"usage\":{\"output_tokens\":12}}\n\n",
+        );
+        assert_eq!(measure_stream_throughput(anthropic, 1000), (2, Some(12.0)));
+    }
+
+    #[test]
+    fn test_measure_stream_throughput_falls_back_to_chunks() {
+        let text = "data: {\"a\":1}\n\ndata: {\"a\":2}\n\ndata: not-json\n\ndata: [DONE]\n";
+        assert_eq!(measure_stream_throughput(text, 500), (3, Some(6.0)));
+        assert_eq!(measure_stream_throughput(text, 0), (3, None));
+        assert_eq!(measure_stream_throughput("{}", 500), (0, None));
+    }
+
+    #[test]
+    fn test_parse_google_json_array_stream() {
+        let text = "[{\"candidates\": [{\"content\": {\"parts\": [{\"text\": \"Hel\"}]}}]}\r\n,\r\n{\"candidates\": [{\"content\": {\"parts\": [{\"text\": \"lo\"}]}}], \"usageMetadata\": {\"candidatesTokenCount\": 4}}\r\n]";
+        let Value::Array(items) = parse_stream_response("@ai-sdk/google", text) else {
+            panic!("expected array");
+        };
+        assert_eq!(items.len(), 2);
+        assert_eq!(google_stream_text(&items), "Hello");
+        assert_eq!(
+            measure_google_stream_throughput(&items, 1000),
+            (2, Some(4.0))
+        );
+
+        // Cut off mid-element: complete chunks survive, the tail is kept raw
+        let truncated =
+            "[{\"candidates\": [{\"content\": {\"parts\": [{\"text\": \"Hi\"}]}}]},\n{\"candi";
+        let items = parse_json_array_stream(truncated);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1]["raw"], "{\"candi");
+        assert_eq!(google_stream_text(&items), "Hi");
+        assert_eq!(
+            measure_google_stream_throughput(&items, 1000),
+            (1, Some(1.0))
+        );
+
+        // SSE framing (alt=sse) and other SDKs keep the SSE path
+        let sse = "data: {\"candidates\": []}\n\n";
+        assert_eq!(
+            parse_stream_response("@ai-sdk/google", sse),
+            json!([{ "candidates": [] }])
+        );
+        assert_eq!(
+            parse_stream_response(
+                "@ai-sdk/openai-compatible",
+                "data: {\"a\":1}\n\ndata: [DONE]\n"
+            ),
+            json!([{ "a": 1 }])
+        );
+    }
+
+    fn anthropic_page(ids: &[&str], has_more: bool) -> AnthropicModelsResponse {
+        AnthropicModelsResponse {
+            data: ids
+                .iter()
+                .map(|id| AnthropicModel {
+                    id: id.to_string(),
+                    model_type: "model".to_string(),
+                    display_name: None,
+                    created_at: None,
+                })
+                .collect(),
+            first_id: ids.first().map(|id| id.to_string()),
+            last_id: ids.last().map(|id| id.to_string()),
+            has_more: Some(has_more),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_pagination_follows_last_id() {
+        let mut requested = Vec::new();
+        let models = collect_anthropic_model_pages(anthropic_page(&["a", "b"], true), |after| {
+            requested.push(after.clone());
+            let page = match after.as_str() {
+                "b" => anthropic_page(&["c", "d"], true),
+                _ => anthropic_page(&["e"], false),
+            };
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        let ids: Vec<&str> = models.iter().map(|model| model.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(requested, vec!["b", "d"]);
+    }
+
+    #[tokio::test]
+    async fn test_anthropic_pagination_stops_on_stuck_or_endless_proxies() {
+        let stuck = collect_anthropic_model_pages(anthropic_page(&["a", "b"], true), |_| async {
+            Ok(anthropic_page(&["a", "b"], true))
+        })
+        .await
+        .unwrap();
+        assert_eq!(stuck.len(), 2);
+
+        let mut calls = 0;
+        let endless = collect_anthropic_model_pages(anthropic_page(&["m0"], true), |_| {
+            calls += 1;
+            let id = format!("m{}", calls);
+            async move { Ok(anthropic_page(&[id.as_str()], true)) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(endless.len(), ANTHROPIC_MODELS_MAX_PAGES);
+        assert_eq!(calls, ANTHROPIC_MODELS_MAX_PAGES - 1);
+    }
 }
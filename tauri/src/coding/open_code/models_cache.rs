@@ -0,0 +1,183 @@
+//! Persistent cache of fetched provider model lists.
+//!
+//! Entries are keyed by the endpoint (custom models URL, else base URL, else provider id), the
+//! SDK type and the API type, so two providers pointing at the same relay share one entry. The
+//! key is a SHA-256 digest and the stored record holds only the response and its fetch time:
+//! API keys and headers never reach the table, and a key embedded in a custom URL query is
+//! not stored in plain text either.
+
+use serde::Serialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use super::models_api::{
+    fetch_provider_models_internal, ApiType, FetchModelsRequest, FetchModelsResponse,
+};
+use crate::db::helpers::{db_delete_all, db_get, db_put};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+const DEFAULT_MODELS_CACHE_MAX_AGE_SECS: u64 = 60 * 60;
+
+/// Fetched models plus where they came from
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedFetchModelsResponse {
+    #[serde(flatten)]
+    pub response: FetchModelsResponse,
+    /// True when the models were served from the cache without a request
+    pub from_cache: bool,
+    /// When the models were fetched from the provider (Unix epoch milliseconds)
+    pub fetched_at: i64,
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Cache id for a request. Only the endpoint identity goes into the digest.
+fn models_cache_key(request: &FetchModelsRequest) -> String {
+    let endpoint = if let Some(custom_url) = non_empty(request.custom_url.as_deref()) {
+        format!("url:{}", custom_url)
+    } else if let Some(base_url) = non_empty(Some(&request.base_url)) {
+        format!("base:{}", base_url.trim_end_matches('/'))
+    } else {
+        format!(
+            "provider:{}",
+            non_empty(request.provider_id.as_deref()).unwrap_or_default()
+        )
+    };
+    let api_type = match request.api_type {
+        ApiType::Native => "native",
+        ApiType::OpenaiCompat => "openai_compat",
+    };
+    let sdk_type = non_empty(request.sdk_type.as_deref()).unwrap_or_default();
+    let digest = Sha256::digest(format!("{}|{}|{}", endpoint, sdk_type, api_type).as_bytes());
+    hex::encode(digest)
+}
+
+fn load_cached_models(
+    db: &SqliteDbState,
+    key: &str,
+) -> Result<Option<(FetchModelsResponse, i64)>, String> {
+    let record = db.with_conn(|conn| db_get(conn, DbTable::ProviderModelsCache, key))?;
+    Ok(record.and_then(|record| {
+        let fetched_at = record.get("fetched_at")?.as_i64()?;
+        let response = serde_json::from_value(record.get("response")?.clone()).ok()?;
+        Some((response, fetched_at))
+    }))
+}
+
+fn save_cached_models(
+    db: &SqliteDbState,
+    key: &str,
+    request: &FetchModelsRequest,
+    response: &FetchModelsResponse,
+    fetched_at: i64,
+) -> Result<(), String> {
+    let record = json!({
+        "sdk_type": request.sdk_type,
+        "response": response,
+        "fetched_at": fetched_at,
+    });
+    db.with_conn(|conn| db_put(conn, DbTable::ProviderModelsCache, key, &record))
+}
+
+/// Drop every cached model list; returns the number of entries removed.
+pub(crate) fn clear_provider_models_cache(db: &SqliteDbState) -> Result<usize, String> {
+    db.with_conn(|conn| db_delete_all(conn, DbTable::ProviderModelsCache))
+}
+
+/// Fetch a provider's models, reusing a cached list younger than `max_age_secs`
+/// (default one hour). `force_refresh` always refetches and replaces the entry.
+#[tauri::command]
+pub async fn fetch_provider_models_cached(
+    state: tauri::State<'_, SqliteDbState>,
+    request: FetchModelsRequest,
+    max_age_secs: Option<u64>,
+    force_refresh: Option<bool>,
+) -> Result<CachedFetchModelsResponse, String> {
+    fetch_models_with_cache(
+        state.db(),
+        request,
+        max_age_secs.unwrap_or(DEFAULT_MODELS_CACHE_MAX_AGE_SECS),
+        force_refresh.unwrap_or(false),
+    )
+    .await
+}
+
+/// Cached model fetch shared by the command and the provider model checks.
+pub(crate) async fn fetch_models_with_cache(
+    db: &SqliteDbState,
+    request: FetchModelsRequest,
+    max_age_secs: u64,
+    force_refresh: bool,
+) -> Result<CachedFetchModelsResponse, String> {
+    let key = models_cache_key(&request);
+    let now = chrono::Utc::now().timestamp_millis();
+
+    if !force_refresh {
+        let max_age_ms = max_age_secs.saturating_mul(1000);
+        // A cache read failure only costs a refetch.
+        match load_cached_models(db, &key) {
+            Ok(Some((response, fetched_at)))
+                if now.saturating_sub(fetched_at) as u64 <= max_age_ms =>
+            {
+                return Ok(CachedFetchModelsResponse {
+                    response,
+                    from_cache: true,
+                    fetched_at,
+                });
+            }
+            Ok(_) => {}
+            Err(error) => log::warn!("Failed to read provider models cache: {}", error),
+        }
+    }
+
+    let response = fetch_provider_models_internal(db, request.clone()).await?;
+    if let Err(error) = save_cached_models(db, &key, &request, &response, now) {
+        log::warn!("Failed to write provider models cache: {}", error);
+    }
+    Ok(CachedFetchModelsResponse {
+        response,
+        from_cache: false,
+        fetched_at: now,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(base_url: &str, api_key: Option<&str>, sdk_type: &str) -> FetchModelsRequest {
+        FetchModelsRequest {
+            provider_id: Some("relay".to_string()),
+            base_url: base_url.to_string(),
+            api_key: api_key.map(str::to_string),
+            headers: None,
+            api_type: ApiType::OpenaiCompat,
+            sdk_type: Some(sdk_type.to_string()),
+            custom_url: None,
+            tool: None,
+            proxy_url: None,
+            max_retries: None,
+        }
+    }
+
+    #[test]
+    fn cache_key_ignores_api_key_and_separates_sdk_types() {
+        let with_key = request("https://relay.example.com/v1/", Some("sk-secret"), "openai");
+        let other_key = request("https://relay.example.com/v1", Some("sk-other"), "openai");
+        let anthropic = request(
+            "https://relay.example.com/v1",
+            Some("sk-secret"),
+            "anthropic",
+        );
+
+        let key = models_cache_key(&with_key);
+        assert_eq!(key, models_cache_key(&other_key));
+        assert_ne!(key, models_cache_key(&anthropic));
+        assert!(!key.contains("sk-"));
+        assert_eq!(key.len(), 64);
+    }
+}
@@ -1,5 +1,6 @@
 use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 
 /// Deserialize a JSON value, normalizing null and empty objects to None
 fn deserialize_nullable_value<'de, D>(
@@ -343,6 +344,24 @@ pub struct UnifiedModelOption {
     pub base_model_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub experimental_mode: Option<String>,
+    /// Every provider offering this model id; only filled when the list is deduplicated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<String>,
+}
+
+/// User curation of the unified model list, keyed by "provider_id/model_id"
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCodeModelPreferences {
+    /// Models listed first, in this order; unlisted models follow in the default order
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// Models left out of the unified list
+    #[serde(default)]
+    pub hidden: Vec<String>,
+    /// Provider to keep per model id when the list is deduplicated
+    #[serde(default)]
+    pub preferred_providers: BTreeMap<String, String>,
 }
 
 // ============================================================================
@@ -424,10 +443,22 @@ pub struct OpenCodeFavoriteProvider {
     /// Saved connectivity diagnostics parameters
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostics: Option<OpenCodeDiagnosticsConfig>,
+    /// Pinned for quick apply (see `list_pinned_providers`)
+    #[serde(default)]
+    pub pinned: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Provider added to the config by `duplicate_opencode_provider`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCodeDuplicatedProvider {
+    /// Config key of the copy
+    pub provider_id: String,
+    pub provider_config: OpenCodeProvider,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenCodeAllApiHubProvider {
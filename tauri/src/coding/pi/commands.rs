@@ -10,6 +10,7 @@ use super::constants::{
     PI_MCP_FILE, PI_MODELS_FILE, PI_PROMPT_FILE, PI_SETTINGS_FILE,
 };
 use super::types::*;
+use crate::coding::atomic_write;
 use crate::coding::db_id::db_new_id;
 use crate::coding::open_code::shell_env;
 use crate::coding::prompt_file::{read_prompt_content_file, write_prompt_content_file};
@@ -20,7 +21,7 @@ use crate::db::helpers::{
 };
 use crate::db::schema::{DbTable, JsonFieldPath, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
-use tauri::{Emitter, Runtime};
+use tauri::{Emitter, Manager, Runtime};
 
 const PI_THINKING_LEVEL_KEYS: [&str; 7] =
     ["off", "minimal", "low", "medium", "high", "xhigh", "max"];
@@ -166,11 +167,7 @@ fn write_json_object(path: &Path, value: &Value) -> Result<(), String> {
         fs::create_dir_all(parent)
             .map_err(|error| format!("Failed to create {}: {error}", parent.display()))?;
     }
-    let content = serde_json::to_string_pretty(value)
-        .map_err(|error| format!("Failed to serialize {}: {error}", path.display()))?;
-    fs::write(path, format!("{content}\n"))
-        .map_err(|error| format!("Failed to write {}: {error}", path.display()))?;
-    Ok(())
+    atomic_write::write_json_atomic(path, value)
 }
 
 #[cfg(unix)]
@@ -489,8 +486,9 @@ fn builtin_providers() -> Vec<PiBuiltinProvider> {
 
 fn emit_config_changed<R: Runtime>(app: &tauri::AppHandle<R>, payload: &str) {
     let _ = app.emit("config-changed", payload);
-    #[cfg(target_os = "windows")]
-    let _ = app.emit("wsl-sync-request-pi", ());
+    if let Some(db_state) = app.try_state::<SqliteDbState>() {
+        crate::coding::wsl::emit_apply_sync_request(db_state.db(), app, "pi");
+    }
 }
 
 #[tauri::command]
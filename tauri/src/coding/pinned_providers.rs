@@ -0,0 +1,149 @@
+//! Pinned providers across tools.
+//!
+//! Claude Code and Codex providers and OpenCode favorite providers carry a `pinned` flag.
+//! `list_pinned_providers` gathers the pinned ones into one list so the UI and the tray can
+//! offer one-click apply regardless of which tool a provider belongs to.
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::coding::open_code::{self, ReadConfigResult};
+use crate::db::SqliteDbState;
+
+/// A pinned provider of any tool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedProvider {
+    /// "claude" | "codex" | "opencode"
+    pub tool: String,
+    /// Provider ID for Claude/Codex, config key for OpenCode
+    pub id: String,
+    pub name: String,
+    /// Applied provider for Claude/Codex; present in opencode.json for OpenCode
+    pub is_applied: bool,
+    pub is_disabled: bool,
+}
+
+impl PinnedProvider {
+    fn new(tool: &str, id: String, name: String, is_applied: bool, is_disabled: bool) -> Self {
+        Self {
+            tool: tool.to_string(),
+            id,
+            name,
+            is_applied,
+            is_disabled,
+        }
+    }
+}
+
+/// OpenCode favorites that are pinned; `configured_ids` are the provider keys in opencode.json.
+fn pinned_opencode_providers(
+    favorites: Vec<open_code::OpenCodeFavoriteProvider>,
+    configured_ids: &[String],
+) -> Vec<PinnedProvider> {
+    favorites
+        .into_iter()
+        .filter(|favorite| favorite.pinned)
+        .map(|favorite| {
+            let name = favorite
+                .provider_config
+                .name
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| favorite.provider_id.clone());
+            let is_applied = configured_ids.contains(&favorite.provider_id);
+            PinnedProvider::new("opencode", favorite.provider_id, name, is_applied, false)
+        })
+        .collect()
+}
+
+/// List pinned providers of Claude Code, Codex and OpenCode, in that order and each in the
+/// tool's own provider order.
+#[tauri::command]
+pub async fn list_pinned_providers(
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<PinnedProvider>, String> {
+    let db = state.db();
+    let mut pinned: Vec<PinnedProvider> =
+        crate::coding::claude_code::commands::list_claude_providers_from_sqlite(db)?
+            .into_iter()
+            .filter(|provider| provider.pinned)
+            .map(|provider| {
+                PinnedProvider::new(
+                    "claude",
+                    provider.id,
+                    provider.name,
+                    provider.is_applied,
+                    provider.is_disabled,
+                )
+            })
+            .collect();
+
+    pinned.extend(
+        crate::coding::codex::commands::list_codex_providers_for_db(db)
+            .await?
+            .into_iter()
+            .filter(|provider| provider.pinned)
+            .map(|provider| {
+                PinnedProvider::new(
+                    "codex",
+                    provider.id,
+                    provider.name,
+                    provider.is_applied,
+                    provider.is_disabled,
+                )
+            }),
+    );
+
+    let configured_ids: Vec<String> = match open_code::read_opencode_config(state.clone()).await? {
+        ReadConfigResult::Success { config } => config
+            .provider
+            .map(|providers| providers.into_keys().collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    pinned.extend(pinned_opencode_providers(
+        open_code::list_opencode_favorite_providers(state).await?,
+        &configured_ids,
+    ));
+
+    Ok(pinned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opencode_pins_use_display_name_and_config_presence() {
+        let favorite = |provider_id: &str, name: Option<&str>, pinned: bool| {
+            serde_json::from_value::<open_code::OpenCodeFavoriteProvider>(serde_json::json!({
+                "id": provider_id,
+                "providerId": provider_id,
+                "npm": "@ai-sdk/openai-compatible",
+                "baseUrl": "",
+                "providerConfig": { "name": name, "models": {} },
+                "pinned": pinned,
+                "createdAt": "",
+                "updatedAt": "",
+            }))
+            .unwrap()
+        };
+        let pinned = pinned_opencode_providers(
+            vec![
+                favorite("relay", Some("My Relay"), true),
+                favorite("unpinned", None, false),
+                favorite("backup", None, true),
+            ],
+            &["relay".to_string()],
+        );
+
+        assert_eq!(
+            pinned,
+            vec![
+                PinnedProvider::new("opencode", "relay".into(), "My Relay".into(), true, false),
+                PinnedProvider::new("opencode", "backup".into(), "backup".into(), false, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! Bulk provider import from CSV.
+//!
+//! Parses a spreadsheet export with a header row (`name,base_url,api_key,model,sdk_type`, any
+//! order, `model` and `sdk_type` optional) into per-tool provider settings. Nothing is written:
+//! the result is a preview, and the frontend creates the confirmed rows through the regular
+//! create-provider commands, the same flow as the All API Hub import.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::coding::all_api_hub::mask_api_key_preview;
+
+const DEFAULT_OPENCODE_SDK: &str = "@ai-sdk/openai-compatible";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedProvider {
+    /// 1-based line number in the CSV (the header is line 1).
+    pub row: usize,
+    pub name: String,
+    /// Suggested provider key for tools that key providers by id (OpenCode).
+    pub provider_key: String,
+    pub base_url: String,
+    pub api_key_preview: String,
+    pub model: Option<String>,
+    pub sdk_type: Option<String>,
+    /// Tool-specific `settings_config` ready for the create command; None when the row is invalid.
+    pub settings_config: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct CsvRow {
+    name: String,
+    base_url: String,
+    api_key: String,
+    model: Option<String>,
+    sdk_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportTool {
+    Claude,
+    Codex,
+    OpenCode,
+}
+
+impl ImportTool {
+    fn parse(tool: &str) -> Result<Self, String> {
+        match tool.trim() {
+            "claude" | "claudecode" => Ok(Self::Claude),
+            "codex" => Ok(Self::Codex),
+            "opencode" => Ok(Self::OpenCode),
+            other => Err(format!(
+                "CSV provider import is not supported for '{}'",
+                other
+            )),
+        }
+    }
+}
+
+/// Split CSV text into records, honoring double-quoted fields with `""` escapes.
+fn parse_csv_records(csv: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(ch),
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(ch),
+        }
+    }
+    if in_quotes {
+        return Err("CSV has an unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn column_index(header: &[String], names: &[&str]) -> Option<usize> {
+    header.iter().position(|column| {
+        let normalized = column.trim().to_ascii_lowercase().replace([' ', '-'], "_");
+        names.contains(&normalized.as_str())
+    })
+}
+
+fn optional_value(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+fn provider_key_from_name(name: &str) -> String {
+    let mut key = String::new();
+    for ch in name.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            key.push(ch.to_ascii_lowercase());
+        } else if !key.ends_with('-') {
+            key.push('-');
+        }
+    }
+    let key = key.trim_matches('-').to_string();
+    if key.is_empty() {
+        "provider".to_string()
+    } else {
+        key
+    }
+}
+
+fn validate_row(row: &CsvRow) -> Result<(), String> {
+    if row.name.is_empty() {
+        return Err("name is required".to_string());
+    }
+    if !(row.base_url.starts_with("http://") || row.base_url.starts_with("https://")) {
+        return Err("base_url must start with http:// or https://".to_string());
+    }
+    if row.api_key.is_empty() {
+        return Err("api_key is required".to_string());
+    }
+    Ok(())
+}
+
+fn toml_string(value: &str) -> String {
+    toml_edit::Value::from(value).to_string()
+}
+
+fn build_settings_config(tool: ImportTool, row: &CsvRow, provider_key: &str) -> String {
+    let base_url = row.base_url.trim_end_matches('/');
+    match tool {
+        ImportTool::Claude => {
+            let mut env = serde_json::Map::new();
+            env.insert(
+                "ANTHROPIC_BASE_URL".to_string(),
+                json!(base_url.trim_end_matches("/v1")),
+            );
+            env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), json!(row.api_key));
+            if let Some(model) = &row.model {
+                env.insert("ANTHROPIC_MODEL".to_string(), json!(model));
+            }
+            json!({ "env": env }).to_string()
+        }
+        ImportTool::Codex => {
+            let mut config_lines = vec!["model_provider = \"custom\"".to_string()];
+            if let Some(model) = &row.model {
+                config_lines.push(format!("model = {}", toml_string(model)));
+            }
+            config_lines.extend([
+                "model_reasoning_effort = \"high\"".to_string(),
+                String::new(),
+                "[model_providers.custom]".to_string(),
+                format!("name = {}", toml_string(&row.name)),
+                "wire_api = \"responses\"".to_string(),
+                "requires_openai_auth = true".to_string(),
+                format!("base_url = {}", toml_string(base_url)),
+            ]);
+            json!({
+                "auth": { "OPENAI_API_KEY": row.api_key },
+                "config": config_lines.join("\n"),
+            })
+            .to_string()
+        }
+        ImportTool::OpenCode => {
+            let mut models = serde_json::Map::new();
+            if let Some(model) = &row.model {
+                models.insert(model.clone(), json!({ "name": model }));
+            }
+            json!({
+                "id": provider_key,
+                "npm": row.sdk_type.as_deref().unwrap_or(DEFAULT_OPENCODE_SDK),
+                "name": row.name,
+                "options": { "baseURL": base_url, "apiKey": row.api_key },
+                "models": models,
+            })
+            .to_string()
+        }
+    }
+}
+
+fn preview_providers_from_csv(
+    tool: ImportTool,
+    csv: &str,
+) -> Result<Vec<ImportedProvider>, String> {
+    let records = parse_csv_records(csv)?;
+    let Some((header, rows)) = records.split_first() else {
+        return Err("CSV is empty".to_string());
+    };
+    let name_index = column_index(header, &["name"])
+        .ok_or_else(|| "CSV header is missing 'name'".to_string())?;
+    let base_url_index = column_index(header, &["base_url", "baseurl", "url"])
+        .ok_or_else(|| "CSV header is missing 'base_url'".to_string())?;
+    let api_key_index = column_index(header, &["api_key", "apikey", "key"])
+        .ok_or_else(|| "CSV header is missing 'api_key'".to_string())?;
+    let model_index = column_index(header, &["model"]);
+    let sdk_type_index = column_index(header, &["sdk_type", "sdk", "npm"]);
+
+    let mut seen_keys = HashSet::new();
+    let mut imported = Vec::new();
+    for (offset, record) in rows.iter().enumerate() {
+        if record.iter().all(|value| value.trim().is_empty()) {
+            continue;
+        }
+        let cell = |index: usize| record.get(index).map(|value| value.trim()).unwrap_or("");
+        let row = CsvRow {
+            name: cell(name_index).to_string(),
+            base_url: cell(base_url_index).to_string(),
+            api_key: cell(api_key_index).to_string(),
+            model: model_index.and_then(|index| optional_value(cell(index))),
+            sdk_type: sdk_type_index.and_then(|index| optional_value(cell(index))),
+        };
+        let provider_key = provider_key_from_name(&row.name);
+        let error = validate_row(&row).err().or_else(|| {
+            (!seen_keys.insert(provider_key.clone()))
+                .then(|| format!("duplicate provider name '{}'", row.name))
+        });
+
+        imported.push(ImportedProvider {
+            row: offset + 2,
+            settings_config: error
+                .is_none()
+                .then(|| build_settings_config(tool, &row, &provider_key)),
+            api_key_preview: mask_api_key_preview(&row.api_key),
+            provider_key,
+            name: row.name,
+            base_url: row.base_url,
+            model: row.model,
+            sdk_type: row.sdk_type,
+            error,
+        });
+    }
+    Ok(imported)
+}
+
+/// Parse a CSV of providers into a preview for `tool` ("claude", "codex" or "opencode").
+/// Invalid rows carry an error instead of aborting the import; nothing is saved or applied.
+#[tauri::command]
+pub fn import_providers_from_csv(
+    tool: String,
+    csv: String,
+) -> Result<Vec<ImportedProvider>, String> {
+    preview_providers_from_csv(ImportTool::parse(&tool)?, &csv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_records_handle_quotes_and_crlf() {
+        let records =
+            parse_csv_records("name,base_url\r\n\"Acme, Inc\",\"https://a\"\"b\"\r\n").unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[1],
+            vec!["Acme, Inc".to_string(), "https://a\"b".to_string()]
+        );
+        assert!(parse_csv_records("name\n\"open").is_err());
+    }
+
+    #[test]
+    fn preview_reports_row_errors_without_aborting() {
+        let csv = "base_url,name,api_key,model\n\
+                   https://gw.example.com/v1,Gateway A,sk-aaaaaaaaaaaa,claude-sonnet-4-5\n\
+                   ftp://bad,Gateway B,sk-bbbbbbbbbbbb,\n\
+                   https://gw2.example.com,Gateway A,sk-cccccccccccc,\n";
+        let preview = preview_providers_from_csv(ImportTool::Claude, csv).unwrap();
+        assert_eq!(preview.len(), 3);
+
+        let first = &preview[0];
+        assert!(first.error.is_none());
+        let settings: serde_json::Value =
+            serde_json::from_str(first.settings_config.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            settings["env"]["ANTHROPIC_BASE_URL"],
+            "https://gw.example.com"
+        );
+        assert_eq!(settings["env"]["ANTHROPIC_MODEL"], "claude-sonnet-4-5");
+        assert!(!first.api_key_preview.contains("aaaaaaaaaa"));
+
+        assert_eq!(preview[1].row, 3);
+        assert!(preview[1].error.as_deref().unwrap().contains("base_url"));
+        assert!(preview[2].error.as_deref().unwrap().contains("duplicate"));
+        assert!(preview[2].settings_config.is_none());
+    }
+
+    #[test]
+    fn preview_builds_codex_and_opencode_settings() {
+        let csv = "name,base_url,api_key,model,sdk_type\nMy GW,https://gw.example.com/v1,sk-123456789,gpt-5,\n";
+        let codex = preview_providers_from_csv(ImportTool::Codex, csv).unwrap();
+        let codex_settings: serde_json::Value =
+            serde_json::from_str(codex[0].settings_config.as_deref().unwrap()).unwrap();
+        assert_eq!(codex_settings["auth"]["OPENAI_API_KEY"], "sk-123456789");
+        let config = codex_settings["config"].as_str().unwrap();
+        assert!(config.contains("base_url = \"https://gw.example.com/v1\""));
+        assert!(config.contains("model = \"gpt-5\""));
+
+        let opencode = preview_providers_from_csv(ImportTool::OpenCode, csv).unwrap();
+        assert_eq!(opencode[0].provider_key, "my-gw");
+        let opencode_settings: serde_json::Value =
+            serde_json::from_str(opencode[0].settings_config.as_deref().unwrap()).unwrap();
+        assert_eq!(opencode_settings["npm"], DEFAULT_OPENCODE_SDK);
+        assert!(opencode_settings["models"]["gpt-5"].is_object());
+    }
+}
@@ -0,0 +1,285 @@
+//! Periodic provider health checks with a bounded per-provider history.
+//!
+//! While `provider_health_monitoring` is on, a background task probes every enabled provider
+//! of the `model_validation::REACHABILITY_TOOLS` each `provider_health_interval_minutes` with a
+//! connectivity test of its first referenced model (the runner behind the manual connectivity
+//! test, `PROBE_CONCURRENCY` providers at a time, each bounded by `PROBE_TIMEOUT_SECS`), and
+//! appends a sample to the provider's record in `provider_health_history`. A history keeps at
+//! most `MAX_SAMPLES` samples and nothing older than `HISTORY_RETENTION_MS`; histories of
+//! deleted or disabled providers are dropped after each round.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, State};
+
+use crate::coding::open_code::models_api::{
+    test_provider_model_connectivity_internal, ConnectivityTestRequest,
+};
+use crate::coding::{db_extract_id, model_validation};
+use crate::db::helpers::{db_delete, db_get, db_list, db_put};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+use crate::settings::types::{
+    MAX_PROVIDER_HEALTH_INTERVAL_MINUTES, MIN_PROVIDER_HEALTH_INTERVAL_MINUTES,
+};
+
+const MAX_SAMPLES: usize = 2000;
+const HISTORY_RETENTION_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+/// How often a disabled monitor re-reads the setting
+const DISABLED_POLL_INTERVAL: Duration = Duration::from_secs(60);
+const PROBE_PROMPT: &str = "say hi!";
+const PROBE_TIMEOUT_SECS: u64 = 30;
+const PROBE_CONCURRENCY: usize = 5;
+
+/// One probe of a provider endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthSample {
+    /// Unix milliseconds
+    pub checked_at: i64,
+    pub ok: bool,
+    pub latency_ms: u64,
+    /// Model the connectivity test was sent for
+    #[serde(default)]
+    pub model_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HealthHistory {
+    #[serde(default)]
+    tool: String,
+    #[serde(default)]
+    samples: Vec<HealthSample>,
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Drop samples older than the retention window, then the oldest beyond `MAX_SAMPLES`.
+/// Returns whether anything was removed.
+fn prune_samples(samples: &mut Vec<HealthSample>, now: i64) -> bool {
+    let before = samples.len();
+    samples.retain(|sample| now - sample.checked_at <= HISTORY_RETENTION_MS);
+    if samples.len() > MAX_SAMPLES {
+        samples.drain(..samples.len() - MAX_SAMPLES);
+    }
+    samples.len() != before
+}
+
+fn load_history(db: &SqliteDbState, provider_id: &str) -> Result<HealthHistory, String> {
+    Ok(db
+        .with_conn(|conn| db_get(conn, DbTable::ProviderHealthHistory, provider_id))?
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default())
+}
+
+fn save_history(
+    db: &SqliteDbState,
+    provider_id: &str,
+    history: &HealthHistory,
+) -> Result<(), String> {
+    let value = serde_json::to_value(history)
+        .map_err(|e| format!("Failed to serialize provider health history: {}", e))?;
+    db.with_conn(|conn| db_put(conn, DbTable::ProviderHealthHistory, provider_id, &value))
+}
+
+/// Single-model connectivity test of a provider, resolved like a manual test (headers, proxy,
+/// stored credentials). `None` for providers on the official endpoint or without a model.
+async fn probe_provider(db: &SqliteDbState, tool: &str, provider_id: &str) -> Option<HealthSample> {
+    let target = match model_validation::provider_probe_target(db, tool, provider_id) {
+        Ok(Some(target)) => target,
+        Ok(None) => return None,
+        Err(error) => {
+            return Some(HealthSample {
+                checked_at: now_ms(),
+                ok: false,
+                latency_ms: 0,
+                model_id: None,
+                error: Some(error),
+            })
+        }
+    };
+    let model_id = target.model_id;
+    let request = ConnectivityTestRequest {
+        npm: target.npm,
+        provider_id: Some(provider_id.to_string()),
+        base_url: target.base_url,
+        api_key: target.api_key,
+        reasoning_effort: None,
+        headers: None,
+        prompt: PROBE_PROMPT.to_string(),
+        temperature: None,
+        max_tokens: None,
+        max_output_tokens: None,
+        stream: Some(true),
+        body: None,
+        model_ids: vec![model_id.clone()],
+        timeout_secs: Some(PROBE_TIMEOUT_SECS),
+        concurrency: None,
+        // Only Claude has tool-level global headers.
+        tool: (tool == "claude").then(|| tool.to_string()),
+        proxy_url: None,
+        max_retries: None,
+    };
+    let result = test_provider_model_connectivity_internal(db, request)
+        .await
+        .and_then(|response| {
+            response
+                .results
+                .into_iter()
+                .next()
+                .ok_or_else(|| "connectivity test returned no result".to_string())
+        });
+    let (ok, latency_ms, error) =
+        match result {
+            Ok(result) if result.status == "success" => (
+                true,
+                result.first_byte_ms.or(result.total_ms).unwrap_or_default(),
+                None,
+            ),
+            Ok(result) => (
+                false,
+                result.total_ms.unwrap_or_default(),
+                Some(result.error_message.unwrap_or_else(|| {
+                    format!("connectivity test ended with '{}'", result.status)
+                })),
+            ),
+            Err(error) => (false, 0, Some(error)),
+        };
+    Some(HealthSample {
+        checked_at: now_ms(),
+        ok,
+        latency_ms,
+        model_id: Some(model_id),
+        error,
+    })
+}
+
+/// Probe every enabled provider once, record the samples and prune stale histories.
+/// Returns how many providers were probed; providers without a testable endpoint are skipped.
+async fn run_health_check_round(db: &SqliteDbState) -> Result<usize, String> {
+    let mut providers = Vec::new();
+    for tool in model_validation::REACHABILITY_TOOLS {
+        for provider in model_validation::enabled_providers(db, tool)? {
+            providers.push((tool, provider.id));
+        }
+    }
+    let known_ids: HashSet<String> = providers.iter().map(|(_, id)| id.clone()).collect();
+
+    let samples: Vec<_> = futures_util::stream::iter(&providers)
+        .map(|(tool, provider_id)| async move {
+            let sample = probe_provider(db, tool, provider_id).await;
+            (*tool, provider_id, sample)
+        })
+        .buffer_unordered(PROBE_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut probed = 0;
+    for (tool, provider_id, sample) in samples {
+        let Some(sample) = sample else {
+            continue;
+        };
+        let now = sample.checked_at;
+        let mut history = load_history(db, provider_id)?;
+        history.tool = tool.to_string();
+        history.samples.push(sample);
+        prune_samples(&mut history.samples, now);
+        save_history(db, provider_id, &history)?;
+        probed += 1;
+    }
+
+    // Age out providers that were not probed this round and drop removed ones
+    let now = now_ms();
+    let records = db.with_conn(|conn| db_list(conn, DbTable::ProviderHealthHistory, None))?;
+    for record in records {
+        let provider_id = db_extract_id(&record);
+        if !known_ids.contains(&provider_id) {
+            db.with_conn(|conn| db_delete(conn, DbTable::ProviderHealthHistory, &provider_id))?;
+            continue;
+        }
+        let mut history: HealthHistory = serde_json::from_value(record).unwrap_or_default();
+        if prune_samples(&mut history.samples, now) {
+            save_history(db, &provider_id, &history)?;
+        }
+    }
+    Ok(probed)
+}
+
+/// Health samples of a provider, oldest first. `since` (Unix milliseconds) limits the
+/// result to newer samples; providers never checked return an empty list.
+#[tauri::command]
+pub async fn get_provider_health_history(
+    state: State<'_, SqliteDbState>,
+    provider_id: String,
+    since: Option<i64>,
+) -> Result<Vec<HealthSample>, String> {
+    let since = since.unwrap_or(i64::MIN);
+    Ok(load_history(&state, &provider_id)?
+        .samples
+        .into_iter()
+        .filter(|sample| sample.checked_at >= since)
+        .collect())
+}
+
+/// Background task: run a health check round every configured interval while
+/// `provider_health_monitoring` is on.
+pub async fn run_provider_health_monitor<R: tauri::Runtime>(app: tauri::AppHandle<R>) {
+    loop {
+        let interval = {
+            let db_state = app.state::<SqliteDbState>();
+            let settings = crate::settings::store::load_settings_from_sqlite_state(&db_state)
+                .unwrap_or_default();
+            if settings.provider_health_monitoring {
+                match run_health_check_round(&db_state).await {
+                    Ok(probed) => log::info!("Provider health check probed {} provider(s)", probed),
+                    Err(error) => log::warn!("Provider health check failed: {}", error),
+                }
+                let minutes = settings.provider_health_interval_minutes.clamp(
+                    MIN_PROVIDER_HEALTH_INTERVAL_MINUTES,
+                    MAX_PROVIDER_HEALTH_INTERVAL_MINUTES,
+                );
+                Duration::from_secs(u64::from(minutes) * 60)
+            } else {
+                DISABLED_POLL_INTERVAL
+            }
+        };
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(checked_at: i64) -> HealthSample {
+        HealthSample {
+            checked_at,
+            ok: true,
+            latency_ms: 10,
+            model_id: Some("model".to_string()),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn prune_samples_enforces_retention_and_bound() {
+        let now = HISTORY_RETENTION_MS * 2;
+        let mut samples = vec![sample(now - HISTORY_RETENTION_MS - 1), sample(now - 5)];
+        assert!(prune_samples(&mut samples, now));
+        assert_eq!(samples, vec![sample(now - 5)]);
+        assert!(!prune_samples(&mut samples, now));
+
+        let mut samples: Vec<HealthSample> = (0..MAX_SAMPLES as i64 + 3)
+            .map(|i| sample(now - 1000 + i))
+            .collect();
+        assert!(prune_samples(&mut samples, now));
+        assert_eq!(samples.len(), MAX_SAMPLES);
+        assert_eq!(samples[0].checked_at, now - 1000 + 3);
+    }
+}
@@ -906,8 +906,20 @@ async fn proxy_details_for_manifest(
         mode: manifest.mode,
         primary_provider_id: manifest.primary_provider_id.clone(),
     };
-    match load_candidate_providers_with_settings_and_selection(db, cli_key, None, Some(&selection))
-        .await
+    let settings = match settings::load_settings_from_sqlite_state(db) {
+        Ok(settings) => Some(settings),
+        Err(error) => {
+            log::warn!("Failed to load gateway settings for provider priorities: {error}");
+            None
+        }
+    };
+    match load_candidate_providers_with_settings_and_selection(
+        db,
+        cli_key,
+        settings.as_ref(),
+        Some(&selection),
+    )
+    .await
     {
         Ok(providers) => {
             details.provider_priorities =
@@ -60,6 +60,14 @@ pub async fn proxy_gateway_get_settings(
 pub async fn proxy_gateway_update_settings(
     gateway_state: tauri::State<'_, ProxyGatewayState>,
     sqlite_state: tauri::State<'_, SqliteDbState>,
+    settings: ProxyGatewaySettings,
+) -> Result<ProxyGatewaySettings, String> {
+    save_settings_and_update_runtime(&gateway_state, &sqlite_state, settings)
+}
+
+fn save_settings_and_update_runtime(
+    gateway_state: &ProxyGatewayState,
+    sqlite_state: &SqliteDbState,
     mut settings: ProxyGatewaySettings,
 ) -> Result<ProxyGatewaySettings, String> {
     // Normalize/validate before touching runtime so invalid expressions never
@@ -74,7 +82,7 @@ pub async fn proxy_gateway_update_settings(
     if running {
         settings.enabled_on_startup = true;
     }
-    let saved = settings::save_settings(&sqlite_state, settings)?;
+    let saved = settings::save_settings(sqlite_state, settings)?;
     if running {
         let mut manager = gateway_state
             .manager
@@ -85,6 +93,82 @@ pub async fn proxy_gateway_update_settings(
     Ok(saved)
 }
 
+/// Provider pinned as the failover fallback of `cli_key`, if any.
+#[tauri::command]
+pub async fn get_fallback_provider(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    cli_key: GatewayCliKey,
+) -> Result<Option<String>, String> {
+    Ok(settings::load_settings_from_sqlite_state(&sqlite_state)?.fallback_provider_for(cli_key))
+}
+
+/// Pin (or clear with `None`) the provider that failover falls back to after
+/// every other candidate failed.
+#[tauri::command]
+pub async fn set_fallback_provider(
+    gateway_state: tauri::State<'_, ProxyGatewayState>,
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    cli_key: GatewayCliKey,
+    provider_id: Option<String>,
+) -> Result<Option<String>, String> {
+    let provider_id = provider_id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let mut settings = settings::load_settings_from_sqlite_state(&sqlite_state)?;
+    if let Some(provider_id) = provider_id.as_deref() {
+        super::runtime::validate_fallback_provider(
+            &sqlite_state,
+            cli_key,
+            provider_id,
+            Some(&settings),
+        )?;
+    }
+    settings
+        .app_configs
+        .entry(cli_key)
+        .or_default()
+        .fallback_provider_id = provider_id;
+    let saved = save_settings_and_update_runtime(&gateway_state, &sqlite_state, settings)?;
+    Ok(saved.fallback_provider_for(cli_key))
+}
+
+/// Drops the failover fallback pin when its provider is deleted. Failures are
+/// logged only so they never block the provider deletion itself.
+pub fn clear_fallback_provider_after_delete(
+    app: &tauri::AppHandle,
+    cli_key: GatewayCliKey,
+    provider_id: &str,
+) {
+    let Some(sqlite_state) = app.try_state::<SqliteDbState>() else {
+        return;
+    };
+    let mut settings = match settings::load_settings_from_sqlite_state(&sqlite_state) {
+        Ok(settings) => settings,
+        Err(error) => {
+            log::warn!("Failed to load gateway settings after provider delete: {error}");
+            return;
+        }
+    };
+    if settings.fallback_provider_for(cli_key).as_deref() != Some(provider_id) {
+        return;
+    }
+    if let Some(app_config) = settings.app_configs.get_mut(&cli_key) {
+        app_config.fallback_provider_id = None;
+    }
+    let result = match app.try_state::<ProxyGatewayState>() {
+        Some(gateway_state) => {
+            save_settings_and_update_runtime(&gateway_state, &sqlite_state, settings).map(|_| ())
+        }
+        None => settings::save_settings(&sqlite_state, settings).map(|_| ()),
+    };
+    if let Err(error) = result {
+        log::warn!(
+            "Failed to clear gateway fallback provider '{provider_id}' for {}: {error}",
+            cli_key.as_str()
+        );
+    }
+}
+
 #[tauri::command]
 pub async fn proxy_gateway_start(
     gateway_state: tauri::State<'_, ProxyGatewayState>,
@@ -301,7 +385,7 @@ pub async fn proxy_gateway_engage_single(
     let next_status =
         cli_proxy::engage_single_cli(db_state.db(), &paths, cli_key, &status, provider_id).await?;
     gateway_state.clear_provider_cache()?;
-    emit_gateway_cli_wsl_sync_request(&app, cli_key);
+    provider_switch::emit_gateway_cli_wsl_sync_request(&app, cli_key);
     Ok(next_status)
 }
 
@@ -323,7 +407,7 @@ pub async fn proxy_gateway_engage_failover(
     let next_status =
         cli_proxy::engage_failover_cli(db_state.db(), &paths, cli_key, &status).await?;
     gateway_state.clear_provider_cache()?;
-    emit_gateway_cli_wsl_sync_request(&app, cli_key);
+    provider_switch::emit_gateway_cli_wsl_sync_request(&app, cli_key);
     Ok(next_status)
 }
 
@@ -345,7 +429,7 @@ pub async fn proxy_gateway_disengage_failover(
     let next_status =
         cli_proxy::disengage_failover_cli(db_state.db(), &paths, cli_key, &status).await?;
     gateway_state.clear_provider_cache()?;
-    emit_gateway_cli_wsl_sync_request(&app, cli_key);
+    provider_switch::emit_gateway_cli_wsl_sync_request(&app, cli_key);
     Ok(next_status)
 }
 
@@ -367,7 +451,7 @@ pub async fn proxy_gateway_restore_cli_direct(
     let next_status =
         cli_proxy::restore_cli_direct(db_state.db(), &paths, cli_key, &status).await?;
     gateway_state.clear_provider_cache()?;
-    emit_gateway_cli_wsl_sync_request(&app, cli_key);
+    provider_switch::emit_gateway_cli_wsl_sync_request(&app, cli_key);
     Ok(next_status)
 }
 
@@ -1053,19 +1137,6 @@ fn proxy_gateway_paths(app: &tauri::AppHandle) -> Result<ProxyGatewayPaths, Stri
     Ok(ProxyGatewayPaths::new(app_data_dir))
 }
 
-fn emit_gateway_cli_wsl_sync_request(app: &tauri::AppHandle, cli_key: GatewayCliKey) {
-    let event_name = match cli_key {
-        GatewayCliKey::Claude => "wsl-sync-request-claude",
-        GatewayCliKey::Codex => "wsl-sync-request-codex",
-        GatewayCliKey::Grok => "wsl-sync-request-grok",
-        GatewayCliKey::Gemini => "wsl-sync-request-geminicli",
-        GatewayCliKey::OpenCode => return,
-    };
-    if let Err(error) = app.emit(event_name, ()) {
-        log::warn!("Failed to emit {event_name} after gateway CLI config change: {error}");
-    }
-}
-
 async fn load_provider_name_map(
     db: &SqliteDbState,
 ) -> Result<HashMap<(GatewayCliKey, String), String>, String> {
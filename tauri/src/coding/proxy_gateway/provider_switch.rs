@@ -131,7 +131,6 @@ fn provider_needs_gateway_proxy_for_switch(
         .unwrap_or("custom");
     let settings_config = record
         .get("settings_config")
-        .or_else(|| record.get("settingsConfig"))
         .and_then(serde_json::Value::as_str)
         .unwrap_or("{}");
 
@@ -325,17 +324,16 @@ fn emit_gateway_cli_config_changed<R: Runtime>(app: &AppHandle<R>, payload: &'st
     }
 }
 
-fn emit_gateway_cli_wsl_sync_request<R: Runtime>(app: &AppHandle<R>, cli_key: GatewayCliKey) {
-    let event_name = match cli_key {
-        GatewayCliKey::Claude => "wsl-sync-request-claude",
-        GatewayCliKey::Codex => "wsl-sync-request-codex",
-        GatewayCliKey::Grok => "wsl-sync-request-grok",
-        GatewayCliKey::Gemini => "wsl-sync-request-geminicli",
+pub(super) fn emit_gateway_cli_wsl_sync_request<R: Runtime>(app: &AppHandle<R>, cli_key: GatewayCliKey) {
+    let module = match cli_key {
+        GatewayCliKey::Claude => "claude",
+        GatewayCliKey::Codex => "codex",
+        GatewayCliKey::Grok => "grok",
+        GatewayCliKey::Gemini => "geminicli",
         GatewayCliKey::OpenCode => return,
     };
-    if let Err(error) = app.emit(event_name, ()) {
-        log::warn!("Failed to emit {event_name} after gateway CLI config change: {error}");
-    }
+    let db_state = app.state::<SqliteDbState>();
+    crate::coding::wsl::emit_apply_sync_request(db_state.db(), app, module);
 }
 
 #[cfg(test)]
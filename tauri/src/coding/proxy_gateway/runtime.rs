@@ -19,7 +19,8 @@ pub(crate) use self::providers::ProviderAuthStrategy;
 pub(crate) use self::providers::UpstreamModelMapping;
 pub(crate) use self::providers::{
     load_candidate_providers, load_candidate_providers_with_settings_and_selection,
-    provider_priority_entries, GatewayProviderSelection, UpstreamProvider,
+    provider_priority_entries, validate_fallback_provider, GatewayProviderSelection,
+    UpstreamProvider,
 };
 
 #[cfg(test)]
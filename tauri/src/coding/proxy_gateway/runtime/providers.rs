@@ -8,7 +8,7 @@ use crate::coding::proxy_gateway::{
     provider_profiles::load_gateway_provider_profiles_for_runtime, transformer::AiProtocol,
 };
 use crate::coding::{claude_code, codex, gemini_cli, grok};
-use crate::db::helpers::db_list;
+use crate::db::helpers::{db_get, db_list};
 use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
 use serde_json::Value;
@@ -74,17 +74,7 @@ pub(crate) async fn load_candidate_providers_with_settings_and_selection(
     settings: Option<&ProxyGatewaySettings>,
     selection: Option<&GatewayProviderSelection>,
 ) -> Result<Vec<UpstreamProvider>, String> {
-    let table = match cli_key {
-        GatewayCliKey::Claude => DbTable::ClaudeProvider,
-        GatewayCliKey::Codex => DbTable::CodexProvider,
-        GatewayCliKey::Grok => DbTable::GrokProvider,
-        GatewayCliKey::Gemini => DbTable::GeminiCliProvider,
-        GatewayCliKey::OpenCode => {
-            return Err(
-                "OpenCode adapter is intentionally out of scope for the gateway MVP".to_string(),
-            )
-        }
-    };
+    let table = provider_table(cli_key)?;
     let order = OrderSpec::new(vec![
         OrderField::json_integer("sort_index", OrderDirection::Asc)?,
         OrderField::json_text("updated_at", OrderDirection::Desc)?,
@@ -106,7 +96,13 @@ pub(crate) async fn load_candidate_providers_with_settings_and_selection(
         return Err(parse_errors.join("; "));
     }
 
-    apply_provider_selection(providers, selection)
+    let providers = apply_provider_selection(providers, selection)?;
+    let fallback_provider_id = settings.and_then(|settings| settings.fallback_provider_for(cli_key));
+    Ok(apply_fallback_provider(
+        providers,
+        selection,
+        fallback_provider_id.as_deref(),
+    ))
 }
 
 pub(crate) async fn load_provider_by_id_for_connectivity_test(
@@ -115,17 +111,7 @@ pub(crate) async fn load_provider_by_id_for_connectivity_test(
     provider_id: &str,
     settings: Option<&ProxyGatewaySettings>,
 ) -> Result<UpstreamProvider, String> {
-    let table = match cli_key {
-        GatewayCliKey::Claude => DbTable::ClaudeProvider,
-        GatewayCliKey::Codex => DbTable::CodexProvider,
-        GatewayCliKey::Grok => DbTable::GrokProvider,
-        GatewayCliKey::Gemini => DbTable::GeminiCliProvider,
-        GatewayCliKey::OpenCode => {
-            return Err(
-                "OpenCode adapter is intentionally out of scope for the gateway MVP".to_string(),
-            )
-        }
-    };
+    let table = provider_table(cli_key)?;
     let records = db.with_conn(|conn| db_list(conn, table, None))?;
     let record = records
         .into_iter()
@@ -143,6 +129,31 @@ pub(crate) async fn load_provider_by_id_for_connectivity_test(
     })
 }
 
+/// Checks that a provider can be pinned as the failover fallback: it must exist
+/// and be eligible as a gateway candidate (not disabled, not official).
+pub(crate) fn validate_fallback_provider(
+    db: &SqliteDbState,
+    cli_key: GatewayCliKey,
+    provider_id: &str,
+    settings: Option<&ProxyGatewaySettings>,
+) -> Result<(), String> {
+    let table = provider_table(cli_key)?;
+    let record = db
+        .with_conn(|conn| db_get(conn, table, provider_id))?
+        .ok_or_else(|| {
+            format!(
+                "Provider '{provider_id}' was not found for {}",
+                cli_key.as_str()
+            )
+        })?;
+    match provider_from_record(cli_key, record, settings)? {
+        Some(_) => Ok(()),
+        None => Err(format!(
+            "Provider '{provider_id}' is disabled or not eligible as a Gateway fallback provider"
+        )),
+    }
+}
+
 pub(crate) fn load_gateway_provider_selection(
     paths: Option<&ProxyGatewayPaths>,
     cli_key: GatewayCliKey,
@@ -226,6 +237,46 @@ fn apply_provider_selection(
     }
 }
 
+/// Moves the pinned fallback provider to the end of the failover chain so it is
+/// only tried after every other candidate failed. The primary provider is never
+/// demoted, and single mode is left untouched.
+fn apply_fallback_provider(
+    mut providers: Vec<UpstreamProvider>,
+    selection: Option<&GatewayProviderSelection>,
+    fallback_provider_id: Option<&str>,
+) -> Vec<UpstreamProvider> {
+    let Some(fallback_provider_id) = fallback_provider_id else {
+        return providers;
+    };
+    if let Some(selection) = selection {
+        if selection.mode == GatewayProxyMode::Single
+            || selection.primary_provider_id == fallback_provider_id
+        {
+            return providers;
+        }
+    }
+    if let Some(index) = providers
+        .iter()
+        .position(|provider| provider.id == fallback_provider_id)
+    {
+        let fallback_provider = providers.remove(index);
+        providers.push(fallback_provider);
+    }
+    providers
+}
+
+fn provider_table(cli_key: GatewayCliKey) -> Result<DbTable, String> {
+    match cli_key {
+        GatewayCliKey::Claude => Ok(DbTable::ClaudeProvider),
+        GatewayCliKey::Codex => Ok(DbTable::CodexProvider),
+        GatewayCliKey::Grok => Ok(DbTable::GrokProvider),
+        GatewayCliKey::Gemini => Ok(DbTable::GeminiCliProvider),
+        GatewayCliKey::OpenCode => {
+            Err("OpenCode adapter is intentionally out of scope for the gateway MVP".to_string())
+        }
+    }
+}
+
 fn sort_candidate_providers(providers: &mut [UpstreamProvider]) {
     providers.sort_by(|left, right| {
         left.sort_index
@@ -250,6 +301,7 @@ fn provider_from_record(
             if is_official_provider_category(&provider.category) {
                 return Ok(None);
             }
+            crate::coding::secret_store::ensure_secrets_revealed(&provider.settings_config)?;
             let settings =
                 parse_json_config(&provider.settings_config, "Claude provider settings_config")?;
             let env = settings.get("env").and_then(Value::as_object);
@@ -1366,6 +1418,50 @@ api_key = "secret"
         assert_eq!(names, vec!["first", "second"]);
     }
 
+    #[test]
+    fn failover_fallback_provider_moves_to_end_of_chain() {
+        let providers = vec![
+            provider("fallback", Some(10)),
+            provider("primary", Some(20)),
+            provider("third", Some(30)),
+        ];
+        let selection = GatewayProviderSelection {
+            mode: GatewayProxyMode::Failover,
+            primary_provider_id: "primary".to_string(),
+        };
+
+        let selected = apply_provider_selection(providers, Some(&selection)).unwrap();
+        let selected = apply_fallback_provider(selected, Some(&selection), Some("fallback"));
+
+        let names: Vec<&str> = selected
+            .iter()
+            .map(|provider| provider.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["primary", "third", "fallback"]);
+    }
+
+    #[test]
+    fn fallback_provider_never_demotes_primary_or_changes_single_mode() {
+        let providers = vec![provider("primary", Some(10)), provider("second", Some(20))];
+        let failover = GatewayProviderSelection {
+            mode: GatewayProxyMode::Failover,
+            primary_provider_id: "primary".to_string(),
+        };
+        let selected = apply_fallback_provider(providers.clone(), Some(&failover), Some("primary"));
+        assert_eq!(selected[0].name, "primary");
+
+        let single = GatewayProviderSelection {
+            mode: GatewayProxyMode::Single,
+            primary_provider_id: "primary".to_string(),
+        };
+        let selected = apply_fallback_provider(providers, Some(&single), Some("second"));
+        let names: Vec<&str> = selected
+            .iter()
+            .map(|provider| provider.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["primary", "second"]);
+    }
+
     #[test]
     fn claude_model_mapping_reads_reasoning_model() {
         let mapping = claude_model_mapping_from_settings(&serde_json::json!({
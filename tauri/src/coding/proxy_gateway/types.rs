@@ -55,6 +55,9 @@ pub struct AppProxyConfig {
     pub retry_interval_secs: Option<u64>,
     pub cost_multiplier: Option<String>,
     pub pricing_model_source: Option<String>,
+    /// Provider pinned as the last resort of the failover chain instead of
+    /// relying on the next provider in sort order.
+    pub fallback_provider_id: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -254,6 +257,15 @@ impl ProxyGatewaySettings {
             .to_string()
     }
 
+    pub fn fallback_provider_for(&self, cli_key: GatewayCliKey) -> Option<String> {
+        self.app_configs
+            .get(&cli_key)
+            .and_then(|config| config.fallback_provider_id.as_deref())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    }
+
     pub fn default_pricing_model_source_for(&self, cli_key: GatewayCliKey) -> String {
         let source = self
             .app_configs
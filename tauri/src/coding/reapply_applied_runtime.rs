@@ -226,7 +226,6 @@ fn record_id(record: &serde_json::Value) -> Option<String> {
 fn record_is_disabled(record: &serde_json::Value) -> bool {
     record
         .get("is_disabled")
-        .or_else(|| record.get("isDisabled"))
         .and_then(|value| value.as_bool())
         .unwrap_or(false)
 }
@@ -640,7 +639,6 @@ fn first_applied_oh_my_config_id(
     Ok(list_all_records(db, table)?.into_iter().find_map(|record| {
         let applied = record
             .get("is_applied")
-            .or_else(|| record.get("isApplied"))
             .and_then(|value| value.as_bool())
             .unwrap_or(false);
         if !applied || record_is_disabled(&record) {
@@ -10,7 +10,7 @@ use crate::coding::{claude_code, codex, gemini_cli, grok, open_claw, open_code,
 use crate::db::helpers::{db_get, db_patch_fields};
 use crate::db::schema::DbTable;
 
-const MODULE_KEYS: [&str; 7] = [
+pub(crate) const MODULE_KEYS: [&str; 7] = [
     "opencode",
     "claude",
     "codex",
@@ -238,14 +238,26 @@ async fn get_cached_or_refresh_runtime_location_async(
     }
 }
 
-#[cfg(test)]
-fn clear_runtime_location_cache() {
-    if let Ok(mut cache) = RUNTIME_LOCATION_CACHE.write() {
-        cache.clear();
-    }
-    if let Ok(mut cache) = CLAUDE_PLUGINS_DIR_CACHE.write() {
-        *cache = None;
-    }
+/// Drop the cached Claude plugins directory; returns 1 when an entry was cached.
+pub(crate) fn clear_claude_plugins_dir_cache() -> usize {
+    CLAUDE_PLUGINS_DIR_CACHE
+        .write()
+        .map(|mut cache| usize::from(cache.take().is_some()))
+        .unwrap_or(0)
+}
+
+/// Drop all cached runtime locations; returns how many modules were cached.
+pub(crate) fn clear_runtime_location_cache() -> usize {
+    let cleared = RUNTIME_LOCATION_CACHE
+        .write()
+        .map(|mut cache| {
+            let count = cache.len();
+            cache.clear();
+            count
+        })
+        .unwrap_or(0);
+    clear_claude_plugins_dir_cache();
+    cleared
 }
 
 pub async fn refresh_runtime_location_cache_for_module_async(
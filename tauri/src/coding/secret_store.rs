@@ -0,0 +1,359 @@
+//! Optional encryption at rest for Claude Code provider secrets.
+//!
+//! With `claude_encrypt_provider_secrets` enabled, secret `env` values in a provider's
+//! `settings_config` (`*_TOKEN`, `*_API_KEY`, `*_SECRET`) are stored as
+//! `enc:v1:<base64(nonce || AES-256-GCM ciphertext)>`. The 256-bit key is generated once and
+//! kept in the OS keyring, so encrypted records only open for the same OS account. Reads are
+//! always decrypted in the Claude adapter, so apply, the gateway and the UI see plaintext
+//! whether or not the setting is on. A value that cannot be decrypted stays encrypted in
+//! memory and `ensure_secrets_revealed` stops it from being used as a credential.
+//!
+//! Writes are sealed on every path while the setting is on: provider saves, config bundle
+//! imports and restored database snapshots.
+
+use std::sync::{LazyLock, Mutex};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use serde_json::Value;
+use tauri::{Emitter, State};
+
+use crate::db::helpers::{db_list, db_put, db_transaction};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+pub const ENCRYPTED_SECRET_PREFIX: &str = "enc:v1:";
+
+const KEYRING_SERVICE: &str = "ai-toolbox";
+const KEYRING_USER: &str = "provider-secrets-key";
+const NONCE_LEN: usize = 12;
+
+pub(crate) type SecretKey = [u8; 32];
+
+static SECRET_KEY_CACHE: LazyLock<Mutex<Option<SecretKey>>> = LazyLock::new(|| Mutex::new(None));
+
+pub(crate) fn is_secret_env_key(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    key.ends_with("_TOKEN") || key.ends_with("_API_KEY") || key.ends_with("_SECRET")
+}
+
+/// Load the key from the keyring, creating and storing one when `create` is set.
+fn secret_key(create: bool) -> Result<SecretKey, String> {
+    let mut cache = SECRET_KEY_CACHE
+        .lock()
+        .map_err(|_| "Secret key cache is poisoned".to_string())?;
+    if let Some(key) = *cache {
+        return Ok(key);
+    }
+
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| format!("Failed to open OS keyring: {}", e))?;
+    let key: SecretKey = match entry.get_password() {
+        Ok(encoded) => base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("Stored provider secrets key is invalid: {}", e))?
+            .try_into()
+            .map_err(|_| "Stored provider secrets key has the wrong length".to_string())?,
+        Err(keyring::Error::NoEntry) if create => {
+            let key: SecretKey = Aes256Gcm::generate_key(OsRng).into();
+            entry
+                .set_password(&base64::engine::general_purpose::STANDARD.encode(key))
+                .map_err(|e| {
+                    format!("Failed to store provider secrets key in OS keyring: {}", e)
+                })?;
+            key
+        }
+        Err(keyring::Error::NoEntry) => {
+            return Err("No provider secrets key in the OS keyring".to_string());
+        }
+        Err(e) => return Err(format!("Failed to read OS keyring: {}", e)),
+    };
+    *cache = Some(key);
+    Ok(key)
+}
+
+fn encrypt_secret(plaintext: &str, key: &SecretKey) -> Result<String, String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| "Failed to encrypt provider secret".to_string())?;
+    let mut payload = nonce.to_vec();
+    payload.extend(ciphertext);
+    Ok(format!(
+        "{}{}",
+        ENCRYPTED_SECRET_PREFIX,
+        base64::engine::general_purpose::STANDARD.encode(payload)
+    ))
+}
+
+fn decrypt_secret(value: &str, key: &SecretKey) -> Result<String, String> {
+    let encoded = value
+        .strip_prefix(ENCRYPTED_SECRET_PREFIX)
+        .ok_or_else(|| "Value is not an encrypted secret".to_string())?;
+    let payload = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| format!("Encrypted secret is not valid base64: {}", e))?;
+    if payload.len() <= NONCE_LEN {
+        return Err("Encrypted secret is truncated".to_string());
+    }
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Encrypted secret does not match the OS keyring key".to_string())?;
+    String::from_utf8(plaintext).map_err(|_| "Decrypted secret is not UTF-8".to_string())
+}
+
+/// Rewrite the secret `env` values of a settings_config JSON string. Returns `None` when
+/// nothing changed (including unparsable configs, which are stored as-is).
+fn transform_env_secrets(
+    settings_config: &str,
+    transform: impl Fn(&str) -> Result<Option<String>, String>,
+) -> Result<Option<String>, String> {
+    let Ok(mut settings) = serde_json::from_str::<Value>(settings_config) else {
+        return Ok(None);
+    };
+    let Some(env) = settings.get_mut("env").and_then(Value::as_object_mut) else {
+        return Ok(None);
+    };
+    let mut changed = false;
+    for (key, value) in env.iter_mut() {
+        let Some(current) = value.as_str() else {
+            continue;
+        };
+        if !is_secret_env_key(key) || current.is_empty() {
+            continue;
+        }
+        if let Some(next) = transform(current)? {
+            *value = Value::String(next);
+            changed = true;
+        }
+    }
+    if !changed {
+        return Ok(None);
+    }
+    serde_json::to_string(&settings)
+        .map(Some)
+        .map_err(|e| format!("Failed to serialize settings_config: {}", e))
+}
+
+fn seal_with_key(settings_config: &str, key: &SecretKey) -> Result<Option<String>, String> {
+    transform_env_secrets(settings_config, |value| {
+        if value.starts_with(ENCRYPTED_SECRET_PREFIX) {
+            Ok(None)
+        } else {
+            encrypt_secret(value, key).map(Some)
+        }
+    })
+}
+
+fn reveal_with_key(settings_config: &str, key: &SecretKey) -> Result<Option<String>, String> {
+    transform_env_secrets(settings_config, |value| {
+        if value.starts_with(ENCRYPTED_SECRET_PREFIX) {
+            decrypt_secret(value, key).map(Some)
+        } else {
+            Ok(None)
+        }
+    })
+}
+
+/// Encrypt the secret env values of `settings_config`, creating the keyring key on first use.
+pub fn seal_settings_config(settings_config: &str) -> Result<String, String> {
+    let key = secret_key(true)?;
+    Ok(seal_with_key(settings_config, &key)?.unwrap_or_else(|| settings_config.to_string()))
+}
+
+/// Decrypt any encrypted env values of `settings_config`. Plaintext configs skip the keyring;
+/// a value that cannot be decrypted is left encrypted and logged, and is then rejected by
+/// `ensure_secrets_revealed` wherever it would be used.
+pub fn reveal_settings_config(settings_config: &str) -> String {
+    if !settings_config.contains(ENCRYPTED_SECRET_PREFIX) {
+        return settings_config.to_string();
+    }
+    match secret_key(false).and_then(|key| reveal_with_key(settings_config, &key)) {
+        Ok(revealed) => revealed.unwrap_or_else(|| settings_config.to_string()),
+        Err(error) => {
+            log::warn!("Failed to decrypt provider secrets: {}", error);
+            settings_config.to_string()
+        }
+    }
+}
+
+/// Fail when a secret env value is still encrypted, i.e. it could not be decrypted with the
+/// keyring key. Guards every place a provider secret leaves the app.
+pub(crate) fn ensure_secrets_revealed(settings_config: &str) -> Result<(), String> {
+    if !settings_config.contains(ENCRYPTED_SECRET_PREFIX) {
+        return Ok(());
+    }
+    transform_env_secrets(settings_config, |value| {
+        if value.starts_with(ENCRYPTED_SECRET_PREFIX) {
+            Err("Provider secrets could not be decrypted with the OS keyring key; re-enter the API key".to_string())
+        } else {
+            Ok(None)
+        }
+    })
+    .map(|_| ())
+}
+
+/// Encrypt a whole text, e.g. a config file snapshot, creating the keyring key on first use.
+pub(crate) fn seal_text(plaintext: &str) -> Result<String, String> {
+    encrypt_secret(plaintext, &secret_key(true)?)
+}
+
+/// Decrypt a text sealed by `seal_text`.
+pub(crate) fn open_text(sealed: &str) -> Result<String, String> {
+    decrypt_secret(sealed, &secret_key(false)?)
+}
+
+fn map_record_settings_config(
+    record: &mut Value,
+    transform: impl FnOnce(&str) -> Result<String, String>,
+) -> Result<bool, String> {
+    let Some(settings_config) = record.get("settings_config").and_then(Value::as_str) else {
+        return Ok(false);
+    };
+    let next = transform(settings_config)?;
+    if next == settings_config {
+        return Ok(false);
+    }
+    record["settings_config"] = Value::String(next);
+    Ok(true)
+}
+
+/// Encrypt a Claude provider DB record before it is written, if the setting is enabled.
+pub(crate) fn seal_claude_provider_record_if_enabled(
+    db: &SqliteDbState,
+    record: &mut Value,
+) -> Result<(), String> {
+    match claude_seal_key_if_enabled(db)? {
+        Some(key) => seal_claude_provider_record(record, &key).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// Keyring key for sealing Claude provider records when the setting is enabled. Load it before
+/// opening a transaction; the settings read needs the connection.
+pub(crate) fn claude_seal_key_if_enabled(db: &SqliteDbState) -> Result<Option<SecretKey>, String> {
+    let settings = crate::settings::store::load_settings_from_sqlite_state(db)?;
+    if !settings.claude_encrypt_provider_secrets {
+        return Ok(None);
+    }
+    secret_key(true).map(Some)
+}
+
+/// Encrypt a Claude provider DB record with an already loaded key.
+pub(crate) fn seal_claude_provider_record(
+    record: &mut Value,
+    key: &SecretKey,
+) -> Result<bool, String> {
+    map_record_settings_config(record, |config| {
+        Ok(seal_with_key(config, key)?.unwrap_or_else(|| config.to_string()))
+    })
+}
+
+fn seal_all_claude_providers(db: &SqliteDbState, key: &SecretKey) -> Result<usize, String> {
+    db.with_conn_mut(|conn| {
+        db_transaction(conn, |tx| {
+            let mut sealed = 0;
+            for mut record in db_list(tx, DbTable::ClaudeProvider, None)? {
+                let Some(id) = record.get("id").and_then(Value::as_str).map(str::to_string) else {
+                    continue;
+                };
+                if seal_claude_provider_record(&mut record, key)? {
+                    db_put(tx, DbTable::ClaudeProvider, &id, &record)?;
+                    sealed += 1;
+                }
+            }
+            Ok(sealed)
+        })
+    })
+}
+
+/// Encrypt plaintext secrets of every Claude provider when the setting is enabled, e.g.
+/// after a database snapshot was restored. Returns how many records changed.
+pub(crate) fn seal_claude_providers_if_enabled(db: &SqliteDbState) -> Result<usize, String> {
+    match claude_seal_key_if_enabled(db)? {
+        Some(key) => seal_all_claude_providers(db, &key),
+        None => Ok(0),
+    }
+}
+
+/// Set a Claude provider record's secrets to encrypted or plaintext form (config bundles).
+pub(crate) fn convert_claude_provider_record(
+    record: &mut Value,
+    encrypt: bool,
+) -> Result<(), String> {
+    if encrypt {
+        map_record_settings_config(record, seal_settings_config).map(|_| ())
+    } else {
+        map_record_settings_config(record, |config| {
+            let revealed = reveal_settings_config(config);
+            ensure_secrets_revealed(&revealed)?;
+            Ok(revealed)
+        })
+        .map(|_| ())
+    }
+}
+
+/// Encrypt the secrets of every existing Claude provider and return how many records changed.
+#[tauri::command]
+pub async fn migrate_secrets_to_encrypted(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<usize, String> {
+    let key = secret_key(true)?;
+    let migrated = seal_all_claude_providers(&state, &key)?;
+    log::info!("Encrypted secrets of {} Claude providers", migrated);
+
+    let _ = app.emit("config-changed", "window");
+    Ok(migrated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_settings_config_round_trips_and_keeps_other_fields() {
+        let key: SecretKey = [7; 32];
+        let config = r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"sk-secret","ANTHROPIC_BASE_URL":"https://relay.example.com"},"model":"opus"}"#;
+
+        let sealed = seal_with_key(config, &key).unwrap().expect("sealed");
+        let sealed_value: Value = serde_json::from_str(&sealed).unwrap();
+        let token = sealed_value["env"]["ANTHROPIC_AUTH_TOKEN"]
+            .as_str()
+            .unwrap();
+        assert!(token.starts_with(ENCRYPTED_SECRET_PREFIX));
+        assert!(!sealed.contains("sk-secret"));
+        assert_eq!(
+            sealed_value["env"]["ANTHROPIC_BASE_URL"],
+            "https://relay.example.com"
+        );
+        assert!(seal_with_key(&sealed, &key).unwrap().is_none());
+
+        let revealed = reveal_with_key(&sealed, &key).unwrap().expect("revealed");
+        let revealed_value: Value = serde_json::from_str(&revealed).unwrap();
+        assert_eq!(revealed_value["env"]["ANTHROPIC_AUTH_TOKEN"], "sk-secret");
+        assert!(reveal_with_key(&sealed, &[8; 32]).is_err());
+    }
+
+    #[test]
+    fn undecryptable_secrets_are_rejected_before_use() {
+        let key: SecretKey = [7; 32];
+        let config = r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"sk-secret"}}"#;
+        let sealed = seal_with_key(config, &key).unwrap().expect("sealed");
+
+        assert!(ensure_secrets_revealed(config).is_ok());
+        assert!(ensure_secrets_revealed(&sealed).is_err());
+
+        let mut record = serde_json::json!({ "id": "p1", "settings_config": config });
+        assert!(seal_claude_provider_record(&mut record, &key).unwrap());
+        assert!(!record["settings_config"]
+            .as_str()
+            .unwrap()
+            .contains("sk-secret"));
+        assert!(!seal_claude_provider_record(&mut record, &key).unwrap());
+    }
+}
@@ -0,0 +1,792 @@
+//! Export the current local setup as a reproducible shell script.
+//!
+//! The script rewrites each tool's config files with the content currently on disk and
+//! re-registers Claude Code MCP servers through the `claude` CLI. Secrets never leave the
+//! machine: string values under secret-like keys are replaced by environment variable
+//! placeholders, and the script refuses to run until every placeholder variable is set.
+//! Files that cannot be parsed for redaction are skipped rather than exported verbatim.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use tauri::State;
+
+use crate::coding::mcp::mcp_store;
+use crate::coding::pi::commands::get_pi_settings_path_async;
+use crate::coding::runtime_location;
+use crate::coding::secret_store;
+use crate::coding::skills::skill_store;
+use crate::db::SqliteDbState;
+
+const SECRET_MARKER_PREFIX: &str = "@@AI_TOOLBOX_SECRET:";
+const SECRET_MARKER_SUFFIX: &str = "@@";
+const HEREDOC_DELIMITER: &str = "AI_TOOLBOX_EOF";
+const CLAUDE_CODE_TOOL_KEY: &str = "claude_code";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptShell {
+    Posix,
+    PowerShell,
+}
+
+impl ScriptShell {
+    fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.map(str::trim).unwrap_or("sh") {
+            "" | "sh" | "posix" | "bash" => Ok(Self::Posix),
+            "powershell" | "pwsh" | "ps1" => Ok(Self::PowerShell),
+            other => Err(format!("Unsupported script shell: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileFormat {
+    Json,
+    /// JSON with comments, e.g. opencode.jsonc; exported as plain JSON
+    Jsonc,
+    Toml,
+    DotEnv,
+    Text,
+}
+
+impl FileFormat {
+    fn from_path(path: &Path) -> Self {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        if file_name == ".env" {
+            return Self::DotEnv;
+        }
+        match path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("json") => Self::Json,
+            Some("jsonc") => Self::Jsonc,
+            Some("toml") => Self::Toml,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Environment variables the generated script needs, in first-seen order.
+#[derive(Debug, Default)]
+struct SecretVars {
+    vars: Vec<(String, String)>,
+}
+
+impl SecretVars {
+    /// Register a secret and return the marker that stands in for it in file content.
+    fn marker(&mut self, scope: &str, key: &str) -> String {
+        let base = env_var_name(&format!("{}_{}", scope, key));
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while self.vars.iter().any(|(existing, _)| existing == &name) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        self.vars
+            .push((name.clone(), format!("{} -> {}", scope, key)));
+        format!("{}{}{}", SECRET_MARKER_PREFIX, name, SECRET_MARKER_SUFFIX)
+    }
+}
+
+fn env_var_name(raw: &str) -> String {
+    let mut name = String::with_capacity(raw.len());
+    let mut last_underscore = false;
+    for ch in raw.chars() {
+        if ch.is_ascii_alphanumeric() {
+            name.push(ch.to_ascii_uppercase());
+            last_underscore = false;
+        } else if !last_underscore {
+            name.push('_');
+            last_underscore = true;
+        }
+    }
+    let name = name.trim_matches('_').to_string();
+    format!("AI_TOOLBOX_{}", name)
+}
+
+/// Key words that mark a secret on their own, beyond the env-style names `secret_store` seals
+const SECRET_KEY_WORDS: &[&str] = &[
+    "apikey",
+    "password",
+    "authorization",
+    "cookie",
+    "credential",
+    "credentials",
+];
+
+/// Lowercase words of a key, split at non-alphanumerics and camelCase boundaries.
+fn key_words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (index, &ch) in chars.iter().enumerate() {
+        if !ch.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if ch.is_ascii_uppercase() && !current.is_empty() {
+            let prev = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|c| c.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_is_lower)
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(ch.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Whether a config key holds a credential. Env-style names are checked with the same rule
+/// `secret_store` seals by (`*_TOKEN`, `*_API_KEY`, `*_SECRET`, also as `apiKey`), so only
+/// whole words match and e.g. `CLAUDE_CODE_MAX_OUTPUT_TOKENS` is kept.
+pub(crate) fn is_secret_key(key: &str) -> bool {
+    let words = key_words(key);
+    if words.is_empty() {
+        return false;
+    }
+    secret_store::is_secret_env_key(&format!("_{}", words.join("_")))
+        || words == ["key"]
+        || words
+            .iter()
+            .any(|word| SECRET_KEY_WORDS.contains(&word.as_str()))
+}
+
+fn redact_json(value: &mut Value, scope: &str, secrets: &mut SecretVars) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match child {
+                    Value::String(text) if is_secret_key(key) && !text.is_empty() => {
+                        *text = secrets.marker(scope, key);
+                    }
+                    _ => redact_json(child, scope, secrets),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact_json(item, scope, secrets);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn redact_toml_table(table: &mut dyn toml_edit::TableLike, scope: &str, secrets: &mut SecretVars) {
+    for (key, item) in table.iter_mut() {
+        let key = key.get().to_string();
+        match item {
+            toml_edit::Item::Value(toml_edit::Value::String(text))
+                if is_secret_key(&key) && !text.value().is_empty() =>
+            {
+                *item = toml_edit::value(secrets.marker(scope, &key));
+            }
+            toml_edit::Item::Value(toml_edit::Value::InlineTable(inline)) => {
+                redact_toml_table(inline, scope, secrets);
+            }
+            toml_edit::Item::Table(child) => redact_toml_table(child, scope, secrets),
+            toml_edit::Item::ArrayOfTables(tables) => {
+                for child in tables.iter_mut() {
+                    redact_toml_table(child, scope, secrets);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn redact_dotenv(content: &str, scope: &str, secrets: &mut SecretVars) -> String {
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let redacted = if trimmed.starts_with('#') {
+            None
+        } else {
+            line.split_once('=').and_then(|(key, value)| {
+                let key_name = key.trim().trim_start_matches("export ").trim();
+                (is_secret_key(key_name) && !value.trim().is_empty())
+                    .then(|| format!("{}={}", key, secrets.marker(scope, key_name)))
+            })
+        };
+        lines.push(redacted.unwrap_or_else(|| line.to_string()));
+    }
+    let mut output = lines.join("\n");
+    if content.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+/// Redact secrets from a config file; `None` means the file could not be parsed safely.
+fn redact_file_content(
+    content: &str,
+    format: FileFormat,
+    scope: &str,
+    secrets: &mut SecretVars,
+) -> Option<String> {
+    match format {
+        FileFormat::Json | FileFormat::Jsonc => {
+            let mut value: Value = if format == FileFormat::Jsonc {
+                json5::from_str(content).ok()?
+            } else {
+                serde_json::from_str(content).ok()?
+            };
+            redact_json(&mut value, scope, secrets);
+            let mut output = serde_json::to_string_pretty(&value).ok()?;
+            output.push('\n');
+            Some(output)
+        }
+        FileFormat::Toml => {
+            let mut document = content.parse::<toml_edit::DocumentMut>().ok()?;
+            redact_toml_table(document.as_table_mut(), scope, secrets);
+            Some(document.to_string())
+        }
+        FileFormat::DotEnv => Some(redact_dotenv(content, scope, secrets)),
+        FileFormat::Text => Some(content.to_string()),
+    }
+}
+
+/// Replace secret markers with shell variable references in already-escaped text.
+fn expand_secret_markers(text: &str, shell: ScriptShell) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(SECRET_MARKER_PREFIX) {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + SECRET_MARKER_PREFIX.len()..];
+        let Some(end) = after.find(SECRET_MARKER_SUFFIX) else {
+            output.push_str(&rest[start..]);
+            return output;
+        };
+        let name = &after[..end];
+        match shell {
+            ScriptShell::Posix => output.push_str(&format!("${{{}}}", name)),
+            ScriptShell::PowerShell => output.push_str(&format!("$($env:{})", name)),
+        }
+        rest = &after[end + SECRET_MARKER_SUFFIX.len()..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Escape text for a POSIX unquoted heredoc or double-quoted string.
+fn escape_posix(text: &str, in_double_quotes: bool) -> String {
+    let mut output = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' | '$' | '`' => {
+                output.push('\\');
+                output.push(ch);
+            }
+            '"' if in_double_quotes => output.push_str("\\\""),
+            _ => output.push(ch),
+        }
+    }
+    output
+}
+
+/// Escape text for a PowerShell expandable string or here-string.
+fn escape_powershell(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for ch in text.chars() {
+        // PowerShell also treats typographic double quotes as string delimiters.
+        if matches!(ch, '`' | '$' | '"' | '\u{201C}' | '\u{201D}' | '\u{201E}') {
+            output.push('`');
+        }
+        output.push(ch);
+    }
+    output
+}
+
+fn quote_posix_single(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+fn quote_powershell_single(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// Render a target path, keeping it relative to `$HOME` when possible.
+fn render_path(path: &Path, home: Option<&Path>, shell: ScriptShell) -> String {
+    let relative = home
+        .and_then(|home| path.strip_prefix(home).ok())
+        .map(|relative| {
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        });
+    match (relative, shell) {
+        (Some(relative), ScriptShell::Posix) => {
+            format!("\"$HOME/{}\"", escape_posix(&relative, true))
+        }
+        (Some(relative), ScriptShell::PowerShell) => {
+            format!("(Join-Path $HOME {})", quote_powershell_single(&relative))
+        }
+        (None, ScriptShell::Posix) => quote_posix_single(&path.to_string_lossy()),
+        (None, ScriptShell::PowerShell) => quote_powershell_single(&path.to_string_lossy()),
+    }
+}
+
+struct ExportedFile {
+    label: String,
+    path: PathBuf,
+    content: String,
+}
+
+struct ClaudeMcpEntry {
+    name: String,
+    config_json: String,
+}
+
+struct SetupScriptInput {
+    generated_at: String,
+    files: Vec<ExportedFile>,
+    skipped: Vec<String>,
+    mcp_servers: Vec<ClaudeMcpEntry>,
+    skill_notes: Vec<String>,
+    secrets: SecretVars,
+}
+
+fn render_posix_script(input: &SetupScriptInput, home: Option<&Path>) -> String {
+    let shell = ScriptShell::Posix;
+    let mut out = String::new();
+    out.push_str("#!/bin/sh\n");
+    out.push_str(&format!(
+        "# Generated by AI Toolbox at {}.\n# Rewrites tool config files with the exported content and re-registers MCP servers.\n",
+        input.generated_at
+    ));
+    push_header_notes(&mut out, input);
+    out.push_str("set -eu\n\n");
+    for (name, _) in &input.secrets.vars {
+        out.push_str(&format!(": \"${{{0}:?{0} must be set}}\"\n", name));
+    }
+    if !input.secrets.vars.is_empty() {
+        out.push('\n');
+    }
+
+    for file in &input.files {
+        let target = render_path(&file.path, home, shell);
+        let body = expand_secret_markers(&escape_posix(&file.content, false), shell);
+        let mut delimiter = HEREDOC_DELIMITER.to_string();
+        while body.lines().any(|line| line == delimiter) {
+            delimiter.push('_');
+        }
+        out.push_str(&format!("# {}\n", file.label));
+        out.push_str(&format!("mkdir -p \"$(dirname {})\"\n", target));
+        out.push_str(&format!("cat > {} <<{}\n", target, delimiter));
+        out.push_str(&body);
+        if !body.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&format!("{}\n\n", delimiter));
+    }
+
+    if !input.mcp_servers.is_empty() {
+        out.push_str("# Claude Code MCP servers\n");
+        out.push_str("if command -v claude >/dev/null 2>&1; then\n");
+        for server in &input.mcp_servers {
+            out.push_str(&format!(
+                "  claude mcp remove --scope user {} >/dev/null 2>&1 || true\n",
+                quote_posix_single(&server.name)
+            ));
+            out.push_str(&format!(
+                "  claude mcp add-json --scope user {} \"{}\"\n",
+                quote_posix_single(&server.name),
+                expand_secret_markers(&escape_posix(&server.config_json, true), shell)
+            ));
+        }
+        out.push_str(
+            "else\n  echo \"claude CLI not found; skipped MCP server registration\" >&2\nfi\n",
+        );
+    }
+    out
+}
+
+fn render_powershell_script(input: &SetupScriptInput, home: Option<&Path>) -> String {
+    let shell = ScriptShell::PowerShell;
+    let mut out = String::new();
+    out.push_str(&format!(
+        "# Generated by AI Toolbox at {}.\n# Rewrites tool config files with the exported content and re-registers MCP servers.\n",
+        input.generated_at
+    ));
+    push_header_notes(&mut out, input);
+    out.push_str("$ErrorActionPreference = 'Stop'\n\n");
+    for (name, _) in &input.secrets.vars {
+        out.push_str(&format!(
+            "if (-not $env:{0}) {{ throw '{0} must be set' }}\n",
+            name
+        ));
+    }
+    if !input.secrets.vars.is_empty() {
+        out.push('\n');
+    }
+
+    for file in &input.files {
+        let target = render_path(&file.path, home, shell);
+        let trailing_newline = file.content.ends_with('\n');
+        let content = file.content.strip_suffix('\n').unwrap_or(&file.content);
+        let body = expand_secret_markers(&escape_powershell(content), shell);
+        out.push_str(&format!("# {}\n", file.label));
+        out.push_str(&format!("$target = {}\n", target));
+        out.push_str(
+            "New-Item -ItemType Directory -Force -Path (Split-Path -Parent $target) | Out-Null\n",
+        );
+        out.push_str("$content = @\"\n");
+        out.push_str(&body);
+        out.push_str("\n\"@\n");
+        if trailing_newline {
+            out.push_str("$content += \"`n\"\n");
+        }
+        out.push_str("[System.IO.File]::WriteAllText($target, $content)\n\n");
+    }
+
+    if !input.mcp_servers.is_empty() {
+        out.push_str("# Claude Code MCP servers\n");
+        out.push_str("if (Get-Command claude -ErrorAction SilentlyContinue) {\n");
+        for server in &input.mcp_servers {
+            out.push_str(&format!(
+                "  claude mcp remove --scope user {} 2>$null\n",
+                quote_powershell_single(&server.name)
+            ));
+            out.push_str(&format!(
+                "  claude mcp add-json --scope user {} \"{}\"\n",
+                quote_powershell_single(&server.name),
+                expand_secret_markers(&escape_powershell(&server.config_json), shell)
+            ));
+        }
+        out.push_str(
+            "} else {\n  Write-Warning 'claude CLI not found; skipped MCP server registration'\n}\n",
+        );
+    }
+    out
+}
+
+fn push_header_notes(out: &mut String, input: &SetupScriptInput) {
+    if !input.secrets.vars.is_empty() {
+        out.push_str("#\n# Required environment variables (secrets are never exported):\n");
+        for (name, description) in &input.secrets.vars {
+            out.push_str(&format!("#   {}  ({})\n", name, description));
+        }
+    }
+    if !input.skipped.is_empty() {
+        out.push_str("#\n# Skipped files:\n");
+        for note in &input.skipped {
+            out.push_str(&format!("#   {}\n", note));
+        }
+    }
+    if !input.skill_notes.is_empty() {
+        out.push_str(
+            "#\n# Skills are not installed by this script; reinstall them in AI Toolbox:\n",
+        );
+        for note in &input.skill_notes {
+            out.push_str(&format!("#   {}\n", note));
+        }
+    }
+    out.push_str("#\n");
+}
+
+async fn collect_config_files(state: &SqliteDbState) -> Vec<(String, String, PathBuf)> {
+    let db = state.db();
+    let candidates: Vec<(&str, &str, Result<PathBuf, String>)> = vec![
+        (
+            "Claude Code",
+            "claude_settings",
+            runtime_location::get_claude_settings_path_async(db).await,
+        ),
+        (
+            "Claude Code",
+            "claude_prompt",
+            runtime_location::get_claude_prompt_path_async(db).await,
+        ),
+        (
+            "Codex",
+            "codex_config",
+            runtime_location::get_codex_config_path_async(db).await,
+        ),
+        (
+            "Codex",
+            "codex_auth",
+            runtime_location::get_codex_auth_path_async(db).await,
+        ),
+        (
+            "Codex",
+            "codex_prompt",
+            runtime_location::get_codex_prompt_path_async(db).await,
+        ),
+        (
+            "Gemini CLI",
+            "gemini_settings",
+            runtime_location::get_gemini_cli_settings_path_async(db).await,
+        ),
+        (
+            "Gemini CLI",
+            "gemini_env",
+            runtime_location::get_gemini_cli_env_path_async(db).await,
+        ),
+        (
+            "Gemini CLI",
+            "gemini_prompt",
+            runtime_location::get_gemini_cli_prompt_path_async(db).await,
+        ),
+        (
+            "Grok",
+            "grok_config",
+            runtime_location::get_grok_config_path_async(db).await,
+        ),
+        (
+            "Grok",
+            "grok_auth",
+            runtime_location::get_grok_auth_path_async(db).await,
+        ),
+        (
+            "Grok",
+            "grok_prompt",
+            runtime_location::get_grok_prompt_path_async(db).await,
+        ),
+        (
+            "OpenCode",
+            "opencode_config",
+            runtime_location::get_opencode_runtime_location_async(db)
+                .await
+                .map(|location| location.host_path),
+        ),
+        (
+            "OpenCode",
+            "opencode_prompt",
+            runtime_location::get_opencode_prompt_path_async(db).await,
+        ),
+        ("Pi", "pi_settings", get_pi_settings_path_async(db).await),
+    ];
+
+    candidates
+        .into_iter()
+        .filter_map(|(tool, scope, path)| match path {
+            Ok(path) if path.is_file() => Some((tool.to_string(), scope.to_string(), path)),
+            Ok(_) => None,
+            Err(error) => {
+                log::warn!("Setup script skipped {} ({}): {}", tool, scope, error);
+                None
+            }
+        })
+        .collect()
+}
+
+fn claude_mcp_config(server_type: &str, server_config: &Value) -> Value {
+    let mut config = server_config.clone();
+    if let Value::Object(map) = &mut config {
+        map.entry("type".to_string())
+            .or_insert_with(|| Value::String(server_type.to_string()));
+    }
+    config
+}
+
+/// Generate a POSIX shell (default) or PowerShell script that reproduces the current setup.
+#[tauri::command]
+pub async fn export_setup_script(
+    state: State<'_, SqliteDbState>,
+    shell: Option<String>,
+) -> Result<String, String> {
+    let shell = ScriptShell::parse(shell.as_deref())?;
+    let mut secrets = SecretVars::default();
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (tool, scope, path) in collect_config_files(&state).await {
+        let label = format!("{}: {}", tool, path.display());
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(error) => {
+                skipped.push(format!("{} (read failed: {})", label, error));
+                continue;
+            }
+        };
+        match redact_file_content(&content, FileFormat::from_path(&path), &scope, &mut secrets) {
+            Some(content) => files.push(ExportedFile {
+                label,
+                path,
+                content,
+            }),
+            None => skipped.push(format!(
+                "{} (could not be parsed for secret redaction)",
+                label
+            )),
+        }
+    }
+
+    let mut mcp_servers = Vec::new();
+    for server in mcp_store::get_mcp_servers(&state).await? {
+        if !server
+            .enabled_tools
+            .iter()
+            .any(|tool| tool == CLAUDE_CODE_TOOL_KEY)
+        {
+            continue;
+        }
+        let mut config = claude_mcp_config(&server.server_type, &server.server_config);
+        redact_json(&mut config, &format!("mcp_{}", server.name), &mut secrets);
+        mcp_servers.push(ClaudeMcpEntry {
+            name: server.name,
+            config_json: serde_json::to_string(&config).map_err(|e| e.to_string())?,
+        });
+    }
+
+    let skill_notes = skill_store::get_managed_skills(&state)
+        .await?
+        .into_iter()
+        .map(|skill| match skill.source_ref.as_deref() {
+            Some(source) if skill.source_type == "git" => {
+                format!("{} (git: {})", skill.name, source)
+            }
+            _ => format!("{} ({})", skill.name, skill.source_type),
+        })
+        .collect();
+
+    let input = SetupScriptInput {
+        generated_at: chrono::Local::now().to_rfc3339(),
+        files,
+        skipped,
+        mcp_servers,
+        skill_notes,
+        secrets,
+    };
+    let home = dirs::home_dir();
+    Ok(match shell {
+        ScriptShell::Posix => render_posix_script(&input, home.as_deref()),
+        ScriptShell::PowerShell => render_powershell_script(&input, home.as_deref()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_secret_json_values_into_required_env_vars() {
+        let mut secrets = SecretVars::default();
+        let content = r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"sk-live","ANTHROPIC_BASE_URL":"https://a.example"}}"#;
+
+        let redacted =
+            redact_file_content(content, FileFormat::Json, "claude_settings", &mut secrets)
+                .expect("json parses");
+
+        assert!(!redacted.contains("sk-live"));
+        assert!(redacted.contains("https://a.example"));
+        assert_eq!(secrets.vars.len(), 1);
+        assert_eq!(
+            secrets.vars[0].0,
+            "AI_TOOLBOX_CLAUDE_SETTINGS_ANTHROPIC_AUTH_TOKEN"
+        );
+    }
+
+    #[test]
+    fn redacts_secret_toml_and_dotenv_values() {
+        let mut secrets = SecretVars::default();
+        let toml = "model = \"gpt\"\n[model_providers.x]\nenv_key = \"OPENAI_API_KEY\"\nexperimental_bearer_token = \"abc\"\n";
+        let redacted = redact_file_content(toml, FileFormat::Toml, "codex_config", &mut secrets)
+            .expect("toml parses");
+        assert!(!redacted.contains("\"abc\""));
+        assert!(redacted.contains("env_key = \"OPENAI_API_KEY\""));
+
+        let env = "GEMINI_API_KEY=secret\nGOOGLE_GEMINI_BASE_URL=https://g.example\n";
+        let redacted = redact_file_content(env, FileFormat::DotEnv, "gemini_env", &mut secrets)
+            .expect("dotenv is line based");
+        assert!(!redacted.contains("=secret"));
+        assert!(redacted.contains("GOOGLE_GEMINI_BASE_URL=https://g.example\n"));
+        assert_eq!(secrets.vars.len(), 2);
+    }
+
+    #[test]
+    fn redacts_jsonc_api_keys() {
+        let mut secrets = SecretVars::default();
+        let content = r#"{
+  // managed by ai-toolbox
+  "provider": {
+    "relay": { "options": { "baseURL": "https://r.example/v1", "apiKey": "sk-relay" } },
+  },
+}"#;
+        assert_eq!(
+            FileFormat::from_path(Path::new("/home/u/.config/opencode/opencode.jsonc")),
+            FileFormat::Jsonc
+        );
+
+        let redacted = redact_file_content(content, FileFormat::Jsonc, "opencode", &mut secrets)
+            .expect("jsonc parses");
+
+        assert!(!redacted.contains("sk-relay"));
+        assert!(redacted.contains("https://r.example/v1"));
+        assert_eq!(secrets.vars[0].0, "AI_TOOLBOX_OPENCODE_APIKEY");
+    }
+
+    #[test]
+    fn secret_keys_match_whole_words() {
+        for key in [
+            "apiKey",
+            "api_key",
+            "OPENAI_API_KEY",
+            "ANTHROPIC_AUTH_TOKEN",
+            "experimental_bearer_token",
+            "client_secret",
+            "Authorization",
+            "key",
+        ] {
+            assert!(is_secret_key(key), "{} should be secret", key);
+        }
+        for key in [
+            "CLAUDE_CODE_MAX_OUTPUT_TOKENS",
+            "max_tokens",
+            "env_key",
+            "keyboard",
+            "ANTHROPIC_BASE_URL",
+        ] {
+            assert!(!is_secret_key(key), "{} should not be secret", key);
+        }
+    }
+
+    #[test]
+    fn unparseable_json_is_skipped_instead_of_exported() {
+        let mut secrets = SecretVars::default();
+        assert!(redact_file_content("{ // jsonc", FileFormat::Json, "x", &mut secrets).is_none());
+    }
+
+    #[test]
+    fn posix_script_escapes_content_and_expands_only_placeholders() {
+        let mut secrets = SecretVars::default();
+        let marker = secrets.marker("codex_auth", "OPENAI_API_KEY");
+        let input = SetupScriptInput {
+            generated_at: "now".to_string(),
+            files: vec![ExportedFile {
+                label: "Codex".to_string(),
+                path: PathBuf::from("/home/u/.codex/auth.json"),
+                content: format!(
+                    "{{\"OPENAI_API_KEY\":\"{}\",\"cmd\":\"echo $PATH\"}}\n",
+                    marker
+                ),
+            }],
+            skipped: Vec::new(),
+            mcp_servers: Vec::new(),
+            skill_notes: Vec::new(),
+            secrets,
+        };
+
+        let script = render_posix_script(&input, Some(Path::new("/home/u")));
+
+        assert!(script.contains(": \"${AI_TOOLBOX_CODEX_AUTH_OPENAI_API_KEY:?"));
+        assert!(script.contains("\"${AI_TOOLBOX_CODEX_AUTH_OPENAI_API_KEY}\""));
+        assert!(script.contains("echo \\$PATH"));
+        assert!(script.contains("cat > \"$HOME/.codex/auth.json\" <<AI_TOOLBOX_EOF"));
+    }
+}
@@ -7,7 +7,7 @@ use tauri::Manager;
 
 use super::central_repo::{merge_skill_settings_sqlite, read_skill_settings_i64_from_sqlite};
 
-const CACHE_DIR_NAME: &str = "skills-git-cache";
+pub(crate) const CACHE_DIR_NAME: &str = "skills-git-cache";
 const CACHE_META_FILE: &str = ".skills-cache.json";
 pub const DEFAULT_GIT_CACHE_CLEANUP_DAYS: i64 = 30;
 const MAX_GIT_CACHE_CLEANUP_DAYS: i64 = 3650;
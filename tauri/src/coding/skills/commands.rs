@@ -15,7 +15,7 @@ use super::central_repo::{
     resolve_default_central_repo_path, resolve_skill_central_path, save_central_repo_path,
     to_relative_central_path,
 };
-use super::content_hash::hash_dir;
+use super::content_hash::{hash_dir, hash_dir_content};
 use super::git_fetcher::{set_proxy, GitProxyMode};
 use super::installer::{
     install_git_skill, install_git_skill_from_selection, install_local_skill,
@@ -40,9 +40,10 @@ use super::types::{
     CentralRepoTargetImpactDto, CentralSkillMatchDto, CentralSkillRepairCandidateDto, CustomTool,
     CustomToolDto, DeleteManagedSkillOptionsDto, DetectedCentralSkillDto, GitSkillCandidate,
     InstallResultDto, ManagedSkillDto, ManagedSkillSummaryDto, OnboardingPlan, Skill,
-    SkillGroupDto, SkillGroupRecord, SkillInventoryGroupJson, SkillInventoryJson,
-    SkillInventoryPreviewDto, SkillInventorySkillJson, SkillRepo, SkillRepoDto, SkillTarget,
-    SkillTargetDto, SyncResultDto, ToolInfoDto, ToolStatusDto, UpdateResultDto,
+    SkillDuplicateGroup, SkillGroupDto, SkillGroupRecord, SkillInventoryGroupJson,
+    SkillInventoryJson, SkillInventoryPreviewDto, SkillInventorySkillJson, SkillMergeResultDto,
+    SkillRepo, SkillRepoDto, SkillTarget, SkillTargetDto, SyncResultDto, ToolInfoDto,
+    ToolStatusDto, UpdateResultDto,
 };
 use crate::coding::runtime_location;
 use crate::http_client;
@@ -206,6 +207,29 @@ fn group_to_dto(group: SkillGroupRecord) -> SkillGroupDto {
 pub async fn skills_get_tool_status(
     state: State<'_, SqliteDbState>,
 ) -> Result<ToolStatusDto, String> {
+    collect_tool_status(&state).await
+}
+
+/// Re-detect installed tools without restarting, e.g. after the user installs a CLI.
+/// Runtime locations are cached at startup, so they are re-resolved before scanning.
+#[tauri::command]
+pub async fn refresh_tool_detection<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqliteDbState>,
+) -> Result<ToolStatusDto, String> {
+    if let Err(error) = runtime_location::refresh_runtime_location_cache_async(state.db()).await {
+        log::warn!("Failed to refresh runtime location cache: {}", error);
+    }
+
+    let status = collect_tool_status(&state).await?;
+
+    // Tray MCP/Skills sections list tools by install state, so rebuild them too.
+    let _ = app.emit("config-changed", "window");
+
+    Ok(status)
+}
+
+async fn collect_tool_status(state: &SqliteDbState) -> Result<ToolStatusDto, String> {
     // Get custom tools
     let custom_tools = skill_store::get_custom_tools(&state)
         .await
@@ -1896,17 +1920,46 @@ pub async fn skills_delete_managed(
     skillId: String,
     options: Option<DeleteManagedSkillOptionsDto>,
 ) -> Result<(), String> {
-    let record = skill_store::get_skill_by_id(&state, &skillId).await?;
+    let remove_failures = delete_managed_skill_record(
+        &app,
+        &state,
+        &skillId,
+        options.map(|options| options.delete_source_files),
+    )
+    .await?;
+
+    // Emit skills-changed for WSL sync
+    let _ = app.emit("skills-changed", "window");
+
+    if !remove_failures.is_empty() {
+        return Err(format!(
+            "Deleted managed record, but some tool directories could not be cleaned:\n- {}",
+            remove_failures.join("\n- ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Remove a managed Skill's tool targets and DB record, optionally deleting its
+/// source directory. Returns cleanup failures that did not block the delete.
+async fn delete_managed_skill_record<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    skill_id: &str,
+    delete_source_files: Option<bool>,
+) -> Result<Vec<String>, String> {
+    let record = skill_store::get_skill_by_id(state, skill_id).await?;
     let mut remove_failures: Vec<String> = Vec::new();
     if let Some(skill) = record {
         // Resolve central_path (handles cross-platform legacy paths)
-        let central_dir = resolve_central_repo_path(&app, &state)
+        let central_dir = resolve_central_repo_path(app, state)
             .await
             .map_err(|e| format_error(e))?;
-        let default_dir = resolve_default_central_repo_path(&app).map_err(|e| format_error(e))?;
+        let default_dir = resolve_default_central_repo_path(app).map_err(|e| format_error(e))?;
         let uses_default_central_dir = paths_equivalent(&central_dir, &default_dir);
         let path = resolve_skill_central_path(&skill.central_path, &central_dir);
-        let targets = skill_store::get_skill_targets(&state, &skillId).await?;
+        let targets = skill_store::get_skill_targets(state, skill_id).await?;
         for target in targets {
             if let Err(err) = remove_skill_target_checked(&path, &target.target_path) {
                 remove_failures.push(format!("{}: {}", target.target_path, err));
@@ -1914,9 +1967,7 @@ pub async fn skills_delete_managed(
         }
 
         let default_delete_source = skill.source_type != "central" && uses_default_central_dir;
-        let delete_source_files = options
-            .map(|options| options.delete_source_files)
-            .unwrap_or(default_delete_source);
+        let delete_source_files = delete_source_files.unwrap_or(default_delete_source);
         if delete_source_files {
             if source_path_missing_for_delete(&path) {
                 // The DB record can still be removed if the user already deleted the source dir.
@@ -1931,20 +1982,171 @@ pub async fn skills_delete_managed(
                 ));
             }
         }
-        skill_store::delete_skill(&state, &skillId).await?;
+        skill_store::delete_skill(state, skill_id).await?;
+    }
+
+    Ok(remove_failures)
+}
+
+// --- Duplicate Skills ---
+
+async fn hash_skill_content<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &SqliteDbState,
+    skill: &Skill,
+) -> Result<String, String> {
+    let source_path = resolve_skill_source_path(app, state, skill).await?;
+    if !source_path.is_dir() {
+        return Err(format!(
+            "Skill source path is missing or not a directory: {}",
+            source_path.display()
+        ));
+    }
+    tokio::task::spawn_blocking(move || hash_dir_content(&source_path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format_error(e))
+}
+
+/// Group managed Skills by file tree content, returning only groups with more than one member.
+fn group_duplicate_skills(hashed: Vec<(String, &Skill)>) -> Vec<SkillDuplicateGroup> {
+    let mut order: Vec<String> = Vec::new();
+    let mut groups: HashMap<String, Vec<ManagedSkillSummaryDto>> = HashMap::new();
+    for (hash, skill) in hashed {
+        let members = groups.entry(hash.clone()).or_insert_with(|| {
+            order.push(hash);
+            Vec::new()
+        });
+        members.push(ManagedSkillSummaryDto {
+            id: skill.id.clone(),
+            name: skill.name.clone(),
+        });
+    }
+
+    order
+        .into_iter()
+        .filter_map(|hash| {
+            let skills = groups.remove(&hash)?;
+            (skills.len() > 1).then_some(SkillDuplicateGroup {
+                content_hash: hash,
+                skills,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn skills_find_duplicates(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<Vec<SkillDuplicateGroup>, String> {
+    let skills = skill_store::get_managed_skills(&state).await?;
+    let mut hashed = Vec::with_capacity(skills.len());
+    for skill in &skills {
+        match hash_skill_content(&app, &state, skill).await {
+            Ok(hash) => hashed.push((hash, skill)),
+            Err(error) => log::warn!(
+                "Skipped duplicate check for Skill '{}': {}",
+                skill.name,
+                error
+            ),
+        }
+    }
+    Ok(group_duplicate_skills(hashed))
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn skills_merge_duplicates(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    keepId: String,
+    removeIds: Vec<String>,
+) -> Result<SkillMergeResultDto, String> {
+    let mut remove_ids: Vec<String> = Vec::new();
+    for id in removeIds {
+        if id == keepId {
+            return Err("The kept Skill cannot also be removed".to_string());
+        }
+        if !remove_ids.contains(&id) {
+            remove_ids.push(id);
+        }
+    }
+
+    let mut keep = skill_store::get_skill_by_id(&state, &keepId)
+        .await?
+        .ok_or_else(|| format!("Skill not found: {}", keepId))?;
+    if !keep.management_enabled {
+        return Err(format!("SKILL_DISABLED|{}", keepId));
+    }
+    let keep_hash = hash_skill_content(&app, &state, &keep).await?;
+
+    // Validate every removed Skill before touching anything, so a stale selection
+    // cannot delete a Skill whose content has diverged since detection.
+    let mut removed = Vec::with_capacity(remove_ids.len());
+    for id in &remove_ids {
+        let skill = skill_store::get_skill_by_id(&state, id)
+            .await?
+            .ok_or_else(|| format!("Skill not found: {}", id))?;
+        if hash_skill_content(&app, &state, &skill).await? != keep_hash {
+            return Err(format!(
+                "Skill '{}' no longer matches '{}' and cannot be merged",
+                skill.name, keep.name
+            ));
+        }
+        removed.push(skill);
+    }
+
+    let mut tools_to_sync: Vec<String> = Vec::new();
+    for skill in &removed {
+        let tools = if skill.management_enabled {
+            &skill.enabled_tools
+        } else {
+            &skill.disabled_previous_tools
+        };
+        for tool in tools {
+            if !keep.enabled_tools.contains(tool) && !tools_to_sync.contains(tool) {
+                tools_to_sync.push(tool.clone());
+            }
+        }
+    }
+
+    let mut failures: Vec<String> = Vec::new();
+    for skill in &removed {
+        let remove_failures = delete_managed_skill_record(&app, &state, &skill.id, None).await?;
+        failures.extend(remove_failures);
+    }
+
+    let source_path = resolve_skill_source_path(&app, &state, &keep).await?;
+    refresh_central_skill_hash_if_needed(&state, &mut keep, &source_path).await?;
+    let custom_tools = skill_store::get_custom_tools(&state)
+        .await
+        .unwrap_or_default();
+    let mut synced_tools = Vec::new();
+    for tool in tools_to_sync {
+        match sync_skill_to_tool_record(&state, &keep, &tool, &source_path, true, &custom_tools)
+            .await
+        {
+            Ok(_) => synced_tools.push(tool),
+            Err(error) => failures.push(format!("{}: {}", tool, error)),
+        }
     }
 
     // Emit skills-changed for WSL sync
     let _ = app.emit("skills-changed", "window");
 
-    if !remove_failures.is_empty() {
+    if !failures.is_empty() {
         return Err(format!(
-            "Deleted managed record, but some tool directories could not be cleaned:\n- {}",
-            remove_failures.join("\n- ")
+            "Merged duplicate Skills, but some tool targets failed:\n- {}",
+            failures.join("\n- ")
         ));
     }
 
-    Ok(())
+    Ok(SkillMergeResultDto {
+        kept_skill_id: keep.id,
+        removed_skill_ids: remove_ids,
+        synced_tools,
+    })
 }
 
 // --- Onboarding ---
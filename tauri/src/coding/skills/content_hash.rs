@@ -41,3 +41,67 @@ pub fn hash_dir(path: &Path) -> Result<String> {
     let digest = hasher.finalize();
     Ok(hex::encode(digest))
 }
+
+/// Hash only the file tree content (relative paths + bytes) in a stable order,
+/// so byte-identical Skills in different directories produce the same digest.
+/// Timestamps, permissions and other metadata are never read.
+pub fn hash_dir_content(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        if entry.depth() == 0 || !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(path)
+            .with_context(|| format!("strip prefix {:?}", entry.path()))?;
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let bytes =
+            std::fs::read(entry.path()).with_context(|| format!("read file {:?}", entry.path()))?;
+
+        hasher.update((relative.len() as u64).to_le_bytes());
+        hasher.update(relative.as_bytes());
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+    }
+
+    let digest = hasher.finalize();
+    Ok(hex::encode(digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_dir_content_matches_identical_trees_in_different_dirs() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        for name in ["a", "b"] {
+            let dir = temp.path().join(name).join("nested");
+            std::fs::create_dir_all(&dir).expect("create dir");
+            std::fs::write(temp.path().join(name).join("SKILL.md"), "# demo").expect("write");
+            std::fs::write(dir.join("tool.py"), "print(1)").expect("write");
+        }
+        std::fs::write(temp.path().join("b").join(".DS_Store"), "noise").expect("write");
+
+        let a = hash_dir_content(&temp.path().join("a")).expect("hash a");
+        let b = hash_dir_content(&temp.path().join("b")).expect("hash b");
+        assert_eq!(a, b);
+
+        std::fs::write(temp.path().join("b").join("SKILL.md"), "# changed").expect("write");
+        let b = hash_dir_content(&temp.path().join("b")).expect("hash b");
+        assert_ne!(a, b);
+    }
+}
@@ -19,7 +19,7 @@ use super::types::{now_ms, Skill, SkillGroupRecord, SkillPreferences, SkillRepo,
 
 const SKILL_PREFERENCES_ID: &str = "default";
 
-fn skill_order() -> Result<OrderSpec, String> {
+pub(crate) fn skill_order() -> Result<OrderSpec, String> {
     Ok(OrderSpec::single(OrderField::json_integer(
         "sort_index",
         OrderDirection::Asc,
@@ -40,7 +40,10 @@ fn skill_repo_order() -> Result<OrderSpec, String> {
     ]))
 }
 
-fn sqlite_get_managed_skills(sqlite_state: &SqliteDbState) -> Result<Vec<Skill>, String> {
+/// Managed skills as stored, without the group migrations `get_managed_skills` runs first
+pub(crate) fn sqlite_get_managed_skills(
+    sqlite_state: &SqliteDbState,
+) -> Result<Vec<Skill>, String> {
     let order = skill_order()?;
     sqlite_state.with_conn(|conn| {
         Ok(db_list(conn, DbTable::Skill, Some(&order))?
@@ -365,6 +365,21 @@ pub struct SkillTargetDto {
     pub synced_at: Option<i64>,
 }
 
+/// Group of managed Skills whose file trees are byte-identical
+#[derive(Debug, Serialize)]
+pub struct SkillDuplicateGroup {
+    pub content_hash: String,
+    pub skills: Vec<ManagedSkillSummaryDto>,
+}
+
+/// DTO for merging duplicate Skills into one kept record
+#[derive(Debug, Serialize)]
+pub struct SkillMergeResultDto {
+    pub kept_skill_id: String,
+    pub removed_skill_ids: Vec<String>,
+    pub synced_tools: Vec<String>,
+}
+
 /// DTO for install result
 #[derive(Debug, Serialize)]
 pub struct InstallResultDto {
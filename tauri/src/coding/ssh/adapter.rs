@@ -2,6 +2,8 @@ use super::super::db_id;
 use super::types::{
     default_directory_excludes_for_mapping, matches_default_directory_excludes,
     normalize_directory_excludes, SSHConnection, SSHFileMapping, SSHSyncConfig,
+    DEFAULT_HEALTH_CHECK_INTERVAL_SECS, DEFAULT_RECONNECT_BACKOFF_MAX_SECS,
+    SYNC_DIRECTION_BIDIRECTIONAL, SYNC_DIRECTION_PUSH,
 };
 use crate::coding::config_cleanup;
 use chrono::Local;
@@ -24,7 +26,6 @@ pub fn config_from_db_value(
             .unwrap_or(false),
         active_connection_id: value
             .get("active_connection_id")
-            .or_else(|| value.get("activeConnectionId"))
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string(),
@@ -35,21 +36,26 @@ pub fn config_from_db_value(
         connections,
         last_sync_time: value
             .get("last_sync_time")
-            .or_else(|| value.get("lastSyncTime"))
             .and_then(|v| v.as_str())
             .map(String::from),
         last_sync_status: value
             .get("last_sync_status")
-            .or_else(|| value.get("lastSyncStatus"))
             .and_then(|v| v.as_str())
             .unwrap_or("never")
             .to_string(),
         last_sync_error: value
             .get("last_sync_error")
-            .or_else(|| value.get("lastSyncError"))
             .and_then(|v| v.as_str())
             .map(String::from),
         module_statuses: vec![],
+        health_check_interval_secs: value
+            .get("health_check_interval_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL_SECS),
+        reconnect_backoff_max_secs: value
+            .get("reconnect_backoff_max_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_RECONNECT_BACKOFF_MAX_SECS),
     }
 }
 
@@ -61,6 +67,8 @@ pub fn config_to_db_value(config: &SSHSyncConfig) -> Value {
         "last_sync_time": config.last_sync_time,
         "last_sync_status": config.last_sync_status,
         "last_sync_error": config.last_sync_error,
+        "health_check_interval_secs": config.health_check_interval_secs,
+        "reconnect_backoff_max_secs": config.reconnect_backoff_max_secs,
     })
 }
 
@@ -92,7 +100,6 @@ pub fn connection_from_db_value(value: Value) -> SSHConnection {
             .to_string(),
         auth_method: value
             .get("auth_method")
-            .or_else(|| value.get("authMethod"))
             .and_then(|v| v.as_str())
             .unwrap_or("key")
             .to_string(),
@@ -103,13 +110,11 @@ pub fn connection_from_db_value(value: Value) -> SSHConnection {
             .to_string(),
         private_key_path: value
             .get("private_key_path")
-            .or_else(|| value.get("privateKeyPath"))
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string(),
         private_key_content: value
             .get("private_key_content")
-            .or_else(|| value.get("privateKeyContent"))
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string(),
@@ -120,7 +125,6 @@ pub fn connection_from_db_value(value: Value) -> SSHConnection {
             .to_string(),
         sort_order: value
             .get("sort_order")
-            .or_else(|| value.get("sortOrder"))
             .and_then(|v| v.as_u64())
             .unwrap_or(0) as u32,
     }
@@ -157,10 +161,7 @@ fn directory_excludes_from_db_value(
     }
 
     let default_excludes = default_directory_excludes_for_mapping(mapping_id);
-    let Some(raw_excludes) = value
-        .get("directory_excludes")
-        .or_else(|| value.get("directoryExcludes"))
-    else {
+    let Some(raw_excludes) = value.get("directory_excludes") else {
         return default_excludes;
     };
 
@@ -193,7 +194,6 @@ fn cleanup_paths_from_db_value(
 ) -> Vec<String> {
     let paths = value
         .get("cleanup_paths")
-        .or_else(|| value.get("cleanupPaths"))
         .and_then(Value::as_array)
         .map(|items| {
             items
@@ -218,23 +218,19 @@ pub fn mapping_from_db_value(value: Value) -> SSHFileMapping {
     let id = db_id::db_extract_id(&value);
     let is_directory = value
         .get("is_directory")
-        .or_else(|| value.get("isDirectory"))
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
     let is_pattern = value
         .get("is_pattern")
-        .or_else(|| value.get("isPattern"))
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
     let local_path = value
         .get("local_path")
-        .or_else(|| value.get("localPath"))
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
     let remote_path = value
         .get("remote_path")
-        .or_else(|| value.get("remotePath"))
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
@@ -264,6 +260,12 @@ pub fn mapping_from_db_value(value: Value) -> SSHFileMapping {
         is_directory,
         directory_excludes,
         cleanup_paths,
+        sync_direction: value
+            .get("sync_direction")
+            .and_then(|v| v.as_str())
+            .filter(|direction| *direction == SYNC_DIRECTION_BIDIRECTIONAL)
+            .unwrap_or(SYNC_DIRECTION_PUSH)
+            .to_string(),
     }
 }
 
@@ -291,6 +293,7 @@ pub fn mapping_to_db_value(mapping: &SSHFileMapping) -> Value {
             &mapping.local_path,
             &mapping.cleanup_paths
         ).unwrap_or_default(),
+        "sync_direction": mapping.sync_direction,
         "updated_at": Local::now().to_rfc3339(),
     })
 }
@@ -460,4 +463,30 @@ mod tests {
         assert!(mapping.cleanup_paths.is_empty());
         assert_eq!(mapping_to_db_value(&mapping)["cleanup_paths"], json!([]));
     }
+
+    #[test]
+    fn sync_direction_round_trips_and_only_applies_to_single_files() {
+        let mapping = |is_directory: bool, sync_direction: Option<&str>| {
+            let mut value = json!({
+                "id": "ssh_file_mapping:custom-notes",
+                "local_path": "~/notes",
+                "remote_path": "~/notes",
+                "is_directory": is_directory,
+            });
+            if let Some(direction) = sync_direction {
+                value["sync_direction"] = json!(direction);
+            }
+            mapping_from_db_value(value)
+        };
+
+        let file = mapping(false, Some("bidirectional"));
+        assert!(file.is_bidirectional());
+        assert_eq!(
+            mapping_to_db_value(&file)["sync_direction"],
+            json!("bidirectional")
+        );
+        assert!(!mapping(true, Some("bidirectional")).is_bidirectional());
+        assert_eq!(mapping(false, None).sync_direction, "push");
+        assert_eq!(mapping(false, Some("pull")).sync_direction, "push");
+    }
 }
@@ -0,0 +1,379 @@
+//! Bidirectional sync for single-file SSH mappings.
+//!
+//! Each side is reduced to a SHA-256 hash (plus mtime for display) and compared with the hash
+//! both sides had after the last successful sync, stored per connection + mapping in
+//! `ssh_sync_baseline`. Only the side that moved since then is copied; when both moved the
+//! mapping is parked as a `SyncConflict` until the user picks a side with
+//! `ssh_resolve_conflict`. Deletions are not propagated: a missing side is recreated.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use base64::Engine;
+use chrono::Local;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use super::session::SshSession;
+use super::sync::{self, CurrentFileReporter};
+use super::types::{SSHFileMapping, SyncConflict};
+use crate::coding::{atomic_write, config_backup};
+use crate::db::helpers::{db_get, db_put};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+const REMOTE_MISSING_MARKER: &str = "__ai_toolbox_missing__";
+
+static PENDING_CONFLICTS: LazyLock<Mutex<HashMap<String, SyncConflict>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncDecision {
+    Unchanged,
+    Push,
+    Pull,
+    Conflict,
+}
+
+/// Pick a direction from the current hashes of both sides and the hash of the last sync.
+fn decide_sync(local: Option<&str>, remote: Option<&str>, baseline: Option<&str>) -> SyncDecision {
+    match (local, remote) {
+        (None, None) => SyncDecision::Unchanged,
+        (Some(local), Some(remote)) if local == remote => SyncDecision::Unchanged,
+        (Some(_), None) => SyncDecision::Push,
+        (None, Some(_)) => SyncDecision::Pull,
+        (Some(local), Some(remote)) => match baseline {
+            Some(baseline) if baseline == remote => SyncDecision::Push,
+            Some(baseline) if baseline == local => SyncDecision::Pull,
+            _ => SyncDecision::Conflict,
+        },
+    }
+}
+
+pub(super) enum BidirectionalOutcome {
+    Synced(Vec<String>),
+    Conflict(SyncConflict),
+}
+
+struct FileState {
+    hash: String,
+    modified: Option<i64>,
+}
+
+fn local_file_state(path: &Path) -> Result<Option<FileState>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content =
+        fs::read(path).map_err(|e| format!("读取本地文件失败 {}: {}", path.display(), e))?;
+    let modified = fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64);
+    Ok(Some(FileState {
+        hash: hex::encode(Sha256::digest(content)),
+        modified,
+    }))
+}
+
+async fn remote_file_state(
+    session: &SshSession,
+    remote_path: &str,
+) -> Result<Option<FileState>, String> {
+    let remote_target = remote_path.replace("~", "$HOME");
+    let command = format!(
+        "f=\"{}\"; if [ -f \"$f\" ]; then stat -c %Y \"$f\" 2>/dev/null || stat -f %m \"$f\"; (sha256sum \"$f\" 2>/dev/null || shasum -a 256 \"$f\") | cut -d ' ' -f 1; else echo {}; fi",
+        remote_target, REMOTE_MISSING_MARKER
+    );
+    let output = session.exec_command(&command).await?;
+    let lines: Vec<&str> = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if lines.first() == Some(&REMOTE_MISSING_MARKER) {
+        return Ok(None);
+    }
+    let hash = lines
+        .last()
+        .filter(|hash| hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| format!("无法计算远端文件哈希: {}", remote_path))?
+        .to_ascii_lowercase();
+    let modified = if lines.len() > 1 {
+        lines[0].parse().ok()
+    } else {
+        None
+    };
+    Ok(Some(FileState { hash, modified }))
+}
+
+/// Copy a remote file to `local_path` byte-for-byte (base64 keeps non-UTF-8 content intact),
+/// backing up the local copy first.
+async fn download_remote_file(
+    session: &SshSession,
+    remote_path: &str,
+    local_path: &Path,
+) -> Result<(), String> {
+    let remote_target = remote_path.replace("~", "$HOME");
+    let encoded = session
+        .exec_command(&format!("base64 < \"{}\"", remote_target))
+        .await?;
+    let compact: String = encoded.split_whitespace().collect();
+    let content = base64::engine::general_purpose::STANDARD
+        .decode(compact)
+        .map_err(|e| format!("解析远端文件内容失败 {}: {}", remote_path, e))?;
+    config_backup::backup_before_write(local_path)?;
+    atomic_write::write_atomic(local_path, content)
+}
+
+fn baseline_id(connection_id: &str, mapping_id: &str) -> String {
+    format!("{}:{}", connection_id, mapping_id)
+}
+
+fn load_baseline(
+    db: &SqliteDbState,
+    connection_id: &str,
+    mapping_id: &str,
+) -> Result<Option<String>, String> {
+    let record = db.with_conn(|conn| {
+        db_get(
+            conn,
+            DbTable::SshSyncBaseline,
+            &baseline_id(connection_id, mapping_id),
+        )
+    })?;
+    Ok(record.and_then(|record| {
+        record
+            .get("hash")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    }))
+}
+
+fn save_baseline(
+    db: &SqliteDbState,
+    connection_id: &str,
+    mapping_id: &str,
+    hash: &str,
+) -> Result<(), String> {
+    let record = json!({
+        "connection_id": connection_id,
+        "mapping_id": mapping_id,
+        "hash": hash,
+        "updated_at": Local::now().to_rfc3339(),
+    });
+    db.with_conn(|conn| {
+        db_put(
+            conn,
+            DbTable::SshSyncBaseline,
+            &baseline_id(connection_id, mapping_id),
+            &record,
+        )
+    })
+}
+
+fn set_pending_conflict(mapping_id: &str, conflict: Option<SyncConflict>) {
+    if let Ok(mut pending) = PENDING_CONFLICTS.lock() {
+        match conflict {
+            Some(conflict) => {
+                pending.insert(mapping_id.to_string(), conflict);
+            }
+            None => {
+                pending.remove(mapping_id);
+            }
+        }
+    }
+}
+
+/// Conflicts found by earlier syncs that the user has not resolved yet.
+pub(super) fn pending_conflicts() -> Vec<SyncConflict> {
+    let mut conflicts: Vec<SyncConflict> = PENDING_CONFLICTS
+        .lock()
+        .map(|pending| pending.values().cloned().collect())
+        .unwrap_or_default();
+    conflicts.sort_by(|a, b| a.mapping_name.cmp(&b.mapping_name));
+    conflicts
+}
+
+pub(super) fn pending_conflict(mapping_id: &str) -> Option<SyncConflict> {
+    PENDING_CONFLICTS
+        .lock()
+        .ok()
+        .and_then(|pending| pending.get(mapping_id).cloned())
+}
+
+/// Sync one bidirectional mapping, pushing or pulling whichever side changed.
+pub(super) async fn sync_bidirectional_file(
+    db: &SqliteDbState,
+    session: &SshSession,
+    mapping: &SSHFileMapping,
+    current_file_reporter: Option<CurrentFileReporter<'_>>,
+) -> Result<BidirectionalOutcome, String> {
+    let connection_id = session
+        .conn()
+        .map(|connection| connection.id.clone())
+        .ok_or_else(|| "SSH 会话未建立".to_string())?;
+    let local_path = PathBuf::from(sync::expand_local_path(&mapping.local_path)?);
+    let local = local_file_state(&local_path)?;
+    let remote = remote_file_state(session, &mapping.remote_path).await?;
+    let baseline = load_baseline(db, &connection_id, &mapping.id)?;
+
+    let decision = decide_sync(
+        local.as_ref().map(|state| state.hash.as_str()),
+        remote.as_ref().map(|state| state.hash.as_str()),
+        baseline.as_deref(),
+    );
+    log::trace!(
+        "SSH bidirectional mapping decision: id={}, decision={:?}, has_baseline={}",
+        mapping.id,
+        decision,
+        baseline.is_some()
+    );
+
+    let (files, synced_hash) = match decision {
+        SyncDecision::Unchanged => (vec![], local.as_ref().map(|state| state.hash.clone())),
+        SyncDecision::Push => {
+            let files = sync::sync_single_file_with_progress(
+                &mapping.local_path,
+                &mapping.remote_path,
+                session,
+                current_file_reporter,
+            )
+            .await?;
+            (files, local.as_ref().map(|state| state.hash.clone()))
+        }
+        SyncDecision::Pull => {
+            if let Some(reporter) = current_file_reporter {
+                reporter(mapping.remote_path.clone());
+            }
+            download_remote_file(session, &mapping.remote_path, &local_path).await?;
+            (
+                vec![format!("{} <- {}", mapping.local_path, mapping.remote_path)],
+                remote.as_ref().map(|state| state.hash.clone()),
+            )
+        }
+        SyncDecision::Conflict => {
+            let conflict = SyncConflict {
+                mapping_id: mapping.id.clone(),
+                mapping_name: mapping.name.clone(),
+                connection_id,
+                local_path: local_path.to_string_lossy().to_string(),
+                remote_path: mapping.remote_path.clone(),
+                local_hash: local
+                    .as_ref()
+                    .map(|state| state.hash.clone())
+                    .unwrap_or_default(),
+                remote_hash: remote
+                    .as_ref()
+                    .map(|state| state.hash.clone())
+                    .unwrap_or_default(),
+                local_modified: local.as_ref().and_then(|state| state.modified),
+                remote_modified: remote.as_ref().and_then(|state| state.modified),
+                detected_at: Local::now().to_rfc3339(),
+            };
+            set_pending_conflict(&mapping.id, Some(conflict.clone()));
+            return Ok(BidirectionalOutcome::Conflict(conflict));
+        }
+    };
+
+    if let Some(hash) = synced_hash {
+        save_baseline(db, &connection_id, &mapping.id, &hash)?;
+    }
+    set_pending_conflict(&mapping.id, None);
+    Ok(BidirectionalOutcome::Synced(files))
+}
+
+/// Resolve a pending conflict. `choice` is "local" (upload the local copy), "remote" (download
+/// the remote copy) or "both" (keep local, save the remote copy next to it, then upload).
+pub(super) async fn resolve_conflict(
+    db: &SqliteDbState,
+    session: &SshSession,
+    conflict: &SyncConflict,
+    choice: &str,
+) -> Result<Vec<String>, String> {
+    let local_path = PathBuf::from(&conflict.local_path);
+    let mut files = Vec::new();
+    match choice {
+        "local" | "both" => {
+            if !local_path.is_file() {
+                return Err(format!("本地文件不存在: {}", conflict.local_path));
+            }
+            if choice == "both" {
+                let copy_path = PathBuf::from(format!(
+                    "{}.remote-{}",
+                    conflict.local_path,
+                    Local::now().format("%Y%m%d-%H%M%S")
+                ));
+                download_remote_file(session, &conflict.remote_path, &copy_path).await?;
+                files.push(format!(
+                    "{} <- {}",
+                    copy_path.to_string_lossy(),
+                    conflict.remote_path
+                ));
+            }
+            files.extend(
+                sync::sync_single_file_with_progress(
+                    &conflict.local_path,
+                    &conflict.remote_path,
+                    session,
+                    None,
+                )
+                .await?,
+            );
+        }
+        "remote" => {
+            download_remote_file(session, &conflict.remote_path, &local_path).await?;
+            files.push(format!(
+                "{} <- {}",
+                conflict.local_path, conflict.remote_path
+            ));
+        }
+        other => return Err(format!("未知的冲突处理方式: {}", other)),
+    }
+
+    if let Some(local) = local_file_state(&local_path)? {
+        save_baseline(
+            db,
+            &conflict.connection_id,
+            &conflict.mapping_id,
+            &local.hash,
+        )?;
+    }
+    set_pending_conflict(&conflict.mapping_id, None);
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decide_sync, SyncDecision};
+
+    #[test]
+    fn decide_sync_copies_only_the_side_that_changed_since_baseline() {
+        assert_eq!(decide_sync(None, None, None), SyncDecision::Unchanged);
+        assert_eq!(
+            decide_sync(Some("a"), Some("a"), None),
+            SyncDecision::Unchanged
+        );
+        assert_eq!(decide_sync(Some("a"), None, Some("a")), SyncDecision::Push);
+        assert_eq!(decide_sync(None, Some("a"), None), SyncDecision::Pull);
+        assert_eq!(
+            decide_sync(Some("b"), Some("a"), Some("a")),
+            SyncDecision::Push
+        );
+        assert_eq!(
+            decide_sync(Some("a"), Some("b"), Some("a")),
+            SyncDecision::Pull
+        );
+        assert_eq!(
+            decide_sync(Some("b"), Some("c"), Some("a")),
+            SyncDecision::Conflict
+        );
+        assert_eq!(
+            decide_sync(Some("b"), Some("c"), None),
+            SyncDecision::Conflict
+        );
+    }
+}
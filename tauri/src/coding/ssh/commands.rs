@@ -1,7 +1,8 @@
+use super::bidirectional::{self, BidirectionalOutcome};
 use super::key_file;
 use super::types::{
     SSHConnection, SSHConnectionResult, SSHFileMapping, SSHStatusResult, SSHSyncConfig,
-    SyncProgress, SyncResult,
+    SyncConflict, SyncProgress, SyncResult,
 };
 use super::{adapter, session::SshSession, session::SshSessionState, sync};
 use crate::coding::claude_code::plugin_metadata_sync;
@@ -11,6 +12,9 @@ use crate::coding::pi::constants::{
     PI_AUTH_FILE, PI_MCP_FILE, PI_MODELS_FILE, PI_PROMPT_FILE, PI_SETTINGS_FILE,
 };
 use crate::coding::runtime_location;
+use crate::coding::sync_mappings::{self, MappingImportPlan};
+use crate::coding::sync_performance;
+use crate::coding::sync_trace;
 use crate::db::helpers::{db_delete, db_delete_all, db_get, db_list, db_put};
 use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
@@ -52,6 +56,13 @@ fn load_ssh_config_record(state: &SqliteDbState) -> Result<Option<serde_json::Va
     state.with_conn(|conn| db_get(conn, DbTable::SshSyncConfig, "config"))
 }
 
+/// Saved connection tuning without loading mappings or connections (health-check loop).
+pub fn load_ssh_connection_tuning(state: &SqliteDbState) -> Result<SSHSyncConfig, String> {
+    Ok(load_ssh_config_record(state)?
+        .map(|record| adapter::config_from_db_value(record, vec![], vec![]))
+        .unwrap_or_default())
+}
+
 fn load_ssh_connections(state: &SqliteDbState) -> Result<Vec<SSHConnection>, String> {
     let order = ssh_connection_order()?;
     state.with_conn(|conn| {
@@ -116,6 +127,7 @@ async fn ensure_session_matches_active_connection(
     config: &SSHSyncConfig,
 ) -> Result<(), String> {
     let active_connection = get_active_connection(config)?;
+    session.set_reconnect_backoff_max(config.reconnect_backoff_max());
     let current_connection_id = session.conn().map(|connection| connection.id.as_str());
 
     if current_connection_id != Some(config.active_connection_id.as_str()) {
@@ -196,6 +208,7 @@ pub async fn ssh_save_config(
 
     // 连接生命周期管理
     let mut session = session_state.0.lock().await;
+    session.set_reconnect_backoff_max(config.reconnect_backoff_max());
     if config.enabled && !config.active_connection_id.is_empty() {
         // 找到目标连接并建立/切换主连接
         if let Some(conn) = config
@@ -436,6 +449,53 @@ pub async fn ssh_reset_file_mappings(
     Ok(())
 }
 
+/// Load SSH file mappings for sync mapping export.
+pub(crate) fn export_file_mappings(state: &SqliteDbState) -> Result<Vec<SSHFileMapping>, String> {
+    load_ssh_file_mappings(state)
+}
+
+/// Load SSH connections for sync mapping export (callers must strip secrets).
+pub(crate) fn export_connections(state: &SqliteDbState) -> Result<Vec<SSHConnection>, String> {
+    load_ssh_connections(state)
+}
+
+/// Work out what restoring SSH file mappings from an export would do, without writing.
+///
+/// With `merge`, incoming mappings whose remote path is already mapped are skipped;
+/// otherwise all current mappings are replaced.
+pub(crate) fn plan_file_mappings(
+    state: &SqliteDbState,
+    mappings: Vec<SSHFileMapping>,
+    merge: bool,
+) -> Result<MappingImportPlan<SSHFileMapping>, String> {
+    Ok(sync_mappings::plan_mapping_import(
+        load_ssh_file_mappings(state)?,
+        mappings,
+        merge,
+        |mapping| &mapping.remote_path,
+        |mapping| &mut mapping.id,
+        validate_file_mapping_cleanup_paths,
+    ))
+}
+
+/// Restore SSH file mappings from an export (see `plan_file_mappings`). Returns
+/// (imported, skipped).
+pub(crate) fn import_file_mappings(
+    state: &SqliteDbState,
+    mappings: Vec<SSHFileMapping>,
+    merge: bool,
+) -> Result<(usize, usize), String> {
+    let plan = plan_file_mappings(state, mappings, merge)?;
+    sync_mappings::write_mapping_import(
+        state,
+        DbTable::SshFileMapping,
+        plan,
+        merge,
+        |mapping| &mapping.id,
+        adapter::mapping_to_db_value,
+    )
+}
+
 // ============================================================================
 // Sync Commands
 // ============================================================================
@@ -449,6 +509,7 @@ pub async fn do_full_sync(
     module: Option<&str>,
     skip_modules: Option<&[String]>,
 ) -> SyncResult {
+    let _job = crate::db::jobs::DbJobGuard::begin("ssh_sync");
     let total_mapping_count = config.file_mappings.len();
     let enabled_mapping_count = config.file_mappings.iter().filter(|m| m.enabled).count();
     let disabled_mapping_count = total_mapping_count.saturating_sub(enabled_mapping_count);
@@ -488,7 +549,8 @@ pub async fn do_full_sync(
 
     // Sync file file_mappings with progress
     let mut result =
-        sync_file_mappings_with_progress(&file_mappings, session, module, skip_modules, app).await;
+        sync_file_mappings_with_progress(state, &file_mappings, session, module, skip_modules, app)
+            .await;
     log::info!(
         "SSH full sync file stage completed: synced_files={}, skipped_files={}, errors={}",
         result.synced_files.len(),
@@ -624,6 +686,7 @@ async fn expand_tilde_with_remote_home(session: &SshSession, path: &str) -> Resu
 
 /// Sync file file_mappings with progress events
 async fn sync_file_mappings_with_progress(
+    state: &SqliteDbState,
     file_mappings: &[SSHFileMapping],
     session: &SshSession,
     module_filter: Option<&str>,
@@ -633,6 +696,7 @@ async fn sync_file_mappings_with_progress(
     let mut synced_files = vec![];
     let mut skipped_files = vec![];
     let mut errors = vec![];
+    let mut conflicts = vec![];
     let mut filtered_file_mappings = Vec::new();
     let mut disabled_mapping_count = 0usize;
     let mut filtered_by_module_count = 0usize;
@@ -721,9 +785,59 @@ async fn sync_file_mappings_with_progress(
             );
         };
 
-        match sync::sync_file_mapping_with_progress(mapping, session, Some(&report_current_file))
+        sync_trace::trace_mapping_start(
+            "ssh",
+            &mapping.id,
+            &mapping.name,
+            &mapping.module,
+            sync_trace::mapping_kind(mapping.is_pattern, mapping.is_directory),
+            &mapping.local_path,
+            &mapping.remote_path,
+            || {
+                sync_performance::local_source_bytes(
+                    &mapping.local_path,
+                    mapping.is_pattern,
+                    mapping.is_directory,
+                )
+            },
+        );
+        let started_at = std::time::Instant::now();
+        let mapping_result = if mapping.is_bidirectional() {
+            match bidirectional::sync_bidirectional_file(
+                state,
+                session,
+                mapping,
+                Some(&report_current_file),
+            )
             .await
-        {
+            {
+                Ok(BidirectionalOutcome::Synced(files)) => Ok(files),
+                Ok(BidirectionalOutcome::Conflict(conflict)) => {
+                    log::warn!(
+                        "SSH sync mapping has conflicting local and remote changes: id={}, name={}, local_path={}, remote_path={}",
+                        mapping.id,
+                        mapping.name,
+                        conflict.local_path,
+                        conflict.remote_path
+                    );
+                    sync_trace::trace_mapping_result(
+                        "ssh",
+                        &mapping.id,
+                        "conflict",
+                        &[],
+                        started_at.elapsed(),
+                    );
+                    skipped_files.push(mapping.name.clone());
+                    conflicts.push(conflict);
+                    continue;
+                }
+                Err(error) => Err(error),
+            }
+        } else {
+            sync::sync_file_mapping_with_progress(mapping, session, Some(&report_current_file))
+                .await
+        };
+        match mapping_result {
             Ok(mut files) => {
                 if !files.is_empty() {
                     match cleanup_synced_file_on_ssh(mapping, session).await {
@@ -749,6 +863,20 @@ async fn sync_file_mappings_with_progress(
                     Ok(catalog_files) => files.extend(catalog_files),
                     Err(error) => errors.push(format!("{}: {}", mapping.name, error)),
                 }
+                sync_trace::trace_mapping_files(
+                    "ssh",
+                    &mapping.id,
+                    if mapping.is_bidirectional() {
+                        "sftp_sync"
+                    } else {
+                        "sftp_upload"
+                    },
+                    &mapping.local_path,
+                    mapping.is_pattern,
+                    mapping.is_directory,
+                    &mapping.directory_excludes,
+                    !files.is_empty(),
+                );
                 if files.is_empty() {
                     log::warn!(
                         "SSH sync mapping produced no uploaded files: id={}, name={}, module={}, local_path={}, remote_path={}",
@@ -758,9 +886,23 @@ async fn sync_file_mappings_with_progress(
                         mapping.local_path,
                         mapping.remote_path
                     );
+                    sync_trace::trace_mapping_result(
+                        "ssh",
+                        &mapping.id,
+                        "skipped (no files uploaded)",
+                        &[],
+                        started_at.elapsed(),
+                    );
                     skipped_files.push(mapping.name.clone());
                     continue;
                 }
+                sync_trace::trace_mapping_result(
+                    "ssh",
+                    &mapping.id,
+                    "synced",
+                    &files,
+                    started_at.elapsed(),
+                );
                 log::trace!(
                     "SSH sync mapping uploaded files: id={}, name={}, module={}, uploaded_count={}, remote_path={}",
                     mapping.id,
@@ -769,6 +911,18 @@ async fn sync_file_mappings_with_progress(
                     files.len(),
                     mapping.remote_path
                 );
+                sync_performance::record_mapping_perf(
+                    "ssh",
+                    &mapping.id,
+                    &mapping.name,
+                    &mapping.module,
+                    sync_performance::local_source_bytes(
+                        &mapping.local_path,
+                        mapping.is_pattern,
+                        mapping.is_directory,
+                    ),
+                    started_at.elapsed(),
+                );
                 synced_files.extend(files);
             }
             Err(e) => {
@@ -781,11 +935,22 @@ async fn sync_file_mappings_with_progress(
                     mapping.remote_path,
                     e
                 );
+                sync_trace::trace_mapping_result(
+                    "ssh",
+                    &mapping.id,
+                    "failed",
+                    std::slice::from_ref(&e),
+                    started_at.elapsed(),
+                );
                 errors.push(format!("{}: {}", mapping.name, e));
             }
         }
     }
 
+    if !conflicts.is_empty() {
+        let _ = app.emit("ssh-sync-conflicts", &conflicts);
+    }
+
     SyncResult {
         success: errors.is_empty(),
         synced_files,
@@ -1042,6 +1207,50 @@ pub async fn ssh_sync(
     Ok(result)
 }
 
+/// List bidirectional mappings whose local and remote copies both changed since the last sync
+#[tauri::command]
+pub fn ssh_list_sync_conflicts() -> Vec<SyncConflict> {
+    bidirectional::pending_conflicts()
+}
+
+/// Resolve a sync conflict by keeping the "local" copy, the "remote" copy, or "both"
+/// (the remote copy is saved next to the local file before the local one is pushed)
+#[tauri::command]
+pub async fn ssh_resolve_conflict(
+    state: tauri::State<'_, SqliteDbState>,
+    session_state: tauri::State<'_, SshSessionState>,
+    app: tauri::AppHandle,
+    mapping_id: String,
+    choice: String,
+) -> Result<Vec<String>, String> {
+    let conflict = bidirectional::pending_conflict(&mapping_id)
+        .ok_or_else(|| format!("没有待处理的同步冲突: {}", mapping_id))?;
+    let config = ssh_get_config(state.clone()).await?;
+    if config.active_connection_id != conflict.connection_id {
+        return Err("SSH 活跃连接已切换，请重新同步后再处理冲突".to_string());
+    }
+
+    let mut session = session_state.0.lock().await;
+    if !session.try_acquire_sync_lock() {
+        return Err("另一个同步操作正在进行中".to_string());
+    }
+    let result = match ensure_session_matches_active_connection(&mut session, &config).await {
+        Ok(()) => bidirectional::resolve_conflict(&state, &session, &conflict, &choice).await,
+        Err(e) => Err(format!("SSH 连接失败: {}", e)),
+    };
+    session.release_sync_lock();
+
+    let files = result?;
+    log::info!(
+        "SSH sync conflict resolved: mapping_id={}, choice={}, files={}",
+        mapping_id,
+        choice,
+        files.len()
+    );
+    let _ = app.emit("ssh-sync-conflicts", bidirectional::pending_conflicts());
+    Ok(files)
+}
+
 /// Get SSH sync status
 #[tauri::command]
 pub async fn ssh_get_status(
@@ -1532,6 +1741,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-oh-my".to_string(),
@@ -1544,6 +1754,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-oh-my-slim".to_string(),
@@ -1556,6 +1767,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-auth".to_string(),
@@ -1568,6 +1780,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-plugins".to_string(),
@@ -1580,6 +1793,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-prompt".to_string(),
@@ -1592,6 +1806,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-agent".to_string(),
@@ -1604,6 +1819,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: true,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "opencode-agents".to_string(),
@@ -1616,6 +1832,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: true,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         // Claude Code
         SSHFileMapping {
@@ -1629,6 +1846,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "claude-config".to_string(),
@@ -1641,6 +1859,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "claude-prompt".to_string(),
@@ -1653,6 +1872,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "claude-plugins".to_string(),
@@ -1667,6 +1887,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
                 super::types::CLAUDE_PLUGINS_MAPPING_ID,
             ),
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         // Codex
         SSHFileMapping {
@@ -1680,6 +1901,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "codex-config".to_string(),
@@ -1692,6 +1914,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "codex-prompt".to_string(),
@@ -1704,6 +1927,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "codex-plugins".to_string(),
@@ -1716,6 +1940,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: true,
             directory_excludes: super::types::default_directory_excludes(),
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         // Grok
         SSHFileMapping {
@@ -1729,6 +1954,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "grok-config".to_string(),
@@ -1741,6 +1967,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "grok-prompt".to_string(),
@@ -1753,6 +1980,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "grok-plugins".to_string(),
@@ -1765,6 +1993,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: true,
             directory_excludes: super::types::default_directory_excludes(),
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         // OpenClaw
         SSHFileMapping {
@@ -1778,6 +2007,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         // Gemini CLI
         SSHFileMapping {
@@ -1791,6 +2021,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "geminicli-settings".to_string(),
@@ -1803,6 +2034,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "geminicli-prompt".to_string(),
@@ -1815,6 +2047,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "geminicli-oauth".to_string(),
@@ -1827,6 +2060,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         // Pi
         SSHFileMapping {
@@ -1840,6 +2074,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "pi-auth".to_string(),
@@ -1852,6 +2087,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "pi-models".to_string(),
@@ -1864,6 +2100,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "pi-mcp".to_string(),
@@ -1876,6 +2113,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "pi-prompt".to_string(),
@@ -1888,6 +2126,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "pi-system".to_string(),
@@ -1900,6 +2139,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "pi-append-system".to_string(),
@@ -1912,6 +2152,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
         SSHFileMapping {
             id: "pi-trust".to_string(),
@@ -1924,6 +2165,7 @@ pub fn default_file_mappings() -> Vec<SSHFileMapping> {
             is_directory: false,
             directory_excludes: vec![],
             cleanup_paths: vec![],
+            sync_direction: "push".to_string(),
         },
     ]
 }
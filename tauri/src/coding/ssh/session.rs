@@ -6,7 +6,7 @@
 use std::collections::HashSet;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use log::{info, warn};
 use russh::keys::ssh_key;
@@ -15,7 +15,10 @@ use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 
 use super::key_file;
-use super::types::SSHConnection;
+use super::types::{SSHConnection, DEFAULT_RECONNECT_BACKOFF_MAX_SECS};
+
+/// 重连退避的初始等待，之后每次失败翻倍
+const RECONNECT_BACKOFF_BASE: Duration = Duration::from_secs(2);
 
 /// 加载私钥：优先从内容直接解析，否则从文件路径加载
 fn load_private_key(conn: &SSHConnection) -> Result<russh::keys::PrivateKey, String> {
@@ -52,6 +55,20 @@ pub enum SessionStatus {
     Failed(String),
 }
 
+/// 连续失败 `failures` 次后的重连等待：从 `RECONNECT_BACKOFF_BASE` 指数增长并封顶 `max`，
+/// 再按 `jitter`（0..=1）随机扣掉最多一半，避免多个客户端同时重试
+fn reconnect_backoff_delay(failures: u32, max: Duration, jitter: f64) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    let delay = RECONNECT_BACKOFF_BASE
+        .saturating_mul(1 << exponent)
+        .min(max);
+    delay.mul_f64(1.0 - 0.5 * jitter.clamp(0.0, 1.0))
+}
+
+fn random_jitter() -> f64 {
+    uuid::Uuid::new_v4().as_u128() as u64 as f64 / u64::MAX as f64
+}
+
 /// russh 客户端 Handler 实现
 struct SshHandler;
 
@@ -125,6 +142,12 @@ pub struct SshSession {
     status: SessionStatus,
     /// 是否正在进行同步操作（防止并发）
     syncing: AtomicBool,
+    /// 连续自动重连失败次数
+    reconnect_failures: u32,
+    /// 退避期内不再自动重连
+    reconnect_not_before: Option<Instant>,
+    /// 退避等待上限
+    reconnect_backoff_max: Duration,
 }
 
 /// 全局 SSH 会话状态，注册到 Tauri State
@@ -138,9 +161,22 @@ impl SshSession {
             handle: None,
             status: SessionStatus::Disconnected,
             syncing: AtomicBool::new(false),
+            reconnect_failures: 0,
+            reconnect_not_before: None,
+            reconnect_backoff_max: Duration::from_secs(DEFAULT_RECONNECT_BACKOFF_MAX_SECS),
         }
     }
 
+    /// 设置重连退避上限（来自 `SSHSyncConfig.reconnect_backoff_max_secs`）
+    pub fn set_reconnect_backoff_max(&mut self, max: Duration) {
+        self.reconnect_backoff_max = max;
+    }
+
+    fn reset_reconnect_backoff(&mut self) {
+        self.reconnect_failures = 0;
+        self.reconnect_not_before = None;
+    }
+
     /// 获取当前状态
     pub fn status(&self) -> &SessionStatus {
         &self.status
@@ -159,6 +195,11 @@ impl SshSession {
             return Ok(());
         }
 
+        // 换目标时旧目标的退避不再适用
+        if self.conn.as_ref().map(|c| &c.id) != Some(&conn.id) {
+            self.reset_reconnect_backoff();
+        }
+
         // 如果之前连接了不同目标，先断开
         self.disconnect().await;
 
@@ -169,6 +210,7 @@ impl SshSession {
             Ok(handle) => {
                 self.handle = Some(handle);
                 self.status = SessionStatus::Connected;
+                self.reset_reconnect_backoff();
                 info!(
                     "SSH 连接已建立: {}@{}:{}",
                     conn.username, conn.host, conn.port
@@ -215,6 +257,9 @@ impl SshSession {
     }
 
     /// 确保连接可用（不可用时自动重连）
+    ///
+    /// 重连失败后按指数退避（带抖动）等待，退避期内直接返回错误而不连服务器；
+    /// 显式 `connect` 不受退避限制。
     pub async fn ensure_connected(&mut self) -> Result<(), String> {
         if self.is_alive() {
             self.status = SessionStatus::Connected;
@@ -224,8 +269,32 @@ impl SshSession {
             .conn
             .clone()
             .ok_or("没有可用的 SSH 连接配置".to_string())?;
+        if let Some(not_before) = self.reconnect_not_before {
+            let now = Instant::now();
+            if now < not_before {
+                return Err(format!(
+                    "SSH 重连退避中，{} 秒后再试",
+                    (not_before - now).as_secs().max(1)
+                ));
+            }
+        }
         warn!("SSH 连接已断开，正在重连...");
-        self.connect(&conn).await
+        let result = self.connect(&conn).await;
+        if result.is_err() {
+            self.reconnect_failures = self.reconnect_failures.saturating_add(1);
+            let delay = reconnect_backoff_delay(
+                self.reconnect_failures,
+                self.reconnect_backoff_max,
+                random_jitter(),
+            );
+            self.reconnect_not_before = Some(Instant::now() + delay);
+            warn!(
+                "SSH 重连连续失败 {} 次，{} 秒内不再自动重连",
+                self.reconnect_failures,
+                delay.as_secs()
+            );
+        }
+        result
     }
 
     /// 断开连接
@@ -727,9 +796,10 @@ impl Drop for SshSession {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_excluded_dir_name, relative_upload_path};
+    use super::{is_excluded_dir_name, reconnect_backoff_delay, relative_upload_path};
     use std::collections::HashSet;
     use std::path::Path;
+    use std::time::Duration;
 
     #[test]
     fn matches_excluded_directory_by_name_segment_only() {
@@ -748,4 +818,17 @@ mod tests {
 
         assert_eq!(relative_upload_path(base, file), "marketplace/cache.json");
     }
+
+    #[test]
+    fn reconnect_backoff_doubles_caps_and_applies_jitter() {
+        let max = Duration::from_secs(30);
+
+        assert_eq!(reconnect_backoff_delay(1, max, 0.0), Duration::from_secs(2));
+        assert_eq!(reconnect_backoff_delay(3, max, 0.0), Duration::from_secs(8));
+        assert_eq!(reconnect_backoff_delay(10, max, 0.0), max);
+        assert_eq!(
+            reconnect_backoff_delay(u32::MAX, max, 1.0),
+            Duration::from_secs(15)
+        );
+    }
 }
@@ -4,7 +4,7 @@ use super::types::{
 };
 use std::path::{Path, PathBuf};
 
-type CurrentFileReporter<'a> = &'a (dyn Fn(String) + Send + Sync);
+pub(super) type CurrentFileReporter<'a> = &'a (dyn Fn(String) + Send + Sync);
 
 fn mapping_kind(mapping: &SSHFileMapping) -> &'static str {
     if mapping.is_directory {
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 // Re-use SyncResult and SyncProgress from wsl module
@@ -17,6 +19,24 @@ pub const DEFAULT_DIRECTORY_EXCLUDES: &[&str] = &[
 
 pub const CLAUDE_PLUGINS_MAPPING_ID: &str = "claude-plugins";
 
+pub const DEFAULT_HEALTH_CHECK_INTERVAL_SECS: u64 = 60;
+pub const DEFAULT_RECONNECT_BACKOFF_MAX_SECS: u64 = 300;
+
+fn default_health_check_interval_secs() -> u64 {
+    DEFAULT_HEALTH_CHECK_INTERVAL_SECS
+}
+
+fn default_reconnect_backoff_max_secs() -> u64 {
+    DEFAULT_RECONNECT_BACKOFF_MAX_SECS
+}
+
+pub const SYNC_DIRECTION_PUSH: &str = "push";
+pub const SYNC_DIRECTION_BIDIRECTIONAL: &str = "bidirectional";
+
+fn default_sync_direction() -> String {
+    SYNC_DIRECTION_PUSH.to_string()
+}
+
 pub fn default_directory_excludes() -> Vec<String> {
     DEFAULT_DIRECTORY_EXCLUDES
         .iter()
@@ -97,6 +117,37 @@ pub struct SSHFileMapping {
     pub directory_excludes: Vec<String>,
     #[serde(default)]
     pub cleanup_paths: Vec<String>,
+    /// "push" | "bidirectional"
+    #[serde(default = "default_sync_direction")]
+    pub sync_direction: String,
+}
+
+impl SSHFileMapping {
+    /// Bidirectional sync only applies to plain single-file mappings without cleanup rules;
+    /// everything else keeps pushing, whatever `sync_direction` says.
+    pub fn is_bidirectional(&self) -> bool {
+        self.sync_direction == SYNC_DIRECTION_BIDIRECTIONAL
+            && !self.is_directory
+            && !self.is_pattern
+            && self.cleanup_paths.is_empty()
+    }
+}
+
+/// A bidirectional mapping whose local and remote copies both changed since the last sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    pub mapping_id: String,
+    pub mapping_name: String,
+    pub connection_id: String,
+    pub local_path: String,
+    pub remote_path: String,
+    pub local_hash: String,
+    pub remote_hash: String,
+    /// Modification times in unix seconds, when available
+    pub local_modified: Option<i64>,
+    pub remote_modified: Option<i64>,
+    pub detected_at: String,
 }
 
 // ============================================================================
@@ -119,6 +170,24 @@ pub struct SSHSyncConfig {
     pub last_sync_error: Option<String>,
     #[serde(default)]
     pub module_statuses: Vec<WslDirectModuleStatus>,
+    /// Seconds between background connection health checks
+    #[serde(default = "default_health_check_interval_secs")]
+    pub health_check_interval_secs: u64,
+    /// Longest wait between reconnect attempts after repeated failures
+    #[serde(default = "default_reconnect_backoff_max_secs")]
+    pub reconnect_backoff_max_secs: u64,
+}
+
+impl SSHSyncConfig {
+    /// Health-check interval, clamped to 10s..=1h
+    pub fn health_check_interval(&self) -> Duration {
+        Duration::from_secs(self.health_check_interval_secs.clamp(10, 3600))
+    }
+
+    /// Reconnect backoff cap, clamped to 5s..=1h
+    pub fn reconnect_backoff_max(&self) -> Duration {
+        Duration::from_secs(self.reconnect_backoff_max_secs.clamp(5, 3600))
+    }
 }
 
 impl Default for SSHSyncConfig {
@@ -134,6 +203,8 @@ impl Default for SSHSyncConfig {
             last_sync_status: "never".to_string(),
             last_sync_error: None,
             module_statuses: vec![],
+            health_check_interval_secs: DEFAULT_HEALTH_CHECK_INTERVAL_SECS,
+            reconnect_backoff_max_secs: DEFAULT_RECONNECT_BACKOFF_MAX_SECS,
         }
     }
 }
@@ -0,0 +1,373 @@
+//! Export/import of WSL and SSH file mapping sets as portable JSON.
+//!
+//! Only mapping structure travels between machines. SSH connections are exported as host
+//! references (no password, key content or passphrase) and are never recreated on import.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{Emitter, State};
+
+use crate::coding::ssh::{self, SSHFileMapping};
+use crate::coding::wsl::{self, FileMapping};
+use crate::db::helpers::{db_delete_all, db_put, db_transaction};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+use crate::settings::backup::import_plan::{ImportPlanItem, PlannedChanges};
+
+const SYNC_MAPPINGS_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SshHostReference {
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub auth_method: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SshMappingSet {
+    pub mappings: Vec<SSHFileMapping>,
+    #[serde(default)]
+    pub hosts: Vec<SshHostReference>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WslMappingSet {
+    pub mappings: Vec<FileMapping>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncMappingsExport {
+    pub schema_version: u32,
+    pub exported_at: String,
+    #[serde(default)]
+    pub wsl: Option<WslMappingSet>,
+    #[serde(default)]
+    pub ssh: Option<SshMappingSet>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncMappingsImportResult {
+    pub wsl_imported: usize,
+    pub wsl_skipped: usize,
+    pub ssh_imported: usize,
+    pub ssh_skipped: usize,
+}
+
+/// What importing one mapping set would write, keep out, and replace.
+pub(crate) struct MappingImportPlan<M> {
+    pub to_write: Vec<M>,
+    /// Incoming mappings whose target path is already mapped
+    pub skipped: Vec<M>,
+    /// Current mappings that a non-merge import deletes
+    pub replaced: Vec<M>,
+    pub errors: Vec<String>,
+}
+
+impl<M> MappingImportPlan<M> {
+    /// Report the plan by target path, since colliding ids are regenerated on write.
+    fn add_to(
+        self,
+        scope: &str,
+        item: impl Fn(&M) -> (String, String),
+        changes: &mut PlannedChanges,
+    ) {
+        let to_items = |mappings: Vec<M>| -> Vec<ImportPlanItem> {
+            mappings
+                .iter()
+                .map(|mapping| {
+                    let (key, label) = item(mapping);
+                    ImportPlanItem::new(scope, key, label)
+                })
+                .collect()
+        };
+        changes.create.extend(to_items(self.to_write));
+        changes.skip.extend(to_items(self.skipped));
+        changes.remove.extend(to_items(self.replaced));
+        changes.errors.extend(self.errors);
+    }
+}
+
+/// Comparison key for deduping mappings by target path.
+pub(crate) fn mapping_path_key(path: &str) -> String {
+    let trimmed = path.trim().replace('\\', "/");
+    let trimmed = trimmed.trim_end_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Dedupe `incoming` by target path against the mappings that stay (all current ones with
+/// `merge`, none otherwise), regenerating ids that collide. Shared by WSL and SSH.
+pub(crate) fn plan_mapping_import<M>(
+    current: Vec<M>,
+    incoming: Vec<M>,
+    merge: bool,
+    target_path: fn(&M) -> &str,
+    id: fn(&mut M) -> &mut String,
+    validate: fn(&M) -> Result<(), String>,
+) -> MappingImportPlan<M> {
+    let errors = incoming
+        .iter()
+        .filter_map(|mapping| validate(mapping).err())
+        .collect();
+    let (existing, replaced) = if merge {
+        (current, Vec::new())
+    } else {
+        (Vec::new(), current)
+    };
+    let mut seen_paths: Vec<String> = existing
+        .iter()
+        .map(|mapping| mapping_path_key(target_path(mapping)))
+        .collect();
+    let mut seen_ids: Vec<String> = existing
+        .into_iter()
+        .map(|mut mapping| std::mem::take(id(&mut mapping)))
+        .collect();
+
+    let mut to_write = Vec::new();
+    let mut skipped = Vec::new();
+    for mut mapping in incoming {
+        let path_key = mapping_path_key(target_path(&mapping));
+        if seen_paths.contains(&path_key) {
+            skipped.push(mapping);
+            continue;
+        }
+        if seen_ids.contains(id(&mut mapping)) {
+            *id(&mut mapping) = crate::coding::db_new_id();
+        }
+        seen_paths.push(path_key);
+        seen_ids.push(id(&mut mapping).clone());
+        to_write.push(mapping);
+    }
+
+    MappingImportPlan {
+        to_write,
+        skipped,
+        replaced,
+        errors,
+    }
+}
+
+/// Write an import plan in one transaction, clearing `table` first unless merging.
+/// Returns (imported, skipped).
+pub(crate) fn write_mapping_import<M>(
+    state: &SqliteDbState,
+    table: DbTable,
+    plan: MappingImportPlan<M>,
+    merge: bool,
+    mapping_id: fn(&M) -> &str,
+    to_db_value: fn(&M) -> Value,
+) -> Result<(usize, usize), String> {
+    if let Some(error) = plan.errors.into_iter().next() {
+        return Err(error);
+    }
+
+    state.with_conn_mut(|conn| {
+        db_transaction(conn, |tx| {
+            if !merge {
+                db_delete_all(tx, table)?;
+            }
+            for mapping in &plan.to_write {
+                db_put(tx, table, mapping_id(mapping), &to_db_value(mapping))?;
+            }
+            Ok(())
+        })
+    })?;
+
+    Ok((plan.to_write.len(), plan.skipped.len()))
+}
+
+fn validate_mapping_fields(
+    kind: &str,
+    index: usize,
+    fields: [(&str, &str); 4],
+) -> Result<(), String> {
+    for (field, value) in fields {
+        if value.trim().is_empty() {
+            return Err(format!(
+                "{} mapping #{} has an empty '{}' field",
+                kind,
+                index + 1,
+                field
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_sync_mappings(json: &str) -> Result<SyncMappingsExport, String> {
+    let export: SyncMappingsExport =
+        serde_json::from_str(json).map_err(|e| format!("Invalid sync mappings JSON: {}", e))?;
+    if export.schema_version != SYNC_MAPPINGS_SCHEMA_VERSION {
+        return Err(format!(
+            "Unsupported sync mappings schema version: {}",
+            export.schema_version
+        ));
+    }
+    if export.wsl.is_none() && export.ssh.is_none() {
+        return Err("Sync mappings JSON contains neither WSL nor SSH mappings".to_string());
+    }
+    if let Some(set) = &export.wsl {
+        for (index, mapping) in set.mappings.iter().enumerate() {
+            validate_mapping_fields(
+                "WSL",
+                index,
+                [
+                    ("id", &mapping.id),
+                    ("module", &mapping.module),
+                    ("windowsPath", &mapping.windows_path),
+                    ("wslPath", &mapping.wsl_path),
+                ],
+            )?;
+        }
+    }
+    if let Some(set) = &export.ssh {
+        for (index, mapping) in set.mappings.iter().enumerate() {
+            validate_mapping_fields(
+                "SSH",
+                index,
+                [
+                    ("id", &mapping.id),
+                    ("module", &mapping.module),
+                    ("localPath", &mapping.local_path),
+                    ("remotePath", &mapping.remote_path),
+                ],
+            )?;
+        }
+    }
+    Ok(export)
+}
+
+/// Export WSL and SSH file mappings as JSON, without any connection secrets.
+#[tauri::command]
+pub async fn export_sync_mappings(state: State<'_, SqliteDbState>) -> Result<String, String> {
+    let hosts = ssh::export_connections(&state)?
+        .into_iter()
+        .map(|connection| SshHostReference {
+            name: connection.name,
+            host: connection.host,
+            port: connection.port,
+            username: connection.username,
+            auth_method: connection.auth_method,
+        })
+        .collect();
+    let export = SyncMappingsExport {
+        schema_version: SYNC_MAPPINGS_SCHEMA_VERSION,
+        exported_at: chrono::Local::now().to_rfc3339(),
+        wsl: Some(WslMappingSet {
+            mappings: wsl::export_file_mappings(&state)?,
+        }),
+        ssh: Some(SshMappingSet {
+            mappings: ssh::export_file_mappings(&state)?,
+            hosts,
+        }),
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
+
+/// Report what `apply_sync_mappings` would do, without writing.
+pub(crate) fn plan_sync_mappings(
+    state: &SqliteDbState,
+    export: &SyncMappingsExport,
+    merge: bool,
+) -> Result<PlannedChanges, String> {
+    let mut changes = PlannedChanges::default();
+    if let Some(set) = &export.wsl {
+        wsl::plan_file_mappings(state, set.mappings.clone(), merge)?.add_to(
+            "wsl_file_mapping",
+            |mapping| (mapping_path_key(&mapping.wsl_path), mapping.name.clone()),
+            &mut changes,
+        );
+    }
+    if let Some(set) = &export.ssh {
+        ssh::plan_file_mappings(state, set.mappings.clone(), merge)?.add_to(
+            "ssh_file_mapping",
+            |mapping| (mapping_path_key(&mapping.remote_path), mapping.name.clone()),
+            &mut changes,
+        );
+    }
+    Ok(changes)
+}
+
+/// Write the sections present in `export` and notify the WSL/SSH settings pages.
+pub(crate) fn apply_sync_mappings(
+    app: &tauri::AppHandle,
+    state: &SqliteDbState,
+    export: SyncMappingsExport,
+    merge: bool,
+) -> Result<SyncMappingsImportResult, String> {
+    let mut result = SyncMappingsImportResult::default();
+
+    if let Some(set) = export.wsl {
+        let (imported, skipped) = wsl::import_file_mappings(state, set.mappings, merge)?;
+        result.wsl_imported = imported;
+        result.wsl_skipped = skipped;
+        let _ = app.emit("wsl-config-changed", ());
+    }
+    if let Some(set) = export.ssh {
+        let (imported, skipped) = ssh::import_file_mappings(state, set.mappings, merge)?;
+        result.ssh_imported = imported;
+        result.ssh_skipped = skipped;
+        let _ = app.emit("ssh-config-changed", ());
+    }
+
+    Ok(result)
+}
+
+/// Import WSL/SSH file mappings. `merge` keeps existing mappings and skips incoming ones
+/// whose target path is already mapped; otherwise each section present replaces the current set.
+#[tauri::command]
+pub async fn import_sync_mappings(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    json: String,
+    merge: bool,
+) -> Result<SyncMappingsImportResult, String> {
+    let export = parse_sync_mappings(&json)?;
+    apply_sync_mappings(&app, &state, export, merge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mapping_path_key_ignores_trailing_separators_and_slash_style() {
+        assert_eq!(mapping_path_key("~/.claude/"), "~/.claude");
+        assert_eq!(mapping_path_key(" ~\\.claude "), "~/.claude");
+        assert_eq!(mapping_path_key("/"), "/");
+    }
+
+    #[test]
+    fn parse_sync_mappings_rejects_bad_shapes() {
+        assert!(parse_sync_mappings("[]").is_err());
+        assert!(parse_sync_mappings(
+            r#"{"schemaVersion":2,"exportedAt":"x","wsl":{"mappings":[]}}"#
+        )
+        .is_err());
+        assert!(parse_sync_mappings(r#"{"schemaVersion":1,"exportedAt":"x"}"#).is_err());
+
+        let empty_path = r#"{"schemaVersion":1,"exportedAt":"x","ssh":{"mappings":[{
+            "id":"m1","name":"Claude","module":"claude","localPath":"~/.claude/settings.json",
+            "remotePath":"","enabled":true,"isPattern":false,"isDirectory":false}]}}"#;
+        assert!(parse_sync_mappings(empty_path)
+            .unwrap_err()
+            .contains("remotePath"));
+
+        let valid = empty_path.replace(
+            r#""remotePath":"""#,
+            r#""remotePath":"~/.claude/settings.json""#,
+        );
+        let export = parse_sync_mappings(&valid).expect("valid export");
+        assert_eq!(export.ssh.expect("ssh section").mappings.len(), 1);
+    }
+}
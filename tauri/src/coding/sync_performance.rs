@@ -0,0 +1,174 @@
+//! Per-mapping throughput of the last WSL/SSH file sync run.
+//!
+//! Measurements are kept in memory (latest run per mapping) and reset on restart. Bytes are
+//! the size of the local source at sync time, so incremental or excluded content still counts;
+//! the figure is meant for spotting unusually slow mappings, not for exact transfer accounting.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use walkdir::WalkDir;
+
+/// Mappings slower than this are flagged, once they run long enough to measure reliably.
+const SLOW_BYTES_PER_SEC: f64 = 256.0 * 1024.0;
+const MIN_MEASURABLE_DURATION: Duration = Duration::from_secs(1);
+
+static LAST_MAPPING_PERF: LazyLock<Mutex<HashMap<String, MappingPerf>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingPerf {
+    pub target: String, // "wsl" | "ssh"
+    pub mapping_id: String,
+    pub name: String,
+    pub module: String,
+    pub source_bytes: u64,
+    pub duration_ms: u64,
+    pub bytes_per_sec: f64,
+    pub slow: bool,
+    pub measured_at: String,
+}
+
+/// Every regular file of a mapping's local source (single file, directory tree or glob
+/// pattern) with its size in bytes. Symlinks inside a directory tree are not followed.
+pub(crate) fn local_source_files(
+    local_path: &str,
+    is_pattern: bool,
+    is_directory: bool,
+) -> Vec<(PathBuf, u64)> {
+    let Ok(path) = crate::coding::expand_local_path(local_path) else {
+        return Vec::new();
+    };
+    let file_size = |entry: PathBuf| {
+        let metadata = std::fs::metadata(&entry).ok()?;
+        metadata.is_file().then(|| (entry, metadata.len()))
+    };
+    if is_pattern {
+        return glob::glob(&path)
+            .map(|paths| paths.flatten().filter_map(file_size).collect())
+            .unwrap_or_default();
+    }
+    if is_directory {
+        return WalkDir::new(Path::new(&path))
+            .follow_links(false)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                metadata
+                    .is_file()
+                    .then(|| (entry.into_path(), metadata.len()))
+            })
+            .collect();
+    }
+    file_size(PathBuf::from(path)).into_iter().collect()
+}
+
+/// Total size of a mapping's local source (single file, directory tree or glob pattern).
+pub(crate) fn local_source_bytes(local_path: &str, is_pattern: bool, is_directory: bool) -> u64 {
+    local_source_files(local_path, is_pattern, is_directory)
+        .iter()
+        .map(|(_, bytes)| bytes)
+        .sum()
+}
+
+fn build_mapping_perf(
+    target: &str,
+    mapping_id: &str,
+    name: &str,
+    module: &str,
+    source_bytes: u64,
+    elapsed: Duration,
+) -> MappingPerf {
+    let seconds = elapsed.as_secs_f64().max(0.001);
+    let bytes_per_sec = source_bytes as f64 / seconds;
+    MappingPerf {
+        target: target.to_string(),
+        mapping_id: mapping_id.to_string(),
+        name: name.to_string(),
+        module: module.to_string(),
+        source_bytes,
+        duration_ms: elapsed.as_millis() as u64,
+        bytes_per_sec,
+        slow: elapsed >= MIN_MEASURABLE_DURATION && bytes_per_sec < SLOW_BYTES_PER_SEC,
+        measured_at: chrono::Local::now().to_rfc3339(),
+    }
+}
+
+/// Record the latest sync measurement for a mapping, replacing the previous one.
+pub(crate) fn record_mapping_perf(
+    target: &str,
+    mapping_id: &str,
+    name: &str,
+    module: &str,
+    source_bytes: u64,
+    elapsed: Duration,
+) {
+    let perf = build_mapping_perf(target, mapping_id, name, module, source_bytes, elapsed);
+    if perf.slow {
+        log::info!(
+            "Slow {} sync mapping: id={}, name={}, bytes={}, duration_ms={}",
+            target,
+            mapping_id,
+            name,
+            perf.source_bytes,
+            perf.duration_ms
+        );
+    }
+    if let Ok(mut cache) = LAST_MAPPING_PERF.lock() {
+        cache.insert(format!("{}:{}", target, mapping_id), perf);
+    }
+}
+
+/// Last-run throughput per WSL/SSH mapping, slowest first.
+#[tauri::command]
+pub fn get_sync_performance() -> Result<Vec<MappingPerf>, String> {
+    let cache = LAST_MAPPING_PERF
+        .lock()
+        .map_err(|_| "Sync performance cache is poisoned".to_string())?;
+    let mut entries: Vec<MappingPerf> = cache.values().cloned().collect();
+    entries.sort_by(|a, b| a.bytes_per_sec.total_cmp(&b.bytes_per_sec));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_flag_requires_measurable_duration() {
+        let quick = build_mapping_perf("ssh", "m1", "tiny", "claude", 10, Duration::from_millis(5));
+        assert!(!quick.slow);
+
+        let slow = build_mapping_perf("ssh", "m2", "big", "claude", 1024, Duration::from_secs(2));
+        assert!(slow.slow);
+        assert_eq!(slow.duration_ms, 2000);
+
+        let fast = build_mapping_perf(
+            "wsl",
+            "m3",
+            "fast",
+            "codex",
+            10 * 1024 * 1024,
+            Duration::from_secs(2),
+        );
+        assert!(!fast.slow);
+    }
+
+    #[test]
+    fn local_source_bytes_sums_directory_files() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        std::fs::create_dir(temp.path().join("nested")).expect("create dir");
+        std::fs::write(temp.path().join("a.txt"), "12345").expect("write");
+        std::fs::write(temp.path().join("nested").join("b.txt"), "123").expect("write");
+        let path = temp.path().to_string_lossy().to_string();
+
+        assert_eq!(local_source_bytes(&path, false, true), 8);
+        let file = temp.path().join("a.txt").to_string_lossy().to_string();
+        assert_eq!(local_source_bytes(&file, false, false), 5);
+    }
+}
@@ -0,0 +1,486 @@
+//! TLS certificate inspection and the per-provider insecure TLS opt-in.
+//!
+//! Inspection connects straight to the provider host (proxy settings are not applied) with the
+//! same rustls root store as the HTTP client, and records the leaf certificate while the normal
+//! verifier runs. Verification is never relaxed there: a failed check aborts the handshake and
+//! is reported through `verified` / `verification_error` next to the certificate details.
+//!
+//! `meta.allowInsecureTls` is the only switch that disables certificate verification. It is set
+//! per provider through `set_provider_allow_insecure_tls` and only affects the model-fetch and
+//! connectivity-test clients built for that provider, which look it up by provider id.
+
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, RootCertStore, SignatureScheme};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{Emitter, State};
+
+use crate::db::helpers::{db_get, db_patch_fields};
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Provider `meta` key of the insecure TLS opt-in
+const ALLOW_INSECURE_TLS_META_KEY: &str = "allowInsecureTls";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsInfo {
+    pub host: String,
+    pub port: u16,
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub days_until_expiry: i64,
+    pub expired: bool,
+    pub self_signed: bool,
+    pub verified: bool,
+    pub verification_error: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CertificateFields {
+    subject: String,
+    issuer: String,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    self_signed: bool,
+}
+
+/// Delegates to the WebPKI verifier and keeps the leaf certificate and verdict it saw.
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    seen: Mutex<Option<(Vec<u8>, Result<(), String>)>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        );
+        if let Ok(mut seen) = self.seen.lock() {
+            let verdict = result.as_ref().map(|_| ()).map_err(ToString::to_string);
+            *seen = Some((end_entity.as_ref().to_vec(), verdict));
+        }
+        result
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+fn root_store() -> Arc<RootCertStore> {
+    static ROOTS: OnceLock<Arc<RootCertStore>> = OnceLock::new();
+    ROOTS
+        .get_or_init(|| {
+            let mut root_store = RootCertStore::empty();
+            root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+            let native = rustls_native_certs::load_native_certs();
+            let (_, ignored) = root_store.add_parsable_certificates(native.certs);
+            if ignored > 0 {
+                log::debug!("Skipped {ignored} native TLS certificates for TLS inspection");
+            }
+            Arc::new(root_store)
+        })
+        .clone()
+}
+
+/// Split one DER element into (tag, content, rest).
+fn der_element(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first_len, rest) = rest.split_first()?;
+    let (len, rest) = if first_len & 0x80 == 0 {
+        (first_len as usize, rest)
+    } else {
+        let count = (first_len & 0x7f) as usize;
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let len = rest[..count]
+            .iter()
+            .fold(0usize, |len, byte| (len << 8) | *byte as usize);
+        (len, &rest[count..])
+    };
+    if rest.len() < len {
+        return None;
+    }
+    Some((tag, &rest[..len], &rest[len..]))
+}
+
+fn der_children(mut content: &[u8]) -> Option<Vec<(u8, &[u8])>> {
+    let mut children = Vec::new();
+    while !content.is_empty() {
+        let (tag, value, rest) = der_element(content)?;
+        children.push((tag, value));
+        content = rest;
+    }
+    Some(children)
+}
+
+fn format_name(name: &[u8]) -> Option<String> {
+    let mut parts = Vec::new();
+    for (_, rdn) in der_children(name)? {
+        for (_, attribute) in der_children(rdn)? {
+            let children = der_children(attribute)?;
+            let [(0x06, oid), (_, value)] = children.as_slice() else {
+                continue;
+            };
+            let label = match *oid {
+                [0x55, 0x04, 0x03] => "CN",
+                [0x55, 0x04, 0x06] => "C",
+                [0x55, 0x04, 0x07] => "L",
+                [0x55, 0x04, 0x08] => "ST",
+                [0x55, 0x04, 0x0a] => "O",
+                [0x55, 0x04, 0x0b] => "OU",
+                _ => continue,
+            };
+            parts.push(format!("{label}={}", String::from_utf8_lossy(value)));
+        }
+    }
+    Some(parts.join(", "))
+}
+
+fn parse_time(tag: u8, value: &[u8]) -> Option<DateTime<Utc>> {
+    let text = std::str::from_utf8(value).ok()?.trim_end_matches('Z');
+    let full = match tag {
+        // UTCTime: YYMMDDHHMMSS, years 50-99 belong to the 1900s
+        0x17 => {
+            let century = if text.get(..2)?.parse::<u8>().ok()? < 50 {
+                "20"
+            } else {
+                "19"
+            };
+            format!("{century}{text}")
+        }
+        // GeneralizedTime: YYYYMMDDHHMMSS
+        0x18 => text.to_string(),
+        _ => return None,
+    };
+    NaiveDateTime::parse_from_str(&full, "%Y%m%d%H%M%S")
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+fn parse_certificate(der: &[u8]) -> Option<CertificateFields> {
+    let (_, certificate, _) = der_element(der)?;
+    let (_, tbs, _) = der_element(certificate)?;
+    let mut fields = der_children(tbs)?.into_iter();
+    let mut next = fields.next()?;
+    // Optional explicit [0] version
+    if next.0 == 0xa0 {
+        next = fields.next()?;
+    }
+    let _serial = next;
+    let _signature_algorithm = fields.next()?;
+    let (_, issuer) = fields.next()?;
+    let (_, validity) = fields.next()?;
+    let (_, subject) = fields.next()?;
+    let times = der_children(validity)?;
+    let [(not_before_tag, not_before), (not_after_tag, not_after)] = times.as_slice() else {
+        return None;
+    };
+
+    Some(CertificateFields {
+        subject: format_name(subject)?,
+        issuer: format_name(issuer)?,
+        not_before: parse_time(*not_before_tag, not_before)?,
+        not_after: parse_time(*not_after_tag, not_after)?,
+        self_signed: issuer == subject,
+    })
+}
+
+async fn inspect_tls(host: &str, port: u16) -> Result<TlsInfo, String> {
+    let inner = WebPkiServerVerifier::builder(root_store())
+        .build()
+        .map_err(|e| format!("Failed to build certificate verifier: {}", e))?;
+    let verifier = Arc::new(RecordingVerifier {
+        inner,
+        seen: Mutex::new(None),
+    });
+    let config = rustls::ClientConfig::builder()
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+    let server_name = ServerName::try_from(host.to_string())
+        .map_err(|e| format!("Invalid server name '{}': {}", host, e))?;
+
+    let stream = tokio::time::timeout(
+        CONNECT_TIMEOUT,
+        tokio::net::TcpStream::connect((host, port)),
+    )
+    .await
+    .map_err(|_| format!("Timed out connecting to {}:{}", host, port))?
+    .map_err(|e| format!("Failed to connect to {}:{}: {}", host, port, e))?;
+    let handshake = tokio::time::timeout(
+        CONNECT_TIMEOUT,
+        tokio_rustls::TlsConnector::from(Arc::new(config)).connect(server_name, stream),
+    )
+    .await
+    .map_err(|_| format!("Timed out during TLS handshake with {}:{}", host, port))?;
+
+    let seen = verifier
+        .seen
+        .lock()
+        .map_err(|_| "Certificate recorder lock poisoned".to_string())?
+        .take();
+    let Some((certificate, verdict)) = seen else {
+        let error = handshake.err().map(|e| e.to_string()).unwrap_or_default();
+        return Err(format!(
+            "TLS handshake failed before a certificate was received: {}",
+            error
+        ));
+    };
+    let fields = parse_certificate(&certificate)
+        .ok_or_else(|| "Failed to parse the server certificate".to_string())?;
+    let verification_error = match (verdict, handshake) {
+        (Err(error), _) => Some(error),
+        (Ok(()), Err(error)) => Some(error.to_string()),
+        (Ok(()), Ok(_)) => None,
+    };
+    let now = Utc::now();
+
+    Ok(TlsInfo {
+        host: host.to_string(),
+        port,
+        subject: fields.subject,
+        issuer: fields.issuer,
+        not_before: fields.not_before.to_rfc3339(),
+        not_after: fields.not_after.to_rfc3339(),
+        days_until_expiry: (fields.not_after - now).num_days(),
+        expired: fields.not_after < now,
+        self_signed: fields.self_signed,
+        verified: verification_error.is_none(),
+        verification_error,
+    })
+}
+
+/// Inspect the TLS certificate served at a Claude or Codex provider's base URL.
+/// Verification failures are reported in the result, never bypassed.
+#[tauri::command]
+pub async fn inspect_provider_tls(
+    state: State<'_, SqliteDbState>,
+    provider_id: String,
+) -> Result<TlsInfo, String> {
+    let base_url = crate::coding::model_validation::find_provider_base_url(&state, &provider_id)?;
+    let url = reqwest::Url::parse(&base_url)
+        .map_err(|e| format!("Invalid provider base URL '{}': {}", base_url, e))?;
+    if url.scheme() != "https" {
+        return Err(format!("Provider base URL is not HTTPS: {}", base_url));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| "Provider base URL has no host".to_string())?;
+    inspect_tls(host, url.port_or_known_default().unwrap_or(443)).await
+}
+
+/// Whether a provider's `meta` opted into skipping TLS certificate verification.
+pub(crate) fn allows_insecure_tls(meta: Option<&Value>) -> bool {
+    meta.and_then(|meta| meta.get(ALLOW_INSECURE_TLS_META_KEY))
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Whether the stored Claude, Codex or Gemini CLI provider `provider_id` opted into skipping
+/// TLS certificate verification. Ids of no such provider (e.g. OpenCode keys) never do.
+pub(crate) fn provider_allows_insecure_tls(
+    db: &SqliteDbState,
+    provider_id: &str,
+) -> Result<bool, String> {
+    db.with_conn(|conn| {
+        for table in [
+            DbTable::ClaudeProvider,
+            DbTable::CodexProvider,
+            DbTable::GeminiCliProvider,
+        ] {
+            if let Some(record) = db_get(conn, table, provider_id)? {
+                return Ok(allows_insecure_tls(record.get("meta")));
+            }
+        }
+        Ok(false)
+    })
+}
+
+fn meta_with_allow_insecure_tls(meta: Option<&Value>, allow: bool) -> Value {
+    let mut meta_object = meta.and_then(Value::as_object).cloned().unwrap_or_default();
+    if allow {
+        meta_object.insert(ALLOW_INSECURE_TLS_META_KEY.to_string(), Value::Bool(true));
+    } else {
+        meta_object.remove(ALLOW_INSECURE_TLS_META_KEY);
+    }
+    Value::Object(meta_object)
+}
+
+/// Opt a Claude, Codex or Gemini CLI provider in or out of skipping TLS certificate
+/// verification for its model fetch and connectivity tests. Never applies globally.
+#[tauri::command]
+pub async fn set_provider_allow_insecure_tls(
+    state: State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    tool: String,
+    provider_id: String,
+    allow: bool,
+) -> Result<(), String> {
+    let table = match tool.as_str() {
+        "claude" => DbTable::ClaudeProvider,
+        "codex" => DbTable::CodexProvider,
+        "geminicli" => DbTable::GeminiCliProvider,
+        other => return Err(format!("Insecure TLS is not supported for '{}'", other)),
+    };
+    state.with_conn(|conn| {
+        let record =
+            db_get(conn, table, &provider_id)?.ok_or_else(|| "Provider not found".to_string())?;
+        let meta = meta_with_allow_insecure_tls(record.get("meta"), allow);
+        db_patch_fields(conn, table, &provider_id, &[("meta", meta)]).map(|_| ())
+    })?;
+    if allow {
+        log::warn!(
+            "Insecure TLS ENABLED for {} provider '{}': certificate verification is skipped for its model fetch and connectivity tests",
+            tool,
+            provider_id
+        );
+    } else {
+        log::info!(
+            "Insecure TLS disabled for {} provider '{}'",
+            tool,
+            provider_id
+        );
+    }
+
+    let _ = app.emit("config-changed", "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![tag];
+        if content.len() < 0x80 {
+            encoded.push(content.len() as u8);
+        } else {
+            encoded.extend([0x82, (content.len() >> 8) as u8, content.len() as u8]);
+        }
+        encoded.extend_from_slice(content);
+        encoded
+    }
+
+    fn name(attributes: &[(&[u8], &str)]) -> Vec<u8> {
+        let rdns: Vec<u8> = attributes
+            .iter()
+            .flat_map(|(oid, value)| {
+                let attribute = [der(0x06, oid), der(0x0c, value.as_bytes())].concat();
+                der(0x31, &der(0x30, &attribute))
+            })
+            .collect();
+        der(0x30, &rdns)
+    }
+
+    fn certificate(issuer: &[u8], subject: &[u8]) -> Vec<u8> {
+        let tbs = [
+            der(0xa0, &der(0x02, &[2])),
+            der(0x02, &[0x01, 0x23]),
+            der(0x30, &der(0x06, &[0x2a, 0x86, 0x48])),
+            issuer.to_vec(),
+            der(
+                0x30,
+                &[der(0x17, b"240101000000Z"), der(0x18, b"20991231235959Z")].concat(),
+            ),
+            subject.to_vec(),
+            der(0x30, &[0u8; 140]),
+        ]
+        .concat();
+        der(
+            0x30,
+            &[der(0x30, &tbs), der(0x30, &[]), der(0x03, &[0])].concat(),
+        )
+    }
+
+    #[test]
+    fn certificate_names_validity_and_self_signed_are_parsed() {
+        let issuer = name(&[
+            (&[0x55, 0x04, 0x06], "US"),
+            (&[0x55, 0x04, 0x0a], "Corp CA"),
+        ]);
+        let subject = name(&[(&[0x55, 0x04, 0x03], "api.example.com")]);
+
+        let fields = parse_certificate(&certificate(&issuer, &subject)).unwrap();
+        assert_eq!(fields.subject, "CN=api.example.com");
+        assert_eq!(fields.issuer, "C=US, O=Corp CA");
+        assert_eq!(fields.not_before.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(fields.not_after.to_rfc3339(), "2099-12-31T23:59:59+00:00");
+        assert!(!fields.self_signed);
+
+        let self_signed = parse_certificate(&certificate(&subject, &subject)).unwrap();
+        assert!(self_signed.self_signed);
+    }
+
+    #[test]
+    fn insecure_tls_opt_in_is_stored_in_meta_and_removed_on_opt_out() {
+        let meta = json!({ "costMultiplier": "1" });
+        assert!(!allows_insecure_tls(Some(&meta)));
+
+        let enabled = meta_with_allow_insecure_tls(Some(&meta), true);
+        assert!(allows_insecure_tls(Some(&enabled)));
+        assert_eq!(enabled["costMultiplier"], "1");
+
+        let disabled = meta_with_allow_insecure_tls(Some(&enabled), false);
+        assert!(!allows_insecure_tls(Some(&disabled)));
+        assert_eq!(disabled, meta);
+
+        assert_eq!(
+            meta_with_allow_insecure_tls(None, true),
+            json!({ "allowInsecureTls": true })
+        );
+    }
+
+    #[test]
+    fn malformed_der_is_rejected() {
+        assert!(parse_certificate(&[]).is_none());
+        assert!(parse_certificate(&[0x30, 0x05, 0x01]).is_none());
+        assert_eq!(
+            parse_time(0x17, b"991231235959Z").unwrap().to_rfc3339(),
+            "1999-12-31T23:59:59+00:00"
+        );
+        assert!(parse_time(0x04, b"20240101000000Z").is_none());
+    }
+}
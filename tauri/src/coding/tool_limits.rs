@@ -0,0 +1,330 @@
+//! Check the configs the app writes for a tool against that tool's known limits.
+//!
+//! Limits live in `TOOL_LIMITS` so a new one is a table row, not new code. Checks read the
+//! config an apply would write (the merged Claude settings for one provider, the MCP servers
+//! enabled for the tool), so problems show up before the tool rejects the file. The Claude
+//! apply and MCP sync paths log the same issues as warnings.
+//!
+//! MCP tool counts come from a server's explicit tool allowlist (`tools` / `includeTools`).
+//! The app does not connect to servers to discover their tools, so a server without an
+//! allowlist counts as one tool and the total is a lower bound.
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use crate::coding::claude_code::commands::{
+    build_claude_settings_for_provider, list_claude_providers_from_sqlite,
+};
+use crate::coding::mcp::mcp_store;
+use crate::coding::mcp::types::McpServer;
+use crate::db::SqliteDbState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LimitKind {
+    /// Length of one `env` value written into the tool's settings
+    EnvValueLength,
+    /// Number of MCP tools exposed by the servers enabled for the tool
+    McpToolCount,
+    /// Length of an MCP server name
+    McpServerNameLength,
+}
+
+impl LimitKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::EnvValueLength => "env_value_length",
+            Self::McpToolCount => "mcp_tool_count",
+            Self::McpServerNameLength => "mcp_server_name_length",
+        }
+    }
+}
+
+struct ToolLimit {
+    tool: &'static str,
+    kind: LimitKind,
+    limit: usize,
+    reason: &'static str,
+}
+
+const TOOL_LIMITS: &[ToolLimit] = &[
+    ToolLimit {
+        tool: "claude_code",
+        kind: LimitKind::EnvValueLength,
+        limit: 32_767,
+        reason: "Windows rejects environment variables longer than 32767 characters",
+    },
+    ToolLimit {
+        tool: "claude_code",
+        kind: LimitKind::McpServerNameLength,
+        limit: 64,
+        reason: "MCP tool names include the server name and are capped at 64 characters",
+    },
+    ToolLimit {
+        tool: "cursor",
+        kind: LimitKind::McpToolCount,
+        limit: 40,
+        reason: "Cursor only sends the first 40 MCP tools to the model",
+    },
+    ToolLimit {
+        tool: "github_copilot",
+        kind: LimitKind::McpToolCount,
+        limit: 128,
+        reason: "GitHub Copilot accepts at most 128 tools per request",
+    },
+];
+
+/// Server config keys that list the MCP tools a client may use
+const MCP_TOOL_ALLOWLIST_KEYS: [&str; 2] = ["tools", "includeTools"];
+
+/// One config value over a tool limit
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitIssue {
+    pub tool: String,
+    /// "env_value_length" | "mcp_tool_count" | "mcp_server_name_length"
+    pub kind: String,
+    pub limit: usize,
+    pub actual: usize,
+    /// The env key, MCP server, or tool that is over the limit
+    pub item: String,
+    pub message: String,
+}
+
+/// Inputs the limits are checked against, gathered once per call.
+#[derive(Default)]
+struct LimitInputs {
+    /// (env key, value length) of the settings about to be written
+    env_values: Vec<(String, usize)>,
+    /// (server name, MCP tool count) of the servers enabled for the tool
+    mcp_servers: Vec<(String, usize)>,
+}
+
+fn has_limit(tool: &str, kinds: &[LimitKind]) -> bool {
+    TOOL_LIMITS
+        .iter()
+        .any(|limit| limit.tool == tool && kinds.contains(&limit.kind))
+}
+
+fn collect_issues(tool: &str, inputs: &LimitInputs) -> Vec<LimitIssue> {
+    let mut issues = Vec::new();
+    for limit in TOOL_LIMITS.iter().filter(|limit| limit.tool == tool) {
+        let mut push = |actual: usize, item: String| {
+            if actual > limit.limit {
+                issues.push(LimitIssue {
+                    tool: tool.to_string(),
+                    kind: limit.kind.as_str().to_string(),
+                    limit: limit.limit,
+                    actual,
+                    item,
+                    message: limit.reason.to_string(),
+                });
+            }
+        };
+        match limit.kind {
+            LimitKind::EnvValueLength => {
+                for (key, len) in &inputs.env_values {
+                    push(*len, key.clone());
+                }
+            }
+            LimitKind::McpToolCount => push(
+                inputs.mcp_servers.iter().map(|(_, count)| count).sum(),
+                tool.to_string(),
+            ),
+            LimitKind::McpServerNameLength => {
+                for (name, _) in &inputs.mcp_servers {
+                    push(name.chars().count(), name.clone());
+                }
+            }
+        }
+    }
+    issues
+}
+
+fn env_values(settings: &Value) -> Vec<(String, usize)> {
+    settings
+        .get("env")
+        .and_then(Value::as_object)
+        .map(|env| {
+            env.iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.chars().count())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// MCP tools a server exposes: its allowlist length, or one when it has none (or allows "*").
+fn mcp_server_tool_count(server: &McpServer) -> usize {
+    MCP_TOOL_ALLOWLIST_KEYS
+        .iter()
+        .find_map(|key| server.server_config.get(*key)?.as_array())
+        .filter(|tools| !tools.iter().any(|tool| tool.as_str() == Some("*")))
+        .map(|tools| tools.len())
+        .unwrap_or(1)
+        .max(1)
+}
+
+async fn enabled_mcp_servers(
+    db: &SqliteDbState,
+    tool: &str,
+) -> Result<Vec<(String, usize)>, String> {
+    Ok(mcp_store::get_mcp_servers(db)
+        .await?
+        .into_iter()
+        .filter(|server| server.enabled_tools.iter().any(|enabled| enabled == tool))
+        .map(|server| {
+            let count = mcp_server_tool_count(&server);
+            (server.name, count)
+        })
+        .collect())
+}
+
+fn log_issues(issues: &[LimitIssue]) {
+    for issue in issues {
+        log::warn!(
+            "{} config is over a limit: {} {} > {} ({})",
+            issue.tool,
+            issue.item,
+            issue.actual,
+            issue.limit,
+            issue.message
+        );
+    }
+}
+
+/// Log the limit issues of Claude settings that are about to be written.
+pub(crate) fn warn_claude_settings_limits(settings: &Value) {
+    let inputs = LimitInputs {
+        env_values: env_values(settings),
+        ..Default::default()
+    };
+    log_issues(&collect_issues("claude_code", &inputs));
+}
+
+/// Log the limit issues of the MCP servers enabled for `tool` before they are synced.
+/// Reading the servers can fail; the sync itself reports that.
+pub(crate) async fn warn_mcp_limits(db: &SqliteDbState, tool: &str) {
+    if !has_limit(
+        tool,
+        &[LimitKind::McpToolCount, LimitKind::McpServerNameLength],
+    ) {
+        return;
+    }
+    if let Ok(mcp_servers) = enabled_mcp_servers(db, tool).await {
+        let inputs = LimitInputs {
+            mcp_servers,
+            ..Default::default()
+        };
+        log_issues(&collect_issues(tool, &inputs));
+    }
+}
+
+/// Report every value of `tool`'s target config that is over one of its known limits. `tool`
+/// is an MCP tool key such as "claude_code" or "cursor". For Claude Code the checked settings
+/// are the ones applying `provider_id` (default: the applied provider) would write. Tools
+/// without known limits return no issues.
+#[tauri::command]
+pub async fn check_tool_limits(
+    state: State<'_, SqliteDbState>,
+    tool: String,
+    provider_id: Option<String>,
+) -> Result<Vec<LimitIssue>, String> {
+    let db = state.db();
+    let mut inputs = LimitInputs::default();
+    if tool == "claude_code" && has_limit(&tool, &[LimitKind::EnvValueLength]) {
+        let provider_id = match provider_id {
+            Some(provider_id) => Some(provider_id),
+            None => list_claude_providers_from_sqlite(db)?
+                .into_iter()
+                .find(|provider| provider.is_applied)
+                .map(|provider| provider.id),
+        };
+        if let Some(provider_id) = provider_id {
+            let settings = build_claude_settings_for_provider(db, &provider_id, None, None).await?;
+            inputs.env_values = env_values(&settings);
+        }
+    }
+    if has_limit(
+        &tool,
+        &[LimitKind::McpToolCount, LimitKind::McpServerNameLength],
+    ) {
+        inputs.mcp_servers = enabled_mcp_servers(db, &tool).await?;
+    }
+    Ok(collect_issues(&tool, &inputs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_issues_report_limit_actual_and_item() {
+        let settings = serde_json::json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "x".repeat(40_000),
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+            }
+        });
+        let inputs = LimitInputs {
+            env_values: env_values(&settings),
+            mcp_servers: vec![("a".repeat(70), 1), ("short".to_string(), 1)],
+        };
+
+        let issues = collect_issues("claude_code", &inputs);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].kind, "env_value_length");
+        assert_eq!(issues[0].item, "ANTHROPIC_AUTH_TOKEN");
+        assert_eq!((issues[0].limit, issues[0].actual), (32_767, 40_000));
+        assert_eq!(issues[1].kind, "mcp_server_name_length");
+        assert_eq!(issues[1].actual, 70);
+
+        let many_tools = LimitInputs {
+            mcp_servers: vec![("github".to_string(), 30), ("docs".to_string(), 11)],
+            ..Default::default()
+        };
+        let cursor = collect_issues("cursor", &many_tools);
+        assert_eq!(cursor.len(), 1);
+        assert_eq!((cursor[0].limit, cursor[0].actual), (40, 41));
+        assert!(collect_issues("codex", &many_tools).is_empty());
+    }
+
+    #[test]
+    fn mcp_tool_count_uses_the_allowlist_or_counts_one() {
+        let server = |config: Value| McpServer {
+            id: "1".to_string(),
+            name: "github".to_string(),
+            server_type: "stdio".to_string(),
+            server_config: config,
+            enabled_tools: vec!["cursor".to_string()],
+            sync_details: None,
+            description: None,
+            user_group: None,
+            user_note: None,
+            tags: Vec::new(),
+            timeout: None,
+            sort_index: 0,
+            created_at: 0,
+            updated_at: 0,
+        };
+
+        assert_eq!(
+            mcp_server_tool_count(&server(serde_json::json!({
+                "command": "npx",
+                "tools": ["search", "create_issue", "get_issue"],
+            }))),
+            3
+        );
+        assert_eq!(
+            mcp_server_tool_count(&server(serde_json::json!({ "includeTools": ["read"] }))),
+            1
+        );
+        assert_eq!(
+            mcp_server_tool_count(&server(serde_json::json!({ "tools": ["*"] }))),
+            1
+        );
+        assert_eq!(
+            mcp_server_tool_count(&server(serde_json::json!({ "command": "npx" }))),
+            1
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Installed AI CLI detection
+//!
+//! Resolves each known CLI the same way launches do (PATH, then common install dirs and
+//! node-manager globals) and reads its version. Version probes spawn a process, so results
+//! are cached per binary path for a few minutes.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::coding::cli_resolver::{
+    build_local_tokio_command, find_cli_in_common_dirs, find_cli_on_path,
+};
+
+const VERSION_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A CLI to detect and the argument lists to try, in order, to make it print its version.
+struct KnownCli {
+    tool: &'static str,
+    command: &'static str,
+    version_args: &'static [&'static [&'static str]],
+}
+
+const VERSION_FLAG: &[&[&str]] = &[&["--version"]];
+
+const KNOWN_CLIS: &[KnownCli] = &[
+    KnownCli {
+        tool: "claude_code",
+        command: "claude",
+        version_args: VERSION_FLAG,
+    },
+    KnownCli {
+        tool: "codex",
+        command: "codex",
+        version_args: VERSION_FLAG,
+    },
+    KnownCli {
+        tool: "grok",
+        command: "grok",
+        version_args: VERSION_FLAG,
+    },
+    KnownCli {
+        tool: "gemini_cli",
+        command: "gemini",
+        version_args: VERSION_FLAG,
+    },
+    KnownCli {
+        tool: "qwen_code",
+        command: "qwen",
+        version_args: VERSION_FLAG,
+    },
+    KnownCli {
+        tool: "opencode",
+        command: "opencode",
+        version_args: &[&["--version"], &["version"]],
+    },
+    KnownCli {
+        tool: "openclaw",
+        command: "openclaw",
+        version_args: VERSION_FLAG,
+    },
+    KnownCli {
+        tool: "pi",
+        command: "pi",
+        version_args: VERSION_FLAG,
+    },
+    KnownCli {
+        tool: "amp",
+        command: "amp",
+        version_args: VERSION_FLAG,
+    },
+    KnownCli {
+        tool: "goose",
+        command: "goose",
+        version_args: &[&["--version"], &["version"]],
+    },
+    KnownCli {
+        tool: "droid",
+        command: "droid",
+        version_args: &[&["--version"], &["-v"]],
+    },
+    KnownCli {
+        tool: "cursor",
+        command: "cursor-agent",
+        version_args: &[&["--version"], &["-v"]],
+    },
+];
+
+static VERSION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z.]+)?").expect("valid version regex")
+});
+
+static VERSION_CACHE: LazyLock<Mutex<HashMap<PathBuf, (Instant, Option<String>)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Detection result for one CLI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledToolInfo {
+    pub tool: String,
+    pub command: String,
+    pub installed: bool,
+    /// True when the CLI was found on PATH rather than only in a known install dir
+    pub on_path: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// None when the CLI is missing or printed no recognizable version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// First version-looking token of a CLI's output, e.g. "2.1.126" from "2.1.126 (Claude Code)".
+fn parse_version(output: &str) -> Option<String> {
+    VERSION_PATTERN
+        .find(output)
+        .map(|found| found.as_str().to_string())
+}
+
+fn cached_version(path: &Path) -> Option<Option<String>> {
+    let cache = VERSION_CACHE.lock().ok()?;
+    let (checked_at, version) = cache.get(path)?;
+    (checked_at.elapsed() < VERSION_CACHE_TTL).then(|| version.clone())
+}
+
+/// Run each argument list until stdout or stderr contains a version.
+async fn probe_version(path: &Path, version_args: &[&[&str]]) -> Option<String> {
+    for args in version_args {
+        let mut command = build_local_tokio_command(path);
+        command
+            .args(*args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let output = match tokio::time::timeout(VERSION_PROBE_TIMEOUT, command.output()).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(error)) => {
+                log::debug!("Failed to run {} {:?}: {}", path.display(), args, error);
+                continue;
+            }
+            Err(_) => {
+                log::debug!("Timed out running {} {:?}", path.display(), args);
+                continue;
+            }
+        };
+        let version = parse_version(&String::from_utf8_lossy(&output.stdout))
+            .or_else(|| parse_version(&String::from_utf8_lossy(&output.stderr)));
+        if version.is_some() {
+            return version;
+        }
+    }
+    None
+}
+
+async fn detect_cli(cli: &KnownCli) -> InstalledToolInfo {
+    let on_path = find_cli_on_path(cli.command);
+    let path = on_path
+        .clone()
+        .or_else(|| find_cli_in_common_dirs(cli.command));
+    let version = match &path {
+        Some(path) => match cached_version(path) {
+            Some(version) => version,
+            None => {
+                let version = probe_version(path, cli.version_args).await;
+                if let Ok(mut cache) = VERSION_CACHE.lock() {
+                    cache.insert(path.clone(), (Instant::now(), version.clone()));
+                }
+                version
+            }
+        },
+        None => None,
+    };
+    InstalledToolInfo {
+        tool: cli.tool.to_string(),
+        command: cli.command.to_string(),
+        installed: path.is_some(),
+        on_path: on_path.is_some(),
+        path: path.map(|path| path.to_string_lossy().to_string()),
+        version,
+    }
+}
+
+/// Report, for every known AI CLI, whether it is installed, where, and its version.
+#[tauri::command]
+pub async fn detect_installed_tools() -> Result<Vec<InstalledToolInfo>, String> {
+    Ok(futures_util::future::join_all(KNOWN_CLIS.iter().map(detect_cli)).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_finds_the_version_token() {
+        assert_eq!(
+            parse_version("2.1.126 (Claude Code)").as_deref(),
+            Some("2.1.126")
+        );
+        assert_eq!(
+            parse_version("codex-cli 0.46.0\n").as_deref(),
+            Some("0.46.0")
+        );
+        assert_eq!(
+            parse_version("goose v1.9.0-beta.2").as_deref(),
+            Some("1.9.0-beta.2")
+        );
+        assert_eq!(parse_version("error: unknown flag --version"), None);
+    }
+}
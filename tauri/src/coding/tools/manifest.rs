@@ -0,0 +1,253 @@
+//! Register custom tools from a shared manifest file
+//!
+//! A manifest is a JSON file listing one or more tool definitions so a team can share them
+//! instead of re-entering each field by hand. Every entry is validated before anything is
+//! written; one bad entry rejects the whole manifest with a per-tool error list.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use tauri::State;
+
+use super::custom_store;
+use super::path_utils::{normalize_path, to_storage_path};
+use super::types::{now_ms, CustomTool};
+use crate::db::SqliteDbState;
+
+/// Manifest file layout: `{ "tools": [ ... ] }`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolManifest {
+    pub tools: Vec<ToolManifestEntry>,
+}
+
+/// One tool definition in a manifest (camelCase keys)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ToolManifestEntry {
+    pub key: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub relative_detect_dir: Option<String>,
+    // Skills related (optional)
+    #[serde(default)]
+    pub relative_skills_dir: Option<String>,
+    #[serde(default)]
+    pub force_copy: Option<bool>,
+    // MCP related (optional, all three or none)
+    #[serde(default)]
+    pub mcp_config_path: Option<String>,
+    #[serde(default)]
+    pub mcp_config_format: Option<String>,
+    #[serde(default)]
+    pub mcp_field: Option<String>,
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn storage_path(value: &str) -> String {
+    to_storage_path(&normalize_path(value))
+}
+
+/// Validate one entry and merge it over the existing record, if any.
+/// Fields the entry leaves out keep their stored values, matching the Skills/MCP save paths.
+fn build_tool_from_entry(
+    entry: &ToolManifestEntry,
+    existing: Option<&CustomTool>,
+    created_at: i64,
+) -> Result<CustomTool, String> {
+    let key = entry.key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err("Key must contain only letters, numbers, and underscores".to_string());
+    }
+    if custom_store::is_builtin_tool_key(key) {
+        return Err(format!("Key '{}' conflicts with a built-in tool", key));
+    }
+    let display_name = entry.display_name.trim();
+    if display_name.is_empty() {
+        return Err("Display name is required".to_string());
+    }
+
+    let skills_dir = non_empty(&entry.relative_skills_dir).map(storage_path);
+    let mcp_path = non_empty(&entry.mcp_config_path).map(storage_path);
+    let mcp_format = non_empty(&entry.mcp_config_format).map(str::to_lowercase);
+    let mcp_field = non_empty(&entry.mcp_field).map(str::to_string);
+    let mcp_fields_set = [
+        mcp_path.is_some(),
+        mcp_format.is_some(),
+        mcp_field.is_some(),
+    ];
+    if mcp_fields_set.iter().any(|set| *set) && !mcp_fields_set.iter().all(|set| *set) {
+        return Err(
+            "mcpConfigPath, mcpConfigFormat and mcpField must be given together".to_string(),
+        );
+    }
+    if let Some(format) = mcp_format.as_deref() {
+        if format != "json" && format != "toml" && format != "jsonc" {
+            return Err("MCP config format must be 'json', 'jsonc' or 'toml'".to_string());
+        }
+    }
+    if skills_dir.is_none() && mcp_path.is_none() {
+        return Err("Tool must define relativeSkillsDir or the MCP config fields".to_string());
+    }
+
+    let mut tool = existing.cloned().unwrap_or_else(|| CustomTool {
+        key: key.to_string(),
+        display_name: String::new(),
+        relative_skills_dir: None,
+        relative_detect_dir: None,
+        force_copy: false,
+        mcp_config_path: None,
+        mcp_config_format: None,
+        mcp_field: None,
+        created_at,
+    });
+    tool.display_name = display_name.to_string();
+    if let Some(detect_dir) = non_empty(&entry.relative_detect_dir) {
+        tool.relative_detect_dir = Some(storage_path(detect_dir));
+    }
+    if skills_dir.is_some() {
+        tool.relative_skills_dir = skills_dir;
+        tool.force_copy = entry.force_copy.unwrap_or(false);
+    }
+    if mcp_path.is_some() {
+        tool.mcp_config_path = mcp_path;
+        tool.mcp_config_format = mcp_format;
+        tool.mcp_field = mcp_field;
+    }
+    Ok(tool)
+}
+
+/// Validate every manifest entry against the stored tools.
+/// Returns the merged tools to save, or one error line per failing entry.
+fn plan_manifest_tools(
+    manifest: &ToolManifest,
+    existing_tools: &[CustomTool],
+    created_at: i64,
+) -> Result<Vec<CustomTool>, Vec<String>> {
+    if manifest.tools.is_empty() {
+        return Err(vec!["Manifest contains no tools".to_string()]);
+    }
+
+    let mut seen_keys = HashSet::new();
+    let mut tools = Vec::new();
+    let mut errors = Vec::new();
+    for (index, entry) in manifest.tools.iter().enumerate() {
+        let key = entry.key.trim();
+        let label = if key.is_empty() {
+            format!("tools[{}]", index)
+        } else {
+            format!("tools[{}] '{}'", index, key)
+        };
+        if !key.is_empty() && !seen_keys.insert(key.to_string()) {
+            errors.push(format!("{}: duplicate key in manifest", label));
+            continue;
+        }
+        let existing = existing_tools.iter().find(|tool| tool.key == key);
+        match build_tool_from_entry(entry, existing, created_at) {
+            Ok(tool) => tools.push(tool),
+            Err(error) => errors.push(format!("{}: {}", label, error)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(tools)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Register or update custom tools from a JSON manifest file.
+/// Nothing is saved unless every entry is valid; the error lists each failing entry.
+#[tauri::command]
+pub async fn register_tools_from_manifest(
+    state: State<'_, SqliteDbState>,
+    path: String,
+) -> Result<Vec<CustomTool>, String> {
+    let manifest_path = crate::coding::expand_local_path(path.trim())?;
+    let content = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read tool manifest {}: {}", manifest_path, e))?;
+    let manifest: ToolManifest = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse tool manifest {}: {}", manifest_path, e))?;
+
+    let existing_tools = custom_store::get_custom_tools(&state).await?;
+    let tools = plan_manifest_tools(&manifest, &existing_tools, now_ms())
+        .map_err(|errors| format!("Invalid tool manifest:\n{}", errors.join("\n")))?;
+    for tool in &tools {
+        custom_store::save_custom_tool(&state, tool).await?;
+    }
+    log::info!(
+        "Registered {} custom tool(s) from manifest {}",
+        tools.len(),
+        manifest_path
+    );
+    Ok(tools)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> ToolManifest {
+        serde_json::from_str(json).expect("parse manifest")
+    }
+
+    #[test]
+    fn manifest_entries_merge_over_existing_tools() {
+        let existing = CustomTool {
+            key: "team_cli".to_string(),
+            display_name: "Old".to_string(),
+            relative_skills_dir: Some("~/.team/skills".to_string()),
+            relative_detect_dir: Some("~/.team".to_string()),
+            force_copy: true,
+            mcp_config_path: None,
+            mcp_config_format: None,
+            mcp_field: None,
+            created_at: 1,
+        };
+        let manifest = parse(
+            r#"{ "tools": [
+                { "key": "team_cli", "displayName": "Team CLI",
+                  "mcpConfigPath": "~/.team/mcp.json", "mcpConfigFormat": "JSON", "mcpField": "mcpServers" },
+                { "key": "other", "displayName": "Other", "relativeSkillsDir": "~/.other/skills" }
+            ] }"#,
+        );
+
+        let tools = plan_manifest_tools(&manifest, &[existing], 5).expect("valid manifest");
+
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].display_name, "Team CLI");
+        assert_eq!(
+            tools[0].relative_skills_dir.as_deref(),
+            Some("~/.team/skills")
+        );
+        assert!(tools[0].force_copy);
+        assert_eq!(tools[0].mcp_config_format.as_deref(), Some("json"));
+        assert_eq!(tools[0].created_at, 1);
+        assert_eq!(tools[1].created_at, 5);
+        assert!(tools[1].mcp_config_path.is_none());
+    }
+
+    #[test]
+    fn invalid_entries_are_reported_per_tool() {
+        let manifest = parse(
+            r#"{ "tools": [
+                { "key": "claude_code", "displayName": "Clash", "relativeSkillsDir": "~/.x" },
+                { "key": "bad-key", "displayName": "Bad", "relativeSkillsDir": "~/.x" },
+                { "key": "partial", "displayName": "Partial", "mcpConfigPath": "~/.p/mcp.json" },
+                { "key": "dup", "displayName": "Dup", "relativeSkillsDir": "~/.d" },
+                { "key": "dup", "displayName": "Dup 2", "relativeSkillsDir": "~/.d" }
+            ] }"#,
+        );
+
+        let errors = plan_manifest_tools(&manifest, &[], 5).unwrap_err();
+
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].starts_with("tools[0] 'claude_code': Key 'claude_code' conflicts"));
+        assert!(errors[1].starts_with("tools[1] 'bad-key': Key must contain"));
+        assert!(errors[2].starts_with("tools[2] 'partial': mcpConfigPath"));
+        assert_eq!(errors[3], "tools[4] 'dup': duplicate key in manifest");
+    }
+}
@@ -5,12 +5,15 @@
 
 pub mod builtin;
 pub mod claude_plugins;
+pub mod cli_versions;
 pub mod custom_store;
 pub mod detection;
+pub mod manifest;
 pub mod path_utils;
 pub mod types;
 
 pub use builtin::*;
 pub use detection::*;
+pub use manifest::*;
 pub use path_utils::*;
 pub use types::*;
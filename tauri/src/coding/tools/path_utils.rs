@@ -221,3 +221,22 @@ pub fn to_platform_path(p: &str) -> String {
         p.replace('/', &std::path::MAIN_SEPARATOR.to_string())
     }
 }
+
+/// Build a key for comparing resolved paths: separators are unified, trailing
+/// separators dropped, and case folded when the filesystem is case-insensitive.
+pub fn path_compare_key(path: &str, case_insensitive: bool) -> String {
+    let normalized = path.trim().replace('\\', "/");
+    let trimmed = normalized.trim_end_matches('/');
+    let key = if trimmed.is_empty() { "/" } else { trimmed };
+    if case_insensitive {
+        key.to_lowercase()
+    } else {
+        key.to_string()
+    }
+}
+
+/// [`path_compare_key`] using the current platform's case sensitivity
+/// (case-insensitive on Windows).
+pub fn platform_path_compare_key(path: &std::path::Path) -> String {
+    path_compare_key(&path.to_string_lossy(), cfg!(windows))
+}
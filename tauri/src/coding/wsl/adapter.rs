@@ -1,5 +1,5 @@
 use super::super::db_id;
-use super::types::{FileMapping, WSLSyncConfig};
+use super::types::{FileMapping, WSLDistroTarget, WSLSyncConfig};
 use crate::coding::config_cleanup;
 use chrono::Local;
 use serde_json::{json, Value};
@@ -20,35 +20,56 @@ pub fn config_from_db_value(value: Value, file_mappings: Vec<FileMapping>) -> WS
             .and_then(|v| v.as_str())
             .unwrap_or("Ubuntu")
             .to_string(),
+        distros: distro_targets_from_db_value(&value),
         // sync_mcp and sync_skills are always true (no UI to toggle them)
         sync_mcp: true,
         sync_skills: true,
         file_mappings,
         last_sync_time: value
             .get("last_sync_time")
-            .or_else(|| value.get("lastSyncTime"))
             .and_then(|v| v.as_str())
             .map(String::from),
         last_sync_status: value
             .get("last_sync_status")
-            .or_else(|| value.get("lastSyncStatus"))
             .and_then(|v| v.as_str())
             .unwrap_or("never")
             .to_string(),
         last_sync_error: value
             .get("last_sync_error")
-            .or_else(|| value.get("lastSyncError"))
             .and_then(|v| v.as_str())
             .map(String::from),
         module_statuses: vec![],
     }
 }
 
+fn distro_targets_from_db_value(value: &Value) -> Vec<WSLDistroTarget> {
+    value
+        .get("distros")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let name = item.get("name").and_then(Value::as_str)?.trim();
+                    if name.is_empty() {
+                        return None;
+                    }
+                    Some(WSLDistroTarget {
+                        name: name.to_string(),
+                        enabled: item.get("enabled").and_then(Value::as_bool).unwrap_or(true),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Convert WSLSyncConfig to database Value
 pub fn config_to_db_value(config: &WSLSyncConfig) -> Value {
     json!({
         "enabled": config.enabled,
         "distro": config.distro,
+        "distros": config.distros,
     })
 }
 
@@ -61,7 +82,6 @@ fn cleanup_paths_from_db_value(
 ) -> Vec<String> {
     let paths = value
         .get("cleanup_paths")
-        .or_else(|| value.get("cleanupPaths"))
         .and_then(Value::as_array)
         .map(|items| {
             items
@@ -87,24 +107,20 @@ pub fn mapping_from_db_value(value: Value) -> FileMapping {
     let id = db_id::db_extract_id(&value);
     let windows_path = value
         .get("windows_path")
-        .or_else(|| value.get("windowsPath"))
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
     let wsl_path = value
         .get("wsl_path")
-        .or_else(|| value.get("wslPath"))
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
     let is_pattern = value
         .get("is_pattern")
-        .or_else(|| value.get("isPattern"))
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
     let is_directory = value
         .get("is_directory")
-        .or_else(|| value.get("isDirectory"))
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
     let cleanup_paths =
@@ -158,9 +174,35 @@ pub fn mapping_to_db_value(mapping: &FileMapping) -> Value {
 
 #[cfg(test)]
 mod tests {
-    use super::{mapping_from_db_value, mapping_to_db_value};
+    use super::{config_from_db_value, mapping_from_db_value, mapping_to_db_value};
     use serde_json::json;
 
+    #[test]
+    fn config_target_distros_fall_back_to_primary_distro() {
+        let legacy = config_from_db_value(json!({ "enabled": true, "distro": "Ubuntu" }), vec![]);
+        assert!(legacy.distros.is_empty());
+        assert_eq!(legacy.target_distro_names(), vec!["Ubuntu".to_string()]);
+
+        let config = config_from_db_value(
+            json!({
+                "distro": "Ubuntu",
+                "distros": [
+                    { "name": "Ubuntu", "enabled": true },
+                    { "name": " ", "enabled": true },
+                    { "name": "Debian", "enabled": true },
+                    { "name": "Alpine", "enabled": false },
+                    { "name": "Debian" }
+                ]
+            }),
+            vec![],
+        );
+        assert_eq!(config.distros.len(), 4);
+        assert_eq!(
+            config.target_distro_names(),
+            vec!["Ubuntu".to_string(), "Debian".to_string()]
+        );
+    }
+
     #[test]
     fn json_or_toml_file_mapping_persists_cleanup_paths() {
         let mapping = mapping_from_db_value(json!({
@@ -1,8 +1,8 @@
 use super::types::{
-    FileMapping, SyncProgress, SyncResult, WSLDetectResult, WSLErrorResult, WSLStatusResult,
-    WSLSyncConfig,
+    FileMapping, SyncProgress, SyncResult, WSLDetectResult, WSLDistroStatus, WSLDistroTarget,
+    WSLErrorResult, WSLStatusResult, WSLSyncConfig,
 };
-use super::{adapter, sync};
+use super::{adapter, status, sync};
 use crate::coding::claude_code::plugin_metadata_sync;
 use crate::coding::codex::constants::AI_TOOLBOX_CODEX_MODEL_CATALOG_FILENAME;
 use crate::coding::config_cleanup;
@@ -14,6 +14,9 @@ use crate::coding::proxy_gateway::{
     types::ProxyGatewaySettings,
 };
 use crate::coding::runtime_location;
+use crate::coding::sync_mappings::{self, MappingImportPlan};
+use crate::coding::sync_performance;
+use crate::coding::sync_trace;
 use crate::db::helpers::{db_delete, db_delete_all, db_get, db_list, db_put};
 use crate::db::schema::{DbTable, OrderDirection, OrderField, OrderSpec};
 use crate::db::SqliteDbState;
@@ -31,10 +34,15 @@ pub fn wsl_detect() -> WSLDetectResult {
     sync::detect_wsl()
 }
 
-/// Check if a specific WSL distro is available
+/// Check if a specific WSL distro is available. Resolved like a sync target, so with
+/// several targets a missing distro is reported as missing instead of falling back.
 #[tauri::command]
-pub fn wsl_check_distro(distro: String) -> WSLErrorResult {
-    match sync::get_effective_distro(&distro) {
+pub async fn wsl_check_distro(
+    state: tauri::State<'_, SqliteDbState>,
+    distro: String,
+) -> Result<WSLErrorResult, String> {
+    let config = wsl_get_config(state).await?;
+    Ok(match sync::resolve_distro(&config, &distro) {
         Ok(_) => WSLErrorResult {
             available: true,
             error: None,
@@ -43,16 +51,20 @@ pub fn wsl_check_distro(distro: String) -> WSLErrorResult {
             available: false,
             error: Some(e),
         },
-    }
+    })
 }
 
 /// Get running state of a specific WSL distro
 #[tauri::command]
-pub fn wsl_get_distro_state(distro: String) -> String {
-    match sync::get_effective_distro(&distro) {
+pub async fn wsl_get_distro_state(
+    state: tauri::State<'_, SqliteDbState>,
+    distro: String,
+) -> Result<String, String> {
+    let config = wsl_get_config(state).await?;
+    Ok(match sync::resolve_distro(&config, &distro) {
         Ok(effective_distro) => sync::get_wsl_distro_state(&effective_distro),
         Err(_) => "Unknown".to_string(),
-    }
+    })
 }
 
 // ============================================================================
@@ -264,6 +276,48 @@ pub async fn wsl_reset_file_mappings(
     Ok(())
 }
 
+/// Load WSL file mappings for sync mapping export.
+pub(crate) fn export_file_mappings(state: &SqliteDbState) -> Result<Vec<FileMapping>, String> {
+    load_wsl_file_mappings(state)
+}
+
+/// Work out what restoring WSL file mappings from an export would do, without writing.
+///
+/// With `merge`, incoming mappings whose WSL path is already mapped are skipped;
+/// otherwise all current mappings are replaced.
+pub(crate) fn plan_file_mappings(
+    state: &SqliteDbState,
+    mappings: Vec<FileMapping>,
+    merge: bool,
+) -> Result<MappingImportPlan<FileMapping>, String> {
+    Ok(sync_mappings::plan_mapping_import(
+        load_wsl_file_mappings(state)?,
+        mappings,
+        merge,
+        |mapping| &mapping.wsl_path,
+        |mapping| &mut mapping.id,
+        validate_file_mapping_cleanup_paths,
+    ))
+}
+
+/// Restore WSL file mappings from an export (see `plan_file_mappings`). Returns
+/// (imported, skipped).
+pub(crate) fn import_file_mappings(
+    state: &SqliteDbState,
+    mappings: Vec<FileMapping>,
+    merge: bool,
+) -> Result<(usize, usize), String> {
+    let plan = plan_file_mappings(state, mappings, merge)?;
+    sync_mappings::write_mapping_import(
+        state,
+        DbTable::WslFileMapping,
+        plan,
+        merge,
+        |mapping| &mapping.id,
+        adapter::mapping_to_db_value,
+    )
+}
+
 // ============================================================================
 // Sync Commands
 // ============================================================================
@@ -276,6 +330,7 @@ pub(super) async fn do_full_sync(
     module: Option<&str>,
     skip_modules: Option<&[String]>,
 ) -> SyncResult {
+    let _job = crate::db::jobs::DbJobGuard::begin("wsl_sync");
     let direct_modules: std::collections::HashSet<String> = config
         .module_statuses
         .iter()
@@ -284,28 +339,100 @@ pub(super) async fn do_full_sync(
         .collect();
     let merged_skip_modules = merge_skip_modules(skip_modules, &direct_modules);
 
-    // Get effective distro (auto-resolve if configured one doesn't exist)
-    let distro = match sync::get_effective_distro(&config.distro) {
-        Ok(d) => d,
-        Err(e) => {
-            log::warn!("WSL full sync skipped: {}", e);
-            return SyncResult {
-                success: false,
-                synced_files: vec![],
-                skipped_files: vec![],
-                errors: vec![e],
-            };
-        }
+    // Resolve every enabled target distro (auto-resolve if configured one doesn't exist)
+    let (distros, distro_errors) = sync::resolve_target_distros(config);
+    if distros.is_empty() {
+        log::warn!("WSL full sync skipped: {}", distro_errors.join("; "));
+        return SyncResult {
+            success: false,
+            synced_files: vec![],
+            skipped_files: vec![],
+            errors: distro_errors,
+        };
+    }
+
+    // Resolve effective local/WSL paths based on current runtime locations.
+    let db = state.db();
+    let file_mappings = resolve_dynamic_paths_with_db(&db, config.file_mappings.clone()).await;
+    let gateway_wsl_rewrite_context = build_gateway_wsl_rewrite_context(&db, app);
+
+    let mut result = SyncResult {
+        success: true,
+        synced_files: vec![],
+        skipped_files: vec![],
+        errors: vec![],
     };
+    for error in distro_errors {
+        log::warn!("WSL sync target skipped: {}", error);
+        result.errors.push(error);
+    }
 
+    // A failing distro only adds errors, the remaining distros still sync
+    let label_distros = distros.len() > 1;
+    for distro in &distros {
+        let distro_result = sync_distro(
+            state,
+            app,
+            &file_mappings,
+            distro,
+            module,
+            &merged_skip_modules,
+            gateway_wsl_rewrite_context.as_ref(),
+        )
+        .await;
+        let label = |item: String| {
+            if label_distros {
+                format!("[{}] {}", distro, item)
+            } else {
+                item
+            }
+        };
+        result
+            .synced_files
+            .extend(distro_result.synced_files.into_iter().map(label));
+        result
+            .skipped_files
+            .extend(distro_result.skipped_files.into_iter().map(label));
+        result
+            .errors
+            .extend(distro_result.errors.into_iter().map(label));
+    }
+
+    // Also sync MCP and Skills to WSL (full sync); both iterate the target distros themselves
+    if config.sync_mcp {
+        if let Err(e) = super::mcp_sync::sync_mcp_to_wsl(state, app.clone()).await {
+            log::warn!("MCP WSL sync failed: {}", e);
+            result.errors.push(format!("MCP sync: {}", e));
+        }
+    }
+    if config.sync_skills {
+        if let Err(e) = super::skills_sync::sync_skills_to_wsl(state, app.clone()).await {
+            log::warn!("Skills WSL sync failed: {}", e);
+            result.errors.push(format!("Skills sync: {}", e));
+        }
+    }
+
+    result.success = result.errors.is_empty();
+    result
+}
+
+/// File mappings, Claude metadata/onboarding and OpenClaw init for a single distro.
+async fn sync_distro(
+    state: &SqliteDbState,
+    app: &tauri::AppHandle,
+    file_mappings: &[FileMapping],
+    distro: &str,
+    module: Option<&str>,
+    merged_skip_modules: &[String],
+    gateway_wsl_rewrite_context: Option<&GatewayWslRewriteContext>,
+) -> SyncResult {
     // Emit initial progress for file mappings
-    let enabled_mappings: Vec<_> = config.file_mappings.iter().filter(|m| m.enabled).collect();
-    let total_files = enabled_mappings.len() as u32;
+    let total_files = file_mappings.iter().filter(|m| m.enabled).count() as u32;
     let _ = app.emit(
         "wsl-sync-progress",
         SyncProgress {
             phase: "files".to_string(),
-            current_item: "准备中...".to_string(),
+            current_item: format!("准备中 ({})...", distro),
             current: 0,
             total: total_files,
             message: format!("文件同步: 0/{}", total_files),
@@ -313,24 +440,19 @@ pub(super) async fn do_full_sync(
         },
     );
 
-    // Resolve effective local/WSL paths based on current runtime locations.
-    let db = state.db();
-    let file_mappings = resolve_dynamic_paths_with_db(&db, config.file_mappings.clone()).await;
-    let gateway_wsl_rewrite_context = build_gateway_wsl_rewrite_context(&db, app);
-
     // Sync file mappings with progress
     let mut result = sync_mappings_with_progress(
-        &file_mappings,
-        &distro,
+        file_mappings,
+        distro,
         module,
-        Some(merged_skip_modules.as_slice()),
+        Some(merged_skip_modules),
         app,
-        gateway_wsl_rewrite_context.as_ref(),
+        gateway_wsl_rewrite_context,
     );
 
     let skip_claude = merged_skip_modules.iter().any(|m| m == "claude");
     if !skip_claude && (module.is_none() || module == Some("claude")) {
-        if let Err(error) = rewrite_claude_plugin_metadata_in_wsl(&db, &distro).await {
+        if let Err(error) = rewrite_claude_plugin_metadata_in_wsl(state, distro).await {
             log::warn!("Claude plugin metadata WSL rewrite failed: {}", error);
             result
                 .errors
@@ -338,26 +460,10 @@ pub(super) async fn do_full_sync(
         }
     }
 
-    // Also sync MCP and Skills to WSL (full sync)
-    if config.sync_mcp {
-        if let Err(e) = super::mcp_sync::sync_mcp_to_wsl(state, app.clone()).await {
-            log::warn!("MCP WSL sync failed: {}", e);
-            result.errors.push(format!("MCP sync: {}", e));
-            result.success = false;
-        }
-    }
-    if config.sync_skills {
-        if let Err(e) = super::skills_sync::sync_skills_to_wsl(state, app.clone()).await {
-            log::warn!("Skills WSL sync failed: {}", e);
-            result.errors.push(format!("Skills sync: {}", e));
-            result.success = false;
-        }
-    }
-
     // Sync Claude Code onboarding status from Windows to WSL
     // Mirror the hasCompletedOnboarding field so WSL skips/shows initial setup accordingly
     if !skip_claude && (module.is_none() || module == Some("claude")) {
-        if let Err(e) = sync_onboarding_to_wsl(state, &distro).await {
+        if let Err(e) = sync_onboarding_to_wsl(state, distro).await {
             log::warn!("Onboarding WSL sync failed: {}", e);
             result.errors.push(format!("Onboarding sync: {}", e));
             result.success = false;
@@ -367,7 +473,7 @@ pub(super) async fn do_full_sync(
     // Ensure OpenClaw config exists in WSL (create empty {} if missing)
     let skip_openclaw = merged_skip_modules.iter().any(|m| m == "openclaw");
     if !skip_openclaw && (module.is_none() || module == Some("openclaw")) {
-        if let Err(e) = ensure_openclaw_config_in_wsl(state, &distro).await {
+        if let Err(e) = ensure_openclaw_config_in_wsl(state, distro).await {
             log::warn!("OpenClaw WSL config init failed: {}", e);
         }
     }
@@ -505,6 +611,24 @@ fn sync_mappings_with_progress(
             },
         );
 
+        sync_trace::trace_mapping_start(
+            "wsl",
+            &mapping.id,
+            &mapping.name,
+            &mapping.module,
+            sync_trace::mapping_kind(mapping.is_pattern, mapping.is_directory),
+            &mapping.windows_path,
+            &mapping.wsl_path,
+            || {
+                sync_performance::local_source_bytes(
+                    &mapping.windows_path,
+                    mapping.is_pattern,
+                    mapping.is_directory,
+                )
+            },
+        );
+        let started_at = std::time::Instant::now();
+        let errors_before = errors.len();
         match sync::sync_file_mapping(mapping, distro) {
             Ok(mut files) => {
                 if !files.is_empty() {
@@ -533,13 +657,61 @@ fn sync_mappings_with_progress(
                     Ok(catalog_files) => files.extend(catalog_files),
                     Err(error) => errors.push(format!("{}: {}", mapping.name, error)),
                 }
+                sync_trace::trace_mapping_files(
+                    "wsl",
+                    &mapping.id,
+                    "cp",
+                    &mapping.windows_path,
+                    mapping.is_pattern,
+                    mapping.is_directory,
+                    &[],
+                    !files.is_empty(),
+                );
                 if files.is_empty() {
+                    sync_trace::trace_mapping_result(
+                        "wsl",
+                        &mapping.id,
+                        "skipped (source missing or nothing matched)",
+                        &[],
+                        started_at.elapsed(),
+                    );
                     skipped_files.push(mapping.name.clone());
                     continue;
                 }
+                sync_trace::trace_mapping_result(
+                    "wsl",
+                    &mapping.id,
+                    "synced",
+                    &files,
+                    started_at.elapsed(),
+                );
+                sync_performance::record_mapping_perf(
+                    "wsl",
+                    &mapping.id,
+                    &mapping.name,
+                    &mapping.module,
+                    sync_performance::local_source_bytes(
+                        &mapping.windows_path,
+                        mapping.is_pattern,
+                        mapping.is_directory,
+                    ),
+                    started_at.elapsed(),
+                );
+                status::record_mapping_synced(distro, &mapping.id);
+                if let Some(error) = errors[errors_before..].last() {
+                    status::record_mapping_failed(distro, &mapping.id, error);
+                }
                 synced_files.extend(files);
             }
             Err(e) => {
+                sync_trace::trace_mapping_result(
+                    "wsl",
+                    &mapping.id,
+                    "failed",
+                    std::slice::from_ref(&e),
+                    started_at.elapsed(),
+                );
+                status::record_mapping_failed(distro, &mapping.id, &e);
                 errors.push(format!("{}: {}", mapping.name, e));
             }
         }
@@ -750,6 +922,70 @@ pub async fn wsl_sync(
     Ok(result)
 }
 
+/// Apply a provider inside one WSL distro only: merge it into that distro's own config and
+/// write it there. The host config is not read as the base nor written. Only Claude Code is
+/// supported; a later file sync of the same mapping overwrites the distro copy again.
+#[tauri::command]
+pub async fn apply_provider_to_wsl(
+    state: tauri::State<'_, SqliteDbState>,
+    tool: String,
+    provider_id: String,
+    distro: String,
+) -> Result<(), String> {
+    let distro = distro.trim();
+    if sync::get_wsl_distro_state(distro) != "Running" {
+        return Err(format!("WSL distro '{}' is not running", distro));
+    }
+    if tool != "claude" {
+        return Err(format!(
+            "Applying to a single WSL distro is not supported for '{}'",
+            tool
+        ));
+    }
+
+    let db = state.db();
+    let settings_path =
+        runtime_location::get_claude_wsl_target_path_async(&db, "settings.json").await;
+    let current_content = if sync::wsl_path_exists(distro, &settings_path) {
+        sync::read_wsl_file(distro, &settings_path)?
+    } else {
+        String::new()
+    };
+    let current_settings = if current_content.trim().is_empty() {
+        None
+    } else {
+        Some(
+            serde_json::from_str::<serde_json::Value>(&current_content)
+                .map_err(|e| format!("Failed to parse WSL settings.json: {}", e))?,
+        )
+    };
+
+    let mut settings = crate::coding::claude_code::build_claude_settings_for_provider_on(
+        &db,
+        &provider_id,
+        current_settings.as_ref(),
+        None,
+        None,
+    )
+    .await?;
+    if let Some(cleaned) =
+        config_cleanup::sanitize_claude_settings_for_non_windows_target(&settings)?
+    {
+        settings = cleaned;
+    }
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    sync::write_wsl_file(distro, &settings_path, &content)?;
+
+    log::info!(
+        "Applied Claude Code provider {} to WSL distro {} only: {}",
+        provider_id,
+        distro,
+        settings_path
+    );
+    Ok(())
+}
+
 /// Whether WSL automatic sync triggers are enabled.
 ///
 /// Automatic triggers include startup sync and event-driven sync from
@@ -763,6 +999,32 @@ pub async fn is_wsl_auto_sync_enabled(state: &SqliteDbState) -> bool {
         .unwrap_or(false)
 }
 
+/// Request a WSL sync for `module` after a local apply (Windows only).
+///
+/// Honors the `auto_sync_on_apply` setting: when it is off and WSL sync is enabled,
+/// `apply-sync-suppressed` is emitted with the module name instead, so the UI can offer a
+/// manual "sync now". Nothing is emitted when no WSL sync would have run.
+pub fn emit_apply_sync_request<R: tauri::Runtime>(
+    state: &SqliteDbState,
+    app: &tauri::AppHandle<R>,
+    module: &str,
+) {
+    #[cfg(target_os = "windows")]
+    {
+        let auto_sync_on_apply = crate::settings::store::load_settings_from_sqlite_state(state)
+            .map(|settings| settings.auto_sync_on_apply)
+            .unwrap_or(true);
+        if auto_sync_on_apply {
+            let _ = app.emit(&format!("wsl-sync-request-{}", module), ());
+        } else if load_wsl_config(state).is_ok_and(|config| config.enabled) {
+            let _ = app.emit("apply-sync-suppressed", module);
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    let _ = (state, app, module);
+}
+
 /// Remove the WSL target for an enabled file mapping when automatic sync is on.
 ///
 /// Normal file sync intentionally skips missing local sources. Delete-style tool
@@ -806,8 +1068,15 @@ pub async fn remove_auto_synced_wsl_mapping_target(
         return Ok(false);
     }
 
-    let distro = sync::get_effective_distro(&config.distro)?;
-    sync::remove_wsl_path(&distro, &mapping.wsl_path)?;
+    let (distros, mut errors) = sync::resolve_target_distros(&config);
+    for distro in &distros {
+        if let Err(error) = sync::remove_wsl_path(distro, &mapping.wsl_path) {
+            errors.push(format!("[{}] {}", distro, error));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
 
     Ok(true)
 }
@@ -820,7 +1089,7 @@ pub async fn wsl_get_status(
     let config = wsl_get_config(state).await?;
 
     let wsl_available = if config.enabled {
-        sync::get_effective_distro(&config.distro).is_ok()
+        !sync::resolve_target_distros(&config).0.is_empty()
     } else {
         false
     };
@@ -834,6 +1103,48 @@ pub async fn wsl_get_status(
     })
 }
 
+/// Per-distro availability and state of every configured sync target. Without a target
+/// list the primary distro is reported as the only target.
+#[tauri::command]
+pub async fn wsl_get_distro_statuses(
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<Vec<WSLDistroStatus>, String> {
+    let config = wsl_get_config(state).await?;
+    let targets = if config.distros.is_empty() {
+        vec![WSLDistroTarget {
+            name: config.distro.clone(),
+            enabled: true,
+        }]
+    } else {
+        config.distros.clone()
+    };
+
+    Ok(targets
+        .into_iter()
+        .map(|target| {
+            // Each target resolves on its own; one failing never hides the others
+            match sync::resolve_distro(&config, &target.name) {
+                Ok(effective) => WSLDistroStatus {
+                    state: sync::get_wsl_distro_state(&effective),
+                    distro: target.name,
+                    enabled: target.enabled,
+                    available: true,
+                    effective_distro: Some(effective),
+                    error: None,
+                },
+                Err(error) => WSLDistroStatus {
+                    distro: target.name,
+                    enabled: target.enabled,
+                    available: false,
+                    effective_distro: None,
+                    state: "Unknown".to_string(),
+                    error: Some(error),
+                },
+            }
+        })
+        .collect())
+}
+
 /// Test if a Windows path exists and can be accessed
 #[tauri::command]
 pub fn wsl_test_path(windows_path: String) -> Result<bool, String> {
@@ -0,0 +1,249 @@
+//! Compare a tool's host config file with its copy inside a WSL distro.
+//!
+//! Both sides are parsed and diffed field by field, so formatting and key order do not
+//! count as differences. The Claude host copy gets the same non-Windows cleanup that sync
+//! applies, otherwise every synced Windows-only env key would show up as drift.
+
+use serde_json::{Map, Value};
+
+use super::sync;
+use super::types::{ConfigComparison, ConfigDifference};
+use crate::coding::{all_api_hub, config_cleanup, file_io, runtime_location, setup_script};
+use crate::db::SqliteDbState;
+
+#[derive(Clone, Copy)]
+pub(crate) enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+/// Parse a JSON/JSON5 or TOML config into JSON; blank content is an empty object.
+pub(crate) fn parse_config(
+    content: &str,
+    format: ConfigFormat,
+    label: &str,
+) -> Result<Value, String> {
+    if content.trim().is_empty() {
+        return Ok(Value::Object(Map::new()));
+    }
+    match format {
+        ConfigFormat::Json => {
+            json5::from_str(content).map_err(|e| format!("Failed to parse {}: {}", label, e))
+        }
+        ConfigFormat::Toml => toml::from_str::<toml::Value>(content)
+            .map_err(|e| format!("Failed to parse {}: {}", label, e))
+            .and_then(|value| {
+                serde_json::to_value(value)
+                    .map_err(|e| format!("Failed to convert {}: {}", label, e))
+            }),
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+/// Field-level differences between two JSON documents. Objects are compared per key;
+/// arrays and scalars are compared as whole values.
+pub(crate) fn diff_json_fields(host: &Value, wsl: &Value) -> Vec<ConfigDifference> {
+    let mut differences = Vec::new();
+    collect_differences("", Some(host), Some(wsl), &mut differences);
+    differences
+}
+
+fn collect_differences(
+    path: &str,
+    host: Option<&Value>,
+    wsl: Option<&Value>,
+    differences: &mut Vec<ConfigDifference>,
+) {
+    match (host, wsl) {
+        (Some(Value::Object(host_map)), Some(Value::Object(wsl_map))) => {
+            let mut keys: Vec<&String> = host_map.keys().chain(wsl_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                collect_differences(
+                    &join_path(path, key),
+                    host_map.get(key),
+                    wsl_map.get(key),
+                    differences,
+                );
+            }
+        }
+        (host, wsl) if host == wsl => {}
+        (host, wsl) => {
+            let under_secret = path.split('.').any(setup_script::is_secret_key);
+            differences.push(ConfigDifference {
+                path: path.to_string(),
+                host: host
+                    .cloned()
+                    .map(|value| redact_secrets(value, under_secret)),
+                wsl: wsl
+                    .cloned()
+                    .map(|value| redact_secrets(value, under_secret)),
+            });
+        }
+    }
+}
+
+/// Mask string values that sit under a secret-like key.
+fn redact_secrets(value: Value, under_secret: bool) -> Value {
+    match value {
+        Value::String(text) if under_secret => {
+            Value::String(all_api_hub::mask_api_key_preview(&text))
+        }
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, child)| {
+                    let secret = under_secret || setup_script::is_secret_key(&key);
+                    (key, redact_secrets(child, secret))
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| redact_secrets(item, under_secret))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Compare the host config of `tool` ("claude" | "codex" | "geminicli") with the distro copy.
+#[tauri::command]
+pub async fn wsl_compare_config(
+    state: tauri::State<'_, SqliteDbState>,
+    tool: String,
+    distro: String,
+) -> Result<ConfigComparison, String> {
+    let db = state.db();
+    let (host_path, wsl_path, format) = match tool.as_str() {
+        "claude" => (
+            runtime_location::get_claude_settings_path_async(&db).await?,
+            runtime_location::get_claude_wsl_target_path_async(&db, "settings.json").await,
+            ConfigFormat::Json,
+        ),
+        "codex" => (
+            runtime_location::get_codex_config_path_async(&db).await?,
+            runtime_location::get_codex_wsl_target_path_async(&db, "config.toml").await,
+            ConfigFormat::Toml,
+        ),
+        "geminicli" => (
+            runtime_location::get_gemini_cli_settings_path_async(&db).await?,
+            runtime_location::get_gemini_cli_wsl_target_path_async(&db, "settings.json").await,
+            ConfigFormat::Json,
+        ),
+        other => {
+            return Err(format!(
+                "Config comparison is not supported for '{}'",
+                other
+            ))
+        }
+    };
+    let distro = distro.trim().to_string();
+    if sync::get_wsl_distro_state(&distro) != "Running" {
+        return Err(format!("WSL distro '{}' is not running", distro));
+    }
+
+    let host_content =
+        file_io::read_optional_text_file_with_timeout(host_path.clone(), "host config").await?;
+    let wsl_content = if sync::wsl_path_exists(&distro, &wsl_path) {
+        Some(sync::read_wsl_file(&distro, &wsl_path)?)
+    } else {
+        None
+    };
+
+    let mut comparison = ConfigComparison {
+        tool: tool.clone(),
+        distro,
+        host_path: host_path.to_string_lossy().to_string(),
+        wsl_path,
+        host_exists: host_content.is_some(),
+        wsl_exists: wsl_content.is_some(),
+        matches: false,
+        differences: Vec::new(),
+    };
+    let (Some(host_content), Some(wsl_content)) = (host_content, wsl_content) else {
+        // Nothing to diff yet; a missing side never counts as in sync
+        comparison.matches = !comparison.host_exists && !comparison.wsl_exists;
+        return Ok(comparison);
+    };
+
+    let mut host_value = parse_config(&host_content, format, "host config")?;
+    let wsl_value = parse_config(&wsl_content, format, "WSL config")?;
+    if tool == "claude" {
+        if let Some(cleaned) =
+            config_cleanup::sanitize_claude_settings_for_non_windows_target(&host_value)?
+        {
+            host_value = cleaned;
+        }
+    }
+
+    comparison.differences = diff_json_fields(&host_value, &wsl_value);
+    comparison.matches = comparison.differences.is_empty();
+    Ok(comparison)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_reports_nested_paths_and_masks_secrets() {
+        let host = json!({
+            "env": { "ANTHROPIC_AUTH_TOKEN": "sk-host-123456789", "ANTHROPIC_MODEL": "a" },
+            "permissions": { "allow": ["Bash"] },
+            "same": 1
+        });
+        let wsl = json!({
+            "env": { "ANTHROPIC_AUTH_TOKEN": "sk-wsl-987654321", "ANTHROPIC_MODEL": "a" },
+            "permissions": { "allow": ["Bash", "Read"] },
+            "same": 1,
+            "extra": { "apiKey": "sk-extra-000000" }
+        });
+
+        let differences = diff_json_fields(&host, &wsl);
+        let paths: Vec<&str> = differences.iter().map(|diff| diff.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["env.ANTHROPIC_AUTH_TOKEN", "extra", "permissions.allow"]
+        );
+
+        let token = &differences[0];
+        assert_ne!(token.host, Some(json!("sk-host-123456789")));
+        assert_ne!(token.wsl, Some(json!("sk-wsl-987654321")));
+        assert_eq!(differences[1].host, None);
+        assert_ne!(
+            differences[1].wsl,
+            Some(json!({ "apiKey": "sk-extra-000000" }))
+        );
+        assert_eq!(differences[2].wsl, Some(json!(["Bash", "Read"])));
+    }
+
+    #[test]
+    fn toml_and_json_parse_into_comparable_values() {
+        let toml_value = parse_config(
+            "model = \"gpt\"\n[features]\nweb = true\n",
+            ConfigFormat::Toml,
+            "toml",
+        )
+        .unwrap();
+        assert_eq!(
+            toml_value,
+            json!({ "model": "gpt", "features": { "web": true } })
+        );
+        assert_eq!(
+            parse_config("  ", ConfigFormat::Json, "json").unwrap(),
+            json!({})
+        );
+        assert!(diff_json_fields(&toml_value, &toml_value).is_empty());
+    }
+}
@@ -53,15 +53,15 @@ pub async fn sync_mcp_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result<()
         return Ok(());
     }
 
-    // Get effective distro (auto-resolve if configured one doesn't exist)
-    let distro = match super::sync::get_effective_distro(&config.distro) {
-        Ok(d) => d,
-        Err(e) => {
-            log::warn!("WSL MCP sync skipped: {}", e);
-            let _ = app.emit("wsl-sync-warning", format!("WSL MCP 同步已跳过：{}", e));
-            return Ok(());
-        }
-    };
+    // Resolve every enabled target distro (auto-resolve if configured one doesn't exist)
+    let (distros, distro_errors) = super::sync::resolve_target_distros(&config);
+    for e in &distro_errors {
+        log::warn!("WSL MCP sync skipped: {}", e);
+        let _ = app.emit("wsl-sync-warning", format!("WSL MCP 同步已跳过：{}", e));
+    }
+    if distros.is_empty() {
+        return Ok(());
+    }
     let direct_statuses = runtime_location::get_wsl_direct_status_map_async(&state.db()).await?;
     let skip_claude = direct_statuses
         .iter()
@@ -83,57 +83,14 @@ pub async fn sync_mcp_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result<()
         .any(|status| status.module == "pi" && status.is_wsl_direct);
 
     // 收集所有错误
-    let mut all_errors: Vec<String> = vec![];
-
-    // Emit progress for MCP sync
-    let _ = app.emit(
-        "wsl-sync-progress",
-        SyncProgress {
-            phase: "mcp".to_string(),
-            current_item: "Claude Code MCP".to_string(),
-            current: 1,
-            total: 2,
-            message: "MCP 同步: Claude Code...".to_string(),
-            current_file: None,
-        },
-    );
+    let mut all_errors: Vec<String> = distro_errors;
 
-    // 1. Claude Code: directly modify WSL ~/.claude.json
     let servers = mcp_store::get_mcp_servers(state).await?;
     let claude_servers: Vec<_> = servers
         .iter()
         .filter(|s| s.enabled_tools.contains(&"claude_code".to_string()))
         .collect();
-
-    if !skip_claude {
-        if let Err(e) = sync_mcp_to_wsl_claude(state, &distro, &claude_servers).await {
-            log::warn!("Skipped claude.json MCP sync: {}", e);
-            all_errors.push(format!("Claude Code: {}", e));
-            let _ = app.emit(
-                "wsl-sync-warning",
-                format!(
-                    "WSL ~/.claude.json 同步已跳过：文件解析失败，请检查该文件格式是否正确。({})",
-                    e
-                ),
-            );
-        }
-    }
-
-    // Emit progress for OpenCode/Codex/Grok/Gemini CLI/Pi
-    let _ = app.emit(
-        "wsl-sync-progress",
-        SyncProgress {
-            phase: "mcp".to_string(),
-            current_item: "OpenCode/Codex/Grok/Gemini CLI/Pi MCP".to_string(),
-            current: 2,
-            total: 2,
-            message: "MCP 同步: OpenCode/Codex/Grok/Gemini CLI/Pi...".to_string(),
-            current_file: None,
-        },
-    );
-
-    // 2. OpenCode/Codex/Grok/Gemini CLI/Pi: sync config files via file mappings
-    match get_file_mappings(state).await {
+    let mcp_mappings = match get_file_mappings(state).await {
         Ok(file_mappings) => {
             let mcp_mappings: Vec<_> = file_mappings
                 .into_iter()
@@ -149,42 +106,7 @@ pub async fn sync_mcp_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result<()
                     )
                 })
                 .collect();
-
-            if !mcp_mappings.is_empty() {
-                let resolved = resolve_dynamic_paths_with_db(&state.db(), mcp_mappings).await;
-                let result = sync_mappings(&resolved, &distro, None);
-                if !result.errors.is_empty() {
-                    let msg = result.errors.join("; ");
-                    log::warn!("MCP file mapping sync errors: {}", msg);
-                    all_errors.push(format!("OpenCode/Codex/Grok/Gemini CLI/Pi: {}", msg));
-                    let _ = app.emit(
-                        "wsl-sync-warning",
-                        format!("OpenCode/Codex/Grok/Gemini CLI/Pi 配置同步部分失败：{}", msg),
-                    );
-                }
-
-                // Post-process: strip cmd /c from synced MCP config files (WSL is Linux, doesn't need it)
-                // Only process files that actually contain MCP server configurations
-                let synced_paths: std::collections::HashSet<String> = result
-                    .synced_files
-                    .iter()
-                    .filter_map(|s| s.split(" -> ").nth(1).map(|p| p.to_string()))
-                    .collect();
-                for mapping in &resolved {
-                    if mapping.enabled
-                        && is_mapped_mcp_config_file(&mapping.id)
-                        && synced_paths.contains(&mapping.wsl_path)
-                    {
-                        if let Err(e) = strip_cmd_c_from_wsl_mcp_file(
-                            &distro,
-                            &mapping.wsl_path,
-                            &mapping.module,
-                        ) {
-                            log::warn!("Failed to strip cmd /c from {}: {}", mapping.wsl_path, e);
-                        }
-                    }
-                }
-            }
+            Some(resolve_dynamic_paths_with_db(&state.db(), mcp_mappings).await)
         }
         Err(e) => {
             log::warn!("Skipped OpenCode/Codex/Grok/Gemini CLI/Pi MCP sync: {}", e);
@@ -193,12 +115,106 @@ pub async fn sync_mcp_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result<()
                 "wsl-sync-warning",
                 format!("OpenCode/Codex/Grok/Gemini CLI/Pi MCP 同步已跳过：{}", e),
             );
+            None
+        }
+    };
+
+    // A failing distro only adds errors, the remaining distros still sync
+    let label_distros = distros.len() > 1;
+    for distro in &distros {
+        let label = if label_distros {
+            format!("[{}] ", distro)
+        } else {
+            String::new()
+        };
+
+        // Emit progress for MCP sync
+        let _ = app.emit(
+            "wsl-sync-progress",
+            SyncProgress {
+                phase: "mcp".to_string(),
+                current_item: "Claude Code MCP".to_string(),
+                current: 1,
+                total: 2,
+                message: format!("{}MCP 同步: Claude Code...", label),
+                current_file: None,
+            },
+        );
+
+        // 1. Claude Code: directly modify WSL ~/.claude.json
+        if !skip_claude {
+            if let Err(e) = sync_mcp_to_wsl_claude(state, distro, &claude_servers).await {
+                log::warn!("Skipped claude.json MCP sync in {}: {}", distro, e);
+                all_errors.push(format!("{}Claude Code: {}", label, e));
+                let _ = app.emit(
+                    "wsl-sync-warning",
+                    format!(
+                        "{}WSL ~/.claude.json 同步已跳过：文件解析失败，请检查该文件格式是否正确。({})",
+                        label, e
+                    ),
+                );
+            }
+        }
+
+        // Emit progress for OpenCode/Codex/Grok/Gemini CLI/Pi
+        let _ = app.emit(
+            "wsl-sync-progress",
+            SyncProgress {
+                phase: "mcp".to_string(),
+                current_item: "OpenCode/Codex/Grok/Gemini CLI/Pi MCP".to_string(),
+                current: 2,
+                total: 2,
+                message: format!("{}MCP 同步: OpenCode/Codex/Grok/Gemini CLI/Pi...", label),
+                current_file: None,
+            },
+        );
+
+        // 2. OpenCode/Codex/Grok/Gemini CLI/Pi: sync config files via file mappings
+        let Some(resolved) = mcp_mappings.as_ref().filter(|m| !m.is_empty()) else {
+            continue;
+        };
+        let result = sync_mappings(resolved, distro, None);
+        if !result.errors.is_empty() {
+            let msg = result.errors.join("; ");
+            log::warn!("MCP file mapping sync errors in {}: {}", distro, msg);
+            all_errors.push(format!(
+                "{}OpenCode/Codex/Grok/Gemini CLI/Pi: {}",
+                label, msg
+            ));
+            let _ = app.emit(
+                "wsl-sync-warning",
+                format!(
+                    "{}OpenCode/Codex/Grok/Gemini CLI/Pi 配置同步部分失败：{}",
+                    label, msg
+                ),
+            );
+        }
+
+        // Post-process: strip cmd /c from synced MCP config files (WSL is Linux, doesn't need it)
+        // Only process files that actually contain MCP server configurations
+        let synced_paths: std::collections::HashSet<String> = result
+            .synced_files
+            .iter()
+            .filter_map(|s| s.split(" -> ").nth(1).map(|p| p.to_string()))
+            .collect();
+        for mapping in resolved {
+            if mapping.enabled
+                && is_mapped_mcp_config_file(&mapping.id)
+                && synced_paths.contains(&mapping.wsl_path)
+            {
+                if let Err(e) =
+                    strip_cmd_c_from_wsl_mcp_file(distro, &mapping.wsl_path, &mapping.module)
+                {
+                    log::warn!("Failed to strip cmd /c from {}: {}", mapping.wsl_path, e);
+                }
+            }
         }
     }
 
     info!(
-        "MCP WSL sync completed: {} servers synced to claude_code",
-        claude_servers.len()
+        "MCP WSL sync completed: {} servers synced to claude_code in {} distro(s)",
+        claude_servers.len(),
+        distros.len()
     );
 
     // 根据真实结果更新状态
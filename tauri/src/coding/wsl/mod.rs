@@ -1,13 +1,18 @@
 mod adapter;
 mod commands;
+mod compare;
 mod mcp_sync;
 mod skills_sync;
+mod status;
 mod sync;
 mod types;
 
 pub use commands::*;
+pub use compare::*;
+pub(crate) use compare::{diff_json_fields, parse_config, ConfigFormat};
 pub use mcp_sync::sync_mcp_to_wsl;
 pub use skills_sync::sync_skills_to_wsl;
+pub use status::*;
 pub use sync::{
     get_effective_distro, get_wsl_user_home, remove_wsl_path, sync_directory, wsl_path_exists,
 };
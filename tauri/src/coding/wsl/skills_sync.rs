@@ -3,6 +3,7 @@
 //! Full sync of managed skills to WSL's central repo with symlinks to tool directories.
 
 use std::collections::HashSet;
+use std::path::Path;
 use std::sync::OnceLock;
 
 use log::info;
@@ -19,6 +20,7 @@ use crate::coding::runtime_location;
 use crate::coding::skills::central_repo::{resolve_central_repo_path, resolve_skill_central_path};
 use crate::coding::skills::content_hash::hash_dir;
 use crate::coding::skills::skill_store;
+use crate::coding::skills::types::Skill;
 use crate::coding::tools::builtin::BUILTIN_TOOLS;
 use crate::db::helpers::db_get;
 use crate::db::schema::DbTable;
@@ -96,14 +98,14 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
         return Ok(());
     }
 
-    // Get effective distro (auto-resolve if configured one doesn't exist)
-    let distro = match super::sync::get_effective_distro(&config.distro) {
-        Ok(d) => d,
-        Err(e) => {
-            log::warn!("WSL Skills sync skipped: {}", e);
-            return Ok(());
-        }
-    };
+    // Resolve every enabled target distro (auto-resolve if configured one doesn't exist)
+    let (distros, mut errors) = super::sync::resolve_target_distros(&config);
+    for e in &errors {
+        log::warn!("WSL Skills sync skipped: {}", e);
+    }
+    if distros.is_empty() {
+        return Ok(());
+    }
     let direct_statuses = runtime_location::get_wsl_direct_status_map_async(&state.db()).await?;
     let skipped_tool_keys: HashSet<String> = direct_statuses
         .into_iter()
@@ -120,7 +122,6 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
 
     // Get all managed skills
     let skills = skill_store::get_managed_skills(state).await?;
-    let db = state.db();
     let central_dir = resolve_central_repo_path(&app, state)
         .await
         .map_err(|e| format!("{}", e))?;
@@ -132,12 +133,70 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
         central_dir.display()
     );
 
+    // A failing distro only adds errors, the remaining distros still sync
+    let mut synced_count = 0;
+    for distro in &distros {
+        match sync_skills_to_distro(
+            state,
+            &app,
+            distro,
+            &skills,
+            &central_dir,
+            &skipped_tool_keys,
+        )
+        .await
+        {
+            Ok(count) => synced_count += count,
+            Err(e) if distros.len() > 1 => errors.push(format!("[{}] {}", distro, e)),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    info!(
+        "Skills WSL sync completed: {} skills updated, {} total, {} distro(s)",
+        synced_count,
+        skills.len(),
+        distros.len()
+    );
+
+    // Update sync status
+    let sync_result = super::types::SyncResult {
+        success: errors.is_empty(),
+        synced_files: vec![],
+        skipped_files: vec![],
+        errors: errors.clone(),
+    };
+    let _ = super::commands::update_sync_status(state, &sync_result).await;
+    let _ = app.emit("wsl-sync-completed", &sync_result);
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    // Emit event for UI feedback
+    let _ = app.emit("wsl-skills-sync-completed", ());
+
+    Ok(())
+}
+
+/// Sync the managed skills into one distro's central repo and tool symlinks.
+/// Returns how many skills had their content updated.
+async fn sync_skills_to_distro(
+    state: &SqliteDbState,
+    app: &AppHandle,
+    distro: &str,
+    skills: &[Skill],
+    central_dir: &Path,
+    skipped_tool_keys: &HashSet<String>,
+) -> Result<u32, String> {
+    let db = state.db();
+    let total_skills = skills.len() as u32;
+
     // Emit initial progress
     let _ = app.emit(
         "wsl-sync-progress",
         SyncProgress {
             phase: "skills".to_string(),
-            current_item: "准备中...".to_string(),
+            current_item: format!("准备中 ({})...", distro),
             current: 0,
             total: total_skills,
             message: format!("Skills 同步: 0/{}", total_skills),
@@ -146,7 +205,7 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
     );
 
     // 1. Get existing skills in WSL central repo
-    let existing_wsl_skills = list_wsl_dir(&distro, WSL_CENTRAL_DIR).unwrap_or_default();
+    let existing_wsl_skills = list_wsl_dir(distro, WSL_CENTRAL_DIR).unwrap_or_default();
 
     // 2. Collect Windows skill names
     let windows_skill_names: HashSet<String> = skills.iter().map(|s| s.name.clone()).collect();
@@ -161,12 +220,12 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
                 }
                 if let Some(wsl_skills_dir) = get_wsl_tool_skills_dir_with_db(&db, tool_key).await {
                     let link_path = format!("{}/{}", wsl_skills_dir, wsl_skill);
-                    let _ = remove_wsl_path(&distro, &link_path);
+                    let _ = remove_wsl_path(distro, &link_path);
                 }
             }
             // Remove from central repo
             let skill_path = format!("{}/{}", WSL_CENTRAL_DIR, wsl_skill);
-            let _ = remove_wsl_path(&distro, &skill_path);
+            let _ = remove_wsl_path(distro, &skill_path);
         }
     }
 
@@ -192,7 +251,7 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
             },
         );
 
-        let source = resolve_skill_central_path(&skill.central_path, &central_dir);
+        let source = resolve_skill_central_path(&skill.central_path, central_dir);
         if !source.exists() {
             info!(
                 "Skills WSL sync: skip '{}', source not found: {}",
@@ -235,7 +294,7 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
         let hash_file = format!("{}/.synced_hash", wsl_target);
 
         // Check if content needs updating using content_hash
-        let wsl_hash = read_wsl_file_raw(&distro, &hash_file)
+        let wsl_hash = read_wsl_file_raw(distro, &hash_file)
             .unwrap_or_default()
             .trim()
             .to_string();
@@ -250,24 +309,17 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
                 "Skills WSL sync: syncing '{}' from {} to {}",
                 skill.name, source_str, wsl_target
             );
-            match sync_directory(&source_str, &wsl_target, &distro) {
+            match sync_directory(&source_str, &wsl_target, distro) {
                 Ok(_) => {
                     // Save hash for future comparison
-                    write_wsl_file(&distro, &hash_file, windows_hash)?;
+                    write_wsl_file(distro, &hash_file, windows_hash)?;
                     synced_count += 1;
                 }
                 Err(e) => {
-                    let error_message =
-                        format!("Skills WSL sync failed for '{}': {}", skill.name, e);
-                    let sync_result = super::types::SyncResult {
-                        success: false,
-                        synced_files: vec![],
-                        skipped_files: vec![],
-                        errors: vec![error_message.clone()],
-                    };
-                    let _ = super::commands::update_sync_status(state, &sync_result).await;
-                    let _ = app.emit("wsl-sync-completed", &sync_result);
-                    return Err(error_message);
+                    return Err(format!(
+                        "Skills WSL sync failed for '{}': {}",
+                        skill.name, e
+                    ));
                 }
             }
         }
@@ -279,8 +331,8 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
             }
             if let Some(wsl_skills_dir) = get_wsl_tool_skills_dir_with_db(&db, tool_key).await {
                 let link_path = format!("{}/{}", wsl_skills_dir, skill.name);
-                if !check_wsl_symlink_exists(&distro, &link_path, &wsl_target) {
-                    if let Err(error) = create_wsl_symlink(&distro, &wsl_target, &link_path) {
+                if !check_wsl_symlink_exists(distro, &link_path, &wsl_target) {
+                    if let Err(error) = create_wsl_symlink(distro, &wsl_target, &link_path) {
                         log::warn!(
                             "Skills WSL sync: failed to create symlink for skill '{}' tool '{}' at '{}': {}",
                             skill.name,
@@ -308,7 +360,7 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
             if !enabled_set.contains(tool_key) {
                 if let Some(wsl_skills_dir) = get_wsl_tool_skills_dir_with_db(&db, tool_key).await {
                     let link_path = format!("{}/{}", wsl_skills_dir, skill.name);
-                    if let Err(error) = remove_wsl_path(&distro, &link_path) {
+                    if let Err(error) = remove_wsl_path(distro, &link_path) {
                         log::warn!(
                             "Skills WSL sync: failed to remove stale symlink for skill '{}' tool '{}' at '{}': {}",
                             skill.name,
@@ -322,24 +374,5 @@ pub async fn sync_skills_to_wsl(state: &SqliteDbState, app: AppHandle) -> Result
         }
     }
 
-    info!(
-        "Skills WSL sync completed: {} skills updated, {} total",
-        synced_count,
-        skills.len()
-    );
-
-    // Update sync status
-    let sync_result = super::types::SyncResult {
-        success: true,
-        synced_files: vec![],
-        skipped_files: vec![],
-        errors: vec![],
-    };
-    let _ = super::commands::update_sync_status(state, &sync_result).await;
-
-    // Emit event for UI feedback
-    let _ = app.emit("wsl-skills-sync-completed", ());
-    let _ = app.emit("wsl-sync-completed", &sync_result);
-
-    Ok(())
+    Ok(synced_count)
 }
@@ -0,0 +1,245 @@
+//! Per-mapping WSL sync status: last sync outcome plus current Windows/WSL drift.
+//!
+//! Outcomes are kept in memory (latest attempt per distro and mapping) and reset on restart.
+//! Drift is computed on demand for single-file mappings in every sync target: size and mtime
+//! are compared first, and both sides are hashed only when those are inconclusive.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::UNIX_EPOCH;
+
+use sha2::{Digest, Sha256};
+
+use super::commands::{resolve_dynamic_paths_with_db, wsl_get_config};
+use super::sync;
+use super::types::{FileMapping, WSLMappingStatus};
+use crate::coding::proxy_gateway::cli_proxy;
+use crate::db::SqliteDbState;
+
+#[derive(Debug, Clone, Default)]
+struct MappingOutcome {
+    last_synced_at: Option<String>,
+    last_error: Option<String>,
+}
+
+/// Keyed by (resolved distro, mapping id) so each sync target keeps its own outcome.
+static LAST_MAPPING_OUTCOME: LazyLock<Mutex<HashMap<(String, String), MappingOutcome>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn outcome_key(distro: &str, mapping_id: &str) -> (String, String) {
+    (distro.to_string(), mapping_id.to_string())
+}
+
+/// Record a successful sync of a mapping into `distro`, clearing its previous error.
+pub(super) fn record_mapping_synced(distro: &str, mapping_id: &str) {
+    if let Ok(mut cache) = LAST_MAPPING_OUTCOME.lock() {
+        let outcome = cache.entry(outcome_key(distro, mapping_id)).or_default();
+        outcome.last_synced_at = Some(chrono::Local::now().to_rfc3339());
+        outcome.last_error = None;
+    }
+}
+
+/// Record a failed sync attempt; the last successful sync time is kept.
+pub(super) fn record_mapping_failed(distro: &str, mapping_id: &str, error: &str) {
+    if let Ok(mut cache) = LAST_MAPPING_OUTCOME.lock() {
+        cache
+            .entry(outcome_key(distro, mapping_id))
+            .or_default()
+            .last_error = Some(error.to_string());
+    }
+}
+
+/// Cheap drift decision from (size, mtime) of the Windows source and WSL target.
+/// Returns None when only a hash comparison can tell.
+///
+/// `cp -f` stamps the target with the copy time, so an in-sync target is never older than
+/// its source. Targets rewritten after the copy (field cleanup, gateway endpoint rewrite)
+/// legitimately differ in size, so only their mtime is meaningful.
+fn quick_drift(source: (u64, i64), target: (u64, i64), rewritten: bool) -> Option<bool> {
+    let target_is_newer = target.1 >= source.1;
+    if rewritten {
+        return Some(!target_is_newer);
+    }
+    if source.0 != target.0 {
+        return Some(true);
+    }
+    if target_is_newer {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Whether the sync rewrites the WSL copy after copying it.
+fn target_is_rewritten(mapping: &FileMapping) -> bool {
+    mapping.id == "claude-settings"
+        || !mapping.cleanup_paths.is_empty()
+        || cli_proxy::wsl_synced_gateway_target_for_mapping(&mapping.id).is_some()
+}
+
+fn local_file_state(path: &str) -> Option<(u64, i64)> {
+    let metadata = std::fs::metadata(path).ok().filter(|m| m.is_file())?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0);
+    Some((metadata.len(), modified))
+}
+
+fn local_file_hash(path: &str) -> Result<String, String> {
+    let content = std::fs::read(path).map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+    Ok(hex::encode(Sha256::digest(content)))
+}
+
+/// Drift of one single-file mapping given the already collected target stat.
+fn file_drift(
+    mapping: &FileMapping,
+    distro: &str,
+    target: Option<(u64, i64)>,
+) -> (String, Option<String>) {
+    let source_path = match sync::expand_env_vars(&mapping.windows_path) {
+        Ok(path) => path,
+        Err(error) => return ("unchecked".to_string(), Some(error)),
+    };
+    let Some(source) = local_file_state(&source_path) else {
+        return ("sourceMissing".to_string(), None);
+    };
+    let Some(target) = target else {
+        return ("targetMissing".to_string(), None);
+    };
+
+    let rewritten = target_is_rewritten(mapping);
+    let detail = if rewritten {
+        "compared by modification time (WSL copy is rewritten after sync)"
+    } else {
+        "compared by size and modification time"
+    };
+    if let Some(drifted) = quick_drift(source, target, rewritten) {
+        return (drift_label(drifted), Some(detail.to_string()));
+    }
+
+    let hashes = local_file_hash(&source_path).and_then(|local| {
+        sync::hash_wsl_file(distro, &mapping.wsl_path).map(|remote| (local, remote))
+    });
+    match hashes {
+        Ok((local, Some(remote))) => (
+            drift_label(local != remote),
+            Some("compared by SHA-256".to_string()),
+        ),
+        Ok((_, None)) => ("targetMissing".to_string(), None),
+        Err(error) => ("unchecked".to_string(), Some(error)),
+    }
+}
+
+fn drift_label(drifted: bool) -> String {
+    if drifted { "drifted" } else { "inSync" }.to_string()
+}
+
+/// Per-distro, per-mapping last sync time, last error and current drift between Windows
+/// and WSL. Every sync target is reported; one that cannot be resolved or is not running
+/// only marks its own rows as "unchecked".
+///
+/// Drift is only checked for enabled single-file mappings while the distro is running;
+/// directory and pattern mappings report "unchecked".
+#[tauri::command]
+pub async fn wsl_get_detailed_status(
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<Vec<WSLMappingStatus>, String> {
+    let config = wsl_get_config(state.clone()).await?;
+    let mappings = resolve_dynamic_paths_with_db(&state, config.file_mappings.clone()).await;
+    let outcomes = LAST_MAPPING_OUTCOME
+        .lock()
+        .map_err(|_| "WSL mapping status cache is poisoned".to_string())?
+        .clone();
+
+    let checkable: Vec<&FileMapping> = mappings
+        .iter()
+        .filter(|m| m.enabled && !m.is_directory && !m.is_pattern)
+        .collect();
+
+    let mut target_names = config.target_distro_names();
+    if target_names.is_empty() {
+        target_names.push(config.distro.clone());
+    }
+
+    let mut statuses = Vec::new();
+    for target in target_names {
+        let resolved = sync::resolve_distro(&config, &target);
+        let distro = resolved.clone().unwrap_or_else(|_| target.clone());
+        // One batched stat call per distro; per-file hashing only happens on demand.
+        let targets = resolved
+            .and_then(|distro| {
+                if sync::get_wsl_distro_state(&distro) == "Running" {
+                    Ok(distro)
+                } else {
+                    Err(format!("WSL distro '{}' is not running", distro))
+                }
+            })
+            .and_then(|distro| {
+                let paths: Vec<String> = checkable.iter().map(|m| m.wsl_path.clone()).collect();
+                let stats = sync::stat_wsl_files(&distro, &paths)?;
+                let stats: HashMap<&str, Option<(u64, i64)>> =
+                    checkable.iter().map(|m| m.id.as_str()).zip(stats).collect();
+                Ok((distro, stats))
+            });
+
+        statuses.extend(mappings.iter().map(|mapping| {
+            let (drift, drift_detail) = if !mapping.enabled {
+                (
+                    "unchecked".to_string(),
+                    Some("mapping is disabled".to_string()),
+                )
+            } else if mapping.is_directory || mapping.is_pattern {
+                (
+                    "unchecked".to_string(),
+                    Some("only single-file mappings are compared".to_string()),
+                )
+            } else {
+                match &targets {
+                    Ok((distro, stats)) => file_drift(
+                        mapping,
+                        distro,
+                        stats.get(mapping.id.as_str()).copied().flatten(),
+                    ),
+                    Err(error) => ("unchecked".to_string(), Some(error.clone())),
+                }
+            };
+            let outcome = outcomes
+                .get(&outcome_key(&distro, &mapping.id))
+                .cloned()
+                .unwrap_or_default();
+            WSLMappingStatus {
+                distro: distro.clone(),
+                mapping_id: mapping.id.clone(),
+                name: mapping.name.clone(),
+                module: mapping.module.clone(),
+                enabled: mapping.enabled,
+                last_synced_at: outcome.last_synced_at,
+                last_error: outcome.last_error,
+                drift,
+                drift_detail,
+            }
+        }));
+    }
+    Ok(statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_drift_hashes_only_when_inconclusive() {
+        // Different size: drifted without hashing
+        assert_eq!(quick_drift((10, 100), (12, 200), false), Some(true));
+        // Same size and target written after the source changed: in sync
+        assert_eq!(quick_drift((10, 100), (10, 200), false), Some(false));
+        // Same size but source is newer: needs a hash
+        assert_eq!(quick_drift((10, 300), (10, 200), false), None);
+        // Rewritten targets only compare mtime
+        assert_eq!(quick_drift((10, 100), (14, 200), true), Some(false));
+        assert_eq!(quick_drift((10, 300), (10, 200), true), Some(true));
+    }
+}
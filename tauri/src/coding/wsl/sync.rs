@@ -1,4 +1,4 @@
-use super::types::{FileMapping, SyncResult, WSLDetectResult};
+use super::types::{FileMapping, SyncResult, WSLDetectResult, WSLSyncConfig};
 use std::path::Path;
 use std::process::Command;
 
@@ -65,6 +65,82 @@ fn decode_wsl_output(bytes: &[u8]) -> String {
     result.replace('\0', "")
 }
 
+/// Match a configured distro name against the installed distros, exactly or by prefix
+/// (e.g. "Ubuntu" matches "Ubuntu-22.04" and vice versa).
+pub fn find_installed_distro(configured_distro: &str, distros: &[String]) -> Option<String> {
+    // Check if configured distro exists exactly
+    if distros.iter().any(|d| d == configured_distro) {
+        return Some(configured_distro.to_string());
+    }
+
+    // Try to find a distro that starts with the configured name (e.g., "Ubuntu" matches "Ubuntu-22.04")
+    // or where the configured name starts with it (e.g., "Ubuntu-22.04" matches "Ubuntu")
+    let matching = distros
+        .iter()
+        .find(|d| d.starts_with(configured_distro))
+        .or_else(|| {
+            distros
+                .iter()
+                .find(|d| configured_distro.starts_with(d.as_str()))
+        })?;
+    log::info!(
+        "WSL distro '{}' not found, using '{}' instead",
+        configured_distro,
+        matching
+    );
+    Some(matching.clone())
+}
+
+/// Resolve the enabled sync targets of `config` to installed distros, without duplicates.
+/// Returns the distros plus one error per target that could not be resolved, so the
+/// remaining targets still sync.
+///
+/// A single target behaves like `get_effective_distro`, including its fallback to the first
+/// installed distro. With several targets a missing distro is an error instead, otherwise it
+/// would silently alias another target.
+pub fn resolve_target_distros(config: &WSLSyncConfig) -> (Vec<String>, Vec<String>) {
+    let names = config.target_distro_names();
+    if names.is_empty() {
+        return (
+            vec![],
+            vec!["No WSL distro is enabled as a sync target".to_string()],
+        );
+    }
+    if names.len() == 1 {
+        return match get_effective_distro(&names[0]) {
+            Ok(distro) => (vec![distro], vec![]),
+            Err(error) => (vec![], vec![error]),
+        };
+    }
+
+    let installed = match get_wsl_distros() {
+        Ok(installed) => installed,
+        Err(error) => return (vec![], vec![error]),
+    };
+    let mut resolved: Vec<String> = Vec::new();
+    let mut errors = Vec::new();
+    for name in names {
+        match find_installed_distro(&name, &installed) {
+            Some(distro) if !resolved.contains(&distro) => resolved.push(distro),
+            Some(_) => {}
+            None => errors.push(format!("WSL distro '{}' not found", name)),
+        }
+    }
+    (resolved, errors)
+}
+
+/// Resolve one distro name for per-distro status checks, with the same rule as
+/// `resolve_target_distros`: only the sole sync target falls back to the first installed
+/// distro, so a missing target never reports the state of another one.
+pub fn resolve_distro(config: &WSLSyncConfig, name: &str) -> Result<String, String> {
+    if config.target_distro_names() == [name] {
+        return get_effective_distro(name);
+    }
+    let installed = get_wsl_distros()?;
+    find_installed_distro(name, &installed)
+        .ok_or_else(|| format!("WSL distro '{}' not found", name))
+}
+
 /// Get the effective distro to use: if configured distro doesn't exist,
 /// try to find a matching one or use the first available distro
 pub fn get_effective_distro(configured_distro: &str) -> Result<String, String> {
@@ -74,32 +150,8 @@ pub fn get_effective_distro(configured_distro: &str) -> Result<String, String> {
         return Err("No WSL distros available".to_string());
     }
 
-    // Check if configured distro exists exactly
-    if distros.iter().any(|d| d == configured_distro) {
-        return Ok(configured_distro.to_string());
-    }
-
-    // Try to find a distro that starts with the configured name (e.g., "Ubuntu" matches "Ubuntu-22.04")
-    if let Some(matching) = distros.iter().find(|d| d.starts_with(configured_distro)) {
-        log::info!(
-            "WSL distro '{}' not found, using '{}' instead",
-            configured_distro,
-            matching
-        );
-        return Ok(matching.clone());
-    }
-
-    // Try to find a distro where configured name starts with it (e.g., "Ubuntu-22.04" matches "Ubuntu")
-    if let Some(matching) = distros
-        .iter()
-        .find(|d| configured_distro.starts_with(d.as_str()))
-    {
-        log::info!(
-            "WSL distro '{}' not found, using '{}' instead",
-            configured_distro,
-            matching
-        );
-        return Ok(matching.clone());
+    if let Some(matching) = find_installed_distro(configured_distro, &distros) {
+        return Ok(matching);
     }
 
     // Fall back to first available distro
@@ -834,6 +886,75 @@ pub fn check_wsl_symlink_exists(distro: &str, link_path: &str, expected_target:
     }
 }
 
+/// Size and mtime (unix seconds) of each WSL path in one WSL call; None for missing paths.
+pub fn stat_wsl_files(
+    distro: &str,
+    wsl_paths: &[String],
+) -> Result<Vec<Option<(u64, i64)>>, String> {
+    if wsl_paths.is_empty() {
+        return Ok(vec![]);
+    }
+    let command = wsl_paths
+        .iter()
+        .map(|path| {
+            format!(
+                "{{ [ -f \"{0}\" ] && stat -c '%s %Y' \"{0}\" 2>/dev/null || echo missing; }}",
+                path.replace("~", "$HOME")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    let output = create_wsl_command()
+        .args(["-d", distro, "--exec", "bash", "-c", &command])
+        .output()
+        .map_err(|e| format!("Failed to stat WSL files: {}", e))?;
+    if !output.status.success() {
+        let stderr = decode_wsl_output(&output.stderr);
+        return Err(format!("WSL stat failed: {}", stderr.trim()));
+    }
+
+    let stdout = decode_wsl_output(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    if lines.len() != wsl_paths.len() {
+        return Err(format!(
+            "WSL stat returned {} lines for {} paths",
+            lines.len(),
+            wsl_paths.len()
+        ));
+    }
+    Ok(lines
+        .into_iter()
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            let size = parts.next()?.parse().ok()?;
+            let modified = parts.next()?.parse().ok()?;
+            Some((size, modified))
+        })
+        .collect())
+}
+
+/// SHA-256 (hex) of a WSL file; None when it does not exist.
+pub fn hash_wsl_file(distro: &str, wsl_path: &str) -> Result<Option<String>, String> {
+    let wsl_target = wsl_path.replace("~", "$HOME");
+    let command = format!(
+        "if [ -f \"{0}\" ]; then sha256sum \"{0}\" | cut -d' ' -f1; fi",
+        wsl_target
+    );
+
+    let output = create_wsl_command()
+        .args(["-d", distro, "--exec", "bash", "-c", &command])
+        .output()
+        .map_err(|e| format!("Failed to hash WSL file: {}", e))?;
+    if !output.status.success() {
+        let stderr = decode_wsl_output(&output.stderr);
+        return Err(format!("WSL hash failed: {}", stderr.trim()));
+    }
+
+    let hash = decode_wsl_output(&output.stdout).trim().to_string();
+    Ok((!hash.is_empty()).then_some(hash))
+}
+
 pub fn wsl_path_exists(distro: &str, wsl_path: &str) -> bool {
     let wsl_target = wsl_path.replace("~", "$HOME");
     let command = format!("[ -e \"{}\" ] && echo yes || echo no", wsl_target);
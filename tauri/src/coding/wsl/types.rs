@@ -26,12 +26,23 @@ pub struct FileMapping {
 // WSL Sync Config Types
 // ============================================================================
 
+/// One WSL distro that files, MCP and Skills are synced into
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WSLDistroTarget {
+    pub name: String,
+    pub enabled: bool,
+}
+
 /// WSL sync configuration API response (camelCase for frontend)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WSLSyncConfig {
     pub enabled: bool,
     pub distro: String,
+    /// Sync targets with per-distro enable flags; empty means only `distro`
+    #[serde(default)]
+    pub distros: Vec<WSLDistroTarget>,
     /// Sync MCP configuration to WSL (default: true)
     #[serde(default = "default_true")]
     pub sync_mcp: bool,
@@ -51,6 +62,7 @@ impl Default for WSLSyncConfig {
         Self {
             enabled: false,
             distro: String::new(),
+            distros: vec![],
             sync_mcp: true,
             sync_skills: true,
             file_mappings: vec![],
@@ -62,6 +74,24 @@ impl Default for WSLSyncConfig {
     }
 }
 
+impl WSLSyncConfig {
+    /// Configured names of the distros to sync into, in order and without duplicates.
+    /// `distro` stays the primary distro (terminal, folder, compare) and is the only target
+    /// when no target list has been configured.
+    pub fn target_distro_names(&self) -> Vec<String> {
+        if self.distros.is_empty() {
+            return vec![self.distro.clone()];
+        }
+        let mut names: Vec<String> = Vec::new();
+        for target in self.distros.iter().filter(|target| target.enabled) {
+            if !names.contains(&target.name) {
+                names.push(target.name.clone());
+            }
+        }
+        names
+    }
+}
+
 fn default_true() -> bool {
     true
 }
@@ -97,6 +127,21 @@ pub struct WSLDetectResult {
     pub error: Option<String>,
 }
 
+/// Availability and running state of one configured sync target (API response)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WSLDistroStatus {
+    /// Configured distro name
+    pub distro: String,
+    pub enabled: bool,
+    pub available: bool,
+    /// Installed distro the name resolved to
+    pub effective_distro: Option<String>,
+    /// "Running" | "Stopped" | "Unknown"
+    pub state: String,
+    pub error: Option<String>,
+}
+
 /// WSL status result (API response)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -131,3 +176,57 @@ pub struct SyncProgress {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub current_file: Option<String>,
 }
+
+// ============================================================================
+// Config Comparison Types
+// ============================================================================
+
+/// One differing field between the host config and the WSL copy (secrets masked)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDifference {
+    /// Dot-separated field path; empty for the document root
+    pub path: String,
+    /// Host value; None when the field is missing on the host
+    pub host: Option<serde_json::Value>,
+    /// WSL value; None when the field is missing in the distro
+    pub wsl: Option<serde_json::Value>,
+}
+
+/// Result of comparing a tool's host config file with the same file inside a WSL distro
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigComparison {
+    pub tool: String,
+    pub distro: String,
+    pub host_path: String,
+    pub wsl_path: String,
+    pub host_exists: bool,
+    pub wsl_exists: bool,
+    pub matches: bool,
+    pub differences: Vec<ConfigDifference>,
+}
+
+// ============================================================================
+// Mapping Status Types
+// ============================================================================
+
+/// Last sync outcome and current drift of one file mapping in one sync target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WSLMappingStatus {
+    /// Installed distro the target resolved to, or the configured name when it did not resolve
+    pub distro: String,
+    pub mapping_id: String,
+    pub name: String,
+    pub module: String,
+    pub enabled: bool,
+    /// Last successful sync of this mapping since app start (RFC 3339)
+    pub last_synced_at: Option<String>,
+    /// Error of the last attempt; cleared by the next successful sync
+    pub last_error: Option<String>,
+    /// "inSync" | "drifted" | "sourceMissing" | "targetMissing" | "unchecked"
+    pub drift: String,
+    /// Why the drift state was decided this way (e.g. how it was compared, or why it was skipped)
+    pub drift_detail: Option<String>,
+}
@@ -2,7 +2,9 @@ pub mod backup;
 pub mod change_hook;
 pub mod health;
 pub mod helpers;
+pub mod jobs;
 pub mod migrations;
+pub mod recovery;
 pub mod model_pricing_seed;
 pub mod schema;
 pub mod sqlite_state;
@@ -19,3 +19,51 @@ pub fn vacuum(conn: &Connection) -> Result<(), String> {
     conn.execute_batch("VACUUM;")
         .map_err(|error| format!("Failed to vacuum SQLite database: {error}"))
 }
+
+/// Rebuild the database file without free pages, then fold the WAL back in and truncate it.
+pub fn compact(conn: &Connection) -> Result<(), String> {
+    vacuum(conn)?;
+    health::checkpoint_truncate(conn)
+}
+
+/// Size of the database file plus its `-wal` and `-shm` side files, in bytes
+pub fn database_files_size(db_path: &Path) -> u64 {
+    ["", "-wal", "-shm"]
+        .iter()
+        .filter_map(|suffix| {
+            let mut path = db_path.as_os_str().to_owned();
+            path.push(suffix);
+            std::fs::metadata(path).ok()
+        })
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_shrinks_database_after_deletes() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let db_path = temp.path().join("ai-toolbox.db");
+        let conn = Connection::open(&db_path).expect("open");
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE churn (id INTEGER PRIMARY KEY, data TEXT);
+             WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 2000)
+             INSERT INTO churn (data) SELECT hex(randomblob(512)) FROM n;
+             DELETE FROM churn;",
+        )
+        .expect("seed");
+        health::checkpoint_truncate(&conn).expect("checkpoint");
+
+        let size_before = database_files_size(&db_path);
+        compact(&conn).expect("compact");
+        let size_after = database_files_size(&db_path);
+        assert!(
+            size_after < size_before / 4,
+            "{size_before} -> {size_after}"
+        );
+    }
+}
@@ -140,6 +140,57 @@ pub fn db_query_by_bool(
     )
 }
 
+/// Records whose JSON array at `field_path` holds a string equal to `expected`, ignoring
+/// ASCII case. Records where the field is missing or not an array never match.
+pub fn db_query_by_array_element_nocase(
+    conn: &Connection,
+    table: DbTable,
+    field_path: &JsonFieldPath,
+    expected: &str,
+    order: Option<&OrderSpec>,
+) -> Result<Vec<Value>, String> {
+    let table_name = table.name();
+    let path = sql_string_literal(&field_path.to_sql_path());
+    let mut sql = format!(
+        "SELECT id, json(data) AS data_json, created_at, updated_at FROM {table_name} \
+         WHERE json_type(data, {path}) = 'array' \
+         AND EXISTS (SELECT 1 FROM json_each({table_name}.data, {path}) AS element \
+         WHERE element.type = 'text' AND element.value = ?1 COLLATE NOCASE)"
+    );
+    append_order_and_limit(&mut sql, order, None);
+    query_rows(conn, &sql, &[&expected])
+}
+
+/// Distinct string elements of the JSON array at `field_path` across all records, grouped
+/// ignoring ASCII case, with how many records contain each. Aggregated in SQLite, so the
+/// records are never loaded; sorted by count descending, then element.
+pub fn db_count_array_elements_nocase(
+    conn: &Connection,
+    table: DbTable,
+    field_path: &JsonFieldPath,
+) -> Result<Vec<(String, i64)>, String> {
+    let table_name = table.name();
+    let path = sql_string_literal(&field_path.to_sql_path());
+    let sql = format!(
+        "SELECT MIN(element.value), COUNT(DISTINCT {table_name}.id) \
+         FROM {table_name}, json_each({table_name}.data, {path}) AS element \
+         WHERE json_type({table_name}.data, {path}) = 'array' \
+         AND element.type = 'text' AND element.value <> '' \
+         GROUP BY element.value COLLATE NOCASE \
+         ORDER BY 2 DESC, 1 COLLATE NOCASE ASC"
+    );
+    let mut statement = conn
+        .prepare(&sql)
+        .map_err(|error| format!("Failed to prepare SQLite query: {error}; sql={sql}"))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })
+        .map_err(|error| format!("Failed to count array elements in {table_name}: {error}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to read array element counts: {error}"))
+}
+
 pub fn db_max_i64(
     conn: &Connection,
     table: DbTable,
@@ -152,6 +203,13 @@ pub fn db_max_i64(
         .map_err(|error| format!("Failed to read max value from {table_name}: {error}"))
 }
 
+/// `sort_index` that appends a record to `table`: one past the current maximum, 0 when empty.
+pub fn db_next_sort_index(conn: &Connection, table: DbTable) -> Result<i32, String> {
+    Ok(db_max_i64(conn, table, &JsonFieldPath::new("sort_index")?)?
+        .map(|value| value as i32 + 1)
+        .unwrap_or(0))
+}
+
 pub fn db_patch_fields(
     conn: &Connection,
     table: DbTable,
@@ -203,6 +261,37 @@ pub fn db_patch_where_bool(
     Ok(changed)
 }
 
+/// Renumber `sort_index` to 0..n following `order`, which must be the order the table's list
+/// view uses so the renumbering never moves a visible row. Records already at their position
+/// are left untouched, so repeated runs are no-ops.
+pub fn db_normalize_sort_index(
+    conn: &Connection,
+    table: DbTable,
+    order: &OrderSpec,
+) -> Result<usize, String> {
+    let records = db_list(conn, table, Some(order))?;
+    let mut changed = 0;
+
+    for (index, record) in records.iter().enumerate() {
+        if record.get("sort_index").and_then(Value::as_i64) == Some(index as i64) {
+            continue;
+        }
+        let id = record
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("Record in {} is missing id", table.name()))?;
+        let mut patch = vec![("sort_index", Value::from(index as i64))];
+        // Ordering maintenance is not a user edit; keep the record's modification time.
+        if let Some(updated_at) = record.get("updated_at") {
+            patch.push(("updated_at", updated_at.clone()));
+        }
+        db_patch_fields(conn, table, id, &patch)?;
+        changed += 1;
+    }
+
+    Ok(changed)
+}
+
 pub fn db_update_applied_status(
     conn: &mut Connection,
     table: DbTable,
@@ -469,3 +558,114 @@ fn now_string() -> String {
 fn value_to_number(value: i64) -> Value {
     Value::Number(Number::from(value))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::schema::{OrderDirection, OrderField};
+    use crate::db::SqliteDbState;
+    use serde_json::json;
+
+    #[test]
+    fn array_element_queries_ignore_case_and_non_arrays() {
+        let state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        let tags = JsonFieldPath::new("tags").expect("path");
+        state
+            .with_conn(|conn| {
+                db_put(
+                    conn,
+                    DbTable::McpServer,
+                    "a",
+                    &json!({ "tags": ["Db", "web"] }),
+                )?;
+                db_put(
+                    conn,
+                    DbTable::McpServer,
+                    "b",
+                    &json!({ "tags": ["db", "DB", "x", 3] }),
+                )?;
+                db_put(conn, DbTable::McpServer, "c", &json!({ "tags": "db" }))?;
+                db_put(
+                    conn,
+                    DbTable::McpServer,
+                    "d",
+                    &json!({ "name": "untagged" }),
+                )?;
+                Ok(())
+            })
+            .expect("seed");
+
+        let order = OrderSpec::new(vec![OrderField::id(OrderDirection::Asc)]);
+        let ids: Vec<String> = state
+            .with_conn(|conn| {
+                db_query_by_array_element_nocase(
+                    conn,
+                    DbTable::McpServer,
+                    &tags,
+                    "dB",
+                    Some(&order),
+                )
+            })
+            .expect("query")
+            .iter()
+            .map(|record| record["id"].as_str().unwrap_or_default().to_string())
+            .collect();
+        assert_eq!(ids, vec!["a".to_string(), "b".to_string()]);
+
+        let counts = state
+            .with_conn(|conn| db_count_array_elements_nocase(conn, DbTable::McpServer, &tags))
+            .expect("count");
+        assert_eq!(
+            counts,
+            vec![
+                ("DB".to_string(), 2),
+                ("web".to_string(), 1),
+                ("x".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_sort_index_fills_gaps_in_the_given_order() {
+        let state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        state
+            .with_conn(|conn| {
+                db_put(conn, DbTable::McpServer, "b", &json!({ "sort_index": 5 }))?;
+                db_put(conn, DbTable::McpServer, "a", &json!({ "sort_index": 5 }))?;
+                db_put(conn, DbTable::McpServer, "c", &json!({ "sort_index": 0 }))?;
+                Ok(())
+            })
+            .expect("seed");
+
+        let order = OrderSpec::new(vec![
+            OrderField::json_integer("sort_index", OrderDirection::Asc).expect("order field"),
+            OrderField::id(OrderDirection::Asc),
+        ]);
+        let changed = state
+            .with_conn(|conn| db_normalize_sort_index(conn, DbTable::McpServer, &order))
+            .expect("normalize");
+        assert_eq!(changed, 2);
+
+        let positions = state
+            .with_conn(|conn| {
+                Ok(db_list(conn, DbTable::McpServer, None)?
+                    .into_iter()
+                    .map(|record| {
+                        (
+                            record["id"].as_str().unwrap_or_default().to_string(),
+                            record["sort_index"].as_i64().unwrap_or(-1),
+                        )
+                    })
+                    .collect::<std::collections::HashMap<_, _>>())
+            })
+            .expect("list");
+        assert_eq!(positions["c"], 0);
+        assert_eq!(positions["a"], 1);
+        assert_eq!(positions["b"], 2);
+
+        let changed = state
+            .with_conn(|conn| db_normalize_sort_index(conn, DbTable::McpServer, &order))
+            .expect("normalize again");
+        assert_eq!(changed, 0);
+    }
+}
@@ -0,0 +1,76 @@
+//! Registry of long-running jobs that read or rewrite the database (backups, restores, WSL/SSH
+//! syncs). Each job holds a `DbJobGuard` for its whole run; database maintenance such as
+//! `compact_database` starts through `DbJobGuard::begin_exclusive` and is refused while any
+//! other job is registered.
+
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex};
+
+static ACTIVE_JOBS: LazyLock<Mutex<BTreeMap<&'static str, usize>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Marks a job as running until dropped
+pub struct DbJobGuard {
+    kind: &'static str,
+}
+
+impl DbJobGuard {
+    /// Register a job of `kind` ("backup", "restore", "wsl_sync", ...); jobs may overlap.
+    pub fn begin(kind: &'static str) -> Self {
+        if let Ok(mut jobs) = ACTIVE_JOBS.lock() {
+            *jobs.entry(kind).or_insert(0) += 1;
+        }
+        Self { kind }
+    }
+
+    /// Register `kind` only if no other job is running; the error names the running jobs.
+    pub fn begin_exclusive(kind: &'static str) -> Result<Self, String> {
+        let mut jobs = ACTIVE_JOBS
+            .lock()
+            .map_err(|_| "Database job registry is poisoned".to_string())?;
+        if !jobs.is_empty() {
+            return Err(format!(
+                "Cannot run {} while other work is in progress: {}",
+                kind,
+                jobs.keys().copied().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        jobs.insert(kind, 1);
+        Ok(Self { kind })
+    }
+}
+
+impl Drop for DbJobGuard {
+    fn drop(&mut self) {
+        if let Ok(mut jobs) = ACTIVE_JOBS.lock() {
+            if let Some(count) = jobs.get_mut(self.kind) {
+                *count -= 1;
+                if *count == 0 {
+                    jobs.remove(self.kind);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclusive_job_waits_for_every_running_job() {
+        let first = DbJobGuard::begin("test_backup");
+        let second = DbJobGuard::begin("test_backup");
+        drop(first);
+        let error = DbJobGuard::begin_exclusive("test_compact")
+            .err()
+            .expect("backup still running");
+        assert!(error.contains("test_backup"));
+
+        drop(second);
+        let exclusive = DbJobGuard::begin_exclusive("test_compact").expect("idle");
+        assert!(DbJobGuard::begin_exclusive("test_compact").is_err());
+        drop(exclusive);
+        assert!(DbJobGuard::begin_exclusive("test_compact").is_ok());
+    }
+}
@@ -1,8 +1,9 @@
 use rusqlite::Connection;
+use serde_json::Value;
 
 use super::schema::{sql_string_literal, DbTable, JsonFieldPath, ALL_TABLES};
 
-pub const TARGET_SCHEMA_VERSION: i32 = 8;
+pub const TARGET_SCHEMA_VERSION: i32 = 13;
 const FUTURE_SCHEMA_ERROR_PREFIX: &str = "AI_TOOLBOX_SQLITE_SCHEMA_TOO_NEW";
 
 pub fn run_all(conn: &mut Connection) -> Result<(), String> {
@@ -32,6 +33,21 @@ pub fn run_all(conn: &mut Connection) -> Result<(), String> {
     if current_version < 8 {
         run_migration_step(conn, 8, migrate_v8)?;
     }
+    if current_version < 9 {
+        run_migration_step(conn, 9, migrate_v9)?;
+    }
+    if current_version < 10 {
+        run_migration_step(conn, 10, migrate_v10)?;
+    }
+    if current_version < 11 {
+        run_migration_step(conn, 11, migrate_v11)?;
+    }
+    if current_version < 12 {
+        run_migration_step(conn, 12, migrate_v12)?;
+    }
+    if current_version < 13 {
+        run_migration_step(conn, 13, migrate_v13)?;
+    }
 
     Ok(())
 }
@@ -77,6 +93,21 @@ pub fn get_user_version(conn: &Connection) -> Result<i32, String> {
         .map_err(|error| format!("Failed to read SQLite user_version: {error}"))
 }
 
+/// Schema version stored in the database and the version this build migrates to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVersionInfo {
+    pub current: i32,
+    pub target: i32,
+}
+
+pub fn get_schema_version_info(conn: &Connection) -> Result<SchemaVersionInfo, String> {
+    Ok(SchemaVersionInfo {
+        current: get_user_version(conn)?,
+        target: TARGET_SCHEMA_VERSION,
+    })
+}
+
 pub fn set_user_version(conn: &Connection, version: i32) -> Result<(), String> {
     conn.execute_batch(&format!("PRAGMA user_version = {version}"))
         .map_err(|error| format!("Failed to set SQLite user_version to {version}: {error}"))
@@ -90,7 +121,7 @@ fn run_migration_step(
     conn.execute_batch("SAVEPOINT ai_toolbox_schema_migration")
         .map_err(|error| format!("Failed to start schema migration savepoint: {error}"))?;
 
-    let result = (|| {
+    let result: Result<(), String> = (|| {
         migration(conn)?;
         set_user_version(conn, target_version)?;
         Ok(())
@@ -104,7 +135,16 @@ fn run_migration_step(
             let _ = conn.execute_batch(
                 "ROLLBACK TO ai_toolbox_schema_migration; RELEASE ai_toolbox_schema_migration",
             );
-            Err(error)
+            // user_version is rolled back with the step, so the next start retries from here
+            log::error!(
+                "SQLite schema migration to v{} failed, version left at v{}: {}",
+                target_version,
+                target_version - 1,
+                error
+            );
+            Err(format!(
+                "Schema migration to v{target_version} failed: {error}"
+            ))
         }
     }
 }
@@ -193,6 +233,139 @@ fn migrate_v8(conn: &Connection) -> Result<(), String> {
     )
 }
 
+fn migrate_v9(conn: &Connection) -> Result<(), String> {
+    create_jsonb_table(conn, DbTable::ProviderModelsCache)
+}
+
+fn migrate_v10(conn: &Connection) -> Result<(), String> {
+    let normalized = normalize_db_field_casing(conn)?;
+    if normalized > 0 {
+        log::info!("Normalized legacy camelCase fields in {normalized} SQLite records");
+    }
+    Ok(())
+}
+
+fn migrate_v11(conn: &Connection) -> Result<(), String> {
+    create_jsonb_table(conn, DbTable::SshSyncBaseline)
+}
+
+fn migrate_v12(conn: &Connection) -> Result<(), String> {
+    create_jsonb_table(conn, DbTable::ProviderHealthHistory)
+}
+
+fn migrate_v13(conn: &Connection) -> Result<(), String> {
+    create_jsonb_table(conn, DbTable::ApplyHistory)
+}
+
+/// Legacy camelCase record fields and their canonical snake_case names. Only top-level
+/// fields are renamed: nested values mirror external config files and keep their casing.
+const LEGACY_CAMEL_CASE_FIELDS: &[(&str, &str)] = &[
+    ("accountId", "account_id"),
+    ("activeConnectionId", "active_connection_id"),
+    ("authMethod", "auth_method"),
+    ("authMode", "auth_mode"),
+    ("authSnapshot", "auth_snapshot"),
+    ("backgroundTask", "background_task"),
+    ("browserAutomationEngine", "browser_automation_engine"),
+    ("claudeCode", "claude_code"),
+    ("cleanupPaths", "cleanup_paths"),
+    ("configPath", "config_path"),
+    ("createdAt", "created_at"),
+    ("directoryExcludes", "directory_excludes"),
+    ("disabledAgents", "disabled_agents"),
+    ("disabledHooks", "disabled_hooks"),
+    ("disabledMcps", "disabled_mcps"),
+    ("disabledSkills", "disabled_skills"),
+    ("extraSettingsConfig", "extra_settings_config"),
+    ("iconColor", "icon_color"),
+    ("isApplied", "is_applied"),
+    ("isDirectory", "is_directory"),
+    ("isDisabled", "is_disabled"),
+    ("isPattern", "is_pattern"),
+    ("lastError", "last_error"),
+    ("lastLimitsFetchedAt", "last_limits_fetched_at"),
+    ("lastRefresh", "last_refresh"),
+    ("lastSyncError", "last_sync_error"),
+    ("lastSyncStatus", "last_sync_status"),
+    ("lastSyncTime", "last_sync_time"),
+    ("limit5hText", "limit_5h_text"),
+    ("limitShortLabel", "limit_short_label"),
+    ("limitWeeklyResetAt", "limit_weekly_reset_at"),
+    ("limitWeeklyText", "limit_weekly_text"),
+    ("localPath", "local_path"),
+    ("otherFields", "other_fields"),
+    ("planType", "plan_type"),
+    ("privateKeyContent", "private_key_content"),
+    ("privateKeyPath", "private_key_path"),
+    ("projectId", "project_id"),
+    ("providerId", "provider_id"),
+    ("remotePath", "remote_path"),
+    ("rootDir", "root_dir"),
+    ("settingsConfig", "settings_config"),
+    ("showPluginsInMenu", "show_plugins_in_menu"),
+    ("showPluginsInTray", "show_plugins_in_tray"),
+    ("sisyphusAgent", "sisyphus_agent"),
+    ("sortIndex", "sort_index"),
+    ("sortOrder", "sort_order"),
+    ("sourceProviderId", "source_provider_id"),
+    ("updatedAt", "updated_at"),
+    ("websiteUrl", "website_url"),
+    ("windowsPath", "windows_path"),
+    ("workspaceRoots", "workspace_roots"),
+    ("wslPath", "wsl_path"),
+];
+
+/// Rename legacy camelCase fields of one record in place. When both spellings exist the
+/// snake_case value wins, as it did for the old dual-key readers. Returns whether the record
+/// changed.
+pub(crate) fn normalize_record_field_casing(record: &mut Value) -> bool {
+    let Some(object) = record.as_object_mut() else {
+        return false;
+    };
+    let mut changed = false;
+    for (camel_key, snake_key) in LEGACY_CAMEL_CASE_FIELDS {
+        if let Some(value) = object.remove(*camel_key) {
+            object.entry(snake_key.to_string()).or_insert(value);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Rewrite every JSON record to the canonical snake_case field names and return the number
+/// of records changed. Idempotent; row timestamps are left untouched.
+pub fn normalize_db_field_casing(conn: &Connection) -> Result<usize, String> {
+    let mut normalized = 0;
+    for table in ALL_TABLES {
+        let table_name = table.name();
+        let mut statement = conn
+            .prepare(&format!("SELECT id, json(data) FROM {table_name}"))
+            .map_err(|error| format!("Failed to read {table_name} for casing fix: {error}"))?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|error| format!("Failed to read {table_name} for casing fix: {error}"))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| format!("Failed to read {table_name} row: {error}"))?;
+
+        for (id, data_json) in rows {
+            let mut record: Value = serde_json::from_str(&data_json)
+                .map_err(|error| format!("Failed to parse {table_name} record {id}: {error}"))?;
+            if !normalize_record_field_casing(&mut record) {
+                continue;
+            }
+            conn.execute(
+                &format!("UPDATE {table_name} SET data = jsonb(?1) WHERE id = ?2"),
+                (record.to_string(), &id),
+            )
+            .map_err(|error| format!("Failed to update {table_name} record {id}: {error}"))?;
+            normalized += 1;
+        }
+    }
+    Ok(normalized)
+}
+
 fn create_jsonb_table(conn: &Connection, table: DbTable) -> Result<(), String> {
     let table_name = table.name();
     conn.execute_batch(&format!(
@@ -716,3 +889,82 @@ fn create_column_index(conn: &Connection, table: DbTable, column: &str) -> Resul
     ))
     .map_err(|error| format!("Failed to create SQLite index {index_name}: {error}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::helpers::{db_get, db_put};
+    use crate::db::SqliteDbState;
+    use serde_json::json;
+
+    #[test]
+    fn failed_migration_step_keeps_the_previous_version() {
+        let conn = Connection::open_in_memory().expect("sqlite");
+        set_user_version(&conn, 4).expect("version");
+
+        let error = run_migration_step(&conn, 5, |conn| {
+            conn.execute_batch("CREATE TABLE half_applied (id TEXT)")
+                .map_err(|error| error.to_string())?;
+            Err("boom".to_string())
+        })
+        .expect_err("step should fail");
+        assert!(error.contains("v5") && error.contains("boom"));
+        assert_eq!(
+            get_schema_version_info(&conn).expect("version"),
+            SchemaVersionInfo {
+                current: 4,
+                target: TARGET_SCHEMA_VERSION,
+            }
+        );
+        let half_applied: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'half_applied'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query");
+        assert_eq!(half_applied, 0);
+    }
+
+    #[test]
+    fn normalize_db_field_casing_renames_legacy_fields_once() {
+        let state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        state
+            .with_conn(|conn| {
+                db_put(
+                    conn,
+                    DbTable::ClaudeProvider,
+                    "mixed",
+                    &json!({
+                        "name": "Relay",
+                        "isApplied": true,
+                        "is_disabled": false,
+                        "isDisabled": true,
+                        "settingsConfig": "{\"env\":{\"fooBar\":1}}",
+                        "meta": { "sortIndex": 3 }
+                    }),
+                )
+            })
+            .expect("seed");
+
+        let normalized = state
+            .with_conn(normalize_db_field_casing)
+            .expect("normalize");
+        assert_eq!(normalized, 1);
+        let record = state
+            .with_conn(|conn| db_get(conn, DbTable::ClaudeProvider, "mixed"))
+            .expect("read")
+            .expect("record");
+        assert_eq!(record["is_applied"], json!(true));
+        assert_eq!(record["is_disabled"], json!(false));
+        assert_eq!(record["settings_config"], json!("{\"env\":{\"fooBar\":1}}"));
+        assert!(record.get("isApplied").is_none());
+        assert!(record.get("isDisabled").is_none());
+        assert_eq!(record["meta"], json!({ "sortIndex": 3 }));
+
+        assert_eq!(
+            state.with_conn(normalize_db_field_casing).expect("rerun"),
+            0
+        );
+    }
+}
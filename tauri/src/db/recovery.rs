@@ -0,0 +1,141 @@
+//! Guided recovery when the SQLite main database cannot be opened at startup.
+//!
+//! The damaged file (with its `-wal`/`-shm` companions) is moved aside, never deleted, and
+//! replaced either by the newest local snapshot or by a fresh database. Snapshots come from
+//! the pre-migration backups next to the database and from raw diagnostics exports; backup
+//! zips are not considered because their location lives in the unreadable settings.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+
+use super::sqlite_state::{SqliteDbState, SQLITE_MIGRATION_BACKUP_DIR};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryAction {
+    RestoreSnapshot(PathBuf),
+    StartFresh,
+}
+
+/// Directories that may hold `.db` snapshots usable for recovery.
+pub fn recovery_snapshot_dirs(db_path: &Path, app_data_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(parent) = db_path.parent() {
+        dirs.push(parent.join(SQLITE_MIGRATION_BACKUP_DIR));
+    }
+    dirs.push(app_data_dir.join(crate::settings::backup::raw_db::RAW_EXPORT_DIR));
+    dirs
+}
+
+/// Newest `.db` file across `dirs` by modification time.
+pub fn find_latest_snapshot(dirs: &[PathBuf]) -> Option<PathBuf> {
+    dirs.iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "db"))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Move the damaged database aside, apply `action`, and verify the result opens.
+/// Returns the path the damaged database was moved to.
+pub fn recover_database(db_path: &Path, action: &RecoveryAction) -> Result<PathBuf, String> {
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+    let quarantine_suffix = format!(".corrupt-{}", timestamp);
+    let quarantined = with_suffix(db_path, &quarantine_suffix);
+
+    for suffix in ["", "-wal", "-shm"] {
+        let source = with_suffix(db_path, suffix);
+        if !source.exists() {
+            continue;
+        }
+        let target = with_suffix(&source, &quarantine_suffix);
+        fs::rename(&source, &target).map_err(|error| {
+            format!(
+                "Failed to move damaged database file {} aside: {error}",
+                source.display()
+            )
+        })?;
+    }
+
+    if let RecoveryAction::RestoreSnapshot(snapshot) = action {
+        fs::copy(snapshot, db_path).map_err(|error| {
+            format!(
+                "Failed to restore database snapshot {}: {error}",
+                snapshot.display()
+            )
+        })?;
+    }
+
+    SqliteDbState::open(db_path.to_path_buf())
+        .map_err(|error| format!("Recovered database still cannot be opened: {error}"))?;
+    log::warn!(
+        "Database recovered with {:?}; damaged file kept at {}",
+        action,
+        quarantined.display()
+    );
+    Ok(quarantined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_garbage_database(db_path: &Path) {
+        fs::write(db_path, b"not a sqlite database").expect("write garbage db");
+        fs::write(with_suffix(db_path, "-wal"), b"wal").expect("write garbage wal");
+    }
+
+    #[test]
+    fn start_fresh_keeps_damaged_files_aside() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let db_path = temp.path().join("ai-toolbox.db");
+        write_garbage_database(&db_path);
+        assert!(SqliteDbState::open(db_path.clone()).is_err());
+
+        let quarantined = recover_database(&db_path, &RecoveryAction::StartFresh).unwrap();
+        assert_eq!(fs::read(&quarantined).unwrap(), b"not a sqlite database");
+        let quarantine_suffix = quarantined.extension().unwrap().to_string_lossy();
+        let quarantined_wal = with_suffix(
+            &with_suffix(&db_path, "-wal"),
+            &format!(".{quarantine_suffix}"),
+        );
+        assert_eq!(fs::read(quarantined_wal).unwrap(), b"wal");
+        assert!(SqliteDbState::open(db_path).is_ok());
+    }
+
+    #[test]
+    fn latest_snapshot_is_restored() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let snapshot_dir = temp.path().join(SQLITE_MIGRATION_BACKUP_DIR);
+        fs::create_dir_all(&snapshot_dir).expect("create snapshot dir");
+        let snapshot = snapshot_dir.join("snapshot.db");
+        {
+            let source = SqliteDbState::in_memory_for_test().expect("sqlite state");
+            source
+                .with_conn(|conn| crate::db::backup::backup_to_path(conn, &snapshot))
+                .expect("write snapshot");
+        }
+        fs::write(snapshot_dir.join("notes.txt"), "ignored").expect("write non-db file");
+
+        let db_path = temp.path().join("ai-toolbox.db");
+        write_garbage_database(&db_path);
+        let found = find_latest_snapshot(&recovery_snapshot_dirs(&db_path, temp.path()));
+        assert_eq!(found.as_deref(), Some(snapshot.as_path()));
+
+        recover_database(&db_path, &RecoveryAction::RestoreSnapshot(snapshot)).unwrap();
+        assert!(SqliteDbState::open(db_path).is_ok());
+    }
+}
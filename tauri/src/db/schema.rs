@@ -49,6 +49,10 @@ pub enum DbTable {
     ImageChannel,
     ImageJob,
     ImageAsset,
+    ProviderModelsCache,
+    SshSyncBaseline,
+    ProviderHealthHistory,
+    ApplyHistory,
 }
 
 pub const ALL_TABLES: &[DbTable] = &[
@@ -99,6 +103,10 @@ pub const ALL_TABLES: &[DbTable] = &[
     DbTable::ImageChannel,
     DbTable::ImageJob,
     DbTable::ImageAsset,
+    DbTable::ProviderModelsCache,
+    DbTable::SshSyncBaseline,
+    DbTable::ProviderHealthHistory,
+    DbTable::ApplyHistory,
 ];
 
 impl DbTable {
@@ -151,6 +159,10 @@ impl DbTable {
             DbTable::ImageChannel => "image_channel",
             DbTable::ImageJob => "image_job",
             DbTable::ImageAsset => "image_asset",
+            DbTable::ProviderModelsCache => "provider_models_cache",
+            DbTable::SshSyncBaseline => "ssh_sync_baseline",
+            DbTable::ProviderHealthHistory => "provider_health_history",
+            DbTable::ApplyHistory => "apply_history",
         }
     }
 }
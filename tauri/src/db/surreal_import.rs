@@ -460,6 +460,8 @@ fn normalize_surreal_record(table: DbTable, record: &Value) -> Result<(String, V
             table.name()
         ));
     }
+    // Schema migrations already ran on the empty SQLite tables, so legacy casing is fixed here.
+    super::migrations::normalize_record_field_casing(&mut payload);
 
     Ok((id, payload))
 }
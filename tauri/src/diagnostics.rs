@@ -0,0 +1,227 @@
+//! Diagnostics bundle for bug reports.
+//!
+//! `export_diagnostics` zips the log files together with a `summary.json` (app version,
+//! OS/arch, detected CLI versions, the WebKitGTK workaround level on Linux and how many
+//! providers/MCP servers/skills are configured). Only counts leave the database, and every
+//! log line goes through `redact_trace_line` so API keys and tokens end up as `***`.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::coding::sync_trace::redact_trace_line;
+use crate::coding::tools::cli_versions::{detect_installed_tools, InstalledToolInfo};
+use crate::db::helpers::db_count;
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+
+/// Most recent daily log files to include; older days rarely matter for a fresh report
+const MAX_LOG_FILES: usize = 3;
+
+const COUNTED_TABLES: &[DbTable] = &[
+    DbTable::ClaudeProvider,
+    DbTable::CodexProvider,
+    DbTable::GrokProvider,
+    DbTable::GeminiCliProvider,
+    DbTable::OpenCodeFavoriteProvider,
+    DbTable::McpServer,
+    DbTable::Skill,
+    DbTable::SkillRepo,
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticsSummary {
+    generated_at: String,
+    app_version: String,
+    os: &'static str,
+    os_family: &'static str,
+    arch: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wayland_workaround_level: Option<u8>,
+    tools: Vec<InstalledToolInfo>,
+    /// Row count per table name; unreadable tables are left out
+    config_counts: std::collections::BTreeMap<&'static str, i64>,
+    log_files: Vec<String>,
+}
+
+/// Daily `ai-toolbox_*.log` files (newest `MAX_LOG_FILES`) plus the sync trace, if present.
+fn collect_log_files(log_dir: &Path) -> Vec<PathBuf> {
+    let mut daily_logs: Vec<PathBuf> = fs::read_dir(log_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().starts_with("ai-toolbox_"))
+                        .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    daily_logs.sort_by(|a, b| b.cmp(a));
+    daily_logs.truncate(MAX_LOG_FILES);
+
+    let sync_trace = log_dir.join("sync-trace.log");
+    if sync_trace.is_file() {
+        daily_logs.push(sync_trace);
+    }
+    daily_logs
+}
+
+fn redact_log_content(content: &str) -> String {
+    content
+        .lines()
+        .map(redact_trace_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn write_bundle(
+    output_path: &Path,
+    summary: &DiagnosticsSummary,
+    log_files: &[PathBuf],
+) -> Result<(), String> {
+    let file = File::create(output_path)
+        .map_err(|e| format!("Failed to create diagnostics bundle: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let summary_json = serde_json::to_string_pretty(summary)
+        .map_err(|e| format!("Failed to serialize diagnostics summary: {}", e))?;
+    zip.start_file("summary.json", options)
+        .map_err(|e| format!("Failed to add summary.json: {}", e))?;
+    zip.write_all(summary_json.as_bytes())
+        .map_err(|e| format!("Failed to write summary.json: {}", e))?;
+
+    for log_file in log_files {
+        let Some(file_name) = log_file.file_name() else {
+            continue;
+        };
+        let content = match fs::read(log_file) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) => {
+                log::warn!("Skipping log file {}: {}", log_file.display(), e);
+                continue;
+            }
+        };
+        let entry_name = format!("logs/{}", file_name.to_string_lossy());
+        zip.start_file(entry_name.as_str(), options)
+            .map_err(|e| format!("Failed to add {}: {}", entry_name, e))?;
+        zip.write_all(redact_log_content(&content).as_bytes())
+            .map_err(|e| format!("Failed to write {}: {}", entry_name, e))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish zip: {}", e))?;
+    Ok(())
+}
+
+/// Write a diagnostics zip (summary + redacted logs) to the Downloads folder, falling back to
+/// the temp dir, and return its path.
+#[tauri::command]
+pub async fn export_diagnostics(
+    app: tauri::AppHandle,
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<String, String> {
+    let config_counts = sqlite_state.with_conn(|conn| {
+        Ok(COUNTED_TABLES
+            .iter()
+            .filter_map(|table| {
+                db_count(conn, *table)
+                    .ok()
+                    .map(|count| (table.name(), count))
+            })
+            .collect())
+    })?;
+    let log_files = crate::get_log_dir()
+        .map(|log_dir| collect_log_files(&log_dir))
+        .unwrap_or_default();
+
+    let summary = DiagnosticsSummary {
+        generated_at: chrono::Local::now().to_rfc3339(),
+        app_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS,
+        os_family: std::env::consts::FAMILY,
+        arch: std::env::consts::ARCH,
+        wayland_workaround_level: crate::get_wayland_webview_workaround_level(app.clone()).ok(),
+        tools: detect_installed_tools().await?,
+        config_counts,
+        log_files: log_files
+            .iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect(),
+    };
+
+    let output_dir = dirs::download_dir().unwrap_or_else(std::env::temp_dir);
+    let output_path = output_dir.join(format!(
+        "ai-toolbox-diagnostics_{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    write_bundle(&output_path, &summary, &log_files)?;
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn bundle_contains_summary_and_redacted_logs() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let dir = temp_dir.path();
+        for day in ["2026-01-01", "2026-01-02", "2026-01-03", "2026-01-04"] {
+            fs::write(
+                dir.join(format!("ai-toolbox_{}.log", day)),
+                format!(
+                    "{} request failed: {{\"api_key\": \"sk-abcdefghijkl\"}}\n",
+                    day
+                ),
+            )
+            .unwrap();
+        }
+        fs::write(dir.join("unrelated.txt"), "ignored").unwrap();
+
+        let log_files = collect_log_files(dir);
+        assert_eq!(log_files.len(), MAX_LOG_FILES);
+        assert!(log_files[0].ends_with("ai-toolbox_2026-01-04.log"));
+
+        let summary = DiagnosticsSummary {
+            generated_at: String::new(),
+            app_version: "1.0.0".to_string(),
+            os: "linux",
+            os_family: "unix",
+            arch: "x86_64",
+            wayland_workaround_level: None,
+            tools: Vec::new(),
+            config_counts: [("mcp_server", 2)].into_iter().collect(),
+            log_files: Vec::new(),
+        };
+        let bundle = dir.join("bundle.zip");
+        write_bundle(&bundle, &summary, &log_files).unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(&bundle).unwrap()).unwrap();
+        let mut summary_json = String::new();
+        archive
+            .by_name("summary.json")
+            .unwrap()
+            .read_to_string(&mut summary_json)
+            .unwrap();
+        assert!(summary_json.contains("\"mcp_server\": 2"));
+        let mut log = String::new();
+        archive
+            .by_name("logs/ai-toolbox_2026-01-04.log")
+            .unwrap()
+            .read_to_string(&mut log)
+            .unwrap();
+        assert!(log.contains("***"));
+        assert!(!log.contains("abcdefghijkl"));
+    }
+}
@@ -0,0 +1,323 @@
+//! Registry of the events the backend emits to the frontend.
+//!
+//! The table is documentation only; emit sites still use string literals. Add a row when
+//! a new event is introduced — the test below scans the sources for literal event names
+//! and fails when one is missing from the table.
+
+use serde::Serialize;
+
+/// One event the backend emits via `AppHandle::emit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventInfo {
+    pub name: &'static str,
+    /// Grouping for readers: config | sync | update | gateway | backup | auth | image
+    pub category: &'static str,
+    /// Payload shape as seen by the frontend listener
+    pub payload: &'static str,
+    /// When the event fires
+    pub fires_when: &'static str,
+}
+
+const fn event(
+    name: &'static str,
+    category: &'static str,
+    payload: &'static str,
+    fires_when: &'static str,
+) -> EventInfo {
+    EventInfo {
+        name,
+        category,
+        payload,
+        fires_when,
+    }
+}
+
+/// Every event the app emits, grouped by category.
+pub const EMITTED_EVENTS: &[EventInfo] = &[
+    // Config
+    event(
+        "config-changed",
+        "config",
+        "\"window\" | \"tray\" (origin of the change)",
+        "Any stored tool config, provider or setting changed; the tray menu and gateway provider cache refresh on it",
+    ),
+    event(
+        "openclaw-config-changed",
+        "config",
+        "\"window\" | \"tray\"",
+        "The OpenClaw config file was written",
+    ),
+    event(
+        "config-file-changed",
+        "config",
+        "{ tool: \"claude_code\" | \"codex\" | \"opencode\", path }",
+        "A watched tool config file was changed on disk outside AI Toolbox",
+    ),
+    event(
+        "mcp-changed",
+        "config",
+        "\"window\"",
+        "MCP servers were created, updated, deleted, imported or restored; triggers WSL/SSH MCP sync",
+    ),
+    event(
+        "skills-changed",
+        "config",
+        "\"window\"",
+        "Managed skills changed; triggers WSL/SSH skills sync",
+    ),
+    event(
+        "wsl-config-changed",
+        "config",
+        "null",
+        "WSL sync config or mappings were saved",
+    ),
+    event(
+        "ssh-config-changed",
+        "config",
+        "null",
+        "SSH sync config, connections or mappings were saved",
+    ),
+    event(
+        "applied-provider-unreachable",
+        "config",
+        "UnreachableProvider[] { tool, providerId, providerName, baseUrl, error }",
+        "The optional startup check found applied Claude/Codex/Gemini CLI/Grok providers whose endpoint did not answer",
+    ),
+    // Sync
+    event(
+        "wsl-sync-request-opencode",
+        "sync",
+        "null",
+        "OpenCode config was applied; the backend listener syncs it to WSL",
+    ),
+    event(
+        "wsl-sync-request-claude",
+        "sync",
+        "null",
+        "Claude Code config was applied; the backend listener syncs it to WSL",
+    ),
+    event(
+        "wsl-sync-request-codex",
+        "sync",
+        "null",
+        "Codex config was applied; the backend listener syncs it to WSL",
+    ),
+    event(
+        "wsl-sync-request-grok",
+        "sync",
+        "null",
+        "Grok CLI config was applied; the backend listener syncs it to WSL",
+    ),
+    event(
+        "wsl-sync-request-openclaw",
+        "sync",
+        "null",
+        "OpenClaw config was applied; the backend listener syncs it to WSL",
+    ),
+    event(
+        "wsl-sync-request-geminicli",
+        "sync",
+        "null",
+        "Gemini CLI config was applied; the backend listener syncs it to WSL",
+    ),
+    event(
+        "wsl-sync-request-pi",
+        "sync",
+        "null",
+        "Pi config was applied; the backend listener syncs it to WSL",
+    ),
+    event(
+        "apply-sync-suppressed",
+        "sync",
+        "string (module name)",
+        "On Windows with WSL sync on, a config was applied while auto_sync_on_apply is off",
+    ),
+    event(
+        "wsl-sync-progress",
+        "sync",
+        "SyncProgress { phase, currentItem, current, total, message, currentFile }",
+        "During a WSL file sync, once per mapping",
+    ),
+    event(
+        "wsl-sync-completed",
+        "sync",
+        "SyncResult { success, syncedFiles, skippedFiles, errors }",
+        "A WSL file, MCP or skills sync finished",
+    ),
+    event(
+        "wsl-mcp-sync-completed",
+        "sync",
+        "null",
+        "WSL MCP sync finished",
+    ),
+    event(
+        "wsl-skills-sync-completed",
+        "sync",
+        "null",
+        "WSL skills sync finished",
+    ),
+    event(
+        "wsl-sync-warning",
+        "sync",
+        "string (message)",
+        "Part of a WSL sync was skipped or failed without aborting the sync",
+    ),
+    event(
+        "ssh-sync-progress",
+        "sync",
+        "SyncProgress { phase, currentItem, current, total, message, currentFile }",
+        "During an SSH file sync, per mapping and per transferred file",
+    ),
+    event(
+        "ssh-sync-completed",
+        "sync",
+        "SyncResult { success, syncedFiles, skippedFiles, errors }",
+        "An SSH file sync finished",
+    ),
+    event(
+        "ssh-sync-conflicts",
+        "sync",
+        "SyncConflict[]",
+        "An SSH file sync found bidirectional mappings changed on both sides",
+    ),
+    event(
+        "ssh-mcp-sync-completed",
+        "sync",
+        "null",
+        "SSH MCP sync finished",
+    ),
+    event(
+        "ssh-skills-sync-completed",
+        "sync",
+        "null",
+        "SSH skills sync finished",
+    ),
+    event(
+        "ssh-sync-warning",
+        "sync",
+        "string (message)",
+        "Part of an SSH sync was skipped or failed without aborting the sync",
+    ),
+    event(
+        "ssh-connection-status",
+        "sync",
+        "\"connected\" | \"disconnected\"",
+        "The background SSH health check lost or re-established the connection",
+    ),
+    // Update
+    event(
+        "update-download-progress",
+        "update",
+        "{ status, progress, downloaded, total, speed }",
+        "While an app update is downloaded and installed",
+    ),
+    // Gateway
+    event(
+        "gateway-running-changed",
+        "gateway",
+        "boolean (running)",
+        "The proxy gateway was started or stopped",
+    ),
+    event(
+        "gateway-failover",
+        "gateway",
+        "{ cli_key, from_provider_id, from_provider_name, to_provider_id, to_provider_name }",
+        "The gateway switched to another provider after an upstream failure",
+    ),
+    event(
+        "usage-log-recorded",
+        "gateway",
+        "{ cli_key, trace_id, data_source, inserted_records }",
+        "Usage records were stored from a proxied request or a session import",
+    ),
+    // Backup
+    event(
+        "auto-backup-completed",
+        "backup",
+        "string (backup time)",
+        "A scheduled automatic backup succeeded",
+    ),
+    event(
+        "auto-backup-failed",
+        "backup",
+        "string (error)",
+        "A scheduled automatic backup failed",
+    ),
+    event(
+        "webdav-backup-progress",
+        "backup",
+        "WebDAVBackupProgress { requestId, stage, bytesSent, totalBytes }",
+        "A manual WebDAV backup upload started, sent another chunk, finished, was cancelled or failed",
+    ),
+    // Auth
+    event(
+        "codex-auth-status",
+        "auth",
+        "{ sessionId, status, message, accountId }",
+        "A Codex official account OAuth login changed state",
+    ),
+    event(
+        "grok-auth-status",
+        "auth",
+        "{ sessionId, status, message, accountId }",
+        "A Grok official account OAuth login changed state",
+    ),
+    // Image
+    event(
+        "image-job-progress",
+        "image",
+        "{ job_id, stage, attempt, max_attempts, retry_count, max_retries, delay_ms, ... }",
+        "An image generation job started, retried, finished or failed",
+    ),
+];
+
+/// List every event the app emits with its payload shape and trigger.
+#[tauri::command]
+pub fn list_emitted_events() -> Result<Vec<EventInfo>, String> {
+    Ok(EMITTED_EVENTS.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Literal event names passed to `emit(` anywhere under `src/`.
+    fn literal_emitted_event_names() -> HashSet<String> {
+        let source_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let pattern = regex::Regex::new(r#"\.emit\(\s*"([a-z0-9-]+)""#).unwrap();
+        let mut names = HashSet::new();
+        for entry in walkdir::WalkDir::new(source_dir).into_iter().flatten() {
+            if entry.path().extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            let content = std::fs::read_to_string(entry.path()).unwrap_or_default();
+            for captures in pattern.captures_iter(&content) {
+                names.insert(captures[1].to_string());
+            }
+        }
+        names
+    }
+
+    #[test]
+    fn event_names_are_unique() {
+        let mut seen = HashSet::new();
+        for info in EMITTED_EVENTS {
+            assert!(seen.insert(info.name), "duplicate event {}", info.name);
+        }
+    }
+
+    #[test]
+    fn every_literal_emit_is_registered() {
+        let registered: HashSet<&str> = EMITTED_EVENTS.iter().map(|info| info.name).collect();
+        let missing: Vec<String> = literal_emitted_event_names()
+            .into_iter()
+            .filter(|name| !registered.contains(name.as_str()))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "events missing from EMITTED_EVENTS: {missing:?}"
+        );
+    }
+}
@@ -0,0 +1,294 @@
+//! One-shot health check behind the "run diagnostics" button.
+//!
+//! `run_health_check` runs the existing probes (SQLite quick_check, config directory
+//! writability, CLI detection, WebDAV PROPFIND, SSH connect) and flattens them into a list of
+//! pass/warn/fail checks, each failure carrying a short remediation hint. WebDAV and SSH are
+//! only checked when configured.
+
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::coding::config_writability::{test_config_writability, WritabilityResult};
+use crate::coding::tools::cli_versions::{detect_installed_tools, InstalledToolInfo};
+use crate::db::SqliteDbState;
+use crate::settings::store;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheck {
+    /// Stable id, e.g. "database", "config_dir:claude", "tool:codex"
+    pub id: String,
+    /// "pass" | "warn" | "fail"
+    pub status: String,
+    pub detail: String,
+    /// What to do about a warn/fail
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl HealthCheck {
+    fn pass(id: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: "pass".to_string(),
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(id: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: "warn".to_string(),
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(id: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            status: "fail".to_string(),
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+fn database_check(state: &SqliteDbState) -> HealthCheck {
+    match state.with_conn(crate::db::health::quick_check) {
+        Ok(()) => HealthCheck::pass("database", state.db_path().display().to_string()),
+        Err(error) => HealthCheck::fail(
+            "database",
+            error,
+            "Restart the app; if the check still fails, restore the latest backup.",
+        ),
+    }
+}
+
+fn app_data_dir_check(app: &tauri::AppHandle) -> HealthCheck {
+    let dir = match app.path().app_data_dir() {
+        Ok(dir) => dir,
+        Err(error) => {
+            return HealthCheck::fail(
+                "app_data_dir",
+                format!("Failed to resolve app data dir: {}", error),
+                "Make sure the home directory is set and accessible.",
+            )
+        }
+    };
+    match tempfile::tempfile_in(&dir) {
+        Ok(_) => HealthCheck::pass("app_data_dir", dir.display().to_string()),
+        Err(error) => HealthCheck::fail(
+            "app_data_dir",
+            format!("{}: {}", dir.display(), error),
+            "Give your user write permission on the app data directory.",
+        ),
+    }
+}
+
+fn config_dir_check(result: &WritabilityResult) -> HealthCheck {
+    let id = format!("config_dir:{}", result.tool);
+    let detail = match &result.error {
+        Some(error) if result.directory.is_empty() => error.clone(),
+        Some(error) => format!("{}: {}", result.directory, error),
+        None => result.directory.clone(),
+    };
+    match result.status.as_str() {
+        "writable" => HealthCheck::pass(id, detail),
+        // Not created until the tool runs or a provider is applied; nothing to fix
+        "missing" => HealthCheck::pass(id, format!("{} (not created yet)", detail)),
+        "not_writable" => HealthCheck::fail(
+            id,
+            detail,
+            "Fix the directory permissions so your user can create and rename files in it.",
+        ),
+        _ => HealthCheck::warn(
+            id,
+            detail,
+            "Check the custom config path for this tool in its settings.",
+        ),
+    }
+}
+
+/// Installed tools only; a CLI that is not installed is not a problem by itself.
+fn tool_check(info: &InstalledToolInfo) -> Option<HealthCheck> {
+    if !info.installed {
+        return None;
+    }
+    let id = format!("tool:{}", info.tool);
+    let path = info.path.clone().unwrap_or_default();
+    Some(match &info.version {
+        Some(version) if info.on_path => HealthCheck::pass(id, format!("{} ({})", version, path)),
+        Some(version) => HealthCheck::warn(
+            id,
+            format!("{} ({})", version, path),
+            format!(
+                "`{}` is installed but not on PATH; add its directory to PATH so terminals find it.",
+                info.command
+            ),
+        ),
+        None => HealthCheck::warn(
+            id,
+            path,
+            format!(
+                "`{} --version` did not print a version; reinstall or update the CLI.",
+                info.command
+            ),
+        ),
+    })
+}
+
+/// WebDAV errors come back as `{"type", "message", "suggestion"}` JSON; keep the message.
+fn webdav_error_message(error: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(error)
+        .ok()
+        .and_then(|value| value.get("message")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| error.to_string())
+}
+
+/// Run every health check and return them in a fixed order: database, app data dir,
+/// config directories, installed tools, then WebDAV and SSH when configured. A probe that
+/// cannot run becomes a warn/fail entry instead of failing the whole check.
+#[tauri::command]
+pub async fn run_health_check(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<Vec<HealthCheck>, String> {
+    let mut checks = vec![database_check(&state), app_data_dir_check(&app)];
+
+    match test_config_writability(state.clone()).await {
+        Ok(results) => checks.extend(results.iter().map(config_dir_check)),
+        Err(error) => checks.push(HealthCheck::warn(
+            "config_dirs",
+            error,
+            "Open each tool's settings page to re-detect its config path.",
+        )),
+    }
+
+    match detect_installed_tools().await {
+        Ok(tools) => checks.extend(tools.iter().filter_map(tool_check)),
+        Err(error) => checks.push(HealthCheck::warn(
+            "tools",
+            error,
+            "Open the tools page to re-detect the installed CLIs.",
+        )),
+    }
+
+    match store::load_settings_from_sqlite_state(&state) {
+        Ok(settings) if !settings.webdav.url.trim().is_empty() => {
+            let webdav = settings.webdav;
+            checks.push(
+                match crate::settings::backup::test_webdav_connection(
+                    state.clone(),
+                    webdav.url.clone(),
+                    webdav.username,
+                    webdav.password,
+                    webdav.remote_path,
+                )
+                .await
+                {
+                    Ok(()) => HealthCheck::pass("webdav", webdav.url),
+                    Err(error) => HealthCheck::fail(
+                        "webdav",
+                        webdav_error_message(&error),
+                        "Check the WebDAV URL, credentials and remote path in backup settings.",
+                    ),
+                },
+            );
+        }
+        Ok(_) => {}
+        Err(error) => checks.push(HealthCheck::fail(
+            "settings",
+            error,
+            "Restart the app; if settings still fail to load, restore the latest backup.",
+        )),
+    }
+
+    let active_connection = match crate::coding::ssh::get_ssh_config_internal(&state, false).await {
+        Ok(ssh_config) => ssh_config
+            .connections
+            .into_iter()
+            .find(|connection| connection.id == ssh_config.active_connection_id)
+            .filter(|_| ssh_config.enabled),
+        Err(error) => {
+            checks.push(HealthCheck::warn(
+                "ssh",
+                error,
+                "Open the SSH sync settings and save the connection again.",
+            ));
+            None
+        }
+    };
+    if let Some(connection) = active_connection {
+        let name = connection.name.clone();
+        let result = crate::coding::ssh::ssh_test_connection(connection).await;
+        checks.push(if result.connected {
+            HealthCheck::pass("ssh", name)
+        } else {
+            HealthCheck::fail(
+                "ssh",
+                format!(
+                    "{}: {}",
+                    name,
+                    result.error.unwrap_or_else(|| "not connected".to_string())
+                ),
+                "Check the host, port, user and key of the active SSH connection.",
+            )
+        });
+    }
+
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_map_probe_results_to_status_and_hint() {
+        let writability = |status: &str| WritabilityResult {
+            tool: "claude".to_string(),
+            directory: "/home/u/.claude".to_string(),
+            status: status.to_string(),
+            error: None,
+        };
+        assert_eq!(config_dir_check(&writability("writable")).status, "pass");
+        assert_eq!(config_dir_check(&writability("missing")).status, "pass");
+        let not_writable = config_dir_check(&writability("not_writable"));
+        assert_eq!(not_writable.id, "config_dir:claude");
+        assert_eq!(not_writable.status, "fail");
+        assert!(not_writable.hint.is_some());
+
+        let tool = |installed: bool, on_path: bool, version: Option<&str>| InstalledToolInfo {
+            tool: "codex".to_string(),
+            command: "codex".to_string(),
+            installed,
+            on_path,
+            path: installed.then(|| "/usr/bin/codex".to_string()),
+            version: version.map(str::to_string),
+        };
+        assert!(tool_check(&tool(false, false, None)).is_none());
+        assert_eq!(
+            tool_check(&tool(true, true, Some("0.40.0")))
+                .unwrap()
+                .status,
+            "pass"
+        );
+        assert_eq!(
+            tool_check(&tool(true, false, Some("0.40.0")))
+                .unwrap()
+                .status,
+            "warn"
+        );
+        assert_eq!(tool_check(&tool(true, true, None)).unwrap().status, "warn");
+
+        assert_eq!(
+            webdav_error_message(
+                r#"{"type":"auth","message":"401 Unauthorized","suggestion":"x"}"#
+            ),
+            "401 Unauthorized"
+        );
+        assert_eq!(webdav_error_message("plain"), "plain");
+    }
+}
@@ -24,6 +24,7 @@
 //! ```
 
 use reqwest::{Client, Proxy};
+use serde::Serialize;
 use std::time::Duration;
 
 use crate::db::SqliteDbState;
@@ -100,7 +101,7 @@ pub async fn client_with_timeout(
     timeout_secs: u64,
 ) -> Result<Client, String> {
     let (proxy_mode, proxy_url) = get_proxy_from_settings(db_state).await?;
-    build_client(proxy_mode, &proxy_url, timeout_secs, false)
+    build_client(proxy_mode, &proxy_url, timeout_secs, false, false)
 }
 
 /// Create an HTTP client with custom timeout and disabled automatic response decompression.
@@ -112,7 +113,46 @@ pub async fn client_with_timeout_no_compression(
     timeout_secs: u64,
 ) -> Result<Client, String> {
     let (proxy_mode, proxy_url) = get_proxy_from_settings(db_state).await?;
-    build_client(proxy_mode, &proxy_url, timeout_secs, true)
+    build_client(proxy_mode, &proxy_url, timeout_secs, true, false)
+}
+
+/// Create an HTTP client that skips TLS certificate verification for one provider.
+///
+/// Only for providers whose `allow_insecure_tls` flag was explicitly enabled (e.g. internal
+/// gateways with self-signed certificates). Never use it as a fallback after a TLS error;
+/// every call is logged as a warning so the relaxed client is always visible in the logs.
+pub async fn client_with_timeout_insecure_tls(
+    db_state: &SqliteDbState,
+    timeout_secs: u64,
+    provider_label: &str,
+) -> Result<Client, String> {
+    log::warn!(
+        "TLS certificate verification is DISABLED for provider '{}' (allow_insecure_tls opt-in)",
+        provider_label
+    );
+    let (proxy_mode, proxy_url) = get_proxy_from_settings(db_state).await?;
+    build_client(proxy_mode, &proxy_url, timeout_secs, false, true)
+}
+
+/// Create an HTTP client that routes through `proxy_url` instead of the global proxy setting.
+///
+/// For one-off requests that carry their own proxy (e.g. model fetching through a provider
+/// specific proxy). The URL must use an http, https, socks5 or socks5h scheme; a URL without
+/// a scheme is treated as http.
+pub fn client_with_proxy_override(
+    proxy_url: &str,
+    timeout_secs: u64,
+    accept_invalid_certs: bool,
+) -> Result<Client, String> {
+    let proxy_url = proxy_url.trim();
+    validate_proxy_scheme(proxy_url)?;
+    build_client(
+        ProxyMode::Custom,
+        proxy_url,
+        timeout_secs,
+        false,
+        accept_invalid_certs,
+    )
 }
 
 /// Build an HTTP client with explicit proxy URL.
@@ -124,6 +164,7 @@ pub async fn client_with_timeout_no_compression(
 /// * `proxy_url` - Proxy URL (e.g., "http://proxy.com:8080" or "socks5://proxy.com:1080")
 ///                 Only used when proxy_mode is custom
 /// * `timeout_secs` - Request timeout in seconds
+/// * `accept_invalid_certs` - Skip certificate verification (per-provider opt-in only)
 ///
 /// # Returns
 /// A configured reqwest::Client
@@ -137,11 +178,16 @@ fn build_client(
     proxy_url: &str,
     timeout_secs: u64,
     disable_content_decoding: bool,
+    accept_invalid_certs: bool,
 ) -> Result<Client, String> {
     let mut builder = Client::builder()
         .use_rustls_tls()
         .timeout(Duration::from_secs(timeout_secs));
 
+    if accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
     if disable_content_decoding {
         builder = builder.no_gzip().no_brotli().no_zstd().no_deflate();
     }
@@ -190,38 +236,237 @@ pub fn create_client_no_proxy(timeout_secs: u64) -> Result<Client, String> {
         .map_err(|e| format!("Failed to create HTTP client: {}", e))
 }
 
-/// Test proxy connectivity by making a request to a test URL.
-///
-/// This function is used by the settings page to validate proxy configuration.
-///
-/// # Arguments
-/// * `proxy_url` - Proxy URL to test
+/// Upper bound on retries for `send_with_retry` (3 attempts in total).
+pub const MAX_TRANSIENT_RETRIES: u32 = 2;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// Longest `Retry-After` wait honored before retrying
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// Statuses worth retrying: rate limits and gateway/overload errors.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 502 | 503 | 504)
+}
+
+/// Connection failures and resets; everything else (TLS, invalid URL, timeout) fails fast.
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    if error.is_connect() {
+        return true;
+    }
+    let mut source = std::error::Error::source(error);
+    while let Some(inner) = source {
+        if let Some(io_error) = inner.downcast_ref::<std::io::Error>() {
+            if matches!(
+                io_error.kind(),
+                std::io::ErrorKind::ConnectionReset | std::io::ErrorKind::ConnectionAborted
+            ) {
+                return true;
+            }
+        }
+        source = inner.source();
+    }
+    false
+}
+
+/// Wait before retry `attempt` (1-based): the server's `Retry-After` seconds when given,
+/// otherwise exponential backoff from `RETRY_BASE_DELAY`.
+fn retry_delay(attempt: u32, retry_after: Option<&reqwest::header::HeaderValue>) -> Duration {
+    retry_after
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(|secs| Duration::from_secs(secs).min(MAX_RETRY_AFTER))
+        .unwrap_or_else(|| RETRY_BASE_DELAY * 2u32.pow(attempt.saturating_sub(1)))
+}
+
+/// Send a request, retrying up to `max_retries` times (capped at `MAX_TRANSIENT_RETRIES`)
+/// on 429/502/503/504 responses and connection failures.
 ///
-/// # Returns
-/// Ok(()) if connection successful, Err with message otherwise
-pub async fn test_proxy(proxy_url: &str) -> Result<(), String> {
-    if proxy_url.is_empty() {
-        return Err("Proxy URL is empty".to_string());
+/// The last response is returned as is, so callers keep their own status handling and
+/// non-retryable statuses such as 400/401 come back after a single attempt. Requests whose
+/// body cannot be cloned are sent once.
+pub async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+    max_retries: u32,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let max_retries = max_retries.min(MAX_TRANSIENT_RETRIES);
+    let mut attempt = 0;
+    loop {
+        let retry_request = if attempt < max_retries {
+            request.try_clone()
+        } else {
+            None
+        };
+        let Some(next_request) = retry_request else {
+            return request.send().await;
+        };
+        attempt += 1;
+        let delay = match next_request.send().await {
+            Ok(response) if is_retryable_status(response.status()) => {
+                let delay = retry_delay(
+                    attempt,
+                    response.headers().get(reqwest::header::RETRY_AFTER),
+                );
+                log::info!(
+                    "Retrying request after HTTP {} in {:?} (attempt {}/{})",
+                    response.status(),
+                    delay,
+                    attempt + 1,
+                    max_retries + 1
+                );
+                delay
+            }
+            Err(error) if is_retryable_error(&error) => {
+                let delay = retry_delay(attempt, None);
+                log::info!(
+                    "Retrying request after connection error in {:?} (attempt {}/{}): {}",
+                    delay,
+                    attempt + 1,
+                    max_retries + 1,
+                    error
+                );
+                delay
+            }
+            result => return result,
+        };
+        tokio::time::sleep(delay).await;
     }
+}
 
-    // Create client with custom proxy mode
-    let client = build_client(ProxyMode::Custom, proxy_url, 10, false)?;
+/// Default ip-echo endpoint for proxy tests; answers `{"origin": "<exit ip>"}`.
+pub const DEFAULT_PROXY_ECHO_URL: &str = "https://httpbin.org/ip";
+/// Per-request timeout of a proxy test probe
+const PROXY_PROBE_TIMEOUT_SECS: u64 = 8;
+/// Upper bound for the whole proxy test, both probes included
+const PROXY_TEST_TIMEOUT: Duration = Duration::from_secs(12);
 
-    // Test with httpbin.org - it's designed for testing HTTP clients
+/// Outcome of a proxy test: HTTPS through a CONNECT tunnel and plain HTTP forwarding,
+/// both against the same ip-echo endpoint.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyTestResult {
+    pub echo_url: String,
+    /// Round trip of the HTTPS request through the proxy
+    pub latency_ms: Option<u64>,
+    /// Address the echo endpoint saw, i.e. the proxy's exit IP
+    pub exit_ip: Option<String>,
+    pub https_ok: bool,
+    pub https_error: Option<String>,
+    pub http_ok: bool,
+    pub http_error: Option<String>,
+}
+
+/// Pull the caller IP out of an ip-echo response: JSON with `origin` (httpbin), `ip`
+/// (ipify) or `query` (ip-api), or a bare address as plain text.
+fn parse_exit_ip(body: &str) -> Option<String> {
+    let body = body.trim();
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(body) {
+        return ["origin", "ip", "query"]
+            .iter()
+            .find_map(|key| value.get(key).and_then(|v| v.as_str()))
+            .map(first_listed_address)
+            .filter(|ip| !ip.is_empty());
+    }
+    let first = first_listed_address(body);
+    first
+        .parse::<std::net::IpAddr>()
+        .ok()
+        .map(|ip| ip.to_string())
+}
+
+/// httpbin lists every hop ("a, b"); the first is the client as seen by the endpoint.
+fn first_listed_address(addresses: &str) -> String {
+    addresses.split(',').next().unwrap_or("").trim().to_string()
+}
+
+/// One GET through the proxy; returns the latency and the exit IP found in the body.
+async fn probe_echo_endpoint(client: &Client, url: &str) -> Result<(u64, Option<String>), String> {
+    let started = std::time::Instant::now();
     let response = client
-        .get("https://httpbin.org/ip")
+        .get(url)
         .send()
         .await
         .map_err(|e| format!("Proxy connection failed: {}", e))?;
-
-    if response.status().is_success() {
-        Ok(())
-    } else {
-        Err(format!(
+    let latency_ms = started.elapsed().as_millis() as u64;
+    if !response.status().is_success() {
+        return Err(format!(
             "Proxy test failed with status: {}",
             response.status()
-        ))
+        ));
+    }
+    let body = response.text().await.unwrap_or_default();
+    Ok((latency_ms, parse_exit_ip(&body)))
+}
+
+/// Test a proxy against an ip-echo endpoint (`echo_url`, default `DEFAULT_PROXY_ECHO_URL`).
+///
+/// Requests the endpoint over HTTPS, which needs CONNECT tunneling, and over plain HTTP,
+/// which the proxy forwards itself, concurrently and bounded by `PROXY_TEST_TIMEOUT`.
+/// Fails only when neither request gets through.
+pub async fn test_proxy(
+    proxy_url: &str,
+    echo_url: Option<&str>,
+) -> Result<ProxyTestResult, String> {
+    if proxy_url.is_empty() {
+        return Err("Proxy URL is empty".to_string());
+    }
+    let echo_url = echo_url
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .unwrap_or(DEFAULT_PROXY_ECHO_URL);
+    let host_and_path = echo_url
+        .strip_prefix("https://")
+        .or_else(|| echo_url.strip_prefix("http://"))
+        .ok_or_else(|| format!("Echo URL must start with http:// or https://: {}", echo_url))?;
+    let https_url = format!("https://{}", host_and_path);
+    let http_url = format!("http://{}", host_and_path);
+
+    // Create client with custom proxy mode
+    let client = build_client(
+        ProxyMode::Custom,
+        proxy_url,
+        PROXY_PROBE_TIMEOUT_SECS,
+        false,
+        false,
+    )?;
+    let (https, http) = tokio::time::timeout(PROXY_TEST_TIMEOUT, async {
+        tokio::join!(
+            probe_echo_endpoint(&client, &https_url),
+            probe_echo_endpoint(&client, &http_url)
+        )
+    })
+    .await
+    .map_err(|_| {
+        format!(
+            "Proxy test timed out after {} seconds",
+            PROXY_TEST_TIMEOUT.as_secs()
+        )
+    })?;
+
+    let mut result = ProxyTestResult {
+        echo_url: echo_url.to_string(),
+        ..Default::default()
+    };
+    match https {
+        Ok((latency_ms, exit_ip)) => {
+            result.https_ok = true;
+            result.latency_ms = Some(latency_ms);
+            result.exit_ip = exit_ip;
+        }
+        Err(error) => result.https_error = Some(error),
+    }
+    match http {
+        Ok((_, exit_ip)) => {
+            result.http_ok = true;
+            if result.exit_ip.is_none() {
+                result.exit_ip = exit_ip;
+            }
+        }
+        Err(error) => result.http_error = Some(error),
     }
+
+    if !result.https_ok && !result.http_ok {
+        return Err(result.https_error.unwrap_or_default());
+    }
+    Ok(result)
 }
 
 /// Read proxy settings from database.
@@ -281,6 +526,23 @@ fn normalize_proxy_url(url: &str) -> String {
     }
 }
 
+/// Reject proxy URLs with a scheme `build_proxy` cannot use (e.g. "ftp://").
+fn validate_proxy_scheme(url: &str) -> Result<(), String> {
+    if url.is_empty() {
+        return Err("Proxy URL is empty".to_string());
+    }
+    match url.split_once("://") {
+        Some((scheme, _)) => match scheme.to_lowercase().as_str() {
+            "http" | "https" | "socks5" | "socks5h" => Ok(()),
+            _ => Err(format!(
+                "Unsupported proxy scheme '{}'; expected http, https, socks5 or socks5h",
+                scheme
+            )),
+        },
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,4 +598,55 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_some());
     }
+
+    #[test]
+    fn test_proxy_override_validates_scheme() {
+        assert!(validate_proxy_scheme("socks5h://proxy.example.com:1080").is_ok());
+        assert!(validate_proxy_scheme("proxy.example.com:8080").is_ok());
+        assert!(validate_proxy_scheme("ftp://proxy.example.com:21").is_err());
+        assert!(validate_proxy_scheme("").is_err());
+        assert!(client_with_proxy_override(" http://proxy.example.com:8080 ", 5, false).is_ok());
+    }
+
+    #[test]
+    fn retry_policy_honors_retry_after_and_backs_off() {
+        use reqwest::header::HeaderValue;
+        use reqwest::StatusCode;
+
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+
+        assert_eq!(retry_delay(1, None), RETRY_BASE_DELAY);
+        assert_eq!(retry_delay(2, None), RETRY_BASE_DELAY * 2);
+        let retry_after = HeaderValue::from_static("3");
+        assert_eq!(retry_delay(1, Some(&retry_after)), Duration::from_secs(3));
+        let huge = HeaderValue::from_static("3600");
+        assert_eq!(retry_delay(1, Some(&huge)), MAX_RETRY_AFTER);
+        let date = HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(retry_delay(2, Some(&date)), RETRY_BASE_DELAY * 2);
+    }
+
+    #[test]
+    fn parse_exit_ip_reads_common_echo_formats() {
+        assert_eq!(
+            parse_exit_ip(r#"{ "origin": "203.0.113.7" }"#).as_deref(),
+            Some("203.0.113.7")
+        );
+        assert_eq!(
+            parse_exit_ip(r#"{"ip":"2001:db8::1"}"#).as_deref(),
+            Some("2001:db8::1")
+        );
+        assert_eq!(
+            parse_exit_ip("198.51.100.2, 10.0.0.1\n").as_deref(),
+            Some("198.51.100.2")
+        );
+        assert_eq!(
+            parse_exit_ip(r#"{"origin": "203.0.113.7, 10.0.0.1"}"#).as_deref(),
+            Some("203.0.113.7")
+        );
+        assert_eq!(parse_exit_ip("<html>blocked</html>"), None);
+        assert_eq!(parse_exit_ip(r#"{"status":"fail"}"#), None);
+    }
 }
@@ -3,12 +3,14 @@ use tauri::{Emitter, Listener, Manager};
 
 use std::fs;
 use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
 use surrealdb::engine::local::SurrealKv;
 use surrealdb::Surreal;
 #[cfg(not(test))]
-use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tauri_plugin_dialog::{
+    DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult,
+};
 
 #[cfg(all(test, target_os = "windows"))]
 #[link(name = "resource", kind = "static")]
@@ -29,7 +31,11 @@ pub mod auto_launch;
 pub mod coding;
 pub mod db;
 pub mod db_migration;
+pub mod diagnostics;
+pub mod events;
+pub mod health_check;
 pub mod http_client;
+pub mod recent_logs;
 pub mod settings;
 pub mod single_instance;
 pub mod tray;
@@ -43,6 +49,9 @@ pub(crate) static APP_EXIT_REQUESTED: AtomicBool = AtomicBool::new(false);
 const AI_TOOLBOX_LATEST_RELEASE_URL: &str =
     "https://github.com/coulsontl/ai-toolbox/releases/latest";
 
+/// Quiet period after the last `config-changed` event before the tray menu is rebuilt.
+const TRAY_REFRESH_DEBOUNCE: Duration = Duration::from_millis(300);
+
 async fn open_legacy_surreal_database(
     db_path: &Path,
 ) -> Result<Surreal<surrealdb::engine::local::Db>, String> {
@@ -174,15 +183,133 @@ fn prepare_startup_migration_state(
     Ok(StartupMigrationState::NeedsSurrealImport)
 }
 
-/// Set window background color (affects macOS titlebar color)
-#[tauri::command]
-fn set_window_background_color(window: tauri::Window, r: u8, g: u8, b: u8) -> Result<(), String> {
+/// Offer to restore the newest local snapshot or start with an empty database when the
+/// SQLite main database cannot be opened, then restart. The damaged files are kept aside.
+#[cfg(not(test))]
+fn show_database_recovery_dialog(
+    app_handle: &tauri::AppHandle,
+    db_path: std::path::PathBuf,
+    app_data_dir: &Path,
+    open_error: String,
+) {
+    use db::recovery::{
+        find_latest_snapshot, recover_database, recovery_snapshot_dirs, RecoveryAction,
+    };
+
+    const RESTORE_LABEL: &str = "从备份恢复";
+    const FRESH_LABEL: &str = "重新开始";
+    const EXIT_LABEL: &str = "退出";
+
+    let snapshot = find_latest_snapshot(&recovery_snapshot_dirs(&db_path, app_data_dir));
+    let (message, buttons) = match &snapshot {
+        Some(snapshot) => (
+            format!(
+                "数据库无法打开，可能已损坏。\n\n错误：{}\n\n可以从最近的本地快照恢复：{}\n或重新开始使用空数据库。原数据库文件会被保留。",
+                open_error,
+                snapshot.display()
+            ),
+            MessageDialogButtons::YesNoCancelCustom(
+                RESTORE_LABEL.to_string(),
+                FRESH_LABEL.to_string(),
+                EXIT_LABEL.to_string(),
+            ),
+        ),
+        None => (
+            format!(
+                "数据库无法打开，可能已损坏。\n\n错误：{}\n\n没有找到可用的本地快照，可以重新开始使用空数据库，之后再从备份恢复。原数据库文件会被保留。",
+                open_error
+            ),
+            MessageDialogButtons::OkCancelCustom(FRESH_LABEL.to_string(), EXIT_LABEL.to_string()),
+        ),
+    };
+
+    let dialog_app_handle = app_handle.clone();
+    app_handle
+        .dialog()
+        .message(message)
+        .title("AI Toolbox 数据库损坏")
+        .kind(MessageDialogKind::Error)
+        .buttons(buttons)
+        .show_with_result(move |result| {
+            let action = match (result, snapshot) {
+                (MessageDialogResult::Yes, Some(snapshot)) => {
+                    RecoveryAction::RestoreSnapshot(snapshot)
+                }
+                (MessageDialogResult::Custom(label), Some(snapshot)) if label == RESTORE_LABEL => {
+                    RecoveryAction::RestoreSnapshot(snapshot)
+                }
+                (MessageDialogResult::Custom(label), _) if label == FRESH_LABEL => {
+                    RecoveryAction::StartFresh
+                }
+                (MessageDialogResult::No, Some(_)) | (MessageDialogResult::Ok, None) => {
+                    RecoveryAction::StartFresh
+                }
+                _ => {
+                    info!("用户取消数据库恢复，退出应用");
+                    dialog_app_handle.exit(1);
+                    return;
+                }
+            };
+
+            error!(
+                "数据库打开失败，原始错误: {}; 恢复操作: {:?}",
+                open_error, action
+            );
+            match recover_database(&db_path, &action) {
+                Ok(quarantined) => {
+                    info!(
+                        "数据库恢复完成，原数据库已保留在 {:?}，正在重启",
+                        quarantined
+                    );
+                    dialog_app_handle.restart();
+                }
+                Err(error) => {
+                    error!("数据库恢复失败: {}", error);
+                    dialog_app_handle.exit(1);
+                }
+            }
+        });
+}
+
+/// Apply the window background color on every platform; on macOS it also tints the titlebar.
+fn apply_window_background_color<R: tauri::Runtime>(
+    window: &tauri::WebviewWindow<R>,
+    color: settings::types::WindowBackgroundColor,
+) -> Result<(), String> {
     use tauri::window::Color;
     window
-        .set_background_color(Some(Color(r, g, b, 255)))
+        .set_background_color(Some(Color(color.r, color.g, color.b, 255)))
         .map_err(|e| format!("Failed to set background color: {}", e))
 }
 
+/// Set window background color (affects macOS titlebar color). Unless `persist` is false
+/// (theme defaults), the color is kept and `setup()` re-applies it before the window shows.
+#[tauri::command]
+async fn set_window_background_color(
+    window: tauri::WebviewWindow,
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    r: u8,
+    g: u8,
+    b: u8,
+    persist: Option<bool>,
+) -> Result<(), String> {
+    let color = settings::types::WindowBackgroundColor { r, g, b };
+    let mut app_settings = settings::store::load_settings_from_sqlite_state(&sqlite_state)?;
+    if persist.unwrap_or(true) && app_settings.window_background_color != Some(color) {
+        app_settings.window_background_color = Some(color);
+        settings::store::save_settings_to_sqlite_state(&sqlite_state, &app_settings)?;
+    }
+    apply_window_background_color(&window, color)
+}
+
+/// Window background color saved by `set_window_background_color`, if any
+#[tauri::command]
+async fn get_window_background_color(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<Option<settings::types::WindowBackgroundColor>, String> {
+    Ok(settings::store::load_settings_from_sqlite_state(&sqlite_state)?.window_background_color)
+}
+
 /// Open a folder in the system file manager
 /// If the path is a file, opens the parent directory
 /// Creates the directory if it doesn't exist
@@ -223,6 +350,255 @@ fn open_existing_folder(path: String) -> Result<(), String> {
     open_folder_in_file_manager(folder)
 }
 
+/// Save a WebKitGTK webview workaround level (0..=4, clamped) to the level file and restart the
+/// app with it. Only returns when saving or the restart failed; on other platforms it is an error.
+#[tauri::command]
+fn set_wayland_webview_workaround_level(app: tauri::AppHandle, level: u8) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        let level = level.min(WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL);
+        info!(
+            "Restarting with WebKitGTK webview workaround level {} (requested by user)",
+            level
+        );
+        let lock_holder = app.state::<SingleInstanceLockHolder>();
+        let result = restart_with_wayland_webview_workaround_level(
+            level,
+            &lock_holder.0,
+            &wayland_webview_workaround_env_to_clear(level),
+        );
+        // The restart failed after the lock was released; take it back for this instance.
+        if let (Ok(lock), Ok(mut guard)) =
+            (single_instance::try_acquire_lock(), lock_holder.0.lock())
+        {
+            *guard = Some(lock);
+        }
+        result
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (app, level);
+        Err("WebKitGTK workaround levels only apply on Linux".to_string())
+    }
+}
+
+/// WebKitGTK workaround level this process runs with (0..=4), falling back to the saved level
+/// before the startup decision is recorded. Set it with `set_wayland_webview_workaround_level`.
+#[tauri::command]
+fn get_wayland_webview_workaround_level(app: tauri::AppHandle) -> Result<u8, String> {
+    #[cfg(target_os = "linux")]
+    {
+        Ok(app
+            .try_state::<WaylandWebviewWorkaroundDecision>()
+            .map(|decision| decision.level)
+            .unwrap_or_else(read_wayland_webview_workaround_level))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = app;
+        Err("WebKitGTK workaround levels only apply on Linux".to_string())
+    }
+}
+
+/// Linux webview rendering environment, for attaching to white-screen bug reports
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinuxRenderReport {
+    pub app_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel_release: Option<String>,
+    /// "wayland" | "x11"
+    pub session_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xdg_session_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_desktop: Option<String>,
+    pub is_appimage: bool,
+    pub is_flatpak: bool,
+    /// The AppImage re-exec with the system libwayland-client has run
+    pub appimage_wayland_preload_applied: bool,
+    /// Level this process runs with; None if the workaround was not set up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workaround_level: Option<u8>,
+    pub workaround_disabled: bool,
+    /// Level saved for the next launch, which auto-downgrade may have raised
+    pub saved_workaround_level: u8,
+    /// False when the EGL stderr monitor is not running (auto-downgrade off)
+    pub egl_failure_monitored: bool,
+    pub egl_failure_detected: bool,
+    /// WebKitGTK / GDK / workaround variables
+    pub webkit_env: std::collections::BTreeMap<String, String>,
+    /// Mesa / NVIDIA / VA-API variables that hint at the GPU driver in use
+    pub gpu_env: std::collections::BTreeMap<String, String>,
+}
+
+/// EGL failure flag shared with the stderr monitor; None when auto-downgrade is off
+#[cfg(target_os = "linux")]
+struct LinuxEglFailureFlag(Option<Arc<std::sync::atomic::AtomicBool>>);
+
+#[cfg(target_os = "linux")]
+const LINUX_RENDER_WEBKIT_ENV_PREFIXES: &[&str] = &[
+    "WEBKIT_",
+    "GDK_",
+    "GTK_",
+    "AI_TOOLBOX_WAYLAND_",
+    "AI_TOOLBOX_DISABLE_WAYLAND_",
+    "AI_TOOLBOX_ENABLE_WAYLAND_",
+    "LD_PRELOAD",
+    "WAYLAND_DISPLAY",
+    "DISPLAY",
+];
+
+#[cfg(target_os = "linux")]
+const LINUX_RENDER_GPU_ENV_PREFIXES: &[&str] = &[
+    "LIBGL_",
+    "MESA_",
+    "__GLX_",
+    "__NV_",
+    "__EGL_",
+    "EGL_PLATFORM",
+    "GBM_BACKEND",
+    "DRI_PRIME",
+    "LIBVA_DRIVER_NAME",
+    "VDPAU_DRIVER",
+];
+
+/// Variables whose name starts with one of `prefixes`, sorted by name
+#[cfg(target_os = "linux")]
+fn env_vars_with_prefixes(
+    vars: impl Iterator<Item = (String, String)>,
+    prefixes: &[&str],
+) -> std::collections::BTreeMap<String, String> {
+    vars.filter(|(key, _)| prefixes.iter().any(|prefix| key.starts_with(prefix)))
+        .collect()
+}
+
+/// Collect the Linux webview rendering environment: session type, WebKitGTK and GPU driver
+/// variables, AppImage/Flatpak packaging, the workaround level and EGL failure detection.
+#[tauri::command]
+fn linux_render_diagnostics(app: tauri::AppHandle) -> Result<LinuxRenderReport, String> {
+    #[cfg(target_os = "linux")]
+    {
+        let decision = app.try_state::<WaylandWebviewWorkaroundDecision>();
+        let egl_failure_flag = app
+            .try_state::<LinuxEglFailureFlag>()
+            .and_then(|flag| flag.0.clone());
+        let session_type = if is_wayland_session() {
+            "wayland"
+        } else {
+            "x11"
+        };
+        Ok(LinuxRenderReport {
+            app_version: app.package_info().version.to_string(),
+            kernel_release: fs::read_to_string("/proc/sys/kernel/osrelease")
+                .ok()
+                .map(|release| release.trim().to_string()),
+            session_type: session_type.to_string(),
+            xdg_session_type: std::env::var("XDG_SESSION_TYPE").ok(),
+            current_desktop: std::env::var("XDG_CURRENT_DESKTOP").ok(),
+            is_appimage: is_appimage_runtime(),
+            is_flatpak: std::env::var_os("FLATPAK_ID").is_some()
+                || Path::new("/.flatpak-info").exists(),
+            appimage_wayland_preload_applied: std::env::var_os(
+                APPIMAGE_WAYLAND_PRELOAD_APPLIED_ENV,
+            )
+            .is_some(),
+            workaround_level: decision.as_ref().map(|decision| decision.level),
+            workaround_disabled: decision.as_ref().is_some_and(|decision| decision.disabled),
+            saved_workaround_level: read_wayland_webview_workaround_level(),
+            egl_failure_monitored: egl_failure_flag.is_some(),
+            egl_failure_detected: egl_failure_flag
+                .is_some_and(|flag| flag.load(std::sync::atomic::Ordering::Relaxed)),
+            webkit_env: env_vars_with_prefixes(std::env::vars(), LINUX_RENDER_WEBKIT_ENV_PREFIXES),
+            gpu_env: env_vars_with_prefixes(std::env::vars(), LINUX_RENDER_GPU_ENV_PREFIXES),
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = app;
+        Err("Linux render diagnostics are only available on Linux".to_string())
+    }
+}
+
+/// Describe which factors picked this process's WebKitGTK workaround level: session type,
+/// AppImage detection, build type, saved level and environment overrides.
+#[tauri::command]
+fn explain_wayland_workaround(app: tauri::AppHandle) -> Result<String, String> {
+    #[cfg(target_os = "linux")]
+    {
+        let decision = app
+            .try_state::<WaylandWebviewWorkaroundDecision>()
+            .ok_or_else(|| "WebKitGTK workaround level has not been decided yet".to_string())?;
+        Ok(format!(
+            "{}\nWorkaround environment:\n{}",
+            decision.explain(),
+            wayland_webview_workaround_env_report()
+        ))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = app;
+        Ok("Not applicable: WebKitGTK workaround levels only apply on Linux".to_string())
+    }
+}
+
+/// Saved frontend-ready timeout in seconds (Linux only); `None` means the watchdog uses
+/// `AI_TOOLBOX_FRONTEND_READY_TIMEOUT_SECS` or the 20s default.
+#[tauri::command]
+fn get_frontend_ready_timeout_secs() -> Result<Option<u64>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        Ok(read_saved_frontend_ready_timeout_secs())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("The frontend-ready watchdog only runs on Linux".to_string())
+    }
+}
+
+/// Save how long the WebKitGTK watchdog waits for `frontend-ready` (5..=300s) before raising
+/// the workaround level; `None` clears it. Takes effect on the next start.
+#[tauri::command]
+fn set_frontend_ready_timeout_secs(secs: Option<u64>) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    {
+        let path = frontend_ready_timeout_path()
+            .ok_or_else(|| "Failed to resolve the runtime directory".to_string())?;
+        let Some(secs) = secs else {
+            return match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(format!("Failed to clear frontend-ready timeout: {}", e))
+                }
+                _ => Ok(()),
+            };
+        };
+        if !FRONTEND_READY_TIMEOUT_RANGE_SECS.contains(&secs) {
+            return Err(format!(
+                "Frontend-ready timeout must be between {} and {} seconds",
+                FRONTEND_READY_TIMEOUT_RANGE_SECS.start(),
+                FRONTEND_READY_TIMEOUT_RANGE_SECS.end()
+            ));
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create runtime directory: {}", e))?;
+        }
+        fs::write(&path, secs.to_string())
+            .map_err(|e| format!("Failed to save frontend-ready timeout: {}", e))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = secs;
+        Err("The frontend-ready watchdog only runs on Linux".to_string())
+    }
+}
+
 fn open_folder_in_file_manager(folder: &Path) -> Result<(), String> {
     #[cfg(target_os = "windows")]
     {
@@ -261,12 +637,15 @@ fn init_logging() -> Option<std::path::PathBuf> {
             .set_max_level(LevelFilter::Warn)
             .add_filter_allow_str("ai_toolbox")
             .build();
-        if TermLogger::init(
-            LevelFilter::Debug,
-            config,
-            TerminalMode::Mixed,
-            ColorChoice::Auto,
-        )
+        if CombinedLogger::init(vec![
+            TermLogger::new(
+                LevelFilter::Debug,
+                config,
+                TerminalMode::Mixed,
+                ColorChoice::Auto,
+            ),
+            recent_logs::RecentLogBuffer::new(LevelFilter::Debug),
+        ])
         .is_err()
         {
             eprintln!("日志系统初始化失败");
@@ -275,11 +654,7 @@ fn init_logging() -> Option<std::path::PathBuf> {
     }
 
     // 正式版本：日志写入文件
-    let log_dir = dirs::data_dir()
-        .map(|p| p.join("com.ai-toolbox").join("logs"))
-        .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox").join("logs")));
-
-    let log_dir = match log_dir {
+    let log_dir = match get_log_dir() {
         Some(dir) => dir,
         None => return None,
     };
@@ -309,12 +684,31 @@ fn init_logging() -> Option<std::path::PathBuf> {
         .add_filter_allow_str("ai_toolbox")
         .build();
 
-    if CombinedLogger::init(vec![WriteLogger::new(LevelFilter::Info, file_config, file)]).is_err() {
+    if CombinedLogger::init(vec![
+        WriteLogger::new(LevelFilter::Info, file_config, file),
+        recent_logs::RecentLogBuffer::new(LevelFilter::Info),
+    ])
+    .is_err()
+    {
         eprintln!("日志系统初始化失败");
         return None;
     }
 
-    // 清理旧日志文件（保留最近 7 天）
+    // 旧日志清理依赖 log_retention_days 设置，在数据库初始化后执行
+    Some(log_file)
+}
+
+pub(crate) fn get_log_dir() -> Option<std::path::PathBuf> {
+    dirs::data_dir()
+        .map(|p| p.join("com.ai-toolbox").join("logs"))
+        .or_else(|| dirs::home_dir().map(|p| p.join(".ai-toolbox").join("logs")))
+}
+
+/// 清理旧日志文件（每天一个文件，保留最近 `retention_days` 天）
+pub(crate) fn cleanup_old_log_files(retention_days: u32) {
+    let Some(log_dir) = get_log_dir() else {
+        return;
+    };
     if let Ok(entries) = fs::read_dir(&log_dir) {
         let mut log_files: Vec<_> = entries
             .filter_map(|e| e.ok())
@@ -328,12 +722,10 @@ fn init_logging() -> Option<std::path::PathBuf> {
 
         log_files.sort_by_key(|e| std::cmp::Reverse(e.path()));
 
-        for old_log in log_files.into_iter().skip(7) {
+        for old_log in log_files.into_iter().skip(retention_days as usize) {
             let _ = fs::remove_file(old_log.path());
         }
     }
-
-    Some(log_file)
 }
 
 /// 设置 panic hook，将 panic 信息写入日志
@@ -542,6 +934,41 @@ fn maybe_reexec_appimage_with_system_wayland_client() {
 #[cfg(target_os = "linux")]
 const WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL: u8 = 4;
 
+/// How long the watchdog waits for `frontend-ready` before raising the workaround level
+#[cfg(target_os = "linux")]
+const FRONTEND_READY_TIMEOUT_DEFAULT_SECS: u64 = 20;
+/// Accepted range for the saved timeout; `AI_TOOLBOX_FRONTEND_READY_TIMEOUT_SECS` takes any
+/// positive value, as it always has
+#[cfg(target_os = "linux")]
+const FRONTEND_READY_TIMEOUT_RANGE_SECS: std::ops::RangeInclusive<u64> = 5..=300;
+
+/// Environment variable, value and the workaround level that first sets it
+#[cfg(target_os = "linux")]
+const WAYLAND_WEBVIEW_WORKAROUND_ENV: [(&str, &str, u8); 4] = [
+    ("WEBKIT_DISABLE_DMABUF_RENDERER", "1", 1),
+    ("WEBKIT_DISABLE_GPU_PROCESS", "1", 2),
+    ("WEBKIT_DISABLE_COMPOSITING_MODE", "1", 3),
+    ("GDK_BACKEND", "x11", 4),
+];
+
+/// File-based single-instance lock, shared so a restart can release it first
+#[cfg(target_os = "linux")]
+struct SingleInstanceLockHolder(Arc<StdMutex<Option<single_instance::SingleInstanceLock>>>);
+
+/// Variables a restarted instance must not inherit to run at `level`: workaround variables of
+/// higher levels and the level override, which would ignore the level file.
+#[cfg(target_os = "linux")]
+fn wayland_webview_workaround_env_to_clear(level: u8) -> Vec<&'static str> {
+    WAYLAND_WEBVIEW_WORKAROUND_ENV
+        .iter()
+        .filter(|(_, _, min_level)| *min_level > level)
+        .map(|(key, _, _)| *key)
+        .chain(std::iter::once(
+            "AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL",
+        ))
+        .collect()
+}
+
 #[cfg(target_os = "linux")]
 fn wayland_webview_workaround_level_path() -> Option<std::path::PathBuf> {
     let base_dir = dirs::data_dir()
@@ -570,14 +997,57 @@ fn read_wayland_webview_workaround_level() -> u8 {
 }
 
 #[cfg(target_os = "linux")]
-fn write_wayland_webview_workaround_level(level: u8) {
-    let Some(path) = wayland_webview_workaround_level_path() else {
-        return;
-    };
+fn write_wayland_webview_workaround_level(level: u8) -> Result<(), String> {
+    let path = wayland_webview_workaround_level_path()
+        .ok_or_else(|| "Failed to resolve the runtime directory".to_string())?;
     if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create runtime directory: {}", e))?;
     }
-    let _ = fs::write(&path, level.to_string());
+    fs::write(&path, level.to_string())
+        .map_err(|e| format!("Failed to save WebKitGTK workaround level: {}", e))
+}
+
+/// Stored next to the workaround level: the watchdog starts before SQLite is opened.
+#[cfg(target_os = "linux")]
+fn frontend_ready_timeout_path() -> Option<std::path::PathBuf> {
+    wayland_webview_workaround_level_path()
+        .map(|path| path.with_file_name("frontend_ready_timeout_secs"))
+}
+
+#[cfg(target_os = "linux")]
+fn parse_frontend_ready_timeout_secs(raw: &str) -> Option<u64> {
+    raw.trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|secs| FRONTEND_READY_TIMEOUT_RANGE_SECS.contains(secs))
+}
+
+#[cfg(target_os = "linux")]
+fn read_saved_frontend_ready_timeout_secs() -> Option<u64> {
+    let raw = fs::read_to_string(frontend_ready_timeout_path()?).ok()?;
+    parse_frontend_ready_timeout_secs(&raw)
+}
+
+/// Any positive whole number of seconds; anything else is ignored with a warning.
+#[cfg(target_os = "linux")]
+fn parse_frontend_ready_timeout_env(raw: &str) -> Option<u64> {
+    let secs = raw.trim().parse::<u64>().ok().filter(|secs| *secs > 0);
+    if secs.is_none() {
+        warn!(
+            "Ignoring AI_TOOLBOX_FRONTEND_READY_TIMEOUT_SECS={:?}: expected a positive number of seconds",
+            raw
+        );
+    }
+    secs
+}
+
+/// Saved setting first, then `AI_TOOLBOX_FRONTEND_READY_TIMEOUT_SECS`, then the default.
+#[cfg(target_os = "linux")]
+fn resolve_frontend_ready_timeout_secs(saved: Option<u64>, env_value: Option<&str>) -> u64 {
+    saved
+        .or_else(|| env_value.and_then(parse_frontend_ready_timeout_env))
+        .unwrap_or(FRONTEND_READY_TIMEOUT_DEFAULT_SECS)
 }
 
 #[cfg(target_os = "linux")]
@@ -675,6 +1145,35 @@ fn setup_linux_wayland_egl_failure_monitor(
     egl_failure_flag
 }
 
+/// Persist `level`, release the single-instance lock and relaunch with the same arguments.
+/// Exits the process once the new instance is spawned, so it only returns on failure.
+#[cfg(target_os = "linux")]
+fn restart_with_wayland_webview_workaround_level(
+    level: u8,
+    single_instance_lock_holder: &StdMutex<Option<single_instance::SingleInstanceLock>>,
+    env_to_clear: &[&str],
+) -> Result<(), String> {
+    write_wayland_webview_workaround_level(level)?;
+
+    if let Ok(mut guard) = single_instance_lock_holder.lock() {
+        let _ = guard.take();
+    }
+
+    let current_exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to get current executable for restart: {}", e))?;
+    let args: Vec<std::ffi::OsString> = std::env::args_os().skip(1).collect();
+
+    let mut command = std::process::Command::new(&current_exe);
+    command.args(args).env("AI_TOOLBOX_RESTART_WAIT_LOCK", "1");
+    for key in env_to_clear {
+        command.env_remove(key);
+    }
+    match command.spawn() {
+        Ok(_) => std::process::exit(0),
+        Err(e) => Err(format!("Failed to spawn restarted instance: {}", e)),
+    }
+}
+
 #[cfg(target_os = "linux")]
 fn start_linux_wayland_webview_auto_downgrade_watchdog(
     app_handle: tauri::AppHandle,
@@ -699,10 +1198,12 @@ fn start_linux_wayland_webview_auto_downgrade_watchdog(
             let _ = ready_tx.send(true);
         });
 
-        let timeout_secs = std::env::var("AI_TOOLBOX_FRONTEND_READY_TIMEOUT_SECS")
-            .ok()
-            .and_then(|v| v.trim().parse::<u64>().ok())
-            .unwrap_or(20);
+        let timeout_secs = resolve_frontend_ready_timeout_secs(
+            read_saved_frontend_ready_timeout_secs(),
+            std::env::var("AI_TOOLBOX_FRONTEND_READY_TIMEOUT_SECS")
+                .ok()
+                .as_deref(),
+        );
         let timeout = Duration::from_secs(timeout_secs);
         let start = std::time::Instant::now();
 
@@ -740,34 +1241,14 @@ fn start_linux_wayland_webview_auto_downgrade_watchdog(
                     );
                 }
 
-                write_wayland_webview_workaround_level(next_level);
-
-                if let Ok(mut guard) = single_instance_lock_holder.lock() {
-                    let _ = guard.take();
-                }
-
-                let current_exe = match std::env::current_exe() {
-                    Ok(p) => p,
-                    Err(e) => {
-                        error!("Failed to get current executable for restart: {}", e);
-                        return;
-                    }
-                };
-                let args: Vec<std::ffi::OsString> = std::env::args_os().skip(1).collect();
-
-                match std::process::Command::new(&current_exe)
-                    .args(args)
-                    .env("AI_TOOLBOX_RESTART_WAIT_LOCK", "1")
-                    .spawn()
-                {
-                    Ok(_) => {
-                        std::process::exit(0);
-                    }
-                    Err(e) => {
-                        error!("Failed to spawn restarted instance: {}", e);
-                        return;
-                    }
+                if let Err(e) = restart_with_wayland_webview_workaround_level(
+                    next_level,
+                    &single_instance_lock_holder,
+                    &[],
+                ) {
+                    error!("{}", e);
                 }
+                return;
             }
 
             tokio::select! {
@@ -795,38 +1276,35 @@ fn start_linux_wayland_webview_auto_downgrade_watchdog(
 /// - Release builds default to level 0 and may auto-downgrade on failure.
 /// - Set `AI_TOOLBOX_DISABLE_WAYLAND_WEBVIEW_WORKAROUND=1` to opt out of both mitigations.
 /// - Set `AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL=0..4` to override.
+/// - The auto-downgrade watchdog waits for `frontend-ready` for the timeout saved with
+///   `set_frontend_ready_timeout_secs`, else `AI_TOOLBOX_FRONTEND_READY_TIMEOUT_SECS`, else 20s.
+/// - `set_wayland_webview_workaround_level` writes the level file and restarts, for forcing a level
+///   from the UI; `get_wayland_webview_workaround_level` reports the level in use.
+/// - `explain_wayland_workaround` reports which of these factors picked the current level.
 #[cfg(target_os = "linux")]
-fn setup_linux_wayland_webview_workaround() -> u8 {
-    if std::env::var_os("AI_TOOLBOX_DISABLE_WAYLAND_WEBVIEW_WORKAROUND").is_some() {
+fn setup_linux_wayland_webview_workaround() -> WaylandWebviewWorkaroundDecision {
+    let decision = WaylandWebviewWorkaroundDecision::resolve(
+        std::env::var_os("AI_TOOLBOX_DISABLE_WAYLAND_WEBVIEW_WORKAROUND").is_some(),
+        is_wayland_session(),
+        is_appimage_runtime(),
+        cfg!(debug_assertions),
+        std::env::var("AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL").ok(),
+        read_wayland_webview_workaround_level(),
+    );
+    if decision.disabled {
         info!(
             "WebKitGTK webview workaround disabled via AI_TOOLBOX_DISABLE_WAYLAND_WEBVIEW_WORKAROUND"
         );
-        return 0;
+        return decision;
     }
 
-    let session_type = if is_wayland_session() {
+    let session_type = if decision.is_wayland {
         "Wayland"
     } else {
         "X11"
     };
-
-    let appimage_min_level = if !cfg!(debug_assertions) && is_appimage_runtime() {
-        1
-    } else {
-        0
-    };
-
-    let level = std::env::var("AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL")
-        .ok()
-        .and_then(|v| v.trim().parse::<u8>().ok())
-        .map(|v| v.min(WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL))
-        .unwrap_or_else(|| {
-            if cfg!(debug_assertions) {
-                WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL
-            } else {
-                read_wayland_webview_workaround_level().max(appimage_min_level)
-            }
-        });
+    let level = decision.level;
+    let appimage_min_level = decision.appimage_min_level();
 
     if appimage_min_level > 0 && level == appimage_min_level {
         info!(
@@ -836,17 +1314,10 @@ fn setup_linux_wayland_webview_workaround() -> u8 {
     }
 
     let mut changed = false;
-    if level >= 1 {
-        changed |= set_env_if_missing("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
-    }
-    if level >= 2 {
-        changed |= set_env_if_missing("WEBKIT_DISABLE_GPU_PROCESS", "1");
-    }
-    if level >= 3 {
-        changed |= set_env_if_missing("WEBKIT_DISABLE_COMPOSITING_MODE", "1");
-    }
-    if level >= 4 {
-        changed |= set_env_if_missing("GDK_BACKEND", "x11");
+    for (key, value, min_level) in WAYLAND_WEBVIEW_WORKAROUND_ENV {
+        if level >= min_level {
+            changed |= set_env_if_missing(key, value);
+        }
     }
 
     if level == 0 {
@@ -870,7 +1341,141 @@ fn setup_linux_wayland_webview_workaround() -> u8 {
         info!("Level 4: Falling back to X11 backend via GDK_BACKEND=x11 (requires XWayland)");
     }
 
-    level
+    decision
+}
+
+/// Inputs that picked the WebKitGTK workaround level at startup, and the level they gave.
+/// Managed as app state so `explain_wayland_workaround` reports what this process used,
+/// even after the level file has been rewritten for the next launch.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone)]
+struct WaylandWebviewWorkaroundDecision {
+    disabled: bool,
+    is_wayland: bool,
+    is_appimage: bool,
+    debug_build: bool,
+    /// Raw `AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL` value
+    env_override: Option<String>,
+    persisted_level: u8,
+    level: u8,
+}
+
+#[cfg(target_os = "linux")]
+impl WaylandWebviewWorkaroundDecision {
+    fn resolve(
+        disabled: bool,
+        is_wayland: bool,
+        is_appimage: bool,
+        debug_build: bool,
+        env_override: Option<String>,
+        persisted_level: u8,
+    ) -> Self {
+        let mut decision = Self {
+            disabled,
+            is_wayland,
+            is_appimage,
+            debug_build,
+            env_override,
+            persisted_level,
+            level: 0,
+        };
+        decision.level = if disabled {
+            0
+        } else {
+            decision.env_override_level().unwrap_or_else(|| {
+                if debug_build {
+                    WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL
+                } else {
+                    persisted_level.max(decision.appimage_min_level())
+                }
+            })
+        };
+        decision
+    }
+
+    fn env_override_level(&self) -> Option<u8> {
+        self.env_override
+            .as_deref()
+            .and_then(|v| v.trim().parse::<u8>().ok())
+            .map(|v| v.min(WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL))
+    }
+
+    /// Release AppImage builds start at level 1 at least
+    fn appimage_min_level(&self) -> u8 {
+        if !self.debug_build && self.is_appimage {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn explain(&self) -> String {
+        let mut lines = vec![format!(
+            "WebKitGTK webview workaround level: {} (0 = none, {} = X11 fallback)",
+            self.level, WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL
+        )];
+        let reason = if self.disabled {
+            "AI_TOOLBOX_DISABLE_WAYLAND_WEBVIEW_WORKAROUND is set, so no workaround is applied"
+                .to_string()
+        } else if let Some(level) = self.env_override_level() {
+            format!(
+                "AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL overrides everything else (level {})",
+                level
+            )
+        } else if self.debug_build {
+            "debug builds always use the highest level".to_string()
+        } else if self.appimage_min_level() > self.persisted_level {
+            format!(
+                "AppImage release builds use at least level {}",
+                self.appimage_min_level()
+            )
+        } else if self.persisted_level > 0 {
+            "the saved level from a previous auto-downgrade or manual choice".to_string()
+        } else {
+            "default level for release builds".to_string()
+        };
+        lines.push(format!("Decided by: {}", reason));
+        lines.push(format!(
+            "- Session type: {}",
+            if self.is_wayland { "Wayland" } else { "X11" }
+        ));
+        lines.push(format!(
+            "- AppImage runtime: {}",
+            if self.is_appimage { "yes" } else { "no" }
+        ));
+        lines.push(format!(
+            "- Build: {}",
+            if self.debug_build { "debug" } else { "release" }
+        ));
+        lines.push(format!("- Saved level: {}", self.persisted_level));
+        lines.push(match self.env_override.as_deref() {
+            Some(raw) if self.env_override_level().is_none() => format!(
+                "- AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL: {:?} (ignored, not a number)",
+                raw
+            ),
+            Some(raw) => format!("- AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL: {:?}", raw),
+            None => "- AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL: unset".to_string(),
+        });
+        lines.push(format!(
+            "- AI_TOOLBOX_DISABLE_WAYLAND_WEBVIEW_WORKAROUND: {}",
+            if self.disabled { "set" } else { "unset" }
+        ));
+        lines.join("\n")
+    }
+}
+
+/// Current values of the workaround variables in this process, whether set by the
+/// workaround or inherited from the launching environment
+#[cfg(target_os = "linux")]
+fn wayland_webview_workaround_env_report() -> String {
+    WAYLAND_WEBVIEW_WORKAROUND_ENV
+        .iter()
+        .map(|(key, _, min_level)| {
+            let value = std::env::var(key).unwrap_or_else(|_| "unset".to_string());
+            format!("- {}={} (level {}+)", key, value, min_level)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -895,7 +1500,9 @@ pub fn run() {
     maybe_reexec_appimage_with_system_wayland_client();
 
     #[cfg(target_os = "linux")]
-    let wayland_webview_workaround_level = setup_linux_wayland_webview_workaround();
+    let wayland_webview_workaround = setup_linux_wayland_webview_workaround();
+    #[cfg(target_os = "linux")]
+    let wayland_webview_workaround_level = wayland_webview_workaround.level;
 
     #[cfg(target_os = "linux")]
     let auto_downgrade_enabled = std::env::var_os("AI_TOOLBOX_DISABLE_WAYLAND_WEBVIEW_WORKAROUND")
@@ -970,6 +1577,13 @@ pub fn run() {
             info!("开始执行 setup()...");
             let app_handle = app.handle().clone();
 
+            #[cfg(target_os = "linux")]
+            app.manage(SingleInstanceLockHolder(single_instance_lock_holder.clone()));
+            #[cfg(target_os = "linux")]
+            app.manage(wayland_webview_workaround.clone());
+            #[cfg(target_os = "linux")]
+            app.manage(LinuxEglFailureFlag(egl_failure_flag.clone()));
+
             #[cfg(target_os = "linux")]
             if auto_downgrade_enabled {
                 start_linux_wayland_webview_auto_downgrade_watchdog(
@@ -1102,6 +1716,17 @@ pub fn run() {
                             return Ok(());
                         }
                     }
+                    #[cfg(not(test))]
+                    {
+                        show_database_recovery_dialog(
+                            &app_handle,
+                            migration_paths.sqlite_database_file.clone(),
+                            &app_data_dir,
+                            e,
+                        );
+                        return Ok(());
+                    }
+                    #[cfg(test)]
                     panic!("Failed to initialize SQLite database: {}", e);
                 }
             };
@@ -1145,6 +1770,23 @@ pub fn run() {
                 {
                     warn!("Gemini CLI 默认配置初始化失败: {}", e);
                 }
+                let renumbered = settings::commands::normalize_all_sort_indexes(&db_state);
+                if renumbered > 0 {
+                    info!("已整理 {} 条记录的 sort_index", renumbered);
+                }
+
+                let startup_settings =
+                    settings::store::load_settings_from_sqlite_state(&db_state).ok();
+                let log_retention_days = startup_settings
+                    .as_ref()
+                    .map(|settings| settings.log_retention_days)
+                    .unwrap_or(settings::types::DEFAULT_LOG_RETENTION_DAYS);
+                coding::sync_trace::set_sync_trace_enabled(
+                    startup_settings.is_some_and(|settings| settings.sync_trace),
+                );
+                cleanup_old_log_files(
+                    log_retention_days.clamp(1, settings::types::MAX_LOG_RETENTION_DAYS),
+                );
 
                 app.manage(db_state);
                 info!("SQLite 主数据库状态已注册到应用");
@@ -1184,6 +1826,24 @@ pub fn run() {
                 ));
                 app.manage(ssh_session);
                 info!("SSH 会话状态已注册到应用");
+
+                // Kiosk baseline: restore the configured MCP snapshot before the window shows
+                let sqlite_state = app_handle.state::<SqliteDbState>();
+                let boot_snapshot_id = settings::store::load_settings_from_sqlite_state(&sqlite_state)
+                    .ok()
+                    .and_then(|settings| settings.boot_snapshot_id);
+                if let Some(snapshot_id) = boot_snapshot_id {
+                    match coding::mcp::mcp_restore_snapshot_internal(
+                        app_handle.clone(),
+                        &sqlite_state,
+                        &snapshot_id,
+                    )
+                    .await
+                    {
+                        Ok(_) => info!("启动快照已恢复: {}", snapshot_id),
+                        Err(e) => warn!("启动快照 {} 恢复失败，保持当前状态: {}", snapshot_id, e),
+                    }
+                }
             });
 
             // Create system tray
@@ -1199,6 +1859,9 @@ pub fn run() {
             tauri::async_runtime::spawn(async move {
                 let value = app_handle_clone.clone();
                 let value_for_closure = value.clone();
+                // Trailing-edge debounce: a burst of events rebuilds the tray once, after the
+                // last event, so the menu always reflects the final state
+                let tray_refresh_generation = Arc::new(AtomicU64::new(0));
                 let _listener = value.listen("config-changed", move |_event| {
                     let app = value_for_closure.app_handle().clone();
                     if let Some(gateway_state) =
@@ -1208,8 +1871,13 @@ pub fn run() {
                             warn!("Failed to clear proxy gateway provider cache: {error}");
                         }
                     }
+                    let generation = tray_refresh_generation.fetch_add(1, Ordering::SeqCst) + 1;
+                    let latest_generation = Arc::clone(&tray_refresh_generation);
                     let _ = tauri::async_runtime::spawn(async move {
-                        let _ = tray::refresh_tray_menus(&app).await;
+                        tokio::time::sleep(TRAY_REFRESH_DEBOUNCE).await;
+                        if latest_generation.load(Ordering::SeqCst) == generation {
+                            let _ = tray::refresh_tray_menus(&app).await;
+                        }
                     });
                 });
 
@@ -1220,22 +1888,31 @@ pub fn run() {
             // Enable auto-launch if setting is true, and handle start_minimized
             let app_handle_clone = app_handle.clone();
             tauri::async_runtime::spawn(async move {
-                let start_minimized = {
+                let (start_minimized, background_color) = {
                     let sqlite_state = app_handle_clone.state::<SqliteDbState>();
                     match settings::store::load_settings_from_sqlite_state(&sqlite_state) {
                         Ok(settings) => {
                             if settings.launch_on_startup {
                                 let _ = auto_launch::enable_auto_launch();
                             }
-                            settings.start_minimized
+                            (settings.start_minimized, settings.window_background_color)
                         }
                         Err(error) => {
                             warn!("读取启动设置失败: {}", error);
-                            false
+                            (false, None)
                         }
                     }
                 }; // db lock released here
 
+                // Re-apply the saved background color before the window is shown
+                if let (Some(color), Some(window)) =
+                    (background_color, app_handle_clone.get_webview_window("main"))
+                {
+                    if let Err(error) = apply_window_background_color(&window, color) {
+                        warn!("恢复窗口背景色失败: {}", error);
+                    }
+                }
+
                 // Show window unless start_minimized is enabled
                 if !start_minimized {
                     if let Some(window) = app_handle_clone.get_webview_window("main") {
@@ -1521,6 +2198,33 @@ pub fn run() {
                 });
             }
 
+            // Optional reachability check of the applied providers (off by default)
+            {
+                let app_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(8)).await;
+                    coding::model_validation::check_applied_providers_on_startup(&app_clone)
+                        .await;
+                });
+            }
+
+            // Periodic provider health checks (behind provider_health_monitoring)
+            {
+                let app_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(30)).await;
+                    coding::provider_health::run_provider_health_monitor(app_clone).await;
+                });
+            }
+
+            // Report external edits to managed config files to the UI
+            {
+                let app_clone = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    coding::config_watcher::run_config_file_watcher(app_clone).await;
+                });
+            }
+
             // Restore SSH session from saved config on cold start without triggering full sync.
             {
                 let app_ssh_restore = app_handle.clone();
@@ -1550,14 +2254,20 @@ pub fn run() {
             // SSH sync listeners (all platforms)
             {
                 // SSH sync request listeners (module-specific)
-                // SSH: 定时健康检查（每60秒）
+                // SSH: 定时健康检查（间隔取 SSHSyncConfig.health_check_interval_secs，默认 60 秒）
                 let app_ssh_health = app_handle.clone();
                 tauri::async_runtime::spawn(async move {
                     // 等待启动同步完成
                     tokio::time::sleep(Duration::from_secs(10)).await;
 
+                    // 只在状态真正变化时发 ssh-connection-status
+                    let mut last_status = "connected";
                     loop {
-                        tokio::time::sleep(Duration::from_secs(60)).await;
+                        let tuning = {
+                            let db_state = app_ssh_health.state::<crate::SqliteDbState>();
+                            coding::ssh::load_ssh_connection_tuning(&db_state).unwrap_or_default()
+                        };
+                        tokio::time::sleep(tuning.health_check_interval()).await;
 
                         let session_state = app_ssh_health.state::<coding::ssh::SshSessionState>();
                         let mut session = session_state.0.lock().await;
@@ -1567,16 +2277,25 @@ pub fn run() {
                             continue;
                         }
 
-                        if !session.is_alive() {
+                        let status = if session.is_alive() {
+                            "connected"
+                        } else {
                             log::info!("SSH 健康检查：连接已断开，尝试重连...");
-                            if let Err(e) = session.ensure_connected().await {
-                                log::warn!("SSH 重连失败: {}", e);
-                                let _ =
-                                    app_ssh_health.emit("ssh-connection-status", "disconnected");
-                            } else {
-                                log::info!("SSH 重连成功");
-                                let _ = app_ssh_health.emit("ssh-connection-status", "connected");
+                            session.set_reconnect_backoff_max(tuning.reconnect_backoff_max());
+                            match session.ensure_connected().await {
+                                Ok(()) => {
+                                    log::info!("SSH 重连成功");
+                                    "connected"
+                                }
+                                Err(e) => {
+                                    log::warn!("SSH 重连失败: {}", e);
+                                    "disconnected"
+                                }
                             }
+                        };
+                        if status != last_status {
+                            let _ = app_ssh_health.emit("ssh-connection-status", status);
+                            last_status = status;
                         }
                     }
                 });
@@ -1819,10 +2538,21 @@ pub fn run() {
             // Common
             open_folder,
             open_existing_folder,
+            get_frontend_ready_timeout_secs,
+            set_frontend_ready_timeout_secs,
+            set_wayland_webview_workaround_level,
+            get_wayland_webview_workaround_level,
+            explain_wayland_workaround,
+            recent_logs::get_recent_logs,
+            diagnostics::export_diagnostics,
+            health_check::run_health_check,
+            linux_render_diagnostics,
             set_window_background_color,
+            get_window_background_color,
             // Update
             update::check_for_updates,
             update::install_update,
+            events::list_emitted_events,
             // Settings
             settings::get_settings,
             settings::save_settings,
@@ -1830,8 +2560,19 @@ pub fn run() {
             settings::list_backup_file_filter_path_options,
             settings::set_auto_launch,
             settings::get_auto_launch_status,
+            settings::set_auto_sync_on_apply,
+            settings::get_schema_version,
+            settings::get_log_retention_days,
+            settings::set_log_retention_days,
+            settings::get_config_sync_mode,
+            settings::set_config_sync_mode,
+            settings::get_providers_show_in_tray,
+            settings::set_providers_show_in_tray,
+            settings::get_boot_snapshot_id,
+            settings::set_boot_snapshot_id,
             settings::restart_app,
             settings::test_proxy_connection,
+            settings::normalize_sort_indexes,
             // Proxy Gateway
             coding::proxy_gateway::proxy_gateway_get_settings,
             coding::proxy_gateway::proxy_gateway_update_settings,
@@ -1848,6 +2589,8 @@ pub fn run() {
             coding::proxy_gateway::proxy_gateway_disengage_failover,
             coding::proxy_gateway::proxy_gateway_restore_cli_direct,
             coding::proxy_gateway::proxy_gateway_switch_primary_provider,
+            coding::proxy_gateway::get_fallback_provider,
+            coding::proxy_gateway::set_fallback_provider,
             coding::proxy_gateway::proxy_gateway_stop_preflight,
             coding::proxy_gateway::proxy_gateway_request_logs,
             coding::proxy_gateway::proxy_gateway_request_log_detail,
@@ -1868,18 +2611,31 @@ pub fn run() {
             // Backup - Local
             settings::backup::backup_database,
             settings::backup::restore_database,
+            settings::backup::verify_backup,
+            settings::backup::raw_db::db_export_all,
+            settings::backup::raw_db::db_import_all,
+            settings::backup::config_bundle::export_config_bundle,
+            settings::backup::config_bundle::import_config_bundle,
+            settings::backup::import_plan::validate_import,
+            settings::backup::import_plan::commit_import,
             settings::backup::get_database_path,
+            settings::backup::compact_database,
             settings::backup::open_app_data_dir,
             // Backup - WebDAV
             settings::backup::backup_to_webdav,
+            settings::backup::cancel_webdav_backup,
             settings::backup::list_webdav_backups,
             settings::backup::restore_from_webdav,
+            settings::backup::verify_webdav_backup,
             settings::backup::test_webdav_connection,
             settings::backup::delete_webdav_backup,
             // Claude Code
             coding::claude_code::list_claude_providers,
             coding::claude_code::create_claude_provider,
+            coding::claude_code::merge_claude_providers,
+            coding::claude_code::duplicate_claude_provider,
             coding::claude_code::update_claude_provider,
+            coding::claude_code::validate_claude_provider_config,
             coding::claude_code::delete_claude_provider,
             coding::claude_code::reorder_claude_providers,
             coding::claude_code::select_claude_provider,
@@ -1890,6 +2646,8 @@ pub fn run() {
             coding::claude_code::read_claude_settings,
             coding::claude_code::apply_claude_config,
             coding::claude_code::toggle_claude_code_provider_disabled,
+            coding::claude_code::set_claude_provider_pinned,
+            coding::claude_code::set_claude_provider_models,
             coding::claude_code::get_claude_common_config,
             coding::claude_code::extract_claude_common_config_from_current_file,
             coding::claude_code::save_claude_common_config,
@@ -1920,6 +2678,11 @@ pub fn run() {
             coding::claude_code::update_claude_plugin_user_scope,
             coding::claude_code::uninstall_claude_plugin_user_scope,
             coding::claude_code::get_claude_onboarding_status,
+            coding::claude_code::claude_full_preview,
+            coding::claude_code::preview_claude_config,
+            coding::claude_code::restore_claude_settings_backup,
+            coding::claude_code::reset_claude_settings,
+            coding::claude_code::claude_setup_selftest,
             coding::claude_code::apply_claude_onboarding_skip,
             coding::claude_code::clear_claude_onboarding_skip,
             // Preset Models
@@ -1933,15 +2696,33 @@ pub fn run() {
             coding::open_code::get_opencode_config_path_info,
             coding::open_code::read_opencode_config,
             coding::open_code::save_opencode_config,
+            coding::open_code::duplicate_opencode_provider,
+            coding::open_code::restore_opencode_config_backup,
+            coding::open_code::reset_opencode_config,
             coding::open_code::list_opencode_markdown_agents,
             coding::open_code::save_opencode_markdown_agent,
             coding::open_code::delete_opencode_markdown_agent,
             coding::open_code::get_opencode_common_config,
             coding::open_code::save_opencode_common_config,
             coding::open_code::fetch_provider_models,
+            coding::open_code::fetch_provider_models_cached,
+            coding::model_validation::validate_provider_models,
+            coding::model_validation::check_applied_providers_reachable,
+            coding::provider_health::get_provider_health_history,
+            coding::apply_history::undo_last_apply,
+            coding::model_benchmark::benchmark_model_across_providers,
+            coding::pinned_providers::list_pinned_providers,
+            coding::global_search::search_all,
+            coding::tls_inspect::inspect_provider_tls,
+            coding::tls_inspect::set_provider_allow_insecure_tls,
             coding::open_code::get_opencode_free_models,
             coding::open_code::get_provider_models,
             coding::open_code::get_opencode_unified_models,
+            coding::open_code::get_opencode_model_preferences,
+            coding::open_code::set_opencode_model_order,
+            coding::open_code::opencode_hide_model,
+            coding::open_code::opencode_show_model,
+            coding::open_code::opencode_set_preferred_model_provider,
             coding::open_code::get_opencode_auth_providers,
             coding::open_code::get_opencode_auth_config_path,
             coding::open_code::backup_opencode_config,
@@ -1951,6 +2732,8 @@ pub fn run() {
             coding::open_code::delete_opencode_favorite_plugin,
             coding::open_code::list_opencode_favorite_providers,
             coding::open_code::upsert_opencode_favorite_provider,
+            coding::open_code::set_opencode_favorite_provider_pinned,
+            coding::open_code::opencode_adopt_auth_provider,
             coding::open_code::delete_opencode_favorite_provider,
             coding::open_code::list_opencode_all_api_hub_providers,
             coding::open_code::resolve_opencode_all_api_hub_providers,
@@ -1976,6 +2759,14 @@ pub fn run() {
             coding::all_api_hub::get_all_api_hub_provider_models,
             coding::cc_switch::has_cc_switch_db,
             coding::cc_switch::list_cc_switch_providers,
+            coding::setup_script::export_setup_script,
+            coding::config_provenance::get_config_provenance,
+            coding::config_writability::test_config_writability,
+            coding::secret_store::migrate_secrets_to_encrypted,
+            coding::tool_limits::check_tool_limits,
+            coding::provider_csv::import_providers_from_csv,
+            coding::global_headers::get_global_headers,
+            coding::global_headers::set_global_headers,
             // Magic Context
             coding::magic_context::read_magic_context_config,
             coding::magic_context::save_magic_context_config,
@@ -2019,6 +2810,8 @@ pub fn run() {
             coding::codex::copy_codex_official_account_token,
             coding::codex::fetch_codex_official_models,
             coding::codex::create_codex_provider,
+            coding::codex::duplicate_codex_provider,
+            coding::codex::import_codex_config_toml,
             coding::codex::update_codex_provider,
             coding::codex::delete_codex_provider,
             coding::codex::repair_codex_providers,
@@ -2026,7 +2819,10 @@ pub fn run() {
             coding::codex::select_codex_provider,
             coding::codex::apply_codex_config,
             coding::codex::toggle_codex_provider_disabled,
+            coding::codex::set_codex_provider_pinned,
             coding::codex::read_codex_settings,
+            coding::codex::restore_codex_config_backup,
+            coding::codex::reset_codex_config,
             coding::codex::get_codex_common_config,
             coding::codex::extract_codex_common_config_from_current_file,
             coding::codex::save_codex_common_config,
@@ -2169,6 +2965,7 @@ pub fn run() {
             coding::oh_my_openagent::delete_oh_my_openagent_config,
             coding::oh_my_openagent::clear_oh_my_openagent_applied_config,
             coding::oh_my_openagent::apply_oh_my_openagent_config,
+            coding::oh_my_openagent::preview_oh_my_opencode_merge,
             coding::oh_my_openagent::reorder_oh_my_openagent_configs,
             coding::oh_my_openagent::toggle_oh_my_openagent_config_disabled,
             coding::oh_my_openagent::get_oh_my_openagent_config_path_info,
@@ -2203,7 +3000,11 @@ pub fn run() {
             coding::wsl::wsl_delete_file_mapping,
             coding::wsl::wsl_reset_file_mappings,
             coding::wsl::wsl_sync,
+            coding::wsl::apply_provider_to_wsl,
+            coding::wsl::wsl_compare_config,
             coding::wsl::wsl_get_status,
+            coding::wsl::wsl_get_distro_statuses,
+            coding::wsl::wsl_get_detailed_status,
             coding::wsl::wsl_test_path,
             coding::wsl::wsl_get_default_mappings,
             coding::wsl::wsl_open_terminal,
@@ -2221,12 +3022,19 @@ pub fn run() {
             coding::ssh::ssh_update_file_mapping,
             coding::ssh::ssh_delete_file_mapping,
             coding::ssh::ssh_reset_file_mappings,
+            coding::sync_mappings::export_sync_mappings,
+            coding::sync_mappings::import_sync_mappings,
+            coding::sync_performance::get_sync_performance,
+            coding::sync_trace::get_sync_trace,
             coding::ssh::ssh_sync,
+            coding::ssh::ssh_list_sync_conflicts,
+            coding::ssh::ssh_resolve_conflict,
             coding::ssh::ssh_get_status,
             coding::ssh::ssh_test_local_path,
             coding::ssh::ssh_get_default_mappings,
             // Skills Hub
             coding::skills::skills_get_tool_status,
+            coding::skills::refresh_tool_detection,
             coding::skills::skills_get_central_repo_path,
             coding::skills::skills_set_central_repo_path,
             coding::skills::skills_get_default_central_repo_path,
@@ -2247,12 +3055,15 @@ pub fn run() {
             coding::skills::skills_unsync_from_tool,
             coding::skills::skills_update_managed,
             coding::skills::skills_delete_managed,
+            coding::skills::skills_find_duplicates,
+            coding::skills::skills_merge_duplicates,
             coding::skills::skills_get_onboarding_plan,
             coding::skills::skills_import_existing,
             coding::skills::skills_get_git_cache_cleanup_days,
             coding::skills::skills_set_git_cache_cleanup_days,
             coding::skills::skills_get_git_cache_ttl_secs,
             coding::skills::skills_clear_git_cache,
+            coding::cache_clear::clear_all_caches,
             coding::skills::skills_get_git_cache_path,
             coding::skills::skills_get_preferred_tools,
             coding::skills::skills_set_preferred_tools,
@@ -2289,28 +3100,46 @@ pub fn run() {
             coding::skills::skills_resync_all,
             // MCP Servers
             coding::mcp::mcp_list_servers,
+            coding::mcp::mcp_list_servers_by_tag,
+            coding::mcp::mcp_list_all_tags,
             coding::mcp::mcp_resolve_package_versions,
             coding::mcp::mcp_create_server,
             coding::mcp::mcp_update_server,
             coding::mcp::mcp_delete_server,
             coding::mcp::mcp_toggle_tool,
+            coding::mcp::mcp_set_tool_for_all,
             coding::mcp::mcp_reorder_servers,
             coding::mcp::mcp_update_metadata,
             coding::mcp::mcp_sync_to_tool,
             coding::mcp::mcp_sync_all,
+            coding::mcp::mcp_snapshot,
+            coding::mcp::mcp_restore_snapshot,
             coding::mcp::mcp_import_from_tool,
+            coding::mcp::mcp_import_from_text,
+            coding::mcp::mcp_check_server_command,
+            coding::mcp::mcp_test_server,
+            coding::mcp::mcp_get_sync_drift,
+            coding::mcp::mcp_reconcile_codex,
             coding::mcp::mcp_get_tools,
+            coding::mcp::detect_shared_config_paths,
             coding::mcp::mcp_scan_servers,
+            coding::mcp::mcp_find_orphans,
+            coding::mcp::mcp_remove_orphans,
             coding::mcp::mcp_get_show_in_tray,
             coding::mcp::mcp_set_show_in_tray,
             coding::mcp::mcp_get_preferred_tools,
             coding::mcp::mcp_set_preferred_tools,
             coding::mcp::mcp_get_limit_add_more_to_preferred_tools,
             coding::mcp::mcp_set_limit_add_more_to_preferred_tools,
+            coding::mcp::mcp_get_env_vars,
+            coding::mcp::mcp_set_env_vars,
             coding::mcp::mcp_get_sync_disabled_to_opencode,
             coding::mcp::mcp_set_sync_disabled_to_opencode,
             coding::mcp::mcp_add_custom_tool,
+            coding::mcp::validate_custom_tool_config_file,
             coding::mcp::mcp_remove_custom_tool,
+            coding::tools::register_tools_from_manifest,
+            coding::tools::cli_versions::detect_installed_tools,
             // MCP Favorites
             coding::mcp::mcp_list_favorites,
             coding::mcp::mcp_upsert_favorite,
@@ -2360,6 +3189,121 @@ pub fn run() {
 mod linux_startup_tests {
     use super::*;
 
+    #[test]
+    fn forced_workaround_level_clears_higher_level_env() {
+        assert_eq!(
+            wayland_webview_workaround_env_to_clear(1),
+            vec![
+                "WEBKIT_DISABLE_GPU_PROCESS",
+                "WEBKIT_DISABLE_COMPOSITING_MODE",
+                "GDK_BACKEND",
+                "AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL",
+            ]
+        );
+        assert_eq!(
+            wayland_webview_workaround_env_to_clear(WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL),
+            vec!["AI_TOOLBOX_WAYLAND_WEBVIEW_WORKAROUND_LEVEL"]
+        );
+    }
+
+    #[test]
+    fn workaround_decision_explains_the_deciding_factor() {
+        let appimage = WaylandWebviewWorkaroundDecision::resolve(false, true, true, false, None, 0);
+        assert_eq!(appimage.level, 1);
+        assert!(appimage
+            .explain()
+            .contains("Decided by: AppImage release builds use at least level 1"));
+
+        let saved = WaylandWebviewWorkaroundDecision::resolve(false, true, true, false, None, 3);
+        assert_eq!(saved.level, 3);
+        assert!(saved.explain().contains("Decided by: the saved level"));
+
+        let overridden = WaylandWebviewWorkaroundDecision::resolve(
+            false,
+            false,
+            false,
+            false,
+            Some("9".to_string()),
+            2,
+        );
+        assert_eq!(overridden.level, WAYLAND_WEBVIEW_WORKAROUND_MAX_LEVEL);
+        assert!(overridden
+            .explain()
+            .contains("overrides everything else (level 4)"));
+
+        let invalid_override = WaylandWebviewWorkaroundDecision::resolve(
+            false,
+            false,
+            false,
+            false,
+            Some("high".to_string()),
+            2,
+        );
+        assert_eq!(invalid_override.level, 2);
+        assert!(invalid_override
+            .explain()
+            .contains("(ignored, not a number)"));
+
+        let disabled = WaylandWebviewWorkaroundDecision::resolve(
+            true,
+            true,
+            true,
+            true,
+            Some("3".to_string()),
+            2,
+        );
+        assert_eq!(disabled.level, 0);
+        assert!(disabled.explain().contains("no workaround is applied"));
+    }
+
+    #[test]
+    fn render_diagnostics_pick_env_vars_by_prefix() {
+        let vars = [
+            ("WEBKIT_DISABLE_DMABUF_RENDERER", "1"),
+            ("__NV_PRIME_RENDER_OFFLOAD", "1"),
+            ("MESA_LOADER_DRIVER_OVERRIDE", "iris"),
+            ("HOME", "/home/user"),
+            ("OPENAI_API_KEY", "sk-secret"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()));
+
+        let gpu = env_vars_with_prefixes(vars.clone(), LINUX_RENDER_GPU_ENV_PREFIXES);
+        assert_eq!(
+            gpu.keys().collect::<Vec<_>>(),
+            vec!["MESA_LOADER_DRIVER_OVERRIDE", "__NV_PRIME_RENDER_OFFLOAD"]
+        );
+        let webkit = env_vars_with_prefixes(vars, LINUX_RENDER_WEBKIT_ENV_PREFIXES);
+        assert_eq!(
+            webkit.keys().collect::<Vec<_>>(),
+            vec!["WEBKIT_DISABLE_DMABUF_RENDERER"]
+        );
+    }
+
+    #[test]
+    fn frontend_ready_timeout_prefers_saved_then_env_then_default() {
+        assert_eq!(
+            resolve_frontend_ready_timeout_secs(Some(60), Some("30")),
+            60
+        );
+        assert_eq!(resolve_frontend_ready_timeout_secs(None, Some(" 30 ")), 30);
+        assert_eq!(resolve_frontend_ready_timeout_secs(None, Some("1")), 1);
+        assert_eq!(resolve_frontend_ready_timeout_secs(None, Some("600")), 600);
+        assert_eq!(
+            resolve_frontend_ready_timeout_secs(None, Some("0")),
+            FRONTEND_READY_TIMEOUT_DEFAULT_SECS
+        );
+        assert_eq!(
+            resolve_frontend_ready_timeout_secs(None, Some("soon")),
+            FRONTEND_READY_TIMEOUT_DEFAULT_SECS
+        );
+        assert_eq!(
+            resolve_frontend_ready_timeout_secs(None, None),
+            FRONTEND_READY_TIMEOUT_DEFAULT_SECS
+        );
+        assert_eq!(parse_frontend_ready_timeout_secs("301"), None);
+    }
+
     #[test]
     fn appimage_wayland_preload_requires_all_conditions() {
         assert!(should_reexec_for_appimage_wayland_preload(
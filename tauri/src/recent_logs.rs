@@ -0,0 +1,189 @@
+//! In-memory ring buffer of recent log records for the in-app diagnostics panel.
+//!
+//! `init_logging` registers `RecentLogBuffer` next to the terminal/file logger with the same
+//! crate filter, so the buffer holds the last `CAPACITY` records the log file would get and
+//! users do not have to find the file to report a problem.
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::{LazyLock, Mutex};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::Serialize;
+use simplelog::{Config, SharedLogger};
+
+const CAPACITY: usize = 2000;
+const DEFAULT_MAX_LINES: usize = 200;
+/// Same crate filter as the terminal/file loggers
+const TARGET_PREFIX: &str = "ai_toolbox";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    /// RFC 3339, local time
+    pub timestamp: String,
+    /// "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE"
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+static BUFFER: LazyLock<Mutex<VecDeque<(Level, LogEntry)>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(CAPACITY)));
+
+/// Logger that keeps records in `BUFFER` instead of writing them anywhere.
+pub struct RecentLogBuffer {
+    level: LevelFilter,
+}
+
+impl RecentLogBuffer {
+    pub fn new(level: LevelFilter) -> Box<Self> {
+        Box::new(Self { level })
+    }
+}
+
+impl Log for RecentLogBuffer {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level && metadata.target().starts_with(TARGET_PREFIX)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        if let Ok(mut buffer) = BUFFER.lock() {
+            push_bounded(&mut buffer, record.level(), entry, CAPACITY);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+impl SharedLogger for RecentLogBuffer {
+    fn level(&self) -> LevelFilter {
+        self.level
+    }
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        self
+    }
+}
+
+fn push_bounded(
+    buffer: &mut VecDeque<(Level, LogEntry)>,
+    level: Level,
+    entry: LogEntry,
+    capacity: usize,
+) {
+    while buffer.len() >= capacity {
+        buffer.pop_front();
+    }
+    buffer.push_back((level, entry));
+}
+
+/// The newest `max_lines` entries at `level_filter` or more severe, oldest first.
+fn select_recent(
+    buffer: &VecDeque<(Level, LogEntry)>,
+    level_filter: LevelFilter,
+    max_lines: usize,
+) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = buffer
+        .iter()
+        .rev()
+        .filter(|(level, _)| *level <= level_filter)
+        .take(max_lines)
+        .map(|(_, entry)| entry.clone())
+        .collect();
+    entries.reverse();
+    entries
+}
+
+/// Recent log records captured in memory, oldest first. `level_filter` ("error" … "trace",
+/// default "trace") keeps that level and more severe ones; `max_lines` defaults to 200 and
+/// is capped by the buffer size.
+#[tauri::command]
+pub fn get_recent_logs(
+    level_filter: Option<String>,
+    max_lines: Option<usize>,
+) -> Result<Vec<LogEntry>, String> {
+    let level_filter = match level_filter.as_deref().map(str::trim) {
+        None | Some("") => LevelFilter::Trace,
+        Some(value) => {
+            LevelFilter::from_str(value).map_err(|_| format!("Unknown log level '{}'", value))?
+        }
+    };
+    let max_lines = max_lines.unwrap_or(DEFAULT_MAX_LINES).min(CAPACITY);
+    let buffer = BUFFER
+        .lock()
+        .map_err(|_| "Recent log buffer is poisoned".to_string())?;
+    Ok(select_recent(&buffer, level_filter, max_lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message: &str, level: Level) -> LogEntry {
+        LogEntry {
+            timestamp: String::new(),
+            level: level.to_string(),
+            target: "ai_toolbox::test".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn ring_buffer_drops_oldest_and_filters_by_level() {
+        let mut buffer = VecDeque::new();
+        for (message, level) in [
+            ("a", Level::Info),
+            ("b", Level::Warn),
+            ("c", Level::Debug),
+            ("d", Level::Error),
+        ] {
+            push_bounded(&mut buffer, level, entry(message, level), 3);
+        }
+        assert_eq!(buffer.len(), 3);
+
+        let messages = |entries: Vec<LogEntry>| {
+            entries
+                .into_iter()
+                .map(|entry| entry.message)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(select_recent(&buffer, LevelFilter::Trace, 10)),
+            ["b", "c", "d"]
+        );
+        assert_eq!(
+            messages(select_recent(&buffer, LevelFilter::Warn, 10)),
+            ["b", "d"]
+        );
+        assert_eq!(
+            messages(select_recent(&buffer, LevelFilter::Trace, 1)),
+            ["d"]
+        );
+        assert!(RecentLogBuffer::new(LevelFilter::Info).enabled(
+            &Metadata::builder()
+                .level(Level::Info)
+                .target("ai_toolbox::coding")
+                .build()
+        ));
+        assert!(!RecentLogBuffer::new(LevelFilter::Info).enabled(
+            &Metadata::builder()
+                .level(Level::Info)
+                .target("hyper::client")
+                .build()
+        ));
+    }
+}
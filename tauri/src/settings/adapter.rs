@@ -1,6 +1,8 @@
 use super::types::{
     default_backup_file_filter_rules, default_sidebar_hidden_by_page, AppSettings,
-    BackupCustomEntry, BackupFileFilterRule, S3Config, WebDAVConfig,
+    BackupCustomEntry, BackupFileFilterRule, ConfigSyncMode, S3Config, WebDAVConfig,
+    WindowBackgroundColor, CONFIG_SYNC_MODE_TOOLS, DEFAULT_LOG_RETENTION_DAYS,
+    DEFAULT_PROVIDER_HEALTH_INTERVAL_MINUTES, TRAY_PROVIDER_TOOLS,
 };
 /**
  * Settings Adapter Layer
@@ -36,6 +38,10 @@ pub fn from_db_value(value: Value) -> AppSettings {
         auto_backup_enabled: get_bool(&value, "auto_backup_enabled", false),
         auto_backup_interval_days: get_u32(&value, "auto_backup_interval_days", 7),
         auto_backup_max_keep: get_u32(&value, "auto_backup_max_keep", 10),
+        backup_mode: get_str(&value, "backup_mode", "full"),
+        backup_diffs_per_baseline: get_u32(&value, "backup_diffs_per_baseline", 6),
+        webdav_auto_backup_enabled: get_bool(&value, "webdav_auto_backup_enabled", false),
+        webdav_backup_keep: get_u32(&value, "webdav_backup_keep", 10),
         last_auto_backup_time: get_opt_str(&value, "last_auto_backup_time"),
         auto_check_update: get_bool(&value, "auto_check_update", true),
         visible_tabs: normalize_visible_tabs_order(get_string_array(
@@ -72,6 +78,28 @@ pub fn from_db_value(value: Value) -> AppSettings {
             false,
         ),
         claude_cli_launch_full_access: get_bool(&value, "claude_cli_launch_full_access", false),
+        claude_encrypt_provider_secrets: get_bool(&value, "claude_encrypt_provider_secrets", false),
+        auto_sync_on_apply: get_bool(&value, "auto_sync_on_apply", true),
+        diagnostics_enabled: get_bool(&value, "diagnostics_enabled", false),
+        sync_trace: get_bool(&value, "sync_trace", false),
+        check_applied_providers_on_startup: get_bool(
+            &value,
+            "check_applied_providers_on_startup",
+            false,
+        ),
+        provider_health_monitoring: get_bool(&value, "provider_health_monitoring", false),
+        provider_health_interval_minutes: get_u32(
+            &value,
+            "provider_health_interval_minutes",
+            DEFAULT_PROVIDER_HEALTH_INTERVAL_MINUTES,
+        ),
+        log_retention_days: get_u32(&value, "log_retention_days", DEFAULT_LOG_RETENTION_DAYS),
+        config_sync_modes: get_config_sync_modes(&value),
+        tray_hidden_provider_tools: get_tray_hidden_provider_tools(&value),
+        boot_snapshot_id: get_opt_str(&value, "boot_snapshot_id").filter(|id| !id.is_empty()),
+        window_background_color: value
+            .get("window_background_color")
+            .and_then(|color| serde_json::from_value::<WindowBackgroundColor>(color.clone()).ok()),
         backup_file_filter_rules: get_backup_file_filter_rules(&value),
     }
 }
@@ -220,6 +248,31 @@ fn string_vec_matches(values: &[String], expected: &[&str]) -> bool {
             .all(|(value, expected)| value == expected)
 }
 
+fn get_config_sync_modes(value: &Value) -> std::collections::HashMap<String, ConfigSyncMode> {
+    let Some(modes_value) = value.get("config_sync_modes").and_then(|v| v.as_object()) else {
+        return std::collections::HashMap::new();
+    };
+
+    CONFIG_SYNC_MODE_TOOLS
+        .iter()
+        .filter_map(|tool| {
+            let mode = modes_value
+                .get(*tool)
+                .and_then(|mode| serde_json::from_value::<ConfigSyncMode>(mode.clone()).ok())?;
+            Some((tool.to_string(), mode))
+        })
+        .collect()
+}
+
+fn get_tray_hidden_provider_tools(value: &Value) -> Vec<String> {
+    let hidden = get_string_array(value, "tray_hidden_provider_tools", &[]);
+    TRAY_PROVIDER_TOOLS
+        .iter()
+        .filter(|tool| hidden.iter().any(|item| item == *tool))
+        .map(|tool| tool.to_string())
+        .collect()
+}
+
 fn get_backup_custom_entries(value: &Value) -> Vec<BackupCustomEntry> {
     value
         .get("backup_custom_entries")
@@ -388,6 +441,19 @@ mod tests {
         assert!(!settings.backup_cli_config_files_enabled);
     }
 
+    #[test]
+    fn boot_snapshot_id_treats_empty_string_as_unset() {
+        assert_eq!(from_db_value(json!({})).boot_snapshot_id, None);
+        assert_eq!(
+            from_db_value(json!({ "boot_snapshot_id": "" })).boot_snapshot_id,
+            None
+        );
+        assert_eq!(
+            from_db_value(json!({ "boot_snapshot_id": "20260101-abc" })).boot_snapshot_id,
+            Some("20260101-abc".to_string())
+        );
+    }
+
     #[test]
     fn backup_custom_entries_default_to_empty() {
         let settings = from_db_value(json!({}));
@@ -680,4 +746,72 @@ mod tests {
             "~/.local/share/opencode/auth.json"
         );
     }
+
+    #[test]
+    fn config_sync_modes_keep_only_known_tools_and_modes() {
+        let settings = from_db_value(json!({
+            "config_sync_modes": {
+                "claude": "db_primary",
+                "codex": "file_primary",
+                "geminicli": "mirror",
+                "opencode": "db_primary"
+            },
+        }));
+
+        assert_eq!(settings.config_sync_modes.len(), 2);
+        assert_eq!(
+            settings.config_sync_modes.get("claude"),
+            Some(&super::ConfigSyncMode::DbPrimary)
+        );
+        assert_eq!(
+            settings.config_sync_modes.get("codex"),
+            Some(&super::ConfigSyncMode::FilePrimary)
+        );
+        assert!(from_db_value(json!({})).config_sync_modes.is_empty());
+    }
+
+    #[test]
+    fn tray_hidden_provider_tools_keep_only_known_tools() {
+        let settings = from_db_value(json!({
+            "tray_hidden_provider_tools": ["codex", "opencode", "codex", "claude"],
+        }));
+
+        assert_eq!(
+            settings.tray_hidden_provider_tools,
+            vec!["claude".to_string(), "codex".to_string()]
+        );
+        assert!(from_db_value(json!({}))
+            .tray_hidden_provider_tools
+            .is_empty());
+    }
+
+    #[test]
+    fn per_tool_setting_keys_use_runtime_module_keys() {
+        use crate::coding::runtime_location::MODULE_KEYS;
+
+        for tool in super::CONFIG_SYNC_MODE_TOOLS
+            .iter()
+            .chain(super::TRAY_PROVIDER_TOOLS)
+        {
+            assert!(MODULE_KEYS.contains(tool), "unknown tool key {tool}");
+        }
+    }
+
+    #[test]
+    fn window_background_color_ignores_invalid_values() {
+        let settings = from_db_value(json!({
+            "window_background_color": {"r": 31, "g": 31, "b": 31},
+        }));
+        assert_eq!(
+            settings.window_background_color,
+            Some(super::WindowBackgroundColor {
+                r: 31,
+                g: 31,
+                b: 31
+            })
+        );
+        let invalid = json!({"window_background_color": {"r": 300, "g": 0, "b": 0}});
+        assert_eq!(from_db_value(invalid).window_background_color, None);
+        assert_eq!(from_db_value(json!({})).window_background_color, None);
+    }
 }
@@ -3,6 +3,7 @@ use log::{error, info, warn};
 use std::time::Duration;
 use tauri::{Emitter, Manager};
 
+use super::differential;
 use super::utils::{create_backup_zip, get_db_path};
 use super::webdav::{delete_webdav_backup_internal, list_webdav_backups_internal};
 use crate::db::SqliteDbState;
@@ -54,28 +55,20 @@ async fn check_and_perform_backup(app_handle: &tauri::AppHandle) -> Result<(), S
 
             info!("Auto-backup is due, performing WebDAV backup...");
 
-            match perform_webdav_backup(app_handle, &db_state, &settings).await {
+            match perform_webdav_backup_with_cleanup(
+                app_handle,
+                &db_state,
+                &settings,
+                settings.auto_backup_max_keep,
+            )
+            .await
+            {
                 Ok(()) => {
                     info!("Auto-backup completed successfully");
 
                     let now = Utc::now().to_rfc3339();
                     update_last_auto_backup_time(&sqlite_state, &db_state, &now).await?;
                     let _ = app_handle.emit("auto-backup-completed", &now);
-
-                    if settings.auto_backup_max_keep > 0 {
-                        if let Err(e) = cleanup_old_webdav_backups(
-                            &db_state,
-                            &settings.webdav.url,
-                            &settings.webdav.username,
-                            &settings.webdav.password,
-                            &settings.webdav.remote_path,
-                            settings.auto_backup_max_keep,
-                        )
-                        .await
-                        {
-                            warn!("Auto-backup cleanup failed: {}", e);
-                        }
-                    }
                 }
                 Err(e) => {
                     warn!("Auto-backup failed: {}", e);
@@ -123,6 +116,25 @@ async fn check_and_perform_backup(app_handle: &tauri::AppHandle) -> Result<(), S
                 }
             }
 
+            // The WebDAV copy runs after the local backup either way and never affects it.
+            if settings.webdav_auto_backup_enabled && !settings.webdav.url.is_empty() {
+                info!("Auto-backup: also uploading to WebDAV...");
+                match perform_webdav_backup_with_cleanup(
+                    app_handle,
+                    &db_state,
+                    &settings,
+                    settings.webdav_backup_keep,
+                )
+                .await
+                {
+                    Ok(()) => info!("Auto-backup (WebDAV copy) completed successfully"),
+                    Err(e) => {
+                        warn!("Auto-backup (WebDAV copy) failed: {}", e);
+                        let _ = app_handle.emit("auto-backup-failed", &e);
+                    }
+                }
+            }
+
             Ok(())
         }
         _ => Ok(()),
@@ -225,17 +237,58 @@ async fn perform_local_backup(
             .map_err(|e| format!("Failed to create backup dir: {}", e))?;
     }
 
-    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-    let backup_filename = format!("ai-toolbox-backup-{}.zip", timestamp);
-    let backup_file_path = backup_dir.join(&backup_filename);
-
-    std::fs::write(&backup_file_path, &zip_data)
-        .map_err(|e| format!("Failed to write backup file: {}", e))?;
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let baseline = (settings.backup_mode == differential::BACKUP_MODE_DIFFERENTIAL)
+        .then(|| differential::latest_baseline(backup_dir))
+        .flatten()
+        .filter(|(_, diffs)| *diffs < settings.backup_diffs_per_baseline as usize);
+
+    let backup_file_path = match baseline {
+        Some((base_path, _)) => {
+            let backup_file_path =
+                backup_dir.join(differential::differential_backup_file_name(&timestamp));
+            differential::write_differential_backup(&zip_data, &base_path, &backup_file_path)?;
+            backup_file_path
+        }
+        None => {
+            let backup_file_path = backup_dir.join(differential::full_backup_file_name(&timestamp));
+            std::fs::write(&backup_file_path, zip_data)
+                .map_err(|e| format!("Failed to write backup file: {}", e))?;
+            backup_file_path
+        }
+    };
 
     info!("Auto-backup: saved to {:?}", backup_file_path);
     Ok(())
 }
 
+/// Perform a WebDAV backup, then keep only the latest `max_keep` remote backups (0 = all).
+/// A failed cleanup is logged and does not fail the backup.
+async fn perform_webdav_backup_with_cleanup(
+    app_handle: &tauri::AppHandle,
+    db_state: &SqliteDbState,
+    settings: &crate::settings::types::AppSettings,
+    max_keep: u32,
+) -> Result<(), String> {
+    perform_webdav_backup(app_handle, db_state, settings).await?;
+
+    if max_keep > 0 {
+        if let Err(e) = cleanup_old_webdav_backups(
+            db_state,
+            &settings.webdav.url,
+            &settings.webdav.username,
+            &settings.webdav.password,
+            &settings.webdav.remote_path,
+            max_keep,
+        )
+        .await
+        {
+            warn!("Auto-backup cleanup failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
 /// Update last_auto_backup_time in SQLite.
 async fn update_last_auto_backup_time(
     sqlite_state: &SqliteDbState,
@@ -309,7 +362,16 @@ fn cleanup_old_local_backups(backup_path: &str, max_keep: u32) -> Result<(), Str
     // Sort descending by filename (most recent first)
     backup_files.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
 
-    let to_delete = &backup_files[max_keep as usize..];
+    // Baselines of kept differential backups are needed to restore them.
+    let (kept, to_delete) = backup_files.split_at(max_keep as usize);
+    let needed_baselines: std::collections::HashSet<String> = kept
+        .iter()
+        .filter_map(|entry| differential::differential_backup_base(&entry.path()))
+        .collect();
+    let to_delete: Vec<_> = to_delete
+        .iter()
+        .filter(|entry| !needed_baselines.contains(&*entry.file_name().to_string_lossy()))
+        .collect();
     info!(
         "Auto-backup cleanup: deleting {} old local backup(s)",
         to_delete.len()
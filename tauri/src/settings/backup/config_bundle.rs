@@ -0,0 +1,462 @@
+//! Portable JSON export/import of the app configuration.
+//!
+//! A bundle holds the user-authored records (providers, common/prompt configs, MCP servers,
+//! favorites, skill repos, settings, ...) keyed by table name, so it can move between
+//! machines without the backup zip's external files. Machine-specific and runtime tables
+//! (official account tokens, WSL/SSH sync, image jobs, caches, migration markers) are left
+//! out. Provider API keys are exported as stored, so every bundle carries credentials.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tauri::{Emitter, State};
+
+use crate::db::helpers::{db_delete_all, db_get, db_list, db_put, db_transaction};
+use crate::db::migrations::normalize_record_field_casing;
+use crate::db::schema::DbTable;
+use crate::db::SqliteDbState;
+use crate::settings::backup::import_plan::{ImportPlanItem, PlannedChanges};
+
+pub const CONFIG_BUNDLE_FORMAT: &str = "ai-toolbox-config-bundle";
+pub const CONFIG_BUNDLE_VERSION: u64 = 1;
+
+const CREDENTIALS_WARNING: &str =
+    "The bundle contains credentials such as provider API keys; store and share it like a password";
+const ENCRYPTED_SECRETS_WARNING: &str =
+    "Claude provider API keys are encrypted with this machine's OS keyring key and only open for the same OS account";
+
+/// Tables carried by a bundle, in import order.
+const BUNDLE_TABLES: &[DbTable] = &[
+    DbTable::Settings,
+    DbTable::ClaudeProvider,
+    DbTable::ClaudeCommonConfig,
+    DbTable::ClaudePromptConfig,
+    DbTable::CodexProvider,
+    DbTable::CodexCommonConfig,
+    DbTable::CodexPromptConfig,
+    DbTable::GrokProvider,
+    DbTable::GrokCommonConfig,
+    DbTable::GrokPromptConfig,
+    DbTable::GeminiCliProvider,
+    DbTable::GeminiCliCommonConfig,
+    DbTable::GeminiCliPromptConfig,
+    DbTable::PiSettingsConfig,
+    DbTable::PiPromptConfig,
+    DbTable::OpenCodeCommonConfig,
+    DbTable::OpenCodePromptConfig,
+    DbTable::OpenCodeFavoritePlugin,
+    DbTable::OpenCodeFavoriteProvider,
+    DbTable::OpenClawCommonConfig,
+    DbTable::OhMyOpenAgentConfig,
+    DbTable::OhMyOpenAgentGlobalConfig,
+    DbTable::OhMyOpenCodeSlimConfig,
+    DbTable::OhMyOpenCodeSlimGlobalConfig,
+    DbTable::SkillRepo,
+    DbTable::CustomTool,
+    DbTable::McpServer,
+    DbTable::McpPreferences,
+    DbTable::FavoriteMcp,
+    DbTable::ProxyGatewaySettings,
+    DbTable::ImageChannel,
+];
+
+/// How imported records combine with existing ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleMergeStrategy {
+    /// Clear each table present in the bundle, then insert its records
+    Replace,
+    /// Insert records whose id does not exist yet; existing records win
+    MergeKeepExisting,
+    /// Upsert every record; bundle records win
+    MergeOverwrite,
+}
+
+impl BundleMergeStrategy {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "replace" => Ok(Self::Replace),
+            "merge-keep-existing" => Ok(Self::MergeKeepExisting),
+            "merge-overwrite" => Ok(Self::MergeOverwrite),
+            other => Err(format!(
+                "Unknown merge strategy '{}'; expected replace, merge-keep-existing or merge-overwrite",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBundleExport {
+    /// Bundle JSON text
+    pub bundle: String,
+    pub record_count: usize,
+    pub contains_credentials: bool,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBundleImportResult {
+    /// Bundle version before migration
+    pub bundle_version: u64,
+    pub imported: usize,
+    /// Records kept because they already existed (merge-keep-existing)
+    pub skipped: usize,
+    /// Records removed by the replace strategy
+    pub removed: usize,
+    pub warnings: Vec<String>,
+}
+
+fn bundle_table(name: &str) -> Option<DbTable> {
+    BUNDLE_TABLES
+        .iter()
+        .copied()
+        .find(|table| table.name() == name)
+}
+
+/// Collect every bundle table. Claude provider secrets are written encrypted when
+/// `encrypt_secrets` is set and as plaintext otherwise, whatever the at-rest setting.
+pub(crate) fn build_config_bundle(
+    db: &SqliteDbState,
+    encrypt_secrets: bool,
+) -> Result<(Value, usize), String> {
+    let mut listed = Vec::new();
+    db.with_conn(|conn| {
+        for table in BUNDLE_TABLES {
+            listed.push((*table, db_list(conn, *table, None)?));
+        }
+        Ok(())
+    })?;
+
+    let mut tables = Map::new();
+    let mut record_count = 0;
+    for (table, mut records) in listed {
+        if table == DbTable::ClaudeProvider {
+            for record in records.iter_mut() {
+                crate::coding::secret_store::convert_claude_provider_record(
+                    record,
+                    encrypt_secrets,
+                )?;
+            }
+        }
+        record_count += records.len();
+        tables.insert(table.name().to_string(), Value::Array(records));
+    }
+    let bundle = json!({
+        "format": CONFIG_BUNDLE_FORMAT,
+        "version": CONFIG_BUNDLE_VERSION,
+        "exported_at": chrono::Local::now().to_rfc3339(),
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "tables": tables,
+    });
+    Ok((bundle, record_count))
+}
+
+/// Bring an older bundle up to `CONFIG_BUNDLE_VERSION`, one version step at a time.
+fn migrate_config_bundle(bundle: Value) -> Result<(Value, u64), String> {
+    if bundle.get("format").and_then(Value::as_str) != Some(CONFIG_BUNDLE_FORMAT) {
+        return Err("Not an AI Toolbox config bundle".to_string());
+    }
+    let original_version = bundle
+        .get("version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "Config bundle has no version".to_string())?;
+    if original_version == 0 {
+        return Err("Config bundle version 0 is not supported".to_string());
+    }
+    if original_version > CONFIG_BUNDLE_VERSION {
+        return Err(format!(
+            "Config bundle version {} is newer than this app supports ({}); update AI Toolbox first",
+            original_version, CONFIG_BUNDLE_VERSION
+        ));
+    }
+    // Version 1 is current; future format changes add one upgrade step per version here.
+    Ok((bundle, original_version))
+}
+
+/// The known tables of a migrated bundle in import order, plus warnings for unknown ones.
+fn bundle_entries(bundle: &Value) -> Result<(Vec<(DbTable, &Vec<Value>)>, Vec<String>), String> {
+    let tables = bundle
+        .get("tables")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "Config bundle has no tables".to_string())?;
+
+    let mut entries: Vec<(DbTable, &Vec<Value>)> = Vec::new();
+    let mut warnings = Vec::new();
+    for (name, records) in tables {
+        let Some(table) = bundle_table(name) else {
+            warnings.push(format!("Skipped unknown table '{}'", name));
+            continue;
+        };
+        let records = records
+            .as_array()
+            .ok_or_else(|| format!("Config bundle table '{}' is not an array", name))?;
+        entries.push((table, records));
+    }
+    entries.sort_by_key(|(table, _)| BUNDLE_TABLES.iter().position(|item| item == table));
+    Ok((entries, warnings))
+}
+
+fn record_label(record: &Value, id: &str) -> String {
+    record
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or(id)
+        .to_string()
+}
+
+/// Report what `apply_config_bundle` would create, update, skip and remove, without writing.
+pub(crate) fn plan_config_bundle(
+    db: &SqliteDbState,
+    bundle: Value,
+    strategy: BundleMergeStrategy,
+) -> Result<PlannedChanges, String> {
+    let (bundle, _) = migrate_config_bundle(bundle)?;
+    let (entries, warnings) = bundle_entries(&bundle)?;
+    let mut changes = PlannedChanges {
+        warnings: vec![CREDENTIALS_WARNING.to_string()],
+        ..Default::default()
+    };
+    changes.warnings.extend(warnings);
+
+    db.with_conn(|conn| {
+        for (table, records) in &entries {
+            let scope = table.name();
+            if strategy == BundleMergeStrategy::Replace {
+                let incoming: HashSet<&str> = records
+                    .iter()
+                    .filter_map(|record| record.get("id").and_then(Value::as_str))
+                    .collect();
+                for existing in db_list(conn, *table, None)? {
+                    let Some(id) = existing.get("id").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    if !incoming.contains(id) {
+                        changes.remove.push(ImportPlanItem::new(
+                            scope,
+                            id,
+                            record_label(&existing, id),
+                        ));
+                    }
+                }
+            }
+            for record in records.iter() {
+                let Some(id) = record.get("id").and_then(Value::as_str) else {
+                    changes
+                        .warnings
+                        .push(format!("Skipped a record without id in table '{}'", scope));
+                    continue;
+                };
+                let item = ImportPlanItem::new(scope, id, record_label(record, id));
+                match (db_get(conn, *table, id)?.is_some(), strategy) {
+                    (false, _) => changes.create.push(item),
+                    (true, BundleMergeStrategy::MergeKeepExisting) => changes.skip.push(item),
+                    (true, _) => changes.update.push(item),
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(changes)
+}
+
+pub(crate) fn apply_config_bundle(
+    db: &SqliteDbState,
+    bundle: Value,
+    strategy: BundleMergeStrategy,
+) -> Result<ConfigBundleImportResult, String> {
+    let (bundle, bundle_version) = migrate_config_bundle(bundle)?;
+    let (entries, warnings) = bundle_entries(&bundle)?;
+
+    let mut result = ConfigBundleImportResult {
+        bundle_version,
+        imported: 0,
+        skipped: 0,
+        removed: 0,
+        warnings: vec![CREDENTIALS_WARNING.to_string()],
+    };
+    result.warnings.extend(warnings);
+    let seal_key = crate::coding::secret_store::claude_seal_key_if_enabled(db)?;
+
+    db.with_conn_mut(|conn| {
+        db_transaction(conn, |tx| {
+            for (table, records) in &entries {
+                if strategy == BundleMergeStrategy::Replace {
+                    result.removed += db_delete_all(tx, *table)?;
+                }
+                for record in records.iter() {
+                    let Some(id) = record.get("id").and_then(Value::as_str) else {
+                        result.warnings.push(format!(
+                            "Skipped a record without id in table '{}'",
+                            table.name()
+                        ));
+                        continue;
+                    };
+                    if strategy == BundleMergeStrategy::MergeKeepExisting
+                        && db_get(tx, *table, id)?.is_some()
+                    {
+                        result.skipped += 1;
+                        continue;
+                    }
+                    let mut record = record.clone();
+                    normalize_record_field_casing(&mut record);
+                    if let (DbTable::ClaudeProvider, Some(key)) = (*table, seal_key.as_ref()) {
+                        crate::coding::secret_store::seal_claude_provider_record(&mut record, key)?;
+                    }
+                    db_put(tx, *table, id, &record)?;
+                    result.imported += 1;
+                }
+            }
+            Ok(())
+        })
+    })?;
+    Ok(result)
+}
+
+/// Export the app configuration as one versioned JSON bundle. `encrypt_secrets`
+/// (default false) keeps Claude provider API keys encrypted with the OS keyring key.
+#[tauri::command]
+pub async fn export_config_bundle(
+    state: State<'_, SqliteDbState>,
+    encrypt_secrets: Option<bool>,
+) -> Result<ConfigBundleExport, String> {
+    let encrypt_secrets = encrypt_secrets.unwrap_or(false);
+    let (bundle, record_count) = build_config_bundle(&state, encrypt_secrets)?;
+    let bundle = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize config bundle: {}", e))?;
+    let mut warnings = vec![CREDENTIALS_WARNING.to_string()];
+    if encrypt_secrets {
+        warnings.push(ENCRYPTED_SECRETS_WARNING.to_string());
+    }
+    Ok(ConfigBundleExport {
+        bundle,
+        record_count,
+        contains_credentials: true,
+        warnings,
+    })
+}
+
+/// Import a bundle from `export_config_bundle`. `merge_strategy` is "replace",
+/// "merge-keep-existing" or "merge-overwrite". Runtime config files are not touched;
+/// re-apply providers afterwards if needed.
+#[tauri::command]
+pub async fn import_config_bundle(
+    app_handle: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    json: String,
+    merge_strategy: String,
+) -> Result<ConfigBundleImportResult, String> {
+    let strategy = BundleMergeStrategy::parse(&merge_strategy)?;
+    let bundle: Value =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid config bundle JSON: {}", e))?;
+    let result = apply_config_bundle(&state, bundle, strategy)?;
+    log::info!(
+        "Config bundle imported ({}): {} records, {} skipped, {} removed",
+        merge_strategy,
+        result.imported,
+        result.skipped,
+        result.removed
+    );
+
+    let _ = app_handle.emit("config-changed", "window");
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_state() -> SqliteDbState {
+        let state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        state
+            .with_conn(|conn| {
+                db_put(conn, DbTable::McpServer, "a", &json!({ "name": "local-a" }))?;
+                db_put(conn, DbTable::McpServer, "b", &json!({ "name": "local-b" }))
+            })
+            .expect("seed");
+        state
+    }
+
+    fn bundle_with_servers() -> Value {
+        json!({
+            "format": CONFIG_BUNDLE_FORMAT,
+            "version": 1,
+            "tables": {
+                "mcp_server": [
+                    { "id": "b", "name": "bundle-b" },
+                    { "id": "c", "name": "bundle-c" }
+                ],
+                "not_a_table": []
+            }
+        })
+    }
+
+    fn server_names(state: &SqliteDbState) -> Vec<String> {
+        state
+            .with_conn(|conn| db_list(conn, DbTable::McpServer, None))
+            .expect("list")
+            .iter()
+            .map(|record| {
+                format!(
+                    "{}={}",
+                    record["id"].as_str().unwrap(),
+                    record["name"].as_str().unwrap()
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_strategies_resolve_id_conflicts() {
+        let state = seeded_state();
+        let result = apply_config_bundle(
+            &state,
+            bundle_with_servers(),
+            BundleMergeStrategy::MergeKeepExisting,
+        )
+        .expect("keep existing");
+        assert_eq!((result.imported, result.skipped), (1, 1));
+        assert!(result.warnings.iter().any(|w| w.contains("not_a_table")));
+        let mut names = server_names(&state);
+        names.sort();
+        assert_eq!(names, vec!["a=local-a", "b=local-b", "c=bundle-c"]);
+
+        let state = seeded_state();
+        apply_config_bundle(
+            &state,
+            bundle_with_servers(),
+            BundleMergeStrategy::MergeOverwrite,
+        )
+        .expect("overwrite");
+        let mut names = server_names(&state);
+        names.sort();
+        assert_eq!(names, vec!["a=local-a", "b=bundle-b", "c=bundle-c"]);
+
+        let state = seeded_state();
+        let result =
+            apply_config_bundle(&state, bundle_with_servers(), BundleMergeStrategy::Replace)
+                .expect("replace");
+        assert_eq!(result.removed, 2);
+        let mut names = server_names(&state);
+        names.sort();
+        assert_eq!(names, vec!["b=bundle-b", "c=bundle-c"]);
+    }
+
+    #[test]
+    fn exported_bundle_round_trips_and_rejects_newer_versions() {
+        let source = seeded_state();
+        let (bundle, record_count) = build_config_bundle(&source, false).expect("export");
+        assert!(record_count >= 2);
+
+        let target = SqliteDbState::in_memory_for_test().expect("sqlite");
+        apply_config_bundle(&target, bundle.clone(), BundleMergeStrategy::Replace).expect("import");
+        assert_eq!(server_names(&target).len(), 2);
+
+        let mut newer = bundle;
+        newer["version"] = json!(CONFIG_BUNDLE_VERSION + 1);
+        let error = apply_config_bundle(&target, newer, BundleMergeStrategy::Replace).unwrap_err();
+        assert!(error.contains("newer"));
+    }
+}
@@ -0,0 +1,351 @@
+//! Differential local auto-backups.
+//!
+//! A differential zip holds only the entries whose content changed since the newest full
+//! baseline in the same folder. Its manifest (see `manifest`) lists the SHA-256 of every entry
+//! of the complete backup, so restore rebuilds baseline + differential into a full archive and verifies it
+//! before anything is written. Differentials always apply to a baseline, never to another
+//! differential, so deleting one differential never breaks the others.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use zip::result::ZipError;
+use zip::{ZipArchive, ZipWriter};
+
+use super::manifest::{
+    enforce_backup_verification, hash_zip_entries, open_backup_archive, read_backup_manifest,
+    verify_backup_archive, write_backup_manifest, BackupManifest, BackupManifestEntry,
+    BackupVerifyReport,
+};
+
+pub const BACKUP_MODE_DIFFERENTIAL: &str = "differential";
+const BACKUP_FILE_PREFIX: &str = "ai-toolbox-backup-";
+const DIFFERENTIAL_BACKUP_SUFFIX: &str = "-diff.zip";
+
+pub fn full_backup_file_name(timestamp: &str) -> String {
+    format!("{}{}.zip", BACKUP_FILE_PREFIX, timestamp)
+}
+
+pub fn differential_backup_file_name(timestamp: &str) -> String {
+    format!(
+        "{}{}{}",
+        BACKUP_FILE_PREFIX, timestamp, DIFFERENTIAL_BACKUP_SUFFIX
+    )
+}
+
+fn is_differential_backup_file_name(name: &str) -> bool {
+    name.starts_with(BACKUP_FILE_PREFIX) && name.ends_with(DIFFERENTIAL_BACKUP_SUFFIX)
+}
+
+/// Paths whose content differs from the baseline, in archive order.
+fn changed_entry_paths<'a>(
+    current: &'a [BackupManifestEntry],
+    base: &[BackupManifestEntry],
+) -> Vec<&'a str> {
+    let base: HashMap<&str, &str> = base
+        .iter()
+        .map(|entry| (entry.path.as_str(), entry.sha256.as_str()))
+        .collect();
+    current
+        .iter()
+        .filter(|entry| base.get(entry.path.as_str()) != Some(&entry.sha256.as_str()))
+        .map(|entry| entry.path.as_str())
+        .collect()
+}
+
+/// Write the complete backup `zip_data` to `output_path` as a differential of `base_path`.
+pub fn write_differential_backup(
+    zip_data: &[u8],
+    base_path: &Path,
+    output_path: &Path,
+) -> Result<(), String> {
+    let base_name = base_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid baseline backup path".to_string())?;
+    let base_manifest = read_backup_manifest(&mut open_backup_archive(base_path)?)?
+        .filter(|manifest| !manifest.is_differential())
+        .ok_or_else(|| format!("{} is not a full backup with a manifest", base_name))?;
+
+    let mut current = ZipArchive::new(Cursor::new(zip_data))
+        .map_err(|e| format!("Failed to read zip archive: {}", e))?;
+    let entries = hash_zip_entries(&mut current)?;
+
+    let file =
+        File::create(output_path).map_err(|e| format!("Failed to create backup file: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    for path in changed_entry_paths(&entries, &base_manifest.entries) {
+        let entry = current
+            .by_name(path)
+            .map_err(|e| format!("Failed to read zip entry {}: {}", path, e))?;
+        zip.raw_copy_file(entry)
+            .map_err(|e| format!("Failed to copy zip entry {}: {}", path, e))?;
+    }
+    write_backup_manifest(
+        &mut zip,
+        &BackupManifest::new(BACKUP_MODE_DIFFERENTIAL, Some(base_name), entries),
+    )?;
+    zip.finish()
+        .map_err(|e| format!("Failed to finish zip: {}", e))?;
+    Ok(())
+}
+
+fn list_backup_files_newest_first(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy())
+                .is_some_and(|name| name.starts_with(BACKUP_FILE_PREFIX) && name.ends_with(".zip"))
+        })
+        .collect();
+    files.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    files
+}
+
+/// Newest full backup in `dir` that carries a manifest, with the number of newer
+/// differentials taken against it. None when the newest full backup has no manifest.
+pub fn latest_baseline(dir: &Path) -> Option<(PathBuf, usize)> {
+    let mut differential_bases: Vec<String> = Vec::new();
+    for path in list_backup_files_newest_first(dir) {
+        let manifest = open_backup_archive(&path)
+            .and_then(|mut archive| read_backup_manifest(&mut archive))
+            .ok()
+            .flatten();
+        match manifest {
+            Some(manifest) if manifest.is_differential() => {
+                differential_bases.extend(manifest.base);
+            }
+            Some(_) => {
+                let name = path.file_name()?.to_string_lossy().to_string();
+                let count = differential_bases
+                    .iter()
+                    .filter(|base| **base == name)
+                    .count();
+                return Some((path, count));
+            }
+            None => {
+                let name = path.file_name()?.to_string_lossy().to_string();
+                // Legacy full backup without manifest: the next backup starts a new baseline.
+                if !is_differential_backup_file_name(&name) {
+                    return None;
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Baseline file name of a differential backup file, None for full backups.
+pub fn differential_backup_base(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_string_lossy();
+    if !is_differential_backup_file_name(&name) {
+        return None;
+    }
+    read_backup_manifest(&mut open_backup_archive(path).ok()?)
+        .ok()
+        .flatten()
+        .filter(BackupManifest::is_differential)
+        .and_then(|manifest| manifest.base)
+}
+
+/// Rebuild a differential backup with its baseline, which must sit in the same folder, into
+/// a temporary complete archive laid out in manifest order.
+fn rebuild_differential_backup(
+    zip_path: &Path,
+    archive: &mut ZipArchive<File>,
+    manifest: &BackupManifest,
+) -> Result<tempfile::NamedTempFile, String> {
+    let base_name = manifest
+        .base
+        .as_deref()
+        .ok_or_else(|| "Differential backup does not name its baseline".to_string())?;
+    let base_path = zip_path
+        .parent()
+        .map(|dir| dir.join(base_name))
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            format!(
+                "Baseline backup {} is missing; keep it in the same folder as the differential backup",
+                base_name
+            )
+        })?;
+    let mut base = open_backup_archive(&base_path)?;
+
+    let mut rebuilt = tempfile::NamedTempFile::new()
+        .map_err(|e| format!("Failed to create temporary restore file: {}", e))?;
+    {
+        let mut zip = ZipWriter::new(rebuilt.as_file_mut());
+        for entry in &manifest.entries {
+            let copied = match archive.by_name(&entry.path) {
+                Ok(file) => zip.raw_copy_file(file),
+                Err(ZipError::FileNotFound) => match base.by_name(&entry.path) {
+                    Ok(file) => zip.raw_copy_file(file),
+                    Err(e) => {
+                        return Err(format!(
+                            "{} is missing from baseline {}: {}",
+                            entry.path, base_name, e
+                        ))
+                    }
+                },
+                Err(e) => Err(e),
+            };
+            copied.map_err(|e| format!("Failed to rebuild {}: {}", entry.path, e))?;
+        }
+        zip.finish()
+            .map_err(|e| format!("Failed to finish rebuilt backup: {}", e))?;
+    }
+    Ok(rebuilt)
+}
+
+/// Verify a local backup file against its manifest. Differentials are rebuilt with their
+/// baseline first and the rebuilt archive is returned, so restore can use what was checked.
+pub fn verify_local_backup_file(
+    zip_path: &Path,
+) -> Result<(BackupVerifyReport, Option<tempfile::NamedTempFile>), String> {
+    let mut archive = open_backup_archive(zip_path)?;
+    let Some(manifest) = read_backup_manifest(&mut archive)? else {
+        return Ok((BackupVerifyReport::without_manifest(), None));
+    };
+    if !manifest.is_differential() {
+        return Ok((verify_backup_archive(&mut archive, &manifest), None));
+    }
+
+    let rebuilt = rebuild_differential_backup(zip_path, &mut archive, &manifest)?;
+    let report = verify_backup_archive(&mut open_backup_archive(rebuilt.path())?, &manifest);
+    Ok((report, Some(rebuilt)))
+}
+
+/// Prepare a local backup for restore. Full backups are verified in place and None is
+/// returned; differentials yield a verified rebuilt archive that the caller restores instead.
+/// A failed check is an error unless `force` is set.
+pub fn prepare_local_restore_archive(
+    zip_path: &Path,
+    force: bool,
+) -> Result<Option<tempfile::NamedTempFile>, String> {
+    let (report, rebuilt) = verify_local_backup_file(zip_path)?;
+    enforce_backup_verification(&report, force)?;
+    Ok(rebuilt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::manifest::{
+        add_full_backup_manifest, add_full_backup_manifest_to_file, BACKUP_MANIFEST_ZIP_PATH,
+    };
+    use super::*;
+    use std::io::{Read, Write};
+    use zip::write::SimpleFileOptions;
+
+    fn backup_zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, content) in files {
+            zip.start_file(*path, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    fn read_entry(path: &Path, name: &str) -> String {
+        let mut archive = open_backup_archive(path).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn differential_stores_changes_and_restores_complete_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().join(full_backup_file_name("20260101-000000"));
+        let base = backup_zip(&[
+            ("sqlite/ai-toolbox.db", "v1"),
+            ("skills/a.md", "same"),
+            ("external-configs/codex/config.toml", "old"),
+        ]);
+        fs::write(&base_path, add_full_backup_manifest(base).unwrap()).unwrap();
+
+        let diff_path = dir
+            .path()
+            .join(differential_backup_file_name("20260102-000000"));
+        let current = backup_zip(&[
+            ("sqlite/ai-toolbox.db", "v2"),
+            ("skills/a.md", "same"),
+            ("image-studio/assets/new.png", "img"),
+        ]);
+        write_differential_backup(&current, &base_path, &diff_path).unwrap();
+
+        let mut diff = open_backup_archive(&diff_path).unwrap();
+        let mut stored: Vec<&str> = diff.file_names().collect();
+        stored.sort_unstable();
+        assert_eq!(
+            stored,
+            vec![
+                BACKUP_MANIFEST_ZIP_PATH,
+                "image-studio/assets/new.png",
+                "sqlite/ai-toolbox.db"
+            ]
+        );
+        assert_eq!(latest_baseline(dir.path()), Some((base_path.clone(), 1)));
+        assert_eq!(
+            differential_backup_base(&diff_path).as_deref(),
+            base_path.file_name().and_then(|name| name.to_str())
+        );
+
+        let rebuilt = prepare_local_restore_archive(&diff_path, false)
+            .unwrap()
+            .expect("differential is rebuilt");
+        let rebuilt_names: Vec<String> = open_backup_archive(rebuilt.path())
+            .unwrap()
+            .file_names()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(
+            rebuilt_names,
+            vec![
+                "sqlite/ai-toolbox.db",
+                "skills/a.md",
+                "image-studio/assets/new.png"
+            ]
+        );
+        assert_eq!(read_entry(rebuilt.path(), "sqlite/ai-toolbox.db"), "v2");
+        assert_eq!(read_entry(rebuilt.path(), "skills/a.md"), "same");
+
+        fs::remove_file(&base_path).unwrap();
+        assert!(prepare_local_restore_archive(&diff_path, false)
+            .unwrap_err()
+            .contains("missing"));
+    }
+
+    #[test]
+    fn full_backup_manifest_detects_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(full_backup_file_name("20260101-000000"));
+        fs::write(&path, backup_zip(&[("sqlite/ai-toolbox.db", "v1")])).unwrap();
+        add_full_backup_manifest_to_file(&path).unwrap();
+        assert!(prepare_local_restore_archive(&path, false)
+            .unwrap()
+            .is_none());
+
+        let tampered = backup_zip(&[
+            ("sqlite/ai-toolbox.db", "v2"),
+            (
+                BACKUP_MANIFEST_ZIP_PATH,
+                &read_entry(&path, BACKUP_MANIFEST_ZIP_PATH),
+            ),
+        ]);
+        fs::write(&path, tampered).unwrap();
+        assert!(prepare_local_restore_archive(&path, false)
+            .unwrap_err()
+            .contains("sqlite/ai-toolbox.db"));
+    }
+}
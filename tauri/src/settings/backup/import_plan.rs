@@ -0,0 +1,279 @@
+//! Two-phase imports: validate first, then commit the validated plan.
+//!
+//! `validate_import` runs an importer's planning step against the current database and
+//! reports every record it would create, update, skip or remove, without writing. A plan
+//! without errors is cached for `PLAN_TTL` under a fresh id; `commit_import` re-plans the
+//! same input and only applies it when nothing changed in between, so the user confirms
+//! exactly what gets written.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{Emitter, State};
+
+use crate::coding::sync_mappings::{self, SyncMappingsExport};
+use crate::db::SqliteDbState;
+use crate::settings::backup::config_bundle::{
+    apply_config_bundle, plan_config_bundle, BundleMergeStrategy,
+};
+
+const PLAN_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// One record an import touches. `key` is the record id, or the target path for file
+/// mappings (their ids are regenerated on collision).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPlanItem {
+    /// Table or mapping set, e.g. "claude_provider" or "ssh_file_mapping"
+    pub scope: String,
+    pub key: String,
+    pub label: String,
+}
+
+impl ImportPlanItem {
+    pub fn new(scope: &str, key: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            scope: scope.to_string(),
+            key: key.into(),
+            label: label.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedChanges {
+    pub create: Vec<ImportPlanItem>,
+    pub update: Vec<ImportPlanItem>,
+    pub skip: Vec<ImportPlanItem>,
+    pub remove: Vec<ImportPlanItem>,
+    /// Problems that block the import
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPlan {
+    /// Pass to `commit_import`; None when the plan has errors and cannot be committed
+    pub plan_id: Option<String>,
+    /// "config_bundle" | "sync_mappings"
+    pub kind: String,
+    pub mode: String,
+    #[serde(flatten)]
+    pub changes: PlannedChanges,
+}
+
+/// Parsed import input, kept so commit applies exactly what was validated.
+enum ImportSource {
+    ConfigBundle {
+        bundle: Value,
+        strategy: BundleMergeStrategy,
+    },
+    SyncMappings {
+        export: SyncMappingsExport,
+        merge: bool,
+    },
+}
+
+struct CachedPlan {
+    created_at: Instant,
+    source: ImportSource,
+    plan: ImportPlan,
+}
+
+static PLAN_CACHE: LazyLock<Mutex<HashMap<String, CachedPlan>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Parse `bundle` for `kind`; `mode` is the kind's merge option with its importer's default.
+fn parse_source(bundle: &str, kind: &str, mode: &str) -> Result<ImportSource, String> {
+    match kind {
+        "config_bundle" => Ok(ImportSource::ConfigBundle {
+            strategy: BundleMergeStrategy::parse(mode)?,
+            bundle: serde_json::from_str(bundle)
+                .map_err(|e| format!("Invalid config bundle JSON: {}", e))?,
+        }),
+        "sync_mappings" => Ok(ImportSource::SyncMappings {
+            merge: match mode {
+                "merge" => true,
+                "replace" => false,
+                other => {
+                    return Err(format!(
+                        "Unknown sync mappings mode '{}'; expected merge or replace",
+                        other
+                    ))
+                }
+            },
+            export: sync_mappings::parse_sync_mappings(bundle)?,
+        }),
+        other => Err(format!(
+            "Unknown import kind '{}'; expected config_bundle or sync_mappings",
+            other
+        )),
+    }
+}
+
+fn default_mode(kind: &str) -> &'static str {
+    match kind {
+        "sync_mappings" => "merge",
+        _ => "merge-keep-existing",
+    }
+}
+
+fn plan_source(db: &SqliteDbState, source: &ImportSource) -> Result<PlannedChanges, String> {
+    match source {
+        ImportSource::ConfigBundle { bundle, strategy } => {
+            plan_config_bundle(db, bundle.clone(), *strategy)
+        }
+        ImportSource::SyncMappings { export, merge } => {
+            sync_mappings::plan_sync_mappings(db, export, *merge)
+        }
+    }
+}
+
+fn take_cached_plan(plan_id: &str) -> Result<CachedPlan, String> {
+    let mut cache = PLAN_CACHE
+        .lock()
+        .map_err(|_| "Import plan cache is poisoned".to_string())?;
+    cache.retain(|_, cached| cached.created_at.elapsed() < PLAN_TTL);
+    cache
+        .remove(plan_id)
+        .ok_or_else(|| "Import plan not found or expired; validate the import again".to_string())
+}
+
+/// Parse an import and report what it would create, update, skip and remove. `kind` is
+/// "config_bundle" (`mode`: a merge strategy, default "merge-keep-existing") or
+/// "sync_mappings" (`mode`: "merge" or "replace", default "merge"). Nothing is written.
+#[tauri::command]
+pub async fn validate_import(
+    state: State<'_, SqliteDbState>,
+    bundle: String,
+    kind: String,
+    mode: Option<String>,
+) -> Result<ImportPlan, String> {
+    let mode = mode.unwrap_or_else(|| default_mode(&kind).to_string());
+    let mut plan = ImportPlan {
+        plan_id: None,
+        kind: kind.clone(),
+        mode: mode.clone(),
+        changes: PlannedChanges::default(),
+    };
+    let source = match parse_source(&bundle, &kind, &mode) {
+        Ok(source) => source,
+        Err(error) => {
+            plan.changes.errors.push(error);
+            return Ok(plan);
+        }
+    };
+    plan.changes = match plan_source(&state, &source) {
+        Ok(changes) => changes,
+        Err(error) => {
+            plan.changes.errors.push(error);
+            return Ok(plan);
+        }
+    };
+    if !plan.changes.errors.is_empty() {
+        return Ok(plan);
+    }
+
+    let plan_id = uuid::Uuid::new_v4().to_string();
+    plan.plan_id = Some(plan_id.clone());
+    let mut cache = PLAN_CACHE
+        .lock()
+        .map_err(|_| "Import plan cache is poisoned".to_string())?;
+    cache.retain(|_, cached| cached.created_at.elapsed() < PLAN_TTL);
+    cache.insert(
+        plan_id,
+        CachedPlan {
+            created_at: Instant::now(),
+            source,
+            plan: plan.clone(),
+        },
+    );
+    Ok(plan)
+}
+
+/// Apply a plan from `validate_import`. Fails without writing when the plan expired or the
+/// current data would no longer produce the same plan.
+#[tauri::command]
+pub async fn commit_import(
+    app: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    plan_id: String,
+) -> Result<ImportPlan, String> {
+    let cached = take_cached_plan(&plan_id)?;
+    if plan_source(&state, &cached.source)? != cached.plan.changes {
+        return Err(
+            "The data changed since this import was validated; validate it again".to_string(),
+        );
+    }
+
+    match cached.source {
+        ImportSource::ConfigBundle { bundle, strategy } => {
+            let result = apply_config_bundle(&state, bundle, strategy)?;
+            log::info!(
+                "Config bundle plan committed ({}): {} records, {} skipped, {} removed",
+                cached.plan.mode,
+                result.imported,
+                result.skipped,
+                result.removed
+            );
+            let _ = app.emit("config-changed", "window");
+        }
+        ImportSource::SyncMappings { export, merge } => {
+            sync_mappings::apply_sync_mappings(&app, &state, export, merge)?;
+        }
+    }
+    Ok(cached.plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::helpers::db_put;
+    use crate::db::schema::DbTable;
+    use serde_json::json;
+
+    #[test]
+    fn config_bundle_plan_lists_changes_and_detects_stale_data() {
+        let state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        state
+            .with_conn(|conn| {
+                db_put(conn, DbTable::McpServer, "a", &json!({ "name": "local-a" }))?;
+                db_put(conn, DbTable::McpServer, "b", &json!({ "name": "local-b" }))
+            })
+            .expect("seed");
+        let bundle = json!({
+            "format": "ai-toolbox-config-bundle",
+            "version": 1,
+            "tables": {
+                "mcp_server": [
+                    { "id": "b", "name": "bundle-b" },
+                    { "id": "c", "name": "bundle-c" }
+                ]
+            }
+        })
+        .to_string();
+
+        let source = parse_source(&bundle, "config_bundle", "replace").expect("source");
+        let changes = plan_source(&state, &source).expect("plan");
+        let keys = |items: &[ImportPlanItem]| -> Vec<String> {
+            items.iter().map(|item| item.key.clone()).collect()
+        };
+        assert_eq!(keys(&changes.create), vec!["c"]);
+        assert_eq!(keys(&changes.update), vec!["b"]);
+        assert_eq!(keys(&changes.remove), vec!["a"]);
+        assert_eq!(changes.remove[0].label, "local-a");
+
+        state
+            .with_conn(|conn| db_put(conn, DbTable::McpServer, "c", &json!({ "name": "new" })))
+            .expect("change");
+        assert_ne!(plan_source(&state, &source).expect("replan"), changes);
+
+        assert!(parse_source(&bundle, "workspace", "merge").is_err());
+        assert!(parse_source("{", "config_bundle", "replace").is_err());
+    }
+}
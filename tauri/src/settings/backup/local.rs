@@ -5,6 +5,12 @@ use tauri::Manager;
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
+use super::differential::{
+    full_backup_file_name, prepare_local_restore_archive, verify_local_backup_file,
+};
+use super::manifest::{
+    add_full_backup_manifest_to_file, BackupVerifyReport, BACKUP_MANIFEST_ZIP_PATH,
+};
 use super::utils::{
     clear_restored_cli_custom_roots, get_claude_mcp_restore_path, get_claude_restore_dir,
     get_codex_restore_dir, get_db_path, get_gemini_cli_restore_dir, get_grok_restore_dir,
@@ -70,9 +76,8 @@ pub async fn backup_database(
     }
 
     // Generate backup filename with timestamp
-    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-    let backup_filename = format!("ai-toolbox-backup-{}.zip", timestamp);
-    let backup_file_path = backup_dir.join(&backup_filename);
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let backup_file_path = backup_dir.join(full_backup_file_name(&timestamp));
 
     let file = File::create(&backup_file_path)
         .map_err(|e| format!("Failed to create backup file: {}", e))?;
@@ -90,16 +95,29 @@ pub async fn backup_database(
     .await?;
     zip.finish()
         .map_err(|e| format!("Failed to finish zip: {}", e))?;
+    add_full_backup_manifest_to_file(&backup_file_path)?;
 
     Ok(backup_file_path.to_string_lossy().to_string())
 }
 
-/// Restore database from a zip file
+/// Check a local backup zip against its checksum manifest and report every file
+#[tauri::command]
+pub async fn verify_backup(path: String) -> Result<BackupVerifyReport, String> {
+    let zip_path = Path::new(&path);
+    if !zip_path.exists() {
+        return Err("Backup file does not exist".to_string());
+    }
+    verify_local_backup_file(zip_path).map(|(report, _)| report)
+}
+
+/// Restore database from a zip file. A backup whose checksum manifest does not match is
+/// refused unless `force` is set.
 #[tauri::command]
 pub async fn restore_database(
     app_handle: tauri::AppHandle,
     zip_file_path: String,
     skip_cli_custom_roots: Option<bool>,
+    force: Option<bool>,
 ) -> Result<RestoreResult, String> {
     let db_path = get_db_path(&app_handle)?;
     let zip_path = Path::new(&zip_file_path);
@@ -109,6 +127,12 @@ pub async fn restore_database(
         return Err("Backup file does not exist".to_string());
     }
 
+    // Verify the checksum manifest; differential backups are rebuilt with their baseline.
+    let rebuilt_archive = prepare_local_restore_archive(zip_path, force.unwrap_or(false))?;
+    let zip_path = rebuilt_archive
+        .as_ref()
+        .map_or(zip_path, |rebuilt| rebuilt.path());
+
     // Open zip file
     let file = File::open(zip_path).map_err(|e| format!("Failed to open backup file: {}", e))?;
     let mut archive =
@@ -288,7 +312,7 @@ pub async fn restore_database(
         if file_name == ".backup_marker" || file_name == "db/.backup_marker" {
             continue;
         }
-        if file_name == "backup_meta.json" {
+        if file_name == "backup_meta.json" || file_name == BACKUP_MANIFEST_ZIP_PATH {
             continue;
         }
         // Skip optional (DB-backed) CLI runtime configs when the pre-restore setting disables them.
@@ -711,6 +735,32 @@ pub async fn restore_database(
     Ok(restore_result)
 }
 
+/// Database size in bytes (including the WAL) before and after `compact_database`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactDatabaseResult {
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+/// VACUUM the SQLite database and truncate its WAL. Refused while a backup, restore or
+/// WSL/SSH sync is running; other commands wait on the connection lock until it finishes.
+#[tauri::command]
+pub async fn compact_database(
+    state: tauri::State<'_, SqliteDbState>,
+) -> Result<CompactDatabaseResult, String> {
+    let _job = crate::db::jobs::DbJobGuard::begin_exclusive("compact")?;
+    let db_path = state.db_path().to_path_buf();
+    let size_before = crate::db::backup::database_files_size(&db_path);
+    state.with_conn(crate::db::backup::compact)?;
+    let size_after = crate::db::backup::database_files_size(&db_path);
+    log::info!("Database compacted: {} -> {} bytes", size_before, size_after);
+    Ok(CompactDatabaseResult {
+        size_before,
+        size_after,
+    })
+}
+
 /// Get database directory path for frontend
 #[tauri::command]
 pub fn get_database_path(app_handle: tauri::AppHandle) -> Result<String, String> {
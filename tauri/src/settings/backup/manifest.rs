@@ -0,0 +1,361 @@
+//! Checksum manifest written into every backup zip, and the checks run against it.
+//!
+//! `backup_manifest.json` lists the SHA-256 and size of each entry of the complete backup, in
+//! archive order, plus the total size. It is added after the archive is finished, so it also
+//! covers entries written by `write_backup_zip_contents` helpers that know nothing about it.
+//! Backups made before manifests existed have none and can only be restored unchecked.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Cursor, Read, Seek, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use super::differential::BACKUP_MODE_DIFFERENTIAL;
+
+pub const BACKUP_MANIFEST_ZIP_PATH: &str = "backup_manifest.json";
+pub const BACKUP_KIND_FULL: &str = "full";
+const BACKUP_MANIFEST_VERSION: u32 = 1;
+
+/// Checksum of one zip entry of the complete backup
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifestEntry {
+    pub path: String,
+    pub sha256: String,
+    /// Uncompressed size in bytes
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// Contents of `backup_manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u32,
+    /// "full" | "differential"
+    pub kind: String,
+    /// File name of the baseline a differential backup applies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Sum of the uncompressed sizes of all entries
+    #[serde(default)]
+    pub total_size: u64,
+    /// Every entry of the complete backup in archive order, including the ones kept in the baseline
+    pub entries: Vec<BackupManifestEntry>,
+}
+
+impl BackupManifest {
+    pub fn new(kind: &str, base: Option<String>, entries: Vec<BackupManifestEntry>) -> Self {
+        Self {
+            version: BACKUP_MANIFEST_VERSION,
+            kind: kind.to_string(),
+            base,
+            total_size: entries.iter().map(|entry| entry.size).sum(),
+            entries,
+        }
+    }
+
+    pub fn is_differential(&self) -> bool {
+        self.kind == BACKUP_MODE_DIFFERENTIAL
+    }
+}
+
+/// Check result for one file listed in the manifest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupFileCheck {
+    pub path: String,
+    /// "ok" | "mismatch" | "missing" | "unreadable"
+    pub status: String,
+    pub expected_sha256: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_sha256: Option<String>,
+    pub expected_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BackupFileCheck {
+    pub fn passed(&self) -> bool {
+        self.status == "ok"
+    }
+}
+
+/// Per-file verification report of a backup archive
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupVerifyReport {
+    /// False for backups made before manifests were written; nothing could be checked
+    pub has_manifest: bool,
+    /// True when a manifest exists and every listed file matches it
+    pub passed: bool,
+    /// "full" | "differential"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub expected_total_size: u64,
+    pub actual_total_size: u64,
+    pub files: Vec<BackupFileCheck>,
+}
+
+impl BackupVerifyReport {
+    pub fn without_manifest() -> Self {
+        Self {
+            has_manifest: false,
+            passed: false,
+            kind: None,
+            expected_total_size: 0,
+            actual_total_size: 0,
+            files: Vec::new(),
+        }
+    }
+
+    /// One-line summary of the failed files, for restore errors and logs
+    pub fn failure_summary(&self) -> String {
+        let failed: Vec<String> = self
+            .files
+            .iter()
+            .filter(|file| !file.passed())
+            .map(|file| format!("{} ({})", file.path, file.status))
+            .collect();
+        format!(
+            "Backup checksum verification failed for {} file(s): {}",
+            failed.len(),
+            failed.join(", ")
+        )
+    }
+}
+
+pub fn open_backup_archive(path: &Path) -> Result<ZipArchive<File>, String> {
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open backup file {}: {}", path.display(), e))?;
+    ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read zip archive {}: {}", path.display(), e))
+}
+
+fn hash_zip_entry<R: Read>(entry: &mut R) -> std::io::Result<(String, u64)> {
+    let mut hasher = Sha256::new();
+    let size = std::io::copy(entry, &mut hasher)?;
+    Ok((hex::encode(hasher.finalize()), size))
+}
+
+/// Hash every entry except the manifest itself, in archive order.
+pub fn hash_zip_entries<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<Vec<BackupManifestEntry>, String> {
+    let mut entries = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+        let path = file.name().to_string();
+        if path == BACKUP_MANIFEST_ZIP_PATH {
+            continue;
+        }
+        let (sha256, size) = hash_zip_entry(&mut file)
+            .map_err(|e| format!("Failed to hash zip entry {}: {}", path, e))?;
+        entries.push(BackupManifestEntry { path, sha256, size });
+    }
+    Ok(entries)
+}
+
+pub fn read_backup_manifest<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<Option<BackupManifest>, String> {
+    let mut file = match archive.by_name(BACKUP_MANIFEST_ZIP_PATH) {
+        Ok(file) => file,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("Failed to read backup manifest: {}", e)),
+    };
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read backup manifest: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Invalid backup manifest: {}", e))
+}
+
+pub fn write_backup_manifest<W: Write + Seek>(
+    zip: &mut ZipWriter<W>,
+    manifest: &BackupManifest,
+) -> Result<(), String> {
+    let content = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(BACKUP_MANIFEST_ZIP_PATH, options)
+        .map_err(|e| format!("Failed to add backup manifest: {}", e))?;
+    zip.write_all(&content)
+        .map_err(|e| format!("Failed to write backup manifest: {}", e))
+}
+
+fn append_full_backup_manifest<A: Read + Write + Seek>(mut target: A) -> Result<A, String> {
+    let entries = hash_zip_entries(
+        &mut ZipArchive::new(&mut target)
+            .map_err(|e| format!("Failed to read zip archive: {}", e))?,
+    )?;
+    let mut zip =
+        ZipWriter::new_append(target).map_err(|e| format!("Failed to reopen backup zip: {}", e))?;
+    write_backup_manifest(
+        &mut zip,
+        &BackupManifest::new(BACKUP_KIND_FULL, None, entries),
+    )?;
+    zip.finish()
+        .map_err(|e| format!("Failed to finish zip: {}", e))
+}
+
+/// Add a full-backup manifest to an in-memory backup zip.
+pub fn add_full_backup_manifest(zip_data: Vec<u8>) -> Result<Vec<u8>, String> {
+    append_full_backup_manifest(Cursor::new(zip_data)).map(Cursor::into_inner)
+}
+
+/// Add a full-backup manifest to a backup zip already written to disk.
+pub fn add_full_backup_manifest_to_file(path: &Path) -> Result<(), String> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open backup file: {}", e))?;
+    append_full_backup_manifest(file).map(|_| ())
+}
+
+/// Check every file listed in `manifest` against `archive`. Entries that fail to decompress
+/// (typically a truncated upload) are reported as "unreadable" instead of aborting the check.
+pub fn verify_backup_archive<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    manifest: &BackupManifest,
+) -> BackupVerifyReport {
+    let files: Vec<BackupFileCheck> = manifest
+        .entries
+        .iter()
+        .map(|expected| {
+            let mut check = BackupFileCheck {
+                path: expected.path.clone(),
+                status: "ok".to_string(),
+                expected_sha256: expected.sha256.clone(),
+                actual_sha256: None,
+                expected_size: expected.size,
+                actual_size: None,
+                error: None,
+            };
+            let hashed = match archive.by_name(&expected.path) {
+                Ok(mut file) => hash_zip_entry(&mut file).map_err(|e| e.to_string()),
+                Err(ZipError::FileNotFound) => {
+                    check.status = "missing".to_string();
+                    return check;
+                }
+                Err(e) => Err(e.to_string()),
+            };
+            match hashed {
+                Ok((sha256, size)) => {
+                    if sha256 != expected.sha256 {
+                        check.status = "mismatch".to_string();
+                    }
+                    check.actual_sha256 = Some(sha256);
+                    check.actual_size = Some(size);
+                }
+                Err(error) => {
+                    check.status = "unreadable".to_string();
+                    check.error = Some(error);
+                }
+            }
+            check
+        })
+        .collect();
+
+    let actual_total_size = files.iter().filter_map(|file| file.actual_size).sum();
+    BackupVerifyReport {
+        has_manifest: true,
+        passed: files.iter().all(BackupFileCheck::passed)
+            && actual_total_size == manifest.total_size,
+        kind: Some(manifest.kind.clone()),
+        expected_total_size: manifest.total_size,
+        actual_total_size,
+        files,
+    }
+}
+
+/// Verify an in-memory backup zip, such as one downloaded from WebDAV.
+pub fn verify_backup_data(zip_data: &[u8]) -> Result<BackupVerifyReport, String> {
+    let mut archive = ZipArchive::new(Cursor::new(zip_data))
+        .map_err(|e| format!("Failed to read zip archive: {}", e))?;
+    Ok(match read_backup_manifest(&mut archive)? {
+        Some(manifest) => verify_backup_archive(&mut archive, &manifest),
+        None => BackupVerifyReport::without_manifest(),
+    })
+}
+
+/// Fail unless `report` passed; with `force` a failure is only logged.
+pub fn enforce_backup_verification(report: &BackupVerifyReport, force: bool) -> Result<(), String> {
+    if report.passed || !report.has_manifest {
+        return Ok(());
+    }
+    let summary = report.failure_summary();
+    if force {
+        log::warn!("Restoring despite failed verification: {}", summary);
+        Ok(())
+    } else {
+        Err(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backup_zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, content) in files {
+            zip.start_file(*path, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn verify_reports_each_file_and_total_size() {
+        let data = add_full_backup_manifest(backup_zip(&[
+            ("sqlite/ai-toolbox.db", "database"),
+            ("skills/a.md", "skill"),
+        ]))
+        .unwrap();
+        let report = verify_backup_data(&data).unwrap();
+        assert!(report.passed);
+        assert_eq!(report.expected_total_size, 13);
+        assert_eq!(report.actual_total_size, 13);
+
+        let manifest = read_backup_manifest(&mut ZipArchive::new(Cursor::new(&data)).unwrap())
+            .unwrap()
+            .unwrap();
+        let tampered = backup_zip(&[
+            ("sqlite/ai-toolbox.db", "databasX"),
+            (
+                BACKUP_MANIFEST_ZIP_PATH,
+                &serde_json::to_string(&manifest).unwrap(),
+            ),
+        ]);
+        let report = verify_backup_data(&tampered).unwrap();
+        assert!(!report.passed);
+        let statuses: Vec<(&str, &str)> = report
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.status.as_str()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("sqlite/ai-toolbox.db", "mismatch"),
+                ("skills/a.md", "missing")
+            ]
+        );
+        assert!(enforce_backup_verification(&report, false).is_err());
+        assert!(enforce_backup_verification(&report, true).is_ok());
+
+        let legacy = verify_backup_data(&backup_zip(&[("db/data", "x")])).unwrap();
+        assert!(!legacy.has_manifest);
+        assert!(enforce_backup_verification(&legacy, false).is_ok());
+    }
+}
@@ -1,5 +1,10 @@
 pub mod auto_backup;
+pub mod config_bundle;
+pub mod differential;
+pub mod import_plan;
 pub mod local;
+pub mod manifest;
+pub mod raw_db;
 pub mod utils;
 pub mod webdav;
 
@@ -0,0 +1,127 @@
+//! Raw database export/import for diagnostics and manual migration.
+//!
+//! Unlike the backup zip, this copies the live SQLite database byte for byte: no external
+//! config files, no filter rules, no post-restore re-apply. Snapshots are only importable into
+//! the same schema version, and both commands require the `diagnostics_enabled` setting.
+//! The legacy SurrealDB store is read once for migration only, so it has no raw export here.
+
+use std::path::Path;
+
+use chrono::Local;
+use rusqlite::{Connection, OpenFlags};
+use tauri::{Emitter, Manager, State};
+
+use crate::db::SqliteDbState;
+use crate::settings::store;
+
+pub(crate) const RAW_EXPORT_DIR: &str = "raw-db-exports";
+
+fn ensure_diagnostics_enabled(state: &SqliteDbState) -> Result<(), String> {
+    if store::load_settings_from_sqlite_state(state)?.diagnostics_enabled {
+        Ok(())
+    } else {
+        Err("Raw database export/import requires diagnostics to be enabled".to_string())
+    }
+}
+
+fn read_snapshot_schema_version(path: &Path) -> Result<i32, String> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|error| format!("Failed to open {}: {error}", path.display()))?;
+    crate::db::migrations::get_user_version(&conn)
+}
+
+/// Export the raw SQLite database to `{app_data}/raw-db-exports` and return the file path.
+/// The snapshot is version-sensitive and includes secrets stored in the database.
+#[tauri::command]
+pub async fn db_export_all(
+    app_handle: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+) -> Result<String, String> {
+    ensure_diagnostics_enabled(&state)?;
+    let export_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(RAW_EXPORT_DIR);
+    std::fs::create_dir_all(&export_dir)
+        .map_err(|e| format!("Failed to create export dir: {}", e))?;
+
+    let export_path = export_dir.join(format!(
+        "ai-toolbox-raw-{}.db",
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    state.with_conn(|conn| crate::db::backup::backup_to_path(conn, &export_path))?;
+    log::warn!(
+        "Raw database exported to {}; it can only be imported by the same schema version",
+        export_path.display()
+    );
+    Ok(export_path.to_string_lossy().to_string())
+}
+
+/// Replace the live database with a raw snapshot from `db_export_all`.
+/// Runtime config files are not touched; re-apply providers afterwards if needed.
+#[tauri::command]
+pub async fn db_import_all(
+    app_handle: tauri::AppHandle,
+    state: State<'_, SqliteDbState>,
+    path: String,
+) -> Result<(), String> {
+    let _job = crate::db::jobs::DbJobGuard::begin("restore");
+    ensure_diagnostics_enabled(&state)?;
+    let snapshot_path = Path::new(&path);
+    if !snapshot_path.is_file() {
+        return Err("Raw database snapshot does not exist".to_string());
+    }
+
+    let snapshot_version = read_snapshot_schema_version(snapshot_path)?;
+    let current_version = state.with_conn(crate::db::migrations::get_user_version)?;
+    if snapshot_version != current_version {
+        return Err(format!(
+            "Raw database snapshot uses schema version {} but this app uses {}",
+            snapshot_version, current_version
+        ));
+    }
+
+    state.with_conn_mut(|conn| {
+        conn.restore(
+            rusqlite::MAIN_DB,
+            snapshot_path,
+            None::<fn(rusqlite::backup::Progress)>,
+        )
+        .map_err(|error| format!("Failed to import raw database snapshot: {error}"))
+    })?;
+    log::warn!("Raw database imported from {}", snapshot_path.display());
+
+    let _ = app_handle.emit("config-changed", "window");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_schema_version_is_read_without_modifying_file() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("snapshot.db");
+        {
+            let conn = Connection::open(&path).expect("create snapshot");
+            conn.execute_batch("PRAGMA user_version = 7;")
+                .expect("set user_version");
+        }
+
+        assert_eq!(read_snapshot_schema_version(&path).unwrap(), 7);
+        assert!(read_snapshot_schema_version(&temp.path().join("missing.db")).is_err());
+    }
+
+    #[test]
+    fn raw_export_is_gated_by_diagnostics_setting() {
+        let state = SqliteDbState::in_memory_for_test().expect("sqlite state");
+        assert!(ensure_diagnostics_enabled(&state).is_err());
+
+        let mut settings = store::load_settings_from_sqlite_state(&state).expect("settings");
+        settings.diagnostics_enabled = true;
+        store::save_settings_to_sqlite_state(&state, &settings).expect("save settings");
+        assert!(ensure_diagnostics_enabled(&state).is_ok());
+    }
+}
@@ -8,6 +8,7 @@ use walkdir::WalkDir;
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
+use super::manifest::add_full_backup_manifest;
 use crate::coding::open_code::shell_env;
 use crate::coding::skills::central_repo::{resolve_central_repo_path_sync, skill_storage_dir_name};
 use crate::coding::{claude_code, codex, gemini_cli, grok, pi, runtime_location};
@@ -76,6 +77,7 @@ pub fn restore_sqlite_database_snapshot_from_zip<R: Read + Seek>(
     archive: &mut ZipArchive<R>,
     app_handle: &tauri::AppHandle,
 ) -> Result<bool, String> {
+    let _job = crate::db::jobs::DbJobGuard::begin("restore");
     let Some(schema_version) = read_backup_schema_version(archive)? else {
         return Ok(false);
     };
@@ -114,11 +116,22 @@ pub fn restore_sqlite_database_snapshot_from_zip<R: Read + Seek>(
             &temp_path,
             None::<fn(rusqlite::backup::Progress)>,
         )
-        .map_err(|error| format!("Failed to restore SQLite backup snapshot: {error}"))
+        .map_err(|error| format!("Failed to restore SQLite backup snapshot: {error}"))?;
+        // Older snapshots are upgraded in place so the live connection matches this app.
+        crate::db::migrations::run_all(conn)
     });
     let _ = std::fs::remove_file(&temp_path);
     restore_result?;
 
+    // The snapshot may predate encryption at rest; the restore itself already succeeded.
+    if let Err(error) = crate::coding::secret_store::seal_claude_providers_if_enabled(&sqlite_state)
+    {
+        log::warn!(
+            "Failed to encrypt restored Claude provider secrets: {}",
+            error
+        );
+    }
+
     Ok(true)
 }
 
@@ -1089,7 +1102,7 @@ pub fn clear_restored_cli_custom_roots(db: &crate::db::SqliteDbState) -> Result<
     use crate::db::helpers::{db_get, db_put};
     use crate::db::schema::DbTable;
 
-    const PATH_KEYS: &[&str] = &["root_dir", "config_path", "rootDir", "configPath"];
+    const PATH_KEYS: &[&str] = &["root_dir", "config_path"];
 
     let clear_table = |conn: &rusqlite::Connection, table: DbTable| -> Result<(), String> {
         let Some(mut record) = db_get(conn, table, "common")? else {
@@ -2694,6 +2707,7 @@ pub(crate) async fn write_backup_zip_contents<W: Write + Seek>(
     filter_rules: &[BackupFileFilterRule],
     options: SimpleFileOptions,
 ) -> Result<(), String> {
+    let _job = crate::db::jobs::DbJobGuard::begin("backup");
     let db_state = app_handle.state::<crate::SqliteDbState>();
     let db = db_state.db();
     let mut added_zip_directories = HashSet::new();
@@ -2803,7 +2817,7 @@ pub(crate) async fn write_backup_zip_contents<W: Write + Seek>(
     Ok(())
 }
 
-/// Create a temporary backup zip file and return its contents as bytes
+/// Create a backup zip in memory, checksum manifest included, and return its bytes
 pub async fn create_backup_zip(
     app_handle: &tauri::AppHandle,
     db_path: &Path,
@@ -2832,7 +2846,7 @@ pub async fn create_backup_zip(
             .map_err(|e| format!("Failed to finish zip: {}", e))?;
     }
 
-    Ok(buffer.into_inner())
+    add_full_backup_manifest(buffer.into_inner())
 }
 
 #[cfg(test)]
@@ -3100,7 +3114,7 @@ mod tests {
                 "common",
                 &serde_json::json!({
                     "id": "common",
-                    "configPath": "C:/old/opencode.jsonc"
+                    "config_path": "C:/old/opencode.jsonc"
                 }),
             )?;
             Ok(())
@@ -3122,7 +3136,7 @@ mod tests {
                 Some("preserved")
             );
             assert!(opencode
-                .get("configPath")
+                .get("config_path")
                 .is_some_and(serde_json::Value::is_null));
             Ok(())
         })
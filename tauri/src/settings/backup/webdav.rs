@@ -1,11 +1,20 @@
+use bytes::Bytes;
 use chrono::Local;
+use futures_util::StreamExt;
 use log::{error, info};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
-use tauri::Manager;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
 use zip::ZipArchive;
 
+use super::manifest::{
+    enforce_backup_verification, verify_backup_data, BackupVerifyReport, BACKUP_MANIFEST_ZIP_PATH,
+};
 use super::utils::{
     clear_restored_cli_custom_roots, create_backup_zip, get_claude_mcp_restore_path,
     get_claude_restore_dir, get_codex_restore_dir, get_db_path, get_gemini_cli_restore_dir,
@@ -45,6 +54,121 @@ fn set_pi_auth_file_permissions(path: &Path) {
 #[cfg(not(unix))]
 fn set_pi_auth_file_permissions(_path: &Path) {}
 
+const BACKUP_UPLOAD_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Cancel flags of running WebDAV backups, keyed by the caller's request id
+static BACKUP_UPLOAD_CANCELS: LazyLock<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Payload of the `webdav-backup-progress` event
+///
+/// `stage` is "start", "progress", "finish", "cancelled" or "failed". A server that rejects
+/// the streamed upload gets a second "start" before the archive is resent in one request.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebDAVBackupProgress {
+    pub request_id: Option<String>,
+    pub stage: String,
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+}
+
+fn emit_backup_progress(
+    app_handle: &tauri::AppHandle,
+    request_id: Option<&str>,
+    stage: &str,
+    bytes_sent: u64,
+    total_bytes: u64,
+) {
+    let _ = app_handle.emit(
+        "webdav-backup-progress",
+        WebDAVBackupProgress {
+            request_id: request_id.map(str::to_string),
+            stage: stage.to_string(),
+            bytes_sent,
+            total_bytes,
+        },
+    );
+}
+
+/// Registers the cancel flag of one backup and unregisters it when dropped
+struct BackupCancelGuard {
+    request_id: Option<String>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl BackupCancelGuard {
+    fn register(request_id: Option<&str>) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        if let (Some(request_id), Ok(mut cancels)) = (request_id, BACKUP_UPLOAD_CANCELS.lock()) {
+            cancels.insert(request_id.to_string(), cancelled.clone());
+        }
+        Self {
+            request_id: request_id.map(str::to_string),
+            cancelled,
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    async fn wait_cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+}
+
+impl Drop for BackupCancelGuard {
+    fn drop(&mut self) {
+        if let (Some(request_id), Ok(mut cancels)) =
+            (self.request_id.as_ref(), BACKUP_UPLOAD_CANCELS.lock())
+        {
+            cancels.remove(request_id);
+        }
+    }
+}
+
+fn cancelled_backup_error() -> String {
+    WebDAVError::new(
+        "CANCELLED",
+        "WebDAV backup cancelled",
+        "settings.webdav.errors.cancelled",
+    )
+    .to_json()
+}
+
+/// Split the archive into chunks so each one handed to the connection reports progress.
+fn split_upload_chunks(data: &Bytes) -> Vec<Bytes> {
+    (0..data.len())
+        .step_by(BACKUP_UPLOAD_CHUNK_SIZE)
+        .map(|start| data.slice(start..(start + BACKUP_UPLOAD_CHUNK_SIZE).min(data.len())))
+        .collect()
+}
+
+/// Streaming request body that emits a "progress" event per chunk.
+fn progress_upload_body(
+    app_handle: tauri::AppHandle,
+    request_id: Option<String>,
+    data: &Bytes,
+    bytes_sent: Arc<AtomicU64>,
+) -> reqwest::Body {
+    let total_bytes = data.len() as u64;
+    let frames = futures_util::stream::iter(split_upload_chunks(data)).map(move |chunk| {
+        let sent = bytes_sent.fetch_add(chunk.len() as u64, Ordering::SeqCst) + chunk.len() as u64;
+        emit_backup_progress(
+            &app_handle,
+            request_id.as_deref(),
+            "progress",
+            sent,
+            total_bytes,
+        );
+        Ok::<_, std::io::Error>(hyper::body::Frame::data(chunk))
+    });
+    reqwest::Body::wrap(http_body_util::StreamBody::new(frames))
+}
+
 /// Backup file info structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackupFileInfo {
@@ -209,8 +333,10 @@ pub async fn backup_to_webdav(
     password: String,
     remote_path: String,
     host_label: String,
+    request_id: Option<String>,
 ) -> Result<String, String> {
     info!("Starting WebDAV backup to: {}", url);
+    let cancel = BackupCancelGuard::register(request_id.as_deref());
 
     let db_path = get_db_path(&app_handle)?;
 
@@ -266,14 +392,64 @@ pub async fn backup_to_webdav(
             e
         })?;
 
-    let response = client
-        .put(&full_url)
-        .basic_auth(&username, Some(&password))
-        .body(zip_data)
-        .send()
-        .await;
+    if cancel.is_cancelled() {
+        return Err(cancelled_backup_error());
+    }
 
-    match response {
+    let zip_data = Bytes::from(zip_data);
+    let total_bytes = zip_data.len() as u64;
+    let bytes_sent = Arc::new(AtomicU64::new(0));
+    emit_backup_progress(&app_handle, request_id.as_deref(), "start", 0, total_bytes);
+
+    let upload = async {
+        let streamed = client
+            .put(&full_url)
+            .basic_auth(&username, Some(&password))
+            .header(reqwest::header::CONTENT_LENGTH, total_bytes)
+            .body(progress_upload_body(
+                app_handle.clone(),
+                request_id.clone(),
+                &zip_data,
+                bytes_sent.clone(),
+            ))
+            .send()
+            .await;
+        match streamed {
+            // Some servers refuse streamed request bodies; resend the archive in one piece.
+            Ok(resp) if matches!(resp.status().as_u16(), 411 | 501) => {
+                info!(
+                    "WebDAV server rejected streamed upload ({}), retrying without streaming",
+                    resp.status()
+                );
+                emit_backup_progress(&app_handle, request_id.as_deref(), "start", 0, total_bytes);
+                bytes_sent.store(0, Ordering::SeqCst);
+                client
+                    .put(&full_url)
+                    .basic_auth(&username, Some(&password))
+                    .body(zip_data.clone())
+                    .send()
+                    .await
+            }
+            other => other,
+        }
+    };
+
+    let response = tokio::select! {
+        response = upload => response,
+        _ = cancel.wait_cancelled() => {
+            info!("WebDAV backup cancelled: {}", full_url);
+            emit_backup_progress(
+                &app_handle,
+                request_id.as_deref(),
+                "cancelled",
+                bytes_sent.load(Ordering::SeqCst),
+                total_bytes,
+            );
+            return Err(cancelled_backup_error());
+        }
+    };
+
+    let result = match response {
         Ok(resp) => {
             if resp.status().is_success() {
                 info!("WebDAV backup successful: {}", full_url);
@@ -289,7 +465,27 @@ pub async fn backup_to_webdav(
             error!("WebDAV backup failed: {:?}", error);
             Err(error.to_json())
         }
+    };
+    let (stage, sent) = if result.is_ok() {
+        ("finish", total_bytes)
+    } else {
+        ("failed", bytes_sent.load(Ordering::SeqCst))
+    };
+    emit_backup_progress(&app_handle, request_id.as_deref(), stage, sent, total_bytes);
+    result
+}
+
+/// Cancel a running `backup_to_webdav` started with `request_id`; unknown ids are ignored.
+#[tauri::command]
+pub fn cancel_webdav_backup(request_id: String) -> Result<(), String> {
+    if let Some(cancelled) = BACKUP_UPLOAD_CANCELS
+        .lock()
+        .map_err(|_| "WebDAV backup cancel lock is poisoned".to_string())?
+        .get(&request_id)
+    {
+        cancelled.store(true, Ordering::SeqCst);
     }
+    Ok(())
 }
 
 /// Internal function: List backup files from WebDAV server
@@ -478,23 +674,15 @@ pub async fn delete_webdav_backup(
     delete_webdav_backup_internal(&state, &url, &username, &password, &remote_path, &filename).await
 }
 
-/// Restore database from WebDAV server
-#[tauri::command]
-pub async fn restore_from_webdav(
-    app_handle: tauri::AppHandle,
-    state: tauri::State<'_, SqliteDbState>,
-    url: String,
-    username: String,
-    password: String,
-    remote_path: String,
-    filename: String,
-    skip_cli_custom_roots: Option<bool>,
-) -> Result<RestoreResult, String> {
-    let skip_cli_custom_roots = skip_cli_custom_roots.unwrap_or(false);
-    info!("Starting WebDAV restore from: {}/{}", url, filename);
-
-    let db_path = get_db_path(&app_handle)?;
-
+/// Download a backup archive from the WebDAV server into memory
+async fn download_webdav_backup(
+    state: &SqliteDbState,
+    url: &str,
+    username: &str,
+    password: &str,
+    remote_path: &str,
+    filename: &str,
+) -> Result<Bytes, String> {
     // Build WebDAV URL
     let base_url = url.trim_end_matches('/');
     let remote = remote_path.trim_matches('/');
@@ -507,7 +695,7 @@ pub async fn restore_from_webdav(
     info!("Downloading backup from: {}", full_url);
 
     // Download from WebDAV with proxy support
-    let client = http_client::client_with_timeout(&state, 300)
+    let client = http_client::client_with_timeout(state, 300)
         .await
         .map_err(|e| {
             error!("Failed to create HTTP client: {}", e);
@@ -516,29 +704,71 @@ pub async fn restore_from_webdav(
 
     let response = client
         .get(&full_url)
-        .basic_auth(&username, Some(&password))
+        .basic_auth(username, Some(password))
         .send()
         .await;
 
-    let zip_data = match response {
+    match response {
         Ok(resp) => {
             if resp.status().is_success() {
                 resp.bytes().await.map_err(|e| {
                     error!("Failed to read response: {}", e);
                     format!("Failed to read response: {}", e)
-                })?
+                })
             } else {
                 let error = analyze_http_error(resp.status(), &full_url);
                 error!("WebDAV download failed: {:?}", error);
-                return Err(error.to_json());
+                Err(error.to_json())
             }
         }
         Err(e) => {
             let error = analyze_reqwest_error(&e, &full_url);
             error!("WebDAV download failed: {:?}", error);
-            return Err(error.to_json());
+            Err(error.to_json())
         }
-    };
+    }
+}
+
+/// Download a WebDAV backup and check it against its checksum manifest
+#[tauri::command]
+pub async fn verify_webdav_backup(
+    state: tauri::State<'_, SqliteDbState>,
+    url: String,
+    username: String,
+    password: String,
+    remote_path: String,
+    filename: String,
+) -> Result<BackupVerifyReport, String> {
+    let zip_data =
+        download_webdav_backup(&state, &url, &username, &password, &remote_path, &filename).await?;
+    verify_backup_data(&zip_data)
+}
+
+/// Restore database from WebDAV server. A backup whose checksum manifest does not match is
+/// refused unless `force` is set.
+#[tauri::command]
+pub async fn restore_from_webdav(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, SqliteDbState>,
+    url: String,
+    username: String,
+    password: String,
+    remote_path: String,
+    filename: String,
+    skip_cli_custom_roots: Option<bool>,
+    force: Option<bool>,
+) -> Result<RestoreResult, String> {
+    let skip_cli_custom_roots = skip_cli_custom_roots.unwrap_or(false);
+    info!("Starting WebDAV restore from: {}/{}", url, filename);
+
+    let db_path = get_db_path(&app_handle)?;
+
+    let zip_data =
+        download_webdav_backup(&state, &url, &username, &password, &remote_path, &filename).await?;
+
+    info!("Verifying backup checksums...");
+    let report = verify_backup_data(&zip_data)?;
+    enforce_backup_verification(&report, force.unwrap_or(false))?;
 
     info!("Extracting backup archive...");
 
@@ -724,7 +954,7 @@ pub async fn restore_from_webdav(
         if file_name == ".backup_marker" || file_name == "db/.backup_marker" {
             continue;
         }
-        if file_name == "backup_meta.json" {
+        if file_name == "backup_meta.json" || file_name == BACKUP_MANIFEST_ZIP_PATH {
             continue;
         }
         // Skip optional (DB-backed) CLI runtime configs when the pre-restore setting disables them.
@@ -1148,3 +1378,34 @@ pub async fn restore_from_webdav(
     info!("WebDAV restore completed successfully");
     Ok(restore_result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upload_chunks_cover_archive_in_order() {
+        let data = Bytes::from(vec![7u8; BACKUP_UPLOAD_CHUNK_SIZE * 2 + 10]);
+        let chunks = split_upload_chunks(&data);
+        let sizes: Vec<usize> = chunks.iter().map(Bytes::len).collect();
+        assert_eq!(
+            sizes,
+            vec![BACKUP_UPLOAD_CHUNK_SIZE, BACKUP_UPLOAD_CHUNK_SIZE, 10]
+        );
+        assert!(split_upload_chunks(&Bytes::new()).is_empty());
+    }
+
+    #[test]
+    fn cancel_reaches_registered_backup_until_it_ends() {
+        let guard = BackupCancelGuard::register(Some("backup-test"));
+        cancel_webdav_backup("backup-test".to_string()).unwrap();
+        assert!(guard.is_cancelled());
+
+        drop(guard);
+        assert!(!BACKUP_UPLOAD_CANCELS
+            .lock()
+            .unwrap()
+            .contains_key("backup-test"));
+        assert!(cancel_webdav_backup("backup-test".to_string()).is_ok());
+    }
+}
@@ -1,6 +1,14 @@
+use tauri::Emitter;
+
 use super::store;
-use super::types::{AppSettings, BackupFileFilterPathOption};
+use super::types::{
+    AppSettings, BackupFileFilterPathOption, ConfigSyncMode, CONFIG_SYNC_MODE_TOOLS,
+    MAX_LOG_RETENTION_DAYS, TRAY_PROVIDER_TOOLS,
+};
 use crate::auto_launch;
+use crate::db::helpers::{db_normalize_sort_index, db_transaction};
+use crate::db::migrations::{get_schema_version_info, SchemaVersionInfo};
+use crate::db::schema::{DbTable, OrderSpec};
 use crate::db::SqliteDbState;
 use crate::tray;
 
@@ -20,6 +28,7 @@ pub async fn save_settings(
     settings: AppSettings,
 ) -> Result<(), String> {
     store::save_settings_to_sqlite_state(&sqlite_state, &settings)?;
+    crate::coding::sync_trace::set_sync_trace_enabled(settings.sync_trace);
 
     if let Err(err) = tray::refresh_tray_menus(&app).await {
         log::warn!("Failed to refresh tray after saving settings: {err}");
@@ -61,6 +70,186 @@ pub fn get_auto_launch_status() -> Result<bool, String> {
         .map_err(|e| format!("Failed to check auto launch status: {}", e))
 }
 
+/// SQLite schema version of the open database and the version this build migrates to.
+#[tauri::command]
+pub async fn get_schema_version(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<SchemaVersionInfo, String> {
+    sqlite_state.with_conn(get_schema_version_info)
+}
+
+/// Get how many days of log files are kept.
+#[tauri::command]
+pub async fn get_log_retention_days(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<u32, String> {
+    Ok(store::load_settings_from_sqlite_state(&sqlite_state)?.log_retention_days)
+}
+
+/// Set how many days of log files are kept and prune older logs right away.
+#[tauri::command]
+pub async fn set_log_retention_days(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    days: u32,
+) -> Result<u32, String> {
+    if !(1..=MAX_LOG_RETENTION_DAYS).contains(&days) {
+        return Err(format!(
+            "Log retention must be between 1 and {} days",
+            MAX_LOG_RETENTION_DAYS
+        ));
+    }
+    let mut settings = store::load_settings_from_sqlite_state(&sqlite_state)?;
+    if settings.log_retention_days != days {
+        settings.log_retention_days = days;
+        store::save_settings_to_sqlite_state(&sqlite_state, &settings)?;
+    }
+    crate::cleanup_old_log_files(days);
+    Ok(days)
+}
+
+/// Toggle whether applying a tool config automatically requests a WSL sync.
+/// When off, apply emits `apply-sync-suppressed` instead of `wsl-sync-request-*` (Windows with
+/// WSL sync enabled only).
+#[tauri::command]
+pub async fn set_auto_sync_on_apply(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    enabled: bool,
+) -> Result<bool, String> {
+    let mut settings = store::load_settings_from_sqlite_state(&sqlite_state)?;
+    if settings.auto_sync_on_apply != enabled {
+        settings.auto_sync_on_apply = enabled;
+        store::save_settings_to_sqlite_state(&sqlite_state, &settings)?;
+    }
+    Ok(enabled)
+}
+
+fn ensure_config_sync_mode_tool(tool: &str) -> Result<(), String> {
+    if CONFIG_SYNC_MODE_TOOLS.contains(&tool) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unsupported config sync tool '{}', expected one of: {}",
+            tool,
+            CONFIG_SYNC_MODE_TOOLS.join(", ")
+        ))
+    }
+}
+
+/// Get the DB/file sync mode of a tool; `None` means the default behavior.
+#[tauri::command]
+pub async fn get_config_sync_mode(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    tool: String,
+) -> Result<Option<ConfigSyncMode>, String> {
+    ensure_config_sync_mode_tool(&tool)?;
+    Ok(store::load_settings_from_sqlite_state(&sqlite_state)?
+        .config_sync_modes
+        .get(&tool)
+        .copied())
+}
+
+/// Set the DB/file sync mode of a tool; `None` restores the default behavior.
+#[tauri::command]
+pub async fn set_config_sync_mode(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    tool: String,
+    mode: Option<ConfigSyncMode>,
+) -> Result<Option<ConfigSyncMode>, String> {
+    ensure_config_sync_mode_tool(&tool)?;
+    let mut settings = store::load_settings_from_sqlite_state(&sqlite_state)?;
+    let previous = match mode {
+        Some(mode) => settings.config_sync_modes.insert(tool, mode),
+        None => settings.config_sync_modes.remove(&tool),
+    };
+    if previous != mode {
+        store::save_settings_to_sqlite_state(&sqlite_state, &settings)?;
+    }
+    Ok(mode)
+}
+
+fn ensure_tray_provider_tool(tool: &str) -> Result<(), String> {
+    if TRAY_PROVIDER_TOOLS.contains(&tool) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unsupported tray provider tool '{}', expected one of: {}",
+            tool,
+            TRAY_PROVIDER_TOOLS.join(", ")
+        ))
+    }
+}
+
+/// Whether a tool's provider submenu is shown in the tray (default: shown).
+#[tauri::command]
+pub async fn get_providers_show_in_tray(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    tool: String,
+) -> Result<bool, String> {
+    ensure_tray_provider_tool(&tool)?;
+    Ok(!store::load_settings_from_sqlite_state(&sqlite_state)?
+        .tray_hidden_provider_tools
+        .contains(&tool))
+}
+
+/// Show or hide a tool's provider submenu in the tray and refresh the tray.
+#[tauri::command]
+pub async fn set_providers_show_in_tray(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    app: tauri::AppHandle,
+    tool: String,
+    enabled: bool,
+) -> Result<bool, String> {
+    ensure_tray_provider_tool(&tool)?;
+    let mut settings = store::load_settings_from_sqlite_state(&sqlite_state)?;
+    let hidden = settings.tray_hidden_provider_tools.contains(&tool);
+    if hidden == enabled {
+        if enabled {
+            settings
+                .tray_hidden_provider_tools
+                .retain(|item| item != &tool);
+        } else {
+            settings.tray_hidden_provider_tools.push(tool);
+        }
+        store::save_settings_to_sqlite_state(&sqlite_state, &settings)?;
+        if let Err(err) = tray::refresh_tray_menus(&app).await {
+            log::warn!("Failed to refresh tray after changing provider visibility: {err}");
+        }
+    }
+    Ok(enabled)
+}
+
+/// Get the MCP snapshot id restored on every startup.
+#[tauri::command]
+pub async fn get_boot_snapshot_id(
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+) -> Result<Option<String>, String> {
+    Ok(store::load_settings_from_sqlite_state(&sqlite_state)?.boot_snapshot_id)
+}
+
+/// Set the MCP snapshot restored on every startup; `None` disables the boot restore.
+/// The snapshot must exist when it is set.
+#[tauri::command]
+pub async fn set_boot_snapshot_id(
+    app: tauri::AppHandle,
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    id: Option<String>,
+) -> Result<Option<String>, String> {
+    let id = id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if let Some(id) = &id {
+        if !crate::coding::mcp::mcp_snapshot_exists(&app, id)? {
+            return Err(format!("MCP snapshot '{}' does not exist", id));
+        }
+    }
+    let mut settings = store::load_settings_from_sqlite_state(&sqlite_state)?;
+    if settings.boot_snapshot_id != id {
+        settings.boot_snapshot_id = id.clone();
+        store::save_settings_to_sqlite_state(&sqlite_state, &settings)?;
+    }
+    Ok(id)
+}
+
 /// Restart the application
 #[tauri::command]
 pub fn restart_app() -> Result<(), String> {
@@ -122,8 +311,86 @@ pub fn restart_app() -> Result<(), String> {
     std::process::exit(0);
 }
 
-/// Test proxy connection
+/// Test proxy connection; `echo_url` overrides the ip-echo endpoint used for the check
 #[tauri::command]
-pub async fn test_proxy_connection(proxy_url: String) -> Result<(), String> {
-    crate::http_client::test_proxy(&proxy_url).await
+pub async fn test_proxy_connection(
+    proxy_url: String,
+    echo_url: Option<String>,
+) -> Result<crate::http_client::ProxyTestResult, String> {
+    crate::http_client::test_proxy(&proxy_url, echo_url.as_deref()).await
+}
+
+/// Sortable tables by their `normalize_sort_indexes` name, with the order each list view uses.
+fn sortable_table(name: &str) -> Result<(DbTable, OrderSpec), String> {
+    use crate::coding::{claude_code, codex, gemini_cli, grok, mcp, skills};
+    match name {
+        "claude_provider" => Ok((
+            DbTable::ClaudeProvider,
+            claude_code::commands::claude_provider_order()?,
+        )),
+        "codex_provider" => Ok((
+            DbTable::CodexProvider,
+            codex::commands::codex_provider_order()?,
+        )),
+        "grok_provider" => Ok((DbTable::GrokProvider, grok::commands::provider_order()?)),
+        "gemini_cli_provider" => Ok((
+            DbTable::GeminiCliProvider,
+            gemini_cli::commands::gemini_provider_order()?,
+        )),
+        "mcp_server" => Ok((DbTable::McpServer, mcp::mcp_store::mcp_server_order()?)),
+        "skill" => Ok((DbTable::Skill, skills::skill_store::skill_order()?)),
+        other => Err(format!(
+            "Table does not support sort_index normalization: {}",
+            other
+        )),
+    }
+}
+
+const SORTABLE_TABLES: [&str; 6] = [
+    "claude_provider",
+    "codex_provider",
+    "grok_provider",
+    "gemini_cli_provider",
+    "mcp_server",
+    "skill",
+];
+
+fn normalize_table_sort_indexes(sqlite_state: &SqliteDbState, name: &str) -> Result<usize, String> {
+    let (table, order) = sortable_table(name)?;
+    sqlite_state
+        .with_conn_mut(|conn| db_transaction(conn, |tx| db_normalize_sort_index(tx, table, &order)))
+}
+
+/// Startup maintenance: normalize every sortable table. A failing table is logged and skipped.
+pub(crate) fn normalize_all_sort_indexes(sqlite_state: &SqliteDbState) -> usize {
+    SORTABLE_TABLES
+        .iter()
+        .map(
+            |name| match normalize_table_sort_indexes(sqlite_state, name) {
+                Ok(changed) => changed,
+                Err(error) => {
+                    log::warn!("Failed to normalize sort_index of {}: {}", name, error);
+                    0
+                }
+            },
+        )
+        .sum()
+}
+
+/// Renumber sort_index to 0..n for a sortable table (providers, MCP servers, skills), keeping
+/// the order its list view shows. Returns how many records were renumbered; running it again
+/// returns 0. Also runs for every table at startup.
+#[tauri::command]
+pub async fn normalize_sort_indexes(
+    app: tauri::AppHandle,
+    sqlite_state: tauri::State<'_, SqliteDbState>,
+    table: String,
+) -> Result<usize, String> {
+    let changed = normalize_table_sort_indexes(&sqlite_state, table.trim())?;
+
+    if changed > 0 {
+        let _ = app.emit("config-changed", "window");
+    }
+
+    Ok(changed)
 }
@@ -1,6 +1,9 @@
 use serde_json::Value;
 
-use super::{adapter, types::AppSettings};
+use super::{
+    adapter,
+    types::{AppSettings, ConfigSyncMode},
+};
 use crate::db::helpers::{db_get, db_patch_fields, db_put};
 use crate::db::schema::DbTable;
 use crate::db::SqliteDbState;
@@ -60,6 +63,27 @@ pub fn save_settings_to_sqlite_conn(
     db_put(conn, DbTable::Settings, SETTINGS_ID, &json)
 }
 
+/// Configured DB/file sync mode for `tool`; `None` means the default behavior.
+pub fn load_config_sync_mode(sqlite_state: &SqliteDbState, tool: &str) -> Option<ConfigSyncMode> {
+    match load_settings_from_sqlite_state(sqlite_state) {
+        Ok(settings) => settings.config_sync_modes.get(tool).copied(),
+        Err(error) => {
+            log::warn!("Failed to load config sync mode for {tool}: {error}");
+            None
+        }
+    }
+}
+
+/// Whether `list_*` / startup init may import a tool's config files into an empty DB.
+pub fn should_import_config_files(sqlite_state: &SqliteDbState, tool: &str) -> bool {
+    load_config_sync_mode(sqlite_state, tool) != Some(ConfigSyncMode::DbPrimary)
+}
+
+/// Whether saving the applied provider or common config rewrites the tool's config files.
+pub fn should_write_config_files_on_save(sqlite_state: &SqliteDbState, tool: &str) -> bool {
+    load_config_sync_mode(sqlite_state, tool) != Some(ConfigSyncMode::FilePrimary)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +131,26 @@ mod tests {
             Some("2026-05-20T00:00:00Z")
         );
     }
+
+    #[test]
+    fn config_sync_mode_defaults_to_import_and_write_through() {
+        let sqlite_state = SqliteDbState::in_memory_for_test().expect("sqlite");
+        assert_eq!(load_config_sync_mode(&sqlite_state, "codex"), None);
+        assert!(should_import_config_files(&sqlite_state, "codex"));
+        assert!(should_write_config_files_on_save(&sqlite_state, "codex"));
+
+        let mut settings = load_settings_from_sqlite_state(&sqlite_state).expect("load settings");
+        settings
+            .config_sync_modes
+            .insert("codex".to_string(), ConfigSyncMode::DbPrimary);
+        settings
+            .config_sync_modes
+            .insert("claude".to_string(), ConfigSyncMode::FilePrimary);
+        save_settings_to_sqlite_state(&sqlite_state, &settings).expect("save settings");
+
+        assert!(!should_import_config_files(&sqlite_state, "codex"));
+        assert!(should_write_config_files_on_save(&sqlite_state, "codex"));
+        assert!(should_import_config_files(&sqlite_state, "claude"));
+        assert!(!should_write_config_files_on_save(&sqlite_state, "claude"));
+    }
 }
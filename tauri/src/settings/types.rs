@@ -1,6 +1,44 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Log files kept by default (one file per day)
+pub const DEFAULT_LOG_RETENTION_DAYS: u32 = 7;
+/// Upper bound accepted for `log_retention_days`
+pub const MAX_LOG_RETENTION_DAYS: u32 = 365;
+
+/// Minutes between provider health checks by default
+pub const DEFAULT_PROVIDER_HEALTH_INTERVAL_MINUTES: u32 = 15;
+/// Bounds accepted for `provider_health_interval_minutes`
+pub const MIN_PROVIDER_HEALTH_INTERVAL_MINUTES: u32 = 1;
+pub const MAX_PROVIDER_HEALTH_INTERVAL_MINUTES: u32 = 24 * 60;
+
+/// Tools whose DB/config-file relationship can be set with `config_sync_modes`.
+/// Tool keys here and below are spelled as in `runtime_location::MODULE_KEYS`.
+pub const CONFIG_SYNC_MODE_TOOLS: &[&str] = &["claude", "codex", "geminicli"];
+
+/// Tools whose provider submenu can be hidden with `tray_hidden_provider_tools`
+pub const TRAY_PROVIDER_TOOLS: &[&str] = &["claude", "codex", "grok", "geminicli"];
+
+/// Which side wins between the database and a tool's runtime config files.
+/// A tool without an entry keeps the default: import files into an empty DB and
+/// rewrite files when the applied provider or common config is saved.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSyncMode {
+    /// The DB is authoritative: never import from files, rewrite files on every save
+    DbPrimary,
+    /// Files are authoritative: import when the DB is empty, only write files on explicit apply
+    FilePrimary,
+}
+
+/// Window background color chosen by the user; also tints the titlebar on macOS
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WindowBackgroundColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
 /// WebDAV configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WebDAVConfig {
@@ -120,6 +158,14 @@ pub struct AppSettings {
     pub auto_backup_interval_days: u32,
     /// Max number of auto backups to keep, 0 = unlimited (default: 10)
     pub auto_backup_max_keep: u32,
+    /// Local auto backup mode: "full" or "differential" (default: "full")
+    pub backup_mode: String,
+    /// Differential auto backups taken against one baseline before a new full baseline (default: 6)
+    pub backup_diffs_per_baseline: u32,
+    /// Also upload each local auto backup to the configured WebDAV server (default: false)
+    pub webdav_auto_backup_enabled: bool,
+    /// Max number of WebDAV backups to keep for those uploads, 0 = unlimited (default: 10)
+    pub webdav_backup_keep: u32,
     /// Last auto backup time in ISO 8601 format
     pub last_auto_backup_time: Option<String>,
     /// Auto check for updates on startup (default: true)
@@ -136,6 +182,30 @@ pub struct AppSettings {
     pub codex_unified_session_history_enabled: bool,
     /// Append --dangerously-skip-permissions when launching Claude provider CLI (default: false)
     pub claude_cli_launch_full_access: bool,
+    /// Encrypt Claude provider API keys at rest with an OS keyring key (default: false)
+    pub claude_encrypt_provider_secrets: bool,
+    /// Request WSL sync automatically after applying a tool config (default: true)
+    pub auto_sync_on_apply: bool,
+    /// Enable diagnostics-only tools such as raw database export/import (default: false)
+    pub diagnostics_enabled: bool,
+    /// Write per-file WSL/SSH sync operations to `sync-trace.log` (default: false)
+    pub sync_trace: bool,
+    /// Probe the applied Claude/Codex provider endpoints shortly after startup (default: false)
+    pub check_applied_providers_on_startup: bool,
+    /// Periodically probe every enabled Claude/Codex provider and keep a history (default: false)
+    pub provider_health_monitoring: bool,
+    /// Minutes between provider health checks (default: 15)
+    pub provider_health_interval_minutes: u32,
+    /// Days of log files to keep, one file per day (default: 7)
+    pub log_retention_days: u32,
+    /// Per-tool DB/file sync mode keyed by `CONFIG_SYNC_MODE_TOOLS`; missing = default behavior
+    pub config_sync_modes: HashMap<String, ConfigSyncMode>,
+    /// Tools from `TRAY_PROVIDER_TOOLS` whose provider submenu is hidden in the tray (default: none)
+    pub tray_hidden_provider_tools: Vec<String>,
+    /// MCP snapshot id restored during every startup; `None` keeps the current state
+    pub boot_snapshot_id: Option<String>,
+    /// Window background color re-applied on startup; `None` follows the theme
+    pub window_background_color: Option<WindowBackgroundColor>,
     /// File filter rules for backup/restore
     pub backup_file_filter_rules: Vec<BackupFileFilterRule>,
 }
@@ -163,6 +233,10 @@ impl Default for AppSettings {
             auto_backup_enabled: false,
             auto_backup_interval_days: 7,
             auto_backup_max_keep: 10,
+            backup_mode: "full".to_string(),
+            backup_diffs_per_baseline: 6,
+            webdav_auto_backup_enabled: false,
+            webdav_backup_keep: 10,
             last_auto_backup_time: None,
             auto_check_update: true,
             visible_tabs: vec![
@@ -183,6 +257,18 @@ impl Default for AppSettings {
             codex_preserve_official_auth_on_switch: false,
             codex_unified_session_history_enabled: false,
             claude_cli_launch_full_access: false,
+            claude_encrypt_provider_secrets: false,
+            auto_sync_on_apply: true,
+            diagnostics_enabled: false,
+            sync_trace: false,
+            check_applied_providers_on_startup: false,
+            provider_health_monitoring: false,
+            provider_health_interval_minutes: DEFAULT_PROVIDER_HEALTH_INTERVAL_MINUTES,
+            log_retention_days: DEFAULT_LOG_RETENTION_DAYS,
+            config_sync_modes: HashMap::new(),
+            tray_hidden_provider_tools: Vec::new(),
+            boot_snapshot_id: None,
+            window_background_color: None,
             backup_file_filter_rules: default_backup_file_filter_rules(),
         }
     }
@@ -478,26 +478,33 @@ pub async fn refresh_tray_menus<R: Runtime>(app: &AppHandle<R>) -> Result<(), St
 
 /// Refresh tray menus with flat structure
 async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
-    let (visible_tabs, texts) = match crate::settings::commands::get_settings(app.state()).await {
-        Ok(settings) => (settings.visible_tabs, tray_texts(&settings.language)),
-        Err(err) => {
-            log::warn!("Failed to read settings for tray visibility: {err}");
-            (
-                vec![
-                    "opencode".to_string(),
-                    "claudecode".to_string(),
-                    "codex".to_string(),
-                    "grok".to_string(),
-                    "geminicli".to_string(),
-                    "openclaw".to_string(),
-                    "pi".to_string(),
-                ],
-                tray_texts("zh-CN"),
-            )
-        }
-    };
+    let (visible_tabs, hidden_provider_tools, texts) =
+        match crate::settings::commands::get_settings(app.state()).await {
+            Ok(settings) => (
+                settings.visible_tabs,
+                settings.tray_hidden_provider_tools,
+                tray_texts(&settings.language),
+            ),
+            Err(err) => {
+                log::warn!("Failed to read settings for tray visibility: {err}");
+                (
+                    vec![
+                        "opencode".to_string(),
+                        "claudecode".to_string(),
+                        "codex".to_string(),
+                        "grok".to_string(),
+                        "geminicli".to_string(),
+                        "openclaw".to_string(),
+                        "pi".to_string(),
+                    ],
+                    vec![],
+                    tray_texts("zh-CN"),
+                )
+            }
+        };
 
     let is_tab_visible = |tab: &str| visible_tabs.iter().any(|item| item == tab);
+    let shows_providers = |tool: &str| !hidden_provider_tools.iter().any(|item| item == tool);
 
     // Check if modules are enabled
     let opencode_enabled =
@@ -579,7 +586,7 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     };
     omo_slim_data.title = texts.omo_slim_header.to_string();
 
-    let mut claude_data = if claude_enabled {
+    let mut claude_data = if claude_enabled && shows_providers("claude") {
         claude_tray::get_claude_code_tray_data(app).await?
     } else {
         claude_tray::TrayProviderData {
@@ -600,7 +607,7 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     };
     claude_prompt_data.title = texts.global_prompt.to_string();
 
-    let mut codex_data = if codex_enabled {
+    let mut codex_data = if codex_enabled && shows_providers("codex") {
         codex_tray::get_codex_tray_data(app).await?
     } else {
         codex_tray::TrayProviderData {
@@ -621,7 +628,7 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     };
     codex_prompt_data.title = texts.global_prompt.to_string();
 
-    let mut grok_data = if grok_enabled {
+    let mut grok_data = if grok_enabled && shows_providers("grok") {
         grok_tray::get_grok_tray_data(app).await?
     } else {
         grok_tray::TrayProviderData {
@@ -651,7 +658,7 @@ async fn refresh_tray_menus_inner<R: Runtime>(app: &AppHandle<R>) -> Result<(),
     };
     grok_prompt_data.title = texts.global_prompt.to_string();
 
-    let mut gemini_cli_data = if gemini_cli_enabled {
+    let mut gemini_cli_data = if gemini_cli_enabled && shows_providers("geminicli") {
         gemini_cli_tray::get_gemini_cli_tray_data(app).await?
     } else {
         gemini_cli_tray::TrayProviderData {
@@ -1,4 +1,6 @@
-use ai_toolbox_lib::coding::tools::{normalize_path, to_storage_path, NormalizedPath, PathType};
+use ai_toolbox_lib::coding::tools::{
+    normalize_path, path_compare_key, to_storage_path, NormalizedPath, PathType,
+};
 
 #[test]
 fn test_tilde_prefix() {
@@ -47,3 +49,23 @@ fn test_storage_path_empty_home() {
     };
     assert_eq!(to_storage_path(&normalized), "~");
 }
+
+#[test]
+fn test_path_compare_key_unifies_separators_and_trailing_slash() {
+    assert_eq!(
+        path_compare_key("C:\\Users\\me\\.cursor\\mcp.json", false),
+        path_compare_key("C:/Users/me/.cursor/mcp.json/", false)
+    );
+}
+
+#[test]
+fn test_path_compare_key_case_folding() {
+    assert_eq!(
+        path_compare_key("C:/Users/Me/MCP.json", true),
+        path_compare_key("c:/users/me/mcp.json", true)
+    );
+    assert_ne!(
+        path_compare_key("/home/Me/mcp.json", false),
+        path_compare_key("/home/me/mcp.json", false)
+    );
+}
@@ -10,6 +10,7 @@ import {
   checkForUpdates,
   openExternalUrl,
   setWindowBackgroundColor,
+  getWindowBackgroundColor,
   installUpdate,
   loadCachedPresetModels,
   fetchRemotePresetModels,
@@ -228,6 +229,32 @@ const AppInitializer: React.FC<{ children: React.ReactNode }> = ({ children }) =
     };
   }, []);
 
+  // Config files edited outside the app: let the open page resync from disk.
+  // No reload fallback here, since the edit did not change anything the app stores.
+  React.useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupListener = async () => {
+      try {
+        unlisten = await listen('config-file-changed', () => {
+          window.dispatchEvent(
+            new CustomEvent(TRAY_CONFIG_REFRESH_EVENT, { cancelable: true }),
+          );
+        });
+      } catch (error) {
+        console.error('Failed to setup config file change listener:', error);
+      }
+    };
+
+    setupListener();
+
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
   return (
     <>
       {children}
@@ -352,12 +379,18 @@ export const Providers: React.FC<ProvidersProps> = ({ children }) => {
     }
   }, [resolvedTheme, themeInitialized]);
 
-  // Set window background color for macOS titlebar
+  // Set window background color for macOS titlebar; a color saved by the user wins
   React.useEffect(() => {
     if (themeInitialized) {
       // Light theme: #ffffff, Dark theme: #1f1f1f
       const bgColor = resolvedTheme === 'dark' ? { r: 31, g: 31, b: 31 } : { r: 255, g: 255, b: 255 };
-      setWindowBackgroundColor(bgColor.r, bgColor.g, bgColor.b).catch(console.error);
+      getWindowBackgroundColor()
+        .then((savedColor) => {
+          if (!savedColor) {
+            return setWindowBackgroundColor(bgColor.r, bgColor.g, bgColor.b, false);
+          }
+        })
+        .catch(console.error);
     }
   }, [resolvedTheme, themeInitialized]);
 
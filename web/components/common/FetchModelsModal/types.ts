@@ -19,6 +19,14 @@ export interface FetchModelsResponse {
   total: number;
 }
 
+/** Response from the cached fetch models API (fetch_provider_models_cached) */
+export interface CachedFetchModelsResponse extends FetchModelsResponse {
+  /** Served from the local cache without contacting the provider */
+  fromCache: boolean;
+  /** When the models were fetched from the provider (epoch ms) */
+  fetchedAt: number;
+}
+
 /** Result returned when applying fetched models */
 export interface FetchModelsApplyResult {
   selectedModels: FetchedModel[];
@@ -36,6 +36,13 @@ export const ClaudeCodeSettingsModal: React.FC<ClaudeCodeSettingsModalProps> = (
   const [vscodeLoading, setVscodeLoading] = React.useState(false);
   const [onboardingLoading, setOnboardingLoading] = React.useState(false);
   const [cliLaunchFullAccessLoading, setCliLaunchFullAccessLoading] = React.useState(false);
+  const claudeEncryptProviderSecrets = useSettingsStore(
+    (state) => state.claudeEncryptProviderSecrets,
+  );
+  const setClaudeEncryptProviderSecrets = useSettingsStore(
+    (state) => state.setClaudeEncryptProviderSecrets,
+  );
+  const [encryptSecretsLoading, setEncryptSecretsLoading] = React.useState(false);
 
   React.useEffect(() => {
     if (open) {
@@ -103,6 +110,19 @@ export const ClaudeCodeSettingsModal: React.FC<ClaudeCodeSettingsModalProps> = (
     }
   };
 
+  const handleEncryptSecretsToggle = async (checked: boolean) => {
+    setEncryptSecretsLoading(true);
+    try {
+      await setClaudeEncryptProviderSecrets(checked);
+      message.success(t('common.success'));
+    } catch (error) {
+      console.error('Failed to toggle Claude provider secret encryption:', error);
+      message.error(String(error));
+    } finally {
+      setEncryptSecretsLoading(false);
+    }
+  };
+
   return (
     <SidebarSettingsModal
       open={open}
@@ -131,6 +151,13 @@ export const ClaudeCodeSettingsModal: React.FC<ClaudeCodeSettingsModalProps> = (
         loading={cliLaunchFullAccessLoading}
         onChange={handleCliLaunchFullAccessToggle}
       />
+      <SettingsToggleRow
+        title={t('claudecode.settings.encryptSecrets')}
+        hint={t('claudecode.settings.encryptSecretsHint')}
+        checked={claudeEncryptProviderSecrets}
+        loading={encryptSecretsLoading}
+        onChange={handleEncryptSecretsToggle}
+      />
     </SidebarSettingsModal>
   );
 };
@@ -419,6 +419,11 @@ const ClaudeProviderCard: React.FC<ClaudeProviderCardProps> = ({
                   <Tag color="gold">{t('gateway.takeover.officialBypassedTag')}</Tag>
                 </Tooltip>
               )}
+              {provider.meta?.allowInsecureTls && (
+                <Tooltip title={t('common.insecureTlsTooltip')}>
+                  <Tag color="red">{t('common.insecureTlsTag')}</Tag>
+                </Tooltip>
+              )}
               {showRuntimeApplied && (
                 <AppliedTag>
                   {t('claudecode.provider.applied')}
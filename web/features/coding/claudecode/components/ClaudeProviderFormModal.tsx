@@ -510,6 +510,7 @@ const ClaudeProviderFormModal: React.FC<ClaudeProviderFormModalProps> = ({
           apiType: fetchApiType,
           sdkType: '@ai-sdk/anthropic',
           customUrl,
+          providerId: provider?.id, // TLS opt-in is read from the stored provider
         },
       });
 
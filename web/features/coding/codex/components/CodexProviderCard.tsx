@@ -648,6 +648,11 @@ const CodexProviderCard: React.FC<CodexProviderCardProps> = ({
                     <Tag color="gold">{t('gateway.takeover.officialBypassedTag')}</Tag>
                   </Tooltip>
                 )}
+                {provider.meta?.allowInsecureTls && (
+                  <Tooltip title={t('common.insecureTlsTooltip')}>
+                    <Tag color="red">{t('common.insecureTlsTag')}</Tag>
+                  </Tooltip>
+                )}
                 {showRuntimeApplied && (
                   <AppliedTag>
                     {t('codex.provider.applied')}
@@ -888,6 +888,7 @@ const CodexProviderFormModal: React.FC<CodexProviderFormModalProps> = ({
           apiKey: apiKey || undefined,
           apiType: 'openai_compat',
           sdkType: '@ai-sdk/openai',
+          providerId: provider?.id, // TLS opt-in is read from the stored provider
         },
       });
 
@@ -591,6 +591,11 @@ const GeminiCliProviderCard: React.FC<GeminiCliProviderCardProps> = ({
                     <Tag color="gold">{t('gateway.takeover.officialBypassedTag')}</Tag>
                   </Tooltip>
                 )}
+                {provider.meta?.allowInsecureTls && (
+                  <Tooltip title={t('common.insecureTlsTooltip')}>
+                    <Tag color="red">{t('common.insecureTlsTag')}</Tag>
+                  </Tooltip>
+                )}
                 {showRuntimeApplied && (
                   <AppliedTag>
                     {t('geminicli.provider.applied')}
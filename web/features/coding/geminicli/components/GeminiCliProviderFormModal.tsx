@@ -708,7 +708,10 @@ const GeminiCliProviderFormModal: React.FC<GeminiCliProviderFormModalProps> = ({
               sdkType: '@ai-sdk/openai',
             };
       const response = await invoke<FetchModelsResponse>('fetch_provider_models', {
-        request: modelFetchRequest,
+        request: {
+          ...modelFetchRequest,
+          providerId: provider?.id, // TLS opt-in is read from the stored provider
+        },
       });
 
       setFetchedModels(response.models);
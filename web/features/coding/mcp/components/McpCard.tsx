@@ -235,7 +235,7 @@ const McpCardContent = React.memo(function McpCardContent({
             return (
               <button
                 key={`${server.id}-${tool.key}`}
-                title={`${tool.display_name} - ${status}`}
+                title={`${tool.display_name} - ${status}${syncDetail?.error_message ? `: ${syncDetail.error_message}` : ''}`}
                 type="button"
                 className={`${styles.toolPill} ${styles.active} ${status === 'error' ? styles.error : ''}${toolsReadOnly ? ` ${styles.readOnlyTool}` : ''}`}
                 onClick={toolsReadOnly ? handleReadOnlyToolClick : () => onToggleTool(server.id, tool.key)}
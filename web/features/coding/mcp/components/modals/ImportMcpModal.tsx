@@ -1,5 +1,5 @@
 import React, { useMemo } from 'react';
-import { Modal, Checkbox, Button, Empty, message, Spin, Tag, Dropdown } from 'antd';
+import { Modal, Checkbox, Button, Empty, message, Spin, Tag, Dropdown, Alert } from 'antd';
 import { PlusOutlined, WarningOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import { useMcpStore } from '../../stores/mcpStore';
@@ -352,6 +352,7 @@ export const ImportMcpModal: React.FC<ImportMcpModalProps> = ({
   };
 
   const totalServersFound = scanResult?.total_servers_found || 0;
+  const skippedSources = scanResult?.skipped ?? [];
 
   return (
     <Modal
@@ -374,6 +375,20 @@ export const ImportMcpModal: React.FC<ImportMcpModalProps> = ({
               <span>{t('mcp.serversFound', { count: totalServersFound })}</span>
             </div>
 
+            {skippedSources.length > 0 && (
+              <Alert
+                type="warning"
+                showIcon
+                className={styles.skippedAlert}
+                message={t('mcp.scanSkipped', { count: skippedSources.length })}
+                description={skippedSources.map((source) => (
+                  <div key={source.tool_key}>
+                    {tools.find((tool) => tool.key === source.tool_key)?.display_name ?? source.tool_key}: {source.reason}
+                  </div>
+                ))}
+              />
+            )}
+
             {allSelectableKeys.length === 0 ? (
               <Empty description={t('mcp.noToolsToImport')} />
             ) : (
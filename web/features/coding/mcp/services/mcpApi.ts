@@ -7,6 +7,17 @@ import type {
   McpImportResult,
   McpTool,
   McpScanResult,
+  McpOrphanScan,
+  McpOrphanSelection,
+  McpOrphanRemoval,
+  McpTagCount,
+  McpDiscoveredServer,
+  McpCommandCheck,
+  McpServerTest,
+  McpSyncDrift,
+  McpReconcileReport,
+  McpServerToolResult,
+  SharedPathGroup,
   McpPackageVersionResolveRequest,
   McpPackageVersionResolveResult,
 } from '../types';
@@ -16,6 +27,14 @@ export const listMcpServers = async (): Promise<McpServer[]> => {
   return invoke<McpServer[]>('mcp_list_servers');
 };
 
+export const listMcpServersByTag = async (tag: string): Promise<McpServer[]> => {
+  return invoke<McpServer[]>('mcp_list_servers_by_tag', { tag });
+};
+
+export const listMcpTags = async (): Promise<McpTagCount[]> => {
+  return invoke<McpTagCount[]>('mcp_list_all_tags');
+};
+
 export const resolveMcpPackageVersions = async (
   requests: McpPackageVersionResolveRequest[],
 ): Promise<McpPackageVersionResolveResult[]> => {
@@ -38,6 +57,17 @@ export const toggleMcpTool = async (serverId: string, toolKey: string): Promise<
   return invoke<boolean>('mcp_toggle_tool', { serverId, toolKey });
 };
 
+/**
+ * Enable or disable a tool for every server; only servers whose state changed are returned.
+ * All or nothing: if one server fails, the servers changed so far are rolled back and the call rejects.
+ */
+export const setMcpToolForAll = async (
+  toolKey: string,
+  enabled: boolean
+): Promise<McpServerToolResult[]> => {
+  return invoke<McpServerToolResult[]>('mcp_set_tool_for_all', { toolKey, enabled });
+};
+
 export const reorderMcpServers = async (ids: string[]): Promise<void> => {
   return invoke('mcp_reorder_servers', { ids });
 };
@@ -59,20 +89,85 @@ export const syncMcpAll = async (): Promise<McpSyncResult[]> => {
   return invoke<McpSyncResult[]>('mcp_sync_all');
 };
 
+// MCP-only snapshots (servers, favorites, preferences)
+export const createMcpSnapshot = async (): Promise<string> => {
+  return invoke<string>('mcp_snapshot');
+};
+
+export const restoreMcpSnapshot = async (id: string): Promise<McpSyncResult[]> => {
+  return invoke<McpSyncResult[]>('mcp_restore_snapshot', { id });
+};
+
 export const importMcpFromTool = async (toolKey: string, enabledTools?: string[]): Promise<McpImportResult> => {
   return invoke<McpImportResult>('mcp_import_from_tool', { toolKey, enabledTools });
 };
 
+/**
+ * Parse pasted MCP config (Claude/VSCode-style JSON or Codex TOML) into servers to confirm.
+ * Servers identical to an existing one are omitted; name clashes come back suffixed.
+ */
+export const importMcpFromText = async (
+  text: string,
+  format: 'json' | 'toml'
+): Promise<McpDiscoveredServer[]> => {
+  return invoke<McpDiscoveredServer[]>('mcp_import_from_text', { text, format });
+};
+
+/**
+ * Compare Codex config.toml mcp_servers with managed servers; pass adopt to take unmanaged
+ * ones into management without rewriting the TOML entries.
+ */
+export const reconcileCodexMcp = async (adopt = false): Promise<McpReconcileReport> => {
+  return invoke<McpReconcileReport>('mcp_reconcile_codex', { adopt });
+};
+
+/**
+ * Compare a server with its entry in each enabled tool's config file on disk.
+ */
+export const getMcpSyncDrift = async (serverId: string): Promise<McpSyncDrift[]> => {
+  return invoke<McpSyncDrift[]>('mcp_get_sync_drift', { serverId });
+};
+
+/**
+ * Check whether a stdio server's command resolves on this machine; remote servers report available.
+ */
+export const checkMcpServerCommand = async (serverId: string): Promise<McpCommandCheck> => {
+  return invoke<McpCommandCheck>('mcp_check_server_command', { serverId });
+};
+
+/**
+ * Launch a server (stdio) or connect to it (http/sse) and run the MCP initialize handshake.
+ */
+export const testMcpServer = async (serverId: string): Promise<McpServerTest> => {
+  return invoke<McpServerTest>('mcp_test_server', { serverId });
+};
+
 // Tools API
 export const getMcpTools = async (): Promise<McpTool[]> => {
   return invoke<McpTool[]>('mcp_get_tools');
 };
 
+// Detect tools whose MCP config resolves to the same file
+export const detectSharedConfigPaths = async (): Promise<SharedPathGroup[]> => {
+  return invoke<SharedPathGroup[]>('detect_shared_config_paths');
+};
+
 // Scan for existing MCP servers in tool configs
 export const scanMcpServers = async (): Promise<McpScanResult> => {
   return invoke<McpScanResult>('mcp_scan_servers');
 };
 
+// Find entries in tool configs that match no managed server
+export const findMcpOrphans = async (): Promise<McpOrphanScan> => {
+  return invoke<McpOrphanScan>('mcp_find_orphans');
+};
+
+export const removeMcpOrphans = async (
+  selections: McpOrphanSelection[],
+): Promise<McpOrphanRemoval[]> => {
+  return invoke<McpOrphanRemoval[]>('mcp_remove_orphans', { selections });
+};
+
 // Preferences
 export const getMcpShowInTray = async (): Promise<boolean> => {
   return invoke<boolean>('mcp_get_show_in_tray');
@@ -98,6 +193,14 @@ export const setMcpLimitAddMoreToPreferredTools = async (enabled: boolean): Prom
   return invoke('mcp_set_limit_add_more_to_preferred_tools', { enabled });
 };
 
+export const getMcpEnvVars = async (): Promise<Record<string, string>> => {
+  return invoke<Record<string, string>>('mcp_get_env_vars');
+};
+
+export const setMcpEnvVars = async (vars: Record<string, string>): Promise<void> => {
+  return invoke('mcp_set_env_vars', { vars });
+};
+
 export const getMcpSyncDisabledToOpencode = async (): Promise<boolean> => {
   return invoke<boolean>('mcp_get_sync_disabled_to_opencode');
 };
@@ -124,6 +227,37 @@ export const removeMcpCustomTool = async (key: string): Promise<void> => {
   return invoke('mcp_remove_custom_tool', { key });
 };
 
+/**
+ * Check that a custom tool's MCP config file parses in its declared format.
+ * Rejects with a list of parse errors (with line numbers); a missing file is valid.
+ */
+export const validateCustomToolConfigFile = async (key: string): Promise<void> => {
+  return invoke('validate_custom_tool_config_file', { key });
+};
+
+/** Custom tool as stored in the database (Skills and MCP fields together) */
+export interface RegisteredCustomTool {
+  key: string;
+  display_name: string;
+  relative_skills_dir: string | null;
+  relative_detect_dir: string | null;
+  force_copy: boolean;
+  mcp_config_path: string | null;
+  mcp_config_format: string | null;
+  mcp_field: string | null;
+  created_at: number;
+}
+
+/**
+ * Register or update custom tools from a JSON manifest (`{ "tools": [...] }`).
+ * Nothing is saved if any entry is invalid; the error lists each failing tool.
+ */
+export const registerToolsFromManifest = async (
+  path: string
+): Promise<RegisteredCustomTool[]> => {
+  return invoke<RegisteredCustomTool[]>('register_tools_from_manifest', { path });
+};
+
 // Favorite MCP API
 export interface FavoriteMcp {
   id: string;
@@ -38,7 +38,7 @@ export interface HttpConfig {
 
 export interface McpSyncDetail {
   tool: string;
-  status: 'ok' | 'error' | 'pending';
+  status: 'ok' | 'warning' | 'error' | 'pending';
   synced_at: number | null;
   error_message: string | null;
 }
@@ -84,10 +84,104 @@ export interface McpDiscoveredServer {
   server_config: StdioConfig | HttpConfig;
 }
 
+export interface McpServerToolResult {
+  server_id: string;
+  server_name: string;
+  success: boolean;
+  error_message: string | null;
+}
+
+export interface McpReconcileReport {
+  /** Unmanaged Codex servers adopted (or that would be, when `adopted` is false) */
+  added: string[];
+  matched: string[];
+  /** Codex entries that differ from the managed server of the same name */
+  conflicting: string[];
+  adopted: boolean;
+}
+
+export interface McpSyncDrift {
+  tool: string;
+  status: 'in-sync' | 'missing' | 'drifted' | 'error';
+  /** Dotted paths of the entry fields that differ from what AI Toolbox would write */
+  differing_keys: string[];
+  error_message: string | null;
+}
+
+export interface McpCommandCheck {
+  server_id: string;
+  /** null for http/sse servers */
+  command: string | null;
+  available: boolean;
+  resolved_path: string | null;
+  message: string | null;
+}
+
+export interface McpServerTest {
+  server_id: string;
+  /** A valid initialize result arrived within the timeout */
+  success: boolean;
+  /** serverInfo from the handshake */
+  server_name: string | null;
+  server_version: string | null;
+  protocol_version: string | null;
+  elapsed_ms: number;
+  /** Failure reason, with the tail of stderr for stdio servers */
+  message: string | null;
+}
+
+export interface McpScanSkippedSource {
+  tool_key: string;
+  reason: string;
+}
+
 export interface McpScanResult {
   total_tools_scanned: number;
   total_servers_found: number;
   servers: McpDiscoveredServer[];
+  /** Sources that failed or timed out during the scan */
+  skipped: McpScanSkippedSource[];
+}
+
+export interface McpOrphan {
+  tool_key: string;
+  tool_name: string;
+  server_name: string;
+  config_path: string;
+}
+
+export interface McpOrphanScan {
+  orphans: McpOrphan[];
+  /** Tool configs that could not be read */
+  skipped: McpScanSkippedSource[];
+}
+
+export interface McpOrphanSelection {
+  tool_key: string;
+  server_name: string;
+}
+
+export interface McpOrphanRemoval {
+  tool_key: string;
+  server_name: string;
+  success: boolean;
+  error_message: string | null;
+}
+
+export interface McpTagCount {
+  tag: string;
+  count: number;
+}
+
+export interface SharedPathTool {
+  key: string;
+  display_name: string;
+  is_custom: boolean;
+}
+
+export interface SharedPathGroup {
+  config_path: string;
+  tools: SharedPathTool[];
 }
 
 export interface McpTool {
@@ -739,6 +739,9 @@ const ConnectivityTestModal: React.FC<ConnectivityTestModalProps> = ({
                 },
                 response: {
                   headers: selectedResult.responseHeaders,
+                  ...(selectedResult.responseText !== undefined && {
+                    text: selectedResult.responseText,
+                  }),
                   body: selectedResult.responseBody,
                 }
               }}
@@ -19,6 +19,8 @@ import type {
   CentralRepoScan,
   AdoptCentralSkillsResult,
   DeleteManagedSkillOptions,
+  SkillDuplicateGroup,
+  SkillMergeResult,
 } from '../types';
 
 // Tool Status
@@ -26,6 +28,10 @@ export const getToolStatus = async (): Promise<ToolStatus> => {
   return invoke<ToolStatus>('skills_get_tool_status');
 };
 
+export const refreshToolDetection = async (): Promise<ToolStatus> => {
+  return invoke<ToolStatus>('refresh_tool_detection');
+};
+
 // Central Repo Path
 export const getCentralRepoPath = async (): Promise<string> => {
   return invoke<string>('skills_get_central_repo_path');
@@ -159,6 +165,17 @@ export const deleteManagedSkill = async (
   return invoke('skills_delete_managed', { skillId, options });
 };
 
+export const findDuplicateSkills = async (): Promise<SkillDuplicateGroup[]> => {
+  return invoke<SkillDuplicateGroup[]>('skills_find_duplicates');
+};
+
+export const mergeDuplicateSkills = async (
+  keepId: string,
+  removeIds: string[],
+): Promise<SkillMergeResult> => {
+  return invoke<SkillMergeResult>('skills_merge_duplicates', { keepId, removeIds });
+};
+
 export const updateSkillMetadata = async (
   skillId: string,
   groupId: string | null,
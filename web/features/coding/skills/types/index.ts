@@ -90,6 +90,17 @@ export interface UpdateResult {
   updated_targets: string[];
 }
 
+export interface SkillDuplicateGroup {
+  content_hash: string;
+  skills: Array<{ id: string; name: string }>;
+}
+
+export interface SkillMergeResult {
+  kept_skill_id: string;
+  removed_skill_ids: string[];
+  synced_tools: string[];
+}
+
 export interface GitSkillCandidate {
   name: string;
   description: string | null;
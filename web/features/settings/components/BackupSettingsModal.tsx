@@ -31,6 +31,7 @@ import {
   normalizeBackupCustomEntryPath,
   testWebDAVConnection,
   type BackupCustomEntry,
+  type BackupMode,
   type BackupCustomEntryType,
   type BackupFileFilterPathOption,
   type BackupFileFilterRule,
@@ -76,6 +77,10 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     autoBackupEnabled,
     autoBackupIntervalDays,
     autoBackupMaxKeep,
+    backupMode,
+    backupDiffsPerBaseline,
+    webdavAutoBackupEnabled,
+    webdavBackupKeep,
     setAutoBackupSettings,
   } = useSettingsStore();
 
@@ -89,6 +94,12 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
   const [currentAutoBackupEnabled, setCurrentAutoBackupEnabled] = React.useState(autoBackupEnabled);
   const [currentIntervalDays, setCurrentIntervalDays] = React.useState(autoBackupIntervalDays);
   const [currentMaxKeep, setCurrentMaxKeep] = React.useState(autoBackupMaxKeep);
+  const [currentBackupMode, setCurrentBackupMode] = React.useState<BackupMode>(backupMode);
+  const [currentDiffsPerBaseline, setCurrentDiffsPerBaseline] =
+    React.useState(backupDiffsPerBaseline);
+  const [currentWebdavAutoBackupEnabled, setCurrentWebdavAutoBackupEnabled] =
+    React.useState(webdavAutoBackupEnabled);
+  const [currentWebdavBackupKeep, setCurrentWebdavBackupKeep] = React.useState(webdavBackupKeep);
   const [currentBackupCustomEntries, setCurrentBackupCustomEntries] =
     React.useState<BackupCustomEntry[]>(backupCustomEntries);
   const [customEntryModalOpen, setCustomEntryModalOpen] = React.useState(false);
@@ -159,6 +170,10 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
       setCurrentAutoBackupEnabled(autoBackupEnabled);
       setCurrentIntervalDays(autoBackupIntervalDays);
       setCurrentMaxKeep(autoBackupMaxKeep);
+      setCurrentBackupMode(backupMode);
+      setCurrentDiffsPerBaseline(backupDiffsPerBaseline);
+      setCurrentWebdavAutoBackupEnabled(webdavAutoBackupEnabled);
+      setCurrentWebdavBackupKeep(webdavBackupKeep);
       setCurrentBackupCustomEntries(backupCustomEntries);
       setCurrentFileFilterRules(backupFileFilterRules);
       form.setFieldsValue({
@@ -179,6 +194,10 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     autoBackupEnabled,
     autoBackupIntervalDays,
     autoBackupMaxKeep,
+    backupMode,
+    backupDiffsPerBaseline,
+    webdavAutoBackupEnabled,
+    webdavBackupKeep,
     form,
     loadFilterPathOptions,
   ]);
@@ -214,6 +233,10 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
         enabled: currentAutoBackupEnabled,
         intervalDays: currentIntervalDays,
         maxKeep: currentMaxKeep,
+        mode: currentBackupMode,
+        diffsPerBaseline: currentDiffsPerBaseline,
+        webdavEnabled: currentWebdavAutoBackupEnabled,
+        webdavKeep: currentWebdavBackupKeep,
       });
       onClose();
     } catch {
@@ -221,6 +244,11 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
     }
   };
 
+  // Local auto backups can also be copied to WebDAV, which needs the server settings.
+  const showWebDAVConfig =
+    currentBackupType === 'webdav' ||
+    (currentAutoBackupEnabled && currentWebdavAutoBackupEnabled);
+
   const handleBackupTypeChange = (value: 'local' | 'webdav') => {
     setCurrentBackupType(value);
   };
@@ -432,7 +460,7 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
               </Form.Item>
             )}
 
-            {currentBackupType === 'webdav' && (
+            {showWebDAVConfig && (
               <div style={{ marginTop: 10 }}>
                 <Form.Item label={t('settings.webdav.url')} name={['webdav', 'url']}>
                   <Input placeholder="https://dav.example.com" />
@@ -707,6 +735,59 @@ const BackupSettingsModal: React.FC<BackupSettingsModalProps> = ({
                     </Typography.Text>
                   )}
                 </Form.Item>
+                {currentBackupType === 'local' && (
+                  <Form.Item label={t('settings.autoBackup.mode')} style={{ marginBottom: 0 }}>
+                    <Select<BackupMode>
+                      value={currentBackupMode}
+                      onChange={setCurrentBackupMode}
+                      style={{ width: 180 }}
+                      options={[
+                        { value: 'full', label: t('settings.autoBackup.modeFull') },
+                        { value: 'differential', label: t('settings.autoBackup.modeDifferential') },
+                      ]}
+                    />
+                  </Form.Item>
+                )}
+                {currentBackupType === 'local' && currentBackupMode === 'differential' && (
+                  <Form.Item label={t('settings.autoBackup.diffsPerBaseline')} style={{ marginBottom: 0 }}>
+                    <InputNumber
+                      value={currentDiffsPerBaseline}
+                      onChange={(v) => setCurrentDiffsPerBaseline(v && v >= 1 ? Math.floor(v) : 1)}
+                      min={1}
+                      precision={0}
+                      style={{ width: 120 }}
+                      addonAfter={t('settings.autoBackup.count')}
+                    />
+                    <Typography.Text type="secondary" style={{ display: 'block' }}>
+                      {t('settings.autoBackup.differentialHint')}
+                    </Typography.Text>
+                  </Form.Item>
+                )}
+                {currentBackupType === 'local' && (
+                  <div className={styles.switchRow}>
+                    <span className={styles.switchLabel}>{t('settings.autoBackup.webdavCopy')}</span>
+                    <Switch
+                      checked={currentWebdavAutoBackupEnabled}
+                      onChange={setCurrentWebdavAutoBackupEnabled}
+                      aria-label={t('settings.autoBackup.webdavCopy')}
+                    />
+                  </div>
+                )}
+                {currentBackupType === 'local' && currentWebdavAutoBackupEnabled && (
+                  <Form.Item label={t('settings.autoBackup.webdavKeep')} style={{ marginBottom: 0 }}>
+                    <InputNumber
+                      value={currentWebdavBackupKeep}
+                      onChange={(v) => setCurrentWebdavBackupKeep(v != null && v >= 0 ? Math.floor(v) : 0)}
+                      min={0}
+                      precision={0}
+                      style={{ width: 120 }}
+                      addonAfter={t('settings.autoBackup.count')}
+                    />
+                    <Typography.Text type="secondary" style={{ display: 'block' }}>
+                      {t('settings.autoBackup.webdavCopyHint')}
+                    </Typography.Text>
+                  </Form.Item>
+                )}
               </div>
             )}
           </section>
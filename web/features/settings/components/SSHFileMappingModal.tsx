@@ -68,6 +68,7 @@ export const SSHFileMappingModal: React.FC<SSHFileMappingModalProps> = ({ open,
           ...mapping,
           directoryExcludes: mapping.directoryExcludes ?? [...DEFAULT_SSH_DIRECTORY_EXCLUDES],
           cleanupPaths: mapping.cleanupPaths ?? [],
+          syncDirection: mapping.syncDirection ?? 'push',
         });
       } else {
         form.resetFields();
@@ -76,6 +77,7 @@ export const SSHFileMappingModal: React.FC<SSHFileMappingModalProps> = ({ open,
           enabled: true,
           isPattern: false,
           isDirectory: false,
+          syncDirection: 'push',
           directoryExcludes: mapping?.directoryExcludes ?? [...DEFAULT_SSH_DIRECTORY_EXCLUDES],
           cleanupPaths: mapping?.cleanupPaths ?? [],
         });
@@ -202,6 +204,17 @@ export const SSHFileMappingModal: React.FC<SSHFileMappingModalProps> = ({ open,
           <Switch onChange={handleDirectoryModeChange} />
         </Form.Item>
 
+        <Form.Item
+          name="syncDirection"
+          label={t('settings.ssh.syncDirection')}
+          extra={t('settings.ssh.syncDirectionHint')}
+        >
+          <Select>
+            <Select.Option value="push">{t('settings.ssh.syncDirectionPush')}</Select.Option>
+            <Select.Option value="bidirectional">{t('settings.ssh.syncDirectionBidirectional')}</Select.Option>
+          </Select>
+        </Form.Item>
+
         <Form.Item
           noStyle
           shouldUpdate={(previousValues, currentValues) => previousValues.isDirectory !== currentValues.isDirectory}
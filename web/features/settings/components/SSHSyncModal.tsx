@@ -25,7 +25,7 @@ import {
 } from '@/services/sshSyncApi';
 import { useSettingsStore } from '@/stores';
 import { DEFAULT_SSH_DIRECTORY_EXCLUDES } from '@/types/sshsync';
-import type { SSHConnection, SSHFileMapping, SSHConnectionResult } from '@/types/sshsync';
+import type { SSHConnection, SSHFileMapping, SSHConnectionResult, SyncConflict, SyncConflictChoice } from '@/types/sshsync';
 import type { WslDirectModuleStatus } from '@/types/wslsync';
 import { SSHConnectionModal } from './SSHConnectionModal';
 import { SSHFileMappingModal } from './SSHFileMappingModal';
@@ -92,7 +92,7 @@ interface SSHSyncModalProps {
 export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) => {
   const { t } = useTranslation();
   const { token } = theme.useToken();
-  const { config, status, loading, syncing, syncWarning, syncProgress, saveConfig, sync, dismissSyncWarning } = useSSHSync();
+  const { config, status, loading, syncing, syncWarning, syncProgress, conflicts, saveConfig, sync, dismissSyncWarning, resolveConflict } = useSSHSync();
   const { visibleTabs } = useSettingsStore();
 
   // Filter module keys by visibleTabs
@@ -107,6 +107,7 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
   const [activeModuleTab, setActiveModuleTab] = useState<string>(visibleModuleKeys[0] || 'all');
   const [testResult, setTestResult] = useState<SSHConnectionResult | null>(null);
   const [testing, setTesting] = useState(false);
+  const [resolvingConflictId, setResolvingConflictId] = useState<string | null>(null);
 
   const moduleStatusMap = React.useMemo(() => {
     return new Map((config?.moduleStatuses || []).map((item) => [item.module, item] as const));
@@ -124,6 +125,23 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
     return mapping ? getMappingDisplayName(mapping) : currentItem;
   };
 
+  const formatConflictTime = (seconds?: number) =>
+    seconds ? new Date(seconds * 1000).toLocaleString() : '-';
+
+  const handleResolveConflict = async (conflict: SyncConflict, choice: SyncConflictChoice) => {
+    setResolvingConflictId(conflict.mappingId);
+    try {
+      await resolveConflict(conflict.mappingId, choice);
+    } catch (error) {
+      AntdModal.error({
+        title: t('settings.ssh.conflicts.resolveFailed'),
+        content: String(error),
+      });
+    } finally {
+      setResolvingConflictId(null);
+    }
+  };
+
   const getAuthMethodLabel = (authMethod: SSHConnection['authMethod']) => {
     if (authMethod === 'key') {
       return t('settings.ssh.authKey');
@@ -800,6 +818,60 @@ export const SSHSyncModal: React.FC<SSHSyncModalProps> = ({ open, onClose }) =>
                 style={{ marginTop: 12 }}
               />
             )}
+            {conflicts.length > 0 && (
+              <Alert
+                type="warning"
+                showIcon
+                style={{ marginTop: 12 }}
+                title={t('settings.ssh.conflicts.title', { count: conflicts.length })}
+                description={
+                  <List
+                    size="small"
+                    dataSource={conflicts}
+                    renderItem={(conflict) => (
+                      <List.Item
+                        actions={[
+                          <Button
+                            key="local"
+                            size="small"
+                            loading={resolvingConflictId === conflict.mappingId}
+                            onClick={() => handleResolveConflict(conflict, 'local')}
+                          >
+                            {t('settings.ssh.conflicts.keepLocal')}
+                          </Button>,
+                          <Button
+                            key="remote"
+                            size="small"
+                            disabled={resolvingConflictId === conflict.mappingId}
+                            onClick={() => handleResolveConflict(conflict, 'remote')}
+                          >
+                            {t('settings.ssh.conflicts.keepRemote')}
+                          </Button>,
+                          <Button
+                            key="both"
+                            size="small"
+                            disabled={resolvingConflictId === conflict.mappingId}
+                            onClick={() => handleResolveConflict(conflict, 'both')}
+                          >
+                            {t('settings.ssh.conflicts.keepBoth')}
+                          </Button>,
+                        ]}
+                      >
+                        <List.Item.Meta
+                          title={conflict.mappingName}
+                          description={t('settings.ssh.conflicts.detail', {
+                            local: conflict.localPath,
+                            localTime: formatConflictTime(conflict.localModified),
+                            remote: conflict.remotePath,
+                            remoteTime: formatConflictTime(conflict.remoteModified),
+                          })}
+                        />
+                      </List.Item>
+                    )}
+                  />
+                }
+              />
+            )}
           </div>
         </Spin>
       </Modal>
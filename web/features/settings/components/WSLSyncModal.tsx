@@ -243,6 +243,28 @@ export const WSLSyncModal: React.FC<WSLSyncModalProps> = ({ open, onClose }) =>
     }
   };
 
+  // Sync targets: every enabled target distro, or the primary distro when none are configured
+  const targetDistros = config?.distros?.length
+    ? config.distros.filter((target) => target.enabled).map((target) => target.name)
+    : [distro];
+
+  const handleTargetDistrosChange = async (values: string[]) => {
+    if (!config) return;
+    // Keep deselected targets as disabled entries so their enable flag round-trips
+    const known = (config.distros ?? []).map((target) => target.name);
+    const names = [...known, ...values.filter((name) => !known.includes(name))];
+    try {
+      await saveConfig({
+        ...config,
+        enabled,
+        distro,
+        distros: names.map((name) => ({ name, enabled: values.includes(name) })),
+      });
+    } catch (error) {
+      console.error('Failed to save sync targets:', error);
+    }
+  };
+
   // Handle distro change - save immediately
   const handleDistroChange = async (value: string) => {
     if (!config) return;
@@ -514,6 +536,26 @@ export const WSLSyncModal: React.FC<WSLSyncModalProps> = ({ open, onClose }) =>
               </Select>
             </div>
 
+            {/* Sync targets - left-right layout */}
+            <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', marginBottom: 16 }}>
+              <Tooltip title={t('settings.wsl.syncTargetsTooltip')}>
+                <Text>{t('settings.wsl.syncTargets')}</Text>
+              </Tooltip>
+              <Select
+                mode="multiple"
+                value={targetDistros}
+                onChange={handleTargetDistrosChange}
+                disabled={!enabled || distros.length === 0}
+                style={{ width: 320 }}
+              >
+                {distros.map((d) => (
+                  <Select.Option key={d} value={d}>
+                    {d}
+                  </Select.Option>
+                ))}
+              </Select>
+            </div>
+
             {/* Connection Status - left-right layout */}
             <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', marginBottom: 16 }}>
               <Space>
@@ -6,13 +6,23 @@
 
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { listen } from '@tauri-apps/api/event';
-import type { SSHSyncConfig, SSHStatusResult, SyncResult, SSHFileMapping, SyncProgress } from '@/types/sshsync';
+import type {
+  SSHSyncConfig,
+  SSHStatusResult,
+  SyncResult,
+  SSHFileMapping,
+  SyncProgress,
+  SyncConflict,
+  SyncConflictChoice,
+} from '@/types/sshsync';
 import {
   sshGetConfig,
   sshSaveConfig,
   sshSync,
   sshGetStatus,
   sshGetDefaultMappings,
+  sshListSyncConflicts,
+  sshResolveConflict,
 } from '@/services/sshSyncApi';
 import { useSettingsStore } from '@/stores';
 
@@ -35,6 +45,7 @@ export function useSSHSync() {
   const [syncing, setSyncing] = useState(false);
   const [syncWarning, setSyncWarning] = useState<string | null>(null);
   const [syncProgress, setSyncProgress] = useState<SyncProgress | null>(null);
+  const [conflicts, setConflicts] = useState<SyncConflict[]>([]);
 
   const skipNextReload = useRef(false);
 
@@ -137,10 +148,27 @@ export function useSSHSync() {
     }
   }, []);
 
+  /**
+   * Keep one side of a conflicting bidirectional mapping (or both)
+   */
+  const resolveConflict = useCallback(async (mappingId: string, choice: SyncConflictChoice) => {
+    try {
+      await sshResolveConflict(mappingId, choice);
+      setConflicts(await sshListSyncConflicts());
+      await loadStatus();
+    } catch (error) {
+      console.error('Failed to resolve SSH sync conflict:', error);
+      throw error;
+    }
+  }, [loadStatus]);
+
   // Load config and status on mount
   useEffect(() => {
     loadConfig();
     loadStatus();
+    sshListSyncConflicts()
+      .then(setConflicts)
+      .catch((error) => console.error('Failed to load SSH sync conflicts:', error));
   }, [loadConfig, loadStatus]);
 
   // Listen to SSH events
@@ -163,11 +191,18 @@ export function useSSHSync() {
       setSyncProgress(event.payload);
     });
 
+    const unlistenConflicts = listen<SyncConflict[]>('ssh-sync-conflicts', () => {
+      sshListSyncConflicts()
+        .then(setConflicts)
+        .catch((error) => console.error('Failed to load SSH sync conflicts:', error));
+    });
+
     return () => {
       unlistenConfig.then(fn => fn());
       unlistenSync.then(fn => fn());
       unlistenWarning.then(fn => fn());
       unlistenProgress.then(fn => fn());
+      unlistenConflicts.then(fn => fn());
     };
   }, [loadConfig, loadStatus]);
 
@@ -185,11 +220,13 @@ export function useSSHSync() {
     syncing,
     syncWarning,
     syncProgress,
+    conflicts,
     loadConfig,
     loadStatus,
     saveConfig,
     sync,
     getDefaultMappings,
     dismissSyncWarning,
+    resolveConflict,
   };
 }
@@ -50,6 +50,7 @@ import {
   type ProxyMode,
   type RestoreResult,
   openAppDataDir,
+  compactDatabase,
   getAppVersion,
   checkForUpdates,
   openGitHubPage,
@@ -194,6 +195,7 @@ const GeneralSettingsPage: React.FC = () => {
   const [webdavRestoreModalOpen, setWebdavRestoreModalOpen] = React.useState(false);
   const [backupLoading, setBackupLoading] = React.useState(false);
   const [restoreLoading, setRestoreLoading] = React.useState(false);
+  const [compactLoading, setCompactLoading] = React.useState(false);
 
   // Proxy settings states
   const [proxyInput, setProxyInput] = React.useState(proxyUrl);
@@ -600,6 +602,25 @@ const GeneralSettingsPage: React.FC = () => {
     }
   };
 
+  const handleCompactDatabase = async () => {
+    const formatSize = (bytes: number) => `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+    setCompactLoading(true);
+    try {
+      const result = await compactDatabase();
+      message.success(
+        t('settings.backupSettings.compactDatabaseSuccess', {
+          before: formatSize(result.sizeBefore),
+          after: formatSize(result.sizeAfter),
+        }),
+      );
+    } catch (error) {
+      console.error('Failed to compact database:', error);
+      message.error(t('settings.backupSettings.compactDatabaseFailed', { error: String(error) }));
+    } finally {
+      setCompactLoading(false);
+    }
+  };
+
   // Save proxy URL when input loses focus
   const handleProxySave = async () => {
     if (proxyInput !== proxyUrl) {
@@ -631,8 +652,18 @@ const GeneralSettingsPage: React.FC = () => {
 
     setProxyTesting(true);
     try {
-      await testProxyConnection(proxyInput);
-      message.success(t('settings.proxy.testSuccess'));
+      const result = await testProxyConnection(proxyInput);
+      if (result.httpsOk) {
+        message.success(t('settings.proxy.testSuccessDetail', {
+          latency: result.latencyMs ?? '-',
+          ip: result.exitIp ?? '-',
+        }));
+        if (!result.httpOk) {
+          message.warning(t('settings.proxy.httpForwardFailed', { error: result.httpError ?? '' }));
+        }
+      } else {
+        message.warning(t('settings.proxy.httpsTunnelFailed', { error: result.httpsError ?? '' }));
+      }
     } catch (error) {
       console.error('Proxy test failed:', error);
       message.error(t('settings.proxy.testFailed') + ': ' + String(error));
@@ -1000,6 +1031,13 @@ const GeneralSettingsPage: React.FC = () => {
               <Typography.Link onClick={handleOpenDataDir} style={{ fontSize: 14 }}>
                 {t('settings.backupSettings.openDataDir')}
               </Typography.Link>
+              <Typography.Link
+                onClick={handleCompactDatabase}
+                disabled={compactLoading}
+                style={{ fontSize: 14 }}
+              >
+                {t('settings.backupSettings.compactDatabase')}
+              </Typography.Link>
             </Space>
             {autoBackupEnabled && (
               <div style={{ marginTop: 12 }}>
@@ -96,11 +96,141 @@ export const hasAllApiHubExtension = async (): Promise<boolean> => {
   return await invoke<boolean>('has_all_api_hub_extension');
 };
 
+export interface WindowBackgroundColor {
+  r: number;
+  g: number;
+  b: number;
+}
+
 /**
  * Set window background color (affects macOS titlebar color)
+ * @param persist Keep the color and re-apply it on the next start; pass false for theme defaults
+ */
+export const setWindowBackgroundColor = async (
+  r: number,
+  g: number,
+  b: number,
+  persist = true
+): Promise<void> => {
+  await invoke('set_window_background_color', { r, g, b, persist });
+};
+
+/**
+ * Window background color saved by the user, or null when it follows the theme
+ */
+export const getWindowBackgroundColor = async (): Promise<WindowBackgroundColor | null> => {
+  return await invoke<WindowBackgroundColor | null>('get_window_background_color');
+};
+
+/**
+ * Save a WebKitGTK white-screen workaround level (0-4) and restart the app with it (Linux only).
+ * Resolves only if the restart failed to start.
+ */
+export const setWaylandWorkaroundLevel = async (level: number): Promise<void> => {
+  await invoke('set_wayland_webview_workaround_level', { level });
+};
+
+/**
+ * WebKitGTK workaround level (0-4) this instance runs with (Linux only; rejects elsewhere)
+ */
+export const getWaylandWorkaroundLevel = async (): Promise<number> => {
+  return await invoke<number>('get_wayland_webview_workaround_level');
+};
+
+/**
+ * Explain which factors chose the current WebKitGTK workaround level (Linux only;
+ * other platforms get a "not applicable" message).
+ */
+export const explainWaylandWorkaround = async (): Promise<string> => {
+  return await invoke<string>('explain_wayland_workaround');
+};
+
+/**
+ * A log record kept in the in-memory recent-log buffer
+ */
+export interface LogEntry {
+  timestamp: string;
+  level: 'ERROR' | 'WARN' | 'INFO' | 'DEBUG' | 'TRACE';
+  target: string;
+  message: string;
+}
+
+/**
+ * Get recent log records, oldest first. `levelFilter` ("error" … "trace") keeps that
+ * level and more severe ones; `maxLines` defaults to 200.
+ */
+export const getRecentLogs = async (
+  levelFilter?: string,
+  maxLines?: number
+): Promise<LogEntry[]> => {
+  return await invoke<LogEntry[]>('get_recent_logs', { levelFilter, maxLines });
+};
+
+/**
+ * Export a diagnostics zip (app/OS info, CLI versions, config counts and redacted logs)
+ * for attaching to bug reports. Returns the zip path.
+ */
+export const exportDiagnostics = async (): Promise<string> => {
+  return await invoke<string>('export_diagnostics');
+};
+
+export interface HealthCheck {
+  /** e.g. "database", "config_dir:claude", "tool:codex", "webdav", "ssh" */
+  id: string;
+  status: 'pass' | 'warn' | 'fail';
+  detail: string;
+  hint?: string;
+}
+
+/**
+ * Check the database, app data dir, tool config dirs, installed CLIs and, when configured,
+ * WebDAV and SSH connectivity
+ */
+export const runHealthCheck = async (): Promise<HealthCheck[]> => {
+  return await invoke<HealthCheck[]>('run_health_check');
+};
+
+/**
+ * Linux webview rendering environment, for attaching to white-screen bug reports
+ */
+export interface LinuxRenderReport {
+  appVersion: string;
+  kernelRelease?: string;
+  sessionType: 'wayland' | 'x11';
+  xdgSessionType?: string;
+  currentDesktop?: string;
+  isAppimage: boolean;
+  isFlatpak: boolean;
+  appimageWaylandPreloadApplied: boolean;
+  workaroundLevel?: number;
+  workaroundDisabled: boolean;
+  savedWorkaroundLevel: number;
+  eglFailureMonitored: boolean;
+  eglFailureDetected: boolean;
+  webkitEnv: Record<string, string>;
+  gpuEnv: Record<string, string>;
+}
+
+/**
+ * Collect Linux rendering diagnostics (rejects on other platforms)
+ */
+export const linuxRenderDiagnostics = async (): Promise<LinuxRenderReport> => {
+  return await invoke<LinuxRenderReport>('linux_render_diagnostics');
+};
+
+/**
+ * Saved frontend-ready timeout (seconds) for the WebKitGTK watchdog, or null when it uses
+ * AI_TOOLBOX_FRONTEND_READY_TIMEOUT_SECS / the 20s default (Linux only; rejects elsewhere)
+ */
+export const getFrontendReadyTimeoutSecs = async (): Promise<number | null> => {
+  return await invoke<number | null>('get_frontend_ready_timeout_secs');
+};
+
+/**
+ * Save the frontend-ready timeout (5-300 seconds), or clear it with null. Applies on next start.
  */
-export const setWindowBackgroundColor = async (r: number, g: number, b: number): Promise<void> => {
-  await invoke('set_window_background_color', { r, g, b });
+export const setFrontendReadyTimeoutSecs = async (secs: number | null): Promise<void> => {
+  await invoke('set_frontend_ready_timeout_secs', { secs });
 };
 
 /**
@@ -0,0 +1,20 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/** Tools whose provider applies can be undone */
+export type ApplyHistoryTool = 'claude' | 'codex' | 'opencode';
+
+export interface ApplyUndoResult {
+  tool: ApplyHistoryTool;
+  /** Provider marked as applied again; undefined when nothing was applied before */
+  restoredProviderId?: string;
+  /** Applies left to undo */
+  remaining: number;
+}
+
+/**
+ * Undo the last apply of a tool: restores the config files it overwrote
+ * and the previously applied provider.
+ */
+export const undoLastApply = async (tool: ApplyHistoryTool): Promise<ApplyUndoResult> => {
+  return await invoke<ApplyUndoResult>('undo_last_apply', { tool });
+};
@@ -28,6 +28,34 @@ export interface RestoreResult {
 
 export interface RestoreOptions {
   skipCliCustomRoots?: boolean;
+  /** Restore even if the backup fails its checksum verification */
+  force?: boolean;
+}
+
+/**
+ * Check result for one file listed in a backup's checksum manifest
+ */
+export interface BackupFileCheck {
+  path: string;
+  status: 'ok' | 'mismatch' | 'missing' | 'unreadable';
+  expectedSha256: string;
+  actualSha256?: string;
+  expectedSize: number;
+  actualSize?: number;
+  error?: string;
+}
+
+/**
+ * Per-file verification report of a backup archive.
+ * `hasManifest` is false for backups made before manifests were written.
+ */
+export interface BackupVerifyReport {
+  hasManifest: boolean;
+  passed: boolean;
+  kind?: 'full' | 'differential';
+  expectedTotalSize: number;
+  actualTotalSize: number;
+  files: BackupFileCheck[];
 }
 
 /**
@@ -55,12 +83,122 @@ export const restoreDatabase = async (
   return await invoke<RestoreResult>('restore_database', {
     zipFilePath,
     skipCliCustomRoots: options?.skipCliCustomRoots ?? false,
+    force: options?.force ?? false,
   });
 };
 
 /**
- * Get the database directory path
+ * Verify a local backup zip against its checksum manifest
+ * @param path - The path to the backup zip file
  */
+export const verifyBackup = async (path: string): Promise<BackupVerifyReport> => {
+  return await invoke<BackupVerifyReport>('verify_backup', { path });
+};
+
+/**
+ * Export the raw SQLite database (diagnostics only). Returns the export file path.
+ * The file is version-sensitive and contains stored secrets.
+ */
+export const dbExportAll = async (): Promise<string> => {
+  return await invoke<string>('db_export_all');
+};
+
+/**
+ * Replace the live database with a raw export from the same app version (diagnostics only)
+ */
+export const dbImportAll = async (path: string): Promise<void> => {
+  await invoke('db_import_all', { path });
+};
+
+export type ConfigBundleMergeStrategy = 'replace' | 'merge-keep-existing' | 'merge-overwrite';
+
+export interface ConfigBundleExport {
+  bundle: string;
+  recordCount: number;
+  containsCredentials: boolean;
+  warnings: string[];
+}
+
+export interface ConfigBundleImportResult {
+  bundleVersion: number;
+  imported: number;
+  skipped: number;
+  removed: number;
+  warnings: string[];
+}
+
+/**
+ * Export providers, MCP servers, favorites, skill repos and settings as one JSON bundle.
+ * The bundle includes API keys; with encryptSecrets, Claude provider keys stay encrypted
+ * with the OS keyring key and only open for the same OS account.
+ */
+export const exportConfigBundle = async (encryptSecrets = false): Promise<ConfigBundleExport> => {
+  return await invoke<ConfigBundleExport>('export_config_bundle', { encryptSecrets });
+};
+
+/**
+ * Import a config bundle produced by exportConfigBundle
+ */
+export const importConfigBundle = async (
+  json: string,
+  mergeStrategy: ConfigBundleMergeStrategy,
+): Promise<ConfigBundleImportResult> => {
+  return await invoke<ConfigBundleImportResult>('import_config_bundle', { json, mergeStrategy });
+};
+
+export type ImportKind = 'config_bundle' | 'sync_mappings';
+
+export interface ImportPlanItem {
+  scope: string;
+  key: string;
+  label: string;
+}
+
+export interface ImportPlan {
+  planId?: string; // Missing when the plan has errors
+  kind: ImportKind;
+  mode: string;
+  create: ImportPlanItem[];
+  update: ImportPlanItem[];
+  skip: ImportPlanItem[];
+  remove: ImportPlanItem[];
+  errors: string[];
+  warnings: string[];
+}
+
+/**
+ * Report what an import would create, update, skip and remove without writing anything.
+ * mode is a ConfigBundleMergeStrategy for config bundles, or 'merge' | 'replace' for sync mappings.
+ */
+export const validateImport = async (
+  bundle: string,
+  kind: ImportKind,
+  mode?: string,
+): Promise<ImportPlan> => {
+  return await invoke<ImportPlan>('validate_import', { bundle, kind, mode });
+};
+
+/**
+ * Apply a plan returned by validateImport; fails if it expired or the data changed since
+ */
+export const commitImport = async (planId: string): Promise<ImportPlan> => {
+  return await invoke<ImportPlan>('commit_import', { planId });
+};
+
+export interface CompactDatabaseResult {
+  sizeBefore: number;
+  sizeAfter: number;
+}
+
+/**
+ * VACUUM the database; rejects while a backup, restore or WSL/SSH sync is running
+ */
+export const compactDatabase = async (): Promise<CompactDatabaseResult> => {
+  return await invoke<CompactDatabaseResult>('compact_database');
+};
+
+/**
+ * Get the database directory path
 export const getDatabasePath = async (): Promise<string> => {
   const result = await invoke<string>('get_database_path');
   return result;
@@ -93,7 +231,8 @@ export const backupToWebDAV = async (
   username: string,
   password: string,
   remotePath: string,
-  hostLabel: string
+  hostLabel: string,
+  requestId?: string
 ): Promise<string> => {
   const result = await invoke<string>('backup_to_webdav', {
     url,
@@ -101,10 +240,28 @@ export const backupToWebDAV = async (
     password,
     remotePath,
     hostLabel,
+    requestId,
   });
   return result;
 };
 
+/**
+ * Payload of the `webdav-backup-progress` event emitted while backupToWebDAV uploads
+ */
+export interface WebDAVBackupProgress {
+  requestId?: string | null;
+  stage: 'start' | 'progress' | 'finish' | 'cancelled' | 'failed';
+  bytesSent: number;
+  totalBytes: number;
+}
+
+/**
+ * Cancel a WebDAV backup started with the given request id
+ */
+export const cancelWebDAVBackup = async (requestId: string): Promise<void> => {
+  await invoke('cancel_webdav_backup', { requestId });
+};
+
 /**
  * List backup files from WebDAV server
  */
@@ -141,6 +298,26 @@ export const restoreFromWebDAV = async (
     remotePath,
     filename,
     skipCliCustomRoots: options?.skipCliCustomRoots ?? false,
+    force: options?.force ?? false,
+  });
+};
+
+/**
+ * Download a WebDAV backup and verify it against its checksum manifest
+ */
+export const verifyWebDAVBackup = async (
+  url: string,
+  username: string,
+  password: string,
+  remotePath: string,
+  filename: string
+): Promise<BackupVerifyReport> => {
+  return await invoke<BackupVerifyReport>('verify_webdav_backup', {
+    url,
+    username,
+    password,
+    remotePath,
+    filename,
   });
 };
 
@@ -179,3 +356,13 @@ export const deleteWebDAVBackup = async (
     filename,
   });
 };
+
+/**
+ * Generate a shell script that reproduces the current tool setup.
+ * Secrets are replaced by environment variable placeholders listed in the script header.
+ */
+export const exportSetupScript = async (
+  shell: 'sh' | 'powershell' = 'sh'
+): Promise<string> => {
+  return await invoke<string>('export_setup_script', { shell });
+};
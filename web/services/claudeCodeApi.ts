@@ -9,10 +9,14 @@ import type {
   ClaudeCodeProvider,
   ClaudeCommonConfig,
   ClaudeCommonConfigInput,
+  ClaudeConfigPreview,
+  ClaudeSelfTest,
+  ClaudeConfigIssue,
   ConfigPathInfo,
   ClaudeLocalConfigInput,
   ClaudeSettings,
   ClaudePluginStatus,
+  ClaudeFullPreview,
   ClaudeInstalledPlugin,
   ClaudeKnownMarketplace,
   ClaudeMarketplaceAddInput,
@@ -25,6 +29,7 @@ import type {
   ClaudePluginBulkActionResult,
   ClaudePluginRuntimeStatus,
   ClaudeProviderInput,
+  ClaudeProviderMergeResult,
 } from '@/types/claudecode';
 import type { OpenCodeAllApiHubProvider, OpenCodeAllApiHubProvidersResult } from '@/services/opencodeApi';
 
@@ -62,6 +67,31 @@ export const createClaudeProvider = async (
   return await invoke<ClaudeCodeProvider>('create_claude_provider', { provider });
 };
 
+/**
+ * Create a provider from a base provider with another provider's settings layered on top
+ */
+export const mergeClaudeProviders = async (
+  baseId: string,
+  overlayId: string,
+  newName: string
+): Promise<ClaudeProviderMergeResult> => {
+  return await invoke<ClaudeProviderMergeResult>('merge_claude_providers', {
+    baseId,
+    overlayId,
+    newName,
+  });
+};
+
+/**
+ * Copy a provider under a new name; the copy is not applied and goes last
+ */
+export const duplicateClaudeProvider = async (
+  providerId: string,
+  newName: string
+): Promise<ClaudeCodeProvider> => {
+  return await invoke<ClaudeCodeProvider>('duplicate_claude_provider', { providerId, newName });
+};
+
 /**
  * Update an existing Claude Code provider
  */
@@ -115,6 +145,14 @@ export const launchClaudeProviderCli = async (
   });
 };
 
+/**
+ * Encrypt the API keys of every stored Claude provider with the OS keyring key.
+ * Returns the number of providers that were rewritten.
+ */
+export const migrateSecretsToEncrypted = async (): Promise<number> => {
+  return await invoke<number>('migrate_secrets_to_encrypted');
+};
+
 /**
  * Read Claude Code settings.json
  */
@@ -254,6 +292,40 @@ export async function toggleClaudeCodeProviderDisabled(
   });
 }
 
+/**
+ * Pin or unpin a provider for quick apply (emits config-changed)
+ */
+export async function setClaudeProviderPinned(
+  providerId: string,
+  pinned: boolean
+): Promise<void> {
+  return invoke('set_claude_provider_pinned', { providerId, pinned });
+}
+
+export interface ClaudeProviderModels {
+  model?: string | null;
+  haiku?: string | null;
+  sonnet?: string | null;
+  opus?: string | null;
+}
+
+/**
+ * Set only the model slots of a provider; empty values clear a slot.
+ * An applied provider is re-applied.
+ */
+export const setClaudeProviderModels = async (
+  providerId: string,
+  models: ClaudeProviderModels
+): Promise<void> => {
+  await invoke('set_claude_provider_models', {
+    providerId,
+    model: models.model ?? null,
+    haiku: models.haiku ?? null,
+    sonnet: models.sonnet ?? null,
+    opus: models.opus ?? null,
+  });
+};
+
 /**
  * Get Claude onboarding status
  * @returns true if hasCompletedOnboarding is set
@@ -270,6 +342,55 @@ export const applyClaudeOnboardingSkip = async (): Promise<boolean> => {
   return await invoke<boolean>('apply_claude_onboarding_skip');
 };
 
+/**
+ * Preview merged settings.json (secrets masked), plugin config and onboarding state
+ * @param providerId Provider to merge for; omit to preview the current settings.json
+ */
+export const claudeFullPreview = async (providerId?: string): Promise<ClaudeFullPreview> => {
+  return await invoke<ClaudeFullPreview>('claude_full_preview', { providerId: providerId ?? null });
+};
+
+/**
+ * Dry run of apply: current settings.json text and the text applying the provider would write.
+ * Nothing is written and the applied provider does not change.
+ */
+export const previewClaudeConfig = async (providerId: string): Promise<ClaudeConfigPreview> => {
+  return await invoke<ClaudeConfigPreview>('preview_claude_config', { providerId });
+};
+
+/**
+ * Check a provider settings_config before saving (e.g. on blur).
+ * Errors would be rejected by create/update; warnings still save.
+ */
+export const validateClaudeProviderConfig = async (
+  settingsConfig: string
+): Promise<ClaudeConfigIssue[]> => {
+  return await invoke<ClaudeConfigIssue[]>('validate_claude_provider_config', { settingsConfig });
+};
+
+/**
+ * Diagnose the Claude setup in one call: applied provider and its endpoint, plugin,
+ * onboarding, settings.json validity and file permissions, each with a remediation hint
+ */
+export const claudeSetupSelftest = async (): Promise<ClaudeSelfTest> => {
+  return await invoke<ClaudeSelfTest>('claude_setup_selftest');
+};
+
+/**
+ * Roll settings.json back to a backup taken before an earlier apply
+ * @param index 1 = newest, up to 3 (settings.json.bak.1 … .bak.3)
+ */
+export const restoreClaudeSettingsBackup = async (index: number): Promise<void> => {
+  await invoke('restore_claude_settings_backup', { index });
+};
+
+/**
+ * Replace settings.json with `{}` (backed up first) and clear the applied provider
+ */
+export const resetClaudeSettings = async (): Promise<void> => {
+  await invoke('reset_claude_settings');
+};
+
 /**
  * Restore Claude Code initial setup confirmation
  * Removes hasCompletedOnboarding field from ~/.claude.json
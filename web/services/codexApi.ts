@@ -278,6 +278,27 @@ export const createCodexProvider = async (
   return await invoke<CodexProvider>('create_codex_provider', { provider });
 };
 
+/**
+ * Copy a provider under a new name; the copy is not applied and goes last
+ */
+export const duplicateCodexProvider = async (
+  providerId: string,
+  newName: string
+): Promise<CodexProvider> => {
+  return await invoke<CodexProvider>('duplicate_codex_provider', { providerId, newName });
+};
+
+/**
+ * Create an unapplied provider from a config.toml copied from another machine.
+ * Missing absolute paths and skipped mcp_servers are listed in the provider notes.
+ */
+export const importCodexConfigToml = async (
+  content: string,
+  name: string
+): Promise<CodexProvider> => {
+  return await invoke<CodexProvider>('import_codex_config_toml', { content, name });
+};
+
 /**
  * Update an existing Codex provider
  */
@@ -308,6 +329,16 @@ export async function toggleCodexProviderDisabled(
   await invoke('toggle_codex_provider_disabled', { providerId, isDisabled });
 }
 
+/**
+ * Pin or unpin a provider for quick apply (emits config-changed)
+ */
+export async function setCodexProviderPinned(
+  providerId: string,
+  pinned: boolean
+): Promise<void> {
+  await invoke('set_codex_provider_pinned', { providerId, pinned });
+}
+
 /**
  * Read Codex settings from files
  */
@@ -315,6 +346,25 @@ export const readCodexSettings = async (): Promise<CodexSettings> => {
   return await invoke<CodexSettings>('read_codex_settings');
 };
 
+/**
+ * Roll config.toml or auth.json back to a backup taken before an earlier apply
+ * @param index 1 = newest, up to 3; each file keeps its own backups
+ */
+export const restoreCodexConfigBackup = async (
+  file: 'config.toml' | 'auth.json',
+  index: number
+): Promise<void> => {
+  await invoke('restore_codex_config_backup', { file, index });
+};
+
+/**
+ * Reset config.toml to just the schema header and clear the applied provider
+ * @param keepAuth Keep auth.json; otherwise it is emptied too. Both files are backed up first
+ */
+export const resetCodexConfig = async (keepAuth: boolean): Promise<void> => {
+  await invoke('reset_codex_config', { keepAuth });
+};
+
 /**
  * Get common configuration
  */
@@ -0,0 +1,25 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type EmittedEventCategory =
+  | 'config'
+  | 'sync'
+  | 'update'
+  | 'gateway'
+  | 'backup'
+  | 'auth'
+  | 'image';
+
+export interface EventInfo {
+  name: string;
+  category: EmittedEventCategory;
+  /** Payload shape as seen by `listen()` callbacks */
+  payload: string;
+  firesWhen: string;
+}
+
+/**
+ * List every event the backend emits, grouped by category, for integrating listeners.
+ */
+export const listEmittedEvents = async (): Promise<EventInfo[]> => {
+  return await invoke<EventInfo[]>('list_emitted_events');
+};
@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type SearchResultKind =
+  | 'claude_provider'
+  | 'codex_provider'
+  | 'opencode_provider'
+  | 'mcp_server'
+  | 'skill';
+
+export interface SearchResult {
+  kind: SearchResultKind;
+  id: string; // Config key for OpenCode providers
+  name: string;
+  matchedField: 'name' | 'id' | 'notes' | 'baseUrl' | 'tag' | 'command';
+  matchedText?: string; // Matched value, for fields other than the name
+}
+
+/**
+ * Case-insensitive search over providers, MCP servers and skills (at most 20 results per kind)
+ */
+export const searchAll = async (query: string): Promise<SearchResult[]> => {
+  return await invoke<SearchResult[]>('search_all', { query });
+};
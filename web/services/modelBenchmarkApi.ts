@@ -0,0 +1,27 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type ModelBenchmarkTool = 'opencode' | 'claude' | 'codex';
+
+export interface ProviderModelBenchmark {
+  providerId: string;
+  modelId: string; // Model id sent to the provider
+  status: 'success' | 'error' | 'timeout';
+  firstByteMs?: number;
+  totalMs?: number;
+  tokensPerSecond?: number;
+  errorMessage?: string;
+}
+
+/**
+ * Run one connectivity test per provider offering the model, fastest first.
+ * Providers without the model are skipped; failed ones are listed after successful ones.
+ */
+export const benchmarkModelAcrossProviders = async (
+  tool: ModelBenchmarkTool,
+  modelId: string
+): Promise<ProviderModelBenchmark[]> => {
+  return await invoke<ProviderModelBenchmark[]>('benchmark_model_across_providers', {
+    tool,
+    modelId,
+  });
+};
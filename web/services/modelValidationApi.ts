@@ -0,0 +1,43 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type ModelValidationTool = 'claude' | 'codex' | 'geminicli' | 'grok';
+
+export interface ModelIssue {
+  field: string; // Provider field referencing the model, e.g. "sonnetModel"
+  model: string;
+  status: 'missing' | 'unverified';
+  message: string;
+}
+
+/**
+ * Flag models referenced by a provider that its endpoint doesn't list.
+ * Endpoints whose model list can't be fetched report each model as "unverified".
+ */
+export const validateProviderModels = async (
+  tool: ModelValidationTool,
+  providerId: string
+): Promise<ModelIssue[]> => {
+  return await invoke<ModelIssue[]>('validate_provider_models', { tool, providerId });
+};
+
+/**
+ * An applied provider whose endpoint did not answer.
+ * Also the payload (as an array) of the `applied-provider-unreachable` startup event.
+ */
+export interface UnreachableProvider {
+  tool: ModelValidationTool;
+  providerId: string;
+  providerName: string;
+  baseUrl: string;
+  error: string;
+}
+
+export const APPLIED_PROVIDER_UNREACHABLE_EVENT = 'applied-provider-unreachable';
+
+/**
+ * Probe the applied Claude Code, Codex, Gemini CLI and Grok providers, bypassing the cached
+ * model list; official endpoints are skipped.
+ */
+export const checkAppliedProvidersReachable = async (): Promise<UnreachableProvider[]> => {
+  return await invoke<UnreachableProvider[]>('check_applied_providers_reachable');
+};
@@ -5,6 +5,7 @@ import type {
 	OhMyOpenAgentGlobalConfig,
 	OhMyOpenAgentLegacyUpgradeResult,
 	OhMyOpenAgentLegacyUpgradeStatus,
+	OhMyOpenAgentMergePreview,
 } from "@/types/ohMyOpenAgent";
 import {
 	OH_MY_OPENAGENT_AGENTS,
@@ -81,6 +82,18 @@ export const applyOhMyOpenAgentConfig = async (
 	await invoke("apply_oh_my_openagent_config", { configId });
 };
 
+/**
+ * Preview the merged config file for a configuration and which layer set each key
+ */
+export const previewOhMyOpenAgentMerge = async (
+	configId: string,
+): Promise<OhMyOpenAgentMergePreview> => {
+	return await invoke<OhMyOpenAgentMergePreview>(
+		"preview_oh_my_opencode_merge",
+		{ configId },
+	);
+};
+
 /**
  * Reorder configurations
  */
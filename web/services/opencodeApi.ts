@@ -6,6 +6,7 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import type { OpenCodeAgentConfig, OpenCodeConfig, OpenCodeProvider } from '@/types/opencode';
+import type { GlobalHeadersTool } from './settingsApi';
 
 /**
  * Configuration path information
@@ -108,6 +109,43 @@ export const saveOpenCodeConfig = async (config: OpenCodeConfig): Promise<void>
   await invoke('save_opencode_config', { config });
 };
 
+/**
+ * Provider added to the config by duplicateOpenCodeProvider
+ */
+export interface OpenCodeDuplicatedProvider {
+  /** Config key of the copy, derived from the new name */
+  providerId: string;
+  providerConfig: OpenCodeProvider;
+}
+
+/**
+ * Copy a provider of the OpenCode config under a new name, appended after the last provider
+ */
+export const duplicateOpenCodeProvider = async (
+  providerId: string,
+  newName: string
+): Promise<OpenCodeDuplicatedProvider> => {
+  return await invoke<OpenCodeDuplicatedProvider>('duplicate_opencode_provider', {
+    providerId,
+    newName,
+  });
+};
+
+/**
+ * Roll the OpenCode config file back to a backup taken before an earlier save
+ * @param index 1 = newest, up to 3
+ */
+export const restoreOpenCodeConfigBackup = async (index: number): Promise<void> => {
+  await invoke('restore_opencode_config_backup', { index });
+};
+
+/**
+ * Replace the OpenCode config with one holding only `$schema` (backed up first)
+ */
+export const resetOpenCodeConfig = async (): Promise<void> => {
+  await invoke('reset_opencode_config');
+};
+
 export const listOpenCodeMarkdownAgents = async (): Promise<OpenCodeMarkdownAgent[]> => {
   return await invoke<OpenCodeMarkdownAgent[]>('list_opencode_markdown_agents');
 };
@@ -196,14 +234,54 @@ export interface UnifiedModelOption {
   isFree: boolean;      // Whether this is a free model
   baseModelId?: string;
   experimentalMode?: string;
+  providers?: string[];  // Every provider offering this model id (deduplicated list only)
 }
 
 /**
  * Get unified model list combining custom providers and official providers from auth.json
- * Returns all available models sorted by display name
+ * Returns models in the saved order (default: sorted by display name), without hidden ones
+ * @param dedupe Collapse models sharing a model id into one entry (default: raw list)
+ */
+export const getOpenCodeUnifiedModels = async (dedupe: boolean = false): Promise<UnifiedModelOption[]> => {
+  return await invoke<UnifiedModelOption[]>('get_opencode_unified_models', { dedupe });
+};
+
+/**
+ * Saved ordering and hidden models of the unified model list (ids are "provider_id/model_id")
  */
-export const getOpenCodeUnifiedModels = async (): Promise<UnifiedModelOption[]> => {
-  return await invoke<UnifiedModelOption[]>('get_opencode_unified_models');
+export interface OpenCodeModelPreferences {
+  order: string[];
+  hidden: string[];
+  preferredProviders: Record<string, string>; // model id -> provider id kept when deduplicating
+}
+
+export const getOpenCodeModelPreferences = async (): Promise<OpenCodeModelPreferences> => {
+  return await invoke<OpenCodeModelPreferences>('get_opencode_model_preferences');
+};
+
+/**
+ * Set which models are listed first and in what order; an empty list restores the default
+ */
+export const setOpenCodeModelOrder = async (order: string[]): Promise<void> => {
+  await invoke('set_opencode_model_order', { order });
+};
+
+export const hideOpenCodeModel = async (id: string): Promise<void> => {
+  await invoke('opencode_hide_model', { id });
+};
+
+export const showOpenCodeModel = async (id: string): Promise<void> => {
+  await invoke('opencode_show_model', { id });
+};
+
+/**
+ * Pick the provider a deduplicated model id resolves to; pass null to clear the choice
+ */
+export const setOpenCodePreferredModelProvider = async (
+  modelId: string,
+  providerId: string | null
+): Promise<void> => {
+  await invoke('opencode_set_preferred_model_provider', { modelId, providerId });
 };
 
 const getPresetVariantKeys = (
@@ -401,6 +479,8 @@ export interface OpenCodeFavoriteProvider {
   providerConfig: OpenCodeProvider;
   /** Last used diagnostics configuration */
   diagnostics?: OpenCodeDiagnosticsConfig;
+  /** Pinned for quick apply */
+  pinned?: boolean;
   createdAt: string;
   updatedAt: string;
 }
@@ -439,6 +519,23 @@ export const upsertFavoriteProvider = async (
   });
 };
 
+/**
+ * Pin or unpin a favorite provider for quick apply (emits config-changed)
+ */
+export const setFavoriteProviderPinned = async (
+  providerId: string,
+  pinned: boolean
+): Promise<void> => {
+  await invoke('set_opencode_favorite_provider_pinned', { providerId, pinned });
+};
+
+/**
+ * Save a provider from OpenCode's auth.json as a favorite provider (API key entries only)
+ */
+export const adoptAuthProvider = async (name: string): Promise<void> => {
+  await invoke('opencode_adopt_auth_provider', { name });
+};
+
 /**
  * Delete a favorite provider from database
  */
@@ -509,7 +606,11 @@ export interface ConnectivityTestRequest {
   stream?: boolean;
   body?: Record<string, unknown>;
   modelIds: string[];
-  timeoutSecs?: number;
+  timeoutSecs?: number; // Per-model timeout
+  concurrency?: number; // Models tested at once (default 5, max 16)
+  tool?: GlobalHeadersTool; // Merge this tool's global headers (request headers win)
+  proxyUrl?: string; // Proxy for this request only; falls back to the global proxy
+  maxRetries?: number; // Retries on 429/502/503/504 and connection resets (max 2, default 0)
 }
 
 export interface ConnectivityTestResult {
@@ -523,6 +624,12 @@ export interface ConnectivityTestResult {
   requestBody: Record<string, unknown>;
   responseHeaders?: Record<string, unknown>;
   responseBody?: unknown;
+  /** Output tokens per second after the first byte; streaming only */
+  tokensPerSecond?: number;
+  /** Stream events received (SSE data events, or array chunks for Google); streaming only */
+  chunkCount?: number;
+  /** Reply text extracted from a Google native stream */
+  responseText?: string;
 }
 
 export interface ConnectivityTestResponse {
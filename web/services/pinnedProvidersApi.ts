@@ -0,0 +1,18 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type PinnedProviderTool = 'claude' | 'codex' | 'opencode';
+
+export interface PinnedProvider {
+  tool: PinnedProviderTool;
+  id: string; // Provider ID for Claude/Codex, config key for OpenCode
+  name: string;
+  isApplied: boolean; // For OpenCode: present in opencode.json
+  isDisabled: boolean;
+}
+
+/**
+ * Pinned providers of Claude Code, Codex and OpenCode, in that order
+ */
+export const listPinnedProviders = async (): Promise<PinnedProvider[]> => {
+  return await invoke<PinnedProvider[]>('list_pinned_providers');
+};
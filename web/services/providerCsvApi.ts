@@ -0,0 +1,26 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export type CsvImportTool = 'claude' | 'codex' | 'opencode';
+
+export interface ImportedProvider {
+  row: number;
+  name: string;
+  providerKey: string;
+  baseUrl: string;
+  apiKeyPreview: string;
+  model: string | null;
+  sdkType: string | null;
+  settingsConfig: string | null; // null when the row is invalid
+  error: string | null;
+}
+
+/**
+ * Parse a CSV (name,base_url,api_key,model,sdk_type) into a provider preview.
+ * Nothing is saved; create the confirmed rows with the tool's create-provider API.
+ */
+export const importProvidersFromCsv = async (
+  tool: CsvImportTool,
+  csv: string
+): Promise<ImportedProvider[]> => {
+  return await invoke<ImportedProvider[]>('import_providers_from_csv', { tool, csv });
+};
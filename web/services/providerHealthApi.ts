@@ -0,0 +1,25 @@
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * One periodic connectivity test of a provider (provider_health_monitoring setting)
+ */
+export interface HealthSample {
+  /** Unix milliseconds */
+  checkedAt: number;
+  ok: boolean;
+  latencyMs: number;
+  /** Model the connectivity test was sent for */
+  modelId?: string;
+  error?: string;
+}
+
+/**
+ * Health samples of a Claude Code, Codex, Gemini CLI or Grok provider, oldest first.
+ * `since` (Unix milliseconds) limits the result to newer samples.
+ */
+export const getProviderHealthHistory = async (
+  providerId: string,
+  since?: number
+): Promise<HealthSample[]> => {
+  return await invoke<HealthSample[]>('get_provider_health_history', { providerId, since });
+};
@@ -0,0 +1,35 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export interface TlsInfo {
+  host: string;
+  port: number;
+  subject: string;
+  issuer: string;
+  notBefore: string; // RFC 3339
+  notAfter: string; // RFC 3339
+  daysUntilExpiry: number;
+  expired: boolean;
+  selfSigned: boolean;
+  verified: boolean;
+  verificationError: string | null;
+}
+
+/**
+ * Inspect the TLS certificate served at a Claude or Codex provider's base URL.
+ * Connects directly (proxy settings are not applied); verification failures are reported, not bypassed.
+ */
+export const inspectProviderTls = async (providerId: string): Promise<TlsInfo> => {
+  return await invoke<TlsInfo>('inspect_provider_tls', { providerId });
+};
+
+/**
+ * Opt a provider in or out of skipping TLS certificate verification.
+ * Only model fetching and connectivity tests honour it; the flag is stored in provider meta.
+ */
+export const setProviderAllowInsecureTls = async (
+  tool: 'claude' | 'codex' | 'geminicli',
+  providerId: string,
+  allow: boolean
+): Promise<void> => {
+  await invoke('set_provider_allow_insecure_tls', { tool, providerId, allow });
+};
@@ -17,6 +17,7 @@ export interface AppProxyConfig {
   retry_interval_secs?: number | null;
   cost_multiplier?: string | null;
   pricing_model_source?: GatewayPricingModelSource | string | null;
+  fallback_provider_id?: string | null;
 }
 
 export interface GatewayPricingConfig {
@@ -505,6 +506,19 @@ export const switchProxyGatewayPrimaryProvider = async (
   return invoke<GatewayCliTakeoverStatus>('proxy_gateway_switch_primary_provider', { cliKey, providerId });
 };
 
+export const getProxyGatewayFallbackProvider = async (
+  cliKey: GatewayCliKey
+): Promise<string | null> => {
+  return invoke<string | null>('get_fallback_provider', { cliKey });
+};
+
+export const setProxyGatewayFallbackProvider = async (
+  cliKey: GatewayCliKey,
+  providerId: string | null
+): Promise<string | null> => {
+  return invoke<string | null>('set_fallback_provider', { cliKey, providerId });
+};
+
 export const preflightStopProxyGateway = async (): Promise<ProxyGatewayStopPreflight> => {
   return invoke<ProxyGatewayStopPreflight>('proxy_gateway_stop_preflight');
 };
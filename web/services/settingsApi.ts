@@ -55,6 +55,9 @@ export type SidebarHiddenByPage = Record<SidebarPageKey, boolean>;
 
 export type ProxyMode = 'direct' | 'custom' | 'system';
 
+/** Local auto-backup strategy: full archives only, or baselines plus differentials */
+export type BackupMode = 'full' | 'differential';
+
 type LegacySidebarVisibilityValue = boolean | {
   hidden?: boolean;
 };
@@ -89,6 +92,19 @@ export const normalizeSidebarHiddenByPage = (
   return normalizedValue;
 };
 
+/** Tools whose DB/config-file relationship can be configured */
+export type ConfigSyncTool = 'claude' | 'codex' | 'geminicli';
+
+/** Tools whose provider submenu can be hidden from the tray */
+export type TrayProviderTool = 'claude' | 'codex' | 'grok' | 'geminicli';
+
+/**
+ * db_primary: never import from config files, rewrite files on every save.
+ * file_primary: import files into an empty DB, only write files on explicit apply.
+ * A tool without a mode keeps the default (import when empty, rewrite on save).
+ */
+export type ConfigSyncMode = 'db_primary' | 'file_primary';
+
 export interface AppSettings {
   language: string;
   current_module: string;
@@ -111,6 +127,10 @@ export interface AppSettings {
   auto_backup_enabled: boolean;
   auto_backup_interval_days: number;
   auto_backup_max_keep: number;
+  backup_mode: BackupMode;
+  backup_diffs_per_baseline: number;
+  webdav_auto_backup_enabled: boolean;
+  webdav_backup_keep: number;
   last_auto_backup_time: string | null;
   auto_check_update: boolean;
   visible_tabs: string[];
@@ -119,6 +139,18 @@ export interface AppSettings {
   codex_preserve_official_auth_on_switch: boolean;
   codex_unified_session_history_enabled: boolean;
   claude_cli_launch_full_access: boolean;
+  claude_encrypt_provider_secrets: boolean;
+  auto_sync_on_apply: boolean;
+  diagnostics_enabled: boolean;
+  sync_trace: boolean;
+  check_applied_providers_on_startup: boolean;
+  provider_health_monitoring: boolean;
+  provider_health_interval_minutes: number;
+  log_retention_days: number;
+  config_sync_modes: Partial<Record<ConfigSyncTool, ConfigSyncMode>>;
+  tray_hidden_provider_tools: TrayProviderTool[];
+  boot_snapshot_id: string | null;
+  window_background_color: { r: number; g: number; b: number } | null;
 }
 
 // Default settings
@@ -159,6 +191,10 @@ export const defaultSettings: AppSettings = {
   auto_backup_enabled: false,
   auto_backup_interval_days: 7,
   auto_backup_max_keep: 10,
+  backup_mode: 'full',
+  backup_diffs_per_baseline: 6,
+  webdav_auto_backup_enabled: false,
+  webdav_backup_keep: 10,
   last_auto_backup_time: null,
   auto_check_update: true,
   visible_tabs: ['opencode', 'claudecode', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'],
@@ -167,6 +203,18 @@ export const defaultSettings: AppSettings = {
   codex_preserve_official_auth_on_switch: false,
   codex_unified_session_history_enabled: false,
   claude_cli_launch_full_access: false,
+  claude_encrypt_provider_secrets: false,
+  auto_sync_on_apply: true,
+  diagnostics_enabled: false,
+  sync_trace: false,
+  check_applied_providers_on_startup: false,
+  provider_health_monitoring: false,
+  provider_health_interval_minutes: 15,
+  log_retention_days: 7,
+  config_sync_modes: {},
+  tray_hidden_provider_tools: [],
+  boot_snapshot_id: null,
+  window_background_color: null,
 };
 
 /**
@@ -184,6 +232,18 @@ export const getSettings = async (): Promise<AppSettings> => {
       backup_cli_config_files_enabled: settings.backup_cli_config_files_enabled ?? true,
       codex_preserve_official_auth_on_switch: settings.codex_preserve_official_auth_on_switch ?? false,
       codex_unified_session_history_enabled: settings.codex_unified_session_history_enabled ?? false,
+      claude_encrypt_provider_secrets: settings.claude_encrypt_provider_secrets ?? false,
+      auto_sync_on_apply: settings.auto_sync_on_apply ?? true,
+      diagnostics_enabled: settings.diagnostics_enabled ?? false,
+      sync_trace: settings.sync_trace ?? false,
+      check_applied_providers_on_startup: settings.check_applied_providers_on_startup ?? false,
+      provider_health_monitoring: settings.provider_health_monitoring ?? false,
+      provider_health_interval_minutes: settings.provider_health_interval_minutes ?? 15,
+      log_retention_days: settings.log_retention_days ?? 7,
+      config_sync_modes: settings.config_sync_modes ?? {},
+      tray_hidden_provider_tools: settings.tray_hidden_provider_tools ?? [],
+      boot_snapshot_id: settings.boot_snapshot_id ?? null,
+      window_background_color: settings.window_background_color ?? null,
       sidebar_hidden_by_page: normalizeSidebarHiddenByPage(
         settings.sidebar_hidden_by_page ?? settings.sidebar_visibility_by_page
       ),
@@ -253,6 +313,91 @@ export const getAutoLaunchStatus = async (): Promise<boolean> => {
   }
 };
 
+/**
+ * Toggle whether applying a tool config triggers WSL sync automatically.
+ * When disabled, apply emits `apply-sync-suppressed` (payload: module) instead, on Windows
+ * with WSL sync enabled only.
+ */
+export const setAutoSyncOnApply = async (enabled: boolean): Promise<boolean> => {
+  return await invoke<boolean>('set_auto_sync_on_apply', { enabled });
+};
+
+export interface SchemaVersionInfo {
+  current: number;
+  target: number;
+}
+
+/**
+ * Database schema version and the version this build migrates to
+ */
+export const getSchemaVersion = async (): Promise<SchemaVersionInfo> => {
+  return await invoke<SchemaVersionInfo>('get_schema_version');
+};
+
+/**
+ * Get how many days of log files are kept
+ */
+export const getLogRetentionDays = async (): Promise<number> => {
+  return await invoke<number>('get_log_retention_days');
+};
+
+/**
+ * Set how many days of log files are kept (1-365); older logs are pruned immediately
+ */
+export const setLogRetentionDays = async (days: number): Promise<number> => {
+  return await invoke<number>('set_log_retention_days', { days });
+};
+
+/**
+ * Get the DB/file sync mode of a tool; null means the default behavior
+ */
+export const getConfigSyncMode = async (
+  tool: ConfigSyncTool
+): Promise<ConfigSyncMode | null> => {
+  return await invoke<ConfigSyncMode | null>('get_config_sync_mode', { tool });
+};
+
+/**
+ * Set the DB/file sync mode of a tool; pass null to restore the default behavior
+ */
+export const setConfigSyncMode = async (
+  tool: ConfigSyncTool,
+  mode: ConfigSyncMode | null
+): Promise<ConfigSyncMode | null> => {
+  return await invoke<ConfigSyncMode | null>('set_config_sync_mode', { tool, mode });
+};
+
+/**
+ * Whether a tool's provider submenu is shown in the tray (default: shown)
+ */
+export const getProvidersShowInTray = async (tool: TrayProviderTool): Promise<boolean> => {
+  return await invoke<boolean>('get_providers_show_in_tray', { tool });
+};
+
+/**
+ * Show or hide a tool's provider submenu in the tray
+ */
+export const setProvidersShowInTray = async (
+  tool: TrayProviderTool,
+  enabled: boolean
+): Promise<boolean> => {
+  return await invoke<boolean>('set_providers_show_in_tray', { tool, enabled });
+};
+
+/**
+ * Get the MCP snapshot id restored on every startup; null means no boot restore
+ */
+export const getBootSnapshotId = async (): Promise<string | null> => {
+  return await invoke<string | null>('get_boot_snapshot_id');
+};
+
+/**
+ * Set the MCP snapshot restored on every startup; pass null to disable it
+ */
+export const setBootSnapshotId = async (id: string | null): Promise<string | null> => {
+  return await invoke<string | null>('set_boot_snapshot_id', { id });
+};
+
 /**
  * Restart the application
  */
@@ -260,9 +405,143 @@ export const restartApp = async (): Promise<void> => {
   await invoke('restart_app');
 };
 
+export interface ProxyTestResult {
+  echoUrl: string;
+  /** Round trip of the HTTPS request through the proxy */
+  latencyMs: number | null;
+  exitIp: string | null;
+  httpsOk: boolean;
+  httpsError: string | null;
+  httpOk: boolean;
+  httpError: string | null;
+}
+
+/**
+ * Test proxy connection over HTTPS (CONNECT) and plain HTTP against an ip-echo endpoint.
+ * Rejects only when neither gets through.
+ */
+export const testProxyConnection = async (
+  proxyUrl: string,
+  echoUrl?: string
+): Promise<ProxyTestResult> => {
+  return await invoke<ProxyTestResult>('test_proxy_connection', { proxyUrl, echoUrl });
+};
+
+export type SortableTable =
+  | 'claude_provider'
+  | 'codex_provider'
+  | 'grok_provider'
+  | 'gemini_cli_provider'
+  | 'mcp_server'
+  | 'skill';
+
+/**
+ * Renumber sort_index to 0..n for a sortable table, keeping the order its list shows;
+ * returns how many records changed. Also runs for every table at startup.
+ */
+export const normalizeSortIndexes = async (table: SortableTable): Promise<number> => {
+  return await invoke<number>('normalize_sort_indexes', { table });
+};
+
+export interface ConfigProvenance {
+  tool: string;
+  path: string;
+  exists: boolean;
+  managed: boolean;              // A marker from a previous apply is present
+  matches: boolean;              // File on disk still matches what the app wrote
+  providerId: string | null;
+  writtenAt: string | null;
+}
+
+/**
+ * Report whether a tool's applied config came from this app or was hand-edited
+ */
+export const getConfigProvenance = async (tool: 'claude' | 'codex' | 'opencode'): Promise<ConfigProvenance> => {
+  return await invoke<ConfigProvenance>('get_config_provenance', { tool });
+};
+
+export interface WritabilityResult {
+  tool: string;
+  directory: string;             // Empty when the directory could not be resolved
+  status: 'writable' | 'not_writable' | 'missing' | 'error';
+  error?: string;
+}
+
+/**
+ * Create and delete a temp file in every tool's config directory to catch permission problems
+ */
+export const testConfigWritability = async (): Promise<WritabilityResult[]> => {
+  return await invoke<WritabilityResult[]>('test_config_writability');
+};
+
+export interface LimitIssue {
+  tool: string;
+  kind: 'env_value_length' | 'mcp_tool_count' | 'mcp_server_name_length';
+  limit: number;
+  actual: number;
+  item: string;                  // Env key, MCP server name, or the tool itself
+  message: string;
+}
+
+/**
+ * Report config values that are over a tool's known limits (e.g. 'claude_code', 'cursor').
+ * For Claude Code, checks the settings applying `providerId` (default: the applied one) would write.
+ */
+export const checkToolLimits = async (tool: string, providerId?: string): Promise<LimitIssue[]> => {
+  return await invoke<LimitIssue[]>('check_tool_limits', { tool, providerId });
+};
+
+export interface InstalledToolInfo {
+  tool: string;                  // Tool key, e.g. 'claude_code'
+  command: string;               // CLI binary name, e.g. 'claude'
+  installed: boolean;
+  onPath: boolean;               // False when only found in a known install dir
+  path?: string;
+  version?: string;
+}
+
+/**
+ * Detect which AI CLIs are installed and their versions (versions are cached for a few minutes)
+ */
+export const detectInstalledTools = async (): Promise<InstalledToolInfo[]> => {
+  return await invoke<InstalledToolInfo[]>('detect_installed_tools');
+};
+
+export type GlobalHeadersTool = 'claude' | 'opencode';
+
+/**
+ * Get request headers applied to every provider of a tool
+ */
+export const getGlobalHeaders = async (tool: GlobalHeadersTool): Promise<Record<string, string>> => {
+  return await invoke<Record<string, string>>('get_global_headers', { tool });
+};
+
+/**
+ * Set request headers applied to every provider of a tool; provider headers override them.
+ * Takes effect on the next apply.
+ */
+export const setGlobalHeaders = async (
+  tool: GlobalHeadersTool,
+  headers: Record<string, string>
+): Promise<void> => {
+  await invoke('set_global_headers', { tool, headers });
+};
+
+export interface CacheClearEntry {
+  name: 'gitCache' | 'providerModels' | 'pluginRegistry' | 'toolDetection';
+  entries: number;
+  bytes: number;
+  error: string | null;
+}
+
+export interface CacheClearReport {
+  caches: CacheClearEntry[];
+  totalBytes: number;
+}
+
 /**
- * Test proxy connection
+ * Clear every rebuildable cache and report what was freed per cache
  */
-export const testProxyConnection = async (proxyUrl: string): Promise<void> => {
-  await invoke('test_proxy_connection', { proxyUrl });
+export const clearAllCaches = async (): Promise<CacheClearReport> => {
+  return await invoke<CacheClearReport>('clear_all_caches');
 };
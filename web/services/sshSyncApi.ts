@@ -11,6 +11,8 @@ import type {
   SSHFileMapping,
   SSHStatusResult,
   SSHSyncConfig,
+  SyncConflict,
+  SyncConflictChoice,
   SyncResult,
 } from '@/types/sshsync';
 
@@ -105,6 +107,23 @@ export const sshSync = async (module?: string, skipModules?: string[]): Promise<
   return await invoke<SyncResult>('ssh_sync', { module, skipModules });
 };
 
+/**
+ * List unresolved conflicts from bidirectional mappings
+ */
+export const sshListSyncConflicts = async (): Promise<SyncConflict[]> => {
+  return await invoke<SyncConflict[]>('ssh_list_sync_conflicts');
+};
+
+/**
+ * Resolve a sync conflict by keeping the local copy, the remote copy, or both
+ */
+export const sshResolveConflict = async (
+  mappingId: string,
+  choice: SyncConflictChoice
+): Promise<string[]> => {
+  return await invoke<string[]>('ssh_resolve_conflict', { mappingId, choice });
+};
+
 /**
  * Get SSH sync status
  */
@@ -125,3 +144,53 @@ export const sshTestLocalPath = async (localPath: string): Promise<boolean> => {
 export const sshGetDefaultMappings = async (): Promise<SSHFileMapping[]> => {
   return await invoke<SSHFileMapping[]>('ssh_get_default_mappings');
 };
+
+export interface SyncMappingsImportResult {
+  wslImported: number;
+  wslSkipped: number;
+  sshImported: number;
+  sshSkipped: number;
+}
+
+/**
+ * Export WSL and SSH file mappings as JSON (connection secrets are never included)
+ */
+export const exportSyncMappings = async (): Promise<string> => {
+  return await invoke<string>('export_sync_mappings');
+};
+
+/**
+ * Import WSL and SSH file mappings; merge skips mappings whose target path already exists
+ */
+export const importSyncMappings = async (
+  json: string,
+  merge: boolean
+): Promise<SyncMappingsImportResult> => {
+  return await invoke<SyncMappingsImportResult>('import_sync_mappings', { json, merge });
+};
+
+export interface MappingPerf {
+  target: 'wsl' | 'ssh';
+  mappingId: string;
+  name: string;
+  module: string;
+  sourceBytes: number;
+  durationMs: number;
+  bytesPerSec: number;
+  slow: boolean;
+  measuredAt: string;
+}
+
+/**
+ * Get last-run throughput per WSL/SSH file mapping (slowest first)
+ */
+export const getSyncPerformance = async (): Promise<MappingPerf[]> => {
+  return await invoke<MappingPerf[]>('get_sync_performance');
+};
+
+/**
+ * Read the WSL/SSH sync trace written while the sync_trace setting is on
+ */
+export const getSyncTrace = async (): Promise<string> => {
+  return await invoke<string>('get_sync_trace');
+};
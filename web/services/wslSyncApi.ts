@@ -10,8 +10,11 @@ import type {
   WSLErrorResult,
   WSLSyncConfig,
   WSLStatusResult,
+  WSLMappingStatus,
+  WSLDistroStatus,
   FileMapping,
   SyncResult,
+  ConfigComparison,
 } from '@/types/wslsync';
 
 /**
@@ -85,6 +88,29 @@ export const wslSync = async (module?: string, skipModules?: string[]): Promise<
   return await invoke<SyncResult>('wsl_sync', { module, skipModules });
 };
 
+/**
+ * Apply a provider inside one running WSL distro only, leaving the host config unchanged.
+ * Only Claude Code ('claude') is supported.
+ */
+export const applyProviderToWsl = async (
+  tool: 'claude',
+  providerId: string,
+  distro: string
+): Promise<void> => {
+  await invoke('apply_provider_to_wsl', { tool, providerId, distro });
+};
+
+/**
+ * Compare a tool's host config file with its copy in a running WSL distro.
+ * hostExists/wslExists report a missing file; differences mask secret values.
+ */
+export const wslCompareConfig = async (
+  tool: 'claude' | 'codex' | 'geminicli',
+  distro: string
+): Promise<ConfigComparison> => {
+  return await invoke<ConfigComparison>('wsl_compare_config', { tool, distro });
+};
+
 /**
  * Get current WSL sync status
  */
@@ -92,6 +118,20 @@ export const wslGetStatus = async (): Promise<WSLStatusResult> => {
   return await invoke<WSLStatusResult>('wsl_get_status');
 };
 
+/**
+ * Get availability and running state of every configured sync target
+ */
+export const wslGetDistroStatuses = async (): Promise<WSLDistroStatus[]> => {
+  return await invoke<WSLDistroStatus[]>('wsl_get_distro_statuses');
+};
+
+/**
+ * Get per-distro, per-mapping last sync time, last error and Windows/WSL drift
+ */
+export const wslGetDetailedStatus = async (): Promise<WSLMappingStatus[]> => {
+  return await invoke<WSLMappingStatus[]>('wsl_get_detailed_status');
+};
+
 /**
  * Test if a Windows path exists and can be accessed
  */
@@ -5,6 +5,7 @@ import {
   setAutoLaunch,
   type AppSettings,
   type ProxyMode,
+  type BackupMode,
   type WebDAVConfig,
   type S3Config,
   type BackupCustomEntry,
@@ -14,6 +15,7 @@ import {
   normalizeSidebarHiddenByPage,
 } from '@/services';
 import { setCodexPreserveOfficialAuthOnSwitch as setCodexPreserveOfficialAuthOnSwitchApi } from '@/services/codexApi';
+import { migrateSecretsToEncrypted } from '@/services/claudeCodeApi';
 import { buildLaunchOnStartupSettings } from './settingsStoreUtils';
 
 // Re-export types for convenience (using camelCase for frontend)
@@ -67,6 +69,10 @@ interface SettingsState {
   autoBackupEnabled: boolean;
   autoBackupIntervalDays: number;
   autoBackupMaxKeep: number;
+  backupMode: BackupMode;
+  backupDiffsPerBaseline: number;
+  webdavAutoBackupEnabled: boolean;
+  webdavBackupKeep: number;
   lastAutoBackupTime: string | null;
 
   // Update settings
@@ -87,6 +93,7 @@ interface SettingsState {
 
   // Claude Code options
   claudeCliLaunchFullAccess: boolean;
+  claudeEncryptProviderSecrets: boolean;
 
   // Actions
   initSettings: () => Promise<void>;
@@ -110,6 +117,10 @@ interface SettingsState {
     enabled: boolean;
     intervalDays: number;
     maxKeep: number;
+    mode: BackupMode;
+    diffsPerBaseline: number;
+    webdavEnabled: boolean;
+    webdavKeep: number;
   }) => Promise<void>;
   setLastAutoBackupTime: (time: string) => void;
   setAutoCheckUpdate: (enabled: boolean) => Promise<void>;
@@ -119,6 +130,7 @@ interface SettingsState {
   setCodexPreserveOfficialAuthOnSwitch: (enabled: boolean) => Promise<void>;
   setCodexUnifiedSessionHistoryEnabled: (enabled: boolean) => void;
   setClaudeCliLaunchFullAccess: (enabled: boolean) => Promise<void>;
+  setClaudeEncryptProviderSecrets: (enabled: boolean) => Promise<void>;
 }
 
 // Convert backend snake_case to frontend camelCase
@@ -200,6 +212,10 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
   autoBackupEnabled: false,
   autoBackupIntervalDays: 7,
   autoBackupMaxKeep: 10,
+  backupMode: 'full',
+  backupDiffsPerBaseline: 6,
+  webdavAutoBackupEnabled: false,
+  webdavBackupKeep: 10,
   lastAutoBackupTime: null,
   autoCheckUpdate: true,
   visibleTabs: ['opencode', 'claudecode', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'],
@@ -208,6 +224,7 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
   codexPreserveOfficialAuthOnSwitch: false,
   codexUnifiedSessionHistoryEnabled: false,
   claudeCliLaunchFullAccess: false,
+  claudeEncryptProviderSecrets: false,
 
   initSettings: async () => {
     if (get().isInitialized) return;
@@ -233,6 +250,10 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
         autoBackupEnabled: settings.auto_backup_enabled ?? false,
         autoBackupIntervalDays: settings.auto_backup_interval_days ?? 7,
         autoBackupMaxKeep: settings.auto_backup_max_keep ?? 10,
+        backupMode: settings.backup_mode ?? 'full',
+        backupDiffsPerBaseline: settings.backup_diffs_per_baseline ?? 6,
+        webdavAutoBackupEnabled: settings.webdav_auto_backup_enabled ?? false,
+        webdavBackupKeep: settings.webdav_backup_keep ?? 10,
         lastAutoBackupTime: settings.last_auto_backup_time ?? null,
         autoCheckUpdate: settings.auto_check_update ?? true,
         visibleTabs: settings.visible_tabs ?? ['opencode', 'claudecode', 'codex', 'grok', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'],
@@ -241,6 +262,7 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
         codexPreserveOfficialAuthOnSwitch: settings.codex_preserve_official_auth_on_switch ?? false,
         codexUnifiedSessionHistoryEnabled: settings.codex_unified_session_history_enabled ?? false,
         claudeCliLaunchFullAccess: settings.claude_cli_launch_full_access ?? false,
+        claudeEncryptProviderSecrets: settings.claude_encrypt_provider_secrets ?? false,
         isInitialized: true,
       });
     } catch (error) {
@@ -394,6 +416,10 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
       autoBackupEnabled: config.enabled,
       autoBackupIntervalDays: config.intervalDays,
       autoBackupMaxKeep: config.maxKeep,
+      backupMode: config.mode,
+      backupDiffsPerBaseline: config.diffsPerBaseline,
+      webdavAutoBackupEnabled: config.webdavEnabled,
+      webdavBackupKeep: config.webdavKeep,
     });
 
     const currentSettings = await getSettings();
@@ -402,6 +428,10 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
       auto_backup_enabled: config.enabled,
       auto_backup_interval_days: config.intervalDays,
       auto_backup_max_keep: config.maxKeep,
+      backup_mode: config.mode,
+      backup_diffs_per_baseline: config.diffsPerBaseline,
+      webdav_auto_backup_enabled: config.webdavEnabled,
+      webdav_backup_keep: config.webdavKeep,
     };
     await saveSettings(newSettings);
   },
@@ -486,4 +516,19 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     };
     await saveSettings(newSettings);
   },
+
+  setClaudeEncryptProviderSecrets: async (enabled) => {
+    // Encrypt existing providers first so a keyring failure leaves the setting off.
+    if (enabled) {
+      await migrateSecretsToEncrypted();
+    }
+
+    const currentSettings = await getSettings();
+    const newSettings: AppSettings = {
+      ...currentSettings,
+      claude_encrypt_provider_secrets: enabled,
+    };
+    await saveSettings(newSettings);
+    set({ claudeEncryptProviderSecrets: enabled });
+  },
 }));
@@ -42,6 +42,10 @@ function createSettings(overrides: Partial<AppSettings> = {}): AppSettings {
     auto_backup_enabled: false,
     auto_backup_interval_days: 7,
     auto_backup_max_keep: 10,
+    backup_mode: 'full',
+    backup_diffs_per_baseline: 6,
+    webdav_auto_backup_enabled: false,
+    webdav_backup_keep: 10,
     last_auto_backup_time: null,
     auto_check_update: true,
     visible_tabs: ['opencode', 'claudecode', 'codex', 'geminicli', 'openclaw', 'pi', 'gateway', 'image', 'ssh', 'wsl'],
@@ -58,6 +62,18 @@ function createSettings(overrides: Partial<AppSettings> = {}): AppSettings {
     codex_preserve_official_auth_on_switch: false,
     codex_unified_session_history_enabled: false,
     claude_cli_launch_full_access: false,
+    claude_encrypt_provider_secrets: false,
+    auto_sync_on_apply: true,
+    diagnostics_enabled: false,
+    sync_trace: false,
+    check_applied_providers_on_startup: false,
+    provider_health_monitoring: false,
+    provider_health_interval_minutes: 15,
+    log_retention_days: 7,
+    config_sync_modes: {},
+    tray_hidden_provider_tools: [],
+    boot_snapshot_id: null,
+    window_background_color: null,
     ...overrides,
   };
 }
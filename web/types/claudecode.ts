@@ -28,6 +28,8 @@ export interface GatewayProviderMeta {
   allowTextOnlyModelHeuristic?: boolean;
   costMultiplier?: string;
   pricingModelSource?: 'upstream' | 'requested' | string;
+  /** Skip TLS certificate checks when fetching models / testing this provider (explicit opt-in) */
+  allowInsecureTls?: boolean;
 }
 
 /**
@@ -79,6 +81,7 @@ export interface ClaudeCodeProvider {
   meta?: GatewayProviderMeta;
   isApplied?: boolean;
   isDisabled?: boolean;
+  pinned?: boolean;
   createdAt: string;
   updatedAt: string;
 }
@@ -207,6 +210,43 @@ export interface ClaudePluginStatus {
   hasConfigFile: boolean; // Whether ~/.claude/config.json exists
 }
 
+export interface ClaudeFullPreview {
+  providerId: string | null;            // null = current settings.json on disk
+  settings: Record<string, unknown>;    // Merged settings.json with secrets masked
+  plugin: ClaudePluginStatus;
+  hasCompletedOnboarding: boolean;
+}
+
+export interface ClaudeConfigPreview {
+  providerId: string;
+  settingsPath: string;
+  current: string | null;               // null = settings.json does not exist yet
+  proposed: string;                     // Exactly what apply would write
+}
+
+export interface ClaudeProviderMergeResult {
+  provider: ClaudeCodeProvider;         // Created, not applied
+  overriddenKeys: string[];             // e.g. 'env.ANTHROPIC_BASE_URL' where the overlay won
+}
+
+export interface ClaudeConfigIssue {
+  field: string;                        // e.g. 'env.ANTHROPIC_BASE_URL'; '' = whole document
+  severity: 'error' | 'warning';        // Errors are rejected on create/update
+  message: string;
+}
+
+export interface ClaudeSelfTestCheck {
+  id: string;                           // e.g. 'providerApplied', 'settingsWritable'
+  status: 'pass' | 'warn' | 'fail';
+  message: string;
+  hint: string | null;                  // Remediation for warn/fail
+}
+
+export interface ClaudeSelfTest {
+  passed: boolean;                      // False when any check failed
+  checks: ClaudeSelfTestCheck[];
+}
+
 export interface ClaudePluginRuntimeStatus {
   mode: 'local' | 'wslDirect';
   source: 'custom' | 'env' | 'shell' | 'default';
@@ -83,6 +83,7 @@ export interface CodexProvider {
   meta?: GatewayProviderMeta;
   isApplied?: boolean;
   isDisabled?: boolean;
+  pinned?: boolean;
   createdAt: string;
   updatedAt: string;
 }
@@ -186,6 +186,28 @@ export interface OhMyOpenAgentLegacyUpgradeResult {
 	sshMappingUpdated: boolean;
 }
 
+export type OhMyOpenAgentMergeLayer =
+	| "globalExplicit"
+	| "globalOtherFields"
+	| "profileAgents"
+	| "profileCategories"
+	| "profileOtherFields";
+
+export interface OhMyOpenAgentMergeKeyProvenance {
+	key: string;
+	layer: OhMyOpenAgentMergeLayer;
+	/** Lower-priority layers that also set the key, lowest first */
+	overriddenLayers: OhMyOpenAgentMergeLayer[];
+}
+
+/**
+ * Config file content an apply would write, with per-key provenance.
+ */
+export interface OhMyOpenAgentMergePreview {
+	config: Record<string, unknown>;
+	provenance: OhMyOpenAgentMergeKeyProvenance[];
+}
+
 /**
  * Main persisted Oh My OpenAgent profile type.
  */
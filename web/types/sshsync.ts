@@ -48,8 +48,32 @@ export interface SSHFileMapping {
   isDirectory: boolean;
   directoryExcludes: string[];
   cleanupPaths: string[];
+  syncDirection?: SSHSyncDirection; // Defaults to "push"
 }
 
+/**
+ * "bidirectional" only applies to single-file mappings without cleanup paths
+ */
+export type SSHSyncDirection = 'push' | 'bidirectional';
+
+/**
+ * Local and remote copies of a bidirectional mapping both changed since the last sync
+ */
+export interface SyncConflict {
+  mappingId: string;
+  mappingName: string;
+  connectionId: string;
+  localPath: string;
+  remotePath: string;
+  localHash: string;
+  remoteHash: string;
+  localModified?: number; // Unix seconds
+  remoteModified?: number; // Unix seconds
+  detectedAt: string;
+}
+
+export type SyncConflictChoice = 'local' | 'remote' | 'both';
+
 /**
  * SSH sync configuration
  */
@@ -64,6 +88,8 @@ export interface SSHSyncConfig {
   lastSyncStatus: string; // "success" | "error" | "never"
   lastSyncError?: string;
   moduleStatuses: WslDirectModuleStatus[];
+  healthCheckIntervalSecs: number; // Background health check interval, default 60
+  reconnectBackoffMaxSecs: number; // Cap for reconnect backoff after failures, default 300
 }
 
 /**
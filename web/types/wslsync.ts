@@ -30,9 +30,20 @@ export interface WslDirectModuleStatus {
 /**
  * WSL sync configuration
  */
+/**
+ * A distro that receives syncs
+ */
+export interface WSLDistroTarget {
+  name: string;
+  enabled: boolean;
+}
+
 export interface WSLSyncConfig {
   enabled: boolean;
+  /** Primary distro (terminal, folder, config comparison) */
   distro: string;
+  /** Sync targets; when empty, only `distro` is synced */
+  distros?: WSLDistroTarget[];
   /** Sync MCP configuration to WSL (default: true) */
   syncMcp: boolean;
   /** Sync Skills to WSL (default: true) */
@@ -71,6 +82,19 @@ export interface WSLErrorResult {
   error?: string;
 }
 
+/**
+ * Availability and running state of one configured sync target
+ */
+export interface WSLDistroStatus {
+  distro: string;
+  enabled: boolean;
+  available: boolean;
+  /** Installed distro the target resolved to */
+  effectiveDistro?: string;
+  state: string; // "Running" | "Stopped" | "Unknown"
+  error?: string;
+}
+
 /**
  * WSL status result
  */
@@ -82,6 +106,25 @@ export interface WSLStatusResult {
   moduleStatuses: WslDirectModuleStatus[];
 }
 
+export type WSLMappingDrift = 'inSync' | 'drifted' | 'sourceMissing' | 'targetMissing' | 'unchecked';
+
+/**
+ * Last sync outcome and current drift of one file mapping in one sync target
+ */
+export interface WSLMappingStatus {
+  /** Installed distro the target resolved to */
+  distro: string;
+  mappingId: string;
+  name: string;
+  module: string;
+  enabled: boolean;
+  /** Last successful sync since app start */
+  lastSyncedAt?: string;
+  lastError?: string;
+  drift: WSLMappingDrift;
+  driftDetail?: string;
+}
+
 /**
  * Sync progress event payload
  */
@@ -99,3 +142,29 @@ export interface SyncProgress {
   /** Current file being uploaded within the current item, when available */
   currentFile?: string;
 }
+
+/**
+ * One differing field between the host config and the WSL copy (secrets masked)
+ */
+export interface ConfigDifference {
+  /** Dot-separated field path; empty for the document root */
+  path: string;
+  /** Host value; null when the field is missing on the host */
+  host: unknown | null;
+  /** WSL value; null when the field is missing in the distro */
+  wsl: unknown | null;
+}
+
+/**
+ * Host config file compared with the same file inside a WSL distro
+ */
+export interface ConfigComparison {
+  tool: string;
+  distro: string;
+  hostPath: string;
+  wslPath: string;
+  hostExists: boolean;
+  wslExists: boolean;
+  matches: boolean;
+  differences: ConfigDifference[];
+}